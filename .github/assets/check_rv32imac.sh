@@ -12,6 +12,7 @@ crates_to_check=(
     reth-chainspec
     reth-consensus
     reth-consensus-common
+    reth-consensus-rules
     reth-prune-types
     reth-static-file-types
     reth-storage-errors
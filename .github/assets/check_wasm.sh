@@ -35,6 +35,7 @@ exclude_crates=(
   reth-network
   reth-node-api
   reth-node-builder
+  reth-node-checkpoints
   reth-node-core
   reth-node-ethereum
   reth-node-events
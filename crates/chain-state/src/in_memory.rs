@@ -2,12 +2,12 @@
 
 use crate::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications,
-    ChainInfoTracker, MemoryOverlayStateProvider,
+    CanonicalEpochNotificationStream, ChainInfoTracker, MemoryOverlayStateProvider,
 };
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber, BlockNumHash};
 use alloy_primitives::{map::HashMap, TxHash, B256};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use reth_chainspec::ChainInfo;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::{Chain, ExecutionOutcome};
@@ -139,6 +139,11 @@ pub(crate) struct CanonicalInMemoryStateInner<N: NodePrimitives> {
     pub(crate) in_memory_state: InMemoryState<N>,
     /// A broadcast stream that emits events when the canonical chain is updated.
     pub(crate) canon_state_notification_sender: CanonStateNotificationSender<N>,
+    /// Identifier of the current canonical view, bumped on every reorg notification.
+    ///
+    /// The lock is held while notifications are sent, so subscribers can derive the epochs of
+    /// notifications from the epoch at the time of subscribing.
+    pub(crate) canonical_epoch: Mutex<u64>,
 }
 
 impl<N: NodePrimitives> CanonicalInMemoryStateInner<N> {
@@ -192,6 +197,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
                 chain_info_tracker,
                 in_memory_state,
                 canon_state_notification_sender,
+                canonical_epoch: Mutex::new(0),
             }),
         }
     }
@@ -216,6 +222,7 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
             chain_info_tracker,
             in_memory_state,
             canon_state_notification_sender,
+            canonical_epoch: Mutex::new(0),
         };
 
         Self { inner: Arc::new(inner) }
@@ -491,6 +498,15 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
         self.inner.canon_state_notification_sender.subscribe()
     }
 
+    /// Subscribe to new blocks events tagged with the canonical epoch they were notified in.
+    pub fn subscribe_canonical_epochs(&self) -> CanonicalEpochNotificationStream<N> {
+        let canonical_epoch = self.inner.canonical_epoch.lock();
+        CanonicalEpochNotificationStream::new(
+            *canonical_epoch,
+            self.inner.canon_state_notification_sender.subscribe(),
+        )
+    }
+
     /// Subscribe to new safe block events.
     pub fn subscribe_safe_block(&self) -> watch::Receiver<Option<SealedHeader<N::BlockHeader>>> {
         self.inner.chain_info_tracker.subscribe_safe_block()
@@ -504,10 +520,24 @@ impl<N: NodePrimitives> CanonicalInMemoryState<N> {
     }
 
     /// Attempts to send a new [`CanonStateNotification`] to all active Receiver handles.
+    ///
+    /// The canonical epoch is bumped if the notification is a reorg.
     pub fn notify_canon_state(&self, event: CanonStateNotification<N>) {
+        let mut canonical_epoch = self.inner.canonical_epoch.lock();
+        if matches!(event, CanonStateNotification::Reorg { .. }) {
+            *canonical_epoch += 1;
+        }
         self.inner.canon_state_notification_sender.send(event).ok();
     }
 
+    /// Returns the current canonical epoch.
+    ///
+    /// The epoch starts at zero and is bumped whenever a reorg is notified, so two reads that
+    /// return the same epoch observed the same canonical chain, apart from blocks appended on top.
+    pub fn canonical_epoch(&self) -> u64 {
+        *self.inner.canonical_epoch.lock()
+    }
+
     /// Return state provider with reference to in-memory blocks that overlay database state.
     ///
     /// This merges the state of all blocks that are part of the chain that the requested block is
@@ -940,8 +970,20 @@ impl<N: NodePrimitives<SignedTx: SignedTransaction>> NewCanonicalChain<N> {
     }
 
     /// Converts the new chain into a notification that will be emitted to listeners
+    ///
+    /// The trie updates are only included for the commit of a single block, because they can't be
+    /// merged cheaply.
     pub fn to_chain_notification(&self) -> CanonStateNotification<N> {
         match self {
+            Self::Commit { new } if new.len() == 1 => {
+                let exec = &new[0];
+                let new = Arc::new(Chain::from_block(
+                    exec.recovered_block().clone(),
+                    exec.execution_outcome().clone(),
+                    exec.trie_updates().cloned(),
+                ));
+                CanonStateNotification::Commit { new }
+            }
             Self::Commit { new } => {
                 let new = Arc::new(new.iter().fold(Chain::default(), |mut chain, exec| {
                     chain.append_block(
@@ -988,7 +1030,7 @@ impl<N: NodePrimitives<SignedTx: SignedTransaction>> NewCanonicalChain<N> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::TestBlockBuilder;
+    use crate::{test_utils::TestBlockBuilder, CanonicalEpochNotification};
     use alloy_eips::eip7685::Requests;
     use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue};
     use rand::Rng;
@@ -1003,6 +1045,7 @@ mod tests {
         AccountProof, HashedStorage, MultiProof, MultiProofTargets, StorageMultiProof,
         StorageProof, TrieInput,
     };
+    use tokio_stream::StreamExt;
 
     fn create_mock_state(
         test_block_builder: &mut TestBlockBuilder<EthPrimitives>,
@@ -1561,6 +1604,20 @@ mod tests {
             }
         );
 
+        // Test single block commit notification, which includes the trie updates
+        let chain_commit = NewCanonicalChain::Commit { new: vec![block0.clone()] };
+
+        assert_eq!(
+            chain_commit.to_chain_notification(),
+            CanonStateNotification::Commit {
+                new: Arc::new(Chain::from_block(
+                    block0.recovered_block().clone(),
+                    block0.execution_outcome().clone(),
+                    block0.trie_updates().cloned()
+                ))
+            }
+        );
+
         // Test reorg notification
         let chain_reorg = NewCanonicalChain::Reorg {
             new: vec![block1a.clone(), block2a.clone()],
@@ -1583,4 +1640,55 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    async fn test_canonical_epoch_bumped_on_reorg() {
+        let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
+        let mut epochs = state.subscribe_canonical_epochs();
+        assert_eq!(state.canonical_epoch(), 0);
+
+        let commit = CanonStateNotification::Commit { new: Arc::default() };
+        state.notify_canon_state(commit.clone());
+        assert_eq!(state.canonical_epoch(), 0);
+
+        let reorg = CanonStateNotification::Reorg { old: Arc::default(), new: Arc::default() };
+        state.notify_canon_state(reorg.clone());
+        assert_eq!(state.canonical_epoch(), 1);
+
+        // a subscription after the reorg starts in the epoch of the reorg
+        let mut later_epochs = state.subscribe_canonical_epochs();
+
+        state.notify_canon_state(commit.clone());
+        assert_eq!(
+            epochs.next().await,
+            Some(CanonicalEpochNotification { canonical_epoch: 0, notification: commit.clone() })
+        );
+        assert_eq!(
+            epochs.next().await,
+            Some(CanonicalEpochNotification { canonical_epoch: 1, notification: reorg })
+        );
+        assert_eq!(
+            epochs.next().await,
+            Some(CanonicalEpochNotification { canonical_epoch: 1, notification: commit.clone() })
+        );
+        assert_eq!(
+            later_epochs.next().await,
+            Some(CanonicalEpochNotification { canonical_epoch: 1, notification: commit })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_canonical_epoch_stream_ends_when_lagging() {
+        let state: CanonicalInMemoryState = CanonicalInMemoryState::empty();
+        let mut epochs = state.subscribe_canonical_epochs();
+
+        // the reorgs of missed notifications are unknown
+        for _ in 0..=CANON_STATE_NOTIFICATION_CHANNEL_SIZE {
+            state.notify_canon_state(CanonStateNotification::Reorg {
+                old: Arc::default(),
+                new: Arc::default(),
+            });
+        }
+        assert_eq!(epochs.next().await, None);
+    }
 }
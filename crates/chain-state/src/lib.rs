@@ -19,7 +19,8 @@ pub use chain_info::ChainInfoTracker;
 mod notifications;
 pub use notifications::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions, ForkChoiceNotifications, ForkChoiceStream,
+    CanonStateNotifications, CanonStateSubscriptions, CanonicalEpochNotification,
+    CanonicalEpochNotificationStream, ForkChoiceNotifications, ForkChoiceStream,
     ForkChoiceSubscriptions,
 };
 
@@ -38,6 +38,24 @@ pub trait CanonStateSubscriptions: NodePrimitivesProvider + Send + Sync {
             st: BroadcastStream::new(self.subscribe_to_canonical_state()),
         }
     }
+
+    /// Get notified when a new canonical chain was imported, with every notification tagged with
+    /// the canonical epoch it was emitted in.
+    ///
+    /// Returns `None` if the canonical epoch isn't tracked, see [`Self::canonical_epoch`].
+    fn subscribe_to_canonical_epochs(
+        &self,
+    ) -> Option<CanonicalEpochNotificationStream<Self::Primitives>> {
+        None
+    }
+
+    /// Returns the current canonical epoch, or `None` if it isn't tracked.
+    ///
+    /// The epoch is bumped whenever a [`CanonStateNotification::Reorg`] is emitted, which allows
+    /// consumers to detect whether two reads were served from the same canonical view.
+    fn canonical_epoch(&self) -> Option<u64> {
+        None
+    }
 }
 
 impl<T: CanonStateSubscriptions> CanonStateSubscriptions for &T {
@@ -48,6 +66,16 @@ impl<T: CanonStateSubscriptions> CanonStateSubscriptions for &T {
     fn canonical_state_stream(&self) -> CanonStateNotificationStream<Self::Primitives> {
         (*self).canonical_state_stream()
     }
+
+    fn subscribe_to_canonical_epochs(
+        &self,
+    ) -> Option<CanonicalEpochNotificationStream<Self::Primitives>> {
+        (*self).subscribe_to_canonical_epochs()
+    }
+
+    fn canonical_epoch(&self) -> Option<u64> {
+        (*self).canonical_epoch()
+    }
 }
 
 /// A Stream of [`CanonStateNotification`].
@@ -76,6 +104,57 @@ impl<N: NodePrimitives> Stream for CanonStateNotificationStream<N> {
     }
 }
 
+/// A Stream of [`CanonStateNotification`]s tagged with the canonical epoch they were emitted in.
+///
+/// The epoch is derived from the notifications themselves: it starts at the canonical epoch at the
+/// time of subscribing and is bumped on every [`CanonStateNotification::Reorg`]. The stream ends if
+/// it lags behind, because the epochs of later notifications can't be derived once a reorg was
+/// missed.
+#[derive(Debug)]
+#[pin_project::pin_project]
+pub struct CanonicalEpochNotificationStream<
+    N: NodePrimitives = reth_ethereum_primitives::EthPrimitives,
+> {
+    /// The canonical epoch of the last notification.
+    canonical_epoch: u64,
+    #[pin]
+    st: BroadcastStream<CanonStateNotification<N>>,
+}
+
+impl<N: NodePrimitives> CanonicalEpochNotificationStream<N> {
+    /// Creates a new stream of the notifications of the receiver.
+    ///
+    /// The receiver must have been subscribed in the given canonical epoch, without any
+    /// notification being sent in between.
+    pub fn new(canonical_epoch: u64, notifications: CanonStateNotifications<N>) -> Self {
+        Self { canonical_epoch, st: BroadcastStream::new(notifications) }
+    }
+}
+
+impl<N: NodePrimitives> Stream for CanonicalEpochNotificationStream<N> {
+    type Item = CanonicalEpochNotification<N>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        match ready!(this.st.poll_next(cx)) {
+            Some(Ok(notification)) => {
+                if matches!(notification, CanonStateNotification::Reorg { .. }) {
+                    *this.canonical_epoch += 1;
+                }
+                Poll::Ready(Some(CanonicalEpochNotification {
+                    canonical_epoch: *this.canonical_epoch,
+                    notification,
+                }))
+            }
+            Some(Err(err)) => {
+                debug!(%err, "canonical epoch notification stream lagging behind, closing");
+                Poll::Ready(None)
+            }
+            None => Poll::Ready(None),
+        }
+    }
+}
+
 /// A notification that is sent when a new block is imported, or an old block is reverted.
 ///
 /// The notification contains at least one [`Chain`] with the imported segment. If some blocks were
@@ -106,6 +185,18 @@ pub enum CanonStateNotification<N: NodePrimitives = reth_ethereum_primitives::Et
     },
 }
 
+/// A [`CanonStateNotification`] tagged with the canonical epoch it was emitted in, see
+/// [`CanonStateSubscriptions::canonical_epoch`].
+///
+/// The epoch of a [`CanonStateNotification::Reorg`] is the epoch it started.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CanonicalEpochNotification<N: NodePrimitives = reth_ethereum_primitives::EthPrimitives> {
+    /// The canonical epoch the notification was emitted in.
+    pub canonical_epoch: u64,
+    /// The notification.
+    pub notification: CanonStateNotification<N>,
+}
+
 impl<N: NodePrimitives> CanonStateNotification<N> {
     /// Get the chain segment that was reverted, if any.
     pub fn reverted(&self) -> Option<Arc<Chain<N>>> {
@@ -1,6 +1,6 @@
 use crate::{
     in_memory::ExecutedBlockWithTrieUpdates, CanonStateNotification, CanonStateNotifications,
-    CanonStateSubscriptions, ExecutedTrieUpdates,
+    CanonStateSubscriptions, CanonicalEpochNotificationStream, ExecutedTrieUpdates,
 };
 use alloy_consensus::{
     Header, SignableTransaction, Transaction as _, TxEip1559, TxReceipt, EMPTY_ROOT_HASH,
@@ -31,7 +31,10 @@ use revm_state::AccountInfo;
 use std::{
     collections::HashMap,
     ops::Range,
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
 };
 use tokio::sync::broadcast::{self, Sender};
 
@@ -315,6 +318,8 @@ impl TestBlockBuilder {
 pub struct TestCanonStateSubscriptions<N: NodePrimitives = reth_ethereum_primitives::EthPrimitives>
 {
     canon_notif_tx: Arc<Mutex<Vec<Sender<CanonStateNotification<N>>>>>,
+    /// Bumped on every reorg, only modified while `canon_notif_tx` is locked.
+    canonical_epoch: Arc<AtomicU64>,
 }
 
 impl TestCanonStateSubscriptions {
@@ -326,10 +331,12 @@ impl TestCanonStateSubscriptions {
     }
 
     /// Adds reorg to the queue that can be consumed with
-    /// [`TestCanonStateSubscriptions::subscribe_to_canonical_state`]
+    /// [`TestCanonStateSubscriptions::subscribe_to_canonical_state`], bumping the canonical epoch
     pub fn add_next_reorg(&self, old: Arc<Chain>, new: Arc<Chain>) {
         let event = CanonStateNotification::Reorg { old, new };
-        self.canon_notif_tx.lock().as_mut().unwrap().retain(|tx| tx.send(event.clone()).is_ok())
+        let mut canon_notif_tx = self.canon_notif_tx.lock().unwrap();
+        self.canonical_epoch.fetch_add(1, Ordering::SeqCst);
+        canon_notif_tx.retain(|tx| tx.send(event.clone()).is_ok())
     }
 }
 
@@ -345,4 +352,20 @@ impl CanonStateSubscriptions for TestCanonStateSubscriptions {
 
         canon_notif_rx
     }
+
+    /// Sets up a broadcast channel with a buffer size of 100.
+    fn subscribe_to_canonical_epochs(&self) -> Option<CanonicalEpochNotificationStream> {
+        let (canon_notif_tx, canon_notif_rx) = broadcast::channel(100);
+        let mut senders = self.canon_notif_tx.lock().unwrap();
+        senders.push(canon_notif_tx);
+
+        Some(CanonicalEpochNotificationStream::new(
+            self.canonical_epoch.load(Ordering::SeqCst),
+            canon_notif_rx,
+        ))
+    }
+
+    fn canonical_epoch(&self) -> Option<u64> {
+        Some(self.canonical_epoch.load(Ordering::SeqCst))
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{ChainSpec, DepositContract};
 use alloc::{boxed::Box, vec::Vec};
 use alloy_chains::Chain;
-use alloy_consensus::Header;
+use alloy_consensus::{constants::MAXIMUM_EXTRA_DATA_SIZE, Header};
 use alloy_eips::{calc_next_block_base_fee, eip1559::BaseFeeParams, eip7840::BlobParams};
 use alloy_genesis::Genesis;
 use alloy_primitives::{B256, U256};
@@ -67,6 +67,13 @@ pub trait EthChainSpec: Send + Sync + Unpin + Debug {
     /// Returns the final total difficulty if the Paris hardfork is known.
     fn final_paris_total_difficulty(&self) -> Option<U256>;
 
+    /// Returns the maximum allowed length of a header's extra data.
+    ///
+    /// Defaults to the Ethereum limit of [`MAXIMUM_EXTRA_DATA_SIZE`] bytes.
+    fn max_extra_data_size(&self) -> usize {
+        MAXIMUM_EXTRA_DATA_SIZE
+    }
+
     /// See [`calc_next_block_base_fee`].
     fn next_block_base_fee(&self, parent: &Self::Header, target_timestamp: u64) -> Option<u64> {
         Some(calc_next_block_base_fee(
@@ -76,6 +83,46 @@ pub trait EthChainSpec: Send + Sync + Unpin + Debug {
             self.base_fee_params_at_timestamp(target_timestamp),
         ))
     }
+
+    /// Returns the excess blob gas of the next block, given the parent's excess blob gas and blob
+    /// gas used and the [`BlobParams`] active for the next block.
+    ///
+    /// Defaults to the EIP-4844 update rule, see [`BlobParams::next_block_excess_blob_gas`].
+    /// Chains with a modified blob fee market can override this.
+    fn next_block_excess_blob_gas(
+        &self,
+        blob_params: BlobParams,
+        parent_excess_blob_gas: u64,
+        parent_blob_gas_used: u64,
+    ) -> u64 {
+        blob_params.next_block_excess_blob_gas(parent_excess_blob_gas, parent_blob_gas_used)
+    }
+
+    /// Returns the blob base fee for the given excess blob gas and [`BlobParams`].
+    ///
+    /// Defaults to the EIP-4844 fee computation, see [`BlobParams::calc_blob_fee`]. Chains with a
+    /// modified blob fee market can override this.
+    fn blob_base_fee(&self, blob_params: BlobParams, excess_blob_gas: u64) -> u128 {
+        blob_params.calc_blob_fee(excess_blob_gas)
+    }
+
+    /// Returns the blob base fee of the block after `parent`, given the [`BlobParams`] active for
+    /// it, using [`Self::next_block_excess_blob_gas`] and [`Self::blob_base_fee`].
+    ///
+    /// Returns `None` if there are no [`BlobParams`] or the parent has no blob gas fields.
+    fn next_block_blob_fee(
+        &self,
+        parent: &Self::Header,
+        blob_params: Option<BlobParams>,
+    ) -> Option<u128> {
+        let blob_params = blob_params?;
+        let excess_blob_gas = self.next_block_excess_blob_gas(
+            blob_params,
+            parent.excess_blob_gas()?,
+            parent.blob_gas_used()?,
+        );
+        Some(self.blob_base_fee(blob_params, excess_blob_gas))
+    }
 }
 
 impl EthChainSpec for ChainSpec {
@@ -142,4 +189,8 @@ impl EthChainSpec for ChainSpec {
     fn final_paris_total_difficulty(&self) -> Option<U256> {
         self.paris_block_and_final_difficulty.map(|(_, final_difficulty)| final_difficulty)
     }
+
+    fn max_extra_data_size(&self) -> usize {
+        self.max_extra_data_size
+    }
 }
@@ -10,7 +10,7 @@ use alloy_chains::{Chain, NamedChain};
 use alloy_consensus::{
     constants::{
         DEV_GENESIS_HASH, EMPTY_WITHDRAWALS, HOLESKY_GENESIS_HASH, HOODI_GENESIS_HASH,
-        MAINNET_GENESIS_HASH, SEPOLIA_GENESIS_HASH,
+        MAINNET_GENESIS_HASH, MAXIMUM_EXTRA_DATA_SIZE, SEPOLIA_GENESIS_HASH,
     },
     Header,
 };
@@ -108,6 +108,7 @@ pub static MAINNET: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: MAINNET_PRUNE_DELETE_LIMIT,
         blob_params: BlobScheduleBlobParams::default(),
+        max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -137,6 +138,7 @@ pub static SEPOLIA: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 10000,
         blob_params: BlobScheduleBlobParams::default(),
+        max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -164,6 +166,7 @@ pub static HOLESKY: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 10000,
         blob_params: BlobScheduleBlobParams::default(),
+        max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -193,6 +196,7 @@ pub static HOODI: LazyLock<Arc<ChainSpec>> = LazyLock::new(|| {
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 10000,
         blob_params: BlobScheduleBlobParams::default(),
+        max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
     };
     spec.genesis.config.dao_fork_support = true;
     spec.into()
@@ -300,6 +304,9 @@ pub struct ChainSpec {
 
     /// The settings passed for blob configurations for specific hardforks.
     pub blob_params: BlobScheduleBlobParams,
+
+    /// The maximum allowed length of a header's extra data.
+    pub max_extra_data_size: usize,
 }
 
 impl Default for ChainSpec {
@@ -314,6 +321,7 @@ impl Default for ChainSpec {
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
             prune_delete_limit: MAINNET_PRUNE_DELETE_LIMIT,
             blob_params: Default::default(),
+            max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
         }
     }
 }
@@ -727,6 +735,8 @@ impl From<Genesis> for ChainSpec {
 
         let hardforks = ChainHardforks::new(ordered_hardforks);
 
+        let max_extra_data_size = max_extra_data_size_from_genesis(&genesis);
+
         Self {
             chain: genesis.config.chain_id.into(),
             genesis_header: SealedHeader::new_unhashed(make_genesis_header(&genesis, &hardforks)),
@@ -735,11 +745,23 @@ impl From<Genesis> for ChainSpec {
             paris_block_and_final_difficulty,
             deposit_contract,
             blob_params,
+            max_extra_data_size,
             ..Default::default()
         }
     }
 }
 
+/// Returns the maximum extra data length configured in the genesis config under the
+/// `maxExtraDataSize` key, falling back to [`MAXIMUM_EXTRA_DATA_SIZE`].
+fn max_extra_data_size_from_genesis(genesis: &Genesis) -> usize {
+    genesis
+        .config
+        .extra_fields
+        .get_deserialized::<usize>("maxExtraDataSize")
+        .and_then(Result::ok)
+        .unwrap_or(MAXIMUM_EXTRA_DATA_SIZE)
+}
+
 impl Hardforks for ChainSpec {
     fn fork<H: Hardfork>(&self, fork: H) -> ForkCondition {
         self.hardforks.fork(fork)
@@ -966,6 +988,7 @@ impl ChainSpecBuilder {
             })
         };
         let genesis = self.genesis.expect("The genesis is required");
+        let max_extra_data_size = max_extra_data_size_from_genesis(&genesis);
         ChainSpec {
             chain: self.chain.expect("The chain is required"),
             genesis_header: SealedHeader::new_unhashed(make_genesis_header(
@@ -976,6 +999,7 @@ impl ChainSpecBuilder {
             hardforks: self.hardforks,
             paris_block_and_final_difficulty,
             deposit_contract: None,
+            max_extra_data_size,
             ..Default::default()
         }
     }
@@ -2223,6 +2247,19 @@ Post-merge hard forks (timestamp based):
         assert_eq!(genesis.base_fee_per_gas, Some(0x1337));
     }
 
+    #[test]
+    fn test_max_extra_data_size_from_genesis() {
+        let s = r#"{"config":{"chainId":1337,"maxExtraDataSize":97},"extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","alloc":{}}"#;
+        let genesis: Genesis = serde_json::from_str(s).unwrap();
+        let chainspec = ChainSpec::from(genesis);
+        assert_eq!(chainspec.max_extra_data_size(), 97);
+
+        // falls back to the Ethereum limit if not configured
+        let chainspec = ChainSpec::from(Genesis::default());
+        assert_eq!(chainspec.max_extra_data_size(), MAXIMUM_EXTRA_DATA_SIZE);
+        assert_eq!(MAINNET.max_extra_data_size(), MAXIMUM_EXTRA_DATA_SIZE);
+    }
+
     #[test]
     fn test_parse_cancun_genesis_json() {
         let s = r#"{"config":{"ethash":{},"chainId":1337,"homesteadBlock":0,"eip150Block":0,"eip155Block":0,"eip158Block":0,"byzantiumBlock":0,"constantinopleBlock":0,"petersburgBlock":0,"istanbulBlock":0,"berlinBlock":0,"londonBlock":0,"terminalTotalDifficulty":0,"terminalTotalDifficultyPassed":true,"shanghaiTime":0,"cancunTime":4661},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{"658bdf435d810c91414ec09147daa6db62406379":{"balance":"0x487a9a304539440000"},"aa00000000000000000000000000000000000000":{"code":"0x6042","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x1","nonce":"0x1"},"bb00000000000000000000000000000000000000":{"code":"0x600154600354","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x2","nonce":"0x1"}},"number":"0x0","gasUsed":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","baseFeePerGas":"0x3b9aca00"}"#;
@@ -95,11 +95,14 @@ impl<C: ChainSpecParser> EnvironmentArgs<C> {
         let (db, sfp) = match access {
             AccessRights::RW => (
                 Arc::new(init_db(db_path, self.db.database_args())?),
-                StaticFileProvider::read_write(sf_path)?,
+                StaticFileProvider::read_write_with_lock_takeover(
+                    sf_path,
+                    self.db.lock_takeover_host(),
+                )?,
             ),
-            AccessRights::RO => (
+            AccessRights::RO | AccessRights::ROFollow => (
                 Arc::new(open_db_read_only(&db_path, self.db.database_args())?),
-                StaticFileProvider::read_only(sf_path, false)?,
+                StaticFileProvider::read_only(sf_path, matches!(access, AccessRights::ROFollow))?,
             ),
         };
 
@@ -200,6 +203,10 @@ pub enum AccessRights {
     RW,
     /// Read-only access
     RO,
+    /// Read-only access to an environment that is concurrently written to by another process.
+    ///
+    /// Static files are watched for changes, so that newly committed data becomes visible.
+    ROFollow,
 }
 
 impl AccessRights {
@@ -1,37 +1,101 @@
 //! CLI command to show configs.
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use eyre::{bail, WrapErr};
 use reth_config::Config;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 /// `reth config` command
 #[derive(Debug, Parser)]
 pub struct Command {
     /// The path to the configuration file to use.
-    #[arg(long, value_name = "FILE", verbatim_doc_comment)]
+    #[arg(long, value_name = "FILE", global = true, verbatim_doc_comment)]
     config: Option<PathBuf>,
 
     /// Show the default config
     #[arg(long, verbatim_doc_comment, conflicts_with = "config")]
     default: bool,
+
+    #[command(subcommand)]
+    command: Option<Subcommands>,
+}
+
+/// `reth config` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Validate the config file, reporting unknown keys and invalid values
+    Validate,
+    /// Show the values of the config file that differ from the defaults
+    Show {
+        /// Show the full effective config, including all default values
+        #[arg(long)]
+        effective: bool,
+    },
 }
 
 impl Command {
     /// Execute `config` command
     pub async fn execute(&self) -> eyre::Result<()> {
-        let config = if self.default {
-            Config::default()
-        } else {
-            let path = self.config.clone().unwrap_or_default();
-            // Check if the file exists
-            if !path.exists() {
-                bail!("Config file does not exist: {}", path.display());
+        match &self.command {
+            None if self.default => {
+                println!("{}", Config::default().to_toml_string()?);
+            }
+            None | Some(Subcommands::Show { effective: true }) => {
+                println!("{}", self.load()?.to_toml_string()?);
             }
-            // Read the configuration file
-            Config::from_path(&path)
-                .wrap_err_with(|| format!("Could not load config file: {}", path.display()))?
-        };
-        println!("{}", toml::to_string_pretty(&config)?);
+            Some(Subcommands::Show { effective: false }) => {
+                for change in self.load()?.diff(&Config::default()) {
+                    println!("{change}");
+                }
+            }
+            Some(Subcommands::Validate) => {
+                let path = self.path()?;
+                let config = std::fs::read_to_string(path)
+                    .wrap_err_with(|| format!("Could not read config file: {}", path.display()))?;
+                if let Err(errors) = Config::parse_strict(&config) {
+                    for error in &errors {
+                        eprintln!("{}: {error}", path.display());
+                    }
+                    bail!("Config file is invalid: {} error(s)", errors.len());
+                }
+                println!("Config file is valid: {}", path.display());
+            }
+        }
         Ok(())
     }
+
+    /// Returns the path of the config file, which must exist.
+    fn path(&self) -> eyre::Result<&Path> {
+        let path = self.config.as_deref().unwrap_or(Path::new(""));
+        // Check if the file exists
+        if !path.exists() {
+            bail!("Config file does not exist: {}", path.display());
+        }
+        Ok(path)
+    }
+
+    /// Loads the config file.
+    fn load(&self) -> eyre::Result<Config> {
+        let path = self.path()?;
+        // Read the configuration file
+        Config::from_path(path)
+            .wrap_err_with(|| format!("Could not load config file: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_subcommands() {
+        let cmd = Command::parse_from(["reth", "validate", "--config", "reth.toml"]);
+        assert!(matches!(cmd.command, Some(Subcommands::Validate)));
+        assert_eq!(cmd.config, Some(PathBuf::from("reth.toml")));
+
+        let cmd = Command::parse_from(["reth", "--config", "reth.toml", "show", "--effective"]);
+        assert!(matches!(cmd.command, Some(Subcommands::Show { effective: true })));
+
+        let cmd = Command::parse_from(["reth", "--default"]);
+        assert!(cmd.default && cmd.command.is_none());
+    }
 }
@@ -0,0 +1,41 @@
+use clap::Parser;
+use reth_db::{
+    mdbx::DatabaseArguments, migration::migrations, open_db, open_db_read_only, DatabaseEnv,
+};
+use std::path::Path;
+use tracing::info;
+
+/// The arguments for the `reth db migrate` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Reports the pending migrations of the database without running them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+impl Command {
+    /// Execute `db migrate` command
+    pub fn execute(self, db_path: &Path, args: DatabaseArguments) -> eyre::Result<()> {
+        let migrator = migrations::<DatabaseEnv>();
+
+        if self.dry_run {
+            let db = open_db_read_only(db_path, args)?;
+            println!("{}", migrator.dry_run(&db, db_path)?);
+            return Ok(())
+        }
+
+        let db = open_db(db_path, args)?;
+        let version = migrator.run(&db, db_path, |progress| {
+            info!(
+                target: "reth::cli",
+                from_version = progress.from_version,
+                migrated = progress.migrated,
+                entries = progress.entries,
+                "Migrating database"
+            )
+        })?;
+        info!(target: "reth::cli", version, "Database is up to date");
+
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
-use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use crate::{
+    common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs},
+    recover::point::RecoveryPoint,
+};
 use clap::{Parser, Subcommand};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
@@ -13,6 +16,7 @@ mod clear;
 mod diff;
 mod get;
 mod list;
+mod migrate;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -45,11 +49,18 @@ pub enum Subcommands {
         /// Bypasses the interactive confirmation and drops the database directly
         #[arg(short, long)]
         force: bool,
+        /// Skip creating a recovery point before dropping the database.
+        ///
+        /// See `reth recover restore`.
+        #[arg(long)]
+        no_recovery_point: bool,
     },
     /// Deletes all table entries
     Clear(clear::Command),
     /// Lists current and local database versions
     Version,
+    /// Migrates the database to the current database version
+    Migrate(migrate::Command),
     /// Returns the full database path
     Path,
 }
@@ -109,7 +120,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool)?;
                 });
             }
-            Subcommands::Drop { force } => {
+            Subcommands::Drop { force, no_recovery_point } => {
                 if !force {
                     // Ask for confirmation
                     print!(
@@ -128,6 +139,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                 }
 
                 let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+                if !no_recovery_point {
+                    RecoveryPoint::create(&provider_factory, &data_dir, "db-drop")?;
+                }
                 let tool = DbTool::new(provider_factory)?;
                 tool.drop(db_path, static_files_path, exex_wal_path)?;
             }
@@ -150,6 +164,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     println!("Local database is uninitialized");
                 }
             }
+            Subcommands::Migrate(command) => {
+                command.execute(&db_path, self.env.db.database_args())?;
+            }
             Subcommands::Path => {
                 println!("{}", db_path.display());
             }
@@ -0,0 +1,271 @@
+//! `reth export state` command.
+
+use crate::{
+    common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs},
+    manifest::{file_manifest_path, ExportKind, ExportManifest, ManifestArgs},
+};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, BlockNumber, Bytes, B256, U256};
+use clap::{Parser, ValueEnum};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    tables,
+    transaction::DbTx,
+};
+use reth_provider::{BlockNumReader, HeaderProvider, ProviderError};
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::info;
+
+/// Supported output formats of `reth export state`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum StateExportFormat {
+    /// Newline-delimited JSON, one record per line.
+    #[default]
+    Ndjson,
+}
+
+/// `reth export state` command
+///
+/// Writes the plain state of the latest block as a sequence of records: a `root` record first,
+/// followed by an `account` record for every account, each immediately followed by the `storage`
+/// records of that account.
+///
+/// Records are written as they are read from the database, so memory usage doesn't depend on the
+/// size of the state. When streaming to stdout, use `--quiet` to keep log output out of the
+/// stream.
+///
+/// When writing to a file, a manifest is written next to it to `<output>.manifest.json`.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The output format.
+    #[arg(long, value_enum, default_value_t = StateExportFormat::Ndjson)]
+    format: StateExportFormat,
+
+    /// Write the records to stdout.
+    #[arg(long, conflicts_with = "output")]
+    stream: bool,
+
+    /// The file to write the records to.
+    #[arg(long, value_name = "PATH", required_unless_present = "stream")]
+    output: Option<PathBuf>,
+
+    /// The manifest written next to the output file.
+    #[command(flatten)]
+    manifest: ManifestArgs,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `export state` command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+
+        let provider = provider_factory.provider()?;
+        let block = provider.best_block_number()?;
+        let root = provider
+            .header_by_number(block)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(block.into()))?
+            .state_root();
+
+        let writer: Box<dyn Write> = match &self.output {
+            Some(path) => {
+                info!(target: "reth::cli", block, path = %path.display(), "Exporting state");
+                Box::new(File::create(path)?)
+            }
+            None => Box::new(io::stdout().lock()),
+        };
+
+        let mut exporter = StateExporter::new(BufWriter::new(writer), self.format);
+        match exporter.export(provider.tx_ref(), block, root) {
+            Ok(()) => {}
+            // the consumer of the stream went away, e.g. `reth export state --stream | head`
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => return Ok(()),
+            Err(err) => return Err(err.into()),
+        }
+
+        info!(
+            target: "reth::cli",
+            block,
+            accounts = exporter.accounts,
+            storage_slots = exporter.storage_slots,
+            "Exported state"
+        );
+
+        if let Some(path) = &self.output {
+            let mut manifest =
+                ExportManifest::new(ExportKind::State, self.env.chain.chain().id(), block, block);
+            manifest.add_file(path)?;
+            let manifest_path = file_manifest_path(path);
+            self.manifest.write(manifest, &manifest_path)?;
+            info!(target: "reth::cli", path = %manifest_path.display(), "Wrote export manifest");
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+/// A single record of the exported state.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum StateRecord {
+    /// The state root of the exported block.
+    Root { block: BlockNumber, root: B256 },
+    /// An account and its bytecode, if any.
+    #[serde(rename_all = "camelCase")]
+    Account {
+        address: Address,
+        nonce: u64,
+        balance: U256,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        code_hash: Option<B256>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        code: Option<Bytes>,
+    },
+    /// A storage slot of the preceding account.
+    Storage { address: Address, key: B256, value: U256 },
+}
+
+/// Writes the plain state to a writer record by record.
+#[derive(Debug)]
+struct StateExporter<W> {
+    writer: W,
+    format: StateExportFormat,
+    accounts: usize,
+    storage_slots: usize,
+}
+
+impl<W: Write> StateExporter<W> {
+    const fn new(writer: W, format: StateExportFormat) -> Self {
+        Self { writer, format, accounts: 0, storage_slots: 0 }
+    }
+
+    /// Walks the plain account and storage tables and writes all records.
+    fn export<TX: DbTx>(&mut self, tx: &TX, block: BlockNumber, root: B256) -> io::Result<()> {
+        self.write(&StateRecord::Root { block, root })?;
+
+        let mut accounts =
+            tx.cursor_read::<tables::PlainAccountState>().map_err(io::Error::other)?;
+        let mut storages =
+            tx.cursor_dup_read::<tables::PlainStorageState>().map_err(io::Error::other)?;
+
+        for entry in accounts.walk(None).map_err(io::Error::other)? {
+            let (address, account) = entry.map_err(io::Error::other)?;
+
+            let code = match account.bytecode_hash {
+                Some(hash) => tx
+                    .get::<tables::Bytecodes>(hash)
+                    .map_err(io::Error::other)?
+                    .map(|bytecode| bytecode.original_bytes()),
+                None => None,
+            };
+            self.write(&StateRecord::Account {
+                address,
+                nonce: account.nonce,
+                balance: account.balance,
+                code_hash: account.bytecode_hash,
+                code,
+            })?;
+            self.accounts += 1;
+
+            for entry in storages.walk_dup(Some(address), None).map_err(io::Error::other)? {
+                let (_, slot) = entry.map_err(io::Error::other)?;
+                self.write(&StateRecord::Storage { address, key: slot.key, value: slot.value })?;
+                self.storage_slots += 1;
+            }
+        }
+
+        self.writer.flush()
+    }
+
+    /// Writes a single record in the configured format.
+    fn write(&mut self, record: &StateRecord) -> io::Result<()> {
+        match self.format {
+            StateExportFormat::Ndjson => {
+                serde_json::to_writer(&mut self.writer, record)?;
+                self.writer.write_all(b"\n")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_output_args() {
+        let cmd = Command::<EthereumChainSpecParser>::parse_from(["reth", "--stream"]);
+        assert!(cmd.stream);
+        assert_eq!(cmd.format, StateExportFormat::Ndjson);
+
+        let cmd =
+            Command::<EthereumChainSpecParser>::parse_from(["reth", "--output", "state.ndjson"]);
+        assert_eq!(cmd.output, Some(PathBuf::from("state.ndjson")));
+
+        // either stdout or a file is required, but not both
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from(["reth"]).is_err());
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--stream",
+            "--output",
+            "state.ndjson"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn write_ndjson_records() {
+        let mut exporter = StateExporter::new(Vec::new(), StateExportFormat::Ndjson);
+        exporter.write(&StateRecord::Root { block: 1, root: B256::ZERO }).unwrap();
+        exporter
+            .write(&StateRecord::Account {
+                address: Address::ZERO,
+                nonce: 1,
+                balance: U256::from(2),
+                code_hash: None,
+                code: None,
+            })
+            .unwrap();
+        exporter
+            .write(&StateRecord::Storage {
+                address: Address::ZERO,
+                key: B256::ZERO,
+                value: U256::from(3),
+            })
+            .unwrap();
+
+        let output = String::from_utf8(exporter.writer).unwrap();
+        let lines = output.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            r#"{"type":"root","block":1,"root":"0x0000000000000000000000000000000000000000000000000000000000000000"}"#
+        );
+        assert_eq!(
+            lines[1],
+            r#"{"type":"account","address":"0x0000000000000000000000000000000000000000","nonce":1,"balance":"0x2"}"#
+        );
+        assert_eq!(
+            lines[2],
+            r#"{"type":"storage","address":"0x0000000000000000000000000000000000000000","key":"0x0000000000000000000000000000000000000000000000000000000000000000","value":"0x3"}"#
+        );
+    }
+}
@@ -1,6 +1,9 @@
 //! Command exporting block data to convert them to ERA1 files.
 
-use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use crate::{
+    common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs},
+    manifest::{ExportKind, ExportManifest, ManifestArgs, MANIFEST_FILE_NAME},
+};
 use clap::{Args, Parser};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
@@ -40,6 +43,9 @@ pub struct ExportArgs {
     /// The block data are read from the database.
     #[arg(long, value_name = "EXPORT_ERA1_PATH", verbatim_doc_comment)]
     path: Option<PathBuf>,
+    /// The manifest written next to the exported files.
+    #[command(flatten)]
+    manifest: ManifestArgs,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportEraCommand<C> {
@@ -97,6 +103,19 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportEraC
             export_config.dir.display()
         );
 
+        let mut manifest = ExportManifest::new(
+            ExportKind::Era1,
+            self.env.chain.chain().id(),
+            export_config.first_block_number,
+            era1::determine_export_range(&provider, &export_config)?,
+        );
+        for file in &exported_files {
+            manifest.add_file(file)?;
+        }
+        let manifest_path = export_config.dir.join(MANIFEST_FILE_NAME);
+        self.export.manifest.write(manifest, &manifest_path)?;
+        info!(target: "reth::cli", path = %manifest_path.display(), "Wrote export manifest");
+
         Ok(())
     }
 }
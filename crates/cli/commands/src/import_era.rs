@@ -6,12 +6,15 @@ use eyre::eyre;
 use reqwest::{Client, Url};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_cli::chainspec::ChainSpecParser;
+use reth_consensus::PreMergeHeaderValidator;
 use reth_era_downloader::{read_dir, EraClient, EraStream, EraStreamConfig};
 use reth_era_utils as era;
+use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_etl::Collector;
 use reth_fs_util as fs;
+use reth_node_api::HeaderTy;
 use reth_node_core::version::SHORT_VERSION;
-use reth_provider::StaticFileProviderFactory;
+use reth_provider::{ChainSpecProvider, StaticFileProviderFactory};
 use reth_static_file_types::StaticFileSegment;
 use std::{path::PathBuf, sync::Arc};
 use tracing::info;
@@ -24,6 +27,13 @@ pub struct ImportEraCommand<C: ChainSpecParser> {
 
     #[clap(flatten)]
     import: ImportArgs,
+
+    /// Verify the headers of every ERA1 file against its epoch accumulator before importing it.
+    ///
+    /// The headers are validated without proof-of-work checks, using the total difficulties and
+    /// the accumulator stored in the files.
+    #[arg(long, verbatim_doc_comment)]
+    verify_accumulator: bool,
 }
 
 #[derive(Debug, Args)]
@@ -67,6 +77,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
     pub async fn execute<N>(self) -> eyre::Result<()>
     where
         N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        C::ChainSpec: EthChainSpec<Header = HeaderTy<N>>,
     {
         info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
 
@@ -80,10 +91,15 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
             .unwrap_or_default() +
             1;
 
+        let validator =
+            self.verify_accumulator.then(|| EthBeaconConsensus::new(provider_factory.chain_spec()));
+        let validator =
+            validator.as_ref().map(|validator| validator as &dyn PreMergeHeaderValidator<_>);
+
         if let Some(path) = self.import.path {
             let stream = read_dir(path, next_block)?;
 
-            era::import(stream, &provider_factory, &mut hash_collector)?;
+            era::import(stream, &provider_factory, &mut hash_collector, validator)?;
         } else {
             let url = match self.import.url {
                 Some(url) => url,
@@ -98,7 +114,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraC
             let client = EraClient::new(Client::new(), url, folder);
             let stream = EraStream::new(client, config);
 
-            era::import(stream, &provider_factory, &mut hash_collector)?;
+            era::import(stream, &provider_factory, &mut hash_collector, validator)?;
         }
 
         Ok(())
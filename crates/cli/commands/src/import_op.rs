@@ -28,6 +28,21 @@ use std::{path::Path, sync::Arc};
 use tokio::sync::watch;
 use tracing::{debug, error, info};
 
+#[cfg(feature = "pre-merge")]
+use alloy_primitives::Sealable;
+#[cfg(feature = "pre-merge")]
+use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
+#[cfg(feature = "pre-merge")]
+use reth_consensus::{OmmerAncestor, OmmerValidator};
+#[cfg(feature = "pre-merge")]
+use reth_ethereum_consensus::{EthBeaconConsensus, MAX_OMMER_DEPTH};
+#[cfg(feature = "pre-merge")]
+use reth_primitives_traits::{Block, BlockBody, SealedHeader};
+#[cfg(feature = "pre-merge")]
+use reth_provider::BlockReader;
+#[cfg(feature = "pre-merge")]
+use std::collections::VecDeque;
+
 /// Configuration for importing blocks from RLP files.
 #[derive(Debug, Clone, Default)]
 pub struct ImportConfig {
@@ -111,6 +126,9 @@ where
         total_decoded_blocks += file_client.headers_len();
         total_decoded_txns += file_client.total_transactions();
 
+        #[cfg(feature = "pre-merge")]
+        validate_ommers(&provider_factory, &file_client)?;
+
         let (mut pipeline, events) = build_import_pipeline_impl(
             config,
             provider_factory.clone(),
@@ -177,6 +195,65 @@ where
     Ok(result)
 }
 
+/// Validates the ommers of all pre-merge blocks of the file client against their ancestors, see
+/// [`OmmerValidator`].
+///
+/// The ancestors of the first blocks of the file client are read from the database.
+#[cfg(feature = "pre-merge")]
+fn validate_ommers<N>(
+    provider_factory: &ProviderFactory<N>,
+    file_client: &FileClient<BlockTy<N>>,
+) -> eyre::Result<()>
+where
+    N: ProviderNodeTypes,
+{
+    let (Some(min_block), Some(max_block)) = (file_client.min_block(), file_client.max_block())
+    else {
+        return Ok(())
+    };
+    let chain_spec = provider_factory.chain_spec();
+    let consensus = EthBeaconConsensus::new(chain_spec.clone());
+
+    // the ancestors of the next block in descending order
+    let mut ancestors = VecDeque::with_capacity(MAX_OMMER_DEPTH + 1);
+    let provider = provider_factory.provider()?;
+    for number in min_block.saturating_sub(MAX_OMMER_DEPTH as u64)..min_block {
+        let Some(block) = provider.block(number.into())? else { continue };
+        let ommers = block.body().ommers().unwrap_or_default().iter().map(|o| o.hash_slow());
+        ancestors.push_front(OmmerAncestor {
+            ommers: ommers.collect(),
+            header: SealedHeader::seal_slow(block.header().clone()),
+        });
+    }
+    drop(provider);
+
+    for number in min_block..=max_block {
+        if chain_spec.is_paris_active_at_block(number) {
+            break
+        }
+        let (header, body) = file_client
+            .block_parts(number)
+            .ok_or_else(|| eyre::eyre!("file client has no block {number}"))?;
+        let ommers = body.ommers().unwrap_or_default();
+
+        if number != 0 {
+            consensus
+                .validate_ommers(&header, ommers, ancestors.make_contiguous())
+                .map_err(|err| eyre::eyre!("invalid ommers of block {number}: {err}"))?;
+        }
+
+        ancestors.push_front(OmmerAncestor {
+            header,
+            ommers: ommers.iter().map(|ommer| ommer.hash_slow()).collect(),
+        });
+        ancestors.truncate(MAX_OMMER_DEPTH);
+    }
+
+    debug!(target: "reth::import", min_block, max_block, "Validated ommers of pre-merge blocks");
+
+    Ok(())
+}
+
 /// Builds import pipeline.
 ///
 /// If configured to execute, all stages will run. Otherwise, only stages that don't require state
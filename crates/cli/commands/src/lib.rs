@@ -13,6 +13,7 @@ pub mod config_cmd;
 pub mod db;
 pub mod download;
 pub mod dump_genesis;
+pub mod export;
 pub mod export_era;
 pub mod import;
 pub mod import_era;
@@ -20,6 +21,7 @@ pub mod import_op;
 pub mod init_cmd;
 pub mod init_state;
 pub mod launcher;
+pub mod manifest;
 pub mod node;
 pub mod p2p;
 pub mod prune;
@@ -28,5 +30,7 @@ pub mod recover;
 pub mod stage;
 #[cfg(feature = "arbitrary")]
 pub mod test_vectors;
+pub mod verify_chain;
+pub mod verify_manifest;
 
 pub use node::NodeCommand;
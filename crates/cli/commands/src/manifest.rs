@@ -0,0 +1,333 @@
+//! Signed manifests of chain data exports.
+//!
+//! A manifest lists the files of an export together with their sizes and content hashes, the
+//! exported block range, the chain id and the version of reth that produced it. It can be signed
+//! with a secp256k1 key, so that recipients of a distributed dataset can verify both its origin and
+//! its integrity with `reth verify-manifest` before importing it.
+
+use alloy_primitives::{hex, keccak256, Address, BlockNumber, Bytes, Keccak256, B256};
+use clap::Args;
+use eyre::{bail, eyre, WrapErr};
+use reth_node_core::version::CARGO_PKG_VERSION;
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId},
+    Message, PublicKey, SecretKey, SECP256K1,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    ffi::OsString,
+    fs::File,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// The version of the manifest format.
+pub const MANIFEST_VERSION: u64 = 1;
+
+/// The file name of the manifest of an export into a directory.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Arguments that configure the manifest written by export commands.
+#[derive(Debug, Clone, Default, Args)]
+pub struct ManifestArgs {
+    /// Path to a file containing the hex encoded secp256k1 private key the manifest of the export
+    /// is signed with.
+    ///
+    /// Without a key the manifest is written unsigned.
+    #[arg(long = "manifest.signing-key", value_name = "PATH")]
+    pub signing_key: Option<PathBuf>,
+}
+
+impl ManifestArgs {
+    /// Reads the configured signing key, if any.
+    pub fn signing_key(&self) -> eyre::Result<Option<SecretKey>> {
+        let Some(path) = &self.signing_key else { return Ok(None) };
+        let key = reth_fs_util::read_to_string(path)?;
+        let key = hex::decode(key.trim())
+            .ok()
+            .and_then(|key| SecretKey::from_slice(&key).ok())
+            .ok_or_else(|| eyre!("invalid manifest signing key in {}", path.display()))?;
+        Ok(Some(key))
+    }
+
+    /// Signs the manifest with the configured key, if any, and writes it to the given path.
+    pub fn write(&self, mut manifest: ExportManifest, path: &Path) -> eyre::Result<()> {
+        if let Some(key) = self.signing_key()? {
+            manifest.sign(&key);
+        }
+        manifest.write(path)
+    }
+}
+
+/// The kind of data an export contains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportKind {
+    /// Block history in ERA1 files.
+    Era1,
+    /// The plain state of a block, see `reth export state`.
+    State,
+}
+
+/// The inclusive range of blocks contained in an export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestBlockRange {
+    /// The first exported block.
+    pub first: BlockNumber,
+    /// The last exported block.
+    pub last: BlockNumber,
+}
+
+/// A file of an export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// The name of the file, relative to the directory of the manifest.
+    pub name: String,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The keccak256 hash of the content of the file.
+    pub hash: B256,
+}
+
+/// The signature of a manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestSignature {
+    /// The address of the key that signed the manifest.
+    pub signer: Address,
+    /// The 65 byte recoverable signature of [`ExportManifest::signing_hash`].
+    pub signature: Bytes,
+}
+
+/// The manifest of a chain data export.
+///
+/// The manifest doesn't contain any timestamps and lists its files ordered by name, so exporting
+/// the same data twice yields identical manifests.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportManifest {
+    /// The version of the manifest format.
+    pub version: u64,
+    /// The kind of the exported data.
+    pub kind: ExportKind,
+    /// The id of the chain the data was exported from.
+    pub chain_id: u64,
+    /// The version of reth that produced the export.
+    pub reth_version: String,
+    /// The exported blocks.
+    pub blocks: ManifestBlockRange,
+    /// The files of the export.
+    pub files: Vec<ManifestFile>,
+    /// The signature of the manifest, if it's signed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ManifestSignature>,
+}
+
+impl ExportManifest {
+    /// Creates a new, empty manifest.
+    pub fn new(kind: ExportKind, chain_id: u64, first: BlockNumber, last: BlockNumber) -> Self {
+        Self {
+            version: MANIFEST_VERSION,
+            kind,
+            chain_id,
+            reth_version: CARGO_PKG_VERSION.to_string(),
+            blocks: ManifestBlockRange { first, last },
+            files: Vec::new(),
+            signature: None,
+        }
+    }
+
+    /// Hashes the file at the given path and adds it to the manifest.
+    pub fn add_file(&mut self, path: &Path) -> eyre::Result<()> {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| eyre!("invalid file name {}", path.display()))?
+            .to_string();
+        let (size, hash) =
+            hash_file(path).wrap_err_with(|| format!("failed to hash {}", path.display()))?;
+
+        let index = self.files.partition_point(|file| file.name < name);
+        self.files.insert(index, ManifestFile { name, size, hash });
+        self.signature = None;
+        Ok(())
+    }
+
+    /// Returns the hash that is signed, the keccak256 hash of the JSON encoding of the manifest
+    /// without its signature.
+    pub fn signing_hash(&self) -> B256 {
+        let unsigned = Self { signature: None, ..self.clone() };
+        keccak256(serde_json::to_vec(&unsigned).expect("manifest is serializable"))
+    }
+
+    /// Signs the manifest with the given key.
+    pub fn sign(&mut self, key: &SecretKey) {
+        let message = Message::from_digest(self.signing_hash().0);
+        let (recovery_id, signature) =
+            SECP256K1.sign_ecdsa_recoverable(&message, key).serialize_compact();
+
+        let mut bytes = Vec::with_capacity(65);
+        bytes.extend_from_slice(&signature);
+        bytes.push(i32::from(recovery_id) as u8);
+        self.signature = Some(ManifestSignature {
+            signer: public_key_to_address(&key.public_key(SECP256K1)),
+            signature: bytes.into(),
+        });
+    }
+
+    /// Verifies the signature of the manifest and returns the signer.
+    ///
+    /// Returns `None` if the manifest isn't signed.
+    pub fn verify_signature(&self) -> eyre::Result<Option<Address>> {
+        let Some(signature) = &self.signature else { return Ok(None) };
+        if signature.signature.len() != 65 {
+            bail!("invalid signature length {}", signature.signature.len())
+        }
+
+        let recovery_id = RecoveryId::try_from(signature.signature[64] as i32)?;
+        let recoverable =
+            RecoverableSignature::from_compact(&signature.signature[..64], recovery_id)?;
+        let message = Message::from_digest(self.signing_hash().0);
+        let signer = public_key_to_address(&SECP256K1.recover_ecdsa(&message, &recoverable)?);
+        if signer != signature.signer {
+            bail!(
+                "manifest claims to be signed by {}, but was signed by {signer}",
+                signature.signer
+            )
+        }
+        Ok(Some(signer))
+    }
+
+    /// Checks the sizes and hashes of all files, which are looked up in the given directory.
+    ///
+    /// Returns a description of every file that is missing or doesn't match.
+    pub fn verify_files(&self, dir: &Path) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        for file in &self.files {
+            // the files of an export are always in the directory of the manifest
+            if Path::new(&file.name).file_name().and_then(|name| name.to_str()) !=
+                Some(file.name.as_str())
+            {
+                mismatches.push(format!("{}: invalid file name", file.name));
+                continue
+            }
+
+            match hash_file(&dir.join(&file.name)) {
+                Ok((size, _)) if size != file.size => mismatches
+                    .push(format!("{}: expected {} bytes, found {size}", file.name, file.size)),
+                Ok((_, hash)) if hash != file.hash => mismatches
+                    .push(format!("{}: expected hash {}, found {hash}", file.name, file.hash)),
+                Ok(_) => {}
+                Err(err) => mismatches.push(format!("{}: {err}", file.name)),
+            }
+        }
+        mismatches
+    }
+
+    /// Reads a manifest from the given path.
+    pub fn read(path: &Path) -> eyre::Result<Self> {
+        let manifest: Self = serde_json::from_str(&reth_fs_util::read_to_string(path)?)
+            .wrap_err_with(|| format!("invalid manifest {}", path.display()))?;
+        if manifest.version != MANIFEST_VERSION {
+            bail!("unsupported manifest version {}", manifest.version)
+        }
+        Ok(manifest)
+    }
+
+    /// Writes the manifest to the given path.
+    pub fn write(&self, path: &Path) -> eyre::Result<()> {
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        reth_fs_util::write(path, json)?;
+        Ok(())
+    }
+}
+
+/// Returns the path of the manifest of an export into a single file, `<path>.manifest.json`.
+pub fn file_manifest_path(path: &Path) -> PathBuf {
+    let mut manifest = OsString::from(path.as_os_str());
+    manifest.push(".");
+    manifest.push(MANIFEST_FILE_NAME);
+    manifest.into()
+}
+
+/// Returns the size and the keccak256 hash of the content of the file at the given path.
+fn hash_file(path: &Path) -> io::Result<(u64, B256)> {
+    let mut file = File::open(path)?;
+    let mut hasher = Keccak256::new();
+    let mut buf = vec![0; 64 * 1024];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+        size += read as u64;
+    }
+    Ok((size, hasher.finalize()))
+}
+
+/// Returns the address of the given public key.
+fn public_key_to_address(key: &PublicKey) -> Address {
+    Address::from_raw_public_key(&key.serialize_uncompressed()[1..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(dir: &Path) -> ExportManifest {
+        std::fs::write(dir.join("b.era1"), b"second").unwrap();
+        std::fs::write(dir.join("a.era1"), b"first").unwrap();
+
+        let mut manifest = ExportManifest::new(ExportKind::Era1, 1, 0, 8191);
+        manifest.add_file(&dir.join("b.era1")).unwrap();
+        manifest.add_file(&dir.join("a.era1")).unwrap();
+        manifest
+    }
+
+    #[test]
+    fn files_are_ordered_and_verified() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = manifest(dir.path());
+
+        let names = manifest.files.iter().map(|file| file.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, ["a.era1", "b.era1"]);
+        assert_eq!(manifest.files[0].hash, keccak256(b"first"));
+        assert!(manifest.verify_files(dir.path()).is_empty());
+
+        std::fs::write(dir.path().join("a.era1"), b"tampered").unwrap();
+        std::fs::remove_file(dir.path().join("b.era1")).unwrap();
+        assert_eq!(manifest.verify_files(dir.path()).len(), 2);
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manifest = manifest(dir.path());
+        assert_eq!(manifest.verify_signature().unwrap(), None);
+
+        let key = SecretKey::from_slice(&[1; 32]).unwrap();
+        manifest.sign(&key);
+        let signer = public_key_to_address(&key.public_key(SECP256K1));
+        assert_eq!(manifest.verify_signature().unwrap(), Some(signer));
+
+        // the signature survives a roundtrip through the file
+        let path = dir.path().join(MANIFEST_FILE_NAME);
+        manifest.write(&path).unwrap();
+        let mut read = ExportManifest::read(&path).unwrap();
+        assert_eq!(read, manifest);
+        assert_eq!(read.verify_signature().unwrap(), Some(signer));
+
+        read.blocks.last += 1;
+        assert!(read.verify_signature().is_err());
+    }
+
+    #[test]
+    fn manifest_path_of_file_export() {
+        assert_eq!(
+            file_manifest_path(Path::new("/data/state.ndjson")),
+            PathBuf::from("/data/state.ndjson.manifest.json")
+        );
+    }
+}
@@ -10,8 +10,9 @@ use reth_db::init_db;
 use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, NetworkArgs,
-        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
+        CheckpointArgs, ConsensusArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs,
+        EraArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, RuntimeArgs,
+        TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
@@ -113,6 +114,18 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten, next_help_heading = "ERA")]
     pub era: EraArgs,
 
+    /// All consensus related arguments with --consensus prefix
+    #[command(flatten, next_help_heading = "Consensus")]
+    pub consensus: ConsensusArgs,
+
+    /// All checkpoint manifest related arguments with --checkpoints prefix
+    #[command(flatten, next_help_heading = "Checkpoints")]
+    pub checkpoints: CheckpointArgs,
+
+    /// All dedicated runtime related arguments with --runtime prefix
+    #[command(flatten, next_help_heading = "Runtime")]
+    pub runtime: RuntimeArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -168,6 +181,9 @@ where
             ext,
             engine,
             era,
+            consensus,
+            checkpoints,
+            runtime,
         } = self;
 
         // set up node config
@@ -187,13 +203,17 @@ where
             pruning,
             engine,
             era,
+            consensus,
+            checkpoints,
+            runtime,
         };
 
         let data_dir = node_config.datadir();
         let db_path = data_dir.db();
 
         tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
-        let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
+        let database =
+            Arc::new(init_db(db_path.clone(), node_config.db.database_args())?.with_metrics());
 
         if with_unused_ports {
             node_config = node_config.with_unused_ports();
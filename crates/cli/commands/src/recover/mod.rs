@@ -7,6 +7,8 @@ use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
 use std::sync::Arc;
 
+pub mod point;
+mod restore;
 mod storage_tries;
 
 /// `reth recover` command
@@ -21,6 +23,8 @@ pub struct Command<C: ChainSpecParser> {
 pub enum Subcommands<C: ChainSpecParser> {
     /// Recover the node by deleting dangling storage tries.
     StorageTries(storage_tries::Command<C>),
+    /// Restore a recovery point that was created before a destructive operation.
+    Restore(restore::Command<C>),
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
@@ -31,6 +35,7 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     ) -> eyre::Result<()> {
         match self.command {
             Subcommands::StorageTries(command) => command.execute::<N>(ctx).await,
+            Subcommands::Restore(command) => command.execute::<N>(ctx).await,
         }
     }
 }
@@ -40,6 +45,7 @@ impl<C: ChainSpecParser> Command<C> {
     pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
         match &self.command {
             Subcommands::StorageTries(command) => command.chain_spec(),
+            Subcommands::Restore(command) => command.chain_spec(),
         }
     }
 }
@@ -0,0 +1,210 @@
+//! Recovery points that are created before destructive operations.
+//!
+//! A recovery point consists of a manifest with the stage and prune checkpoints and a snapshot of
+//! the static files directory. Static files that are finalized are hardlinked into the snapshot,
+//! so creating a recovery point is cheap. The highest static file of every segment is still
+//! appended to and truncated in place, so it's copied instead.
+
+use alloy_primitives::map::HashMap;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_provider::{
+    providers::ProviderNodeTypes, ProviderFactory, PruneCheckpointReader, StageCheckpointReader,
+    StaticFileProviderFactory,
+};
+use reth_prune::{PruneCheckpoint, PruneSegment};
+use reth_stages::StageCheckpoint;
+use reth_static_file_types::StaticFileSegment;
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::info;
+
+/// The name of the manifest file of a recovery point.
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// The name of the static files directory of a recovery point.
+const STATIC_FILES_DIR_NAME: &str = "static_files";
+
+/// A recovery point of the stage and prune progress and the static files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecoveryPoint {
+    /// The name of the recovery point.
+    pub name: String,
+    /// The operation that the recovery point was created for.
+    pub operation: String,
+    /// The unix timestamp of the creation, in seconds.
+    pub created_at: u64,
+    /// The checkpoints of all stages.
+    pub stage_checkpoints: Vec<(String, StageCheckpoint)>,
+    /// The checkpoints of all prune segments.
+    pub prune_checkpoints: Vec<(PruneSegment, PruneCheckpoint)>,
+    /// The names of the static files in the snapshot.
+    pub static_files: Vec<String>,
+}
+
+impl RecoveryPoint {
+    /// Creates a recovery point of the current storage before running the given operation.
+    pub fn create<N: ProviderNodeTypes>(
+        provider_factory: &ProviderFactory<N>,
+        data_dir: &ChainPath<DataDirPath>,
+        operation: &str,
+    ) -> eyre::Result<Self> {
+        let created_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let name = format!("{operation}-{created_at}");
+        let path = data_dir.recovery_points().join(&name);
+        eyre::ensure!(!path.exists(), "Recovery point already exists: {path:?}");
+
+        let provider = provider_factory.provider()?;
+        let stage_checkpoints = provider.get_all_checkpoints()?;
+        let prune_checkpoints = provider.get_prune_checkpoints()?;
+        drop(provider);
+
+        let static_files = snapshot_static_files(
+            provider_factory.static_file_provider().directory(),
+            &path.join(STATIC_FILES_DIR_NAME),
+        )?;
+
+        let point = Self {
+            name,
+            operation: operation.to_string(),
+            created_at,
+            stage_checkpoints,
+            prune_checkpoints,
+            static_files,
+        };
+        reth_fs_util::write_json_file(&path.join(MANIFEST_FILE_NAME), &point)?;
+
+        info!(target: "reth::cli", name = %point.name, ?path, "Created recovery point");
+        Ok(point)
+    }
+
+    /// Loads the recovery point with the given name.
+    pub fn load(data_dir: &ChainPath<DataDirPath>, name: &str) -> eyre::Result<Self> {
+        let path = data_dir.recovery_points().join(name).join(MANIFEST_FILE_NAME);
+        eyre::ensure!(path.is_file(), "Recovery point does not exist: {name}");
+        Ok(reth_fs_util::read_json_file(&path)?)
+    }
+
+    /// Returns all recovery points, ordered by creation time.
+    pub fn list(data_dir: &ChainPath<DataDirPath>) -> eyre::Result<Vec<Self>> {
+        let path = data_dir.recovery_points();
+        if !path.is_dir() {
+            return Ok(Vec::new())
+        }
+
+        let mut points = Vec::new();
+        for entry in reth_fs_util::read_dir(&path)? {
+            let manifest = entry?.path().join(MANIFEST_FILE_NAME);
+            if manifest.is_file() {
+                points.push(reth_fs_util::read_json_file::<Self>(&manifest)?);
+            }
+        }
+        points.sort_by_key(|point| point.created_at);
+        Ok(points)
+    }
+
+    /// Returns the path of the static files snapshot of this recovery point.
+    pub fn static_files_path(&self, data_dir: &ChainPath<DataDirPath>) -> PathBuf {
+        data_dir.recovery_points().join(&self.name).join(STATIC_FILES_DIR_NAME)
+    }
+
+    /// Replaces the static files of the datadir with the ones of this recovery point.
+    pub fn restore_static_files(&self, data_dir: &ChainPath<DataDirPath>) -> eyre::Result<()> {
+        let static_files = data_dir.static_files();
+        if static_files.is_dir() {
+            for entry in reth_fs_util::read_dir(&static_files)? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if entry.file_type()?.is_file() && parse_static_file_name(&name).is_some() {
+                    reth_fs_util::remove_file(entry.path())?;
+                }
+            }
+        }
+
+        snapshot_static_files(&self.static_files_path(data_dir), &static_files)?;
+        Ok(())
+    }
+}
+
+/// Snapshots all static files of the `from` directory into the `to` directory, and returns the
+/// names of the snapshotted files.
+///
+/// Files of the highest static file of every segment are copied, all others are hardlinked.
+fn snapshot_static_files(from: &Path, to: &Path) -> eyre::Result<Vec<String>> {
+    reth_fs_util::create_dir_all(to)?;
+
+    let mut files = Vec::new();
+    for entry in reth_fs_util::read_dir(from)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type()?.is_file() && parse_static_file_name(&name).is_some() {
+            files.push(name);
+        }
+    }
+    files.sort();
+
+    let highest = highest_static_files(&files);
+    for name in &files {
+        let (source, target) = (from.join(name), to.join(name));
+        let is_highest = parse_static_file_name(name)
+            .is_some_and(|(segment, start)| highest.get(&segment) == Some(&start));
+        if is_highest {
+            std::fs::copy(&source, &target)?;
+        } else {
+            hard_link_or_copy(&source, &target)?;
+        }
+    }
+
+    Ok(files)
+}
+
+/// Parses the segment and the start block of a static file name, ignoring any extension, e.g.
+/// `static_file_headers_0_499999.off`.
+fn parse_static_file_name(name: &str) -> Option<(StaticFileSegment, u64)> {
+    let jar_name = name.split('.').next()?;
+    StaticFileSegment::parse_filename(jar_name).map(|(segment, range)| (segment, range.start()))
+}
+
+/// Returns the start block of the highest static file of every segment.
+fn highest_static_files(files: &[String]) -> HashMap<StaticFileSegment, u64> {
+    let mut highest = HashMap::<StaticFileSegment, u64>::default();
+    for (segment, start) in files.iter().filter_map(|name| parse_static_file_name(name)) {
+        let entry = highest.entry(segment).or_default();
+        *entry = (*entry).max(start);
+    }
+    highest
+}
+
+/// Hardlinks the file, falling back to a copy if the target is on a different filesystem.
+fn hard_link_or_copy(source: &Path, target: &Path) -> io::Result<()> {
+    if std::fs::hard_link(source, target).is_err() {
+        std::fs::copy(source, target)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highest_static_file_per_segment() {
+        let files = [
+            "lock",
+            "static_file_headers_0_499999",
+            "static_file_headers_0_499999.off",
+            "static_file_headers_500000_999999.conf",
+            "static_file_receipts_0_499999",
+        ]
+        .map(String::from);
+
+        let highest = highest_static_files(&files);
+        assert_eq!(highest.get(&StaticFileSegment::Headers), Some(&500000));
+        assert_eq!(highest.get(&StaticFileSegment::Receipts), Some(&0));
+        assert_eq!(highest.get(&StaticFileSegment::Transactions), None);
+        assert_eq!(parse_static_file_name("lock"), None);
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    common::{AccessRights, CliNodeTypes, EnvironmentArgs},
+    recover::point::RecoveryPoint,
+};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use reth_db::init_db;
+use reth_db_api::{
+    database::Database,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use std::{
+    io::{self, Write},
+    sync::Arc,
+};
+use tracing::info;
+
+/// `reth recover restore` command
+///
+/// Restores the stage and prune checkpoints and the static files of a recovery point. Recovery
+/// points are created automatically before destructive operations such as `reth stage unwind`.
+///
+/// Note that data that was deleted from the database tables is not restored.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The name of the recovery point to restore. Lists all recovery points if omitted.
+    #[arg(long, value_name = "POINT")]
+    to: Option<String>,
+
+    /// Bypasses the interactive confirmation and restores the recovery point directly
+    #[arg(long)]
+    force: bool,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `restore` recovery command
+    pub async fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(
+        self,
+        _ctx: CliContext,
+    ) -> eyre::Result<()> {
+        let data_dir = self.env.datadir.clone().resolve_datadir(self.env.chain.chain());
+
+        let Some(name) = self.to.as_deref() else {
+            let points = RecoveryPoint::list(&data_dir)?;
+            if points.is_empty() {
+                println!("No recovery points found in {data_dir}");
+            }
+            for point in points {
+                println!(
+                    "{}\toperation: {}\tstatic files: {}",
+                    point.name,
+                    point.operation,
+                    point.static_files.len()
+                );
+            }
+            return Ok(())
+        };
+
+        let point = RecoveryPoint::load(&data_dir, name)?;
+
+        if !self.force {
+            // Ask for confirmation
+            print!(
+                "Are you sure you want to restore the recovery point {name} in {data_dir}? The current static files will be replaced. (y/N): "
+            );
+            // Flush the buffer to ensure the message is printed immediately
+            io::stdout().flush().unwrap();
+
+            let mut input = String::new();
+            io::stdin().read_line(&mut input).expect("Failed to read line");
+
+            if !input.trim().eq_ignore_ascii_case("y") {
+                println!("Restore aborted!");
+                return Ok(())
+            }
+        }
+
+        // Restore the checkpoints before the storage is opened, so the consistency check doesn't
+        // truncate the restored static files to the current checkpoints.
+        {
+            let db = init_db(data_dir.db(), self.env.db.database_args())?;
+            let tx = db.tx_mut()?;
+            tx.clear::<tables::StageCheckpoints>()?;
+            for (stage, checkpoint) in &point.stage_checkpoints {
+                tx.put::<tables::StageCheckpoints>(stage.clone(), *checkpoint)?;
+            }
+            tx.clear::<tables::PruneCheckpoints>()?;
+            for (segment, checkpoint) in &point.prune_checkpoints {
+                tx.put::<tables::PruneCheckpoints>(*segment, *checkpoint)?;
+            }
+            tx.commit()?;
+        }
+
+        point.restore_static_files(&data_dir)?;
+
+        // Opening the storage heals any remaining inconsistencies between the database and the
+        // restored static files.
+        self.env.init::<N>(AccessRights::RW)?;
+
+        info!(target: "reth::cli", name, "Restored recovery point");
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
@@ -1,5 +1,8 @@
 //! Database debugging tool
-use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use crate::{
+    common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs},
+    recover::point::RecoveryPoint,
+};
 use clap::Parser;
 use itertools::Itertools;
 use reth_chainspec::EthChainSpec;
@@ -30,6 +33,12 @@ pub struct Command<C: ChainSpecParser> {
     env: EnvironmentArgs<C>,
 
     stage: StageEnum,
+
+    /// Skip creating a recovery point before dropping the stage.
+    ///
+    /// See `reth recover restore`.
+    #[arg(long)]
+    no_recovery_point: bool,
 }
 
 impl<C: ChainSpecParser> Command<C> {
@@ -38,7 +47,12 @@ impl<C: ChainSpecParser> Command<C> {
     where
         C: ChainSpecParser<ChainSpec = N::ChainSpec>,
     {
-        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+        let Environment { provider_factory, data_dir, .. } =
+            self.env.init::<N>(AccessRights::RW)?;
+
+        if !self.no_recovery_point {
+            RecoveryPoint::create(&provider_factory, &data_dir, "drop-stage")?;
+        }
 
         let tool = DbTool::new(provider_factory)?;
 
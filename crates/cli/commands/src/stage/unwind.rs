@@ -2,6 +2,7 @@
 
 use crate::{
     common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs},
+    recover::point::RecoveryPoint,
     stage::CliNodeComponents,
 };
 use alloy_eips::BlockHashOrNumber;
@@ -15,14 +16,20 @@ use reth_db::DatabaseEnv;
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_evm::ConfigureEvm;
 use reth_exex::ExExManagerHandle;
+use reth_node_api::NodeTypesWithDBAdapter;
+use reth_node_core::args::StageEnum;
 use reth_provider::{
-    providers::ProviderNodeTypes, BlockExecutionWriter, BlockNumReader, ChainStateBlockReader,
-    ChainStateBlockWriter, ProviderFactory, StaticFileProviderFactory, StorageLocation,
+    providers::ProviderNodeTypes, writer::UnifiedStorageWriter, BlockExecutionWriter,
+    BlockNumReader, ChainStateBlockReader, ChainStateBlockWriter, DatabaseProviderFactory,
+    ProviderFactory, StageCheckpointReader, StageCheckpointWriter, StaticFileProviderFactory,
+    StorageLocation,
 };
 use reth_stages::{
     sets::{DefaultStages, OfflineStages},
-    stages::ExecutionStage,
-    ExecutionStageThresholds, Pipeline, StageSet,
+    stages::{
+        ExecutionStage, IndexAccountHistoryStage, IndexStorageHistoryStage, TransactionLookupStage,
+    },
+    ExecutionStageThresholds, Pipeline, Stage, StageSet, UnwindInput, UnwindOutput,
 };
 use reth_static_file::StaticFileProducer;
 use std::sync::Arc;
@@ -42,6 +49,21 @@ pub struct Command<C: ChainSpecParser> {
     /// unwound.
     #[arg(long)]
     offline: bool,
+
+    /// Only unwind the data of the given stage, e.g. a single index that is suspected to be
+    /// corrupt, and leave the data of all other stages untouched.
+    ///
+    /// Only stages whose data no other stage depends on are supported: `tx-lookup`,
+    /// `account-history` and `storage-history`. The stage is rebuilt from the target block on the
+    /// next run of the pipeline, or with `reth stage run`.
+    #[arg(long, value_enum, value_name = "STAGE", conflicts_with = "offline")]
+    only: Option<StageEnum>,
+
+    /// Skip creating a recovery point before unwinding.
+    ///
+    /// See `reth recover restore`.
+    #[arg(long)]
+    no_recovery_point: bool,
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
@@ -54,10 +76,19 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         Comp: CliNodeComponents<N>,
         F: FnOnce(Arc<C::ChainSpec>) -> Comp,
     {
-        let Environment { provider_factory, config, .. } = self.env.init::<N>(AccessRights::RW)?;
+        let Environment { provider_factory, config, data_dir } =
+            self.env.init::<N>(AccessRights::RW)?;
 
         let target = self.command.unwind_target(provider_factory.clone())?;
 
+        if !self.no_recovery_point {
+            RecoveryPoint::create(&provider_factory, &data_dir, "unwind")?;
+        }
+
+        if let Some(stage) = self.only {
+            return unwind_stage::<N>(stage, target, &config, &provider_factory)
+        }
+
         let components = components(provider_factory.chain_spec());
 
         let highest_static_file_block = provider_factory
@@ -171,6 +202,60 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
     }
 }
 
+/// Unwinds only the data of the given stage to the target block and lowers its checkpoint
+/// accordingly, so that the pipeline rebuilds just this stage on its next run.
+fn unwind_stage<N: CliNodeTypes>(
+    stage: StageEnum,
+    target: u64,
+    config: &Config,
+    provider_factory: &ProviderFactory<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>,
+) -> eyre::Result<()> {
+    let etl_config = config.stages.etl.clone();
+    let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+
+    let mut unwind_stage: Box<dyn Stage<_>> = match stage {
+        StageEnum::TxLookup => Box::new(TransactionLookupStage::new(
+            config.stages.transaction_lookup,
+            etl_config,
+            prune_modes.transaction_lookup,
+        )),
+        StageEnum::AccountHistory => Box::new(IndexAccountHistoryStage::new(
+            config.stages.index_account_history,
+            etl_config,
+            prune_modes.account_history,
+        )),
+        StageEnum::StorageHistory => Box::new(IndexStorageHistoryStage::new(
+            config.stages.index_storage_history,
+            etl_config,
+            prune_modes.storage_history,
+        )),
+        stage => eyre::bail!(
+            "Stage {stage} can't be unwound on its own because other stages depend on its data"
+        ),
+    };
+
+    let provider_rw = provider_factory.database_provider_rw()?;
+    let stage_id = unwind_stage.id();
+    let checkpoint = provider_rw.get_stage_checkpoint(stage_id)?.unwrap_or_default();
+    if checkpoint.block_number <= target {
+        info!(target: "reth::cli", stage = %stage_id, checkpoint = checkpoint.block_number, ?target, "Stage is already at or below the target");
+        return Ok(())
+    }
+
+    info!(target: "reth::cli", stage = %stage_id, checkpoint = checkpoint.block_number, ?target, "Executing a stage unwind.");
+    let mut input = UnwindInput { checkpoint, unwind_to: target, bad_block: None };
+    while input.checkpoint.block_number > target {
+        let UnwindOutput { checkpoint } = unwind_stage.unwind(&provider_rw, input)?;
+        provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
+        input.checkpoint = checkpoint;
+    }
+    UnifiedStorageWriter::commit_unwind(provider_rw)?;
+
+    info!(target: "reth::cli", stage = %stage_id, ?target, "Unwound stage");
+
+    Ok(())
+}
+
 impl<C: ChainSpecParser> Command<C> {
     /// Return the underlying chain being used to run this command
     pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
@@ -243,4 +328,30 @@ mod tests {
         ]);
         assert_eq!(cmd.command, Subcommands::NumBlocks { amount: 100 });
     }
+
+    #[test]
+    fn parse_unwind_only() {
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--only",
+            "account-history",
+            "to-block",
+            "100",
+        ]);
+        assert_eq!(cmd.only, Some(StageEnum::AccountHistory));
+
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "--datadir",
+            "dir",
+            "--only",
+            "tx-lookup",
+            "--offline",
+            "num-blocks",
+            "100",
+        ])
+        .is_err());
+    }
 }
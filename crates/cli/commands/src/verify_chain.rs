@@ -0,0 +1,350 @@
+//! Re-verify the canonical chain by re-executing it in parallel workers.
+
+use crate::common::{
+    AccessRights, CliComponentsBuilder, CliNodeComponents, CliNodeTypes, Environment,
+    EnvironmentArgs,
+};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::BlockNumber;
+use clap::{builder::RangedU64ValueParser, Parser};
+use eyre::bail;
+use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_consensus::FullConsensus;
+use reth_evm::{execute::Executor, ConfigureEvm};
+use reth_primitives_traits::{format_gas_throughput, GotExpected};
+use reth_provider::{
+    BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
+    HashedPostStateProvider, StateRootProvider, StaticFileProviderFactory, TransactionVariant,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_stages::stages::calculate_gas_used_from_headers;
+use serde::Serialize;
+use std::{
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{sync::mpsc, task::JoinSet};
+use tracing::*;
+
+/// `reth verify-chain` command
+///
+/// Re-executes the canonical chain in parallel workers and verifies the receipts and state roots
+/// of the blocks against the stored headers.
+///
+/// Every worker verifies a contiguous range of blocks, starting from the historical state before
+/// its first block, which is reconstructed from the changesets. Workers therefore don't depend on
+/// each other and a failure doesn't stop the verification of the remaining blocks.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to verify.
+    #[arg(long, default_value = "1", value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    from: u64,
+
+    /// The last block to verify. Defaults to the latest block.
+    #[arg(long)]
+    to: Option<u64>,
+
+    /// Number of workers verifying blocks in parallel.
+    #[arg(long, default_value = "10", value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    workers: u64,
+
+    /// Number of blocks after which the state root is verified.
+    ///
+    /// The state root is always verified at the end of the range of a worker. An interval of 1
+    /// verifies the state root of every block, which is considerably slower.
+    #[arg(long, default_value = "100", value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    state_root_interval: u64,
+
+    /// Write a JSON report of the verification to the given path.
+    #[arg(long, value_name = "PATH")]
+    report: Option<PathBuf>,
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
+    /// Execute `verify-chain` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+
+        let provider = provider_factory.database_provider_ro()?;
+        let components = components(provider_factory.chain_spec());
+
+        let from = self.from;
+        let to = self.to.unwrap_or(provider.best_block_number()?);
+        if from > to {
+            bail!("invalid block range {from}..={to}")
+        }
+
+        let total_gas =
+            calculate_gas_used_from_headers(&provider_factory.static_file_provider(), from..=to)?;
+
+        let db_at = {
+            let provider_factory = provider_factory.clone();
+            move |block_number: u64| {
+                eyre::Ok(StateProviderDatabase(
+                    provider_factory.history_by_block_number(block_number)?,
+                ))
+            }
+        };
+
+        let (stats_tx, mut stats_rx) = mpsc::unbounded_channel();
+
+        let mut tasks = JoinSet::new();
+        for shard in shards(from..=to, self.workers) {
+            let provider_factory = provider_factory.clone();
+            let evm_config = components.evm_config().clone();
+            let consensus = components.consensus().clone();
+            let db_at = db_at.clone();
+            let stats_tx = stats_tx.clone();
+            let state_root_interval = self.state_root_interval;
+            tasks.spawn_blocking(move || {
+                let mut failures = Vec::new();
+                let mut executor = evm_config.batch_executor(db_at(shard.start() - 1)?);
+                // the first block whose changes are accumulated in the executor
+                let mut segment_start = *shard.start();
+
+                for number in shard.clone() {
+                    let block = provider_factory
+                        .recovered_block(number.into(), TransactionVariant::NoHash)?
+                        .ok_or_else(|| eyre::eyre!("block {number} not found"))?;
+
+                    let failure = match executor.execute_one(&block) {
+                        Ok(result) => consensus
+                            .validate_block_post_execution(&block, &result)
+                            .err()
+                            .map(|err| (FailureKind::PostExecution, err.to_string())),
+                        Err(err) => Some((FailureKind::Execution, err.to_string())),
+                    };
+                    let _ = stats_tx.send(block.gas_used());
+
+                    if let Some((kind, error)) = failure {
+                        failures.push(VerificationFailure { block: number, kind, error });
+                        // continue with the correct state after the failed block
+                        executor = evm_config.batch_executor(db_at(number)?);
+                        segment_start = number + 1;
+                        continue
+                    }
+
+                    if number == *shard.end() ||
+                        (number - segment_start + 1) % state_root_interval == 0
+                    {
+                        // the accumulated changes are applied on top of the state before the
+                        // segment, which yields the state after this block
+                        let state = executor.into_state();
+                        let state_provider =
+                            provider_factory.history_by_block_number(segment_start - 1)?;
+                        let hashed_state = state_provider.hashed_post_state(&state.bundle_state);
+                        let state_root = state_provider.state_root(hashed_state)?;
+                        if state_root != block.state_root() {
+                            let mismatch =
+                                GotExpected { got: state_root, expected: block.state_root() };
+                            failures.push(VerificationFailure {
+                                block: number,
+                                kind: FailureKind::StateRoot,
+                                error: format!("state root mismatch: {mismatch}"),
+                            });
+                        }
+
+                        executor = evm_config.batch_executor(db_at(number)?);
+                        segment_start = number + 1;
+                    }
+                }
+
+                eyre::Ok(failures)
+            });
+        }
+        drop(stats_tx);
+
+        let instant = Instant::now();
+        let mut verified_blocks = 0;
+        let mut verified_gas = 0;
+        let mut failures = Vec::new();
+
+        let mut last_logged_gas = 0;
+        let mut last_logged_blocks = 0;
+        let mut last_logged_time = Instant::now();
+
+        let mut interval = tokio::time::interval(Duration::from_secs(10));
+
+        loop {
+            tokio::select! {
+                Some(gas_used) = stats_rx.recv() => {
+                    verified_blocks += 1;
+                    verified_gas += gas_used;
+                }
+                result = tasks.join_next() => {
+                    let Some(result) = result else { break };
+                    let shard_failures = result??;
+                    for failure in &shard_failures {
+                        error!(
+                            block = failure.block,
+                            kind = ?failure.kind,
+                            error = %failure.error,
+                            "Verification failed"
+                        );
+                    }
+                    failures.extend(shard_failures);
+                }
+                _ = interval.tick() => {
+                    let blocks_verified = verified_blocks - last_logged_blocks;
+                    let gas_verified = verified_gas - last_logged_gas;
+
+                    if blocks_verified > 0 {
+                        let progress = 100.0 * verified_gas as f64 / total_gas as f64;
+                        info!(
+                            throughput=?format_gas_throughput(gas_verified, last_logged_time.elapsed()),
+                            progress=format!("{progress:.2}%"),
+                            failures=failures.len(),
+                            "Verified {blocks_verified} blocks"
+                        );
+                    }
+
+                    last_logged_blocks = verified_blocks;
+                    last_logged_gas = verified_gas;
+                    last_logged_time = Instant::now();
+                }
+            }
+        }
+        // collect the stats of the last blocks
+        while let Ok(gas_used) = stats_rx.try_recv() {
+            verified_blocks += 1;
+            verified_gas += gas_used;
+        }
+
+        failures.sort_by_key(|failure| failure.block);
+        let report = VerificationReport {
+            from,
+            to,
+            workers: self.workers,
+            state_root_interval: self.state_root_interval,
+            verified_blocks,
+            verified_gas,
+            failures,
+        };
+        if let Some(path) = &self.report {
+            let mut json = serde_json::to_string_pretty(&report)?;
+            json.push('\n');
+            reth_fs_util::write(path, json)?;
+            info!(path = %path.display(), "Wrote verification report");
+        }
+
+        if !report.failures.is_empty() {
+            bail!("{} of {} blocks failed verification", report.failures.len(), verified_blocks)
+        }
+
+        info!(
+            start_block = from,
+            end_block = to,
+            throughput=?format_gas_throughput(verified_gas, instant.elapsed()),
+            "Verified chain"
+        );
+
+        Ok(())
+    }
+}
+
+/// The result of a `reth verify-chain` run.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationReport {
+    /// The first verified block.
+    pub from: BlockNumber,
+    /// The last verified block.
+    pub to: BlockNumber,
+    /// The number of workers.
+    pub workers: u64,
+    /// The number of blocks after which the state root was verified.
+    pub state_root_interval: u64,
+    /// The number of re-executed blocks.
+    pub verified_blocks: u64,
+    /// The gas used by the re-executed blocks.
+    pub verified_gas: u64,
+    /// The blocks that failed verification, ordered by block number.
+    pub failures: Vec<VerificationFailure>,
+}
+
+/// A block that failed verification.
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationFailure {
+    /// The number of the block.
+    pub block: BlockNumber,
+    /// The check that failed.
+    pub kind: FailureKind,
+    /// A description of the failure.
+    pub error: String,
+}
+
+/// The check a block failed.
+///
+/// A state root failure is reported for the last block of the interval the mismatch was detected
+/// in, the block that caused it can be any block of that interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FailureKind {
+    /// The block couldn't be executed.
+    Execution,
+    /// The receipts, gas used or requests of the block don't match its header.
+    PostExecution,
+    /// The state root after the block doesn't match its header.
+    StateRoot,
+}
+
+/// Splits the range into at most `workers` contiguous, non-empty shards.
+fn shards(range: RangeInclusive<u64>, workers: u64) -> Vec<RangeInclusive<u64>> {
+    let (from, to) = range.into_inner();
+    let blocks_per_worker = (to - from + 1).div_ceil(workers);
+    (from..=to)
+        .step_by(blocks_per_worker as usize)
+        .map(|start| start..=(start + blocks_per_worker - 1).min(to))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_args() {
+        let cmd = Command::<EthereumChainSpecParser>::parse_from([
+            "reth",
+            "--to",
+            "100",
+            "--workers",
+            "4",
+        ]);
+        assert_eq!(cmd.from, 1);
+        assert_eq!(cmd.to, Some(100));
+        assert_eq!(cmd.workers, 4);
+        assert_eq!(cmd.state_root_interval, 100);
+
+        assert!(
+            Command::<EthereumChainSpecParser>::try_parse_from(["reth", "--workers", "0"]).is_err()
+        );
+        assert!(
+            Command::<EthereumChainSpecParser>::try_parse_from(["reth", "--from", "0"]).is_err()
+        );
+    }
+
+    #[test]
+    fn split_into_shards() {
+        assert_eq!(shards(1..=10, 3), [1..=4, 5..=8, 9..=10]);
+        assert_eq!(shards(1..=2, 10), [1..=1, 2..=2]);
+        assert_eq!(shards(5..=5, 1), [5..=5]);
+    }
+}
@@ -0,0 +1,88 @@
+//! Command that verifies a chain data export against its manifest.
+
+use crate::manifest::ExportManifest;
+use alloy_primitives::Address;
+use clap::Parser;
+use eyre::bail;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// `reth verify-manifest` command
+///
+/// Checks the signature of the manifest and the sizes and hashes of all files it lists. The files
+/// are expected in the directory of the manifest.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path of the manifest.
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+
+    /// The address that must have signed the manifest.
+    #[arg(long, value_name = "ADDRESS")]
+    signer: Option<Address>,
+
+    /// Accept manifests without a signature.
+    #[arg(long, conflicts_with = "signer")]
+    allow_unsigned: bool,
+}
+
+impl Command {
+    /// Execute `verify-manifest` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let manifest = ExportManifest::read(&self.path)?;
+        info!(
+            target: "reth::cli",
+            kind = ?manifest.kind,
+            chain_id = manifest.chain_id,
+            first_block = manifest.blocks.first,
+            last_block = manifest.blocks.last,
+            reth_version = %manifest.reth_version,
+            files = manifest.files.len(),
+            "Verifying manifest"
+        );
+
+        match (manifest.verify_signature()?, self.signer) {
+            (Some(signer), Some(expected)) if signer != expected => {
+                bail!("manifest is signed by {signer}, expected {expected}")
+            }
+            (Some(signer), _) => info!(target: "reth::cli", %signer, "Verified signature"),
+            (None, _) if self.allow_unsigned => {
+                warn!(target: "reth::cli", "Manifest is not signed")
+            }
+            (None, _) => bail!("manifest is not signed, use --allow-unsigned to accept it"),
+        }
+
+        let dir = self.path.parent().map(PathBuf::from).unwrap_or_default();
+        let mismatches = manifest.verify_files(&dir);
+        if !mismatches.is_empty() {
+            for mismatch in &mismatches {
+                warn!(target: "reth::cli", "{mismatch}");
+            }
+            bail!("{} of {} files failed verification", mismatches.len(), manifest.files.len())
+        }
+
+        info!(target: "reth::cli", files = manifest.files.len(), "Verified all files");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_args() {
+        let cmd = Command::parse_from(["reth", "manifest.json"]);
+        assert_eq!(cmd.path, PathBuf::from("manifest.json"));
+        assert!(!cmd.allow_unsigned);
+
+        assert!(Command::try_parse_from([
+            "reth",
+            "manifest.json",
+            "--signer",
+            "0x0000000000000000000000000000000000000001",
+            "--allow-unsigned"
+        ])
+        .is_err());
+    }
+}
@@ -29,6 +29,9 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration of the firehose, which is disabled if unset.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub firehose: Option<FirehoseConfig>,
 }
 
 impl Config {
@@ -42,21 +45,26 @@ impl Config {
 impl Config {
     /// Load a [`Config`] from a specified path.
     ///
+    /// The file is parsed with [`Config::parse_strict`], so unknown keys and invalid values are
+    /// rejected.
+    ///
     /// A new configuration file is created with default values if none
     /// exists.
     pub fn from_path(path: impl AsRef<Path>) -> eyre::Result<Self> {
         let path = path.as_ref();
         match std::fs::read_to_string(path) {
-            Ok(cfg_string) => {
-                toml::from_str(&cfg_string).map_err(|e| eyre::eyre!("Failed to parse TOML: {e}"))
-            }
+            Ok(cfg_string) => Self::parse_strict(&cfg_string).map_err(|errors| {
+                let errors = errors.iter().map(|e| format!("\n  - {e}")).collect::<String>();
+                eyre::eyre!("Invalid configuration:{errors}")
+            }),
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
                 if let Some(parent) = path.parent() {
                     std::fs::create_dir_all(parent)
                         .map_err(|e| eyre::eyre!("Failed to create directory: {e}"))?;
                 }
                 let cfg = Self::default();
-                let s = toml::to_string_pretty(&cfg)
+                let s = cfg
+                    .to_toml_string()
                     .map_err(|e| eyre::eyre!("Failed to serialize to TOML: {e}"))?;
                 std::fs::write(path, s)
                     .map_err(|e| eyre::eyre!("Failed to write configuration file: {e}"))?;
@@ -90,7 +98,7 @@ impl Config {
 
         std::fs::write(
             path,
-            toml::to_string(self)
+            self.to_toml_string()
                 .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?,
         )
     }
@@ -278,6 +286,12 @@ pub struct ExecutionConfig {
         )
     )]
     pub max_duration: Option<Duration>,
+    /// Whether deep unwinds may resync from genesis, i.e. restore the genesis state and replay all
+    /// blocks up to the unwind target, instead of reverting the changesets above it, if that is
+    /// estimated to be cheaper.
+    ///
+    /// This rewrites the entire plain state during the unwind, so it's disabled by default.
+    pub genesis_resync_unwind: bool,
 }
 
 impl Default for ExecutionConfig {
@@ -289,6 +303,7 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            genesis_resync_unwind: false,
         }
     }
 }
@@ -487,6 +502,51 @@ impl PruneConfig {
     }
 }
 
+/// Firehose configuration.
+///
+/// The firehose publishes transaction pool and canonical chain events to an external message
+/// queue, see `reth-node-firehose` for the schema of the events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct FirehoseConfig {
+    /// The message queue events are published to, either a NATS server (`nats://host:port`) or a
+    /// Kafka broker (`kafka://host:port`).
+    ///
+    /// Kafka records are produced to partition 0 of their topic, the broker is used to discover
+    /// the leaders of these partitions.
+    pub url: Url,
+    /// The prefix of the NATS subjects or Kafka topics, events are published to
+    /// `<prefix>.transactions`, `<prefix>.blocks` and `<prefix>.reorgs`.
+    pub prefix: String,
+    /// Whether to publish transaction pool events.
+    pub transactions: bool,
+    /// Whether to publish canonical chain events.
+    pub blocks: bool,
+    /// The maximum number of events that are published at once.
+    pub batch_size: usize,
+    /// The maximum time events are held back to fill a batch.
+    #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
+    pub flush_interval: Duration,
+    /// The maximum number of events that are buffered while the message queue is slow or
+    /// unreachable. Further events are dropped until there's room in the buffer again.
+    pub buffer_size: usize,
+}
+
+impl Default for FirehoseConfig {
+    fn default() -> Self {
+        Self {
+            url: Url::parse("nats://127.0.0.1:4222").expect("valid url"),
+            prefix: "reth".to_string(),
+            transactions: true,
+            blocks: true,
+            batch_size: 100,
+            flush_interval: Duration::from_secs(1),
+            buffer_size: 10_000,
+        }
+    }
+}
+
 /// Helper type to support older versions of Duration deserialization.
 #[cfg(feature = "serde")]
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
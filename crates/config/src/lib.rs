@@ -9,4 +9,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 pub mod config;
-pub use config::{BodiesConfig, Config, PruneConfig};
+pub use config::{BodiesConfig, Config, FirehoseConfig, PruneConfig};
+
+#[cfg(feature = "serde")]
+pub mod schema;
+#[cfg(feature = "serde")]
+pub use schema::{ConfigChange, ConfigError, CONFIG_VERSION};
@@ -0,0 +1,425 @@
+//! Versioned and strictly validated schema of the configuration file.
+//!
+//! Unlike plain deserialization, which silently ignores unknown keys, [`Config::parse_strict`]
+//! rejects every key that isn't part of the schema, suggesting the closest known key, and checks
+//! the semantic constraints of [`Config::validate`].
+
+use crate::{config::StageConfig, Config};
+use std::{collections::BTreeMap, fmt};
+use toml::{Table, Value};
+
+/// The latest version of the configuration file schema.
+pub const CONFIG_VERSION: u64 = 1;
+
+/// The top-level key that holds the version of the configuration file schema.
+///
+/// The key is optional, files without it are treated as the latest version.
+pub const CONFIG_VERSION_KEY: &str = "version";
+
+/// Keys that are accepted in place of other keys, see the `serde(alias)` attributes of the
+/// configuration types.
+const KEY_ALIASES: &[(&str, &str)] =
+    &[("parts", "segments"), ("connect_trusted_nodes_only", "trusted_nodes_only")];
+
+/// The maximum edit distance of a known key to an unknown key to be suggested.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
+/// An error in a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConfigError {
+    /// The file is not valid TOML or doesn't match the types of the schema.
+    #[error("failed to parse TOML: {0}")]
+    Parse(String),
+    /// The file was written for a newer version of the schema.
+    #[error("unsupported config version {0}, the latest supported version is {CONFIG_VERSION}")]
+    UnsupportedVersion(i64),
+    /// The key is not part of the schema.
+    #[error("unknown key `{key}`{}", did_you_mean(.suggestion))]
+    UnknownKey {
+        /// The dotted path of the key.
+        key: String,
+        /// The closest known key, if any is similar enough.
+        suggestion: Option<String>,
+    },
+    /// The value of the key violates a constraint of the schema.
+    #[error("invalid value of `{key}`: {reason}")]
+    InvalidValue {
+        /// The dotted path of the key.
+        key: String,
+        /// Why the value is invalid.
+        reason: String,
+    },
+}
+
+impl ConfigError {
+    fn invalid(key: &str, reason: impl Into<String>) -> Self {
+        Self::InvalidValue { key: key.to_string(), reason: reason.into() }
+    }
+}
+
+/// A value of a configuration that differs from the value of another configuration, usually the
+/// default one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    /// The dotted path of the key, e.g. `stages.headers.commit_threshold`.
+    pub key: String,
+    /// The value in the base configuration, if the key is set there.
+    pub default: Option<String>,
+    /// The value in the configuration, if the key is set there.
+    pub value: Option<String>,
+}
+
+impl fmt::Display for ConfigChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let default = self.default.as_deref().unwrap_or("<unset>");
+        let value = self.value.as_deref().unwrap_or("<unset>");
+        write!(f, "{}: {default} -> {value}", self.key)
+    }
+}
+
+impl Config {
+    /// Parses a configuration from a TOML string, rejecting unknown keys and invalid values.
+    ///
+    /// Returns all errors that were found, not only the first one.
+    pub fn parse_strict(s: &str) -> Result<Self, Vec<ConfigError>> {
+        let mut table: Table = toml::from_str(s).map_err(|e| vec![parse_error(e)])?;
+
+        if let Some(version) = table.remove(CONFIG_VERSION_KEY) {
+            match version.as_integer() {
+                Some(version) if (1..=CONFIG_VERSION as i64).contains(&version) => {}
+                Some(version) => return Err(vec![ConfigError::UnsupportedVersion(version)]),
+                None => {
+                    return Err(vec![ConfigError::invalid(
+                        CONFIG_VERSION_KEY,
+                        "expected a positive integer",
+                    )])
+                }
+            }
+        }
+
+        let config: Self = toml::from_str(s).map_err(|e| vec![parse_error(e)])?;
+
+        // every key of the file must show up in the serialized config, either because it was
+        // deserialized into it or because it's a key of the default config
+        let effective = config.to_toml_table().map_err(|e| vec![e])?;
+        let default = Self::default().to_toml_table().map_err(|e| vec![e])?;
+        let mut errors = Vec::new();
+        collect_unknown_keys(&table, &effective, Some(&default), "", &mut errors);
+
+        if let Err(invalid) = config.validate() {
+            errors.extend(invalid);
+        }
+
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Checks the semantic constraints of the configuration that can't be expressed by its types.
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+        let mut non_zero = |key: &str, value: u64| {
+            if value == 0 {
+                errors.push(ConfigError::invalid(key, "must be greater than zero"));
+            }
+        };
+
+        let StageConfig { headers, bodies, .. } = &self.stages;
+        non_zero("stages.headers.downloader_request_limit", headers.downloader_request_limit);
+        non_zero("stages.headers.commit_threshold", headers.commit_threshold);
+        non_zero(
+            "stages.headers.downloader_max_buffered_responses",
+            headers.downloader_max_buffered_responses as u64,
+        );
+        non_zero("stages.bodies.downloader_request_limit", bodies.downloader_request_limit);
+        non_zero(
+            "stages.bodies.downloader_stream_batch_size",
+            bodies.downloader_stream_batch_size as u64,
+        );
+        non_zero(
+            "stages.sender_recovery.commit_threshold",
+            self.stages.sender_recovery.commit_threshold,
+        );
+        non_zero("stages.prune.commit_threshold", self.stages.prune.commit_threshold as u64);
+        non_zero("stages.etl.file_size", self.stages.etl.file_size as u64);
+        if let Some(prune) = &self.prune {
+            non_zero("prune.block_interval", prune.block_interval as u64);
+        }
+        if let Some(firehose) = &self.firehose {
+            non_zero("firehose.batch_size", firehose.batch_size as u64);
+            non_zero("firehose.buffer_size", firehose.buffer_size as u64);
+            non_zero("firehose.flush_interval", firehose.flush_interval.as_millis() as u64);
+        }
+
+        if headers.downloader_min_concurrent_requests > headers.downloader_max_concurrent_requests {
+            errors.push(ConfigError::invalid(
+                "stages.headers.downloader_min_concurrent_requests",
+                "must not exceed `downloader_max_concurrent_requests`",
+            ));
+        }
+        if bodies.downloader_min_concurrent_requests > bodies.downloader_max_concurrent_requests {
+            errors.push(ConfigError::invalid(
+                "stages.bodies.downloader_min_concurrent_requests",
+                "must not exceed `downloader_max_concurrent_requests`",
+            ));
+        }
+        if self.stages.era.path.is_some() && self.stages.era.url.is_some() {
+            errors.push(ConfigError::invalid("stages.era.path", "conflicts with `url`"));
+        }
+        if let Some(firehose) = &self.firehose {
+            if !matches!(firehose.url.scheme(), "nats" | "kafka") {
+                errors.push(ConfigError::invalid(
+                    "firehose.url",
+                    "expected a `nats://` or `kafka://` url",
+                ));
+            }
+            if firehose.url.host_str().is_none() {
+                errors.push(ConfigError::invalid("firehose.url", "missing host"));
+            }
+            if firehose.prefix.is_empty() ||
+                !firehose.prefix.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+            {
+                errors.push(ConfigError::invalid(
+                    "firehose.prefix",
+                    "expected a non-empty name of ASCII letters, digits, `.`, `_` and `-`",
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns every value of this configuration that differs from the `base` configuration,
+    /// ordered by key.
+    pub fn diff(&self, base: &Self) -> Vec<ConfigChange> {
+        let mut values = BTreeMap::new();
+        let mut defaults = BTreeMap::new();
+        if let Ok(table) = self.to_toml_table() {
+            flatten(&table, "", &mut values);
+        }
+        if let Ok(table) = base.to_toml_table() {
+            flatten(&table, "", &mut defaults);
+        }
+
+        let mut keys = values.keys().chain(defaults.keys()).cloned().collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.dedup();
+
+        keys.into_iter()
+            .filter_map(|key| {
+                let value = values.remove(&key);
+                let default = defaults.remove(&key);
+                (value != default).then_some(ConfigChange { key, default, value })
+            })
+            .collect()
+    }
+
+    /// Serializes the configuration to a TOML string that starts with the schema version.
+    pub fn to_toml_string(&self) -> Result<String, toml::ser::Error> {
+        let config = toml::to_string_pretty(self)?;
+        Ok(format!("{CONFIG_VERSION_KEY} = {CONFIG_VERSION}\n\n{config}"))
+    }
+
+    fn to_toml_table(&self) -> Result<Table, ConfigError> {
+        Table::try_from(self).map_err(|e| ConfigError::Parse(e.to_string()))
+    }
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    suggestion.as_ref().map(|key| format!(", did you mean `{key}`?")).unwrap_or_default()
+}
+
+fn parse_error(err: toml::de::Error) -> ConfigError {
+    ConfigError::Parse(err.to_string())
+}
+
+/// Returns the value of the `key` in the `table`, resolving aliases and ignoring the case, which
+/// isn't significant for e.g. addresses used as keys.
+fn find_key<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    let key = KEY_ALIASES.iter().find(|(alias, _)| *alias == key).map_or(key, |(_, key)| key);
+    table.iter().find(|(known, _)| known.eq_ignore_ascii_case(key)).map(|(_, value)| value)
+}
+
+/// Records an error for every key of the `user` table that is neither in the `known` nor in the
+/// `default` table, recursing into nested tables.
+fn collect_unknown_keys(
+    user: &Table,
+    known: &Table,
+    default: Option<&Table>,
+    path: &str,
+    errors: &mut Vec<ConfigError>,
+) {
+    for (key, value) in user {
+        let default_value = default.and_then(|default| find_key(default, key));
+        let Some(known_value) = find_key(known, key).or(default_value) else {
+            let candidates = known.keys().chain(default.into_iter().flat_map(|d| d.keys()));
+            errors.push(ConfigError::UnknownKey {
+                key: join_key(path, key),
+                suggestion: closest_key(key, candidates).map(|known| join_key(path, known)),
+            });
+            continue
+        };
+
+        // values like durations may be given as tables but are serialized as strings, so only
+        // recurse if both sides are tables
+        if let (Value::Table(user), Value::Table(known)) = (value, known_value) {
+            collect_unknown_keys(
+                user,
+                known,
+                default_value.and_then(Value::as_table),
+                &join_key(path, key),
+                errors,
+            );
+        }
+    }
+}
+
+/// Returns the candidate with the smallest edit distance to the `key`, if it's close enough.
+fn closest_key<'a>(key: &str, candidates: impl Iterator<Item = &'a String>) -> Option<&'a str> {
+    candidates
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE && *distance < key.len())
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// Returns the Levenshtein distance of the two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Flattens the nested tables into dotted keys and their TOML encoded values.
+fn flatten(table: &Table, path: &str, values: &mut BTreeMap<String, String>) {
+    for (key, value) in table {
+        let key = join_key(path, key);
+        match value {
+            Value::Table(table) => flatten(table, &key, values),
+            value => {
+                values.insert(key, value.to_string());
+            }
+        }
+    }
+}
+
+fn join_key(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{path}.{key}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_unknown_keys_with_suggestion() {
+        let s = r"
+version = 1
+
+[stages.headers]
+commit_treshold = 100
+foo = 1
+
+[peers]
+connect_trusted_nodes_only = true
+";
+        let errors = Config::parse_strict(s).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ConfigError::UnknownKey {
+                    key: "stages.headers.commit_treshold".to_string(),
+                    suggestion: Some("stages.headers.commit_threshold".to_string()),
+                },
+                ConfigError::UnknownKey { key: "stages.headers.foo".to_string(), suggestion: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_version_and_invalid_values() {
+        let errors = Config::parse_strict("version = 2").unwrap_err();
+        assert_eq!(errors, vec![ConfigError::UnsupportedVersion(2)]);
+
+        let s = r"
+[stages.bodies]
+downloader_min_concurrent_requests = 10
+downloader_max_concurrent_requests = 5
+";
+        let errors = Config::parse_strict(s).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![ConfigError::invalid(
+                "stages.bodies.downloader_min_concurrent_requests",
+                "must not exceed `downloader_max_concurrent_requests`",
+            )]
+        );
+    }
+
+    #[test]
+    fn roundtrip_and_diff() {
+        let mut config = Config::default();
+        config.stages.headers.commit_threshold = 42;
+        let s = config.to_toml_string().unwrap();
+        assert!(s.starts_with("version = 1"));
+        assert_eq!(Config::parse_strict(&s).unwrap(), config);
+
+        assert_eq!(
+            config.diff(&Config::default()),
+            vec![ConfigChange {
+                key: "stages.headers.commit_threshold".to_string(),
+                default: Some("10000".to_string()),
+                value: Some("42".to_string()),
+            }]
+        );
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn firehose_section() {
+        let s = r#"
+[firehose]
+url = "kafka://localhost:9092"
+flush_interval = "500ms"
+"#;
+        let firehose = Config::parse_strict(s).unwrap().firehose.unwrap();
+        assert_eq!(firehose.url.as_str(), "kafka://localhost:9092");
+        assert_eq!(firehose.flush_interval, std::time::Duration::from_millis(500));
+        assert_eq!(firehose.batch_size, 100);
+
+        let s = r#"
+[firehose]
+url = "amqp://localhost:5672"
+topic = "reth"
+batch_size = 0
+"#;
+        let errors = Config::parse_strict(s).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ConfigError::UnknownKey { key: "firehose.topic".to_string(), suggestion: None },
+                ConfigError::invalid("firehose.batch_size", "must be greater than zero"),
+                ConfigError::invalid("firehose.url", "expected a `nats://` or `kafka://` url"),
+            ]
+        );
+    }
+}
@@ -10,4 +10,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 /// Collection of consensus validation methods.
-pub mod validation;
+///
+/// The methods are defined in [`reth_consensus::validation`] and re-exported here.
+pub mod validation {
+    pub use reth_consensus::validation::*;
+}
+
+/// The arithmetic of the header validation rules, usable in `no_std` verifiers.
+///
+/// The methods are defined in [`reth_consensus::rules`] and re-exported here.
+pub mod rules {
+    pub use reth_consensus::rules::*;
+}
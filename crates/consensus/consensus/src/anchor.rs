@@ -0,0 +1,166 @@
+//! Validation of the boundaries of a downloaded batch of headers.
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{BlockHash, BlockNumber};
+use reth_primitives_traits::{BlockHeader, GotExpected, SealedHeader};
+
+/// The boundary of a header batch that failed [`validate_header_batch_anchors`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderBatchBoundary {
+    /// The lowest header of the batch is not attached to the expected parent.
+    Start,
+    /// Two consecutive headers of the batch are not linked.
+    Interior,
+    /// The highest header of the batch is not the expected terminal header.
+    End,
+}
+
+/// Error returned by [`validate_header_batch_anchors`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum HeaderBatchAnchorError {
+    /// The batch contains no headers.
+    #[error("header batch is empty")]
+    Empty,
+    /// The lowest header does not follow the number of the expected parent.
+    #[error("first header number does not follow the anchor: {0}")]
+    AnchorNumber(GotExpected<BlockNumber>),
+    /// The parent hash of the lowest header is not the hash of the expected parent.
+    #[error("first header parent hash does not match the anchor: {0}")]
+    AnchorHash(GotExpected<BlockHash>),
+    /// The header at the given number is not a child of the header below it.
+    #[error("header {number} is detached from its parent: {parent_hash}")]
+    Detached {
+        /// The number of the detached header.
+        number: BlockNumber,
+        /// The parent hash of the detached header and the hash of the header below it.
+        parent_hash: GotExpected<BlockHash>,
+    },
+    /// The hash of the highest header is not the expected terminal hash.
+    #[error("last header hash does not match the terminal hash: {0}")]
+    Terminal(GotExpected<BlockHash>),
+}
+
+impl HeaderBatchAnchorError {
+    /// Returns the boundary of the batch that failed, or `None` if the batch is empty.
+    pub const fn boundary(&self) -> Option<HeaderBatchBoundary> {
+        match self {
+            Self::Empty => None,
+            Self::AnchorNumber(_) | Self::AnchorHash(_) => Some(HeaderBatchBoundary::Start),
+            Self::Detached { .. } => Some(HeaderBatchBoundary::Interior),
+            Self::Terminal(_) => Some(HeaderBatchBoundary::End),
+        }
+    }
+}
+
+/// Validates that a downloaded batch of headers connects the expected parent to the expected
+/// terminal hash.
+///
+/// This checks that the lowest header is the child of `anchor`, that every header is the child of
+/// the header below it and that the highest header has the `terminal` hash. The checks are done in
+/// that order, so the first failed boundary is returned. The headers themselves are not validated,
+/// see [`HeaderValidator`](crate::HeaderValidator) for that.
+///
+/// Note: this expects that the headers are in natural order (ascending block number)
+pub fn validate_header_batch_anchors<H: BlockHeader>(
+    headers: &[SealedHeader<H>],
+    anchor: BlockNumHash,
+    terminal: BlockHash,
+) -> Result<(), HeaderBatchAnchorError> {
+    let (first, last) = match (headers.first(), headers.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Err(HeaderBatchAnchorError::Empty),
+    };
+
+    if first.number() != anchor.number + 1 {
+        return Err(HeaderBatchAnchorError::AnchorNumber(GotExpected {
+            got: first.number(),
+            expected: anchor.number + 1,
+        }))
+    }
+    if first.parent_hash() != anchor.hash {
+        return Err(HeaderBatchAnchorError::AnchorHash(GotExpected {
+            got: first.parent_hash(),
+            expected: anchor.hash,
+        }))
+    }
+
+    for pair in headers.windows(2) {
+        let (parent, child) = (&pair[0], &pair[1]);
+        if child.number() != parent.number() + 1 || child.parent_hash() != parent.hash() {
+            return Err(HeaderBatchAnchorError::Detached {
+                number: child.number(),
+                parent_hash: GotExpected { got: child.parent_hash(), expected: parent.hash() },
+            })
+        }
+    }
+
+    if last.hash() != terminal {
+        return Err(HeaderBatchAnchorError::Terminal(GotExpected {
+            got: last.hash(),
+            expected: terminal,
+        }))
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use alloy_consensus::Header;
+    use alloy_primitives::B256;
+
+    /// Returns a chain of `len` headers on top of the returned anchor.
+    fn chain(len: u64) -> (BlockNumHash, Vec<SealedHeader>) {
+        let anchor = SealedHeader::seal_slow(Header { number: 10, ..Default::default() });
+        let mut headers = Vec::new();
+        let mut parent = anchor.num_hash();
+        for _ in 0..len {
+            let header = SealedHeader::seal_slow(Header {
+                number: parent.number + 1,
+                parent_hash: parent.hash,
+                ..Default::default()
+            });
+            parent = header.num_hash();
+            headers.push(header);
+        }
+        (anchor.num_hash(), headers)
+    }
+
+    #[test]
+    fn accepts_connected_batch() {
+        let (anchor, headers) = chain(3);
+        let terminal = headers.last().unwrap().hash();
+        assert_eq!(validate_header_batch_anchors(&headers, anchor, terminal), Ok(()));
+        assert_eq!(validate_header_batch_anchors(&headers[..1], anchor, headers[0].hash()), Ok(()));
+    }
+
+    #[test]
+    fn reports_failed_boundary() {
+        let (anchor, headers) = chain(3);
+        let terminal = headers.last().unwrap().hash();
+
+        let err = validate_header_batch_anchors::<Header>(&[], anchor, terminal).unwrap_err();
+        assert_eq!(err, HeaderBatchAnchorError::Empty);
+        assert_eq!(err.boundary(), None);
+
+        let wrong_anchor = BlockNumHash::new(anchor.number, B256::with_last_byte(1));
+        let err = validate_header_batch_anchors(&headers, wrong_anchor, terminal).unwrap_err();
+        assert!(matches!(err, HeaderBatchAnchorError::AnchorHash(_)));
+        assert_eq!(err.boundary(), Some(HeaderBatchBoundary::Start));
+
+        let err = validate_header_batch_anchors(&headers[1..], anchor, terminal).unwrap_err();
+        assert!(matches!(err, HeaderBatchAnchorError::AnchorNumber(_)));
+        assert_eq!(err.boundary(), Some(HeaderBatchBoundary::Start));
+
+        let gapped = [headers[0].clone(), headers[2].clone()];
+        let err = validate_header_batch_anchors(&gapped, anchor, terminal).unwrap_err();
+        assert!(matches!(err, HeaderBatchAnchorError::Detached { number: 13, .. }));
+        assert_eq!(err.boundary(), Some(HeaderBatchBoundary::Interior));
+
+        let err = validate_header_batch_anchors(&headers[..2], anchor, terminal).unwrap_err();
+        assert!(matches!(err, HeaderBatchAnchorError::Terminal(_)));
+        assert_eq!(err.boundary(), Some(HeaderBatchBoundary::End));
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{Consensus, ConsensusError, FullConsensus, HeaderValidator, ProofAssistedConsensus};
+use alloc::sync::Arc;
+use alloy_consensus::Sealable;
+use alloy_eips::eip7685::Requests;
+use alloy_primitives::B256;
+use core::fmt;
+use parking_lot::Mutex;
+use reth_execution_types::BlockExecutionResult;
+use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
+use schnellru::{ByLength, LruMap};
+
+/// The default number of header validation results kept by [`CachedConsensus`].
+pub const DEFAULT_MAX_CACHED_HEADER_VALIDATIONS: u32 = 1024;
+
+/// A consensus wrapper that memoizes header validation results by sealed header hash.
+///
+/// During reorgs the same headers are validated multiple times by the downloader, the engine tree
+/// and the pipeline. Sharing a [`CachedConsensus`] between them avoids redundant work: results of
+/// [`HeaderValidator::validate_header`] are cached by header hash and results of
+/// [`HeaderValidator::validate_header_against_parent`] by header and parent hash.
+///
+/// Errors that depend on the local clock, i.e. [`ConsensusError::TimestampIsInFuture`], are never
+/// cached because they can resolve over time.
+///
+/// Clones share the same cache. All other validations are forwarded to the inner consensus.
+pub struct CachedConsensus<C> {
+    /// The wrapped consensus.
+    inner: C,
+    /// Cached header validation results.
+    cache: Arc<Mutex<HeaderValidationCache>>,
+}
+
+impl<C> CachedConsensus<C> {
+    /// Wraps the given consensus, caching up to [`DEFAULT_MAX_CACHED_HEADER_VALIDATIONS`] results
+    /// per validation kind.
+    pub fn new(inner: C) -> Self {
+        Self::with_capacity(inner, DEFAULT_MAX_CACHED_HEADER_VALIDATIONS)
+    }
+
+    /// Wraps the given consensus, caching up to `capacity` results per validation kind.
+    pub fn with_capacity(inner: C, capacity: u32) -> Self {
+        Self { inner, cache: Arc::new(Mutex::new(HeaderValidationCache::new(capacity))) }
+    }
+
+    /// Returns the wrapped consensus.
+    pub const fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&self) {
+        self.cache.lock().clear();
+    }
+
+    /// Returns the number of cache hits and misses since creation.
+    pub fn stats(&self) -> (u64, u64) {
+        let cache = self.cache.lock();
+        (cache.hits, cache.misses)
+    }
+}
+
+impl<C: Clone> Clone for CachedConsensus<C> {
+    fn clone(&self) -> Self {
+        Self { inner: self.inner.clone(), cache: self.cache.clone() }
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for CachedConsensus<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cache = self.cache.lock();
+        f.debug_struct("CachedConsensus")
+            .field("inner", &self.inner)
+            .field("headers", &cache.headers.len())
+            .field("headers_against_parent", &cache.headers_against_parent.len())
+            .finish()
+    }
+}
+
+impl<H, C> HeaderValidator<H> for CachedConsensus<C>
+where
+    H: Sealable,
+    C: HeaderValidator<H>,
+{
+    fn validate_header(&self, header: &SealedHeader<H>) -> Result<(), ConsensusError> {
+        let hash = header.hash();
+        if let Some(result) = self.cache.lock().header(&hash) {
+            return result
+        }
+
+        let result = self.inner.validate_header(header);
+        self.cache.lock().insert_header(hash, &result);
+        result
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader<H>,
+        parent: &SealedHeader<H>,
+    ) -> Result<(), ConsensusError> {
+        let key = (header.hash(), parent.hash());
+        if let Some(result) = self.cache.lock().header_against_parent(&key) {
+            return result
+        }
+
+        let result = self.inner.validate_header_against_parent(header, parent);
+        self.cache.lock().insert_header_against_parent(key, &result);
+        result
+    }
+}
+
+impl<B, C> Consensus<B> for CachedConsensus<C>
+where
+    B: Block,
+    C: Consensus<B>,
+{
+    type Error = C::Error;
+
+    fn validate_body_against_header(
+        &self,
+        body: &B::Body,
+        header: &SealedHeader<B::Header>,
+    ) -> Result<(), Self::Error> {
+        self.inner.validate_body_against_header(body, header)
+    }
+
+    fn validate_block_pre_execution(&self, block: &SealedBlock<B>) -> Result<(), Self::Error> {
+        self.inner.validate_block_pre_execution(block)
+    }
+}
+
+impl<N, C> FullConsensus<N> for CachedConsensus<C>
+where
+    N: NodePrimitives,
+    C: FullConsensus<N>,
+{
+    fn validate_block_post_execution(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_block_post_execution(block, result)
+    }
+
+    fn validate_chain_post_execution(
+        &self,
+        blocks: &[RecoveredBlock<N::Block>],
+        results: &[BlockExecutionResult<N::Receipt>],
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_chain_post_execution(blocks, results)
+    }
+
+    fn validate_block_post_execution_with_requests(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+        expected_requests: &Requests,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_block_post_execution_with_requests(block, result, expected_requests)
+    }
+}
+
+impl<N, C> ProofAssistedConsensus<N> for CachedConsensus<C>
+where
+    N: NodePrimitives,
+    C: ProofAssistedConsensus<N>,
+{
+    type Proof = C::Proof;
+
+    fn validate_block_post_execution_with_proof(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+        proof: &Self::Proof,
+    ) -> Result<(), ConsensusError> {
+        self.inner.validate_block_post_execution_with_proof(block, result, proof)
+    }
+}
+
+/// LRU caches of header validation results.
+struct HeaderValidationCache {
+    /// Results of [`HeaderValidator::validate_header`] by header hash.
+    headers: LruMap<B256, Result<(), ConsensusError>, ByLength>,
+    /// Results of [`HeaderValidator::validate_header_against_parent`] by header and parent hash.
+    headers_against_parent: LruMap<(B256, B256), Result<(), ConsensusError>, ByLength>,
+    /// Number of cache hits.
+    hits: u64,
+    /// Number of cache misses.
+    misses: u64,
+}
+
+impl HeaderValidationCache {
+    fn new(capacity: u32) -> Self {
+        Self {
+            headers: LruMap::new(ByLength::new(capacity)),
+            headers_against_parent: LruMap::new(ByLength::new(capacity)),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.headers.clear();
+        self.headers_against_parent.clear();
+    }
+
+    fn header(&mut self, hash: &B256) -> Option<Result<(), ConsensusError>> {
+        let result = self.headers.get(hash).cloned();
+        self.record(result.is_some());
+        result
+    }
+
+    fn header_against_parent(&mut self, key: &(B256, B256)) -> Option<Result<(), ConsensusError>> {
+        let result = self.headers_against_parent.get(key).cloned();
+        self.record(result.is_some());
+        result
+    }
+
+    fn insert_header(&mut self, hash: B256, result: &Result<(), ConsensusError>) {
+        if is_cacheable(result) {
+            self.headers.insert(hash, result.clone());
+        }
+    }
+
+    fn insert_header_against_parent(
+        &mut self,
+        key: (B256, B256),
+        result: &Result<(), ConsensusError>,
+    ) {
+        if is_cacheable(result) {
+            self.headers_against_parent.insert(key, result.clone());
+        }
+    }
+
+    const fn record(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+}
+
+/// Returns `true` if the validation result doesn't depend on the local clock.
+const fn is_cacheable(result: &Result<(), ConsensusError>) -> bool {
+    !matches!(result, Err(ConsensusError::TimestampIsInFuture { .. }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct CountingValidator(AtomicUsize);
+
+    impl HeaderValidator for CountingValidator {
+        fn validate_header(&self, header: &SealedHeader) -> Result<(), ConsensusError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            if header.number == 1 {
+                Err(ConsensusError::TimestampIsInFuture { timestamp: 1, present_timestamp: 0 })
+            } else {
+                Err(ConsensusError::BaseFeeMissing)
+            }
+        }
+
+        fn validate_header_against_parent(
+            &self,
+            _header: &SealedHeader,
+            _parent: &SealedHeader,
+        ) -> Result<(), ConsensusError> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn caches_header_validations() {
+        let consensus = CachedConsensus::new(CountingValidator::default());
+        let parent = SealedHeader::seal_slow(Header::default());
+        let header = SealedHeader::seal_slow(Header { number: 2, ..Default::default() });
+
+        for _ in 0..2 {
+            assert_eq!(consensus.validate_header(&header), Err(ConsensusError::BaseFeeMissing));
+            assert_eq!(consensus.validate_header_against_parent(&header, &parent), Ok(()));
+        }
+        assert_eq!(consensus.inner().0.load(Ordering::SeqCst), 2);
+        assert_eq!(consensus.stats(), (2, 2));
+
+        // clock dependent errors are not cached
+        let future = SealedHeader::seal_slow(Header { number: 1, ..Default::default() });
+        assert!(consensus.validate_header(&future).is_err());
+        assert!(consensus.validate_header(&future).is_err());
+        assert_eq!(consensus.inner().0.load(Ordering::SeqCst), 4);
+    }
+}
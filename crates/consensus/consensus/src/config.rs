@@ -0,0 +1,48 @@
+/// The default number of seconds a header timestamp is allowed to be ahead of the local clock.
+///
+/// This matches [`ALLOWED_FUTURE_BLOCK_TIME_SECONDS`](https://docs.rs/alloy-eips/latest/alloy_eips/merge/constant.ALLOWED_FUTURE_BLOCK_TIME_SECONDS.html).
+pub const DEFAULT_MAX_FUTURE_TIMESTAMP_DRIFT: u64 = 15;
+
+/// Configuration for header validation rules that depend on the local environment rather than on
+/// the chain itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderValidatorConfig {
+    /// The maximum number of seconds a header timestamp may be ahead of the local clock.
+    ///
+    /// `None` disables the check, which can be useful for private devnets with skewed clocks.
+    pub max_future_timestamp_drift: Option<u64>,
+}
+
+impl HeaderValidatorConfig {
+    /// Creates the default config, allowing header timestamps to be up to
+    /// [`DEFAULT_MAX_FUTURE_TIMESTAMP_DRIFT`] seconds ahead of the local clock.
+    pub const fn new() -> Self {
+        Self { max_future_timestamp_drift: Some(DEFAULT_MAX_FUTURE_TIMESTAMP_DRIFT) }
+    }
+
+    /// Sets the maximum number of seconds a header timestamp may be ahead of the local clock.
+    pub const fn with_max_future_timestamp_drift(mut self, drift: u64) -> Self {
+        self.max_future_timestamp_drift = Some(drift);
+        self
+    }
+
+    /// Disables the check for header timestamps that are ahead of the local clock.
+    pub const fn without_future_timestamp_check(mut self) -> Self {
+        self.max_future_timestamp_drift = None;
+        self
+    }
+
+    /// Returns `true` if `timestamp` is further ahead of `present_timestamp` than allowed.
+    pub const fn is_timestamp_in_future(&self, timestamp: u64, present_timestamp: u64) -> bool {
+        match self.max_future_timestamp_drift {
+            Some(drift) => timestamp > present_timestamp.saturating_add(drift),
+            None => false,
+        }
+    }
+}
+
+impl Default for HeaderValidatorConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,82 @@
+use alloc::boxed::Box;
+use core::any::Any;
+
+/// A trait for additional consensus errors that are not covered by
+/// [`ConsensusError`](crate::ConsensusError), e.g. errors of L2 stacks.
+///
+/// Custom errors are carried by [`ConsensusError::Custom`](crate::ConsensusError::Custom) and can
+/// be inspected with [`ConsensusError::as_custom`](crate::ConsensusError::as_custom).
+///
+/// Errors opt in with an empty impl. [`DynConsensusError`] is provided for all custom errors that
+/// are [`Clone`] and [`PartialEq`]. [`ConsensusError`](crate::ConsensusError) itself is not a
+/// custom error, so it can't be nested in
+/// [`ConsensusError::Custom`](crate::ConsensusError::Custom).
+pub trait CustomConsensusError: DynConsensusError {}
+
+/// Object safe cloning, comparison and downcasting of [`CustomConsensusError`]s.
+///
+/// This is implemented for all custom errors that are [`Clone`] and [`PartialEq`].
+pub trait DynConsensusError: core::error::Error + Send + Sync + 'static {
+    /// Returns a reference to `self` as a `&dyn Any`, enabling downcasting.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Clones the error into a new boxed trait object.
+    fn clone_box(&self) -> Box<dyn CustomConsensusError>;
+
+    /// Returns `true` if `other` is the same error as `self`.
+    ///
+    /// Errors of different types are never equal.
+    fn dyn_eq(&self, other: &dyn CustomConsensusError) -> bool;
+}
+
+impl<T> DynConsensusError for T
+where
+    T: CustomConsensusError + Clone + PartialEq,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn CustomConsensusError> {
+        Box::new(self.clone())
+    }
+
+    fn dyn_eq(&self, other: &dyn CustomConsensusError) -> bool {
+        other.as_any().downcast_ref::<Self>().is_some_and(|other| self == other)
+    }
+}
+
+impl Clone for Box<dyn CustomConsensusError> {
+    fn clone(&self) -> Self {
+        (**self).clone_box()
+    }
+}
+
+impl PartialEq for Box<dyn CustomConsensusError> {
+    fn eq(&self, other: &Self) -> bool {
+        (**self).dyn_eq(&**other)
+    }
+}
+
+impl Eq for Box<dyn CustomConsensusError> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ConsensusError, CustomConsensusError};
+
+    #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+    #[error("l2 error {0}")]
+    struct L2Error(u64);
+
+    impl CustomConsensusError for L2Error {}
+
+    #[test]
+    fn custom_error_downcast() {
+        let err = ConsensusError::custom(L2Error(1));
+        assert_eq!(err.to_string(), "l2 error 1");
+        assert_eq!(err.as_custom::<L2Error>(), Some(&L2Error(1)));
+        assert_eq!(err.clone(), err);
+        assert_ne!(err, ConsensusError::custom(L2Error(2)));
+        assert_eq!(ConsensusError::BaseFeeMissing.as_custom::<L2Error>(), None);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::ConsensusError;
+use core::fmt;
+use reth_chainspec::{EthereumHardfork, EthereumHardforks};
+use reth_primitives_traits::BlockHeader;
+
+/// The hardfork a header looks like compared to the hardfork that is active for it according to
+/// the chain spec.
+///
+/// Attached to errors about fork specific header fields, see
+/// [`ConsensusError::HardforkMismatch`], so that a misconfigured chain spec is easy to spot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ActiveForkInfo {
+    /// The latest hardfork whose fields are present in the header.
+    pub detected: EthereumHardfork,
+    /// The latest hardfork that is active for the header according to the chain spec.
+    pub expected: EthereumHardfork,
+}
+
+impl ActiveForkInfo {
+    /// Detects the hardfork of the header from its fork specific fields and looks up the hardfork
+    /// that is active for it in the chain spec.
+    ///
+    /// Only hardforks that introduced new header fields are distinguished, i.e. London, Paris,
+    /// Shanghai, Cancun and Prague. Headers of earlier hardforks are reported as Frontier.
+    pub fn new<H, ChainSpec>(header: &H, chain_spec: &ChainSpec) -> Self
+    where
+        H: BlockHeader,
+        ChainSpec: EthereumHardforks,
+    {
+        Self { detected: Self::detect(header), expected: Self::active(header, chain_spec) }
+    }
+
+    /// Returns `true` if the detected hardfork differs from the expected one.
+    pub fn is_mismatch(&self) -> bool {
+        self.detected != self.expected
+    }
+
+    /// Returns the latest hardfork whose fields are present in the header.
+    fn detect<H: BlockHeader>(header: &H) -> EthereumHardfork {
+        if header.requests_hash().is_some() {
+            EthereumHardfork::Prague
+        } else if header.blob_gas_used().is_some() ||
+            header.excess_blob_gas().is_some() ||
+            header.parent_beacon_block_root().is_some()
+        {
+            EthereumHardfork::Cancun
+        } else if header.withdrawals_root().is_some() {
+            EthereumHardfork::Shanghai
+        } else if header.difficulty().is_zero() {
+            EthereumHardfork::Paris
+        } else if header.base_fee_per_gas().is_some() {
+            EthereumHardfork::London
+        } else {
+            EthereumHardfork::Frontier
+        }
+    }
+
+    /// Returns the latest hardfork that is active for the header according to the chain spec.
+    fn active<H, ChainSpec>(header: &H, chain_spec: &ChainSpec) -> EthereumHardfork
+    where
+        H: BlockHeader,
+        ChainSpec: EthereumHardforks,
+    {
+        if chain_spec.is_prague_active_at_timestamp(header.timestamp()) {
+            EthereumHardfork::Prague
+        } else if chain_spec.is_cancun_active_at_timestamp(header.timestamp()) {
+            EthereumHardfork::Cancun
+        } else if chain_spec.is_shanghai_active_at_timestamp(header.timestamp()) {
+            EthereumHardfork::Shanghai
+        } else if chain_spec.is_paris_active_at_block(header.number()) {
+            EthereumHardfork::Paris
+        } else if chain_spec.is_london_active_at_block(header.number()) {
+            EthereumHardfork::London
+        } else {
+            EthereumHardfork::Frontier
+        }
+    }
+}
+
+impl fmt::Display for ActiveForkInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "block looks like {:?} but chain spec says {:?}", self.detected, self.expected)
+    }
+}
+
+impl ConsensusError {
+    /// Returns `true` if the error is about a header field that is present or absent relative to
+    /// the active hardfork.
+    pub const fn is_hardfork_field_error(&self) -> bool {
+        matches!(
+            self,
+            Self::WithdrawalsRootMissing |
+                Self::WithdrawalsRootUnexpected |
+                Self::BlobGasUsedMissing |
+                Self::BlobGasUsedUnexpected |
+                Self::ExcessBlobGasMissing |
+                Self::ExcessBlobGasUnexpected |
+                Self::ParentBeaconBlockRootMissing |
+                Self::ParentBeaconBlockRootUnexpected |
+                Self::RequestsHashMissing |
+                Self::RequestsHashUnexpected |
+                Self::BaseFeeMissing |
+                Self::TheMergeDifficultyIsNotZero
+        )
+    }
+
+    /// Attaches the detected and expected hardfork of the header to the error, if the error is a
+    /// [hardfork field error](Self::is_hardfork_field_error) and the hardforks differ.
+    pub fn with_fork_info<H, ChainSpec>(self, header: &H, chain_spec: &ChainSpec) -> Self
+    where
+        H: BlockHeader,
+        ChainSpec: EthereumHardforks,
+    {
+        if !self.is_hardfork_field_error() {
+            return self
+        }
+
+        let fork = ActiveForkInfo::new(header, chain_spec);
+        if fork.is_mismatch() {
+            Self::HardforkMismatch { error: alloc::boxed::Box::new(self), fork }
+        } else {
+            self
+        }
+    }
+
+    /// Returns the error without the fork info attached by [`ConsensusError::with_fork_info`].
+    pub fn without_fork_info(&self) -> &Self {
+        match self {
+            Self::HardforkMismatch { error, .. } => error,
+            err => err,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::{B256, U256};
+    use reth_chainspec::{ChainSpecBuilder, MAINNET};
+
+    #[test]
+    fn attaches_fork_info_on_mismatch() {
+        let chain_spec = ChainSpecBuilder::mainnet().shanghai_activated().build();
+        let header = Header {
+            withdrawals_root: Some(B256::ZERO),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+
+        let fork = ActiveForkInfo::new(&header, &chain_spec);
+        assert_eq!(fork.detected, EthereumHardfork::Cancun);
+        assert_eq!(fork.expected, EthereumHardfork::Shanghai);
+        assert_eq!(fork.to_string(), "block looks like Cancun but chain spec says Shanghai");
+
+        let err = ConsensusError::BlobGasUsedUnexpected.with_fork_info(&header, &chain_spec);
+        assert_eq!(
+            err,
+            ConsensusError::HardforkMismatch {
+                error: alloc::boxed::Box::new(ConsensusError::BlobGasUsedUnexpected),
+                fork
+            }
+        );
+        assert_eq!(err.code(), ConsensusError::BlobGasUsedUnexpected.code());
+        assert_eq!(err.without_fork_info(), &ConsensusError::BlobGasUsedUnexpected);
+
+        // unrelated errors are left untouched
+        let err = ConsensusError::ExtraDataExceedsMax { len: 33, max: 32 };
+        assert_eq!(err.clone().with_fork_info(&header, &chain_spec), err);
+
+        // matching forks are left untouched
+        let err = ConsensusError::BlobGasUsedUnexpected;
+        let header = Header { difficulty: U256::from(1), ..Default::default() };
+        assert_eq!(err.clone().with_fork_info(&header, &*MAINNET), err);
+    }
+}
@@ -0,0 +1,399 @@
+//! Stable, machine-readable classification of [`ConsensusError`]s.
+
+use crate::ConsensusError;
+use core::fmt;
+
+/// The kind of a [`ConsensusError`], without any of the attached data.
+///
+/// Every kind maps to a stable numeric code (see [`ConsensusErrorKind::as_u16`]) and a stable
+/// string identifier (see [`ConsensusErrorKind::as_str`]), which allows classifying rejections
+/// without relying on the [`Display`](fmt::Display) output of the error.
+///
+/// Codes are never reused: new kinds are appended with a new code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+#[repr(u16)]
+pub enum ConsensusErrorKind {
+    /// See [`ConsensusError::HeaderGasUsedExceedsGasLimit`].
+    HeaderGasUsedExceedsGasLimit = 1,
+    /// See [`ConsensusError::HeaderGasLimitExceedsMax`].
+    HeaderGasLimitExceedsMax = 2,
+    /// See [`ConsensusError::BlockGasUsed`].
+    BlockGasUsed = 3,
+    /// See [`ConsensusError::BodyOmmersHashDiff`].
+    BodyOmmersHashDiff = 4,
+    /// See [`ConsensusError::BodyStateRootDiff`].
+    BodyStateRootDiff = 5,
+    /// See [`ConsensusError::BodyTransactionRootDiff`].
+    BodyTransactionRootDiff = 6,
+    /// See [`ConsensusError::BodyReceiptRootDiff`].
+    BodyReceiptRootDiff = 7,
+    /// See [`ConsensusError::BodyBloomLogDiff`].
+    BodyBloomLogDiff = 8,
+    /// See [`ConsensusError::BodyWithdrawalsRootDiff`].
+    BodyWithdrawalsRootDiff = 9,
+    /// See [`ConsensusError::BodyRequestsHashDiff`].
+    BodyRequestsHashDiff = 10,
+    /// See [`ConsensusError::BlockKnown`].
+    BlockKnown = 11,
+    /// See [`ConsensusError::ParentUnknown`].
+    ParentUnknown = 12,
+    /// See [`ConsensusError::ParentBlockNumberMismatch`].
+    ParentBlockNumberMismatch = 13,
+    /// See [`ConsensusError::ParentHashMismatch`].
+    ParentHashMismatch = 14,
+    /// See [`ConsensusError::TimestampIsInFuture`].
+    TimestampIsInFuture = 15,
+    /// See [`ConsensusError::BaseFeeMissing`].
+    BaseFeeMissing = 16,
+    /// See [`ConsensusError::TransactionSignerRecoveryError`].
+    TransactionSignerRecoveryError = 17,
+    /// See [`ConsensusError::ExtraDataExceedsMax`].
+    ExtraDataExceedsMax = 18,
+    /// See [`ConsensusError::TheMergeDifficultyIsNotZero`].
+    TheMergeDifficultyIsNotZero = 19,
+    /// See [`ConsensusError::TheMergeNonceIsNotZero`].
+    TheMergeNonceIsNotZero = 20,
+    /// See [`ConsensusError::TheMergeOmmerRootIsNotEmpty`].
+    TheMergeOmmerRootIsNotEmpty = 21,
+    /// See [`ConsensusError::WithdrawalsRootMissing`].
+    WithdrawalsRootMissing = 22,
+    /// See [`ConsensusError::RequestsHashMissing`].
+    RequestsHashMissing = 23,
+    /// See [`ConsensusError::WithdrawalsRootUnexpected`].
+    WithdrawalsRootUnexpected = 24,
+    /// See [`ConsensusError::RequestsHashUnexpected`].
+    RequestsHashUnexpected = 25,
+    /// See [`ConsensusError::BodyWithdrawalsMissing`].
+    BodyWithdrawalsMissing = 26,
+    /// See [`ConsensusError::BodyRequestsMissing`].
+    BodyRequestsMissing = 27,
+    /// See [`ConsensusError::BlobGasUsedMissing`].
+    BlobGasUsedMissing = 28,
+    /// See [`ConsensusError::BlobGasUsedUnexpected`].
+    BlobGasUsedUnexpected = 29,
+    /// See [`ConsensusError::ExcessBlobGasMissing`].
+    ExcessBlobGasMissing = 30,
+    /// See [`ConsensusError::ExcessBlobGasUnexpected`].
+    ExcessBlobGasUnexpected = 31,
+    /// See [`ConsensusError::ParentBeaconBlockRootMissing`].
+    ParentBeaconBlockRootMissing = 32,
+    /// See [`ConsensusError::ParentBeaconBlockRootUnexpected`].
+    ParentBeaconBlockRootUnexpected = 33,
+    /// See [`ConsensusError::BlobGasUsedExceedsMaxBlobGasPerBlock`].
+    BlobGasUsedExceedsMaxBlobGasPerBlock = 34,
+    /// See [`ConsensusError::BlobGasUsedNotMultipleOfBlobGasPerBlob`].
+    BlobGasUsedNotMultipleOfBlobGasPerBlob = 35,
+    /// See [`ConsensusError::BlobGasUsedDiff`].
+    BlobGasUsedDiff = 36,
+    /// See [`ConsensusError::InvalidTransaction`].
+    InvalidTransaction = 37,
+    /// See [`ConsensusError::BaseFeeDiff`].
+    BaseFeeDiff = 38,
+    /// See [`ConsensusError::ExcessBlobGasDiff`].
+    ExcessBlobGasDiff = 39,
+    /// See [`ConsensusError::GasLimitInvalidIncrease`].
+    GasLimitInvalidIncrease = 40,
+    /// See [`ConsensusError::GasLimitInvalidMinimum`].
+    GasLimitInvalidMinimum = 41,
+    /// See [`ConsensusError::GasLimitInvalidBlockMaximum`].
+    GasLimitInvalidBlockMaximum = 42,
+    /// See [`ConsensusError::GasLimitInvalidDecrease`].
+    GasLimitInvalidDecrease = 43,
+    /// See [`ConsensusError::TimestampIsInPast`].
+    TimestampIsInPast = 44,
+    /// See [`ConsensusError::Custom`].
+    Custom = 45,
+    /// See [`ConsensusError::ExecutionResultsLengthMismatch`].
+    ExecutionResultsLengthMismatch = 46,
+    /// See [`ConsensusError::BodyRequestsDiff`].
+    BodyRequestsDiff = 47,
+    /// See [`ConsensusError::InvalidRequests`].
+    InvalidRequests = 48,
+    /// See [`ConsensusError::InvalidValidationProof`].
+    InvalidValidationProof = 49,
+    /// See [`ConsensusError::TotalDifficultyMismatch`].
+    TotalDifficultyMismatch = 50,
+    /// See [`ConsensusError::EpochAccumulatorMismatch`].
+    EpochAccumulatorMismatch = 51,
+    /// See [`ConsensusError::BlobSidecarCountMismatch`].
+    BlobSidecarCountMismatch = 52,
+    /// See [`ConsensusError::InvalidBlobSidecar`].
+    InvalidBlobSidecar = 53,
+    /// See [`ConsensusError::TooManyOmmers`].
+    TooManyOmmers = 54,
+    /// See [`ConsensusError::DuplicateOmmer`].
+    DuplicateOmmer = 55,
+    /// See [`ConsensusError::DanglingOmmer`].
+    DanglingOmmer = 56,
+    /// See [`ConsensusError::InvalidOmmerHeader`].
+    InvalidOmmerHeader = 57,
+}
+
+impl ConsensusErrorKind {
+    /// All known kinds, in order of their numeric code.
+    pub const ALL: &'static [Self] = &[
+        Self::HeaderGasUsedExceedsGasLimit,
+        Self::HeaderGasLimitExceedsMax,
+        Self::BlockGasUsed,
+        Self::BodyOmmersHashDiff,
+        Self::BodyStateRootDiff,
+        Self::BodyTransactionRootDiff,
+        Self::BodyReceiptRootDiff,
+        Self::BodyBloomLogDiff,
+        Self::BodyWithdrawalsRootDiff,
+        Self::BodyRequestsHashDiff,
+        Self::BlockKnown,
+        Self::ParentUnknown,
+        Self::ParentBlockNumberMismatch,
+        Self::ParentHashMismatch,
+        Self::TimestampIsInFuture,
+        Self::BaseFeeMissing,
+        Self::TransactionSignerRecoveryError,
+        Self::ExtraDataExceedsMax,
+        Self::TheMergeDifficultyIsNotZero,
+        Self::TheMergeNonceIsNotZero,
+        Self::TheMergeOmmerRootIsNotEmpty,
+        Self::WithdrawalsRootMissing,
+        Self::RequestsHashMissing,
+        Self::WithdrawalsRootUnexpected,
+        Self::RequestsHashUnexpected,
+        Self::BodyWithdrawalsMissing,
+        Self::BodyRequestsMissing,
+        Self::BlobGasUsedMissing,
+        Self::BlobGasUsedUnexpected,
+        Self::ExcessBlobGasMissing,
+        Self::ExcessBlobGasUnexpected,
+        Self::ParentBeaconBlockRootMissing,
+        Self::ParentBeaconBlockRootUnexpected,
+        Self::BlobGasUsedExceedsMaxBlobGasPerBlock,
+        Self::BlobGasUsedNotMultipleOfBlobGasPerBlob,
+        Self::BlobGasUsedDiff,
+        Self::InvalidTransaction,
+        Self::BaseFeeDiff,
+        Self::ExcessBlobGasDiff,
+        Self::GasLimitInvalidIncrease,
+        Self::GasLimitInvalidMinimum,
+        Self::GasLimitInvalidBlockMaximum,
+        Self::GasLimitInvalidDecrease,
+        Self::TimestampIsInPast,
+        Self::Custom,
+        Self::ExecutionResultsLengthMismatch,
+        Self::BodyRequestsDiff,
+        Self::InvalidRequests,
+        Self::InvalidValidationProof,
+        Self::TotalDifficultyMismatch,
+        Self::EpochAccumulatorMismatch,
+        Self::BlobSidecarCountMismatch,
+        Self::InvalidBlobSidecar,
+        Self::TooManyOmmers,
+        Self::DuplicateOmmer,
+        Self::DanglingOmmer,
+        Self::InvalidOmmerHeader,
+    ];
+
+    /// Returns the stable numeric code of this kind.
+    pub const fn as_u16(&self) -> u16 {
+        *self as u16
+    }
+
+    /// Returns the kind for the given numeric code, if it is known.
+    pub fn from_u16(code: u16) -> Option<Self> {
+        Self::ALL.iter().find(|kind| kind.as_u16() == code).copied()
+    }
+
+    /// Returns the stable `snake_case` identifier of this kind.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::HeaderGasUsedExceedsGasLimit => "header_gas_used_exceeds_gas_limit",
+            Self::HeaderGasLimitExceedsMax => "header_gas_limit_exceeds_max",
+            Self::BlockGasUsed => "block_gas_used",
+            Self::BodyOmmersHashDiff => "body_ommers_hash_diff",
+            Self::BodyStateRootDiff => "body_state_root_diff",
+            Self::BodyTransactionRootDiff => "body_transaction_root_diff",
+            Self::BodyReceiptRootDiff => "body_receipt_root_diff",
+            Self::BodyBloomLogDiff => "body_bloom_log_diff",
+            Self::BodyWithdrawalsRootDiff => "body_withdrawals_root_diff",
+            Self::BodyRequestsHashDiff => "body_requests_hash_diff",
+            Self::BlockKnown => "block_known",
+            Self::ParentUnknown => "parent_unknown",
+            Self::ParentBlockNumberMismatch => "parent_block_number_mismatch",
+            Self::ParentHashMismatch => "parent_hash_mismatch",
+            Self::TimestampIsInFuture => "timestamp_is_in_future",
+            Self::BaseFeeMissing => "base_fee_missing",
+            Self::TransactionSignerRecoveryError => "transaction_signer_recovery_error",
+            Self::ExtraDataExceedsMax => "extra_data_exceeds_max",
+            Self::TheMergeDifficultyIsNotZero => "the_merge_difficulty_is_not_zero",
+            Self::TheMergeNonceIsNotZero => "the_merge_nonce_is_not_zero",
+            Self::TheMergeOmmerRootIsNotEmpty => "the_merge_ommer_root_is_not_empty",
+            Self::WithdrawalsRootMissing => "withdrawals_root_missing",
+            Self::RequestsHashMissing => "requests_hash_missing",
+            Self::WithdrawalsRootUnexpected => "withdrawals_root_unexpected",
+            Self::RequestsHashUnexpected => "requests_hash_unexpected",
+            Self::BodyWithdrawalsMissing => "body_withdrawals_missing",
+            Self::BodyRequestsMissing => "body_requests_missing",
+            Self::BlobGasUsedMissing => "blob_gas_used_missing",
+            Self::BlobGasUsedUnexpected => "blob_gas_used_unexpected",
+            Self::ExcessBlobGasMissing => "excess_blob_gas_missing",
+            Self::ExcessBlobGasUnexpected => "excess_blob_gas_unexpected",
+            Self::ParentBeaconBlockRootMissing => "parent_beacon_block_root_missing",
+            Self::ParentBeaconBlockRootUnexpected => "parent_beacon_block_root_unexpected",
+            Self::BlobGasUsedExceedsMaxBlobGasPerBlock => {
+                "blob_gas_used_exceeds_max_blob_gas_per_block"
+            }
+            Self::BlobGasUsedNotMultipleOfBlobGasPerBlob => {
+                "blob_gas_used_not_multiple_of_blob_gas_per_blob"
+            }
+            Self::BlobGasUsedDiff => "blob_gas_used_diff",
+            Self::InvalidTransaction => "invalid_transaction",
+            Self::BaseFeeDiff => "base_fee_diff",
+            Self::ExcessBlobGasDiff => "excess_blob_gas_diff",
+            Self::GasLimitInvalidIncrease => "gas_limit_invalid_increase",
+            Self::GasLimitInvalidMinimum => "gas_limit_invalid_minimum",
+            Self::GasLimitInvalidBlockMaximum => "gas_limit_invalid_block_maximum",
+            Self::GasLimitInvalidDecrease => "gas_limit_invalid_decrease",
+            Self::TimestampIsInPast => "timestamp_is_in_past",
+            Self::Custom => "custom",
+            Self::ExecutionResultsLengthMismatch => "execution_results_length_mismatch",
+            Self::BodyRequestsDiff => "body_requests_diff",
+            Self::InvalidRequests => "invalid_requests",
+            Self::InvalidValidationProof => "invalid_validation_proof",
+            Self::TotalDifficultyMismatch => "total_difficulty_mismatch",
+            Self::EpochAccumulatorMismatch => "epoch_accumulator_mismatch",
+            Self::BlobSidecarCountMismatch => "blob_sidecar_count_mismatch",
+            Self::InvalidBlobSidecar => "invalid_blob_sidecar",
+            Self::TooManyOmmers => "too_many_ommers",
+            Self::DuplicateOmmer => "duplicate_ommer",
+            Self::DanglingOmmer => "dangling_ommer",
+            Self::InvalidOmmerHeader => "invalid_ommer_header",
+        }
+    }
+}
+
+impl fmt::Display for ConsensusErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<ConsensusErrorKind> for u16 {
+    fn from(kind: ConsensusErrorKind) -> Self {
+        kind.as_u16()
+    }
+}
+
+impl ConsensusError {
+    /// Returns the machine-readable [`ConsensusErrorKind`] of this error.
+    ///
+    /// Errors with attached fork info, see [`ConsensusError::with_fork_info`], report the kind of
+    /// the underlying error.
+    pub const fn code(&self) -> ConsensusErrorKind {
+        match self {
+            Self::HeaderGasUsedExceedsGasLimit { .. } => {
+                ConsensusErrorKind::HeaderGasUsedExceedsGasLimit
+            }
+            Self::HeaderGasLimitExceedsMax { .. } => ConsensusErrorKind::HeaderGasLimitExceedsMax,
+            Self::BlockGasUsed { .. } => ConsensusErrorKind::BlockGasUsed,
+            Self::BodyOmmersHashDiff { .. } => ConsensusErrorKind::BodyOmmersHashDiff,
+            Self::BodyStateRootDiff { .. } => ConsensusErrorKind::BodyStateRootDiff,
+            Self::BodyTransactionRootDiff { .. } => ConsensusErrorKind::BodyTransactionRootDiff,
+            Self::BodyReceiptRootDiff { .. } => ConsensusErrorKind::BodyReceiptRootDiff,
+            Self::BodyBloomLogDiff { .. } => ConsensusErrorKind::BodyBloomLogDiff,
+            Self::BodyWithdrawalsRootDiff { .. } => ConsensusErrorKind::BodyWithdrawalsRootDiff,
+            Self::BodyRequestsHashDiff { .. } => ConsensusErrorKind::BodyRequestsHashDiff,
+            Self::BlockKnown { .. } => ConsensusErrorKind::BlockKnown,
+            Self::ParentUnknown { .. } => ConsensusErrorKind::ParentUnknown,
+            Self::ParentBlockNumberMismatch { .. } => ConsensusErrorKind::ParentBlockNumberMismatch,
+            Self::ParentHashMismatch { .. } => ConsensusErrorKind::ParentHashMismatch,
+            Self::TimestampIsInFuture { .. } => ConsensusErrorKind::TimestampIsInFuture,
+            Self::BaseFeeMissing { .. } => ConsensusErrorKind::BaseFeeMissing,
+            Self::TransactionSignerRecoveryError { .. } => {
+                ConsensusErrorKind::TransactionSignerRecoveryError
+            }
+            Self::ExtraDataExceedsMax { .. } => ConsensusErrorKind::ExtraDataExceedsMax,
+            Self::TheMergeDifficultyIsNotZero { .. } => {
+                ConsensusErrorKind::TheMergeDifficultyIsNotZero
+            }
+            Self::TheMergeNonceIsNotZero { .. } => ConsensusErrorKind::TheMergeNonceIsNotZero,
+            Self::TheMergeOmmerRootIsNotEmpty { .. } => {
+                ConsensusErrorKind::TheMergeOmmerRootIsNotEmpty
+            }
+            Self::WithdrawalsRootMissing { .. } => ConsensusErrorKind::WithdrawalsRootMissing,
+            Self::RequestsHashMissing { .. } => ConsensusErrorKind::RequestsHashMissing,
+            Self::WithdrawalsRootUnexpected { .. } => ConsensusErrorKind::WithdrawalsRootUnexpected,
+            Self::RequestsHashUnexpected { .. } => ConsensusErrorKind::RequestsHashUnexpected,
+            Self::BodyWithdrawalsMissing { .. } => ConsensusErrorKind::BodyWithdrawalsMissing,
+            Self::BodyRequestsMissing { .. } => ConsensusErrorKind::BodyRequestsMissing,
+            Self::BlobGasUsedMissing { .. } => ConsensusErrorKind::BlobGasUsedMissing,
+            Self::BlobGasUsedUnexpected { .. } => ConsensusErrorKind::BlobGasUsedUnexpected,
+            Self::ExcessBlobGasMissing { .. } => ConsensusErrorKind::ExcessBlobGasMissing,
+            Self::ExcessBlobGasUnexpected { .. } => ConsensusErrorKind::ExcessBlobGasUnexpected,
+            Self::ParentBeaconBlockRootMissing { .. } => {
+                ConsensusErrorKind::ParentBeaconBlockRootMissing
+            }
+            Self::ParentBeaconBlockRootUnexpected { .. } => {
+                ConsensusErrorKind::ParentBeaconBlockRootUnexpected
+            }
+            Self::BlobGasUsedExceedsMaxBlobGasPerBlock { .. } => {
+                ConsensusErrorKind::BlobGasUsedExceedsMaxBlobGasPerBlock
+            }
+            Self::BlobGasUsedNotMultipleOfBlobGasPerBlob { .. } => {
+                ConsensusErrorKind::BlobGasUsedNotMultipleOfBlobGasPerBlob
+            }
+            Self::BlobGasUsedDiff { .. } => ConsensusErrorKind::BlobGasUsedDiff,
+            Self::InvalidTransaction { .. } => ConsensusErrorKind::InvalidTransaction,
+            Self::BaseFeeDiff { .. } => ConsensusErrorKind::BaseFeeDiff,
+            Self::ExcessBlobGasDiff { .. } => ConsensusErrorKind::ExcessBlobGasDiff,
+            Self::GasLimitInvalidIncrease { .. } => ConsensusErrorKind::GasLimitInvalidIncrease,
+            Self::GasLimitInvalidMinimum { .. } => ConsensusErrorKind::GasLimitInvalidMinimum,
+            Self::GasLimitInvalidBlockMaximum { .. } => {
+                ConsensusErrorKind::GasLimitInvalidBlockMaximum
+            }
+            Self::GasLimitInvalidDecrease { .. } => ConsensusErrorKind::GasLimitInvalidDecrease,
+            Self::TimestampIsInPast { .. } => ConsensusErrorKind::TimestampIsInPast,
+            Self::ExecutionResultsLengthMismatch { .. } => {
+                ConsensusErrorKind::ExecutionResultsLengthMismatch
+            }
+            Self::BodyRequestsDiff { .. } => ConsensusErrorKind::BodyRequestsDiff,
+            Self::InvalidRequests { .. } => ConsensusErrorKind::InvalidRequests,
+            Self::InvalidValidationProof(_) => ConsensusErrorKind::InvalidValidationProof,
+            Self::HardforkMismatch { error, .. } => error.code(),
+            Self::TotalDifficultyMismatch { .. } => ConsensusErrorKind::TotalDifficultyMismatch,
+            Self::EpochAccumulatorMismatch(_) => ConsensusErrorKind::EpochAccumulatorMismatch,
+            Self::BlobSidecarCountMismatch(_) => ConsensusErrorKind::BlobSidecarCountMismatch,
+            Self::InvalidBlobSidecar(_) => ConsensusErrorKind::InvalidBlobSidecar,
+            Self::TooManyOmmers { .. } => ConsensusErrorKind::TooManyOmmers,
+            Self::DuplicateOmmer(_) => ConsensusErrorKind::DuplicateOmmer,
+            Self::DanglingOmmer(_) => ConsensusErrorKind::DanglingOmmer,
+            Self::InvalidOmmerHeader { .. } => ConsensusErrorKind::InvalidOmmerHeader,
+            Self::Custom { .. } => ConsensusErrorKind::Custom,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives_traits::GotExpected;
+
+    #[test]
+    fn codes_are_unique_and_roundtrip() {
+        for (idx, kind) in ConsensusErrorKind::ALL.iter().enumerate() {
+            assert_eq!(kind.as_u16() as usize, idx + 1);
+            assert_eq!(ConsensusErrorKind::from_u16(kind.as_u16()), Some(*kind));
+        }
+        assert_eq!(ConsensusErrorKind::from_u16(0), None);
+    }
+
+    #[test]
+    fn error_code() {
+        let err = ConsensusError::BaseFeeMissing;
+        assert_eq!(err.code(), ConsensusErrorKind::BaseFeeMissing);
+        assert_eq!(err.code().as_str(), "base_fee_missing");
+
+        let err = ConsensusError::ExecutionResultsLengthMismatch(GotExpected::new(1, 2));
+        assert_eq!(err.code(), ConsensusErrorKind::ExecutionResultsLengthMismatch);
+    }
+}
@@ -11,9 +11,10 @@
 
 extern crate alloc;
 
-use alloc::{fmt::Debug, string::String, vec::Vec};
+use alloc::{boxed::Box, fmt::Debug, string::String, vec::Vec};
 use alloy_consensus::Header;
-use alloy_primitives::{BlockHash, BlockNumber, Bloom, B256};
+use alloy_eips::{eip7594::BlobTransactionSidecarVariant, eip7685::Requests};
+use alloy_primitives::{BlockHash, BlockNumber, Bloom, Bytes, B256, U256};
 use reth_execution_types::BlockExecutionResult;
 use reth_primitives_traits::{
     constants::{MAXIMUM_GAS_LIMIT_BLOCK, MINIMUM_GAS_LIMIT},
@@ -22,13 +23,46 @@ use reth_primitives_traits::{
     SealedHeader,
 };
 
+mod anchor;
+pub use anchor::{validate_header_batch_anchors, HeaderBatchAnchorError, HeaderBatchBoundary};
+
+#[cfg(feature = "std")]
+mod cached;
+#[cfg(feature = "std")]
+pub use cached::{CachedConsensus, DEFAULT_MAX_CACHED_HEADER_VALIDATIONS};
+
+mod config;
+pub use config::{HeaderValidatorConfig, DEFAULT_MAX_FUTURE_TIMESTAMP_DRIFT};
+
+mod custom;
+pub use custom::{CustomConsensusError, DynConsensusError};
+
+mod fork;
+pub use fork::ActiveForkInfo;
+
+mod kind;
+pub use kind::ConsensusErrorKind;
+
 /// A consensus implementation that does nothing.
 pub mod noop;
 
+mod observer;
+pub use observer::{observe_validation, ConsensusObserver, ValidationStage};
+
+mod report;
+pub use report::{RuleOutcome, ValidationReport};
+
+/// The arithmetic of the header validation rules, usable in `no_std` verifiers.
+///
+/// The rules are defined in [`reth_consensus_rules`] and re-exported here.
+pub use reth_consensus_rules as rules;
+
 #[cfg(any(test, feature = "test-utils"))]
 /// test helpers for mocking consensus
 pub mod test_utils;
 
+pub mod validation;
+
 /// [`Consensus`] implementation which knows full node primitives and is able to validation block's
 /// execution outcome.
 #[auto_impl::auto_impl(&, Arc)]
@@ -44,6 +78,88 @@ pub trait FullConsensus<N: NodePrimitives>: Consensus<N::Block> {
         block: &RecoveredBlock<N::Block>,
         result: &BlockExecutionResult<N::Receipt>,
     ) -> Result<(), ConsensusError>;
+
+    /// Validates a chain of blocks considering world state, see
+    /// [`FullConsensus::validate_block_post_execution`].
+    ///
+    /// `results` must contain the execution result of each block in `blocks`, in the same order.
+    ///
+    /// By default this validates every block individually. Implementations can override this to
+    /// amortize work across the batch, e.g. by reusing scratch buffers for receipt root and bloom
+    /// computation.
+    fn validate_chain_post_execution(
+        &self,
+        blocks: &[RecoveredBlock<N::Block>],
+        results: &[BlockExecutionResult<N::Receipt>],
+    ) -> Result<(), ConsensusError> {
+        if blocks.len() != results.len() {
+            return Err(ConsensusError::ExecutionResultsLengthMismatch(GotExpected {
+                got: results.len(),
+                expected: blocks.len(),
+            }))
+        }
+        blocks
+            .iter()
+            .zip(results)
+            .try_for_each(|(block, result)| self.validate_block_post_execution(block, result))
+    }
+
+    /// Validates a block considering world state like
+    /// [`FullConsensus::validate_block_post_execution`], when the requests the block is expected
+    /// to produce are known, e.g. the execution requests of a payload.
+    ///
+    /// This allows implementations to report which request type differs instead of only a
+    /// mismatching requests hash. By default the expected requests are ignored.
+    fn validate_block_post_execution_with_requests(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+        expected_requests: &Requests,
+    ) -> Result<(), ConsensusError> {
+        let _ = expected_requests;
+        self.validate_block_post_execution(block, result)
+    }
+}
+
+/// Extension of [`FullConsensus`] for stateless verification pipelines.
+///
+/// Instead of recomputing the roots of a block's execution outcome, e.g. the receipts root and logs
+/// bloom, implementations verify a proof bundle that was pre-computed by an external prover and
+/// check the roots it attests against the block.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ProofAssistedConsensus<N: NodePrimitives>: FullConsensus<N> {
+    /// The proof bundle accepted by this consensus.
+    type Proof: Debug + Send + Sync;
+
+    /// Validate a block considering world state like
+    /// [`FullConsensus::validate_block_post_execution`], using the given proof instead of
+    /// recomputing the roots of the execution outcome.
+    ///
+    /// Returns [`ConsensusError::InvalidValidationProof`] if the proof can't be verified.
+    fn validate_block_post_execution_with_proof(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+        proof: &Self::Proof,
+    ) -> Result<(), ConsensusError>;
+}
+
+/// Extension of [`Consensus`] for validating the blob sidecars of a block.
+///
+/// Blob sidecars are not part of the block itself, so this is used wherever a block and its
+/// sidecars are received together, applying the same KZG commitment and proof checks for EIP-4844
+/// sidecars and EIP-7594 cell proofs.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait BlobSidecarConsensus<B: Block>: Consensus<B> {
+    /// Validates the sidecars of the blob transactions of the block.
+    ///
+    /// The sidecars are expected in the order of the blob transactions in the block body, one
+    /// sidecar per blob transaction.
+    fn validate_blob_sidecars(
+        &self,
+        block: &SealedBlock<B>,
+        sidecars: &[BlobTransactionSidecarVariant],
+    ) -> Result<(), ConsensusError>;
 }
 
 /// Consensus is a protocol that chooses canonical chain.
@@ -123,6 +239,55 @@ pub trait HeaderValidator<H = Header>: Debug + Send + Sync {
     }
 }
 
+/// `PreMergeHeaderValidator` validates historical pre-merge headers against the epoch accumulator
+/// they were shipped with, e.g. in era1 files.
+///
+/// The accumulator commits to the hash and total difficulty of every header of an epoch, so this
+/// replaces checking the proof-of-work and looking up the total difficulty of the parent in the
+/// local chain.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait PreMergeHeaderValidator<H = Header>: Debug + Send + Sync {
+    /// Validates a contiguous range of pre-merge headers, each paired with its total difficulty,
+    /// against the root of their epoch accumulator.
+    ///
+    /// Note: this expects that the headers are in natural order (ascending block number)
+    fn validate_pre_merge_headers(
+        &self,
+        headers: &[(SealedHeader<H>, U256)],
+        accumulator_root: B256,
+    ) -> Result<(), ConsensusError>;
+}
+
+/// An ancestor of a block, as required for validating the ommers of the block.
+#[derive(Debug, Clone)]
+pub struct OmmerAncestor<H = Header> {
+    /// The header of the ancestor.
+    pub header: SealedHeader<H>,
+    /// The hashes of the ommers the ancestor included.
+    pub ommers: Vec<B256>,
+}
+
+/// `OmmerValidator` validates the ommers of pre-merge blocks against the ancestors of the block,
+/// see the Yellow Paper section 11.1 "Ommer Validation".
+///
+/// [`Consensus::validate_block_pre_execution`] only checks that the ommers of a block match its
+/// ommers hash, which requires no access to the chain. This additionally checks that every ommer
+/// is a valid header that branches off a recent ancestor and wasn't included before.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait OmmerValidator<H = Header>: Debug + Send + Sync {
+    /// Validates the ommers of the block with the given header.
+    ///
+    /// `ancestors` are the closest ancestors of the block in descending order, starting with its
+    /// parent. Ommers must branch off one of the given ancestors, so fewer ancestors than the
+    /// ommer depth window of the implementation are only expected close to genesis.
+    fn validate_ommers(
+        &self,
+        header: &SealedHeader<H>,
+        ommers: &[H],
+        ancestors: &[OmmerAncestor<H>],
+    ) -> Result<(), ConsensusError>;
+}
+
 /// Consensus Errors
 #[derive(Debug, PartialEq, Eq, Clone, thiserror::Error)]
 pub enum ConsensusError {
@@ -234,10 +399,12 @@ pub enum ConsensusError {
     TransactionSignerRecoveryError,
 
     /// Error when the extra data length exceeds the maximum allowed.
-    #[error("extra data {len} exceeds max length")]
+    #[error("extra data {len} exceeds max length {max}")]
     ExtraDataExceedsMax {
         /// The length of the extra data.
         len: usize,
+        /// The maximum allowed length of the extra data.
+        max: usize,
     },
 
     /// Error when the difficulty after a merge is not zero.
@@ -395,9 +562,101 @@ pub enum ConsensusError {
         /// The block's timestamp.
         timestamp: u64,
     },
-    /// Other, likely an injected L2 error.
+    /// Error when the number of execution results does not match the number of blocks to validate.
+    #[error("execution results length mismatch: {0}")]
+    ExecutionResultsLengthMismatch(GotExpected<usize>),
+
+    /// Error when the requests of a single EIP-7685 request type don't match the expected ones.
+    #[error("mismatched requests of type {request_type}: {requests}")]
+    BodyRequestsDiff {
+        /// The EIP-7685 request type.
+        request_type: u8,
+        /// The request data of this type, without the request type byte.
+        requests: GotExpectedBoxed<Bytes>,
+    },
+
+    /// Error when the requests of a single EIP-7685 request type violate the rules of that type.
+    #[error("invalid requests of type {request_type}: {reason}")]
+    InvalidRequests {
+        /// The EIP-7685 request type.
+        request_type: u8,
+        /// Why the requests are invalid.
+        reason: InvalidRequestsReason,
+    },
+
+    /// Error when an externally-provided validation proof could not be verified, see
+    /// [`ProofAssistedConsensus`].
+    #[error("invalid validation proof: {0}")]
+    InvalidValidationProof(String),
+
+    /// Error about a fork specific header field, with the hardfork the header looks like and the
+    /// hardfork the chain spec expects, see [`ConsensusError::with_fork_info`].
+    ///
+    /// This only attaches data to the underlying error: its [`code`](ConsensusError::code) is the
+    /// one of the underlying error, which is returned by [`ConsensusError::without_fork_info`].
+    #[error("{error} ({fork})")]
+    HardforkMismatch {
+        /// The underlying error.
+        error: Box<Self>,
+        /// The detected and expected hardfork of the header.
+        fork: ActiveForkInfo,
+    },
+
+    /// Error when the total difficulty of a pre-merge header doesn't match the total difficulty of
+    /// its parent plus its difficulty.
+    #[error("mismatched total difficulty of block {block_number}: {total_difficulty}")]
+    TotalDifficultyMismatch {
+        /// The block number.
+        block_number: BlockNumber,
+        /// The total difficulty.
+        total_difficulty: GotExpectedBoxed<U256>,
+    },
+
+    /// Error when the epoch accumulator root computed from pre-merge headers doesn't match the
+    /// expected one.
+    #[error("mismatched epoch accumulator root: {0}")]
+    EpochAccumulatorMismatch(GotExpectedBoxed<B256>),
+
+    /// Error when the number of blob sidecars doesn't match the number of blob transactions of
+    /// the block, see [`BlobSidecarConsensus`].
+    #[error("blob sidecar count mismatch: {0}")]
+    BlobSidecarCountMismatch(GotExpected<usize>),
+
+    /// Error when the blob sidecar of a transaction is invalid, see [`BlobSidecarConsensus`].
+    #[error(transparent)]
+    InvalidBlobSidecar(Box<InvalidBlobSidecar>),
+
+    /// Error when a block has more ommers than allowed, see [`OmmerValidator`].
+    #[error("block has {count} ommers, the maximum is {max}")]
+    TooManyOmmers {
+        /// The number of ommers of the block.
+        count: usize,
+        /// The maximum number of ommers of a block.
+        max: usize,
+    },
+
+    /// Error when an ommer is included twice, is an ancestor of the block or was already included
+    /// by an ancestor, see [`OmmerValidator`].
+    #[error("ommer {0} is already included")]
+    DuplicateOmmer(B256),
+
+    /// Error when an ommer doesn't branch off an ancestor within the ommer depth window, see
+    /// [`OmmerValidator`].
+    #[error("ommer {0} doesn't branch off a recent ancestor")]
+    DanglingOmmer(B256),
+
+    /// Error when the header of an ommer is invalid, see [`OmmerValidator`].
+    #[error("invalid ommer {hash}: {error}")]
+    InvalidOmmerHeader {
+        /// The hash of the ommer.
+        hash: B256,
+        /// Why the header of the ommer is invalid.
+        error: Box<Self>,
+    },
+
+    /// Custom error, likely an injected L2 error, see [`CustomConsensusError`].
     #[error("{0}")]
-    Other(String),
+    Custom(Box<dyn CustomConsensusError>),
 }
 
 impl ConsensusError {
@@ -405,6 +664,20 @@ impl ConsensusError {
     pub const fn is_state_root_error(&self) -> bool {
         matches!(self, Self::BodyStateRootDiff(_))
     }
+
+    /// Creates a new [`ConsensusError::Custom`] from the given error.
+    pub fn custom<E: CustomConsensusError>(err: E) -> Self {
+        Self::Custom(Box::new(err))
+    }
+
+    /// Returns a reference to the custom error if this is a [`ConsensusError::Custom`] of type
+    /// `E`.
+    pub fn as_custom<E: CustomConsensusError>(&self) -> Option<&E> {
+        match self {
+            Self::Custom(err) => err.as_any().downcast_ref(),
+            _ => None,
+        }
+    }
 }
 
 impl From<InvalidTransactionError> for ConsensusError {
@@ -413,7 +686,88 @@ impl From<InvalidTransactionError> for ConsensusError {
     }
 }
 
+impl From<rules::RuleError> for ConsensusError {
+    fn from(value: rules::RuleError) -> Self {
+        use rules::RuleError;
+
+        match value {
+            RuleError::HeaderGasUsedExceedsGasLimit { gas_used, gas_limit } => {
+                Self::HeaderGasUsedExceedsGasLimit { gas_used, gas_limit }
+            }
+            RuleError::HeaderGasLimitExceedsMax { gas_limit } => {
+                Self::HeaderGasLimitExceedsMax { gas_limit }
+            }
+            RuleError::GasLimitInvalidIncrease { parent_gas_limit, child_gas_limit } => {
+                Self::GasLimitInvalidIncrease { parent_gas_limit, child_gas_limit }
+            }
+            RuleError::GasLimitInvalidDecrease { parent_gas_limit, child_gas_limit } => {
+                Self::GasLimitInvalidDecrease { parent_gas_limit, child_gas_limit }
+            }
+            RuleError::GasLimitInvalidMinimum { child_gas_limit } => {
+                Self::GasLimitInvalidMinimum { child_gas_limit }
+            }
+            RuleError::BaseFeeDiff { got, expected } => {
+                Self::BaseFeeDiff(GotExpected { got, expected })
+            }
+            RuleError::BlobGasUsedNotMultipleOfBlobGasPerBlob { blob_gas_used } => {
+                Self::BlobGasUsedNotMultipleOfBlobGasPerBlob {
+                    blob_gas_used,
+                    blob_gas_per_blob: rules::DATA_GAS_PER_BLOB,
+                }
+            }
+            RuleError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+                blob_gas_used,
+                max_blob_gas_per_block,
+            } => {
+                Self::BlobGasUsedExceedsMaxBlobGasPerBlock { blob_gas_used, max_blob_gas_per_block }
+            }
+            RuleError::ExcessBlobGasDiff {
+                got,
+                expected,
+                parent_excess_blob_gas,
+                parent_blob_gas_used,
+            } => Self::ExcessBlobGasDiff {
+                diff: GotExpected { got, expected },
+                parent_excess_blob_gas,
+                parent_blob_gas_used,
+            },
+            RuleError::TimestampIsInPast { parent_timestamp, timestamp } => {
+                Self::TimestampIsInPast { parent_timestamp, timestamp }
+            }
+        }
+    }
+}
+
 /// `HeaderConsensusError` combines a `ConsensusError` with the `SealedHeader` it relates to.
 #[derive(thiserror::Error, Debug)]
 #[error("Consensus error: {0}, Invalid header: {1:?}")]
 pub struct HeaderConsensusError<H>(ConsensusError, SealedHeader<H>);
+
+/// An invalid blob sidecar, see [`ConsensusError::InvalidBlobSidecar`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid blob sidecar of transaction {tx_hash}: {reason}")]
+pub struct InvalidBlobSidecar {
+    /// The hash of the blob transaction.
+    pub tx_hash: B256,
+    /// Why the sidecar is invalid.
+    pub reason: String,
+}
+
+/// The reason a list of EIP-7685 requests of a single request type is invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidRequestsReason {
+    /// The request type is not known.
+    #[error("unknown request type")]
+    UnknownType,
+    /// The request type is not greater than the type of the preceding requests.
+    #[error("requests are not ordered by ascending request type")]
+    NotAscending,
+    /// The request data is not a sequence of requests of the fixed size of this type.
+    #[error("request data length {len} is not a multiple of the request size {size}")]
+    InvalidLength {
+        /// The length of the request data.
+        len: usize,
+        /// The size of a single request of this type.
+        size: usize,
+    },
+}
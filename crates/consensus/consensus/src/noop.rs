@@ -1,23 +1,41 @@
-use crate::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
+use crate::{
+    observe_validation, Consensus, ConsensusError, ConsensusObserver, FullConsensus,
+    HeaderValidator, ValidationStage,
+};
 use alloc::sync::Arc;
 use reth_execution_types::BlockExecutionResult;
 use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
 
 /// A Consensus implementation that does nothing.
-#[derive(Debug, Copy, Clone, Default)]
+#[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-pub struct NoopConsensus;
+pub struct NoopConsensus {
+    /// Optional observer that is notified about every (no-op) validation.
+    observer: Option<Arc<dyn ConsensusObserver>>,
+}
 
 impl NoopConsensus {
     /// Creates an Arc instance of Self.
     pub fn arc() -> Arc<Self> {
         Arc::new(Self::default())
     }
+
+    /// Sets the [`ConsensusObserver`] that is notified about every validation.
+    ///
+    /// Every validation is reported as a single `noop` rule.
+    pub fn with_observer(mut self, observer: Arc<dyn ConsensusObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    fn observe(&self, stage: ValidationStage) -> Result<(), ConsensusError> {
+        observe_validation(self.observer.as_deref(), stage, "noop", || Ok(()))
+    }
 }
 
 impl<H> HeaderValidator<H> for NoopConsensus {
     fn validate_header(&self, _header: &SealedHeader<H>) -> Result<(), ConsensusError> {
-        Ok(())
+        self.observe(ValidationStage::Header)
     }
 
     fn validate_header_against_parent(
@@ -25,7 +43,7 @@ impl<H> HeaderValidator<H> for NoopConsensus {
         _header: &SealedHeader<H>,
         _parent: &SealedHeader<H>,
     ) -> Result<(), ConsensusError> {
-        Ok(())
+        self.observe(ValidationStage::HeaderAgainstParent)
     }
 }
 
@@ -37,11 +55,11 @@ impl<B: Block> Consensus<B> for NoopConsensus {
         _body: &B::Body,
         _header: &SealedHeader<B::Header>,
     ) -> Result<(), Self::Error> {
-        Ok(())
+        self.observe(ValidationStage::BodyAgainstHeader)
     }
 
     fn validate_block_pre_execution(&self, _block: &SealedBlock<B>) -> Result<(), Self::Error> {
-        Ok(())
+        self.observe(ValidationStage::PreExecution)
     }
 }
 
@@ -51,6 +69,6 @@ impl<N: NodePrimitives> FullConsensus<N> for NoopConsensus {
         _block: &RecoveredBlock<N::Block>,
         _result: &BlockExecutionResult<N::Receipt>,
     ) -> Result<(), ConsensusError> {
-        Ok(())
+        self.observe(ValidationStage::PostExecution)
     }
 }
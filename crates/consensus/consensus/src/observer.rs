@@ -0,0 +1,76 @@
+//! Hooks for observing the outcome of consensus validation.
+
+use crate::ConsensusError;
+use core::{fmt::Debug, time::Duration};
+
+/// The validation step a [`ConsensusObserver`] is notified about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationStage {
+    /// [`HeaderValidator::validate_header`](crate::HeaderValidator::validate_header).
+    Header,
+    /// [`HeaderValidator::validate_header_against_parent`](crate::HeaderValidator::validate_header_against_parent).
+    HeaderAgainstParent,
+    /// [`Consensus::validate_body_against_header`](crate::Consensus::validate_body_against_header).
+    BodyAgainstHeader,
+    /// [`Consensus::validate_block_pre_execution`](crate::Consensus::validate_block_pre_execution).
+    PreExecution,
+    /// [`FullConsensus::validate_block_post_execution`](crate::FullConsensus::validate_block_post_execution).
+    PostExecution,
+}
+
+impl ValidationStage {
+    /// Returns the stage as a string, suitable for use as a metric label.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Header => "header",
+            Self::HeaderAgainstParent => "header_against_parent",
+            Self::BodyAgainstHeader => "body_against_header",
+            Self::PreExecution => "pre_execution",
+            Self::PostExecution => "post_execution",
+        }
+    }
+}
+
+/// Receives a callback for every validation rule applied by a consensus implementation.
+///
+/// This can be used to export per-rule validation latency and failure counts, e.g. as metrics,
+/// without wrapping every consensus call site.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ConsensusObserver: Debug + Send + Sync {
+    /// Called after the validation `rule` of the given `stage` completed.
+    ///
+    /// `rule` is the name of the rule, suitable for use as a metric label. The names match the
+    /// rules of a [`ValidationReport`](crate::ValidationReport). Validation stops at the first
+    /// failed rule, so the rules after it aren't reported.
+    ///
+    /// `elapsed` is the time spent in the rule. It is always [`Duration::ZERO`] without the `std`
+    /// feature.
+    fn on_validation(
+        &self,
+        stage: ValidationStage,
+        rule: &'static str,
+        elapsed: Duration,
+        result: Result<(), &ConsensusError>,
+    );
+}
+
+/// Runs the validation `rule` of the `stage` and reports its outcome to the `observer`, if any.
+pub fn observe_validation(
+    observer: Option<&dyn ConsensusObserver>,
+    stage: ValidationStage,
+    rule: &'static str,
+    f: impl FnOnce() -> Result<(), ConsensusError>,
+) -> Result<(), ConsensusError> {
+    let Some(observer) = observer else { return f() };
+
+    #[cfg(feature = "std")]
+    let start = std::time::Instant::now();
+    let result = f();
+    #[cfg(feature = "std")]
+    let elapsed = start.elapsed();
+    #[cfg(not(feature = "std"))]
+    let elapsed = Duration::ZERO;
+
+    observer.on_validation(stage, rule, elapsed, result.as_ref().copied());
+    result
+}
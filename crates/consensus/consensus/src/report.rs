@@ -0,0 +1,107 @@
+//! Reports of all failed validation rules of a block.
+
+use crate::ConsensusError;
+use alloc::vec::Vec;
+use core::time::Duration;
+
+/// The outcome of a single rule in a [`ValidationReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleOutcome {
+    /// The name of the rule, suitable for use as a metric label.
+    pub rule: &'static str,
+    /// The time spent in the rule. It is always [`Duration::ZERO`] without the `std` feature.
+    pub elapsed: Duration,
+    /// The error of the rule, if it failed.
+    pub error: Option<ConsensusError>,
+}
+
+impl RuleOutcome {
+    /// Returns `true` if the rule passed.
+    pub const fn is_ok(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// The outcome of every validation rule that was applied to a block.
+///
+/// Unlike the [`Consensus`](crate::Consensus) methods, which return on the first failed rule, a
+/// report runs all rules and collects every failure. This is useful for dry-running the validation
+/// of a block, e.g. to debug a block builder.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    outcomes: Vec<RuleOutcome>,
+}
+
+impl ValidationReport {
+    /// Creates an empty report.
+    pub const fn new() -> Self {
+        Self { outcomes: Vec::new() }
+    }
+
+    /// Runs the validation `f` and records its outcome and duration under the given `rule` name.
+    pub fn run(&mut self, rule: &'static str, f: impl FnOnce() -> Result<(), ConsensusError>) {
+        #[cfg(feature = "std")]
+        let start = std::time::Instant::now();
+        let result = f();
+        #[cfg(feature = "std")]
+        let elapsed = start.elapsed();
+        #[cfg(not(feature = "std"))]
+        let elapsed = Duration::ZERO;
+
+        self.outcomes.push(RuleOutcome { rule, elapsed, error: result.err() });
+    }
+
+    /// Appends all outcomes of the `other` report to this report.
+    pub fn append(&mut self, other: Self) {
+        self.outcomes.extend(other.outcomes);
+    }
+
+    /// Returns the outcomes of all rules, in the order they were run.
+    pub fn outcomes(&self) -> &[RuleOutcome] {
+        &self.outcomes
+    }
+
+    /// Returns an iterator over the outcomes of the failed rules.
+    pub fn failures(&self) -> impl Iterator<Item = &RuleOutcome> + '_ {
+        self.outcomes.iter().filter(|outcome| !outcome.is_ok())
+    }
+
+    /// Returns `true` if no rule failed.
+    pub fn is_valid(&self) -> bool {
+        self.outcomes.iter().all(RuleOutcome::is_ok)
+    }
+
+    /// Returns the total time spent in all rules.
+    pub fn total_elapsed(&self) -> Duration {
+        self.outcomes.iter().map(|outcome| outcome.elapsed).sum()
+    }
+
+    /// Converts the report into a result, returning the error of the first failed rule.
+    pub fn into_result(self) -> Result<(), ConsensusError> {
+        match self.outcomes.into_iter().find_map(|outcome| outcome.error) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collects_all_failures() {
+        let mut report = ValidationReport::new();
+        report.run("gas", || Err(ConsensusError::BaseFeeMissing));
+        report.run("extra_data", || Ok(()));
+        report.run("requests_hash", || Err(ConsensusError::RequestsHashMissing));
+
+        assert!(!report.is_valid());
+        assert_eq!(report.outcomes().len(), 3);
+        assert_eq!(
+            report.failures().map(|outcome| outcome.rule).collect::<Vec<_>>(),
+            ["gas", "requests_hash"]
+        );
+        assert_eq!(report.into_result(), Err(ConsensusError::BaseFeeMissing));
+    }
+}
@@ -1,7 +1,14 @@
-use crate::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
+use crate::{Consensus, ConsensusError, FullConsensus, HeaderValidator, ValidationStage};
+use alloc::{vec, vec::Vec};
+use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+use alloy_primitives::{Bytes, B256};
 use core::sync::atomic::{AtomicBool, Ordering};
+use reth_chainspec::EthChainSpec;
 use reth_execution_types::BlockExecutionResult;
-use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader};
+use reth_primitives_traits::{
+    constants::MAXIMUM_GAS_LIMIT_BLOCK, Block, BlockBody, GotExpected, NodePrimitives,
+    RecoveredBlock, SealedBlock, SealedHeader,
+};
 
 /// Consensus engine implementation for testing
 #[derive(Debug)]
@@ -105,3 +112,280 @@ impl<H> HeaderValidator<H> for TestConsensus {
         }
     }
 }
+
+/// A systematically invalid variant of a valid block, produced by [`BlockMutator`].
+#[derive(Debug, Clone)]
+pub struct InvalidBlockCase<B: Block> {
+    /// Short, stable name of the violated rule, e.g. `"bad_ommers_hash"`.
+    pub name: &'static str,
+    /// The validation that checks the violated rule.
+    ///
+    /// For [`ValidationStage::PostExecution`], the block is validated against the execution
+    /// result of the valid block.
+    pub stage: ValidationStage,
+    /// The mutated and resealed block.
+    pub block: SealedBlock<B>,
+    /// The error a validator is expected to return for the violated rule.
+    ///
+    /// Validators may attach the hardfork info to errors of missing hardfork fields, see
+    /// [`ConsensusError::with_fork_info`].
+    pub expected: ConsensusError,
+}
+
+/// Generates invalid variants of a valid block, each annotated with the [`ConsensusError`] the
+/// violated rule is expected to produce.
+///
+/// Every case breaks exactly one rule, so the expected error is the one returned by the check for
+/// that rule. This allows rule-implementing crates to run a shared conformance suite against their
+/// [`HeaderValidator`] and [`Consensus`] implementations.
+#[derive(Debug, Clone)]
+pub struct BlockMutator<B: Block> {
+    /// The parent of `block`.
+    parent: SealedHeader<B::Header>,
+    /// The valid block to mutate.
+    block: SealedBlock<B>,
+    /// The cumulative gas used after each transaction of the valid block, if known.
+    gas_spent_by_tx: Option<Vec<(u64, u64)>>,
+    /// The maximum extra data size of the chain the block belongs to.
+    max_extra_data_size: usize,
+}
+
+impl<B> BlockMutator<B>
+where
+    B: Block<Header = Header>,
+{
+    /// Creates a new mutator for the given valid block and its parent.
+    ///
+    /// Chain specific limits, like the maximum extra data size, are read from the `chain_spec` the
+    /// block is validated against.
+    pub fn new<ChainSpec: EthChainSpec>(
+        chain_spec: &ChainSpec,
+        parent: SealedHeader<Header>,
+        block: SealedBlock<B>,
+    ) -> Self {
+        Self {
+            parent,
+            block,
+            gas_spent_by_tx: None,
+            max_extra_data_size: chain_spec.max_extra_data_size(),
+        }
+    }
+
+    /// Sets the cumulative gas used after each transaction of the valid block, as returned by
+    /// [`gas_spent_by_transactions`](reth_primitives_traits::receipt::gas_spent_by_transactions)
+    /// for its receipts.
+    ///
+    /// This is required for the `wrong_gas_used` case of blocks with transactions.
+    pub fn with_gas_spent_by_tx(mut self, gas_spent_by_tx: Vec<(u64, u64)>) -> Self {
+        self.gas_spent_by_tx = Some(gas_spent_by_tx);
+        self
+    }
+
+    /// Returns the parent header.
+    pub const fn parent(&self) -> &SealedHeader<Header> {
+        &self.parent
+    }
+
+    /// Returns the valid block.
+    pub const fn block(&self) -> &SealedBlock<B> {
+        &self.block
+    }
+
+    /// Returns all invalid variants of the block.
+    ///
+    /// Cases that don't apply to the block are skipped, e.g. a missing withdrawals root is only
+    /// generated for blocks that have one, and a wrong gas used only for blocks without
+    /// transactions or with [known gas usage](Self::with_gas_spent_by_tx).
+    pub fn cases(&self) -> Vec<InvalidBlockCase<B>> {
+        let header = self.block.header();
+        let body = self.block.body();
+
+        let mut cases = vec![
+            self.mutate_header("gas_used_exceeds_gas_limit", ValidationStage::Header, |header| {
+                header.gas_used = header.gas_limit + 1;
+                ConsensusError::HeaderGasUsedExceedsGasLimit {
+                    gas_used: header.gas_used,
+                    gas_limit: header.gas_limit,
+                }
+            }),
+            self.mutate_header("gas_limit_exceeds_max", ValidationStage::Header, |header| {
+                header.gas_limit = MAXIMUM_GAS_LIMIT_BLOCK + 1;
+                ConsensusError::HeaderGasLimitExceedsMax { gas_limit: header.gas_limit }
+            }),
+            self.mutate_header("extra_data_exceeds_max", ValidationStage::Header, |header| {
+                header.extra_data = Bytes::from(vec![0u8; self.max_extra_data_size + 1]);
+                ConsensusError::ExtraDataExceedsMax {
+                    len: header.extra_data.len(),
+                    max: self.max_extra_data_size,
+                }
+            }),
+            self.mutate_header("bad_ommers_hash", ValidationStage::PreExecution, |header| {
+                header.ommers_hash = invalid_root(header.ommers_hash);
+                ConsensusError::BodyOmmersHashDiff(
+                    GotExpected {
+                        got: body.calculate_ommers_root().unwrap_or(EMPTY_OMMER_ROOT_HASH),
+                        expected: header.ommers_hash,
+                    }
+                    .into(),
+                )
+            }),
+            self.mutate_header("bad_transactions_root", ValidationStage::PreExecution, |header| {
+                header.transactions_root = invalid_root(header.transactions_root);
+                ConsensusError::BodyTransactionRootDiff(
+                    GotExpected {
+                        got: body.calculate_tx_root(),
+                        expected: header.transactions_root,
+                    }
+                    .into(),
+                )
+            }),
+            self.mutate_header("stale_timestamp", ValidationStage::HeaderAgainstParent, |header| {
+                header.timestamp = self.parent.timestamp;
+                ConsensusError::TimestampIsInPast {
+                    parent_timestamp: self.parent.timestamp,
+                    timestamp: header.timestamp,
+                }
+            }),
+            self.mutate_header(
+                "parent_number_mismatch",
+                ValidationStage::HeaderAgainstParent,
+                |header| {
+                    header.number = self.parent.number + 2;
+                    ConsensusError::ParentBlockNumberMismatch {
+                        parent_block_number: self.parent.number,
+                        block_number: header.number,
+                    }
+                },
+            ),
+            self.mutate_header(
+                "parent_hash_mismatch",
+                ValidationStage::HeaderAgainstParent,
+                |header| {
+                    header.parent_hash = invalid_root(self.parent.hash());
+                    ConsensusError::ParentHashMismatch(
+                        GotExpected { got: header.parent_hash, expected: self.parent.hash() }
+                            .into(),
+                    )
+                },
+            ),
+        ];
+
+        if header.base_fee_per_gas.is_some() {
+            cases.push(self.mutate_header("base_fee_missing", ValidationStage::Header, |header| {
+                header.base_fee_per_gas = None;
+                ConsensusError::BaseFeeMissing
+            }));
+        }
+
+        if header.withdrawals_root.is_some() {
+            cases.push(self.mutate_header(
+                "withdrawals_root_missing",
+                ValidationStage::Header,
+                |header| {
+                    header.withdrawals_root = None;
+                    ConsensusError::WithdrawalsRootMissing
+                },
+            ));
+        }
+
+        if header.blob_gas_used.is_some() {
+            cases.push(self.mutate_header(
+                "blob_gas_used_missing",
+                ValidationStage::Header,
+                |header| {
+                    header.blob_gas_used = None;
+                    ConsensusError::BlobGasUsedMissing
+                },
+            ));
+        }
+
+        let gas_spent_by_tx =
+            self.gas_spent_by_tx.clone().or_else(|| body.transactions().is_empty().then(Vec::new));
+        if let Some(gas_spent_by_tx) = gas_spent_by_tx {
+            cases.push(self.mutate_header(
+                "wrong_gas_used",
+                ValidationStage::PostExecution,
+                |header| {
+                    // the block is validated against the execution result of the valid block
+                    let gas_used = header.gas_used;
+                    header.gas_used =
+                        if gas_used < header.gas_limit { gas_used + 1 } else { gas_used - 1 };
+                    ConsensusError::BlockGasUsed {
+                        gas: GotExpected { got: gas_used, expected: header.gas_used },
+                        gas_spent_by_tx,
+                    }
+                },
+            ));
+        }
+
+        if header.parent_beacon_block_root.is_some() {
+            cases.push(self.mutate_header(
+                "parent_beacon_block_root_missing",
+                ValidationStage::Header,
+                |header| {
+                    header.parent_beacon_block_root = None;
+                    ConsensusError::ParentBeaconBlockRootMissing
+                },
+            ));
+        }
+
+        cases
+    }
+
+    /// Applies `f` to a copy of the block header, reseals the block and returns the case with the
+    /// error returned by `f`.
+    fn mutate_header(
+        &self,
+        name: &'static str,
+        stage: ValidationStage,
+        f: impl FnOnce(&mut Header) -> ConsensusError,
+    ) -> InvalidBlockCase<B> {
+        let (mut header, body) = self.block.clone().split_header_body();
+        let expected = f(&mut header);
+        InvalidBlockCase { name, stage, block: SealedBlock::seal_parts(header, body), expected }
+    }
+}
+
+/// Returns a root that is guaranteed to differ from `root`.
+fn invalid_root(root: B256) -> B256 {
+    if root == B256::ZERO {
+        B256::with_last_byte(1)
+    } else {
+        B256::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{BlockBody as AlloyBlockBody, TxEnvelope};
+    use reth_chainspec::ChainSpec;
+
+    type TestBlock = alloy_consensus::Block<TxEnvelope>;
+
+    #[test]
+    fn block_mutator_cases() {
+        let parent = SealedHeader::seal_slow(Header {
+            number: 1,
+            timestamp: 12,
+            gas_limit: 30_000_000,
+            ..Default::default()
+        });
+        let header = Header {
+            parent_hash: parent.hash(),
+            number: 2,
+            timestamp: 24,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            withdrawals_root: Some(B256::with_last_byte(1)),
+            ..Default::default()
+        };
+        let block = SealedBlock::<TestBlock>::seal_parts(header, AlloyBlockBody::default());
+
+        let cases = BlockMutator::new(&ChainSpec::default(), parent, block.clone()).cases();
+        assert_eq!(cases.len(), 11);
+        for case in cases {
+            assert_ne!(case.block.hash(), block.hash(), "{}", case.name);
+        }
+    }
+}
@@ -0,0 +1,702 @@
+//! Collection of methods for block validation.
+//!
+//! These are pure functions of the validated data and the chain spec, they don't depend on a
+//! provider, an executor or the local clock. This allows verifiers that don't run a full node,
+//! e.g. zkVM guest programs, to apply exactly the same rules as reth.
+
+use crate::{rules, ConsensusError, ValidationReport};
+use alloy_consensus::{BlockHeader as _, EMPTY_OMMER_ROOT_HASH};
+use alloy_eips::eip7840::BlobParams;
+use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks};
+use reth_primitives_traits::{
+    Block, BlockBody, BlockHeader, GotExpected, SealedBlock, SealedHeader,
+};
+
+/// Gas used needs to be less than gas limit. Gas used is going to be checked after execution.
+#[inline]
+pub fn validate_header_gas<H: BlockHeader>(header: &H) -> Result<(), ConsensusError> {
+    Ok(rules::validate_gas_used(header.gas_used(), header.gas_limit())?)
+}
+
+/// Ensure the EIP-1559 base fee is set if the London hardfork is active.
+#[inline]
+pub fn validate_header_base_fee<H: BlockHeader, ChainSpec: EthereumHardforks>(
+    header: &H,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    if chain_spec.is_london_active_at_block(header.number()) && header.base_fee_per_gas().is_none()
+    {
+        return Err(ConsensusError::BaseFeeMissing)
+    }
+    Ok(())
+}
+
+/// Validate that withdrawals are present in Shanghai
+///
+/// See [EIP-4895]: Beacon chain push withdrawals as operations
+///
+/// [EIP-4895]: https://eips.ethereum.org/EIPS/eip-4895
+#[inline]
+pub fn validate_shanghai_withdrawals<B: Block>(
+    block: &SealedBlock<B>,
+) -> Result<(), ConsensusError> {
+    let withdrawals = block.body().withdrawals().ok_or(ConsensusError::BodyWithdrawalsMissing)?;
+    let withdrawals_root = alloy_consensus::proofs::calculate_withdrawals_root(withdrawals);
+    let header_withdrawals_root =
+        block.withdrawals_root().ok_or(ConsensusError::WithdrawalsRootMissing)?;
+    if withdrawals_root != *header_withdrawals_root {
+        return Err(ConsensusError::BodyWithdrawalsRootDiff(
+            GotExpected { got: withdrawals_root, expected: header_withdrawals_root }.into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Validate that blob gas is present in the block if Cancun is active.
+///
+/// See [EIP-4844]: Shard Blob Transactions
+///
+/// [EIP-4844]: https://eips.ethereum.org/EIPS/eip-4844
+#[inline]
+pub fn validate_cancun_gas<B: Block>(block: &SealedBlock<B>) -> Result<(), ConsensusError> {
+    // Check that the blob gas used in the header matches the sum of the blob gas used by each
+    // blob tx
+    let header_blob_gas_used = block.blob_gas_used().ok_or(ConsensusError::BlobGasUsedMissing)?;
+    let total_blob_gas = block.body().blob_gas_used();
+    if total_blob_gas != header_blob_gas_used {
+        return Err(ConsensusError::BlobGasUsedDiff(GotExpected {
+            got: header_blob_gas_used,
+            expected: total_blob_gas,
+        }));
+    }
+    Ok(())
+}
+
+/// Ensures the block response data matches the header.
+///
+/// This ensures the body response items match the header's hashes:
+///   - ommer hash
+///   - transaction root
+///   - withdrawals root
+pub fn validate_body_against_header<B, H>(body: &B, header: &H) -> Result<(), ConsensusError>
+where
+    B: BlockBody,
+    H: BlockHeader,
+{
+    validate_body_ommers_hash(body, header)?;
+    validate_body_transaction_root(body, header)?;
+    validate_body_withdrawals_root(body, header)
+}
+
+/// Validates that the ommers hash in the header matches the ommers of the body.
+#[inline]
+pub fn validate_body_ommers_hash<B, H>(body: &B, header: &H) -> Result<(), ConsensusError>
+where
+    B: BlockBody,
+    H: BlockHeader,
+{
+    let ommers_hash = body.calculate_ommers_root();
+    if Some(header.ommers_hash()) != ommers_hash {
+        return Err(ConsensusError::BodyOmmersHashDiff(
+            GotExpected {
+                got: ommers_hash.unwrap_or(EMPTY_OMMER_ROOT_HASH),
+                expected: header.ommers_hash(),
+            }
+            .into(),
+        ))
+    }
+    Ok(())
+}
+
+/// Validates that the transactions root in the header matches the transactions of the body.
+#[inline]
+pub fn validate_body_transaction_root<B, H>(body: &B, header: &H) -> Result<(), ConsensusError>
+where
+    B: BlockBody,
+    H: BlockHeader,
+{
+    let tx_root = body.calculate_tx_root();
+    if header.transactions_root() != tx_root {
+        return Err(ConsensusError::BodyTransactionRootDiff(
+            GotExpected { got: tx_root, expected: header.transactions_root() }.into(),
+        ))
+    }
+    Ok(())
+}
+
+/// Validates that the withdrawals root in the header matches the withdrawals of the body, and
+/// that either both or neither of them are present.
+#[inline]
+pub fn validate_body_withdrawals_root<B, H>(body: &B, header: &H) -> Result<(), ConsensusError>
+where
+    B: BlockBody,
+    H: BlockHeader,
+{
+    match (header.withdrawals_root(), body.calculate_withdrawals_root()) {
+        (Some(header_withdrawals_root), Some(withdrawals_root)) => {
+            if withdrawals_root != header_withdrawals_root {
+                return Err(ConsensusError::BodyWithdrawalsRootDiff(
+                    GotExpected { got: withdrawals_root, expected: header_withdrawals_root }.into(),
+                ))
+            }
+        }
+        (None, None) => {
+            // this is ok because we assume the fork is not active in this case
+        }
+        _ => return Err(ConsensusError::WithdrawalsRootUnexpected),
+    }
+
+    Ok(())
+}
+
+/// Validate a block without regard for state:
+///
+/// - Compares the ommer hash in the block header to the block body
+/// - Compares the transactions root in the block header to the block body
+/// - Pre-execution transaction validation
+pub fn validate_block_pre_execution<B, ChainSpec>(
+    block: &SealedBlock<B>,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError>
+where
+    B: Block,
+    ChainSpec: EthereumHardforks,
+{
+    validate_block_ommers_hash(block)?;
+    validate_block_transaction_root(block)?;
+
+    // EIP-4895: Beacon chain push withdrawals as operations
+    if chain_spec.is_shanghai_active_at_timestamp(block.timestamp()) {
+        validate_shanghai_withdrawals(block)?;
+    }
+
+    if chain_spec.is_cancun_active_at_timestamp(block.timestamp()) {
+        validate_cancun_gas(block)?;
+    }
+
+    Ok(())
+}
+
+/// Validates that the ommers hash in the block header matches the ommers of the block body.
+#[inline]
+pub fn validate_block_ommers_hash<B: Block>(block: &SealedBlock<B>) -> Result<(), ConsensusError> {
+    let ommers_hash = block.body().calculate_ommers_root();
+    if Some(block.ommers_hash()) != ommers_hash {
+        return Err(ConsensusError::BodyOmmersHashDiff(
+            GotExpected {
+                got: ommers_hash.unwrap_or(EMPTY_OMMER_ROOT_HASH),
+                expected: block.ommers_hash(),
+            }
+            .into(),
+        ))
+    }
+    Ok(())
+}
+
+/// Validates that the transactions root in the block header matches the transactions of the block
+/// body.
+#[inline]
+pub fn validate_block_transaction_root<B: Block>(
+    block: &SealedBlock<B>,
+) -> Result<(), ConsensusError> {
+    block
+        .ensure_transaction_root_valid()
+        .map_err(|error| ConsensusError::BodyTransactionRootDiff(error.into()))
+}
+
+/// Validates that the EIP-4844 header fields exist and conform to the spec. This ensures that:
+///
+///  * `blob_gas_used` exists as a header field
+///  * `excess_blob_gas` exists as a header field
+///  * `parent_beacon_block_root` exists as a header field
+///  * `blob_gas_used` is a multiple of `DATA_GAS_PER_BLOB`
+///  * `excess_blob_gas` is a multiple of `DATA_GAS_PER_BLOB`
+///  * `blob_gas_used` doesn't exceed the max allowed blob gas based on the given params
+///
+/// Note: This does not enforce any restrictions on `blob_gas_used`
+pub fn validate_4844_header_standalone<H: BlockHeader>(
+    header: &H,
+    blob_params: BlobParams,
+) -> Result<(), ConsensusError> {
+    let blob_gas_used = header.blob_gas_used().ok_or(ConsensusError::BlobGasUsedMissing)?;
+
+    if header.parent_beacon_block_root().is_none() {
+        return Err(ConsensusError::ParentBeaconBlockRootMissing)
+    }
+
+    Ok(rules::validate_blob_gas_used(blob_gas_used, blob_params.max_blob_gas_per_block())?)
+}
+
+/// Validates a header on its own, without its parent, according to the Ethereum consensus rules.
+///
+/// This checks:
+///  * the post-merge difficulty, nonce and ommers hash
+///  * the extra data size, gas usage and base fee
+///  * the presence of the fork specific fields: withdrawals root, EIP-4844 fields and requests hash
+///
+/// Note: This doesn't check whether the timestamp of pre-merge headers is in the future, because
+/// that depends on the local clock.
+pub fn validate_header_standalone<H, ChainSpec>(
+    header: &H,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError>
+where
+    H: BlockHeader,
+    ChainSpec: EthChainSpec + EthereumHardforks,
+{
+    validate_header_merge_fields(header, chain_spec)?;
+    validate_header_extra_data(header, chain_spec.max_extra_data_size())?;
+    validate_header_gas(header)?;
+    validate_header_base_fee(header, chain_spec)?;
+    validate_header_withdrawals_root(header, chain_spec)?;
+    validate_header_blob_fields(header, chain_spec)?;
+    validate_header_requests_hash(header, chain_spec)?;
+
+    Ok(())
+}
+
+/// Validates that the difficulty and nonce are zero and the ommers hash is empty once the merge is
+/// active.
+#[inline]
+pub fn validate_header_merge_fields<H: BlockHeader, ChainSpec: EthereumHardforks>(
+    header: &H,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    if chain_spec.is_paris_active_at_block(header.number()) {
+        if !header.difficulty().is_zero() {
+            return Err(ConsensusError::TheMergeDifficultyIsNotZero)
+        }
+
+        if !header.nonce().is_some_and(|nonce| nonce.is_zero()) {
+            return Err(ConsensusError::TheMergeNonceIsNotZero)
+        }
+
+        if header.ommers_hash() != EMPTY_OMMER_ROOT_HASH {
+            return Err(ConsensusError::TheMergeOmmerRootIsNotEmpty)
+        }
+    }
+    Ok(())
+}
+
+/// Validates that the withdrawals root is present if and only if Shanghai is active.
+///
+/// See [EIP-4895]: Beacon chain push withdrawals as operations
+///
+/// [EIP-4895]: https://eips.ethereum.org/EIPS/eip-4895
+#[inline]
+pub fn validate_header_withdrawals_root<H: BlockHeader, ChainSpec: EthereumHardforks>(
+    header: &H,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    let shanghai = chain_spec.is_shanghai_active_at_timestamp(header.timestamp());
+    if shanghai && header.withdrawals_root().is_none() {
+        return Err(ConsensusError::WithdrawalsRootMissing)
+    } else if !shanghai && header.withdrawals_root().is_some() {
+        return Err(ConsensusError::WithdrawalsRootUnexpected)
+    }
+    Ok(())
+}
+
+/// Validates that the EIP-4844 fields are valid once Cancun is active, and absent before.
+///
+/// See also [`validate_4844_header_standalone`].
+#[inline]
+pub fn validate_header_blob_fields<H, ChainSpec>(
+    header: &H,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError>
+where
+    H: BlockHeader,
+    ChainSpec: EthChainSpec + EthereumHardforks,
+{
+    if chain_spec.is_cancun_active_at_timestamp(header.timestamp()) {
+        validate_4844_header_standalone(
+            header,
+            chain_spec
+                .blob_params_at_timestamp(header.timestamp())
+                .unwrap_or_else(BlobParams::cancun),
+        )?;
+    } else if header.blob_gas_used().is_some() {
+        return Err(ConsensusError::BlobGasUsedUnexpected)
+    } else if header.excess_blob_gas().is_some() {
+        return Err(ConsensusError::ExcessBlobGasUnexpected)
+    } else if header.parent_beacon_block_root().is_some() {
+        return Err(ConsensusError::ParentBeaconBlockRootUnexpected)
+    }
+    Ok(())
+}
+
+/// Validates that the requests hash is present if and only if Prague is active.
+#[inline]
+pub fn validate_header_requests_hash<H: BlockHeader, ChainSpec: EthereumHardforks>(
+    header: &H,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    if chain_spec.is_prague_active_at_timestamp(header.timestamp()) {
+        if header.requests_hash().is_none() {
+            return Err(ConsensusError::RequestsHashMissing)
+        }
+    } else if header.requests_hash().is_some() {
+        return Err(ConsensusError::RequestsHashUnexpected)
+    }
+    Ok(())
+}
+
+/// Validates a header against its parent according to the Ethereum consensus rules.
+///
+/// This checks the parent hash and number, the timestamp, the gas limit, the EIP-1559 base fee and
+/// the EIP-4844 blob gas fields.
+pub fn validate_header_against_parent<ChainSpec: EthChainSpec + EthereumHardforks>(
+    header: &ChainSpec::Header,
+    parent: &SealedHeader<ChainSpec::Header>,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    validate_against_parent_hash_number(header, parent)?;
+
+    validate_against_parent_timestamp(header, parent.header())?;
+
+    // TODO Check difficulty increment between parent and self
+    // Ace age did increment it by some formula that we need to follow.
+    validate_against_parent_gas_limit(header, parent.header(), chain_spec)?;
+
+    validate_against_parent_eip1559_base_fee(header, parent.header(), chain_spec)?;
+
+    // ensure that the blob gas fields for this block
+    if let Some(blob_params) = chain_spec.blob_params_at_timestamp(header.timestamp()) {
+        validate_against_parent_4844(header, parent.header(), blob_params, chain_spec)?;
+    }
+
+    Ok(())
+}
+
+/// Runs all pre-execution rules of the Ethereum consensus on the block and its parent, and returns
+/// a [`ValidationReport`] with the outcome and duration of every rule.
+///
+/// This applies the same rules as [`validate_header_standalone`],
+/// [`validate_header_against_parent`], [`validate_body_against_header`] and
+/// [`validate_block_pre_execution`], but doesn't stop at the first failed rule.
+pub fn validate_block_full_report<B, ChainSpec>(
+    block: &SealedBlock<B>,
+    parent: &SealedHeader<B::Header>,
+    chain_spec: &ChainSpec,
+) -> ValidationReport
+where
+    B: Block,
+    ChainSpec: EthChainSpec<Header = B::Header> + EthereumHardforks,
+{
+    let header = block.header();
+    let timestamp = header.timestamp();
+    let mut report = ValidationReport::new();
+    let with_fork_info = |err: ConsensusError| err.with_fork_info(header, chain_spec);
+
+    // header
+    report.run("merge_fields", || {
+        validate_header_merge_fields(header, chain_spec).map_err(with_fork_info)
+    });
+    let max_extra_data_size = chain_spec.max_extra_data_size();
+    report.run("extra_data", || validate_header_extra_data(header, max_extra_data_size));
+    report.run("gas", || validate_header_gas(header));
+    report.run("base_fee", || validate_header_base_fee(header, chain_spec).map_err(with_fork_info));
+    report.run("withdrawals_root", || {
+        validate_header_withdrawals_root(header, chain_spec).map_err(with_fork_info)
+    });
+    report.run("blob_fields", || {
+        validate_header_blob_fields(header, chain_spec).map_err(with_fork_info)
+    });
+    report.run("requests_hash", || {
+        validate_header_requests_hash(header, chain_spec).map_err(with_fork_info)
+    });
+
+    // header against parent
+    report.run("parent_hash_number", || validate_against_parent_hash_number(header, parent));
+    report.run("parent_timestamp", || validate_against_parent_timestamp(header, parent.header()));
+    report.run("parent_gas_limit", || {
+        validate_against_parent_gas_limit(header, parent.header(), chain_spec)
+    });
+    report.run("parent_base_fee", || {
+        validate_against_parent_eip1559_base_fee(header, parent.header(), chain_spec)
+    });
+    if let Some(blob_params) = chain_spec.blob_params_at_timestamp(timestamp) {
+        report.run("parent_blob_gas", || {
+            validate_against_parent_4844(header, parent.header(), blob_params, chain_spec)
+        });
+    }
+
+    // body
+    report.run("ommers_hash", || validate_block_ommers_hash(block));
+    report.run("transaction_root", || validate_block_transaction_root(block));
+    if chain_spec.is_shanghai_active_at_timestamp(timestamp) {
+        report.run("withdrawals", || validate_shanghai_withdrawals(block));
+    }
+    if chain_spec.is_cancun_active_at_timestamp(timestamp) {
+        report.run("blob_gas_used", || validate_cancun_gas(block));
+    }
+
+    report
+}
+
+/// Validates the header's extra data according to the beacon consensus rules.
+///
+/// From yellow paper: extraData: An arbitrary byte array containing data relevant to this block.
+/// This must be 32 bytes or fewer; formally Hx.
+///
+/// Chains may allow longer extra data, see [`EthChainSpec::max_extra_data_size`].
+#[inline]
+pub fn validate_header_extra_data<H: BlockHeader>(
+    header: &H,
+    max_extra_data_size: usize,
+) -> Result<(), ConsensusError> {
+    let extra_data_len = header.extra_data().len();
+    if extra_data_len > max_extra_data_size {
+        Err(ConsensusError::ExtraDataExceedsMax { len: extra_data_len, max: max_extra_data_size })
+    } else {
+        Ok(())
+    }
+}
+
+/// Validates against the parent hash and number.
+///
+/// This function ensures that the header block number is sequential and that the hash of the parent
+/// header matches the parent hash in the header.
+#[inline]
+pub fn validate_against_parent_hash_number<H: BlockHeader>(
+    header: &H,
+    parent: &SealedHeader<H>,
+) -> Result<(), ConsensusError> {
+    // Parent number is consistent.
+    if parent.number() + 1 != header.number() {
+        return Err(ConsensusError::ParentBlockNumberMismatch {
+            parent_block_number: parent.number(),
+            block_number: header.number(),
+        })
+    }
+
+    if parent.hash() != header.parent_hash() {
+        return Err(ConsensusError::ParentHashMismatch(
+            GotExpected { got: header.parent_hash(), expected: parent.hash() }.into(),
+        ))
+    }
+
+    Ok(())
+}
+
+/// Validates the base fee against the parent and EIP-1559 rules.
+#[inline]
+pub fn validate_against_parent_eip1559_base_fee<ChainSpec: EthChainSpec + EthereumHardforks>(
+    header: &ChainSpec::Header,
+    parent: &ChainSpec::Header,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    if chain_spec.is_london_active_at_block(header.number()) {
+        let base_fee = header.base_fee_per_gas().ok_or(ConsensusError::BaseFeeMissing)?;
+
+        let expected_base_fee = if chain_spec
+            .ethereum_fork_activation(EthereumHardfork::London)
+            .transitions_at_block(header.number())
+        {
+            alloy_eips::eip1559::INITIAL_BASE_FEE
+        } else {
+            chain_spec
+                .next_block_base_fee(parent, header.timestamp())
+                .ok_or(ConsensusError::BaseFeeMissing)?
+        };
+        rules::validate_base_fee(base_fee, expected_base_fee)?;
+    }
+
+    Ok(())
+}
+
+/// Checks the gas limit for consistency between parent and header.
+///
+/// The maximum allowable difference between the header and parent gas limits is determined by the
+/// parent's gas limit divided by the
+/// [`GAS_LIMIT_BOUND_DIVISOR`](reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR), see
+/// [`rules::validate_gas_limit_delta`].
+pub fn validate_against_parent_gas_limit<H, ChainSpec>(
+    header: &H,
+    parent: &H,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError>
+where
+    H: BlockHeader,
+    ChainSpec: EthChainSpec + EthereumHardforks,
+{
+    // Determine the parent gas limit, considering elasticity multiplier on the London fork.
+    let parent_gas_limit = if !chain_spec.is_london_active_at_block(parent.number()) &&
+        chain_spec.is_london_active_at_block(header.number())
+    {
+        parent.gas_limit() *
+            chain_spec.base_fee_params_at_timestamp(header.timestamp()).elasticity_multiplier
+                as u64
+    } else {
+        parent.gas_limit()
+    };
+
+    Ok(rules::validate_gas_limit_delta(parent_gas_limit, header.gas_limit())?)
+}
+
+/// Validates the timestamp against the parent to make sure it is in the past.
+#[inline]
+pub fn validate_against_parent_timestamp<H: BlockHeader>(
+    header: &H,
+    parent: &H,
+) -> Result<(), ConsensusError> {
+    Ok(rules::validate_timestamp(parent.timestamp(), header.timestamp())?)
+}
+
+/// Validates that the EIP-4844 header fields are correct with respect to the parent block. This
+/// ensures that the `blob_gas_used` and `excess_blob_gas` fields exist in the child header, and
+/// that the `excess_blob_gas` field matches the expected `excess_blob_gas` calculated from the
+/// parent header fields.
+///
+/// The expected `excess_blob_gas` is computed with [`EthChainSpec::next_block_excess_blob_gas`],
+/// so that chains with a modified blob fee market are validated against their own update rule.
+pub fn validate_against_parent_4844<H, ChainSpec>(
+    header: &H,
+    parent: &H,
+    blob_params: BlobParams,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError>
+where
+    H: BlockHeader,
+    ChainSpec: EthChainSpec,
+{
+    // From [EIP-4844](https://eips.ethereum.org/EIPS/eip-4844#header-extension):
+    //
+    // > For the first post-fork block, both parent.blob_gas_used and parent.excess_blob_gas
+    // > are evaluated as 0.
+    //
+    // This means in the first post-fork block, calc_excess_blob_gas will return 0.
+    let parent_blob_gas_used = parent.blob_gas_used().unwrap_or(0);
+    let parent_excess_blob_gas = parent.excess_blob_gas().unwrap_or(0);
+
+    if header.blob_gas_used().is_none() {
+        return Err(ConsensusError::BlobGasUsedMissing)
+    }
+    let excess_blob_gas = header.excess_blob_gas().ok_or(ConsensusError::ExcessBlobGasMissing)?;
+
+    let expected_excess_blob_gas = chain_spec.next_block_excess_blob_gas(
+        blob_params,
+        parent_excess_blob_gas,
+        parent_blob_gas_used,
+    );
+    Ok(rules::validate_excess_blob_gas(
+        excess_blob_gas,
+        expected_excess_blob_gas,
+        parent_excess_blob_gas,
+        parent_blob_gas_used,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{BlockBody, Header, TxEip4844};
+    use alloy_eips::{eip4844::DATA_GAS_PER_BLOB, eip4895::Withdrawals};
+    use alloy_primitives::{Address, Bytes, Signature, U256};
+    use rand::Rng;
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_ethereum_primitives::{Transaction, TransactionSigned};
+    use reth_primitives_traits::{constants, proofs};
+
+    #[test]
+    fn rules_constants() {
+        assert_eq!(rules::MINIMUM_GAS_LIMIT, constants::MINIMUM_GAS_LIMIT);
+        assert_eq!(rules::MAXIMUM_GAS_LIMIT_BLOCK, constants::MAXIMUM_GAS_LIMIT_BLOCK);
+        assert_eq!(rules::GAS_LIMIT_BOUND_DIVISOR, constants::GAS_LIMIT_BOUND_DIVISOR);
+        assert_eq!(rules::DATA_GAS_PER_BLOB, DATA_GAS_PER_BLOB);
+    }
+
+    fn mock_blob_tx(nonce: u64, num_blobs: usize) -> TransactionSigned {
+        let mut rng = rand::rng();
+        let request = Transaction::Eip4844(TxEip4844 {
+            chain_id: 1u64,
+            nonce,
+            max_fee_per_gas: 0x28f000fff,
+            max_priority_fee_per_gas: 0x28f000fff,
+            max_fee_per_blob_gas: 0x7,
+            gas_limit: 10,
+            to: Address::default(),
+            value: U256::from(3_u64),
+            input: Bytes::from(vec![1, 2]),
+            access_list: Default::default(),
+            blob_versioned_hashes: std::iter::repeat_with(|| rng.random())
+                .take(num_blobs)
+                .collect(),
+        });
+
+        let signature = Signature::new(U256::default(), U256::default(), true);
+
+        TransactionSigned::new_unhashed(request, signature)
+    }
+
+    #[test]
+    fn cancun_block_incorrect_blob_gas_used() {
+        let chain_spec = ChainSpecBuilder::mainnet().cancun_activated().build();
+
+        // create a tx with 10 blobs
+        let transaction = mock_blob_tx(1, 10);
+
+        let header = Header {
+            base_fee_per_gas: Some(1337),
+            withdrawals_root: Some(proofs::calculate_withdrawals_root(&[])),
+            blob_gas_used: Some(1),
+            transactions_root: proofs::calculate_transaction_root(std::slice::from_ref(
+                &transaction,
+            )),
+            ..Default::default()
+        };
+        let body = BlockBody {
+            transactions: vec![transaction],
+            ommers: vec![],
+            withdrawals: Some(Withdrawals::default()),
+        };
+
+        let block = SealedBlock::seal_slow(alloy_consensus::Block { header, body });
+
+        // 10 blobs times the blob gas per blob.
+        let expected_blob_gas_used = 10 * DATA_GAS_PER_BLOB;
+
+        // validate blob, it should fail blob gas used validation
+        assert_eq!(
+            validate_block_pre_execution(&block, &chain_spec),
+            Err(ConsensusError::BlobGasUsedDiff(GotExpected {
+                got: 1,
+                expected: expected_blob_gas_used
+            }))
+        );
+    }
+
+    #[test]
+    fn full_report_collects_all_failures() {
+        let chain_spec = ChainSpecBuilder::mainnet().cancun_activated().build();
+
+        let transaction = mock_blob_tx(1, 10);
+        let header = Header {
+            base_fee_per_gas: Some(1337),
+            withdrawals_root: Some(proofs::calculate_withdrawals_root(&[])),
+            blob_gas_used: Some(1),
+            transactions_root: proofs::calculate_transaction_root(std::slice::from_ref(
+                &transaction,
+            )),
+            ..Default::default()
+        };
+        let body = BlockBody {
+            transactions: vec![transaction],
+            ommers: vec![],
+            withdrawals: Some(Withdrawals::default()),
+        };
+        let block = SealedBlock::seal_slow(alloy_consensus::Block { header, body });
+        // same number as the block
+        let parent = SealedHeader::seal_slow(Header::default());
+
+        let report = validate_block_full_report(&block, &parent, &chain_spec);
+        assert!(!report.is_valid());
+
+        let failures = report.failures().map(|outcome| outcome.rule).collect::<Vec<_>>();
+        assert!(failures.contains(&"blob_fields"));
+        assert!(failures.contains(&"parent_hash_number"));
+        assert!(failures.contains(&"blob_gas_used"));
+        assert!(!failures.contains(&"transaction_root"));
+    }
+}
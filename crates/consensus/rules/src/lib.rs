@@ -0,0 +1,285 @@
+//! The arithmetic of the header validation rules.
+//!
+//! These are the rules `reth_consensus::validation` applies to headers, see there for why the
+//! validation doesn't depend on a provider or executor. This crate goes one step further and only
+//! operates on the numeric header fields, without a header type, a chain spec, an allocator or
+//! any reth or alloy dependency, so `no_std` verifiers can apply the rules to headers of any
+//! format.
+//!
+//! Values that depend on the chain configuration, e.g. the expected base fee or excess blob gas,
+//! are passed in by the caller.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Minimum gas limit allowed for blocks.
+pub const MINIMUM_GAS_LIMIT: u64 = 5000;
+
+/// Maximum gas limit allowed for blocks.
+/// In hex this number is `0x7fffffffffffffff`
+pub const MAXIMUM_GAS_LIMIT_BLOCK: u64 = 2u64.pow(63) - 1;
+
+/// The bound divisor of the gas limit, used in update calculations.
+pub const GAS_LIMIT_BOUND_DIVISOR: u64 = 1024;
+
+/// The gas consumed by a single EIP-4844 blob.
+pub const DATA_GAS_PER_BLOB: u64 = 131_072;
+
+/// A violation of a header validation rule.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, thiserror::Error)]
+pub enum RuleError {
+    /// Error when the gas used in the header exceeds the gas limit.
+    #[error("block used gas ({gas_used}) is greater than gas limit ({gas_limit})")]
+    HeaderGasUsedExceedsGasLimit {
+        /// The gas used in the block header.
+        gas_used: u64,
+        /// The gas limit in the block header.
+        gas_limit: u64,
+    },
+    /// Error when the gas the gas limit is more than the maximum allowed.
+    #[error(
+        "header gas limit ({gas_limit}) exceed the maximum allowed gas limit ({MAXIMUM_GAS_LIMIT_BLOCK})"
+    )]
+    HeaderGasLimitExceedsMax {
+        /// The gas limit in the block header.
+        gas_limit: u64,
+    },
+    /// Error when the child gas limit exceeds the maximum allowed increase.
+    #[error("child gas_limit {child_gas_limit} max increase is {parent_gas_limit}/1024")]
+    GasLimitInvalidIncrease {
+        /// The parent gas limit.
+        parent_gas_limit: u64,
+        /// The child gas limit.
+        child_gas_limit: u64,
+    },
+    /// Error when the child gas limit exceeds the maximum allowed decrease.
+    #[error("child gas_limit {child_gas_limit} max decrease is {parent_gas_limit}/1024")]
+    GasLimitInvalidDecrease {
+        /// The parent gas limit.
+        parent_gas_limit: u64,
+        /// The child gas limit.
+        child_gas_limit: u64,
+    },
+    /// Error when the child gas limit is less than [`MINIMUM_GAS_LIMIT`].
+    #[error(
+        "child gas limit {child_gas_limit} is below the minimum allowed limit ({MINIMUM_GAS_LIMIT})"
+    )]
+    GasLimitInvalidMinimum {
+        /// The child gas limit.
+        child_gas_limit: u64,
+    },
+    /// Error when the block's base fee is different from the expected base fee.
+    #[error("block base fee mismatch: got {got}, expected {expected}")]
+    BaseFeeDiff {
+        /// The base fee of the block.
+        got: u64,
+        /// The expected base fee.
+        expected: u64,
+    },
+    /// Error when blob gas used is not a multiple of [`DATA_GAS_PER_BLOB`].
+    #[error(
+        "blob gas used {blob_gas_used} is not a multiple of blob gas per blob {DATA_GAS_PER_BLOB}"
+    )]
+    BlobGasUsedNotMultipleOfBlobGasPerBlob {
+        /// The actual blob gas used.
+        blob_gas_used: u64,
+    },
+    /// Error when blob gas used exceeds the maximum allowed.
+    #[error("blob gas used {blob_gas_used} exceeds maximum allowance {max_blob_gas_per_block}")]
+    BlobGasUsedExceedsMaxBlobGasPerBlock {
+        /// The actual blob gas used.
+        blob_gas_used: u64,
+        /// The maximum allowed blob gas per block.
+        max_blob_gas_per_block: u64,
+    },
+    /// Error when the excess blob gas is different from the expected excess blob gas.
+    #[error(
+        "invalid excess blob gas: got {got}, expected {expected}; \
+            parent excess blob gas: {parent_excess_blob_gas}, \
+            parent blob gas used: {parent_blob_gas_used}"
+    )]
+    ExcessBlobGasDiff {
+        /// The excess blob gas of the block.
+        got: u64,
+        /// The expected excess blob gas.
+        expected: u64,
+        /// The parent excess blob gas.
+        parent_excess_blob_gas: u64,
+        /// The parent blob gas used.
+        parent_blob_gas_used: u64,
+    },
+    /// Error when the block timestamp is in the past compared to the parent timestamp.
+    #[error(
+        "block timestamp {timestamp} is in the past compared to the parent timestamp {parent_timestamp}"
+    )]
+    TimestampIsInPast {
+        /// The parent block's timestamp.
+        parent_timestamp: u64,
+        /// The block's timestamp.
+        timestamp: u64,
+    },
+}
+
+/// Checks that the gas used doesn't exceed the gas limit, and that the gas limit doesn't exceed
+/// [`MAXIMUM_GAS_LIMIT_BLOCK`].
+#[inline]
+pub const fn validate_gas_used(gas_used: u64, gas_limit: u64) -> Result<(), RuleError> {
+    if gas_used > gas_limit {
+        return Err(RuleError::HeaderGasUsedExceedsGasLimit { gas_used, gas_limit })
+    }
+    if gas_limit > MAXIMUM_GAS_LIMIT_BLOCK {
+        return Err(RuleError::HeaderGasLimitExceedsMax { gas_limit })
+    }
+    Ok(())
+}
+
+/// Checks that the gas limit changed by less than `parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR`
+/// compared to the parent, and that it is at least [`MINIMUM_GAS_LIMIT`].
+///
+/// On the London activation block, `parent_gas_limit` must already be multiplied by the
+/// elasticity multiplier of the base fee params.
+#[inline]
+pub const fn validate_gas_limit_delta(
+    parent_gas_limit: u64,
+    gas_limit: u64,
+) -> Result<(), RuleError> {
+    let max_delta = parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR;
+    if gas_limit > parent_gas_limit {
+        if gas_limit - parent_gas_limit >= max_delta {
+            return Err(RuleError::GasLimitInvalidIncrease {
+                parent_gas_limit,
+                child_gas_limit: gas_limit,
+            })
+        }
+    } else if parent_gas_limit - gas_limit >= max_delta {
+        return Err(RuleError::GasLimitInvalidDecrease {
+            parent_gas_limit,
+            child_gas_limit: gas_limit,
+        })
+    } else if gas_limit < MINIMUM_GAS_LIMIT {
+        return Err(RuleError::GasLimitInvalidMinimum { child_gas_limit: gas_limit })
+    }
+    Ok(())
+}
+
+/// Checks that the EIP-1559 base fee matches the base fee expected from the parent block.
+#[inline]
+pub const fn validate_base_fee(base_fee: u64, expected_base_fee: u64) -> Result<(), RuleError> {
+    if base_fee != expected_base_fee {
+        return Err(RuleError::BaseFeeDiff { got: base_fee, expected: expected_base_fee })
+    }
+    Ok(())
+}
+
+/// Checks that the EIP-4844 blob gas used is a multiple of [`DATA_GAS_PER_BLOB`] and doesn't
+/// exceed `max_blob_gas_per_block`.
+#[inline]
+pub const fn validate_blob_gas_used(
+    blob_gas_used: u64,
+    max_blob_gas_per_block: u64,
+) -> Result<(), RuleError> {
+    if blob_gas_used % DATA_GAS_PER_BLOB != 0 {
+        return Err(RuleError::BlobGasUsedNotMultipleOfBlobGasPerBlob { blob_gas_used })
+    }
+    if blob_gas_used > max_blob_gas_per_block {
+        return Err(RuleError::BlobGasUsedExceedsMaxBlobGasPerBlock {
+            blob_gas_used,
+            max_blob_gas_per_block,
+        })
+    }
+    Ok(())
+}
+
+/// Checks that the EIP-4844 excess blob gas matches the excess blob gas expected from the parent
+/// block.
+///
+/// The parent fields are only used to report the error.
+#[inline]
+pub const fn validate_excess_blob_gas(
+    excess_blob_gas: u64,
+    expected_excess_blob_gas: u64,
+    parent_excess_blob_gas: u64,
+    parent_blob_gas_used: u64,
+) -> Result<(), RuleError> {
+    if excess_blob_gas != expected_excess_blob_gas {
+        return Err(RuleError::ExcessBlobGasDiff {
+            got: excess_blob_gas,
+            expected: expected_excess_blob_gas,
+            parent_excess_blob_gas,
+            parent_blob_gas_used,
+        })
+    }
+    Ok(())
+}
+
+/// Checks that the timestamp is greater than the timestamp of the parent block.
+#[inline]
+pub const fn validate_timestamp(parent_timestamp: u64, timestamp: u64) -> Result<(), RuleError> {
+    if timestamp <= parent_timestamp {
+        return Err(RuleError::TimestampIsInPast { parent_timestamp, timestamp })
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_limit_delta() {
+        let parent_gas_limit = 30_000_000;
+        let max_delta = parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR;
+
+        assert!(validate_gas_limit_delta(parent_gas_limit, parent_gas_limit).is_ok());
+        assert!(
+            validate_gas_limit_delta(parent_gas_limit, parent_gas_limit + max_delta - 1).is_ok()
+        );
+        assert!(
+            validate_gas_limit_delta(parent_gas_limit, parent_gas_limit - max_delta + 1).is_ok()
+        );
+        assert!(matches!(
+            validate_gas_limit_delta(parent_gas_limit, parent_gas_limit + max_delta),
+            Err(RuleError::GasLimitInvalidIncrease { .. })
+        ));
+        assert!(matches!(
+            validate_gas_limit_delta(parent_gas_limit, parent_gas_limit - max_delta),
+            Err(RuleError::GasLimitInvalidDecrease { .. })
+        ));
+        assert!(matches!(
+            validate_gas_limit_delta(MINIMUM_GAS_LIMIT, MINIMUM_GAS_LIMIT - 1),
+            Err(RuleError::GasLimitInvalidMinimum { .. })
+        ));
+    }
+
+    #[test]
+    fn blob_gas_used() {
+        let max_blob_gas_per_block = 6 * DATA_GAS_PER_BLOB;
+
+        assert!(validate_blob_gas_used(0, max_blob_gas_per_block).is_ok());
+        assert!(validate_blob_gas_used(max_blob_gas_per_block, max_blob_gas_per_block).is_ok());
+        assert!(matches!(
+            validate_blob_gas_used(DATA_GAS_PER_BLOB + 1, max_blob_gas_per_block),
+            Err(RuleError::BlobGasUsedNotMultipleOfBlobGasPerBlob { .. })
+        ));
+        assert!(matches!(
+            validate_blob_gas_used(
+                max_blob_gas_per_block + DATA_GAS_PER_BLOB,
+                max_blob_gas_per_block
+            ),
+            Err(RuleError::BlobGasUsedExceedsMaxBlobGasPerBlock { .. })
+        ));
+    }
+
+    #[test]
+    fn timestamp() {
+        assert!(validate_timestamp(1, 2).is_ok());
+        assert!(validate_timestamp(2, 2).is_err());
+        assert!(validate_timestamp(3, 2).is_err());
+    }
+}
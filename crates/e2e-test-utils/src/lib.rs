@@ -36,6 +36,9 @@ mod payload;
 /// Helper for setting up nodes with pre-imported chain data
 pub mod setup_import;
 
+/// Single in-process node for integration tests
+pub mod test_node;
+
 /// Helper for network operations
 mod network;
 
@@ -0,0 +1,222 @@
+//! A single in-process node with direct handles to its components.
+//!
+//! [`TestNode`] is meant for integration tests of projects that build on reth and want to run
+//! against a real node without copying the setup of reth's own e2e tests.
+
+use crate::{
+    setup_engine_with_connection, wallet::Wallet, Adapter, NodeBuilderHelper, NodeHelperType, TmpDB,
+};
+use alloy_primitives::{Address, B256, U256};
+use alloy_rpc_types_engine::PayloadAttributes;
+use reth_chainspec::{make_genesis_header, ChainSpec, ChainSpecBuilder, MAINNET};
+use reth_engine_local::LocalPayloadAttributesBuilder;
+use reth_node_api::{
+    BeaconConsensusEngineHandle, FullNodeComponents, NodeTypes, PayloadAttributesBuilder,
+    PayloadTypes, TreeConfig,
+};
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_payload_builder::EthPayloadBuilderAttributes;
+use reth_primitives_traits::SealedHeader;
+use reth_provider::providers::BlockchainProvider;
+use reth_tasks::TaskManager;
+use std::{marker::PhantomData, sync::Arc};
+
+/// Builder of a [`TestNode`].
+///
+/// By default, the node runs on the genesis of reth's e2e tests, in which the accounts of
+/// [`Wallet::default`] are funded, with all hardforks up to Cancun activated.
+///
+/// # Example
+///
+/// ```no_run
+/// # async fn example() -> eyre::Result<()> {
+/// use alloy_primitives::{Address, U256};
+/// use reth_e2e_test_utils::test_node::TestNode;
+/// use reth_node_ethereum::EthereumNode;
+///
+/// let node = TestNode::<EthereumNode>::builder()
+///     .with_funded_account(Address::with_last_byte(1), U256::from(1_000_000))
+///     .with_instant_mining()
+///     .launch()
+///     .await?;
+/// let pool = node.pool();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TestNodeBuilder<N> {
+    chain_spec: ChainSpec,
+    funded_accounts: Vec<(Address, U256)>,
+    instant_mining: bool,
+    tree_config: TreeConfig,
+    _node: PhantomData<N>,
+}
+
+impl<N> Default for TestNodeBuilder<N> {
+    fn default() -> Self {
+        let chain_spec = ChainSpecBuilder::default()
+            .chain(MAINNET.chain)
+            .genesis(serde_json::from_str(include_str!("testsuite/assets/genesis.json")).unwrap())
+            .cancun_activated()
+            .build();
+        Self {
+            chain_spec,
+            funded_accounts: Vec::new(),
+            instant_mining: false,
+            tree_config: TreeConfig::default(),
+            _node: PhantomData,
+        }
+    }
+}
+
+impl<N> TestNodeBuilder<N>
+where
+    N: NodeBuilderHelper,
+    LocalPayloadAttributesBuilder<N::ChainSpec>:
+        PayloadAttributesBuilder<<N::Payload as PayloadTypes>::PayloadAttributes>,
+{
+    /// Sets the chain spec of the node.
+    pub fn with_chain_spec(mut self, chain_spec: ChainSpec) -> Self {
+        self.chain_spec = chain_spec;
+        self
+    }
+
+    /// Funds the account with the given balance in the genesis of the chain spec.
+    pub fn with_funded_account(mut self, address: Address, balance: U256) -> Self {
+        self.funded_accounts.push((address, balance));
+        self
+    }
+
+    /// Mines a new block as soon as a transaction enters the pool, like the `--dev` mode.
+    ///
+    /// Without instant mining, blocks are only produced through the engine API, e.g. with
+    /// [`NodeTestContext::advance_block`](crate::node::NodeTestContext::advance_block).
+    pub const fn with_instant_mining(mut self) -> Self {
+        self.instant_mining = true;
+        self
+    }
+
+    /// Sets the config of the engine tree.
+    pub fn with_tree_config(mut self, tree_config: TreeConfig) -> Self {
+        self.tree_config = tree_config;
+        self
+    }
+
+    /// Launches the node.
+    ///
+    /// The node and all its tasks are shut down once the returned [`TestNode`] is dropped.
+    pub async fn launch(self) -> eyre::Result<TestNode<N>> {
+        let Self { mut chain_spec, funded_accounts, instant_mining, tree_config, _node } = self;
+
+        if !funded_accounts.is_empty() {
+            for (address, balance) in funded_accounts {
+                chain_spec.genesis.alloc.entry(address).or_default().balance = balance;
+            }
+            chain_spec.genesis_header = SealedHeader::seal_slow(make_genesis_header(
+                &chain_spec.genesis,
+                &chain_spec.hardforks,
+            ));
+        }
+
+        let chain_id = chain_spec.chain.id();
+        let (mut nodes, tasks, _) = setup_engine_with_connection::<N>(
+            1,
+            Arc::new(chain_spec.into()),
+            instant_mining,
+            tree_config,
+            payload_attributes::<N>,
+            false,
+        )
+        .await?;
+
+        Ok(TestNode {
+            context: nodes.pop().expect("one node was launched"),
+            wallet: Wallet::default().with_chain_id(chain_id),
+            _tasks: tasks,
+        })
+    }
+}
+
+/// A single in-process node, see [`TestNodeBuilder`].
+#[expect(missing_debug_implementations)]
+pub struct TestNode<N>
+where
+    N: NodeBuilderHelper,
+    LocalPayloadAttributesBuilder<N::ChainSpec>:
+        PayloadAttributesBuilder<<N::Payload as PayloadTypes>::PayloadAttributes>,
+{
+    /// The test context of the node, with helpers to build and submit payloads and to access the
+    /// RPC and network of the node.
+    pub context: NodeHelperType<N>,
+    /// The wallet of the accounts that are funded in the default genesis.
+    pub wallet: Wallet,
+    /// Keeps the tasks of the node running until the node is dropped.
+    _tasks: TaskManager,
+}
+
+impl<N> TestNode<N>
+where
+    N: NodeBuilderHelper,
+    LocalPayloadAttributesBuilder<N::ChainSpec>:
+        PayloadAttributesBuilder<<N::Payload as PayloadTypes>::PayloadAttributes>,
+{
+    /// Returns a builder of a node with the default genesis.
+    pub fn builder() -> TestNodeBuilder<N> {
+        TestNodeBuilder::default()
+    }
+
+    /// Returns the transaction pool of the node.
+    pub fn pool(&self) -> &<Adapter<N> as FullNodeComponents>::Pool {
+        &self.context.inner.pool
+    }
+
+    /// Returns the provider of the node.
+    pub fn provider(&self) -> &BlockchainProvider<NodeTypesWithDBAdapter<N, TmpDB>> {
+        &self.context.inner.provider
+    }
+
+    /// Returns the handle to send engine API messages to the consensus engine of the node.
+    pub fn engine(&self) -> &BeaconConsensusEngineHandle<<N as NodeTypes>::Payload> {
+        &self.context.inner.add_ons_handle.beacon_engine_handle
+    }
+}
+
+/// Returns the payload attributes of the blocks built by the node at the given timestamp.
+fn payload_attributes<N>(timestamp: u64) -> <N::Payload as PayloadTypes>::PayloadBuilderAttributes
+where
+    N: NodeBuilderHelper,
+    LocalPayloadAttributesBuilder<N::ChainSpec>:
+        PayloadAttributesBuilder<<N::Payload as PayloadTypes>::PayloadAttributes>,
+{
+    let attributes = PayloadAttributes {
+        timestamp,
+        prev_randao: B256::ZERO,
+        suggested_fee_recipient: Address::ZERO,
+        withdrawals: Some(vec![]),
+        parent_beacon_block_root: Some(B256::ZERO),
+    };
+    EthPayloadBuilderAttributes::new(B256::ZERO, attributes).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_ethereum::EthereumNode;
+    use reth_provider::{StateProvider, StateProviderFactory};
+
+    #[tokio::test]
+    async fn launches_node_with_funded_account() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        let address = Address::with_last_byte(1);
+        let balance = U256::from(1_000_000);
+        let node = TestNode::<EthereumNode>::builder()
+            .with_funded_account(address, balance)
+            .with_instant_mining()
+            .launch()
+            .await?;
+
+        assert_eq!(node.provider().latest()?.account_balance(&address)?, Some(balance));
+        Ok(())
+    }
+}
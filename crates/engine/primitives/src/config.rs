@@ -65,6 +65,9 @@ pub struct TreeConfig {
     always_compare_trie_updates: bool,
     /// Whether to disable cross-block caching and parallel prewarming.
     disable_caching_and_prewarming: bool,
+    /// Whether to disable prewarming the caches with the transactions that are likely included in
+    /// the next payload when a forkchoice update with payload attributes is received.
+    disable_speculative_prewarming: bool,
     /// Whether to enable the parallel sparse trie state root algorithm.
     enable_parallel_sparse_trie: bool,
     /// Whether to enable state provider metrics.
@@ -108,6 +111,7 @@ impl Default for TreeConfig {
             legacy_state_root: false,
             always_compare_trie_updates: false,
             disable_caching_and_prewarming: false,
+            disable_speculative_prewarming: false,
             enable_parallel_sparse_trie: false,
             state_provider_metrics: false,
             cross_block_cache_size: DEFAULT_CROSS_BLOCK_CACHE_SIZE,
@@ -133,6 +137,7 @@ impl TreeConfig {
         legacy_state_root: bool,
         always_compare_trie_updates: bool,
         disable_caching_and_prewarming: bool,
+        disable_speculative_prewarming: bool,
         enable_parallel_sparse_trie: bool,
         state_provider_metrics: bool,
         cross_block_cache_size: u64,
@@ -152,6 +157,7 @@ impl TreeConfig {
             legacy_state_root,
             always_compare_trie_updates,
             disable_caching_and_prewarming,
+            disable_speculative_prewarming,
             enable_parallel_sparse_trie,
             state_provider_metrics,
             cross_block_cache_size,
@@ -220,6 +226,12 @@ impl TreeConfig {
         self.disable_caching_and_prewarming
     }
 
+    /// Returns whether prewarming the caches for the next payload on forkchoice updates with
+    /// payload attributes is disabled.
+    pub const fn disable_speculative_prewarming(&self) -> bool {
+        self.disable_speculative_prewarming
+    }
+
     /// Returns whether to always compare trie updates from the state root task to the trie updates
     /// from the regular state root calculation.
     pub const fn always_compare_trie_updates(&self) -> bool {
@@ -311,6 +323,16 @@ impl TreeConfig {
         self
     }
 
+    /// Setter for whether to disable prewarming the caches for the next payload on forkchoice
+    /// updates with payload attributes.
+    pub const fn without_speculative_prewarming(
+        mut self,
+        disable_speculative_prewarming: bool,
+    ) -> Self {
+        self.disable_speculative_prewarming = disable_speculative_prewarming;
+        self
+    }
+
     /// Setter for whether to always compare trie updates from the state root task to the trie
     /// updates from the regular state root calculation.
     pub const fn with_always_compare_trie_updates(
@@ -8,14 +8,14 @@ use reth_engine_tree::{
     download::BasicBlockDownloader,
     engine::{EngineApiKind, EngineApiRequest, EngineApiRequestHandler, EngineHandler},
     persistence::PersistenceHandle,
-    tree::{EngineApiTreeHandler, InvalidBlockHook, TreeConfig},
+    tree::{EngineApiTreeHandler, InvalidBlockHook, PrewarmTransactions, TreeConfig},
 };
 pub use reth_engine_tree::{
     chain::{ChainEvent, ChainOrchestrator},
     engine::EngineApiEvent,
 };
 use reth_ethereum_primitives::EthPrimitives;
-use reth_evm::ConfigureEvm;
+use reth_evm::{precompile_stats::PrecompileStatsStore, ConfigureEvm};
 use reth_network_p2p::BlockClient;
 use reth_node_types::{BlockTy, NodeTypes};
 use reth_payload_builder::PayloadBuilderHandle;
@@ -83,6 +83,8 @@ where
         payload_validator: V,
         tree_config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N::Primitives>>,
+        precompile_stats: PrecompileStatsStore,
+        prewarm_transactions: Box<dyn PrewarmTransactions<N::Primitives>>,
         sync_metrics_tx: MetricEventsSender,
         evm_config: C,
     ) -> Self
@@ -109,6 +111,8 @@ where
             canonical_in_memory_state,
             tree_config,
             invalid_block_hook,
+            precompile_stats,
+            prewarm_transactions,
             engine_kind,
             evm_config,
         );
@@ -150,7 +154,10 @@ mod tests {
     use super::*;
     use reth_chainspec::{ChainSpecBuilder, MAINNET};
     use reth_engine_primitives::BeaconEngineMessage;
-    use reth_engine_tree::{test_utils::TestPipelineBuilder, tree::NoopInvalidBlockHook};
+    use reth_engine_tree::{
+        test_utils::TestPipelineBuilder,
+        tree::{NoopInvalidBlockHook, NoopPrewarmTransactions},
+    };
     use reth_ethereum_consensus::EthBeaconConsensus;
     use reth_ethereum_engine_primitives::EthEngineTypes;
     use reth_evm_ethereum::EthEvmConfig;
@@ -211,6 +218,8 @@ mod tests {
             engine_payload_validator,
             TreeConfig::default(),
             Box::new(NoopInvalidBlockHook::default()),
+            PrecompileStatsStore::default(),
+            Box::new(NoopPrewarmTransactions::default()),
             sync_metrics_tx,
             evm_config,
         );
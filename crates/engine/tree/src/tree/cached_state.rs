@@ -292,6 +292,12 @@ pub(crate) struct ProviderCaches {
 }
 
 impl ProviderCaches {
+    /// Returns the cached account, if the account is cached.
+    #[cfg(test)]
+    pub(crate) fn get_account(&self, address: &Address) -> Option<Option<Account>> {
+        self.account_cache.get(address)
+    }
+
     /// Get storage value from hierarchical cache.
     ///
     /// Returns a `SlotStatus` indicating whether:
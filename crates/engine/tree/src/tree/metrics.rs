@@ -1,3 +1,4 @@
+use alloy_primitives::Address;
 use reth_evm::metrics::ExecutorMetrics;
 use reth_metrics::{
     metrics::{Counter, Gauge, Histogram},
@@ -83,6 +84,24 @@ impl BlockValidationMetrics {
     }
 }
 
+/// Metrics for the calls of a single precompile during block execution.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "sync.execution.precompiles")]
+pub(crate) struct PrecompileMetrics {
+    /// The number of calls of the precompile.
+    pub(crate) calls: Counter,
+    /// The gas used by the calls of the precompile.
+    pub(crate) gas_used: Counter,
+}
+
+impl PrecompileMetrics {
+    /// Creates a new instance of [`PrecompileMetrics`] with the given address as an `address`
+    /// label.
+    pub(crate) fn new_with_address(address: Address) -> Self {
+        Self::new_with_labels(&[("address", format!("0x{address:02x}"))])
+    }
+}
+
 /// Metrics for the blockchain tree block buffer
 #[derive(Metrics)]
 #[metrics(scope = "blockchain_tree.block_buffer")]
@@ -4,7 +4,9 @@ use crate::{
     engine::{DownloadRequest, EngineApiEvent, EngineApiKind, EngineApiRequest, FromEngine},
     persistence::PersistenceHandle,
     tree::{
-        cached_state::CachedStateProvider, executor::WorkloadExecutor, metrics::EngineApiMetrics,
+        cached_state::CachedStateProvider,
+        executor::WorkloadExecutor,
+        metrics::{EngineApiMetrics, PrecompileMetrics},
     },
 };
 use alloy_consensus::BlockHeader;
@@ -30,7 +32,12 @@ use reth_engine_primitives::{
     ExecutionPayload, ForkchoiceStateTracker, OnForkChoiceUpdated,
 };
 use reth_errors::{ConsensusError, ProviderResult};
-use reth_evm::{ConfigureEvm, Evm, SpecFor};
+use reth_evm::{
+    precompile_stats::{
+        MeteredPrecompile, PrecompileCounter, PrecompileStats, PrecompileStatsStore,
+    },
+    ConfigureEvm, Evm, SpecFor,
+};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{EngineApiMessageVersion, PayloadBuilderAttributes, PayloadTypes};
 use reth_primitives_traits::{
@@ -84,7 +91,10 @@ use crate::tree::error::AdvancePersistenceError;
 pub use block_buffer::BlockBuffer;
 pub use invalid_block_hook::{InvalidBlockHooks, NoopInvalidBlockHook};
 pub use invalid_headers::InvalidHeaderCache;
-pub use payload_processor::*;
+pub use payload_processor::{
+    prewarm::{NoopPrewarmTransactions, PrewarmTransactions},
+    *,
+};
 pub use persistence_state::PersistenceState;
 pub use reth_engine_primitives::TreeConfig;
 use reth_evm::execute::BlockExecutionOutput;
@@ -277,6 +287,12 @@ where
     precompile_cache_map: PrecompileCacheMap<SpecFor<C>>,
     /// Metrics for precompile cache, stored per address to avoid re-allocation.
     precompile_cache_metrics: HashMap<Address, CachedPrecompileMetrics>,
+    /// Call counters and metrics of the precompiles, stored per address to avoid re-allocation.
+    precompile_counters: HashMap<Address, (Arc<PrecompileCounter>, PrecompileMetrics)>,
+    /// Stores the precompile usage of the executed blocks.
+    precompile_stats: PrecompileStatsStore,
+    /// Provides the transactions used to prewarm the caches for the next payload.
+    prewarm_transactions: Arc<dyn PrewarmTransactions<N>>,
 }
 
 impl<N, P: Debug, T: PayloadTypes + Debug, V: Debug, C> std::fmt::Debug
@@ -372,6 +388,9 @@ where
             evm_config,
             precompile_cache_map,
             precompile_cache_metrics: HashMap::new(),
+            precompile_counters: HashMap::new(),
+            precompile_stats: PrecompileStatsStore::default(),
+            prewarm_transactions: Arc::new(NoopPrewarmTransactions),
         }
     }
 
@@ -380,6 +399,16 @@ where
         self.invalid_block_hook = invalid_block_hook;
     }
 
+    /// Sets the store that receives the precompile usage of every executed block.
+    fn set_precompile_stats(&mut self, precompile_stats: PrecompileStatsStore) {
+        self.precompile_stats = precompile_stats;
+    }
+
+    /// Sets the source of the transactions used to prewarm the caches for the next payload.
+    fn set_prewarm_transactions(&mut self, prewarm_transactions: Box<dyn PrewarmTransactions<N>>) {
+        self.prewarm_transactions = prewarm_transactions.into();
+    }
+
     /// Creates a new [`EngineApiTreeHandler`] instance and spawns it in its
     /// own thread.
     ///
@@ -395,6 +424,8 @@ where
         canonical_in_memory_state: CanonicalInMemoryState<N>,
         config: TreeConfig,
         invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
+        precompile_stats: PrecompileStatsStore,
+        prewarm_transactions: Box<dyn PrewarmTransactions<N>>,
         kind: EngineApiKind,
         evm_config: C,
     ) -> (Sender<FromEngine<EngineApiRequest<T, N>, N::Block>>, UnboundedReceiver<EngineApiEvent<N>>)
@@ -430,6 +461,8 @@ where
             evm_config,
         );
         task.set_invalid_block_hook(invalid_block_hook);
+        task.set_precompile_stats(precompile_stats);
+        task.set_prewarm_transactions(prewarm_transactions);
         let incoming = task.incoming_tx.clone();
         std::thread::Builder::new().name("Tree Task".to_string()).spawn(|| task.run()).unwrap();
         (incoming, outgoing)
@@ -2202,6 +2235,9 @@ where
             "Deciding which state root algorithm to run"
         );
 
+        // the payload is being processed, so prewarming for it is no longer useful
+        self.payload_processor.stop_speculative_prewarm();
+
         // use prewarming background task
         let header = block.clone_sealed_header();
         let txs = block.clone_transactions_recovered().collect();
@@ -2455,18 +2491,40 @@ where
             });
         }
 
+        executor.evm_mut().precompiles_mut().map_precompiles(|address, precompile| {
+            let (counter, _) = self.precompile_counters.entry(*address).or_insert_with(|| {
+                (Default::default(), PrecompileMetrics::new_with_address(*address))
+            });
+            MeteredPrecompile::wrap(precompile, counter.clone())
+        });
+
         let execution_start = Instant::now();
-        let output = self.metrics.executor.execute_metered(
-            executor,
-            block,
-            Box::new(handle.state_hook()),
-        )?;
+        let output =
+            self.metrics.executor.execute_metered(executor, block, Box::new(handle.state_hook()));
         let execution_finish = Instant::now();
+        // drain the counters even if the execution failed, so they start at zero for the next block
+        let precompile_stats = self.take_precompile_stats();
+        let output = output?;
+        self.precompile_stats.insert(block.hash(), precompile_stats);
         let execution_time = execution_finish.duration_since(execution_start);
         debug!(target: "engine::tree", elapsed = ?execution_time, number=?block.number(), "Executed block");
         Ok((output, execution_finish))
     }
 
+    /// Drains the precompile call counters into [`PrecompileStats`] and records them as metrics.
+    fn take_precompile_stats(&self) -> PrecompileStats {
+        let mut stats = PrecompileStats::default();
+        for (address, (counter, metrics)) in &self.precompile_counters {
+            let usage = counter.take();
+            if !usage.is_empty() {
+                metrics.calls.increment(usage.calls);
+                metrics.gas_used.increment(usage.gas_used);
+                stats.record(*address, usage);
+            }
+        }
+        stats
+    }
+
     /// Compute state root for the given hashed post state in parallel.
     ///
     /// # Returns
@@ -2765,7 +2823,7 @@ where
     /// Note: At this point, the fork choice update is considered to be VALID, however, we can still
     /// return an error if the payload attributes are invalid.
     fn process_payload_attributes(
-        &self,
+        &mut self,
         attrs: T::PayloadAttributes,
         head: &N::BlockHeader,
         state: ForkchoiceState,
@@ -2792,6 +2850,8 @@ where
                 // id, initiating payload job is handled asynchronously
                 let pending_payload_id = self.payload_builder.send_new_payload(attributes);
 
+                self.prewarm_next_payload(SealedHeader::new(head.clone(), state.head_block_hash));
+
                 // Client software MUST respond to this method call in the following way:
                 // {
                 //      payloadStatus: {
@@ -2812,6 +2872,36 @@ where
         }
     }
 
+    /// Prewarms the caches for the payload that is built on top of the given head block, by
+    /// executing the transactions the payload will most likely include.
+    ///
+    /// This runs in the background while the payload is being built, so that the state accessed by
+    /// the payload is already cached when it is received via `newPayload`. The transactions are
+    /// collected by the prewarming task, not on the engine thread.
+    fn prewarm_next_payload(&mut self, head: SealedHeader<N::BlockHeader>) {
+        if self.config.disable_caching_and_prewarming() ||
+            self.config.disable_speculative_prewarming()
+        {
+            return
+        }
+
+        let provider_builder = match self.state_provider_builder(head.hash()) {
+            Ok(Some(provider_builder)) => provider_builder,
+            Ok(None) => return,
+            Err(err) => {
+                debug!(target: "engine::tree", %err, head=?head.num_hash(), "Failed to create state provider for prewarming");
+                return
+            }
+        };
+
+        debug!(target: "engine::tree", head=?head.num_hash(), "Prewarming caches for the next payload");
+        self.payload_processor.spawn_speculative_prewarm(
+            head,
+            self.prewarm_transactions.clone(),
+            provider_builder,
+        );
+    }
+
     /// Remove all blocks up to __and including__ the given block number.
     ///
     /// If a finalized hash is provided, the only non-canonical blocks which will be removed are
@@ -3,7 +3,7 @@
 use crate::tree::{
     cached_state::{CachedStateMetrics, ProviderCacheBuilder, ProviderCaches, SavedCache},
     payload_processor::{
-        prewarm::{PrewarmCacheTask, PrewarmContext, PrewarmTaskEvent},
+        prewarm::{PrewarmCacheTask, PrewarmContext, PrewarmTaskEvent, PrewarmTransactions},
         sparse_trie::StateRootComputeOutcome,
     },
     sparse_trie::SparseTrieTask,
@@ -80,6 +80,9 @@ where
     sparse_trie: Option<SparseTrie<ConfiguredSparseTrie>>,
     /// Whether to use the parallel sparse trie.
     use_parallel_sparse_trie: bool,
+    /// The prewarming task for the next payload, spawned on a forkchoice update with payload
+    /// attributes.
+    speculative_prewarm: Option<CacheTaskHandle>,
     _marker: std::marker::PhantomData<N>,
 }
 
@@ -106,6 +109,7 @@ where
             precompile_cache_map,
             sparse_trie: None,
             use_parallel_sparse_trie: config.enable_parallel_sparse_trie(),
+            speculative_prewarm: None,
             _marker: Default::default(),
         }
     }
@@ -257,6 +261,52 @@ where
         PayloadHandle { to_multi_proof: None, prewarm_handle, state_root: None }
     }
 
+    /// Spawns prewarming of the transactions that are likely to be included in the payload built on
+    /// top of the given parent block.
+    ///
+    /// The transactions are collected from the given source by the prewarming task and executed
+    /// with the EVM environment of the parent block, and the state they access is stored in the
+    /// cache of the parent block, which is then used to execute the payload. Any previous
+    /// speculative prewarming is stopped.
+    pub(super) fn spawn_speculative_prewarm<P>(
+        &mut self,
+        parent: SealedHeaderFor<N>,
+        transactions: Arc<dyn PrewarmTransactions<N>>,
+        provider_builder: StateProviderBuilder<N, P>,
+    ) where
+        P: BlockReader
+            + StateProviderFactory
+            + StateReader
+            + StateCommitmentProvider
+            + Clone
+            + 'static,
+    {
+        self.stop_speculative_prewarm();
+
+        let saved_cache = self.cache_for(parent.hash());
+        // make the cache available to the execution of the payload
+        self.execution_cache.save_cache(saved_cache.clone());
+
+        let handle = self.spawn_prewarm_task(
+            parent.clone(),
+            saved_cache,
+            move || transactions.prewarm_transactions(&parent).into(),
+            provider_builder,
+            None,
+        );
+        self.speculative_prewarm = Some(handle);
+    }
+
+    /// Stops the transaction execution of the speculative prewarming, if any.
+    ///
+    /// This should be called before processing a payload, so that both don't compete for the same
+    /// resources.
+    pub(super) fn stop_speculative_prewarm(&mut self) {
+        if let Some(handle) = self.speculative_prewarm.take() {
+            handle.stop_prewarming_execution();
+        }
+    }
+
     /// Sets the sparse trie to be kept around for the state root computation.
     pub(super) fn set_sparse_trie(&mut self, sparse_trie: SparseTrie<ConfiguredSparseTrie>) {
         self.sparse_trie = Some(sparse_trie);
@@ -284,7 +334,35 @@ where
             transactions.clear();
         }
 
-        let (cache, cache_metrics) = self.cache_for(header.parent_hash()).split();
+        let saved_cache = self.cache_for(header.parent_hash());
+        self.spawn_prewarm_task(
+            header,
+            saved_cache,
+            move || transactions,
+            provider_builder,
+            to_multi_proof,
+        )
+    }
+
+    /// Spawns the prewarming task that operates on the given cache, for the transactions returned
+    /// by `transactions`, which is called by the task.
+    fn spawn_prewarm_task<P>(
+        &self,
+        header: SealedHeaderFor<N>,
+        saved_cache: SavedCache,
+        transactions: impl FnOnce() -> VecDeque<Recovered<N::SignedTx>> + Send + 'static,
+        provider_builder: StateProviderBuilder<N, P>,
+        to_multi_proof: Option<Sender<MultiProofMessage>>,
+    ) -> CacheTaskHandle
+    where
+        P: BlockReader
+            + StateProviderFactory
+            + StateReader
+            + StateCommitmentProvider
+            + Clone
+            + 'static,
+    {
+        let (cache, cache_metrics) = saved_cache.split();
         // configure prewarming
         let prewarm_ctx = PrewarmContext {
             header,
@@ -303,13 +381,12 @@ where
             self.execution_cache.clone(),
             prewarm_ctx,
             to_multi_proof,
-            transactions,
         );
         let to_prewarm_task = prewarm_task.actions_tx();
 
         // spawn pre-warm task
         self.executor.spawn_blocking(move || {
-            prewarm_task.run();
+            prewarm_task.run(transactions());
         });
         CacheTaskHandle { cache, to_prewarm_task: Some(to_prewarm_task), cache_metrics }
     }
@@ -602,26 +679,32 @@ mod tests {
             evm_state_to_hashed_post_state, executor::WorkloadExecutor, PayloadProcessor,
         },
         precompile_cache::PrecompileCacheMap,
-        StateProviderBuilder, TreeConfig,
+        PrewarmTransactions, StateProviderBuilder, TreeConfig,
     };
+    use alloy_consensus::{transaction::Recovered, TxEip1559};
+    use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT_30M, INITIAL_BASE_FEE};
     use alloy_evm::block::StateChangeSource;
+    use alloy_genesis::{Genesis, GenesisAccount};
     use rand::Rng;
-    use reth_chainspec::ChainSpec;
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
     use reth_db_common::init::init_genesis;
-    use reth_ethereum_primitives::EthPrimitives;
+    use reth_ethereum_primitives::{EthPrimitives, Transaction, TransactionSigned};
     use reth_evm::OnStateHook;
     use reth_evm_ethereum::EthEvmConfig;
-    use reth_primitives_traits::{Account, StorageEntry};
+    use reth_primitives_traits::{Account, SealedHeader, StorageEntry};
     use reth_provider::{
         providers::{BlockchainProvider, ConsistentDbView},
         test_utils::create_test_provider_factory_with_chain_spec,
-        ChainSpecProvider, HashingWriter,
+        ChainSpecProvider, HashingWriter, HeaderProvider,
     };
-    use reth_testing_utils::generators;
+    use reth_testing_utils::generators::{self, sign_tx_with_key_pair};
     use reth_trie::{test_utils::state_root, HashedPostState, TrieInput};
     use revm_primitives::{Address, HashMap, B256, KECCAK_EMPTY, U256};
     use revm_state::{AccountInfo, AccountStatus, EvmState, EvmStorageSlot};
-    use std::sync::Arc;
+    use std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    };
 
     fn create_mock_state_updates(num_accounts: usize, updates_per_account: usize) -> Vec<EvmState> {
         let mut rng = generators::rng();
@@ -754,4 +837,82 @@ mod tests {
             "State root mismatch: task={root_from_task}, base={root_from_regular}"
         );
     }
+
+    /// Provides the same transactions for every parent block.
+    struct StaticPrewarmTransactions(Vec<Recovered<TransactionSigned>>);
+
+    impl PrewarmTransactions<EthPrimitives> for StaticPrewarmTransactions {
+        fn prewarm_transactions(
+            &self,
+            _parent: &SealedHeader,
+        ) -> Vec<Recovered<TransactionSigned>> {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn test_speculative_prewarm_fills_cache_of_parent() {
+        reth_tracing::init_test_tracing();
+
+        let mut rng = generators::rng();
+        let key = generators::generate_key(&mut rng);
+        let sender = Address::from_raw_public_key(&key.public_key().serialize_uncompressed()[1..]);
+        let recipient: Address = rng.random();
+
+        let genesis = Genesis { gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, ..Default::default() }
+            .extend_accounts([(sender, GenesisAccount::default().with_balance(U256::MAX))]);
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(genesis)
+                .london_activated()
+                .build(),
+        );
+        let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+        let genesis_hash = init_genesis(&factory).unwrap();
+        let provider = BlockchainProvider::new(factory).unwrap();
+        let parent = provider.sealed_header(0).unwrap().unwrap();
+
+        let transfer = sign_tx_with_key_pair(
+            key,
+            Transaction::Eip1559(TxEip1559 {
+                chain_id: MAINNET.chain.id(),
+                gas_limit: 21_000,
+                max_fee_per_gas: INITIAL_BASE_FEE as u128,
+                to: recipient.into(),
+                value: U256::from(1),
+                ..Default::default()
+            }),
+        );
+        let transactions =
+            StaticPrewarmTransactions(vec![Recovered::new_unchecked(transfer, sender)]);
+
+        let mut payload_processor = PayloadProcessor::<EthPrimitives, _>::new(
+            WorkloadExecutor::default(),
+            EthEvmConfig::new(chain_spec),
+            &TreeConfig::default(),
+            PrecompileCacheMap::default(),
+        );
+        payload_processor.spawn_speculative_prewarm(
+            parent,
+            Arc::new(transactions),
+            StateProviderBuilder::new(provider, genesis_hash, None),
+        );
+
+        // the accounts touched by the transfer end up in the cache the payload is executed with
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            let cache = payload_processor
+                .execution_cache
+                .get_cache_for(genesis_hash)
+                .expect("cache of the parent block is saved");
+            if cache.cache().get_account(&sender).is_some() &&
+                cache.cache().get_account(&recipient).is_some()
+            {
+                break
+            }
+            assert!(Instant::now() < deadline, "prewarming didn't fill the cache");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
 }
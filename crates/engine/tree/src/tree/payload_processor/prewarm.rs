@@ -65,19 +65,18 @@ where
     P: BlockReader + StateProviderFactory + StateReader + StateCommitmentProvider + Clone + 'static,
     Evm: ConfigureEvm<Primitives = N> + 'static,
 {
-    /// Initializes the task with the given transactions pending execution
+    /// Initializes the task, the transactions to execute are passed to [`Self::run`].
     pub(super) fn new(
         executor: WorkloadExecutor,
         execution_cache: ExecutionCache,
         ctx: PrewarmContext<N, P, Evm>,
         to_multi_proof: Option<Sender<MultiProofMessage>>,
-        pending: VecDeque<Recovered<N::SignedTx>>,
     ) -> Self {
         let (actions_tx, actions_rx) = channel();
         Self {
             executor,
             execution_cache,
-            pending,
+            pending: VecDeque::new(),
             ctx,
             max_concurrency: 64,
             to_multi_proof,
@@ -145,11 +144,12 @@ where
         self.actions_tx = channel().0;
     }
 
-    /// Executes the task.
+    /// Executes the task for the given transactions.
     ///
     /// This will execute the transactions until all transactions have been processed or the task
     /// was cancelled.
-    pub(super) fn run(mut self) {
+    pub(super) fn run(mut self, pending: VecDeque<Recovered<N::SignedTx>>) {
+        self.pending = pending;
         self.ctx.metrics.transactions.set(self.pending.len() as f64);
         self.ctx.metrics.transactions_histogram.record(self.pending.len() as f64);
 
@@ -382,6 +382,28 @@ pub(super) enum PrewarmTaskEvent {
     },
 }
 
+/// Provides the transactions that are likely to be included in the next payload.
+///
+/// When a forkchoice update with payload attributes is received, these transactions are executed
+/// on top of the new head to prewarm the caches for the `newPayload` of the built block. The
+/// transactions are requested by the prewarming task, so this doesn't block the engine.
+pub trait PrewarmTransactions<N: NodePrimitives>: Send + Sync {
+    /// Returns the transactions a payload built on top of the given parent block would most
+    /// likely include, in the order they would be included.
+    fn prewarm_transactions(&self, parent: &SealedHeaderFor<N>) -> Vec<Recovered<N::SignedTx>>;
+}
+
+/// A [`PrewarmTransactions`] implementation that doesn't provide any transactions.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct NoopPrewarmTransactions;
+
+impl<N: NodePrimitives> PrewarmTransactions<N> for NoopPrewarmTransactions {
+    fn prewarm_transactions(&self, _parent: &SealedHeaderFor<N>) -> Vec<Recovered<N::SignedTx>> {
+        Vec::new()
+    }
+}
+
 /// Metrics for transactions prewarming.
 #[derive(Metrics, Clone)]
 #[metrics(scope = "sync.prewarm")]
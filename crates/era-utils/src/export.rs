@@ -2,7 +2,7 @@
 //! and injecting them into era1 files with `Era1Writer`.
 
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{BlockNumber, B256, U256};
+use alloy_primitives::{BlockNumber, Sealable, U256};
 use eyre::{eyre, Result};
 use reth_era::{
     era1_file::Era1Writer,
@@ -12,6 +12,7 @@ use reth_era::{
         TotalDifficulty, MAX_BLOCKS_PER_ERA1,
     },
 };
+use reth_ethereum_consensus::epoch_accumulator_root;
 use reth_fs_util as fs;
 use reth_storage_api::{BlockNumReader, BlockReader, HeaderProvider};
 use std::{
@@ -154,10 +155,11 @@ where
         let mut offsets = Vec::with_capacity(block_count);
         let mut position = VERSION_ENTRY_SIZE as i64;
         let mut blocks_written = 0;
-        let mut final_header_data = Vec::new();
+        let mut header_records = Vec::with_capacity(block_count);
 
         for (i, header) in headers.into_iter().enumerate() {
             let expected_block_number = start_block + i as u64;
+            let block_hash = header.hash_slow();
 
             let (compressed_header, compressed_body, compressed_receipts) = compress_block_data(
                 provider,
@@ -166,10 +168,8 @@ where
                 &mut total_difficulty,
             )?;
 
-            // Save last block's header data for accumulator
-            if expected_block_number == end_block {
-                final_header_data = compressed_header.data.clone();
-            }
+            // Save the header record for the accumulator
+            header_records.push((block_hash, total_difficulty));
 
             let difficulty = TotalDifficulty::new(total_difficulty);
 
@@ -206,9 +206,7 @@ where
             }
         }
         if blocks_written > 0 {
-            let accumulator_hash =
-                B256::from_slice(&final_header_data[0..32.min(final_header_data.len())]);
-            let accumulator = Accumulator::new(accumulator_hash);
+            let accumulator = Accumulator::new(epoch_accumulator_root(header_records));
             let block_index = BlockIndex::new(start_block, offsets);
 
             writer.write_accumulator(&accumulator)?;
@@ -233,9 +231,10 @@ where
     Ok(created_files)
 }
 
-// Determines the actual last block number that can be exported,
-// Uses `headers_range` fallback when `best_block_number` is stale due to static file storage.
-fn determine_export_range<P>(provider: &P, config: &ExportConfig) -> Result<BlockNumber>
+/// Determines the actual last block number that can be exported.
+///
+/// Uses `headers_range` as fallback when `best_block_number` is stale due to static file storage.
+pub fn determine_export_range<P>(provider: &P, config: &ExportConfig) -> Result<BlockNumber>
 where
     P: HeaderProvider + BlockNumReader,
 {
@@ -1,5 +1,6 @@
 use alloy_primitives::{BlockHash, BlockNumber, U256};
 use futures_util::{Stream, StreamExt};
+use reth_consensus::PreMergeHeaderValidator;
 use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW},
     table::Value,
@@ -15,7 +16,7 @@ use reth_era::{
 use reth_era_downloader::EraMeta;
 use reth_etl::Collector;
 use reth_fs_util as fs;
-use reth_primitives_traits::{Block, FullBlockBody, FullBlockHeader, NodePrimitives};
+use reth_primitives_traits::{Block, FullBlockBody, FullBlockHeader, NodePrimitives, SealedHeader};
 use reth_provider::{
     providers::StaticFileProviderRWRefMut, writer::UnifiedStorageWriter, BlockWriter,
     ProviderError, StaticFileProviderFactory, StaticFileSegment, StaticFileWriter,
@@ -40,11 +41,15 @@ use tracing::info;
 
 /// Imports blocks from `downloader` using `provider`.
 ///
+/// If a `validator` is given, the headers of every file are verified against the epoch accumulator
+/// of the file before they are imported, see [`verify`].
+///
 /// Returns current block height.
 pub fn import<Downloader, Era, PF, B, BB, BH>(
     mut downloader: Downloader,
     provider_factory: &PF,
     hash_collector: &mut Collector<BlockHash, BlockNumber>,
+    validator: Option<&dyn PreMergeHeaderValidator<BH>>,
 ) -> eyre::Result<BlockNumber>
 where
     B: Block<Header = BH, Body = BB>,
@@ -86,11 +91,16 @@ where
         .ok_or(ProviderError::TotalDifficultyNotFound(height))?;
 
     while let Some(meta) = rx.recv()? {
+        let meta = meta?;
+        if let Some(validator) = validator {
+            verify(&meta, validator)?;
+        }
+
         let from = height;
         let provider = provider_factory.database_provider_rw()?;
 
         height = process(
-            &meta?,
+            &meta,
             &mut static_file_provider.latest_writer(StaticFileSegment::Headers)?,
             &provider,
             hash_collector,
@@ -239,6 +249,32 @@ where
     Ok(reader)
 }
 
+/// Verifies the headers of the era file described by `meta` using `validator`.
+///
+/// The headers are validated against the total difficulties and the epoch accumulator stored in
+/// the file, so this doesn't need the total difficulty of any previously imported header.
+pub fn verify<Era, BH>(meta: &Era, validator: &dyn PreMergeHeaderValidator<BH>) -> eyre::Result<()>
+where
+    Era: EraMeta + ?Sized,
+    BH: FullBlockHeader + Value,
+{
+    let mut iter = open(meta)?.iter();
+    let headers = (&mut iter)
+        .map(|block| {
+            let block = block?;
+            let header: BH = block.header.decode()?;
+            Ok((SealedHeader::seal_slow(header), block.total_difficulty.value))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    let accumulator = iter
+        .accumulator()
+        .ok_or_else(|| eyre::eyre!("missing accumulator in {}", meta.path().display()))?;
+    validator.validate_pre_merge_headers(&headers, accumulator.root)?;
+
+    Ok(())
+}
+
 /// Extracts a pair of [`FullBlockHeader`] and [`FullBlockBody`] from [`BlockTuple`].
 pub fn decode<BH, BB, E>(block: Result<BlockTuple, E>) -> eyre::Result<(BH, BB)>
 where
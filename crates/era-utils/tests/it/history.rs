@@ -4,9 +4,12 @@ use reth_db_common::init::init_genesis;
 use reth_era::execution_types::MAX_BLOCKS_PER_ERA1;
 use reth_era_downloader::{EraClient, EraStream, EraStreamConfig};
 use reth_era_utils::{export, import, ExportConfig};
+use reth_ethereum_consensus::EthBeaconConsensus;
 use reth_etl::Collector;
 use reth_fs_util as fs;
-use reth_provider::{test_utils::create_test_provider_factory, BlockNumReader, BlockReader};
+use reth_provider::{
+    test_utils::create_test_provider_factory, BlockNumReader, BlockReader, ChainSpecProvider,
+};
 use std::str::FromStr;
 use tempfile::tempdir;
 
@@ -37,8 +40,11 @@ async fn test_history_imports_from_fresh_state_successfully() {
     let folder = Some(folder.path().to_owned());
     let mut hash_collector = Collector::new(4096, folder);
 
+    // Verify the headers against the accumulator of the file
+    let validator = EthBeaconConsensus::new(pf.chain_spec());
+
     let expected_block_number = 8191;
-    let actual_block_number = import(stream, &pf, &mut hash_collector).unwrap();
+    let actual_block_number = import(stream, &pf, &mut hash_collector, Some(&validator)).unwrap();
 
     assert_eq!(actual_block_number, expected_block_number);
 }
@@ -67,7 +73,7 @@ async fn test_roundtrip_export_after_import() {
     let mut hash_collector = Collector::new(4096, folder);
 
     // Import blocks from one era1 file into database
-    let last_imported_block_height = import(stream, &pf, &mut hash_collector).unwrap();
+    let last_imported_block_height = import(stream, &pf, &mut hash_collector, None).unwrap();
 
     assert_eq!(last_imported_block_height, 8191);
     let provider_ref = pf.provider_rw().unwrap().0;
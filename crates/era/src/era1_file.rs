@@ -91,6 +91,14 @@ impl<R: Read> BlockTupleIterator<R> {
             block_index: None,
         }
     }
+
+    /// Returns the [`Accumulator`] of the file, once the iterator has read past it.
+    ///
+    /// The accumulator follows all block tuples, so it's available after the iterator is
+    /// exhausted.
+    pub const fn accumulator(&self) -> Option<&Accumulator> {
+        self.accumulator.as_ref()
+    }
 }
 
 impl<R: Read + Seek> Iterator for BlockTupleIterator<R> {
@@ -1,15 +1,16 @@
 //! CLI definition and entrypoint to executable
 
-use crate::chainspec::EthereumChainSpecParser;
+use crate::{chainspec::EthereumChainSpecParser, replica::ReplicaCommand};
 use clap::{Parser, Subcommand};
 use reth_chainspec::{ChainSpec, EthChainSpec, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     common::{CliComponentsBuilder, CliNodeTypes},
-    config_cmd, db, download, dump_genesis, export_era, import, import_era, init_cmd, init_state,
+    config_cmd, db, download, dump_genesis, export, export_era, import, import_era, init_cmd,
+    init_state,
     launcher::FnLauncher,
     node::{self, NoArgs},
-    p2p, prune, re_execute, recover, stage,
+    p2p, prune, re_execute, recover, stage, verify_chain, verify_manifest,
 };
 use reth_cli_runner::CliRunner;
 use reth_db::DatabaseEnv;
@@ -152,12 +153,20 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
     ///     })
     ///     .unwrap();
     /// ```
-    pub fn with_runner<L, Fut>(self, runner: CliRunner, launcher: L) -> eyre::Result<()>
+    pub fn with_runner<L, Fut>(mut self, runner: CliRunner, launcher: L) -> eyre::Result<()>
     where
         L: FnOnce(WithLaunchContext<NodeBuilder<Arc<DatabaseEnv>, C::ChainSpec>>, Ext) -> Fut,
         Fut: Future<Output = eyre::Result<()>>,
         C: ChainSpecParser<ChainSpec = ChainSpec>,
     {
+        // The replica serves the Ethereum RPC and is therefore only available with the Ethereum
+        // components
+        if matches!(self.command, Commands::Replica(_)) {
+            let _guard = self.init_cli()?;
+            let Commands::Replica(command) = self.command else { unreachable!() };
+            return runner.run_command_until_exit(|ctx| command.execute(ctx))
+        }
+
         let components = |spec: Arc<C::ChainSpec>| {
             (EthEvmConfig::ethereum(spec.clone()), EthBeaconConsensus::new(spec))
         };
@@ -183,20 +192,11 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
     where
         N: CliNodeTypes<
             Primitives: NodePrimitives<BlockHeader = alloy_consensus::Header>,
-            ChainSpec: Hardforks,
+            ChainSpec: Hardforks + EthChainSpec<Header = alloy_consensus::Header>,
         >,
         C: ChainSpecParser<ChainSpec = N::ChainSpec>,
     {
-        // Add network name if available to the logs dir
-        if let Some(chain_spec) = self.command.chain_spec() {
-            self.logs.log_file_directory =
-                self.logs.log_file_directory.join(chain_spec.chain().to_string());
-        }
-        let _guard = self.init_tracing()?;
-        info!(target: "reth::cli", "Initialized tracing, debug log directory: {}", self.logs.log_file_directory);
-
-        // Install the prometheus recorder to be sure to record all metrics
-        let _ = install_prometheus_recorder();
+        let _guard = self.init_cli()?;
 
         match self.command {
             Commands::Node(command) => runner.run_command_until_exit(|ctx| {
@@ -215,6 +215,8 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
             Commands::ExportEra(command) => {
                 runner.run_blocking_until_ctrl_c(command.execute::<N>())
             }
+            Commands::Export(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
+            Commands::VerifyManifest(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::DumpGenesis(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
             Commands::Download(command) => runner.run_blocking_until_ctrl_c(command.execute::<N>()),
@@ -232,7 +234,35 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Cli<C, Ext> {
             Commands::ReExecute(command) => {
                 runner.run_until_ctrl_c(command.execute::<N>(components))
             }
+            Commands::VerifyChain(command) => {
+                runner.run_until_ctrl_c(command.execute::<N>(components))
+            }
+            Commands::Replica(_) => Err(eyre::eyre!(
+                "the replica command is only supported with the Ethereum components"
+            )),
+        }
+    }
+
+    /// Initializes tracing and metrics for the configured command.
+    ///
+    /// If file logging is enabled, this function returns a guard that must be kept alive to ensure
+    /// that all logs are flushed to disk.
+    fn init_cli(&mut self) -> eyre::Result<Option<FileWorkerGuard>>
+    where
+        C: ChainSpecParser<ChainSpec: EthChainSpec>,
+    {
+        // Add network name if available to the logs dir
+        if let Some(chain_spec) = self.command.chain_spec() {
+            self.logs.log_file_directory =
+                self.logs.log_file_directory.join(chain_spec.chain().to_string());
         }
+        let guard = self.init_tracing()?;
+        info!(target: "reth::cli", "Initialized tracing, debug log directory: {}", self.logs.log_file_directory);
+
+        // Install the prometheus recorder to be sure to record all metrics
+        let _ = install_prometheus_recorder();
+
+        Ok(guard)
     }
 
     /// Initializes tracing with the configured options.
@@ -267,6 +297,12 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Exports block to era1 files in a specified directory.
     #[command(name = "export-era")]
     ExportEra(export_era::ExportEraCommand<C>),
+    /// Exports node data, e.g. the current state.
+    #[command(name = "export")]
+    Export(export::Command<C>),
+    /// Verifies a chain data export against its signed manifest.
+    #[command(name = "verify-manifest")]
+    VerifyManifest(verify_manifest::Command),
     /// Dumps genesis block JSON configuration to stdout.
     DumpGenesis(dump_genesis::DumpGenesisCommand<C>),
     /// Database debugging utilities
@@ -297,6 +333,12 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
+    /// Re-verify the receipts and state roots of historical blocks in parallel.
+    #[command(name = "verify-chain")]
+    VerifyChain(verify_chain::Command<C>),
+    /// Serve read-only RPC from the datadir of a primary node.
+    #[command(name = "replica")]
+    Replica(ReplicaCommand<C>),
 }
 
 impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
@@ -308,6 +350,8 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
             Self::InitState(cmd) => cmd.chain_spec(),
             Self::Import(cmd) => cmd.chain_spec(),
             Self::ExportEra(cmd) => cmd.chain_spec(),
+            Self::Export(cmd) => cmd.chain_spec(),
+            Self::VerifyManifest(_) => None,
             Self::ImportEra(cmd) => cmd.chain_spec(),
             Self::DumpGenesis(cmd) => cmd.chain_spec(),
             Self::Db(cmd) => cmd.chain_spec(),
@@ -320,6 +364,8 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Commands<C, Ext> {
             Self::Recover(cmd) => cmd.chain_spec(),
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::VerifyChain(cmd) => cmd.chain_spec(),
+            Self::Replica(cmd) => cmd.chain_spec(),
         }
     }
 }
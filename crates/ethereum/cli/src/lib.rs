@@ -12,6 +12,7 @@
 pub mod chainspec;
 pub mod interface;
 pub use interface::Cli;
+pub mod replica;
 
 #[cfg(test)]
 mod test {
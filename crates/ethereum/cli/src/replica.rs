@@ -0,0 +1,137 @@
+//! `reth replica` command.
+
+use clap::Parser;
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
+use reth_cli_runner::CliContext;
+use reth_cli_util::parse_duration_from_secs_or_ms;
+use reth_network_api::noop::NoopNetwork;
+use reth_node_core::args::RpcServerArgs;
+use reth_node_ethereum::{consensus::EthBeaconConsensus, EthEvmConfig, EthereumNode};
+use reth_provider::{
+    providers::{BlockchainProvider, ReplicaFollower},
+    ChainSpecProvider,
+};
+use reth_rpc::EthApiBuilder;
+use reth_rpc_builder::{config::RethRpcServerConfig, RpcModuleBuilder};
+use reth_transaction_pool::noop::NoopTransactionPool;
+use std::{sync::Arc, time::Duration};
+use tracing::{debug, info};
+
+/// `reth replica` command
+///
+/// Serves read-only RPC from the datadir of a primary node that runs in a separate process.
+///
+/// The storage is opened read-only and the primary's committed head is followed, so additional
+/// replicas can be started to scale read throughput without running additional full nodes.
+#[derive(Debug, Parser)]
+pub struct ReplicaCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// All rpc related arguments
+    #[command(flatten)]
+    rpc: RpcServerArgs,
+
+    /// The interval in which the datadir is checked for blocks committed by the primary.
+    #[arg(
+        long = "replica.poll-interval",
+        value_parser = parse_duration_from_secs_or_ms,
+        default_value = "1",
+        value_name = "DURATION"
+    )]
+    poll_interval: Duration,
+}
+
+impl<C: ChainSpecParser> ReplicaCommand<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.env.chain)
+    }
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> ReplicaCommand<C> {
+    /// Execute `replica` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } =
+            self.env.init::<EthereumNode>(AccessRights::ROFollow)?;
+        let chain_spec = provider_factory.chain_spec();
+        let provider = BlockchainProvider::new(provider_factory)?;
+        let evm_config = EthEvmConfig::new(chain_spec.clone());
+
+        let eth_config = self.rpc.eth_config();
+        let eth_api = EthApiBuilder::new(
+            provider.clone(),
+            NoopTransactionPool::default(),
+            NoopNetwork::default(),
+            evm_config.clone(),
+        )
+        .eth_state_cache_config(eth_config.cache)
+        .task_spawner(ctx.task_executor.clone())
+        .gas_cap(eth_config.rpc_gas_cap.into())
+        .max_simulate_blocks(eth_config.rpc_max_simulate_blocks)
+        .eth_proof_window(eth_config.eth_proof_window)
+        .fee_history_cache_config(eth_config.fee_history_cache)
+        .proof_permits(eth_config.proof_permits)
+        .proof_cache_max_entries(eth_config.proof_cache_max_entries)
+        .gas_oracle_config(eth_config.gas_oracle)
+        .tx_forwarder(self.rpc.tx_forwarder_config())
+        .build();
+
+        let modules = RpcModuleBuilder::default()
+            .with_provider(provider.clone())
+            .with_noop_pool()
+            .with_noop_network()
+            .with_executor(Box::new(ctx.task_executor))
+            .with_evm_config(evm_config)
+            .with_consensus(EthBeaconConsensus::new(chain_spec))
+            .build(self.rpc.transport_rpc_module_config(), eth_api);
+
+        let server_config = self.rpc.rpc_server_config().with_roles(self.rpc.rpc_roles()?);
+        #[cfg(feature = "graphql")]
+        let server_config = server_config.with_graphql(self.rpc.graphql_config().map(|config| {
+            reth_rpc_graphql::GraphQlLayer::new(reth_rpc_graphql::graphql_schema(
+                Arc::new(provider.clone()),
+                config,
+            ))
+        }));
+        #[cfg(not(feature = "graphql"))]
+        if self.rpc.graphql {
+            tracing::warn!(target: "reth::cli", "Ignoring --graphql, reth was built without the graphql feature");
+        }
+        let _handle = server_config.start(&modules).await?;
+        info!(target: "reth::cli", "Replica RPC server started");
+
+        #[cfg(feature = "grpc")]
+        if self.rpc.grpc {
+            let grpc_config = reth_rpc_grpc::GrpcConfig {
+                max_block_range: self.rpc.rpc_max_blocks_per_filter.0,
+                max_logs_per_stream: self.rpc.rpc_max_logs_per_stream.0.map(|max| max as usize),
+            };
+            let addr = std::net::SocketAddr::new(self.rpc.grpc_addr, self.rpc.grpc_port);
+            let handle = reth_rpc_grpc::GrpcServer::new(Arc::new(provider.clone()), grpc_config)
+                .start(addr)
+                .await?;
+            info!(target: "reth::cli", url = %handle.local_addr(), "Replica gRPC server started");
+        }
+        #[cfg(not(feature = "grpc"))]
+        if self.rpc.grpc {
+            tracing::warn!(target: "reth::cli", "Ignoring --grpc, reth was built without the grpc feature");
+        }
+
+        let follower = ReplicaFollower::new(provider);
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Some(head) = follower.poll()? {
+                debug!(
+                    target: "reth::cli",
+                    number = head.number,
+                    hash = ?head.hash(),
+                    "Replica head advanced"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::EthBeaconConsensus;
+use alloc::{
+    boxed::Box,
+    fmt::Debug,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_consensus::{BlockHeader as _, Transaction};
+use alloy_eips::{
+    eip4844::env_settings::{EnvKzgSettings, KzgSettings},
+    eip7594::BlobTransactionSidecarVariant,
+    Typed2718,
+};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_consensus::{BlobSidecarConsensus, ConsensusError, InvalidBlobSidecar};
+use reth_primitives_traits::{Block, BlockBody, GotExpected, SealedBlock, SignedTransaction};
+
+impl<B, ChainSpec> BlobSidecarConsensus<B> for EthBeaconConsensus<ChainSpec>
+where
+    B: Block,
+    ChainSpec: EthChainSpec<Header = B::Header> + EthereumHardforks + Debug + Send + Sync,
+{
+    fn validate_blob_sidecars(
+        &self,
+        block: &SealedBlock<B>,
+        sidecars: &[BlobTransactionSidecarVariant],
+    ) -> Result<(), ConsensusError> {
+        validate_blob_sidecars(block, sidecars, &self.chain_spec, EnvKzgSettings::Default.get())
+    }
+}
+
+/// Validates the sidecars of the blob transactions of the block with the given KZG settings.
+///
+/// Expects one sidecar per blob transaction, in the order of the block body. Before Osaka the
+/// sidecars must be EIP-4844 sidecars with blob proofs, from Osaka on EIP-7594 sidecars with cell
+/// proofs.
+pub fn validate_blob_sidecars<B, ChainSpec>(
+    block: &SealedBlock<B>,
+    sidecars: &[BlobTransactionSidecarVariant],
+    chain_spec: &ChainSpec,
+    settings: &KzgSettings,
+) -> Result<(), ConsensusError>
+where
+    B: Block,
+    ChainSpec: EthereumHardforks,
+{
+    let blob_txs =
+        block.body().transactions_iter().filter(|tx| tx.is_eip4844()).collect::<Vec<_>>();
+    if blob_txs.len() != sidecars.len() {
+        return Err(ConsensusError::BlobSidecarCountMismatch(GotExpected {
+            got: sidecars.len(),
+            expected: blob_txs.len(),
+        }))
+    }
+
+    let is_osaka = chain_spec.is_osaka_active_at_timestamp(block.header().timestamp());
+    for (tx, sidecar) in blob_txs.into_iter().zip(sidecars) {
+        let invalid = |reason: String| {
+            ConsensusError::InvalidBlobSidecar(Box::new(InvalidBlobSidecar {
+                tx_hash: *tx.tx_hash(),
+                reason,
+            }))
+        };
+        let versioned_hashes = tx.blob_versioned_hashes().unwrap_or_default();
+
+        match sidecar {
+            BlobTransactionSidecarVariant::Eip4844(sidecar) if !is_osaka => {
+                sidecar.validate(versioned_hashes, settings)
+            }
+            BlobTransactionSidecarVariant::Eip7594(sidecar) if is_osaka => {
+                sidecar.validate(versioned_hashes, settings)
+            }
+            BlobTransactionSidecarVariant::Eip4844(_) => {
+                return Err(invalid("unexpected EIP-4844 sidecar after Osaka".to_string()))
+            }
+            BlobTransactionSidecarVariant::Eip7594(_) => {
+                return Err(invalid("unexpected EIP-7594 sidecar before Osaka".to_string()))
+            }
+        }
+        .map_err(|err| invalid(err.to_string()))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip4844::BlobTransactionSidecar;
+    use reth_chainspec::MAINNET;
+    use reth_ethereum_primitives::Block;
+
+    #[test]
+    fn rejects_sidecars_without_blob_transactions() {
+        let consensus = EthBeaconConsensus::new(MAINNET.clone());
+        let block = SealedBlock::seal_slow(Block::default());
+
+        assert_eq!(consensus.validate_blob_sidecars(&block, &[]), Ok(()));
+
+        let sidecar = BlobTransactionSidecarVariant::Eip4844(BlobTransactionSidecar::default());
+        assert_eq!(
+            consensus.validate_blob_sidecars(&block, &[sidecar]),
+            Err(ConsensusError::BlobSidecarCountMismatch(GotExpected { got: 1, expected: 0 }))
+        );
+    }
+}
@@ -0,0 +1,717 @@
+//! Header validation for Clique proof-of-authority networks, see [EIP-225](https://eips.ethereum.org/EIPS/eip-225).
+//!
+//! [`CliqueHeaderValidator`] checks everything that can be checked with a header and its parent:
+//! the layout of the extra data, the vote and checkpoint fields, the timestamp period and the
+//! seal signature. Whether the signer of a block was authorized depends on all votes since the
+//! last checkpoint, which is tracked by a [`CliqueSnapshot`] that headers are applied to in
+//! ascending order.
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+    sync::Arc,
+    vec::Vec,
+};
+use alloy_consensus::{Header, EMPTY_OMMER_ROOT_HASH};
+use alloy_primitives::{Address, Bytes, Signature, B256, B64, U256};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_consensus::{
+    validation::{
+        validate_against_parent_eip1559_base_fee, validate_against_parent_gas_limit,
+        validate_against_parent_hash_number, validate_header_base_fee, validate_header_gas,
+    },
+    ConsensusError, CustomConsensusError, HeaderValidator,
+};
+use reth_primitives_traits::{
+    crypto::secp256k1::recover_signer_unchecked, GotExpected, SealedHeader,
+};
+
+/// The number of extra data bytes reserved for the signer vanity.
+pub const CLIQUE_EXTRA_VANITY: usize = 32;
+
+/// The number of extra data bytes reserved for the signer seal.
+pub const CLIQUE_EXTRA_SEAL: usize = 65;
+
+/// The length of an address in the list of signers of a checkpoint header.
+const ADDRESS_LEN: usize = 20;
+
+/// The default number of blocks after which votes are reset and the signers are checkpointed.
+pub const CLIQUE_DEFAULT_EPOCH: u64 = 30_000;
+
+/// The nonce of a block that votes to authorize the beneficiary as signer.
+pub const CLIQUE_NONCE_AUTH_VOTE: B64 = B64::new([0xff; 8]);
+
+/// The nonce of a block that votes to drop the beneficiary as signer.
+pub const CLIQUE_NONCE_DROP_VOTE: B64 = B64::ZERO;
+
+/// The difficulty of a block that was signed in turn.
+pub const CLIQUE_DIFF_IN_TURN: U256 = U256::from_limbs([2, 0, 0, 0]);
+
+/// The difficulty of a block that was signed out of turn.
+pub const CLIQUE_DIFF_NO_TURN: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+/// The Clique parameters of a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CliqueConfig {
+    /// The minimum number of seconds between two blocks.
+    pub period: u64,
+    /// The number of blocks after which votes are reset and the signers are checkpointed.
+    pub epoch: u64,
+}
+
+impl CliqueConfig {
+    /// Returns `true` if the block with the given number is a checkpoint block.
+    pub const fn is_checkpoint(&self, number: u64) -> bool {
+        number % self.epoch == 0
+    }
+}
+
+impl Default for CliqueConfig {
+    fn default() -> Self {
+        Self { period: 15, epoch: CLIQUE_DEFAULT_EPOCH }
+    }
+}
+
+/// Errors of the Clique header rules.
+///
+/// These are carried by [`ConsensusError::Custom`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CliqueError {
+    /// The extra data can't hold the vanity and the seal.
+    #[error("extra data of {len} bytes is shorter than the vanity and seal")]
+    ExtraDataTooShort {
+        /// The length of the extra data.
+        len: usize,
+    },
+    /// A block that isn't a checkpoint contains a list of signers.
+    #[error("non-checkpoint block {number} contains a list of signers")]
+    ExtraSigners {
+        /// The number of the block.
+        number: u64,
+    },
+    /// The list of signers of a checkpoint block isn't a list of addresses.
+    #[error("invalid list of signers of {len} bytes in checkpoint block {number}")]
+    InvalidCheckpointSigners {
+        /// The number of the block.
+        number: u64,
+        /// The length of the list of signers.
+        len: usize,
+    },
+    /// The list of signers of a checkpoint block doesn't match the signers of the snapshot.
+    #[error("list of signers in checkpoint block {number} doesn't match the authorized signers")]
+    MismatchingCheckpointSigners {
+        /// The number of the block.
+        number: u64,
+    },
+    /// A checkpoint block votes on a beneficiary.
+    #[error("checkpoint block {number} has a non-zero beneficiary")]
+    InvalidCheckpointBeneficiary {
+        /// The number of the block.
+        number: u64,
+    },
+    /// A checkpoint block has a vote nonce.
+    #[error("checkpoint block {number} has a non-zero vote nonce")]
+    InvalidCheckpointVote {
+        /// The number of the block.
+        number: u64,
+    },
+    /// The nonce is neither an authorize nor a drop vote.
+    #[error("invalid vote nonce {nonce}")]
+    InvalidVote {
+        /// The nonce of the block.
+        nonce: B64,
+    },
+    /// The mix digest is not zero.
+    #[error("non-zero mix digest")]
+    InvalidMixDigest,
+    /// The ommers hash is not the hash of an empty list.
+    #[error("non-empty ommers hash")]
+    InvalidOmmersHash,
+    /// The difficulty is neither the in-turn nor the out-of-turn difficulty.
+    #[error("invalid difficulty {difficulty}")]
+    InvalidDifficulty {
+        /// The difficulty of the block.
+        difficulty: U256,
+    },
+    /// The difficulty doesn't match the turn of the signer.
+    #[error("wrong difficulty: {0}")]
+    WrongDifficulty(GotExpected<U256>),
+    /// The block was sealed too soon after its parent.
+    #[error("timestamp {timestamp} is less than {period}s after the parent timestamp {parent_timestamp}")]
+    InvalidTimestamp {
+        /// The timestamp of the block.
+        timestamp: u64,
+        /// The timestamp of the parent block.
+        parent_timestamp: u64,
+        /// The minimum number of seconds between two blocks.
+        period: u64,
+    },
+    /// The signer can't be recovered from the seal.
+    #[error("invalid seal signature")]
+    InvalidSignature,
+    /// The signer isn't an authorized signer.
+    #[error("unauthorized signer {signer}")]
+    UnauthorizedSigner {
+        /// The signer of the block.
+        signer: Address,
+    },
+    /// The signer signed one of the most recent blocks.
+    #[error("signer {signer} signed recently")]
+    RecentlySigned {
+        /// The signer of the block.
+        signer: Address,
+    },
+    /// The block doesn't follow the last block that was applied to the snapshot.
+    #[error("block {number} doesn't follow the snapshot at block {snapshot}")]
+    NonContiguousBlock {
+        /// The number of the block.
+        number: u64,
+        /// The number of the last block of the snapshot.
+        snapshot: u64,
+    },
+}
+
+impl CustomConsensusError for CliqueError {}
+
+impl From<CliqueError> for ConsensusError {
+    fn from(err: CliqueError) -> Self {
+        Self::custom(err)
+    }
+}
+
+/// Returns the hash that the signer of the header signed, which is the hash of the header without
+/// the seal.
+pub fn clique_seal_hash(header: &Header) -> Result<B256, CliqueError> {
+    let len = header.extra_data.len();
+    if len < CLIQUE_EXTRA_VANITY + CLIQUE_EXTRA_SEAL {
+        return Err(CliqueError::ExtraDataTooShort { len })
+    }
+    let mut unsealed = header.clone();
+    unsealed.extra_data = Bytes::copy_from_slice(&header.extra_data[..len - CLIQUE_EXTRA_SEAL]);
+    Ok(unsealed.hash_slow())
+}
+
+/// Recovers the signer of the header from the seal in its extra data.
+pub fn recover_clique_signer(header: &Header) -> Result<Address, CliqueError> {
+    let seal_hash = clique_seal_hash(header)?;
+    let seal = &header.extra_data[header.extra_data.len() - CLIQUE_EXTRA_SEAL..];
+    let y_parity = match seal[64] {
+        0 => false,
+        1 => true,
+        _ => return Err(CliqueError::InvalidSignature),
+    };
+    let signature = Signature::new(
+        U256::from_be_slice(&seal[..32]),
+        U256::from_be_slice(&seal[32..64]),
+        y_parity,
+    );
+    recover_signer_unchecked(&signature, seal_hash).map_err(|_| CliqueError::InvalidSignature)
+}
+
+/// Returns the list of signers in the extra data of a checkpoint header.
+pub fn clique_checkpoint_signers(header: &Header) -> Result<Vec<Address>, CliqueError> {
+    let signers = clique_signers_bytes(header)?;
+    if signers.len() % ADDRESS_LEN != 0 {
+        return Err(CliqueError::InvalidCheckpointSigners {
+            number: header.number,
+            len: signers.len(),
+        })
+    }
+    Ok(signers.chunks_exact(ADDRESS_LEN).map(Address::from_slice).collect())
+}
+
+/// Returns the bytes between the vanity and the seal of the extra data.
+fn clique_signers_bytes(header: &Header) -> Result<&[u8], CliqueError> {
+    let len = header.extra_data.len();
+    if len < CLIQUE_EXTRA_VANITY + CLIQUE_EXTRA_SEAL {
+        return Err(CliqueError::ExtraDataTooShort { len })
+    }
+    Ok(&header.extra_data[CLIQUE_EXTRA_VANITY..len - CLIQUE_EXTRA_SEAL])
+}
+
+/// Validates the Clique rules of a standalone header, except for the seal.
+pub fn validate_clique_header(header: &Header, config: &CliqueConfig) -> Result<(), CliqueError> {
+    let number = header.number;
+    let checkpoint = config.is_checkpoint(number);
+
+    if checkpoint && header.beneficiary != Address::ZERO {
+        return Err(CliqueError::InvalidCheckpointBeneficiary { number })
+    }
+    if header.nonce != CLIQUE_NONCE_AUTH_VOTE && header.nonce != CLIQUE_NONCE_DROP_VOTE {
+        return Err(CliqueError::InvalidVote { nonce: header.nonce })
+    }
+    if checkpoint && header.nonce != CLIQUE_NONCE_DROP_VOTE {
+        return Err(CliqueError::InvalidCheckpointVote { number })
+    }
+
+    if checkpoint {
+        clique_checkpoint_signers(header)?;
+    } else if !clique_signers_bytes(header)?.is_empty() {
+        return Err(CliqueError::ExtraSigners { number })
+    }
+
+    if header.mix_hash != B256::ZERO {
+        return Err(CliqueError::InvalidMixDigest)
+    }
+    if header.ommers_hash != EMPTY_OMMER_ROOT_HASH {
+        return Err(CliqueError::InvalidOmmersHash)
+    }
+    if number > 0 &&
+        header.difficulty != CLIQUE_DIFF_IN_TURN &&
+        header.difficulty != CLIQUE_DIFF_NO_TURN
+    {
+        return Err(CliqueError::InvalidDifficulty { difficulty: header.difficulty })
+    }
+
+    Ok(())
+}
+
+/// A [`HeaderValidator`] for Clique proof-of-authority networks.
+///
+/// This validates the header fields and the seal, but not whether the signer was authorized to
+/// seal the block, which requires a [`CliqueSnapshot`].
+#[derive(Debug, Clone)]
+pub struct CliqueHeaderValidator<ChainSpec> {
+    /// Configuration
+    chain_spec: Arc<ChainSpec>,
+    /// The Clique parameters of the chain.
+    config: CliqueConfig,
+}
+
+impl<ChainSpec: EthChainSpec + EthereumHardforks> CliqueHeaderValidator<ChainSpec> {
+    /// Create a new instance of [`CliqueHeaderValidator`]
+    pub const fn new(chain_spec: Arc<ChainSpec>, config: CliqueConfig) -> Self {
+        Self { chain_spec, config }
+    }
+
+    /// Creates a new instance with the Clique parameters of the chain's genesis config.
+    ///
+    /// Returns `None` if the chain isn't a Clique chain.
+    pub fn from_chain_spec(chain_spec: Arc<ChainSpec>) -> Option<Self> {
+        let clique = chain_spec.genesis().config.clique.as_ref()?;
+        let config = CliqueConfig {
+            period: clique.period.unwrap_or_default(),
+            epoch: clique.epoch.filter(|epoch| *epoch > 0).unwrap_or(CLIQUE_DEFAULT_EPOCH),
+        };
+        Some(Self::new(chain_spec, config))
+    }
+
+    /// Returns the Clique parameters of the chain.
+    pub const fn config(&self) -> &CliqueConfig {
+        &self.config
+    }
+}
+
+impl<ChainSpec> HeaderValidator<Header> for CliqueHeaderValidator<ChainSpec>
+where
+    ChainSpec: EthChainSpec<Header = Header> + EthereumHardforks + Debug + Send + Sync,
+{
+    fn validate_header(&self, header: &SealedHeader<Header>) -> Result<(), ConsensusError> {
+        let header = header.header();
+
+        validate_clique_header(header, &self.config)?;
+        validate_header_gas(header)?;
+        validate_header_base_fee(header, &self.chain_spec)?;
+        if header.withdrawals_root.is_some() {
+            return Err(ConsensusError::WithdrawalsRootUnexpected)
+        }
+
+        // the genesis block isn't sealed
+        if header.number > 0 {
+            recover_clique_signer(header)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_header_against_parent(
+        &self,
+        header: &SealedHeader<Header>,
+        parent: &SealedHeader<Header>,
+    ) -> Result<(), ConsensusError> {
+        validate_against_parent_hash_number(header.header(), parent)?;
+
+        if parent.timestamp.saturating_add(self.config.period) > header.timestamp {
+            return Err(CliqueError::InvalidTimestamp {
+                timestamp: header.timestamp,
+                parent_timestamp: parent.timestamp,
+                period: self.config.period,
+            }
+            .into())
+        }
+
+        validate_against_parent_gas_limit(header.header(), parent.header(), &self.chain_spec)?;
+        validate_against_parent_eip1559_base_fee(
+            header.header(),
+            parent.header(),
+            &self.chain_spec,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// A vote of a signer to authorize or drop a signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Vote {
+    /// The signer that cast the vote.
+    signer: Address,
+    /// The address that is voted on.
+    address: Address,
+    /// Whether to authorize or drop the address.
+    authorize: bool,
+}
+
+/// The running tally of the votes on an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Tally {
+    /// Whether the votes authorize or drop the address.
+    authorize: bool,
+    /// The number of votes.
+    votes: usize,
+}
+
+/// The set of authorized signers at a given block, along with the pending votes.
+///
+/// A snapshot starts at a checkpoint block and is advanced by [applying](Self::apply) every
+/// following header in ascending order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CliqueSnapshot {
+    /// The Clique parameters of the chain.
+    config: CliqueConfig,
+    /// The number of the last applied block.
+    number: u64,
+    /// The hash of the last applied block.
+    hash: B256,
+    /// The authorized signers.
+    signers: BTreeSet<Address>,
+    /// The signers of the most recent blocks, by block number.
+    recents: BTreeMap<u64, Address>,
+    /// The votes since the last checkpoint, in the order they were cast.
+    votes: Vec<Vote>,
+    /// The tally of the votes since the last checkpoint.
+    tally: BTreeMap<Address, Tally>,
+}
+
+impl CliqueSnapshot {
+    /// Creates a snapshot from the list of signers of a checkpoint header, e.g. the genesis
+    /// header.
+    pub fn from_checkpoint(
+        config: CliqueConfig,
+        header: &SealedHeader<Header>,
+    ) -> Result<Self, CliqueError> {
+        Ok(Self {
+            config,
+            number: header.number,
+            hash: header.hash(),
+            signers: clique_checkpoint_signers(header.header())?.into_iter().collect(),
+            recents: BTreeMap::new(),
+            votes: Vec::new(),
+            tally: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the number of the last applied block.
+    pub const fn number(&self) -> u64 {
+        self.number
+    }
+
+    /// Returns the hash of the last applied block.
+    pub const fn hash(&self) -> B256 {
+        self.hash
+    }
+
+    /// Returns the authorized signers, in ascending order.
+    pub const fn signers(&self) -> &BTreeSet<Address> {
+        &self.signers
+    }
+
+    /// Returns `true` if the signer is expected to seal the block with the given number.
+    pub fn is_in_turn(&self, number: u64, signer: &Address) -> bool {
+        let Some(offset) = self.signers.iter().position(|s| s == signer) else { return false };
+        number % self.signers.len() as u64 == offset as u64
+    }
+
+    /// Validates that the header was sealed by an authorized signer and applies its vote.
+    ///
+    /// The header must be the child of the last applied block. Returns the signer of the header.
+    pub fn apply(&mut self, header: &SealedHeader<Header>) -> Result<Address, ConsensusError> {
+        if header.number != self.number + 1 || header.parent_hash != self.hash {
+            return Err(CliqueError::NonContiguousBlock {
+                number: header.number,
+                snapshot: self.number,
+            }
+            .into())
+        }
+        let signer = recover_clique_signer(header.header())?;
+        self.apply_signed(header, signer)?;
+        Ok(signer)
+    }
+
+    /// Applies a header that was sealed by the given signer.
+    fn apply_signed(
+        &mut self,
+        header: &SealedHeader<Header>,
+        signer: Address,
+    ) -> Result<(), CliqueError> {
+        let number = header.number;
+
+        if self.config.is_checkpoint(number) {
+            let signers = clique_checkpoint_signers(header.header())?;
+            if !signers.iter().eq(self.signers.iter()) {
+                return Err(CliqueError::MismatchingCheckpointSigners { number })
+            }
+        }
+
+        if !self.signers.contains(&signer) {
+            return Err(CliqueError::UnauthorizedSigner { signer })
+        }
+        // a signer may only sign again once its last block shifted out of the recent window
+        let limit = self.signer_limit();
+        if self
+            .recents
+            .iter()
+            .any(|(seen, recent)| *recent == signer && number.checked_sub(limit) < Some(*seen))
+        {
+            return Err(CliqueError::RecentlySigned { signer })
+        }
+        let expected = if self.is_in_turn(number, &signer) {
+            CLIQUE_DIFF_IN_TURN
+        } else {
+            CLIQUE_DIFF_NO_TURN
+        };
+        if header.difficulty != expected {
+            return Err(CliqueError::WrongDifficulty(GotExpected {
+                got: header.difficulty,
+                expected,
+            }))
+        }
+
+        let authorize = if header.nonce == CLIQUE_NONCE_AUTH_VOTE {
+            true
+        } else if header.nonce == CLIQUE_NONCE_DROP_VOTE {
+            false
+        } else {
+            return Err(CliqueError::InvalidVote { nonce: header.nonce })
+        };
+
+        // all validations passed, update the snapshot
+        if self.config.is_checkpoint(number) {
+            self.votes.clear();
+            self.tally.clear();
+        }
+        if number >= limit {
+            self.recents.remove(&(number - limit));
+        }
+        self.recents.insert(number, signer);
+
+        // a signer can only have a single vote per address, discard the previous one
+        let address = header.beneficiary;
+        if let Some(index) =
+            self.votes.iter().position(|vote| vote.signer == signer && vote.address == address)
+        {
+            let vote = self.votes.remove(index);
+            self.uncast(vote.address, vote.authorize);
+        }
+        if self.cast(address, authorize) {
+            self.votes.push(Vote { signer, address, authorize });
+        }
+
+        // apply the vote if it passed
+        if self.tally.get(&address).is_some_and(|tally| tally.votes > self.signers.len() / 2) {
+            if authorize {
+                self.signers.insert(address);
+            } else {
+                self.signers.remove(&address);
+
+                // the signer list shrunk, so the oldest recent signer may sign again
+                let limit = self.signer_limit();
+                if number >= limit {
+                    self.recents.remove(&(number - limit));
+                }
+
+                // discard all votes of the dropped signer
+                let (dropped, votes) =
+                    self.votes.drain(..).partition::<Vec<_>, _>(|vote| vote.signer == address);
+                self.votes = votes;
+                for vote in dropped {
+                    self.uncast(vote.address, vote.authorize);
+                }
+            }
+
+            // discard all votes on the address
+            self.votes.retain(|vote| vote.address != address);
+            self.tally.remove(&address);
+        }
+
+        self.number = number;
+        self.hash = header.hash();
+
+        Ok(())
+    }
+
+    /// Returns the number of consecutive blocks after which a signer may sign again.
+    fn signer_limit(&self) -> u64 {
+        self.signers.len() as u64 / 2 + 1
+    }
+
+    /// Adds a vote to the tally, returns `false` if the vote is pointless.
+    fn cast(&mut self, address: Address, authorize: bool) -> bool {
+        if self.signers.contains(&address) == authorize {
+            return false
+        }
+        self.tally.entry(address).or_insert(Tally { authorize, votes: 0 }).votes += 1;
+        true
+    }
+
+    /// Removes a vote from the tally.
+    fn uncast(&mut self, address: Address, authorize: bool) {
+        let Some(tally) = self.tally.get_mut(&address) else { return };
+        if tally.authorize != authorize {
+            return
+        }
+        if tally.votes > 1 {
+            tally.votes -= 1;
+        } else {
+            self.tally.remove(&address);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives_traits::crypto::secp256k1::sign_message;
+
+    const CONFIG: CliqueConfig = CliqueConfig { period: 15, epoch: 30_000 };
+
+    fn extra_data(signers: &[Address]) -> Bytes {
+        let mut extra = vec![0; CLIQUE_EXTRA_VANITY];
+        for signer in signers {
+            extra.extend_from_slice(signer.as_slice());
+        }
+        extra.extend_from_slice(&[0; CLIQUE_EXTRA_SEAL]);
+        extra.into()
+    }
+
+    fn header(number: u64, beneficiary: Address, nonce: B64, difficulty: U256) -> Header {
+        Header {
+            number,
+            beneficiary,
+            nonce,
+            difficulty,
+            extra_data: extra_data(&[]),
+            ..Default::default()
+        }
+    }
+
+    fn seal(mut header: Header, secret: B256) -> Header {
+        let signature = sign_message(secret, clique_seal_hash(&header).unwrap()).unwrap();
+        let mut extra = header.extra_data[..header.extra_data.len() - CLIQUE_EXTRA_SEAL].to_vec();
+        extra.extend_from_slice(&signature.r().to_be_bytes::<32>());
+        extra.extend_from_slice(&signature.s().to_be_bytes::<32>());
+        extra.push(signature.v() as u8);
+        header.extra_data = extra.into();
+        header
+    }
+
+    #[test]
+    fn recovers_sealed_signer() {
+        let header = header(1, Address::ZERO, CLIQUE_NONCE_DROP_VOTE, CLIQUE_DIFF_IN_TURN);
+        let secret = B256::with_last_byte(7);
+        let signature = sign_message(secret, clique_seal_hash(&header).unwrap()).unwrap();
+        let expected = recover_signer_unchecked(&signature, clique_seal_hash(&header).unwrap());
+
+        assert_eq!(recover_clique_signer(&seal(header, secret)).ok(), expected.ok());
+    }
+
+    #[test]
+    fn header_rules() {
+        let voter = Address::with_last_byte(1);
+        let valid = header(1, voter, CLIQUE_NONCE_AUTH_VOTE, CLIQUE_DIFF_NO_TURN);
+        assert_eq!(validate_clique_header(&valid, &CONFIG), Ok(()));
+
+        let invalid = Header { nonce: B64::with_last_byte(1), ..valid.clone() };
+        assert_eq!(
+            validate_clique_header(&invalid, &CONFIG),
+            Err(CliqueError::InvalidVote { nonce: B64::with_last_byte(1) })
+        );
+
+        let invalid = Header { extra_data: Bytes::from_static(&[0; 32]), ..valid };
+        assert_eq!(
+            validate_clique_header(&invalid, &CONFIG),
+            Err(CliqueError::ExtraDataTooShort { len: 32 })
+        );
+
+        let invalid = Header { extra_data: extra_data(&[voter]), ..valid };
+        assert_eq!(
+            validate_clique_header(&invalid, &CONFIG),
+            Err(CliqueError::ExtraSigners { number: 1 })
+        );
+
+        let invalid = Header { difficulty: U256::from(3), ..valid };
+        assert_eq!(
+            validate_clique_header(&invalid, &CONFIG),
+            Err(CliqueError::InvalidDifficulty { difficulty: U256::from(3) })
+        );
+
+        let checkpoint = header(30_000, voter, CLIQUE_NONCE_DROP_VOTE, CLIQUE_DIFF_IN_TURN);
+        assert_eq!(
+            validate_clique_header(&checkpoint, &CONFIG),
+            Err(CliqueError::InvalidCheckpointBeneficiary { number: 30_000 })
+        );
+
+        let checkpoint = Header { beneficiary: Address::ZERO, ..checkpoint };
+        let invalid = Header { nonce: CLIQUE_NONCE_AUTH_VOTE, ..checkpoint.clone() };
+        assert_eq!(
+            validate_clique_header(&invalid, &CONFIG),
+            Err(CliqueError::InvalidCheckpointVote { number: 30_000 })
+        );
+
+        let invalid = Header { extra_data: vec![0; 32 + 21 + 65].into(), ..checkpoint };
+        assert_eq!(
+            validate_clique_header(&invalid, &CONFIG),
+            Err(CliqueError::InvalidCheckpointSigners { number: 30_000, len: 21 })
+        );
+    }
+
+    #[test]
+    fn snapshot_applies_votes() {
+        let a = Address::with_last_byte(1);
+        let b = Address::with_last_byte(2);
+        let c = Address::with_last_byte(3);
+        let genesis = Header { extra_data: extra_data(&[a, b]), ..Default::default() };
+        let mut snapshot =
+            CliqueSnapshot::from_checkpoint(CONFIG, &SealedHeader::seal_slow(genesis)).unwrap();
+        assert!(snapshot.is_in_turn(1, &b));
+
+        // a votes to authorize c, which isn't a majority of two signers
+        let block = header(1, c, CLIQUE_NONCE_AUTH_VOTE, CLIQUE_DIFF_NO_TURN);
+        snapshot.apply_signed(&SealedHeader::seal_slow(block), a).unwrap();
+        assert!(!snapshot.signers().contains(&c));
+
+        // a signed the previous block
+        let block = header(2, c, CLIQUE_NONCE_AUTH_VOTE, CLIQUE_DIFF_NO_TURN);
+        assert_eq!(
+            snapshot.apply_signed(&SealedHeader::seal_slow(block.clone()), a),
+            Err(CliqueError::RecentlySigned { signer: a })
+        );
+        assert_eq!(
+            snapshot.apply_signed(&SealedHeader::seal_slow(block.clone()), c),
+            Err(CliqueError::UnauthorizedSigner { signer: c })
+        );
+        let in_turn = Header { difficulty: CLIQUE_DIFF_IN_TURN, ..block.clone() };
+        assert_eq!(
+            snapshot.apply_signed(&SealedHeader::seal_slow(in_turn), b),
+            Err(CliqueError::WrongDifficulty(GotExpected {
+                got: CLIQUE_DIFF_IN_TURN,
+                expected: CLIQUE_DIFF_NO_TURN
+            }))
+        );
+
+        // b votes to authorize c as well, which passes the vote
+        snapshot.apply_signed(&SealedHeader::seal_slow(block), b).unwrap();
+        assert_eq!(snapshot.signers().iter().copied().collect::<Vec<_>>(), [a, b, c]);
+        assert_eq!(snapshot.number(), 2);
+    }
+}
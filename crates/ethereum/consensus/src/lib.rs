@@ -12,24 +12,63 @@
 extern crate alloc;
 
 use alloc::{fmt::Debug, sync::Arc};
-use alloy_consensus::EMPTY_OMMER_ROOT_HASH;
-use alloy_eips::eip7840::BlobParams;
+use alloy_consensus::BlockHeader as _;
+use alloy_eips::eip7685::Requests;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
-use reth_consensus::{Consensus, ConsensusError, FullConsensus, HeaderValidator};
-use reth_consensus_common::validation::{
-    validate_4844_header_standalone, validate_against_parent_4844,
-    validate_against_parent_eip1559_base_fee, validate_against_parent_hash_number,
-    validate_against_parent_timestamp, validate_block_pre_execution, validate_body_against_header,
-    validate_header_base_fee, validate_header_extra_data, validate_header_gas,
+use reth_consensus::{
+    observe_validation,
+    validation::{
+        validate_against_parent_4844, validate_against_parent_eip1559_base_fee,
+        validate_against_parent_gas_limit, validate_against_parent_hash_number,
+        validate_against_parent_timestamp, validate_block_full_report, validate_block_ommers_hash,
+        validate_block_transaction_root, validate_body_ommers_hash, validate_body_transaction_root,
+        validate_body_withdrawals_root, validate_cancun_gas, validate_header_base_fee,
+        validate_header_blob_fields, validate_header_extra_data, validate_header_gas,
+        validate_header_merge_fields, validate_header_requests_hash,
+        validate_header_withdrawals_root, validate_shanghai_withdrawals,
+    },
+    Consensus, ConsensusError, ConsensusObserver, FullConsensus, HeaderValidator,
+    HeaderValidatorConfig, ProofAssistedConsensus, ValidationReport, ValidationStage,
 };
 use reth_execution_types::BlockExecutionResult;
 use reth_primitives_traits::{
-    constants::{GAS_LIMIT_BOUND_DIVISOR, MINIMUM_GAS_LIMIT},
-    Block, BlockHeader, NodePrimitives, RecoveredBlock, SealedBlock, SealedHeader,
+    proofs::IncrementalReceiptsRoot, Block, BlockHeader, GotExpected, NodePrimitives, Receipt,
+    RecoveredBlock, SealedBlock, SealedHeader,
 };
 
+#[cfg(feature = "c-kzg")]
+mod blob;
+#[cfg(feature = "c-kzg")]
+pub use blob::validate_blob_sidecars;
+
+mod clique;
+pub use clique::{
+    clique_checkpoint_signers, clique_seal_hash, recover_clique_signer, validate_clique_header,
+    CliqueConfig, CliqueError, CliqueHeaderValidator, CliqueSnapshot, CLIQUE_DEFAULT_EPOCH,
+    CLIQUE_DIFF_IN_TURN, CLIQUE_DIFF_NO_TURN, CLIQUE_EXTRA_SEAL, CLIQUE_EXTRA_VANITY,
+    CLIQUE_NONCE_AUTH_VOTE, CLIQUE_NONCE_DROP_VOTE,
+};
+
+#[cfg(feature = "pre-merge")]
+mod ommers;
+#[cfg(feature = "pre-merge")]
+pub use ommers::{MAX_OMMERS, MAX_OMMER_DEPTH};
+
+mod pre_merge;
+pub use pre_merge::{epoch_accumulator_root, EPOCH_ACCUMULATOR_SIZE};
+
+mod proof;
+pub use proof::{ExecutionOutcomeProof, ExecutionProofVerifier};
+
 mod validation;
-pub use validation::validate_block_post_execution;
+pub use validation::{
+    compare_requests, diff_receipts, validate_block_post_execution,
+    validate_block_post_execution_with_proof, validate_block_post_execution_with_requests,
+    validate_requests, ReceiptDivergence, ReceiptDivergenceReason,
+};
+use validation::{
+    validate_block_post_execution_with_proof_observed, validate_block_post_execution_with_scratch,
+};
 
 /// Ethereum beacon consensus
 ///
@@ -38,61 +77,181 @@ pub use validation::validate_block_post_execution;
 pub struct EthBeaconConsensus<ChainSpec> {
     /// Configuration
     chain_spec: Arc<ChainSpec>,
+    /// Optional observer that is notified about every validation result.
+    observer: Option<Arc<dyn ConsensusObserver>>,
+    /// Configuration for environment dependent header rules.
+    header_config: HeaderValidatorConfig,
+    /// Optional verifier of externally-provided execution outcome proofs.
+    proof_verifier: Option<Arc<dyn ExecutionProofVerifier>>,
+    /// Whether to look for the divergent receipt if the receipts root doesn't match.
+    receipt_diagnostics: bool,
 }
 
 impl<ChainSpec: EthChainSpec + EthereumHardforks> EthBeaconConsensus<ChainSpec> {
     /// Create a new instance of [`EthBeaconConsensus`]
     pub const fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec }
+        Self {
+            chain_spec,
+            observer: None,
+            header_config: HeaderValidatorConfig::new(),
+            proof_verifier: None,
+            receipt_diagnostics: false,
+        }
+    }
+
+    /// Sets the [`HeaderValidatorConfig`], e.g. to configure the allowed clock drift for header
+    /// timestamps.
+    pub const fn with_header_validator_config(mut self, config: HeaderValidatorConfig) -> Self {
+        self.header_config = config;
+        self
     }
 
-    /// Checks the gas limit for consistency between parent and self headers.
+    /// Sets the [`ConsensusObserver`] that is notified about every validation result.
+    pub fn with_observer(mut self, observer: Arc<dyn ConsensusObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Sets the [`ExecutionProofVerifier`] used to verify the proofs passed to
+    /// [`ProofAssistedConsensus::validate_block_post_execution_with_proof`].
+    pub fn with_proof_verifier(mut self, verifier: Arc<dyn ExecutionProofVerifier>) -> Self {
+        self.proof_verifier = Some(verifier);
+        self
+    }
+
+    /// Enables logging the first divergent receipt of a block whose receipts root doesn't match,
+    /// see [`diff_receipts`].
+    pub const fn with_receipt_diagnostics(mut self, enabled: bool) -> Self {
+        self.receipt_diagnostics = enabled;
+        self
+    }
+
+    /// Runs all pre-execution rules on the block and its parent and returns a [`ValidationReport`]
+    /// with every failed rule, instead of stopping at the first one.
     ///
-    /// The maximum allowable difference between self and parent gas limits is determined by the
-    /// parent's gas limit divided by the [`GAS_LIMIT_BOUND_DIVISOR`].
-    fn validate_against_parent_gas_limit<H: BlockHeader>(
+    /// See [`validate_block_full_report`].
+    pub fn validate_block_full_report<B>(
         &self,
-        header: &SealedHeader<H>,
-        parent: &SealedHeader<H>,
-    ) -> Result<(), ConsensusError> {
-        // Determine the parent gas limit, considering elasticity multiplier on the London fork.
-        let parent_gas_limit = if !self.chain_spec.is_london_active_at_block(parent.number()) &&
-            self.chain_spec.is_london_active_at_block(header.number())
+        block: &SealedBlock<B>,
+        parent: &SealedHeader<B::Header>,
+    ) -> ValidationReport
+    where
+        B: Block,
+        ChainSpec: EthChainSpec<Header = B::Header>,
+    {
+        let mut report = ValidationReport::new();
+        #[cfg(feature = "std")]
         {
-            parent.gas_limit() *
-                self.chain_spec
-                    .base_fee_params_at_timestamp(header.timestamp())
-                    .elasticity_multiplier as u64
-        } else {
-            parent.gas_limit()
-        };
-
-        // Check for an increase in gas limit beyond the allowed threshold.
-        if header.gas_limit() > parent_gas_limit {
-            if header.gas_limit() - parent_gas_limit >= parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR {
-                return Err(ConsensusError::GasLimitInvalidIncrease {
-                    parent_gas_limit,
-                    child_gas_limit: header.gas_limit(),
-                })
+            if !self.chain_spec.is_paris_active_at_block(block.number()) {
+                report.run("timestamp_in_future", || {
+                    let present_timestamp = std::time::SystemTime::now()
+                        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+                        .unwrap()
+                        .as_secs();
+                    let timestamp = block.timestamp();
+                    if self.header_config.is_timestamp_in_future(timestamp, present_timestamp) {
+                        return Err(ConsensusError::TimestampIsInFuture {
+                            timestamp,
+                            present_timestamp,
+                        })
+                    }
+                    Ok(())
+                });
             }
         }
-        // Check for a decrease in gas limit beyond the allowed threshold.
-        else if parent_gas_limit - header.gas_limit() >=
-            parent_gas_limit / GAS_LIMIT_BOUND_DIVISOR
-        {
-            return Err(ConsensusError::GasLimitInvalidDecrease {
-                parent_gas_limit,
-                child_gas_limit: header.gas_limit(),
-            })
+        report.append(validate_block_full_report(block, parent, &self.chain_spec));
+        report
+    }
+
+    /// Logs the first divergent receipt of the block, if receipt diagnostics are enabled and the
+    /// error is a receipts root mismatch.
+    fn diagnose_receipts<B: Block, R: Receipt>(
+        &self,
+        block: &RecoveredBlock<B>,
+        receipts: &[R],
+        error: &ConsensusError,
+    ) {
+        if !self.receipt_diagnostics || !matches!(error, ConsensusError::BodyReceiptRootDiff(_)) {
+            return
         }
-        // Check if the self gas limit is below the minimum required limit.
-        else if header.gas_limit() < MINIMUM_GAS_LIMIT {
-            return Err(ConsensusError::GasLimitInvalidMinimum {
-                child_gas_limit: header.gas_limit(),
-            })
+        match diff_receipts(block, receipts, None) {
+            Some(divergence) => tracing::warn!(
+                number = block.header().number(),
+                hash = ?block.hash(),
+                index = divergence.index,
+                reason = ?divergence.reason,
+                cumulative_gas_used = divergence.cumulative_gas_used,
+                logs = divergence.logs,
+                receipt = %divergence.encoded,
+                "Found divergent receipt"
+            ),
+            None => tracing::warn!(
+                number = block.header().number(),
+                hash = ?block.hash(),
+                "Receipts are consistent with the transactions, the divergence is in their content"
+            ),
         }
+    }
 
-        Ok(())
+    /// Runs the validation `rule` of the `stage` and reports its outcome to the observer, if any.
+    fn observe(
+        &self,
+        stage: ValidationStage,
+        rule: &'static str,
+        f: impl FnOnce() -> Result<(), ConsensusError>,
+    ) -> Result<(), ConsensusError> {
+        observe_validation(self.observer.as_deref(), stage, rule, f)
+    }
+
+    /// Validates the header on its own, see [`HeaderValidator::validate_header`].
+    ///
+    /// This applies the rules of
+    /// [`validate_header_standalone`](reth_consensus::validation::validate_header_standalone) one
+    /// by one, so that every rule is reported to the observer.
+    fn validate_standalone_header<H: BlockHeader>(&self, header: &H) -> Result<(), ConsensusError> {
+        let stage = ValidationStage::Header;
+        let chain_spec = &*self.chain_spec;
+        let with_fork_info = |err: ConsensusError| err.with_fork_info(header, chain_spec);
+
+        #[cfg(feature = "std")]
+        if !chain_spec.is_paris_active_at_block(header.number()) {
+            self.observe(stage, "timestamp_in_future", || {
+                let present_timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::SystemTime::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+
+                if self.header_config.is_timestamp_in_future(header.timestamp(), present_timestamp)
+                {
+                    return Err(ConsensusError::TimestampIsInFuture {
+                        timestamp: header.timestamp(),
+                        present_timestamp,
+                    });
+                }
+                Ok(())
+            })?;
+        }
+
+        self.observe(stage, "merge_fields", || {
+            validate_header_merge_fields(header, chain_spec).map_err(with_fork_info)
+        })?;
+        self.observe(stage, "extra_data", || {
+            validate_header_extra_data(header, chain_spec.max_extra_data_size())
+                .map_err(with_fork_info)
+        })?;
+        self.observe(stage, "gas", || validate_header_gas(header).map_err(with_fork_info))?;
+        self.observe(stage, "base_fee", || {
+            validate_header_base_fee(header, chain_spec).map_err(with_fork_info)
+        })?;
+        self.observe(stage, "withdrawals_root", || {
+            validate_header_withdrawals_root(header, chain_spec).map_err(with_fork_info)
+        })?;
+        self.observe(stage, "blob_fields", || {
+            validate_header_blob_fields(header, chain_spec).map_err(with_fork_info)
+        })?;
+        self.observe(stage, "requests_hash", || {
+            validate_header_requests_hash(header, chain_spec).map_err(with_fork_info)
+        })
     }
 }
 
@@ -106,7 +265,97 @@ where
         block: &RecoveredBlock<N::Block>,
         result: &BlockExecutionResult<N::Receipt>,
     ) -> Result<(), ConsensusError> {
-        validate_block_post_execution(block, &self.chain_spec, &result.receipts, &result.requests)
+        validate_block_post_execution_with_scratch(
+            block,
+            &self.chain_spec,
+            &result.receipts,
+            &result.requests,
+            None,
+            &mut IncrementalReceiptsRoot::default(),
+            self.observer.as_deref(),
+        )
+        .inspect_err(|err| self.diagnose_receipts(block, &result.receipts, err))
+    }
+
+    fn validate_block_post_execution_with_requests(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+        expected_requests: &Requests,
+    ) -> Result<(), ConsensusError> {
+        validate_block_post_execution_with_scratch(
+            block,
+            &self.chain_spec,
+            &result.receipts,
+            &result.requests,
+            Some(expected_requests),
+            &mut IncrementalReceiptsRoot::default(),
+            self.observer.as_deref(),
+        )
+        .inspect_err(|err| self.diagnose_receipts(block, &result.receipts, err))
+    }
+
+    fn validate_chain_post_execution(
+        &self,
+        blocks: &[RecoveredBlock<N::Block>],
+        results: &[BlockExecutionResult<N::Receipt>],
+    ) -> Result<(), ConsensusError> {
+        if blocks.len() != results.len() {
+            return Err(ConsensusError::ExecutionResultsLengthMismatch(GotExpected {
+                got: results.len(),
+                expected: blocks.len(),
+            }))
+        }
+
+        // reuse the receipts root buffers across all blocks
+        let mut scratch = IncrementalReceiptsRoot::default();
+        for (block, result) in blocks.iter().zip(results) {
+            validate_block_post_execution_with_scratch(
+                block,
+                &self.chain_spec,
+                &result.receipts,
+                &result.requests,
+                None,
+                &mut scratch,
+                self.observer.as_deref(),
+            )
+            .inspect_err(|err| self.diagnose_receipts(block, &result.receipts, err))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<ChainSpec, N> ProofAssistedConsensus<N> for EthBeaconConsensus<ChainSpec>
+where
+    ChainSpec: Send + Sync + EthChainSpec<Header = N::BlockHeader> + EthereumHardforks + Debug,
+    N: NodePrimitives,
+{
+    type Proof = ExecutionOutcomeProof;
+
+    fn validate_block_post_execution_with_proof(
+        &self,
+        block: &RecoveredBlock<N::Block>,
+        result: &BlockExecutionResult<N::Receipt>,
+        proof: &Self::Proof,
+    ) -> Result<(), ConsensusError> {
+        self.observe(ValidationStage::PostExecution, "proof", || {
+            let Some(verifier) = &self.proof_verifier else {
+                return Err(ConsensusError::InvalidValidationProof(
+                    "no proof verifier configured".into(),
+                ))
+            };
+            verifier.verify(proof).map_err(ConsensusError::InvalidValidationProof)
+        })?;
+
+        validate_block_post_execution_with_proof_observed(
+            block,
+            &self.chain_spec,
+            &result.receipts,
+            &result.requests,
+            proof,
+            self.observer.as_deref(),
+        )
     }
 }
 
@@ -122,11 +371,28 @@ where
         body: &B::Body,
         header: &SealedHeader<B::Header>,
     ) -> Result<(), Self::Error> {
-        validate_body_against_header(body, header.header())
+        let stage = ValidationStage::BodyAgainstHeader;
+        let header = header.header();
+        self.observe(stage, "ommers_hash", || validate_body_ommers_hash(body, header))?;
+        self.observe(stage, "transaction_root", || validate_body_transaction_root(body, header))?;
+        self.observe(stage, "withdrawals_root", || validate_body_withdrawals_root(body, header))
     }
 
     fn validate_block_pre_execution(&self, block: &SealedBlock<B>) -> Result<(), Self::Error> {
-        validate_block_pre_execution(block, &self.chain_spec)
+        let stage = ValidationStage::PreExecution;
+        self.observe(stage, "ommers_hash", || validate_block_ommers_hash(block))?;
+        self.observe(stage, "transaction_root", || validate_block_transaction_root(block))?;
+
+        // EIP-4895: Beacon chain push withdrawals as operations
+        if self.chain_spec.is_shanghai_active_at_timestamp(block.timestamp()) {
+            self.observe(stage, "withdrawals", || validate_shanghai_withdrawals(block))?;
+        }
+
+        if self.chain_spec.is_cancun_active_at_timestamp(block.timestamp()) {
+            self.observe(stage, "blob_gas_used", || validate_cancun_gas(block))?;
+        }
+
+        Ok(())
     }
 }
 
@@ -136,79 +402,7 @@ where
     ChainSpec: EthChainSpec<Header = H> + EthereumHardforks + Debug + Send + Sync,
 {
     fn validate_header(&self, header: &SealedHeader<H>) -> Result<(), ConsensusError> {
-        let header = header.header();
-        let is_post_merge = self.chain_spec.is_paris_active_at_block(header.number());
-
-        if is_post_merge {
-            if !header.difficulty().is_zero() {
-                return Err(ConsensusError::TheMergeDifficultyIsNotZero);
-            }
-
-            if !header.nonce().is_some_and(|nonce| nonce.is_zero()) {
-                return Err(ConsensusError::TheMergeNonceIsNotZero);
-            }
-
-            if header.ommers_hash() != EMPTY_OMMER_ROOT_HASH {
-                return Err(ConsensusError::TheMergeOmmerRootIsNotEmpty);
-            }
-        } else {
-            #[cfg(feature = "std")]
-            {
-                let present_timestamp = std::time::SystemTime::now()
-                    .duration_since(std::time::SystemTime::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs();
-
-                if header.timestamp() >
-                    present_timestamp + alloy_eips::merge::ALLOWED_FUTURE_BLOCK_TIME_SECONDS
-                {
-                    return Err(ConsensusError::TimestampIsInFuture {
-                        timestamp: header.timestamp(),
-                        present_timestamp,
-                    });
-                }
-            }
-        }
-        validate_header_extra_data(header)?;
-        validate_header_gas(header)?;
-        validate_header_base_fee(header, &self.chain_spec)?;
-
-        // EIP-4895: Beacon chain push withdrawals as operations
-        if self.chain_spec.is_shanghai_active_at_timestamp(header.timestamp()) &&
-            header.withdrawals_root().is_none()
-        {
-            return Err(ConsensusError::WithdrawalsRootMissing)
-        } else if !self.chain_spec.is_shanghai_active_at_timestamp(header.timestamp()) &&
-            header.withdrawals_root().is_some()
-        {
-            return Err(ConsensusError::WithdrawalsRootUnexpected)
-        }
-
-        // Ensures that EIP-4844 fields are valid once cancun is active.
-        if self.chain_spec.is_cancun_active_at_timestamp(header.timestamp()) {
-            validate_4844_header_standalone(
-                header,
-                self.chain_spec
-                    .blob_params_at_timestamp(header.timestamp())
-                    .unwrap_or_else(BlobParams::cancun),
-            )?;
-        } else if header.blob_gas_used().is_some() {
-            return Err(ConsensusError::BlobGasUsedUnexpected)
-        } else if header.excess_blob_gas().is_some() {
-            return Err(ConsensusError::ExcessBlobGasUnexpected)
-        } else if header.parent_beacon_block_root().is_some() {
-            return Err(ConsensusError::ParentBeaconBlockRootUnexpected)
-        }
-
-        if self.chain_spec.is_prague_active_at_timestamp(header.timestamp()) {
-            if header.requests_hash().is_none() {
-                return Err(ConsensusError::RequestsHashMissing)
-            }
-        } else if header.requests_hash().is_some() {
-            return Err(ConsensusError::RequestsHashUnexpected)
-        }
-
-        Ok(())
+        self.validate_standalone_header(header.header())
     }
 
     fn validate_header_against_parent(
@@ -216,23 +410,26 @@ where
         header: &SealedHeader<H>,
         parent: &SealedHeader<H>,
     ) -> Result<(), ConsensusError> {
-        validate_against_parent_hash_number(header.header(), parent)?;
-
-        validate_against_parent_timestamp(header.header(), parent.header())?;
-
-        // TODO Check difficulty increment between parent and self
-        // Ace age did increment it by some formula that we need to follow.
-        self.validate_against_parent_gas_limit(header, parent)?;
-
-        validate_against_parent_eip1559_base_fee(
-            header.header(),
-            parent.header(),
-            &self.chain_spec,
-        )?;
-
-        // ensure that the blob gas fields for this block
-        if let Some(blob_params) = self.chain_spec.blob_params_at_timestamp(header.timestamp()) {
-            validate_against_parent_4844(header.header(), parent.header(), blob_params)?;
+        // the rules of `validate_header_against_parent`, one by one
+        let stage = ValidationStage::HeaderAgainstParent;
+        let chain_spec = &*self.chain_spec;
+        let header = header.header();
+        self.observe(stage, "parent_hash_number", || {
+            validate_against_parent_hash_number(header, parent)
+        })?;
+        self.observe(stage, "parent_timestamp", || {
+            validate_against_parent_timestamp(header, parent.header())
+        })?;
+        self.observe(stage, "parent_gas_limit", || {
+            validate_against_parent_gas_limit(header, parent.header(), chain_spec)
+        })?;
+        self.observe(stage, "parent_base_fee", || {
+            validate_against_parent_eip1559_base_fee(header, parent.header(), chain_spec)
+        })?;
+        if let Some(blob_params) = chain_spec.blob_params_at_timestamp(header.timestamp()) {
+            self.observe(stage, "parent_blob_gas", || {
+                validate_against_parent_4844(header, parent.header(), blob_params, chain_spec)
+            })?;
         }
 
         Ok(())
@@ -244,7 +441,11 @@ mod tests {
     use super::*;
     use alloy_primitives::B256;
     use reth_chainspec::{ChainSpec, ChainSpecBuilder};
-    use reth_primitives_traits::proofs;
+    use reth_consensus::{test_utils::BlockMutator, validation::validate_against_parent_gas_limit};
+    use reth_primitives_traits::{
+        constants::{GAS_LIMIT_BOUND_DIVISOR, MINIMUM_GAS_LIMIT},
+        proofs,
+    };
 
     fn header_with_gas_limit(gas_limit: u64) -> SealedHeader {
         let header = reth_primitives_traits::Header { gas_limit, ..Default::default() };
@@ -257,8 +458,11 @@ mod tests {
         let child = header_with_gas_limit((parent.gas_limit + 5) as u64);
 
         assert_eq!(
-            EthBeaconConsensus::new(Arc::new(ChainSpec::default()))
-                .validate_against_parent_gas_limit(&child, &parent),
+            validate_against_parent_gas_limit(
+                child.header(),
+                parent.header(),
+                &ChainSpec::default()
+            ),
             Ok(())
         );
     }
@@ -269,8 +473,11 @@ mod tests {
         let child = header_with_gas_limit(MINIMUM_GAS_LIMIT - 1);
 
         assert_eq!(
-            EthBeaconConsensus::new(Arc::new(ChainSpec::default()))
-                .validate_against_parent_gas_limit(&child, &parent),
+            validate_against_parent_gas_limit(
+                child.header(),
+                parent.header(),
+                &ChainSpec::default()
+            ),
             Err(ConsensusError::GasLimitInvalidMinimum { child_gas_limit: child.gas_limit as u64 })
         );
     }
@@ -283,8 +490,11 @@ mod tests {
         );
 
         assert_eq!(
-            EthBeaconConsensus::new(Arc::new(ChainSpec::default()))
-                .validate_against_parent_gas_limit(&child, &parent),
+            validate_against_parent_gas_limit(
+                child.header(),
+                parent.header(),
+                &ChainSpec::default()
+            ),
             Err(ConsensusError::GasLimitInvalidIncrease {
                 parent_gas_limit: parent.gas_limit,
                 child_gas_limit: child.gas_limit,
@@ -298,8 +508,11 @@ mod tests {
         let child = header_with_gas_limit(parent.gas_limit - 5);
 
         assert_eq!(
-            EthBeaconConsensus::new(Arc::new(ChainSpec::default()))
-                .validate_against_parent_gas_limit(&child, &parent),
+            validate_against_parent_gas_limit(
+                child.header(),
+                parent.header(),
+                &ChainSpec::default()
+            ),
             Ok(())
         );
     }
@@ -312,8 +525,11 @@ mod tests {
         );
 
         assert_eq!(
-            EthBeaconConsensus::new(Arc::new(ChainSpec::default()))
-                .validate_against_parent_gas_limit(&child, &parent),
+            validate_against_parent_gas_limit(
+                child.header(),
+                parent.header(),
+                &ChainSpec::default()
+            ),
             Err(ConsensusError::GasLimitInvalidDecrease {
                 parent_gas_limit: parent.gas_limit,
                 child_gas_limit: child.gas_limit,
@@ -338,4 +554,247 @@ mod tests {
             Ok(())
         );
     }
+
+    #[test]
+    fn fork_field_errors_include_fork_info() {
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().shanghai_activated().build());
+
+        let header = reth_primitives_traits::Header {
+            base_fee_per_gas: Some(1337),
+            withdrawals_root: Some(proofs::calculate_withdrawals_root(&[])),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            ..Default::default()
+        };
+
+        let err = EthBeaconConsensus::new(chain_spec)
+            .validate_header(&SealedHeader::seal_slow(header))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unexpected blob gas used (block looks like Cancun but chain spec says Shanghai)"
+        );
+    }
+
+    #[test]
+    fn future_timestamp_drift_is_configurable() {
+        let header = SealedHeader::seal_slow(reth_primitives_traits::Header {
+            timestamp: u64::MAX / 2,
+            ..Default::default()
+        });
+        let consensus = EthBeaconConsensus::new(Arc::new(ChainSpec::default()));
+
+        assert!(matches!(
+            consensus.validate_header(&header),
+            Err(ConsensusError::TimestampIsInFuture { .. })
+        ));
+
+        let consensus = consensus.with_header_validator_config(
+            HeaderValidatorConfig::default().without_future_timestamp_check(),
+        );
+        assert_eq!(consensus.validate_header(&header), Ok(()));
+    }
+
+    #[test]
+    fn observer_receives_validation_results() {
+        #[derive(Debug, Default)]
+        struct Recorder(std::sync::Mutex<Vec<(ValidationStage, &'static str, bool)>>);
+
+        impl ConsensusObserver for Recorder {
+            fn on_validation(
+                &self,
+                stage: ValidationStage,
+                rule: &'static str,
+                _elapsed: core::time::Duration,
+                result: Result<(), &ConsensusError>,
+            ) {
+                self.0.lock().unwrap().push((stage, rule, result.is_ok()));
+            }
+        }
+
+        let recorder = Arc::new(Recorder::default());
+        let consensus =
+            EthBeaconConsensus::new(Arc::new(ChainSpec::default())).with_observer(recorder.clone());
+
+        let parent = header_with_gas_limit(MINIMUM_GAS_LIMIT);
+        let child = SealedHeader::seal_slow(reth_primitives_traits::Header {
+            parent_hash: parent.hash(),
+            number: 1,
+            timestamp: 1,
+            gas_limit: MINIMUM_GAS_LIMIT - 1,
+            ..Default::default()
+        });
+        assert!(consensus.validate_header_against_parent(&child, &parent).is_err());
+
+        // every rule up to the first failed one is reported
+        let stage = ValidationStage::HeaderAgainstParent;
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![
+                (stage, "parent_hash_number", true),
+                (stage, "parent_timestamp", true),
+                (stage, "parent_gas_limit", false)
+            ]
+        );
+    }
+
+    #[test]
+    fn chain_post_execution_length_mismatch() {
+        let consensus = EthBeaconConsensus::new(Arc::new(ChainSpec::default()));
+        let blocks = vec![RecoveredBlock::<reth_ethereum_primitives::Block>::new_unhashed(
+            Default::default(),
+            vec![],
+        )];
+
+        assert_eq!(
+            FullConsensus::<reth_ethereum_primitives::EthPrimitives>::validate_chain_post_execution(
+                &consensus,
+                &blocks,
+                &[]
+            ),
+            Err(ConsensusError::ExecutionResultsLengthMismatch(GotExpected {
+                got: 0,
+                expected: 1
+            }))
+        );
+    }
+
+    #[test]
+    fn post_execution_with_proof() {
+        #[derive(Debug)]
+        struct AcceptNonEmpty;
+
+        impl ExecutionProofVerifier for AcceptNonEmpty {
+            fn verify(&self, proof: &ExecutionOutcomeProof) -> Result<(), String> {
+                if proof.proof.is_empty() {
+                    return Err("empty proof".into())
+                }
+                Ok(())
+            }
+        }
+
+        let block = RecoveredBlock::<reth_ethereum_primitives::Block>::new_unhashed(
+            Default::default(),
+            vec![],
+        );
+        let result = BlockExecutionResult::<reth_ethereum_primitives::Receipt> {
+            receipts: vec![],
+            requests: Default::default(),
+            gas_used: 0,
+        };
+        let mut proof = ExecutionOutcomeProof {
+            block_hash: block.hash(),
+            receipts_root: block.header().receipts_root,
+            logs_bloom: block.header().logs_bloom,
+            proof: alloy_primitives::Bytes::from_static(&[1]),
+        };
+        let validate = |consensus: &EthBeaconConsensus<ChainSpec>,
+                        proof: &ExecutionOutcomeProof| {
+            ProofAssistedConsensus::<reth_ethereum_primitives::EthPrimitives>::validate_block_post_execution_with_proof(
+                consensus, &block, &result, proof,
+            )
+        };
+
+        let chain_spec = Arc::new(ChainSpecBuilder::mainnet().byzantium_activated().build());
+        let consensus = EthBeaconConsensus::new(chain_spec);
+        assert!(matches!(
+            validate(&consensus, &proof),
+            Err(ConsensusError::InvalidValidationProof(_))
+        ));
+
+        let consensus = consensus.with_proof_verifier(Arc::new(AcceptNonEmpty));
+        assert_eq!(validate(&consensus, &proof), Ok(()));
+
+        proof.receipts_root = B256::with_last_byte(1);
+        assert!(matches!(
+            validate(&consensus, &proof),
+            Err(ConsensusError::BodyReceiptRootDiff(_))
+        ));
+
+        proof.proof = Default::default();
+        assert_eq!(
+            validate(&consensus, &proof),
+            Err(ConsensusError::InvalidValidationProof("empty proof".into()))
+        );
+    }
+
+    #[test]
+    fn block_mutator_conformance() {
+        use reth_ethereum_primitives::{Block, BlockBody, EthPrimitives, Receipt};
+
+        // a chain specific extra data limit
+        let mut chain_spec = ChainSpecBuilder::mainnet().cancun_activated().build();
+        chain_spec.max_extra_data_size = 64;
+        let chain_spec = Arc::new(chain_spec);
+        let consensus = EthBeaconConsensus::new(chain_spec.clone());
+
+        let parent = SealedHeader::seal_slow(reth_primitives_traits::Header {
+            number: 1,
+            timestamp: 12,
+            gas_limit: 30_000_000,
+            gas_used: 15_000_000,
+            base_fee_per_gas: Some(alloy_eips::eip1559::INITIAL_BASE_FEE),
+            withdrawals_root: Some(proofs::calculate_withdrawals_root(&[])),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        });
+        let header = reth_primitives_traits::Header {
+            parent_hash: parent.hash(),
+            number: 2,
+            timestamp: 24,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: chain_spec.next_block_base_fee(parent.header(), 24),
+            withdrawals_root: Some(proofs::calculate_withdrawals_root(&[])),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(0),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        };
+        let body = BlockBody { withdrawals: Some(Default::default()), ..Default::default() };
+        let block = SealedBlock::<Block>::seal_parts(header, body);
+        let result = BlockExecutionResult::<Receipt> {
+            receipts: vec![],
+            requests: Default::default(),
+            gas_used: 0,
+        };
+
+        let validate = |stage: ValidationStage, block: &SealedBlock<Block>| match stage {
+            ValidationStage::Header => consensus.validate_header(block.sealed_header()),
+            ValidationStage::HeaderAgainstParent => {
+                consensus.validate_header_against_parent(block.sealed_header(), &parent)
+            }
+            ValidationStage::BodyAgainstHeader => Consensus::<Block>::validate_body_against_header(
+                &consensus,
+                block.body(),
+                block.sealed_header(),
+            ),
+            ValidationStage::PreExecution => consensus.validate_block_pre_execution(block),
+            ValidationStage::PostExecution => {
+                FullConsensus::<EthPrimitives>::validate_block_post_execution(
+                    &consensus,
+                    &RecoveredBlock::new_sealed(block.clone(), vec![]),
+                    &result,
+                )
+            }
+        };
+
+        for stage in [
+            ValidationStage::Header,
+            ValidationStage::HeaderAgainstParent,
+            ValidationStage::BodyAgainstHeader,
+            ValidationStage::PreExecution,
+            ValidationStage::PostExecution,
+        ] {
+            assert_eq!(validate(stage, &block), Ok(()), "{}", stage.as_str());
+        }
+
+        let cases = BlockMutator::new(&*chain_spec, parent.clone(), block).cases();
+        assert!(cases.iter().any(|case| case.name == "wrong_gas_used"));
+        for case in cases {
+            let err = validate(case.stage, &case.block).expect_err(case.name);
+            assert_eq!(err.without_fork_info(), &case.expected, "{}", case.name);
+        }
+    }
 }
@@ -0,0 +1,167 @@
+//! Validation of the ommers of pre-merge blocks.
+
+use crate::EthBeaconConsensus;
+use alloc::boxed::Box;
+use alloy_primitives::{map::HashSet, B256};
+use core::{fmt::Debug, iter};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_consensus::{
+    validation::{validate_header_against_parent, validate_header_standalone},
+    ConsensusError, OmmerAncestor, OmmerValidator,
+};
+use reth_primitives_traits::{BlockHeader, SealedHeader};
+
+/// The maximum number of ommers of a block.
+pub const MAX_OMMERS: usize = 2;
+
+/// The number of ancestors of a block, starting with its parent, that are considered when
+/// validating the ommers of the block.
+///
+/// Ommers must branch off one of these ancestors other than the parent, i.e. an ommer is at most
+/// six generations away from the block, and must not have been included by any of them.
+pub const MAX_OMMER_DEPTH: usize = 7;
+
+impl<H, ChainSpec> OmmerValidator<H> for EthBeaconConsensus<ChainSpec>
+where
+    H: BlockHeader,
+    ChainSpec: EthChainSpec<Header = H> + EthereumHardforks + Debug + Send + Sync,
+{
+    fn validate_ommers(
+        &self,
+        header: &SealedHeader<H>,
+        ommers: &[H],
+        ancestors: &[OmmerAncestor<H>],
+    ) -> Result<(), ConsensusError> {
+        if ommers.len() > MAX_OMMERS {
+            return Err(ConsensusError::TooManyOmmers { count: ommers.len(), max: MAX_OMMERS })
+        }
+        let ancestors = &ancestors[..ancestors.len().min(MAX_OMMER_DEPTH)];
+
+        // the block, its ancestors and all ommers they included
+        let mut included = iter::once(header.hash())
+            .chain(ancestors.iter().flat_map(|ancestor| {
+                iter::once(ancestor.header.hash()).chain(ancestor.ommers.iter().copied())
+            }))
+            .collect::<HashSet<B256>>();
+
+        for ommer in ommers {
+            let ommer = SealedHeader::seal_slow(ommer.clone());
+            if !included.insert(ommer.hash()) {
+                return Err(ConsensusError::DuplicateOmmer(ommer.hash()))
+            }
+
+            // siblings of the block itself are not valid ommers
+            let Some(parent) = ancestors
+                .iter()
+                .skip(1)
+                .find(|ancestor| ancestor.header.hash() == ommer.parent_hash())
+            else {
+                return Err(ConsensusError::DanglingOmmer(ommer.hash()))
+            };
+
+            validate_header_standalone(ommer.header(), &self.chain_spec)
+                .and_then(|_| {
+                    validate_header_against_parent(ommer.header(), &parent.header, &self.chain_spec)
+                })
+                .map_err(|error| ConsensusError::InvalidOmmerHeader {
+                    hash: ommer.hash(),
+                    error: Box::new(error),
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+    use alloy_primitives::U256;
+    use reth_chainspec::MAINNET;
+    use reth_primitives_traits::Header;
+
+    fn child(parent: &SealedHeader, extra: u8) -> SealedHeader {
+        SealedHeader::seal_slow(Header {
+            number: parent.number + 1,
+            parent_hash: parent.hash(),
+            timestamp: parent.timestamp + 13,
+            difficulty: U256::from(100),
+            gas_limit: 5000,
+            extra_data: vec![extra].into(),
+            ..Default::default()
+        })
+    }
+
+    /// Returns a chain of ten headers and their ancestors in descending order.
+    fn chain() -> Vec<OmmerAncestor> {
+        let mut chain = vec![SealedHeader::seal_slow(Header {
+            timestamp: 1,
+            gas_limit: 5000,
+            ..Default::default()
+        })];
+        for _ in 0..10 {
+            chain.push(child(chain.last().unwrap(), 0));
+        }
+        chain.into_iter().rev().map(|header| OmmerAncestor { header, ommers: Vec::new() }).collect()
+    }
+
+    #[test]
+    fn validates_ommers() {
+        let consensus = EthBeaconConsensus::new(MAINNET.clone());
+        let mut ancestors = chain();
+        let header = child(&ancestors[0].header, 0);
+
+        // siblings of the parent and the ancestors up to six generations away are valid
+        let ommer = child(&ancestors[1].header, 1);
+        let oldest = child(&ancestors[MAX_OMMER_DEPTH - 1].header, 1);
+        let ommers = [ommer.header().clone(), oldest.header().clone()];
+        assert_eq!(consensus.validate_ommers(&header, &ommers, &ancestors), Ok(()));
+
+        let too_old = child(&ancestors[MAX_OMMER_DEPTH].header, 1);
+        assert_eq!(
+            consensus.validate_ommers(&header, &[too_old.header().clone()], &ancestors),
+            Err(ConsensusError::DanglingOmmer(too_old.hash()))
+        );
+        let sibling = child(&ancestors[0].header, 1);
+        assert_eq!(
+            consensus.validate_ommers(&header, &[sibling.header().clone()], &ancestors),
+            Err(ConsensusError::DanglingOmmer(sibling.hash()))
+        );
+
+        assert_eq!(
+            consensus.validate_ommers(&header, &vec![ommer.header().clone(); 2], &ancestors),
+            Err(ConsensusError::DuplicateOmmer(ommer.hash()))
+        );
+        assert!(matches!(
+            consensus.validate_ommers(&header, &vec![ommer.header().clone(); 3], &ancestors),
+            Err(ConsensusError::TooManyOmmers { count: 3, max: MAX_OMMERS })
+        ));
+
+        // an ommer can only be included once
+        ancestors[0].ommers.push(ommer.hash());
+        assert_eq!(
+            consensus.validate_ommers(&header, &[ommer.header().clone()], &ancestors),
+            Err(ConsensusError::DuplicateOmmer(ommer.hash()))
+        );
+        assert_eq!(
+            consensus.validate_ommers(&header, &[ancestors[0].header.header().clone()], &ancestors),
+            Err(ConsensusError::DuplicateOmmer(ancestors[0].header.hash()))
+        );
+    }
+
+    #[test]
+    fn validates_ommer_headers() {
+        let consensus = EthBeaconConsensus::new(MAINNET.clone());
+        let ancestors = chain();
+        let header = child(&ancestors[0].header, 0);
+
+        let mut ommer = child(&ancestors[1].header, 1).unseal();
+        ommer.number += 1;
+        let ommer = SealedHeader::seal_slow(ommer);
+        assert!(matches!(
+            consensus.validate_ommers(&header, &[ommer.header().clone()], &ancestors),
+            Err(ConsensusError::InvalidOmmerHeader { hash, .. }) if hash == ommer.hash()
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+use crate::EthBeaconConsensus;
+use alloc::vec::Vec;
+use alloy_primitives::{B256, U256};
+use core::fmt::Debug;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_consensus::{
+    validation::{validate_header_against_parent, validate_header_standalone},
+    ConsensusError, PreMergeHeaderValidator,
+};
+use reth_primitives_traits::{BlockHeader, GotExpected, SealedHeader};
+use sha2::{Digest, Sha256};
+
+/// The maximum number of headers of an epoch accumulator.
+pub const EPOCH_ACCUMULATOR_SIZE: usize = 8192;
+
+/// Computes the root of the epoch accumulator of the given header hashes and total difficulties.
+///
+/// This is the SSZ hash tree root of a `List[HeaderRecord, 8192]`, where a `HeaderRecord` is the
+/// container `{ block_hash: Bytes32, total_difficulty: uint256 }`, as stored in era1 files.
+pub fn epoch_accumulator_root(records: impl IntoIterator<Item = (B256, U256)>) -> B256 {
+    let mut layer = records
+        .into_iter()
+        .map(|(block_hash, total_difficulty)| {
+            sha256_pair(block_hash, B256::from(total_difficulty.to_le_bytes::<32>()))
+        })
+        .collect::<Vec<_>>();
+    let len = layer.len();
+
+    // merkleize the records, padding every layer with the root of an empty subtree
+    let mut zero = B256::ZERO;
+    for _ in 0..EPOCH_ACCUMULATOR_SIZE.trailing_zeros() {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+        layer = layer.chunks_exact(2).map(|pair| sha256_pair(pair[0], pair[1])).collect();
+        zero = sha256_pair(zero, zero);
+    }
+    let root = layer.first().copied().unwrap_or(zero);
+
+    // mix in the length of the list
+    sha256_pair(root, B256::from(U256::from(len).to_le_bytes::<32>()))
+}
+
+/// Returns the SHA-256 hash of the concatenation of two chunks.
+fn sha256_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from(<[u8; 32]>::from(hasher.finalize()))
+}
+
+impl<H, ChainSpec> PreMergeHeaderValidator<H> for EthBeaconConsensus<ChainSpec>
+where
+    H: BlockHeader,
+    ChainSpec: EthChainSpec<Header = H> + EthereumHardforks + Debug + Send + Sync,
+{
+    fn validate_pre_merge_headers(
+        &self,
+        headers: &[(SealedHeader<H>, U256)],
+        accumulator_root: B256,
+    ) -> Result<(), ConsensusError> {
+        for (header, _) in headers {
+            validate_header_standalone(header.header(), &self.chain_spec)?;
+        }
+
+        for pair in headers.windows(2) {
+            let [(parent, parent_total_difficulty), (header, total_difficulty)] = pair else {
+                unreachable!("windows of two")
+            };
+            validate_header_against_parent(header.header(), parent, &self.chain_spec)?;
+
+            let expected = *parent_total_difficulty + header.difficulty();
+            if *total_difficulty != expected {
+                return Err(ConsensusError::TotalDifficultyMismatch {
+                    block_number: header.number(),
+                    total_difficulty: GotExpected { got: *total_difficulty, expected }.into(),
+                })
+            }
+        }
+
+        let root = epoch_accumulator_root(
+            headers.iter().map(|(header, total_difficulty)| (header.hash(), *total_difficulty)),
+        );
+        if root != accumulator_root {
+            return Err(ConsensusError::EpochAccumulatorMismatch(
+                GotExpected { got: root, expected: accumulator_root }.into(),
+            ))
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_primitives_traits::Header;
+
+    fn pre_merge_headers() -> Vec<(SealedHeader, U256)> {
+        let mut headers: Vec<(SealedHeader, U256)> = Vec::new();
+        for number in 1..=3 {
+            let parent = headers.last().map(|(parent, td)| (parent.hash(), parent.timestamp, *td));
+            let header = Header {
+                number,
+                parent_hash: parent.map(|(hash, ..)| hash).unwrap_or_default(),
+                timestamp: parent.map(|(_, timestamp, _)| timestamp + 13).unwrap_or(1),
+                difficulty: U256::from(100 + number),
+                gas_limit: 5000,
+                ..Default::default()
+            };
+            let td = parent.map(|(.., td)| td).unwrap_or_default() + header.difficulty;
+            headers.push((SealedHeader::seal_slow(header), td));
+        }
+        headers
+    }
+
+    #[test]
+    fn empty_epoch_accumulator_root() {
+        // root of an empty list is the root of the empty tree mixed in with a zero length
+        let mut zero = B256::ZERO;
+        for _ in 0..13 {
+            zero = sha256_pair(zero, zero);
+        }
+        assert_eq!(epoch_accumulator_root([]), sha256_pair(zero, B256::ZERO));
+    }
+
+    #[test]
+    fn validates_pre_merge_headers_against_accumulator() {
+        let consensus = EthBeaconConsensus::new(MAINNET.clone());
+        let headers = pre_merge_headers();
+        let root = epoch_accumulator_root(headers.iter().map(|(header, td)| (header.hash(), *td)));
+
+        assert_eq!(consensus.validate_pre_merge_headers(&headers, root), Ok(()));
+        assert!(matches!(
+            consensus.validate_pre_merge_headers(&headers, B256::ZERO),
+            Err(ConsensusError::EpochAccumulatorMismatch(_))
+        ));
+
+        let mut headers = headers;
+        headers[2].1 += U256::from(1);
+        assert!(matches!(
+            consensus.validate_pre_merge_headers(&headers, root),
+            Err(ConsensusError::TotalDifficultyMismatch { block_number: 3, .. })
+        ));
+    }
+}
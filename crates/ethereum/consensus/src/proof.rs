@@ -0,0 +1,29 @@
+//! Externally-provided proofs of block execution outcomes.
+
+use alloc::{fmt::Debug, string::String};
+use alloy_primitives::{Bloom, Bytes, B256};
+
+/// The roots of a block's execution outcome, attested by an external prover, e.g. a stateless
+/// prover.
+///
+/// See [`EthBeaconConsensus`](crate::EthBeaconConsensus)'s
+/// [`ProofAssistedConsensus`](reth_consensus::ProofAssistedConsensus) implementation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionOutcomeProof {
+    /// The hash of the block the proof was produced for.
+    pub block_hash: B256,
+    /// The receipts root computed by the prover.
+    pub receipts_root: B256,
+    /// The logs bloom computed by the prover.
+    pub logs_bloom: Bloom,
+    /// The proof attesting the roots, opaque to consensus.
+    pub proof: Bytes,
+}
+
+/// Verifies [`ExecutionOutcomeProof`]s.
+pub trait ExecutionProofVerifier: Debug + Send + Sync {
+    /// Verifies that the proof attests the roots it carries.
+    ///
+    /// Returns the reason if the proof is invalid.
+    fn verify(&self, proof: &ExecutionOutcomeProof) -> Result<(), String>;
+}
@@ -1,11 +1,18 @@
-use alloc::vec::Vec;
-use alloy_consensus::{proofs::calculate_receipt_root, BlockHeader, TxReceipt};
-use alloy_eips::{eip7685::Requests, Encodable2718};
+use crate::ExecutionOutcomeProof;
+use alloc::{collections::BTreeMap, format, vec::Vec};
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_eips::{
+    eip6110::DEPOSIT_REQUEST_TYPE, eip7002::WITHDRAWAL_REQUEST_TYPE,
+    eip7251::CONSOLIDATION_REQUEST_TYPE, eip7685::Requests, Encodable2718, Typed2718,
+};
 use alloy_primitives::{Bloom, Bytes, B256};
 use reth_chainspec::EthereumHardforks;
-use reth_consensus::ConsensusError;
+use reth_consensus::{
+    observe_validation, ConsensusError, ConsensusObserver, InvalidRequestsReason, ValidationStage,
+};
 use reth_primitives_traits::{
-    receipt::gas_spent_by_transactions, Block, GotExpected, Receipt, RecoveredBlock,
+    proofs::IncrementalReceiptsRoot, receipt::gas_spent_by_transactions, Block, BlockBody,
+    GotExpected, Receipt, RecoveredBlock,
 };
 
 /// Validate a block with regard to execution results:
@@ -23,7 +30,166 @@ where
     R: Receipt,
     ChainSpec: EthereumHardforks,
 {
-    // Check if gas used matches the value set in header.
+    validate_block_post_execution_with_scratch(
+        block,
+        chain_spec,
+        receipts,
+        requests,
+        None,
+        &mut IncrementalReceiptsRoot::default(),
+        None,
+    )
+}
+
+/// Validate a block with regard to execution results like [`validate_block_post_execution`], when
+/// the requests the block is expected to produce are known, e.g. the execution requests of a
+/// payload.
+///
+/// If the requests hash doesn't match, the produced requests are compared against the expected
+/// requests with [`compare_requests`] to report the request type that differs.
+pub fn validate_block_post_execution_with_requests<B, R, ChainSpec>(
+    block: &RecoveredBlock<B>,
+    chain_spec: &ChainSpec,
+    receipts: &[R],
+    requests: &Requests,
+    expected_requests: &Requests,
+) -> Result<(), ConsensusError>
+where
+    B: Block,
+    R: Receipt,
+    ChainSpec: EthereumHardforks,
+{
+    validate_block_post_execution_with_scratch(
+        block,
+        chain_spec,
+        receipts,
+        requests,
+        Some(expected_requests),
+        &mut IncrementalReceiptsRoot::default(),
+        None,
+    )
+}
+
+/// Performs the checks of [`validate_block_post_execution`], using `scratch` to compute the
+/// receipts root and logs bloom, and reports every rule to the `observer`, if any.
+///
+/// This allows reusing the allocation when validating multiple blocks.
+pub(crate) fn validate_block_post_execution_with_scratch<B, R, ChainSpec>(
+    block: &RecoveredBlock<B>,
+    chain_spec: &ChainSpec,
+    receipts: &[R],
+    requests: &Requests,
+    expected_requests: Option<&Requests>,
+    scratch: &mut IncrementalReceiptsRoot,
+    observer: Option<&dyn ConsensusObserver>,
+) -> Result<(), ConsensusError>
+where
+    B: Block,
+    R: Receipt,
+    ChainSpec: EthereumHardforks,
+{
+    let stage = ValidationStage::PostExecution;
+    observe_validation(observer, stage, "gas_used", || validate_gas_used(block, receipts))?;
+
+    // Before Byzantium, receipts contained state root that would mean that expensive
+    // operation as hashing that is required for state root got calculated in every
+    // transaction This was replaced with is_success flag.
+    // See more about EIP here: https://eips.ethereum.org/EIPS/eip-658
+    if chain_spec.is_byzantium_active_at_block(block.header().number()) {
+        observe_validation(observer, stage, "receipts_root", || {
+            verify_receipts_with_scratch(
+                block.header().receipts_root(),
+                block.header().logs_bloom(),
+                receipts,
+                scratch,
+            )
+            .inspect_err(|error| {
+                let receipts = receipts
+                    .iter()
+                    .map(|r| Bytes::from(r.with_bloom_ref().encoded_2718()))
+                    .collect::<Vec<_>>();
+                tracing::debug!(%error, ?receipts, "receipts verification failed");
+            })
+        })?;
+    }
+
+    observe_validation(observer, stage, "requests_hash", || {
+        validate_requests_hash(block, chain_spec, requests, expected_requests)
+    })
+}
+
+/// Validate a block with regard to execution results like [`validate_block_post_execution`], but
+/// compares the receipts root and logs bloom attested by the given proof against the block header
+/// instead of recomputing them from the receipts.
+///
+/// The proof itself must have been verified by the caller.
+pub fn validate_block_post_execution_with_proof<B, R, ChainSpec>(
+    block: &RecoveredBlock<B>,
+    chain_spec: &ChainSpec,
+    receipts: &[R],
+    requests: &Requests,
+    proof: &ExecutionOutcomeProof,
+) -> Result<(), ConsensusError>
+where
+    B: Block,
+    R: Receipt,
+    ChainSpec: EthereumHardforks,
+{
+    validate_block_post_execution_with_proof_observed(
+        block, chain_spec, receipts, requests, proof, None,
+    )
+}
+
+/// Performs the checks of [`validate_block_post_execution_with_proof`] and reports every rule to
+/// the `observer`, if any.
+pub(crate) fn validate_block_post_execution_with_proof_observed<B, R, ChainSpec>(
+    block: &RecoveredBlock<B>,
+    chain_spec: &ChainSpec,
+    receipts: &[R],
+    requests: &Requests,
+    proof: &ExecutionOutcomeProof,
+    observer: Option<&dyn ConsensusObserver>,
+) -> Result<(), ConsensusError>
+where
+    B: Block,
+    R: Receipt,
+    ChainSpec: EthereumHardforks,
+{
+    let stage = ValidationStage::PostExecution;
+    observe_validation(observer, stage, "proof_block_hash", || {
+        if proof.block_hash != block.hash() {
+            return Err(ConsensusError::InvalidValidationProof(format!(
+                "proof for block {} used for block {}",
+                proof.block_hash,
+                block.hash()
+            )))
+        }
+        Ok(())
+    })?;
+
+    observe_validation(observer, stage, "gas_used", || validate_gas_used(block, receipts))?;
+
+    if chain_spec.is_byzantium_active_at_block(block.header().number()) {
+        observe_validation(observer, stage, "receipts_root", || {
+            compare_receipts_root_and_logs_bloom(
+                proof.receipts_root,
+                proof.logs_bloom,
+                block.header().receipts_root(),
+                block.header().logs_bloom(),
+            )
+        })?;
+    }
+
+    observe_validation(observer, stage, "requests_hash", || {
+        validate_requests_hash(block, chain_spec, requests, None)
+    })
+}
+
+/// Checks that the gas used in the block header matches the cumulative gas used of the receipts.
+fn validate_gas_used<B: Block, R: Receipt>(
+    block: &RecoveredBlock<B>,
+    receipts: &[R],
+) -> Result<(), ConsensusError> {
     let cumulative_gas_used =
         receipts.last().map(|receipt| receipt.cumulative_gas_used()).unwrap_or(0);
     if block.header().gas_used() != cumulative_gas_used {
@@ -33,30 +199,34 @@ where
         })
     }
 
-    // Before Byzantium, receipts contained state root that would mean that expensive
-    // operation as hashing that is required for state root got calculated in every
-    // transaction This was replaced with is_success flag.
-    // See more about EIP here: https://eips.ethereum.org/EIPS/eip-658
-    if chain_spec.is_byzantium_active_at_block(block.header().number()) {
-        if let Err(error) =
-            verify_receipts(block.header().receipts_root(), block.header().logs_bloom(), receipts)
-        {
-            let receipts = receipts
-                .iter()
-                .map(|r| Bytes::from(r.with_bloom_ref().encoded_2718()))
-                .collect::<Vec<_>>();
-            tracing::debug!(%error, ?receipts, "receipts verification failed");
-            return Err(error)
-        }
-    }
+    Ok(())
+}
 
-    // Validate that the header requests hash matches the calculated requests hash
+/// Validates that the header requests hash matches the calculated requests hash, after Prague.
+///
+/// On a mismatch, the requests are checked request type by request type, against the
+/// `expected_requests` if they are known, to report which request type is off.
+fn validate_requests_hash<B, ChainSpec>(
+    block: &RecoveredBlock<B>,
+    chain_spec: &ChainSpec,
+    requests: &Requests,
+    expected_requests: Option<&Requests>,
+) -> Result<(), ConsensusError>
+where
+    B: Block,
+    ChainSpec: EthereumHardforks,
+{
     if chain_spec.is_prague_active_at_timestamp(block.header().timestamp()) {
         let Some(header_requests_hash) = block.header().requests_hash() else {
             return Err(ConsensusError::RequestsHashMissing)
         };
         let requests_hash = requests.requests_hash();
         if requests_hash != header_requests_hash {
+            // Report malformed system contract outputs by request type, if any.
+            validate_requests(requests)?;
+            if let Some(expected_requests) = expected_requests {
+                compare_requests(requests, expected_requests)?;
+            }
             return Err(ConsensusError::BodyRequestsHashDiff(
                 GotExpected::new(requests_hash, header_requests_hash).into(),
             ))
@@ -66,19 +236,259 @@ where
     Ok(())
 }
 
+/// Returns the size of a single request of the given EIP-7685 request type, if the type is known.
+const fn request_size(request_type: u8) -> Option<usize> {
+    match request_type {
+        // pubkey (48) + withdrawal credentials (32) + amount (8) + signature (96) + index (8)
+        DEPOSIT_REQUEST_TYPE => Some(192),
+        // source address (20) + validator pubkey (48) + amount (8)
+        WITHDRAWAL_REQUEST_TYPE => Some(76),
+        // source address (20) + source pubkey (48) + target pubkey (48)
+        CONSOLIDATION_REQUEST_TYPE => Some(116),
+        _ => None,
+    }
+}
+
+/// Validates the EIP-7685 requests produced by the system contracts of a block, request type by
+/// request type:
+///
+/// - Request types must be known and strictly ascending
+/// - The data of each request type must be a sequence of requests of the fixed size of that type
+///
+/// Requests without data are ignored, as they are not part of the requests hash.
+pub fn validate_requests(requests: &Requests) -> Result<(), ConsensusError> {
+    let mut previous_type = None;
+    for request in requests.iter() {
+        let Some((&request_type, data)) = request.split_first() else { continue };
+        if data.is_empty() {
+            continue
+        }
+
+        if previous_type.is_some_and(|previous| previous >= request_type) {
+            return Err(ConsensusError::InvalidRequests {
+                request_type,
+                reason: InvalidRequestsReason::NotAscending,
+            })
+        }
+        previous_type = Some(request_type);
+
+        let Some(size) = request_size(request_type) else {
+            return Err(ConsensusError::InvalidRequests {
+                request_type,
+                reason: InvalidRequestsReason::UnknownType,
+            })
+        };
+        if data.len() % size != 0 {
+            return Err(ConsensusError::InvalidRequests {
+                request_type,
+                reason: InvalidRequestsReason::InvalidLength { len: data.len(), size },
+            })
+        }
+    }
+
+    Ok(())
+}
+
+/// Compares the requests produced by executing a block against the expected requests, e.g. the
+/// execution requests of a payload, request type by request type.
+///
+/// Returns [`ConsensusError::BodyRequestsDiff`] for the lowest request type whose requests
+/// differ.
+pub fn compare_requests(got: &Requests, expected: &Requests) -> Result<(), ConsensusError> {
+    let got = requests_by_type(got);
+    let mut expected = requests_by_type(expected);
+
+    for (request_type, got) in got {
+        let expected = expected.remove(&request_type).unwrap_or_default();
+        if got != expected {
+            return Err(ConsensusError::BodyRequestsDiff {
+                request_type,
+                requests: GotExpected::new(got, expected).into(),
+            })
+        }
+    }
+
+    if let Some((request_type, expected)) = expected.pop_first() {
+        return Err(ConsensusError::BodyRequestsDiff {
+            request_type,
+            requests: GotExpected::new(Bytes::new(), expected).into(),
+        })
+    }
+
+    Ok(())
+}
+
+/// Returns the request data of the non-empty requests by request type.
+fn requests_by_type(requests: &Requests) -> BTreeMap<u8, Bytes> {
+    requests
+        .iter()
+        .filter_map(|request| request.split_first())
+        .filter(|(_, data)| !data.is_empty())
+        .map(|(request_type, data)| (*request_type, Bytes::copy_from_slice(data)))
+        .collect()
+}
+
+/// The first receipt of a block that diverges, see [`diff_receipts`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceiptDivergence {
+    /// The index of the receipt in the block.
+    pub index: usize,
+    /// Why the receipt diverges.
+    pub reason: ReceiptDivergenceReason,
+    /// The cumulative gas used of the receipt, or of the previous receipt if it's missing.
+    pub cumulative_gas_used: u64,
+    /// The number of logs of the receipt.
+    pub logs: usize,
+    /// The EIP-2718 encoding of the receipt with its bloom, empty if it's missing.
+    pub encoded: Bytes,
+}
+
+/// The reason a receipt diverges, see [`ReceiptDivergence`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceiptDivergenceReason {
+    /// There is no receipt for the transaction at the index.
+    MissingReceipt,
+    /// There is no transaction for the receipt at the index.
+    UnexpectedReceipt,
+    /// The receipt type doesn't match the transaction type.
+    TxType(GotExpected<u8>),
+    /// The cumulative gas used is lower than the cumulative gas used of the previous receipt.
+    CumulativeGasDecreased {
+        /// The cumulative gas used of the previous receipt.
+        previous: u64,
+    },
+    /// The gas used by the transaction exceeds its gas limit.
+    GasUsedExceedsGasLimit {
+        /// The gas used by the transaction.
+        gas_used: u64,
+        /// The gas limit of the transaction.
+        gas_limit: u64,
+    },
+    /// The receipt differs from the expected receipt.
+    Receipt(GotExpected<Bytes>),
+}
+
+/// Compares the receipts of a block against its transactions one by one and returns the first
+/// receipt that is inconsistent with its transaction.
+///
+/// If the `expected` receipts are known, e.g. from another node, the encoding of every receipt is
+/// compared to the expected receipt as well.
+///
+/// This is a diagnostic for a [`ConsensusError::BodyReceiptRootDiff`], which only tells that
+/// some receipt differs.
+pub fn diff_receipts<B, R>(
+    block: &RecoveredBlock<B>,
+    receipts: &[R],
+    expected: Option<&[R]>,
+) -> Option<ReceiptDivergence>
+where
+    B: Block,
+    R: Receipt,
+{
+    let transactions = block.body().transactions();
+    let mut previous_cumulative_gas_used = 0;
+
+    for index in 0..transactions.len().max(receipts.len()) {
+        let (transaction, receipt) = match (transactions.get(index), receipts.get(index)) {
+            (Some(transaction), Some(receipt)) => (transaction, receipt),
+            (Some(_), None) => {
+                return Some(ReceiptDivergence {
+                    index,
+                    reason: ReceiptDivergenceReason::MissingReceipt,
+                    cumulative_gas_used: previous_cumulative_gas_used,
+                    logs: 0,
+                    encoded: Bytes::new(),
+                })
+            }
+            (None, Some(receipt)) => {
+                return Some(receipt_divergence(
+                    index,
+                    receipt,
+                    ReceiptDivergenceReason::UnexpectedReceipt,
+                ))
+            }
+            (None, None) => unreachable!("index is below the number of transactions or receipts"),
+        };
+
+        let cumulative_gas_used = receipt.cumulative_gas_used();
+        let reason = if receipt.ty() != transaction.ty() {
+            Some(ReceiptDivergenceReason::TxType(GotExpected {
+                got: receipt.ty(),
+                expected: transaction.ty(),
+            }))
+        } else if cumulative_gas_used < previous_cumulative_gas_used {
+            Some(ReceiptDivergenceReason::CumulativeGasDecreased {
+                previous: previous_cumulative_gas_used,
+            })
+        } else if cumulative_gas_used - previous_cumulative_gas_used > transaction.gas_limit() {
+            Some(ReceiptDivergenceReason::GasUsedExceedsGasLimit {
+                gas_used: cumulative_gas_used - previous_cumulative_gas_used,
+                gas_limit: transaction.gas_limit(),
+            })
+        } else {
+            expected.and_then(|expected| expected.get(index)).and_then(|expected| {
+                let got = Bytes::from(receipt.with_bloom_ref().encoded_2718());
+                let expected = Bytes::from(expected.with_bloom_ref().encoded_2718());
+                (got != expected)
+                    .then(|| ReceiptDivergenceReason::Receipt(GotExpected { got, expected }))
+            })
+        };
+        if let Some(reason) = reason {
+            return Some(receipt_divergence(index, receipt, reason))
+        }
+
+        previous_cumulative_gas_used = cumulative_gas_used;
+    }
+
+    None
+}
+
+/// Returns the [`ReceiptDivergence`] of the given receipt.
+fn receipt_divergence<R: Receipt>(
+    index: usize,
+    receipt: &R,
+    reason: ReceiptDivergenceReason,
+) -> ReceiptDivergence {
+    ReceiptDivergence {
+        index,
+        reason,
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: receipt.logs().len(),
+        encoded: receipt.with_bloom_ref().encoded_2718().into(),
+    }
+}
+
 /// Calculate the receipts root, and compare it against the expected receipts root and logs
 /// bloom.
+#[cfg(test)]
 fn verify_receipts<R: Receipt>(
     expected_receipts_root: B256,
     expected_logs_bloom: Bloom,
     receipts: &[R],
 ) -> Result<(), ConsensusError> {
-    // Calculate receipts root.
-    let receipts_with_bloom = receipts.iter().map(TxReceipt::with_bloom_ref).collect::<Vec<_>>();
-    let receipts_root = calculate_receipt_root(&receipts_with_bloom);
+    verify_receipts_with_scratch(
+        expected_receipts_root,
+        expected_logs_bloom,
+        receipts,
+        &mut IncrementalReceiptsRoot::default(),
+    )
+}
 
-    // Calculate header logs bloom.
-    let logs_bloom = receipts_with_bloom.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom_ref());
+/// Calculate the receipts root, and compare it against the expected receipts root and logs
+/// bloom.
+///
+/// The `scratch` receipts root is cleared and used to compute the receipts root and logs bloom,
+/// the same way they are computed when building a block.
+fn verify_receipts_with_scratch<R: Receipt>(
+    expected_receipts_root: B256,
+    expected_logs_bloom: Bloom,
+    receipts: &[R],
+    scratch: &mut IncrementalReceiptsRoot,
+) -> Result<(), ConsensusError> {
+    scratch.clear();
+    scratch.extend(receipts);
+    let receipts_root = scratch.root();
+    let logs_bloom = scratch.logs_bloom();
 
     compare_receipts_root_and_logs_bloom(
         receipts_root,
@@ -119,6 +529,100 @@ mod tests {
     use alloy_primitives::{b256, hex};
     use reth_ethereum_primitives::Receipt;
 
+    #[test]
+    fn test_validate_requests() {
+        let withdrawal = [&[WITHDRAWAL_REQUEST_TYPE][..], &[0u8; 76]].concat();
+        let consolidation = [&[CONSOLIDATION_REQUEST_TYPE][..], &[0u8; 116]].concat();
+
+        let requests = Requests::new(vec![withdrawal.clone().into(), consolidation.clone().into()]);
+        assert!(validate_requests(&requests).is_ok());
+
+        let requests = Requests::new(vec![consolidation.into(), withdrawal.clone().into()]);
+        assert_eq!(
+            validate_requests(&requests),
+            Err(ConsensusError::InvalidRequests {
+                request_type: WITHDRAWAL_REQUEST_TYPE,
+                reason: InvalidRequestsReason::NotAscending
+            })
+        );
+
+        let requests = Requests::new(vec![withdrawal[..70].to_vec().into()]);
+        assert_eq!(
+            validate_requests(&requests),
+            Err(ConsensusError::InvalidRequests {
+                request_type: WITHDRAWAL_REQUEST_TYPE,
+                reason: InvalidRequestsReason::InvalidLength { len: 69, size: 76 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_compare_requests() {
+        let deposit = Bytes::from([&[DEPOSIT_REQUEST_TYPE][..], &[1u8; 192]].concat());
+        let withdrawal = Bytes::from([&[WITHDRAWAL_REQUEST_TYPE][..], &[2u8; 76]].concat());
+
+        let got = Requests::new(vec![deposit.clone(), withdrawal.clone()]);
+        assert!(compare_requests(&got, &got.clone()).is_ok());
+
+        let expected = Requests::new(vec![deposit]);
+        assert_eq!(
+            compare_requests(&got, &expected),
+            Err(ConsensusError::BodyRequestsDiff {
+                request_type: WITHDRAWAL_REQUEST_TYPE,
+                requests: GotExpected::new(withdrawal.slice(1..), Bytes::new()).into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_post_execution_requests_diff() {
+        use alloy_consensus::{Header, EMPTY_ROOT_HASH};
+        use reth_chainspec::ChainSpecBuilder;
+        use reth_ethereum_primitives::Block;
+
+        let deposit = Bytes::from([&[DEPOSIT_REQUEST_TYPE][..], &[1u8; 192]].concat());
+        let withdrawal = Bytes::from([&[WITHDRAWAL_REQUEST_TYPE][..], &[2u8; 76]].concat());
+        let expected = Requests::new(vec![deposit.clone(), withdrawal.clone()]);
+        let got = Requests::new(vec![deposit]);
+
+        let chain_spec = ChainSpecBuilder::mainnet().prague_activated().build();
+        let header = Header {
+            receipts_root: EMPTY_ROOT_HASH,
+            requests_hash: Some(expected.requests_hash()),
+            ..Default::default()
+        };
+        let block = RecoveredBlock::new_unhashed(Block::new(header, Default::default()), vec![]);
+        let receipts: [Receipt; 0] = [];
+
+        assert!(validate_block_post_execution_with_requests(
+            &block,
+            &chain_spec,
+            &receipts,
+            &expected,
+            &expected
+        )
+        .is_ok());
+
+        // without the expected requests only the hash mismatch can be reported
+        assert!(matches!(
+            validate_block_post_execution(&block, &chain_spec, &receipts, &got),
+            Err(ConsensusError::BodyRequestsHashDiff(_))
+        ));
+        assert_eq!(
+            validate_block_post_execution_with_requests(
+                &block,
+                &chain_spec,
+                &receipts,
+                &got,
+                &expected
+            ),
+            Err(ConsensusError::BodyRequestsDiff {
+                request_type: WITHDRAWAL_REQUEST_TYPE,
+                requests: GotExpected::new(Bytes::new(), withdrawal.slice(1..)).into()
+            })
+        );
+    }
+
     #[test]
     fn test_verify_receipts_success() {
         // Create a vector of 5 default Receipt instances
@@ -184,6 +688,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_diff_receipts() {
+        use alloy_consensus::{Header, TxLegacy};
+        use alloy_primitives::{Address, Signature};
+        use reth_ethereum_primitives::{Block, BlockBody, Transaction, TransactionSigned};
+
+        let transaction = TransactionSigned::new_unhashed(
+            Transaction::Legacy(TxLegacy { gas_limit: 21_000, ..Default::default() }),
+            Signature::test_signature(),
+        );
+        let body = BlockBody {
+            transactions: vec![transaction.clone(), transaction],
+            ..Default::default()
+        };
+        let block = RecoveredBlock::new_unhashed(
+            Block::new(Header::default(), body),
+            vec![Address::ZERO; 2],
+        );
+        let receipt = |cumulative_gas_used| Receipt { cumulative_gas_used, ..Default::default() };
+
+        let receipts = [receipt(21_000), receipt(42_000)];
+        assert_eq!(diff_receipts(&block, &receipts, None), None);
+
+        // the second transaction used more gas than its gas limit
+        let divergence = diff_receipts(&block, &[receipt(21_000), receipt(50_000)], None).unwrap();
+        assert_eq!(divergence.index, 1);
+        assert_eq!(divergence.cumulative_gas_used, 50_000);
+        assert_eq!(
+            divergence.reason,
+            ReceiptDivergenceReason::GasUsedExceedsGasLimit { gas_used: 29_000, gas_limit: 21_000 }
+        );
+
+        let divergence = diff_receipts(&block, &receipts[..1], None).unwrap();
+        assert_eq!(divergence.index, 1);
+        assert_eq!(divergence.reason, ReceiptDivergenceReason::MissingReceipt);
+
+        let expected = [receipt(21_000), Receipt { success: true, ..receipt(42_000) }];
+        let divergence = diff_receipts(&block, &receipts, Some(&expected)).unwrap();
+        assert_eq!(divergence.index, 1);
+        assert!(matches!(divergence.reason, ReceiptDivergenceReason::Receipt(_)));
+    }
+
     #[test]
     fn test_compare_log_bloom_failure() {
         let calculated_receipts_root = B256::random();
@@ -1,6 +1,6 @@
 use alloc::sync::Arc;
 use alloy_consensus::{
-    proofs, Block, BlockBody, BlockHeader, Header, Transaction, TxReceipt, EMPTY_OMMER_ROOT_HASH,
+    proofs, Block, BlockBody, BlockHeader, Header, Transaction, EMPTY_OMMER_ROOT_HASH,
 };
 use alloy_eips::merge::BEACON_NONCE;
 use alloy_evm::{block::BlockExecutorFactory, eth::EthBlockExecutionCtx};
@@ -9,7 +9,6 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
 use reth_evm::execute::{BlockAssembler, BlockAssemblerInput, BlockExecutionError};
 use reth_execution_types::BlockExecutionResult;
-use reth_primitives_traits::logs_bloom;
 
 /// Block builder for Ethereum.
 #[derive(Debug, Clone)]
@@ -47,16 +46,16 @@ where
             execution_ctx: ctx,
             parent,
             transactions,
-            output: BlockExecutionResult { receipts, requests, gas_used },
+            output: BlockExecutionResult { requests, gas_used, .. },
             state_root,
+            receipts_root,
+            logs_bloom,
             ..
         } = input;
 
         let timestamp = evm_env.block_env.timestamp.saturating_to();
 
         let transactions_root = proofs::calculate_transaction_root(&transactions);
-        let receipts_root = Receipt::calculate_receipt_root_no_memo(receipts);
-        let logs_bloom = logs_bloom(receipts.iter().flat_map(|r| r.logs()));
 
         let withdrawals = self
             .chain_spec
@@ -78,13 +77,24 @@ where
             blob_gas_used =
                 Some(transactions.iter().map(|tx| tx.blob_gas_used().unwrap_or_default()).sum());
             excess_blob_gas = if self.chain_spec.is_cancun_active_at_timestamp(parent.timestamp) {
-                parent.maybe_next_block_excess_blob_gas(
-                    self.chain_spec.blob_params_at_timestamp(timestamp),
-                )
+                self.chain_spec
+                    .blob_params_at_timestamp(timestamp)
+                    .zip(parent.excess_blob_gas.zip(parent.blob_gas_used))
+                    .map(|(blob_params, (excess_blob_gas, blob_gas_used))| {
+                        self.chain_spec.next_block_excess_blob_gas(
+                            blob_params,
+                            excess_blob_gas,
+                            blob_gas_used,
+                        )
+                    })
             } else {
                 // for the first post-fork block, both parent.blob_gas_used and
                 // parent.excess_blob_gas are evaluated as 0
-                Some(alloy_eips::eip7840::BlobParams::cancun().next_block_excess_blob_gas(0, 0))
+                Some(self.chain_spec.next_block_excess_blob_gas(
+                    alloy_eips::eip7840::BlobParams::cancun(),
+                    0,
+                    0,
+                ))
             };
         }
 
@@ -165,7 +165,7 @@ where
         // blobparams
         let blob_excess_gas_and_price =
             header.excess_blob_gas.zip(blob_params).map(|(excess_blob_gas, params)| {
-                let blob_gasprice = params.calc_blob_fee(excess_blob_gas);
+                let blob_gasprice = self.chain_spec().blob_base_fee(params, excess_blob_gas);
                 BlobExcessGasAndPrice { excess_blob_gas, blob_gasprice }
             });
 
@@ -207,12 +207,15 @@ where
 
         // if the parent block did not have excess blob gas (i.e. it was pre-cancun), but it is
         // cancun now, we need to set the excess blob gas to the default value(0)
-        let blob_excess_gas_and_price = parent
-            .maybe_next_block_excess_blob_gas(blob_params)
+        let blob_excess_gas_and_price = blob_params
+            .zip(parent.excess_blob_gas.zip(parent.blob_gas_used))
+            .map(|(params, (excess_blob_gas, blob_gas_used))| {
+                chain_spec.next_block_excess_blob_gas(params, excess_blob_gas, blob_gas_used)
+            })
             .or_else(|| (spec_id == SpecId::CANCUN).then_some(0))
             .map(|excess_blob_gas| {
-                let blob_gasprice =
-                    blob_params.unwrap_or_else(BlobParams::cancun).calc_blob_fee(excess_blob_gas);
+                let blob_gasprice = chain_spec
+                    .blob_base_fee(blob_params.unwrap_or_else(BlobParams::cancun), excess_blob_gas);
                 BlobExcessGasAndPrice { excess_blob_gas, blob_gasprice }
             });
 
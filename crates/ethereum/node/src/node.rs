@@ -5,7 +5,7 @@ use crate::{EthEngineTypes, EthEvmConfig};
 use alloy_eips::{eip7840::BlobParams, merge::EPOCH_SLOTS};
 use alloy_rpc_types_engine::ExecutionData;
 use reth_chainspec::{ChainSpec, EthChainSpec, EthereumHardforks, Hardforks};
-use reth_consensus::{ConsensusError, FullConsensus};
+use reth_consensus::{CachedConsensus, ConsensusError, FullConsensus};
 use reth_engine_local::LocalPayloadAttributesBuilder;
 use reth_engine_primitives::EngineTypes;
 use reth_ethereum_consensus::EthBeaconConsensus;
@@ -155,7 +155,9 @@ where
         .eth_proof_window(ctx.config.eth_proof_window)
         .fee_history_cache_config(ctx.config.fee_history_cache)
         .proof_permits(ctx.config.proof_permits)
+        .proof_cache_max_entries(ctx.config.proof_cache_max_entries)
         .gas_oracle_config(ctx.config.gas_oracle)
+        .tx_forwarder(ctx.tx_forwarder)
         .build();
         Ok(api)
     }
@@ -504,7 +506,13 @@ where
     type Consensus = Arc<dyn FullConsensus<EthPrimitives, Error = ConsensusError>>;
 
     async fn build_consensus(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Consensus> {
-        Ok(Arc::new(EthBeaconConsensus::new(ctx.chain_spec())))
+        // the consensus is shared by the downloaders, the engine tree and the pipeline, so header
+        // validation results are cached across all of them
+        Ok(Arc::new(CachedConsensus::new(
+            EthBeaconConsensus::new(ctx.chain_spec())
+                .with_header_validator_config(ctx.config().consensus.header_validator_config())
+                .with_receipt_diagnostics(ctx.config().consensus.receipt_diagnostics),
+        )))
     }
 }
 
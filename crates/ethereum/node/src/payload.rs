@@ -4,7 +4,9 @@ use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_ethereum_payload_builder::EthereumBuilderConfig;
+use reth_ethereum_payload_builder::{
+    EthPayloadTransactions, EthereumBuilderConfig, InclusionConstraints,
+};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_evm::ConfigureEvm;
 use reth_node_api::{FullNodeTypes, NodeTypes, PrimitivesTy, TxTy};
@@ -14,11 +16,25 @@ use reth_node_builder::{
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
 /// A basic ethereum payload service.
+///
+/// The transactions of the payloads are selected by `Txs`, see [`EthPayloadTransactions`].
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder<Txs = ()> {
+    /// The type responsible for yielding the transactions of the payloads.
+    best_transactions: Txs,
+}
+
+impl<Txs> EthereumPayloadBuilder<Txs> {
+    /// Configures the type responsible for yielding the transactions that should be included in the
+    /// payloads, for example to implement a custom ordering.
+    pub fn with_transactions<T>(self, best_transactions: T) -> EthereumPayloadBuilder<T> {
+        EthereumPayloadBuilder { best_transactions }
+    }
+}
 
-impl<Types, Node, Pool, Evm> PayloadBuilderBuilder<Node, Pool, Evm> for EthereumPayloadBuilder
+impl<Types, Node, Pool, Evm, Txs> PayloadBuilderBuilder<Node, Pool, Evm>
+    for EthereumPayloadBuilder<Txs>
 where
     Types: NodeTypes<ChainSpec: EthereumHardforks, Primitives = EthPrimitives>,
     Node: FullNodeTypes<Types = Types>,
@@ -34,9 +50,10 @@ where
         PayloadAttributes = EthPayloadAttributes,
         PayloadBuilderAttributes = EthPayloadBuilderAttributes,
     >,
+    Txs: EthPayloadTransactions<Pool>,
 {
     type PayloadBuilder =
-        reth_ethereum_payload_builder::EthereumPayloadBuilder<Pool, Node::Provider, Evm>;
+        reth_ethereum_payload_builder::EthereumPayloadBuilder<Pool, Node::Provider, Evm, Txs>;
 
     async fn build_payload_builder(
         self,
@@ -52,7 +69,12 @@ where
             ctx.provider().clone(),
             pool,
             evm_config,
-            EthereumBuilderConfig::new().with_gas_limit(gas_limit),
-        ))
+            EthereumBuilderConfig::new().with_gas_limit(gas_limit).with_inclusion_constraints(
+                InclusionConstraints::new()
+                    .with_denied_addresses(conf.denied_addresses().iter().copied())
+                    .with_mandatory_transactions(conf.mandatory_transactions().iter().copied()),
+            ),
+        )
+        .with_transactions(self.best_transactions))
     }
 }
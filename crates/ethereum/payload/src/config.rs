@@ -1,3 +1,4 @@
+use crate::InclusionConstraints;
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
 
@@ -9,6 +10,8 @@ pub struct EthereumBuilderConfig {
     /// Waits for the first payload to be built if there is no payload built when the payload is
     /// being resolved.
     pub await_payload_on_missing: bool,
+    /// Constraints on which transactions are included.
+    pub inclusion: InclusionConstraints,
 }
 
 impl Default for EthereumBuilderConfig {
@@ -20,7 +23,11 @@ impl Default for EthereumBuilderConfig {
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new() -> Self {
-        Self { desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M, await_payload_on_missing: true }
+        Self {
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            await_payload_on_missing: true,
+            inclusion: InclusionConstraints::new(),
+        }
     }
 
     /// Set desired gas limit.
@@ -35,6 +42,13 @@ impl EthereumBuilderConfig {
         self.await_payload_on_missing = await_payload_on_missing;
         self
     }
+
+    /// Sets the [`InclusionConstraints`], e.g. a denylist of addresses whose transactions must
+    /// not be included.
+    pub fn with_inclusion_constraints(mut self, inclusion: InclusionConstraints) -> Self {
+        self.inclusion = inclusion;
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
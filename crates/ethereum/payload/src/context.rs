@@ -0,0 +1,174 @@
+//! The context of a payload build attempt, exposed to custom transaction ordering.
+
+use reth_payload_builder::EthPayloadBuilderAttributes;
+use reth_payload_primitives::PayloadBuilderAttributes;
+use reth_primitives_traits::SealedHeader;
+use reth_storage_api::StateProvider;
+use reth_transaction_pool::{
+    BestTransactions, BestTransactionsAttributes, TransactionPool, ValidPoolTransaction,
+};
+use std::{fmt, sync::Arc};
+
+/// The transactions of the pool in the order they should be included in a payload.
+pub type BestTransactionsIter<Pool> = Box<
+    dyn BestTransactions<Item = Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>,
+>;
+
+/// The parameters of a payload that is built on top of a parent block.
+///
+/// This is handed to [`EthPayloadTransactions`] before any transaction is executed, so that custom
+/// ordering logic can select the transactions of a payload without reimplementing the rest of the
+/// payload builder.
+pub struct PayloadBuildContext<'a, Pool> {
+    parent_header: &'a SealedHeader,
+    attributes: &'a EthPayloadBuilderAttributes,
+    gas_limit: u64,
+    base_fee: u64,
+    blob_gas_price: Option<u128>,
+    max_blob_count: u64,
+    state: &'a dyn StateProvider,
+    pool: &'a Pool,
+}
+
+impl<'a, Pool> PayloadBuildContext<'a, Pool> {
+    /// Creates the context of a payload with the given fee parameters.
+    #[expect(clippy::too_many_arguments)]
+    pub const fn new(
+        parent_header: &'a SealedHeader,
+        attributes: &'a EthPayloadBuilderAttributes,
+        gas_limit: u64,
+        base_fee: u64,
+        blob_gas_price: Option<u128>,
+        max_blob_count: u64,
+        state: &'a dyn StateProvider,
+        pool: &'a Pool,
+    ) -> Self {
+        Self {
+            parent_header,
+            attributes,
+            gas_limit,
+            base_fee,
+            blob_gas_price,
+            max_blob_count,
+            state,
+            pool,
+        }
+    }
+
+    /// Returns the header of the block the payload is built on.
+    pub const fn parent_header(&self) -> &'a SealedHeader {
+        self.parent_header
+    }
+
+    /// Returns the attributes of the payload.
+    pub const fn attributes(&self) -> &'a EthPayloadBuilderAttributes {
+        self.attributes
+    }
+
+    /// Returns the timestamp of the payload.
+    pub fn timestamp(&self) -> u64 {
+        self.attributes.timestamp()
+    }
+
+    /// Returns the gas limit of the payload.
+    pub const fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+
+    /// Returns the base fee of the payload.
+    pub const fn base_fee(&self) -> u64 {
+        self.base_fee
+    }
+
+    /// Returns the blob gas price of the payload, if blob transactions are active.
+    pub const fn blob_gas_price(&self) -> Option<u128> {
+        self.blob_gas_price
+    }
+
+    /// Returns the maximum number of blobs of the payload.
+    pub const fn max_blob_count(&self) -> u64 {
+        self.max_blob_count
+    }
+
+    /// Returns the state of the parent block.
+    ///
+    /// Reads are not cached across build attempts, unlike the reads of the executed transactions.
+    pub const fn state(&self) -> &'a dyn StateProvider {
+        self.state
+    }
+
+    /// Returns the transaction pool.
+    pub const fn pool(&self) -> &'a Pool {
+        self.pool
+    }
+
+    /// Returns the attributes for selecting the best transactions of the pool that can pay the
+    /// fees of the payload.
+    pub fn best_transactions_attributes(&self) -> BestTransactionsAttributes {
+        BestTransactionsAttributes::new(
+            self.base_fee,
+            self.blob_gas_price.map(|gas_price| gas_price as u64),
+        )
+    }
+}
+
+impl<Pool> fmt::Debug for PayloadBuildContext<'_, Pool> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PayloadBuildContext")
+            .field("parent_header", &self.parent_header)
+            .field("attributes", &self.attributes)
+            .field("gas_limit", &self.gas_limit)
+            .field("base_fee", &self.base_fee)
+            .field("blob_gas_price", &self.blob_gas_price)
+            .field("max_blob_count", &self.max_blob_count)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A type that returns the transactions that should be included in a payload, in the order they
+/// should be included.
+///
+/// The default implementation for `()` yields the best transactions of the pool. Transactions that
+/// don't fit into the payload, or fail to execute, are marked invalid on the returned iterator,
+/// which is expected to skip their descendants.
+pub trait EthPayloadTransactions<Pool: TransactionPool>:
+    Clone + Send + Sync + Unpin + 'static
+{
+    /// Returns the transactions of the payload with the given context.
+    fn best_transactions(&self, ctx: &PayloadBuildContext<'_, Pool>) -> BestTransactionsIter<Pool>;
+}
+
+impl<Pool: TransactionPool> EthPayloadTransactions<Pool> for () {
+    fn best_transactions(&self, ctx: &PayloadBuildContext<'_, Pool>) -> BestTransactionsIter<Pool> {
+        ctx.pool().best_transactions_with_attributes(ctx.best_transactions_attributes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use reth_storage_api::noop::NoopProvider;
+    use reth_transaction_pool::noop::NoopTransactionPool;
+
+    #[test]
+    fn default_transactions_use_payload_fees() {
+        let parent_header = SealedHeader::seal_slow(Header::default());
+        let attributes = EthPayloadBuilderAttributes::default();
+        let state: NoopProvider = NoopProvider::default();
+        let pool: NoopTransactionPool = NoopTransactionPool::default();
+        let ctx = PayloadBuildContext::new(
+            &parent_header,
+            &attributes,
+            30_000_000,
+            7,
+            Some(3),
+            9,
+            &state,
+            &pool,
+        );
+
+        assert_eq!(ctx.best_transactions_attributes(), BestTransactionsAttributes::new(7, Some(3)));
+        assert!(().best_transactions(&ctx).next().is_none());
+    }
+}
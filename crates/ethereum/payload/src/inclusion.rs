@@ -0,0 +1,273 @@
+//! Inclusion constraints applied to the transactions selected by the payload builder.
+
+use alloy_primitives::{Address, TxHash};
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_transaction_pool::{
+    error::InvalidPoolTransactionError, BestTransactions, PoolTransaction, TransactionPool,
+    ValidPoolTransaction,
+};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, LazyLock},
+};
+
+/// Constraints on which transactions the payload builder includes.
+///
+/// Transactions are filtered out if their sender or recipient is on the denylist. Mandatory
+/// transactions are taken from the pending subpool and executed before any other transaction,
+/// unless they are filtered out by the denylist themselves.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InclusionConstraints {
+    /// Addresses whose transactions must not be included, either as sender or recipient.
+    denied_addresses: Option<Arc<HashSet<Address>>>,
+    /// Hashes of transactions that must be included if they are pending in the pool.
+    mandatory_transactions: Option<Arc<Vec<TxHash>>>,
+}
+
+impl InclusionConstraints {
+    /// Creates new constraints that don't restrict the included transactions.
+    pub const fn new() -> Self {
+        Self { denied_addresses: None, mandatory_transactions: None }
+    }
+
+    /// Sets the addresses whose transactions must not be included.
+    pub fn with_denied_addresses(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        let addresses = addresses.into_iter().collect::<HashSet<_>>();
+        self.denied_addresses = (!addresses.is_empty()).then(|| Arc::new(addresses));
+        self
+    }
+
+    /// Sets the hashes of transactions that must be included if they are pending in the pool.
+    ///
+    /// Mandatory transactions are executed in the given order, except that the transactions of a
+    /// sender are executed in nonce order.
+    pub fn with_mandatory_transactions(mut self, hashes: impl IntoIterator<Item = TxHash>) -> Self {
+        let hashes = hashes.into_iter().collect::<Vec<_>>();
+        self.mandatory_transactions = (!hashes.is_empty()).then(|| Arc::new(hashes));
+        self
+    }
+
+    /// Returns the addresses whose transactions must not be included, if any.
+    pub fn denied_addresses(&self) -> Option<&HashSet<Address>> {
+        self.denied_addresses.as_deref()
+    }
+
+    /// Returns the hashes of transactions that must be included if available.
+    pub fn mandatory_transactions(&self) -> &[TxHash] {
+        self.mandatory_transactions.as_deref().map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns `true` if no constraints are configured.
+    pub const fn is_empty(&self) -> bool {
+        self.denied_addresses.is_none() && self.mandatory_transactions.is_none()
+    }
+
+    /// Returns `true` if the transaction from `sender` to `to` may be included.
+    pub fn is_allowed(&self, sender: &Address, to: Option<&Address>) -> bool {
+        let Some(denied) = &self.denied_addresses else { return true };
+        !denied.contains(sender) && !to.is_some_and(|to| denied.contains(to))
+    }
+
+    /// Returns `true` if the pool transaction may be included.
+    pub fn is_transaction_allowed<T: PoolTransaction>(&self, tx: &ValidPoolTransaction<T>) -> bool {
+        self.denied_addresses.is_none() || self.is_allowed(&tx.sender(), tx.to().as_ref())
+    }
+}
+
+/// Report of how the [`InclusionConstraints`] affected a single payload build attempt.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InclusionReport {
+    /// Transactions that were skipped because of the denylist.
+    pub filtered: Vec<TxHash>,
+    /// Mandatory transactions that were executed successfully.
+    pub mandatory_included: Vec<TxHash>,
+    /// Mandatory transactions that were not pending in the pool.
+    pub mandatory_missing: Vec<TxHash>,
+}
+
+impl InclusionReport {
+    /// Records the report in the [`InclusionMetrics`].
+    pub(crate) fn record(&self) {
+        let metrics = &*INCLUSION_METRICS;
+        metrics.filtered_transactions.increment(self.filtered.len() as u64);
+        metrics.mandatory_transactions_included.increment(self.mandatory_included.len() as u64);
+        metrics.mandatory_transactions_missing.increment(self.mandatory_missing.len() as u64);
+    }
+}
+
+/// The [`InclusionMetrics`], registered once and shared by all payload builds.
+static INCLUSION_METRICS: LazyLock<InclusionMetrics> = LazyLock::new(InclusionMetrics::default);
+
+/// Metrics for the [`InclusionConstraints`] of payload jobs.
+#[derive(Metrics)]
+#[metrics(scope = "payloads")]
+pub(crate) struct InclusionMetrics {
+    /// Total number of transactions skipped because of the builder denylist.
+    pub(crate) filtered_transactions: Counter,
+    /// Total number of mandatory transactions included in built payloads.
+    pub(crate) mandatory_transactions_included: Counter,
+    /// Total number of mandatory transactions that were not pending in the pool.
+    pub(crate) mandatory_transactions_missing: Counter,
+}
+
+/// Returns the pending mandatory transactions that can be executed before any other transaction,
+/// in execution order.
+///
+/// The transactions are ordered as given by `order`, except that the transactions of a sender are
+/// ordered by nonce. A transaction that depends on a pending transaction which is not mandatory
+/// can't be executed first, it's left to the regular transaction order instead.
+pub(crate) fn executable_mandatory_transactions<Pool: TransactionPool>(
+    pool: &Pool,
+    order: &[TxHash],
+    pending: &[Arc<ValidPoolTransaction<Pool::Transaction>>],
+) -> Vec<Arc<ValidPoolTransaction<Pool::Transaction>>> {
+    let position =
+        order.iter().enumerate().map(|(idx, hash)| (*hash, idx)).collect::<HashMap<_, _>>();
+    let mut ordered = pending.to_vec();
+    ordered.sort_by_key(|tx| position.get(tx.hash()).copied());
+
+    // the transactions of a sender take the positions of the sender's transactions in nonce order
+    let mut positions_by_sender = HashMap::<Address, Vec<usize>>::new();
+    for (idx, tx) in ordered.iter().enumerate() {
+        positions_by_sender.entry(tx.sender()).or_default().push(idx);
+    }
+    for positions in positions_by_sender.values() {
+        let mut txs = positions.iter().map(|idx| ordered[*idx].clone()).collect::<Vec<_>>();
+        txs.sort_by_key(|tx| tx.nonce());
+        for (idx, tx) in positions.iter().zip(txs) {
+            ordered[*idx] = tx;
+        }
+    }
+
+    // pending transactions are gapless, so a transaction can be executed first if all pending
+    // transactions of its sender with a lower nonce are mandatory as well
+    let hashes = pending.iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
+    let first_blocking_nonce = positions_by_sender
+        .into_keys()
+        .map(|sender| {
+            let nonce = pool
+                .get_pending_transactions_by_sender(sender)
+                .iter()
+                .filter(|tx| !hashes.contains(tx.hash()))
+                .map(|tx| tx.nonce())
+                .min();
+            (sender, nonce)
+        })
+        .collect::<HashMap<_, _>>();
+    ordered.retain(|tx| {
+        first_blocking_nonce[&tx.sender()].is_none_or(|blocking_nonce| tx.nonce() < blocking_nonce)
+    });
+    ordered
+}
+
+/// A [`BestTransactions`] iterator that yields the given mandatory transactions before the
+/// transactions of the wrapped iterator.
+///
+/// Mandatory transactions are not yielded again by the wrapped iterator. A mandatory transaction
+/// that is marked invalid is skipped together with its descendants for the rest of the iteration
+/// only, like any other transaction.
+pub(crate) struct MandatoryFirst<T: PoolTransaction, I> {
+    /// Mandatory transactions that haven't been yielded yet.
+    mandatory: VecDeque<Arc<ValidPoolTransaction<T>>>,
+    /// Hashes of all mandatory transactions.
+    mandatory_hashes: HashSet<TxHash>,
+    /// The wrapped iterator.
+    best: I,
+}
+
+impl<T: PoolTransaction, I> MandatoryFirst<T, I> {
+    /// Creates a new iterator that yields `mandatory` before the transactions of `best`.
+    pub(crate) fn new(mandatory: Vec<Arc<ValidPoolTransaction<T>>>, best: I) -> Self {
+        let mandatory_hashes = mandatory.iter().map(|tx| *tx.hash()).collect();
+        Self { mandatory: mandatory.into(), mandatory_hashes, best }
+    }
+}
+
+impl<T, I> Iterator for MandatoryFirst<T, I>
+where
+    T: PoolTransaction,
+    I: Iterator<Item = Arc<ValidPoolTransaction<T>>>,
+{
+    type Item = Arc<ValidPoolTransaction<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(tx) = self.mandatory.pop_front() {
+            return Some(tx)
+        }
+        loop {
+            let tx = self.best.next()?;
+            if !self.mandatory_hashes.contains(tx.hash()) {
+                return Some(tx)
+            }
+        }
+    }
+}
+
+impl<T, I> BestTransactions for MandatoryFirst<T, I>
+where
+    T: PoolTransaction,
+    I: BestTransactions<Item = Arc<ValidPoolTransaction<T>>>,
+{
+    fn mark_invalid(&mut self, tx: &Self::Item, kind: InvalidPoolTransactionError) {
+        // drop queued mandatory descendants of the invalid transaction
+        self.mandatory
+            .retain(|queued| queued.sender() != tx.sender() || queued.nonce() < tx.nonce());
+        self.best.mark_invalid(tx, kind)
+    }
+
+    fn no_updates(&mut self) {
+        self.best.no_updates()
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        if skip_blobs {
+            self.mandatory.retain(|tx| !tx.is_eip4844());
+        }
+        self.best.set_skip_blobs(skip_blobs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionOrigin,
+    };
+
+    #[test]
+    fn denylist_filters_sender_and_recipient() {
+        let denied = Address::with_last_byte(1);
+        let constraints = InclusionConstraints::new().with_denied_addresses([denied]);
+
+        assert!(!constraints.is_allowed(&denied, None));
+        assert!(!constraints.is_allowed(&Address::ZERO, Some(&denied)));
+        assert!(constraints.is_allowed(&Address::ZERO, Some(&Address::with_last_byte(2))));
+        assert!(InclusionConstraints::new().is_empty());
+        assert!(InclusionConstraints::new().with_denied_addresses([]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn mandatory_transactions_in_execution_order() {
+        let pool = testing_pool();
+        let a0 = MockTransaction::eip1559().with_sender(Address::with_last_byte(1));
+        let a1 = a0.next();
+        let a2 = a1.next();
+        let b0 = MockTransaction::eip1559().with_sender(Address::with_last_byte(2));
+        let b1 = b0.next();
+        for tx in [&a0, &a1, &a2, &b0, &b1] {
+            pool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+        }
+
+        let order = [*a1.hash(), *b1.hash(), *a0.hash()];
+        let pending = pool.get_pending_transactions_with_predicate(|tx| order.contains(tx.hash()));
+        let mandatory = executable_mandatory_transactions(&pool, &order, &pending);
+
+        // the transactions of a sender are executed in nonce order, while `b1` depends on `b0`,
+        // which is not mandatory, so it's left to the regular transaction order
+        assert_eq!(
+            mandatory.iter().map(|tx| *tx.hash()).collect::<Vec<_>>(),
+            vec![*a0.hash(), *a1.hash()]
+        );
+    }
+}
@@ -31,26 +31,28 @@ use reth_revm::{database::StateProviderDatabase, db::State};
 use reth_storage_api::StateProviderFactory;
 use reth_transaction_pool::{
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError},
-    BestTransactions, BestTransactionsAttributes, PoolTransaction, TransactionPool,
-    ValidPoolTransaction,
+    BestTransactions, PoolTransaction, TransactionPool,
 };
 use revm::context_interface::Block as _;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use tracing::{debug, trace, warn};
 
 mod config;
 pub use config::*;
 
+mod context;
+pub use context::{BestTransactionsIter, EthPayloadTransactions, PayloadBuildContext};
+
+mod inclusion;
+use inclusion::{executable_mandatory_transactions, MandatoryFirst};
+pub use inclusion::{InclusionConstraints, InclusionReport};
+
 pub mod validator;
 pub use validator::EthereumExecutionPayloadValidator;
 
-type BestTransactionsIter<Pool> = Box<
-    dyn BestTransactions<Item = Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>,
->;
-
 /// Ethereum payload builder
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig> {
+pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig, Txs = ()> {
     /// Client providing access to node state.
     client: Client,
     /// Transaction pool.
@@ -59,6 +61,8 @@ pub struct EthereumPayloadBuilder<Pool, Client, EvmConfig = EthEvmConfig> {
     evm_config: EvmConfig,
     /// Payload builder configuration.
     builder_config: EthereumBuilderConfig,
+    /// The type responsible for yielding the transactions of the payload.
+    best_transactions: Txs,
 }
 
 impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
@@ -69,16 +73,30 @@ impl<Pool, Client, EvmConfig> EthereumPayloadBuilder<Pool, Client, EvmConfig> {
         evm_config: EvmConfig,
         builder_config: EthereumBuilderConfig,
     ) -> Self {
-        Self { client, pool, evm_config, builder_config }
+        Self { client, pool, evm_config, builder_config, best_transactions: () }
+    }
+}
+
+impl<Pool, Client, EvmConfig, Txs> EthereumPayloadBuilder<Pool, Client, EvmConfig, Txs> {
+    /// Configures the type responsible for yielding the transactions that should be included in the
+    /// payload, see [`EthPayloadTransactions`].
+    pub fn with_transactions<T>(
+        self,
+        best_transactions: T,
+    ) -> EthereumPayloadBuilder<Pool, Client, EvmConfig, T> {
+        let Self { client, pool, evm_config, builder_config, .. } = self;
+        EthereumPayloadBuilder { client, pool, evm_config, builder_config, best_transactions }
     }
 }
 
 // Default implementation of [PayloadBuilder] for unit type
-impl<Pool, Client, EvmConfig> PayloadBuilder for EthereumPayloadBuilder<Pool, Client, EvmConfig>
+impl<Pool, Client, EvmConfig, Txs> PayloadBuilder
+    for EthereumPayloadBuilder<Pool, Client, EvmConfig, Txs>
 where
     EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks> + Clone,
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+    Txs: EthPayloadTransactions<Pool>,
 {
     type Attributes = EthPayloadBuilderAttributes;
     type BuiltPayload = EthBuiltPayload;
@@ -93,7 +111,7 @@ where
             self.pool.clone(),
             self.builder_config.clone(),
             args,
-            |attributes| self.pool.best_transactions_with_attributes(attributes),
+            |ctx| self.best_transactions.best_transactions(ctx),
         )
     }
 
@@ -120,7 +138,7 @@ where
             self.pool.clone(),
             self.builder_config.clone(),
             args,
-            |attributes| self.pool.best_transactions_with_attributes(attributes),
+            |ctx| self.best_transactions.best_transactions(ctx),
         )?
         .into_payload()
         .ok_or_else(|| PayloadBuilderError::MissingPayload)
@@ -132,6 +150,9 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// The transactions are yielded by `best_txs`, which is called with the [`PayloadBuildContext`] of
+/// the payload.
 #[inline]
 pub fn default_ethereum_payload<EvmConfig, Client, Pool, F>(
     evm_config: EvmConfig,
@@ -145,7 +166,7 @@ where
     EvmConfig: ConfigureEvm<Primitives = EthPrimitives, NextBlockEnvCtx = NextBlockEnvAttributes>,
     Client: StateProviderFactory + ChainSpecProvider<ChainSpec: EthereumHardforks>,
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
-    F: FnOnce(BestTransactionsAttributes) -> BestTransactionsIter<Pool>,
+    F: FnOnce(&PayloadBuildContext<'_, Pool>) -> BestTransactionsIter<Pool>,
 {
     let BuildArguments { mut cached_reads, config, cancel, best_payload } = args;
     let PayloadConfig { parent_header, attributes } = config;
@@ -176,11 +197,52 @@ where
     let mut cumulative_gas_used = 0;
     let block_gas_limit: u64 = builder.evm_mut().block().gas_limit;
     let base_fee = builder.evm_mut().block().basefee;
+    let blob_gas_price = builder.evm_mut().block().blob_gasprice();
+
+    let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
+    let max_blob_count =
+        blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
 
-    let mut best_txs = best_txs(BestTransactionsAttributes::new(
+    // mandatory transactions are executed first, if they are pending in the pool
+    let inclusion = &builder_config.inclusion;
+    let mut inclusion_report = InclusionReport::default();
+    let mut mandatory = Vec::new();
+    let mut mandatory_hashes = HashSet::new();
+    if !inclusion.mandatory_transactions().is_empty() {
+        let requested = inclusion.mandatory_transactions().iter().collect::<HashSet<_>>();
+        let pending =
+            pool.get_pending_transactions_with_predicate(|tx| requested.contains(tx.hash()));
+        mandatory_hashes = pending.iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
+        mandatory =
+            executable_mandatory_transactions(&pool, inclusion.mandatory_transactions(), &pending);
+        inclusion_report.mandatory_missing = inclusion
+            .mandatory_transactions()
+            .iter()
+            .filter(|hash| !mandatory_hashes.contains(*hash))
+            .copied()
+            .collect();
+    }
+
+    let ctx = PayloadBuildContext::new(
+        &parent_header,
+        &attributes,
+        block_gas_limit,
         base_fee,
-        builder.evm_mut().block().blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+        blob_gas_price,
+        max_blob_count,
+        state_provider.as_ref(),
+        &pool,
+    );
+    let mut filtered = Vec::new();
+    let mut best_txs =
+        MandatoryFirst::new(mandatory, best_txs(&ctx)).filter_transactions(|tx| {
+            let allowed = inclusion.is_transaction_allowed(tx);
+            if !allowed {
+                trace!(target: "payload_builder", tx=?tx.hash(), "skipping transaction excluded by inclusion constraints");
+                filtered.push(*tx.hash());
+            }
+            allowed
+        });
     let mut total_fees = U256::ZERO;
 
     builder.apply_pre_execution_changes().map_err(|err| {
@@ -194,10 +256,6 @@ where
 
     let mut block_blob_count = 0;
 
-    let blob_params = chain_spec.blob_params_at_timestamp(attributes.timestamp);
-    let max_blob_count =
-        blob_params.as_ref().map(|params| params.max_blob_count).unwrap_or_default();
-
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
@@ -317,6 +375,18 @@ where
         if let Some(sidecar) = blob_tx_sidecar {
             blob_sidecars.push_sidecar_variant(sidecar.as_ref().clone());
         }
+
+        if mandatory_hashes.contains(tx.hash()) {
+            inclusion_report.mandatory_included.push(*tx.hash());
+        }
+    }
+
+    // report how the inclusion constraints affected this attempt
+    drop(best_txs);
+    if !inclusion.is_empty() {
+        inclusion_report.filtered = filtered;
+        inclusion_report.record();
+        debug!(target: "payload_builder", id=%attributes.id, filtered=inclusion_report.filtered.len(), mandatory_included=inclusion_report.mandatory_included.len(), mandatory_missing=?inclusion_report.mandatory_missing, "applied inclusion constraints");
     }
 
     // check if we have a better block
@@ -457,12 +457,13 @@ mod tests {
     use crate::TransactionSigned;
     use alloy_eips::eip2718::Encodable2718;
     use alloy_primitives::{
-        address, b256, bloom, bytes, hex_literal::hex, Address, Bytes, Log, LogData,
+        address, b256, bloom, bytes, hex_literal::hex, logs_bloom, Address, Bytes, Log, LogData,
     };
     use alloy_rlp::Decodable;
     use reth_codecs::Compact;
     use reth_primitives_traits::proofs::{
         calculate_receipt_root, calculate_transaction_root, calculate_withdrawals_root,
+        IncrementalReceiptsRoot,
     };
 
     /// Ethereum full block.
@@ -671,4 +672,45 @@ mod tests {
             b256!("0xfe70ae4a136d98944951b2123859698d59ad251a381abc9960fa81cae3d0d4a0")
         );
     }
+
+    #[test]
+    fn incremental_receipts_root() {
+        let receipt = |index: usize| Receipt {
+            tx_type: if index % 2 == 0 { TxType::Legacy } else { TxType::Eip1559 },
+            success: index % 5 != 0,
+            cumulative_gas_used: 21_000 * (index as u64 + 1),
+            logs: (0..index % 3)
+                .map(|i| Log {
+                    address: Address::with_last_byte(i as u8),
+                    data: LogData::new_unchecked(
+                        vec![B256::with_last_byte(index as u8)],
+                        Default::default(),
+                    ),
+                })
+                .collect(),
+        };
+
+        let mut root = IncrementalReceiptsRoot::default();
+        for len in [0, 1, 2, 127, 128, 129, 300] {
+            let receipts = (0..len).map(receipt).collect::<Vec<_>>();
+            let expected = Receipt::calculate_receipt_root_no_memo(&receipts);
+            root.clear();
+            root.extend(&receipts);
+
+            assert_eq!(root.len(), len);
+            assert_eq!(root.logs_bloom(), logs_bloom(receipts.iter().flat_map(|r| r.logs())));
+            assert_eq!(root.root(), expected, "{len} receipts");
+
+            let mut from_fields = IncrementalReceiptsRoot::default();
+            for receipt in &receipts {
+                from_fields.push_fields(
+                    receipt.tx_type as u8,
+                    receipt.success,
+                    receipt.cumulative_gas_used,
+                    &receipt.logs,
+                );
+            }
+            assert_eq!(from_fields.root(), expected, "{len} receipts from fields");
+        }
+    }
 }
@@ -3,13 +3,13 @@
 use crate::{ConfigureEvm, Database, OnStateHook};
 use alloc::{boxed::Box, vec::Vec};
 use alloy_consensus::{BlockHeader, Header};
-use alloy_eips::eip2718::WithEncoded;
+use alloy_eips::{eip2718::WithEncoded, Typed2718};
 pub use alloy_evm::block::{BlockExecutor, BlockExecutorFactory};
 use alloy_evm::{
     block::{CommitChanges, ExecutableTx},
     Evm, EvmEnv, EvmFactory,
 };
-use alloy_primitives::B256;
+use alloy_primitives::{Bloom, Log, B256};
 use core::fmt::Debug;
 pub use reth_execution_errors::{
     BlockExecutionError, BlockValidationError, InternalBlockExecutionError,
@@ -17,7 +17,8 @@ pub use reth_execution_errors::{
 use reth_execution_types::BlockExecutionResult;
 pub use reth_execution_types::{BlockExecutionOutput, ExecutionOutcome};
 use reth_primitives_traits::{
-    Block, HeaderTy, NodePrimitives, ReceiptTy, Recovered, RecoveredBlock, SealedHeader, TxTy,
+    proofs::IncrementalReceiptsRoot, Block, HeaderTy, NodePrimitives, Receipt, ReceiptTy,
+    Recovered, RecoveredBlock, SealedHeader, TxTy,
 };
 use reth_storage_api::StateProvider;
 pub use reth_storage_errors::provider::ProviderError;
@@ -156,6 +157,8 @@ pub struct ExecuteOutput<R> {
 /// - `bundle_state`: Accumulated state changes from all transactions
 /// - `state_provider`: Access to the current state for additional lookups
 /// - `state_root`: The calculated state root after all changes
+/// - `receipts_root` and `logs_bloom`: The receipts root and logs bloom of the receipts, computed
+///   while the transactions were executed
 ///
 /// # Usage
 ///
@@ -172,6 +175,8 @@ pub struct ExecuteOutput<R> {
 ///     bundle_state: &state_changes,
 ///     state_provider: &state,
 ///     state_root: calculated_root,
+///     receipts_root,
+///     logs_bloom,
 /// };
 ///
 /// let block = assembler.assemble_block(input)?;
@@ -198,6 +203,14 @@ pub struct BlockAssemblerInput<'a, 'b, F: BlockExecutorFactory, H = Header> {
     pub state_provider: &'b dyn StateProvider,
     /// State root for this block.
     pub state_root: B256,
+    /// Receipts root of the receipts in [`Self::output`].
+    ///
+    /// This is computed incrementally while the transactions are executed, with the receipts
+    /// encoded like Ethereum receipts. Assemblers of chains with receipts that are encoded
+    /// differently have to compute the root themselves.
+    pub receipts_root: B256,
+    /// Logs bloom of the receipts in [`Self::output`].
+    pub logs_bloom: Bloom,
 }
 
 /// A type that knows how to assemble a block from execution results.
@@ -233,6 +246,8 @@ pub struct BlockAssemblerInput<'a, 'b, F: BlockExecutorFactory, H = Header> {
 ///     bundle_state,      // All state changes
 ///     state_provider,    // For additional lookups if needed
 ///     state_root,        // Computed state root
+///     receipts_root,     // Receipts root computed during execution
+///     logs_bloom,        // Logs bloom of the receipts
 /// })?;
 /// ```
 ///
@@ -353,6 +368,49 @@ where
     pub(crate) ctx: F::ExecutionCtx<'a>,
     pub(crate) parent: &'a SealedHeader<HeaderTy<N>>,
     pub(crate) assembler: Builder,
+    /// The receipts root of the executed transactions, updated with every transaction.
+    pub(crate) receipts_root: ReceiptsRootTracker,
+}
+
+/// Tracks the receipts root of the transactions executed by a [`BlockBuilder`].
+///
+/// [`BlockExecutor`] doesn't expose the receipts it builds, so the Ethereum receipt of every
+/// committed transaction is derived from its execution result and added to the receipts trie right
+/// away, instead of hashing all receipts once the block is sealed.
+#[derive(Debug, Default)]
+pub(crate) struct ReceiptsRootTracker {
+    /// The receipts root of the committed transactions.
+    root: IncrementalReceiptsRoot,
+    /// The gas used by the committed transactions.
+    cumulative_gas_used: u64,
+    /// Whether the transaction that is being committed succeeded, and its logs.
+    pending: Option<(bool, Vec<Log>)>,
+}
+
+impl ReceiptsRootTracker {
+    /// Records the result of a transaction that is going to be committed.
+    fn on_result<H>(&mut self, result: &ExecutionResult<H>) {
+        self.pending = Some((result.is_success(), result.logs().to_vec()));
+    }
+
+    /// Adds the receipt of the committed transaction to the trie.
+    fn on_commit(&mut self, ty: u8, gas_used: u64) {
+        let Some((success, logs)) = self.pending.take() else { return };
+        self.cumulative_gas_used += gas_used;
+        self.root.push_fields(ty, success, self.cumulative_gas_used, &logs);
+    }
+
+    /// Returns the receipts root and logs bloom of the given receipts of the block.
+    ///
+    /// The tracked root is only used if it covers all receipts, otherwise, e.g. if transactions
+    /// were executed on the executor directly, the root is computed from the receipts.
+    fn finish<R: Receipt>(mut self, receipts: &[R]) -> (B256, Bloom) {
+        if self.root.len() != receipts.len() {
+            self.root.clear();
+            self.root.extend(receipts);
+        }
+        (self.root.root(), self.root.logs_bloom())
+    }
 }
 
 /// Conversions for executable transactions.
@@ -417,10 +475,20 @@ where
             &ExecutionResult<<<Self::Executor as BlockExecutor>::Evm as Evm>::HaltReason>,
         ) -> CommitChanges,
     ) -> Result<Option<u64>, BlockExecutionError> {
-        if let Some(gas_used) =
-            self.executor.execute_transaction_with_commit_condition(tx.as_executable(), f)?
-        {
-            self.transactions.push(tx.into_recovered());
+        let receipts_root = &mut self.receipts_root;
+        if let Some(gas_used) = self.executor.execute_transaction_with_commit_condition(
+            tx.as_executable(),
+            |result| {
+                let commit = f(result);
+                if matches!(commit, CommitChanges::Yes) {
+                    receipts_root.on_result(result);
+                }
+                commit
+            },
+        )? {
+            let tx = tx.into_recovered();
+            self.receipts_root.on_commit(tx.ty(), gas_used);
+            self.transactions.push(tx);
             Ok(Some(gas_used))
         } else {
             Ok(None)
@@ -432,6 +500,7 @@ where
         state: impl StateProvider,
     ) -> Result<BlockBuilderOutcome<N>, BlockExecutionError> {
         let (evm, result) = self.executor.finish()?;
+        let (receipts_root, logs_bloom) = self.receipts_root.finish(&result.receipts);
         let (db, evm_env) = evm.finish();
 
         // merge all transitions into bundle state
@@ -455,6 +524,8 @@ where
             bundle_state: &db.bundle_state,
             state_provider: &state,
             state_root,
+            receipts_root,
+            logs_bloom,
         })?;
 
         let block = RecoveredBlock::new_unhashed(block, senders);
@@ -0,0 +1,243 @@
+//! Metering of precompile calls during block execution.
+//!
+//! [`MeteredPrecompile`] wraps a precompile and counts its invocations and the gas they consumed
+//! in a [`PrecompileCounter`]. After a block was executed, the counters are drained into
+//! [`PrecompileStats`], which can be kept in a [`PrecompileStatsStore`] for the most recent blocks.
+
+use crate::precompiles::{DynPrecompile, Precompile, PrecompileInput};
+use alloy_primitives::{Address, B256};
+use parking_lot::RwLock;
+use revm::precompile::PrecompileResult;
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// The default number of blocks a [`PrecompileStatsStore`] keeps the stats of.
+pub const DEFAULT_PRECOMPILE_STATS_BLOCKS: usize = 10_000;
+
+/// The number of calls of a precompile and the gas they consumed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PrecompileUsage {
+    /// The number of calls.
+    pub calls: u64,
+    /// The total gas used by all successful calls.
+    pub gas_used: u64,
+}
+
+impl PrecompileUsage {
+    /// Returns `true` if the precompile wasn't called.
+    pub const fn is_empty(&self) -> bool {
+        self.calls == 0
+    }
+
+    /// Adds the `other` usage to this usage.
+    pub const fn add(&mut self, other: Self) {
+        self.calls = self.calls.saturating_add(other.calls);
+        self.gas_used = self.gas_used.saturating_add(other.gas_used);
+    }
+}
+
+/// The usage of all precompiles that were called, e.g. in a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrecompileStats {
+    usage: BTreeMap<Address, PrecompileUsage>,
+}
+
+impl PrecompileStats {
+    /// Returns `true` if no precompile was called.
+    pub fn is_empty(&self) -> bool {
+        self.usage.is_empty()
+    }
+
+    /// Returns the usage of the precompile at the given address, if it was called.
+    pub fn get(&self, address: &Address) -> Option<&PrecompileUsage> {
+        self.usage.get(address)
+    }
+
+    /// Returns an iterator over the usage of all called precompiles, ordered by address.
+    pub fn iter(&self) -> impl Iterator<Item = (&Address, &PrecompileUsage)> + '_ {
+        self.usage.iter()
+    }
+
+    /// Adds the usage of the precompile at the given address.
+    pub fn record(&mut self, address: Address, usage: PrecompileUsage) {
+        if !usage.is_empty() {
+            self.usage.entry(address).or_default().add(usage);
+        }
+    }
+
+    /// Adds the usage of all precompiles of `other`.
+    pub fn merge(&mut self, other: &Self) {
+        for (address, usage) in other.iter() {
+            self.record(*address, *usage);
+        }
+    }
+}
+
+/// Lock-free counters of the calls of a single precompile.
+#[derive(Debug, Default)]
+pub struct PrecompileCounter {
+    calls: AtomicU64,
+    gas_used: AtomicU64,
+}
+
+impl PrecompileCounter {
+    /// Records a call of the precompile.
+    pub fn record(&self, gas_used: u64) {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.gas_used.fetch_add(gas_used, Ordering::Relaxed);
+    }
+
+    /// Returns the usage recorded since the last call and resets the counters.
+    pub fn take(&self) -> PrecompileUsage {
+        PrecompileUsage {
+            calls: self.calls.swap(0, Ordering::Relaxed),
+            gas_used: self.gas_used.swap(0, Ordering::Relaxed),
+        }
+    }
+}
+
+/// A precompile that records every call in a [`PrecompileCounter`].
+#[derive(Debug)]
+pub struct MeteredPrecompile {
+    /// The precompile.
+    precompile: DynPrecompile,
+    /// The counter of the calls.
+    counter: Arc<PrecompileCounter>,
+}
+
+impl MeteredPrecompile {
+    /// Wraps the given precompile so that all its calls are recorded in the `counter`.
+    pub fn wrap(precompile: DynPrecompile, counter: Arc<PrecompileCounter>) -> DynPrecompile {
+        let wrapped = Self { precompile, counter };
+        (move |input: PrecompileInput<'_>| -> PrecompileResult { wrapped.call(input) }).into()
+    }
+}
+
+impl Precompile for MeteredPrecompile {
+    fn call(&self, input: PrecompileInput<'_>) -> PrecompileResult {
+        let result = self.precompile.call(input);
+        self.counter.record(result.as_ref().map_or(0, |output| output.gas_used));
+        result
+    }
+}
+
+/// Keeps the [`PrecompileStats`] of the most recently executed blocks, by block hash.
+///
+/// Once the capacity is reached, the stats of the oldest inserted block are evicted. The store is
+/// cheap to clone and can be shared between the block executor and e.g. the RPC.
+#[derive(Debug, Clone)]
+pub struct PrecompileStatsStore {
+    inner: Arc<RwLock<PrecompileStatsStoreInner>>,
+}
+
+#[derive(Debug)]
+struct PrecompileStatsStoreInner {
+    capacity: usize,
+    blocks: HashMap<B256, PrecompileStats>,
+    order: VecDeque<B256>,
+}
+
+impl PrecompileStatsStore {
+    /// Creates a new store that keeps the stats of at most `capacity` blocks.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(PrecompileStatsStoreInner {
+                capacity,
+                blocks: HashMap::new(),
+                order: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Returns the maximum number of blocks the store keeps the stats of.
+    pub fn capacity(&self) -> usize {
+        self.inner.read().capacity
+    }
+
+    /// Inserts the stats of the block with the given hash, evicting the oldest block if the store
+    /// is full.
+    pub fn insert(&self, block_hash: B256, stats: PrecompileStats) {
+        let mut inner = self.inner.write();
+        if inner.capacity == 0 {
+            return
+        }
+        if inner.blocks.insert(block_hash, stats).is_none() {
+            inner.order.push_back(block_hash);
+        }
+        while inner.order.len() > inner.capacity {
+            if let Some(evicted) = inner.order.pop_front() {
+                inner.blocks.remove(&evicted);
+            }
+        }
+    }
+
+    /// Returns the stats of the block with the given hash, if they are stored.
+    pub fn get(&self, block_hash: &B256) -> Option<PrecompileStats> {
+        self.inner.read().blocks.get(block_hash).cloned()
+    }
+
+    /// Sums up the stats of the given blocks.
+    ///
+    /// Returns the stats and the number of blocks that had stats in the store.
+    pub fn aggregate<'a>(
+        &self,
+        block_hashes: impl IntoIterator<Item = &'a B256>,
+    ) -> (PrecompileStats, usize) {
+        let inner = self.inner.read();
+        let mut stats = PrecompileStats::default();
+        let mut found = 0;
+        for stored in block_hashes.into_iter().filter_map(|hash| inner.blocks.get(hash)) {
+            stats.merge(stored);
+            found += 1;
+        }
+        (stats, found)
+    }
+}
+
+impl Default for PrecompileStatsStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_PRECOMPILE_STATS_BLOCKS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const ECRECOVER: Address = address!("0x0000000000000000000000000000000000000001");
+
+    fn stats(calls: u64, gas_used: u64) -> PrecompileStats {
+        let mut stats = PrecompileStats::default();
+        stats.record(ECRECOVER, PrecompileUsage { calls, gas_used });
+        stats
+    }
+
+    #[test]
+    fn counter_take_resets() {
+        let counter = PrecompileCounter::default();
+        counter.record(3000);
+        counter.record(3000);
+        assert_eq!(counter.take(), PrecompileUsage { calls: 2, gas_used: 6000 });
+        assert!(counter.take().is_empty());
+    }
+
+    #[test]
+    fn store_evicts_oldest_block() {
+        let store = PrecompileStatsStore::new(2);
+        let hashes = [B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3)];
+        for (i, hash) in hashes.iter().enumerate() {
+            store.insert(*hash, stats(1, 1000 * (i as u64 + 1)));
+        }
+
+        assert!(store.get(&hashes[0]).is_none());
+        let (aggregated, found) = store.aggregate(&hashes);
+        assert_eq!(found, 2);
+        assert_eq!(aggregated.get(&ECRECOVER), Some(&PrecompileUsage { calls: 2, gas_used: 5000 }));
+    }
+}
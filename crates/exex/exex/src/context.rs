@@ -5,6 +5,7 @@ use reth_node_api::{FullNodeComponents, NodePrimitives, NodeTypes, PrimitivesTy}
 use reth_node_core::node_config::NodeConfig;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_provider::BlockReader;
+use reth_prune_types::PrunePin;
 use reth_tasks::TaskExecutor;
 use std::fmt::Debug;
 use tokio::sync::mpsc::{error::SendError, UnboundedSender};
@@ -129,6 +130,22 @@ where
     ) -> Result<(), SendError<BlockNumHash>> {
         self.events.send(ExExEvent::FinishedHeight(height)).map_err(|_| SendError(height))
     }
+
+    /// Sends an [`ExExEvent::PinPruneSegment`] to the ExEx task manager, preventing the pruner
+    /// from pruning the pinned block range of the segment.
+    ///
+    /// Returns an error if the channel was closed (ExEx task manager panicked).
+    pub fn pin_prune_segment(&self, pin: PrunePin) -> Result<(), SendError<PrunePin>> {
+        self.events.send(ExExEvent::PinPruneSegment(pin)).map_err(|_| SendError(pin))
+    }
+
+    /// Sends an [`ExExEvent::UnpinPruneSegment`] to the ExEx task manager, releasing a pin
+    /// previously acquired with [`Self::pin_prune_segment`].
+    ///
+    /// Returns an error if the channel was closed (ExEx task manager panicked).
+    pub fn unpin_prune_segment(&self, pin: PrunePin) -> Result<(), SendError<PrunePin>> {
+        self.events.send(ExExEvent::UnpinPruneSegment(pin)).map_err(|_| SendError(pin))
+    }
 }
 
 #[cfg(test)]
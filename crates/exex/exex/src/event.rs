@@ -1,4 +1,5 @@
 use alloy_eips::BlockNumHash;
+use reth_prune_types::PrunePin;
 
 /// Events emitted by an `ExEx`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -10,4 +11,11 @@ pub enum ExExEvent {
     ///
     /// On reorgs, it's possible for the height to go down.
     FinishedHeight(BlockNumHash),
+    /// Pins a block range of a prune segment, e.g. receipts needed for backfill.
+    ///
+    /// The pruner doesn't prune the pinned range of the segment until the `ExEx` releases it with
+    /// [`ExExEvent::UnpinPruneSegment`], regardless of the finished height.
+    PinPruneSegment(PrunePin),
+    /// Releases a pin previously acquired with [`ExExEvent::PinPruneSegment`].
+    UnpinPruneSegment(PrunePin),
 }
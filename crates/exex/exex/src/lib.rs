@@ -106,6 +106,8 @@ pub use manager::*;
 mod notifications;
 pub use notifications::*;
 
+pub mod trace_index;
+
 mod wal;
 pub use wal::*;
 
@@ -13,6 +13,7 @@ use reth_metrics::{metrics::Counter, Metrics};
 use reth_node_api::NodePrimitives;
 use reth_primitives_traits::SealedHeader;
 use reth_provider::HeaderProvider;
+use reth_prune_types::PrunePins;
 use reth_tracing::tracing::{debug, warn};
 use std::{
     collections::VecDeque,
@@ -243,6 +244,9 @@ pub struct ExExManager<P, N: NodePrimitives> {
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Sender<FinishedExExHeight>,
 
+    /// The prune segment pins of all `ExEx`'s.
+    prune_pins: watch::Sender<PrunePins>,
+
     /// Write-Ahead Log for the [`ExExNotification`]s.
     wal: Wal<N>,
     /// A stream of finalized headers.
@@ -282,6 +286,8 @@ where
             FinishedExExHeight::NotReady
         });
 
+        let (prune_pins_tx, prune_pins_rx) = watch::channel(PrunePins::default());
+
         let current_capacity = Arc::new(AtomicUsize::new(max_capacity));
 
         let metrics = ExExManagerMetrics::default();
@@ -303,6 +309,7 @@ where
 
             is_ready: is_ready_tx,
             finished_height: finished_height_tx,
+            prune_pins: prune_pins_tx,
 
             wal,
             finalized_header_stream,
@@ -314,6 +321,7 @@ where
                 is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
                 current_capacity,
                 finished_height: finished_height_rx,
+                prune_pins: prune_pins_rx,
             },
             metrics,
         }
@@ -448,6 +456,14 @@ where
                 exex.metrics.events_sent_total.increment(1);
                 match event {
                     ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::PinPruneSegment(pin) => {
+                        this.prune_pins.send_if_modified(|pins| pins.insert(exex.id.clone(), pin));
+                    }
+                    ExExEvent::UnpinPruneSegment(pin) => {
+                        if !this.prune_pins.send_if_modified(|pins| pins.remove(&exex.id, &pin)) {
+                            warn!(target: "exex::manager", exex_id = %exex.id, ?pin, "ExEx released a prune segment pin it doesn't hold");
+                        }
+                    }
                 }
             }
         }
@@ -551,6 +567,8 @@ pub struct ExExManagerHandle<N: NodePrimitives = EthPrimitives> {
     current_capacity: Arc<AtomicUsize>,
     /// The finished height of all `ExEx`'s.
     finished_height: watch::Receiver<FinishedExExHeight>,
+    /// The prune segment pins of all `ExEx`'s.
+    prune_pins: watch::Receiver<PrunePins>,
 }
 
 impl<N: NodePrimitives> ExExManagerHandle<N> {
@@ -563,6 +581,7 @@ impl<N: NodePrimitives> ExExManagerHandle<N> {
         let (exex_tx, _) = mpsc::unbounded_channel();
         let (_, is_ready_rx) = watch::channel(true);
         let (_, finished_height_rx) = watch::channel(FinishedExExHeight::NoExExs);
+        let (_, prune_pins_rx) = watch::channel(PrunePins::default());
 
         Self {
             exex_tx,
@@ -571,6 +590,7 @@ impl<N: NodePrimitives> ExExManagerHandle<N> {
             is_ready: ReusableBoxFuture::new(make_wait_future(is_ready_rx)),
             current_capacity: Arc::new(AtomicUsize::new(0)),
             finished_height: finished_height_rx,
+            prune_pins: prune_pins_rx,
         }
     }
 
@@ -621,6 +641,11 @@ impl<N: NodePrimitives> ExExManagerHandle<N> {
         self.finished_height.clone()
     }
 
+    /// The prune segment pins of all `ExEx`'s.
+    pub fn prune_pins(&self) -> watch::Receiver<PrunePins> {
+        self.prune_pins.clone()
+    }
+
     /// Wait until the manager is ready for new notifications.
     pub async fn ready(&mut self) {
         poll_fn(|cx| self.poll_ready(cx)).await
@@ -651,6 +676,7 @@ impl<N: NodePrimitives> Clone for ExExManagerHandle<N> {
             is_ready: ReusableBoxFuture::new(make_wait_future(self.is_ready_receiver.clone())),
             current_capacity: self.current_capacity.clone(),
             finished_height: self.finished_height.clone(),
+            prune_pins: self.prune_pins.clone(),
         }
     }
 }
@@ -669,6 +695,7 @@ mod tests {
         providers::BlockchainProvider, test_utils::create_test_provider_factory, BlockReader,
         BlockWriter, Chain, DatabaseProviderFactory, StorageLocation, TransactionVariant,
     };
+    use reth_prune_types::{PrunePin, PruneSegment};
     use reth_testing_utils::generators::{self, random_block, BlockParams};
 
     fn empty_finalized_header_stream() -> ForkChoiceStream<SealedHeader> {
@@ -906,6 +933,44 @@ mod tests {
         assert_eq!(finished_height, FinishedExExHeight::Height(42));
     }
 
+    #[tokio::test]
+    async fn test_updates_prune_pins() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let (exex_handle, event_tx, mut _notification_rx) = ExExHandle::new(
+            "test_exex".to_string(),
+            Default::default(),
+            (),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+
+        let receipts = PrunePin::new(PruneSegment::Receipts, 10..=20);
+        let transactions = PrunePin::new(PruneSegment::Transactions, 5..=20);
+        event_tx.send(ExExEvent::PinPruneSegment(receipts)).unwrap();
+        event_tx.send(ExExEvent::PinPruneSegment(transactions)).unwrap();
+        event_tx.send(ExExEvent::UnpinPruneSegment(transactions)).unwrap();
+
+        let exex_manager = ExExManager::new(
+            create_test_provider_factory(),
+            vec![exex_handle],
+            10,
+            wal,
+            empty_finalized_header_stream(),
+        );
+        let receiver = exex_manager.handle().prune_pins();
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+        let mut pinned_manager = std::pin::pin!(exex_manager);
+        let _ = pinned_manager.as_mut().poll(&mut cx);
+
+        let pins = receiver.borrow().clone();
+        assert_eq!(pins.iter().collect::<Vec<_>>(), vec![("test_exex", &receipts)]);
+        assert_eq!(pins.prune_target_block(PruneSegment::Receipts, 100), Some(9));
+        assert_eq!(pins.prune_target_block(PruneSegment::Transactions, 100), Some(100));
+    }
+
     #[tokio::test]
     async fn test_updates_block_height_lower() {
         let temp_dir = tempfile::tempdir().unwrap();
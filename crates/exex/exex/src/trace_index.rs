@@ -0,0 +1,279 @@
+//! An `ExEx` that maintains an index of the blocks in which an address appears in a call trace.
+
+use crate::{ExExContext, ExExEvent, ExExHead};
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Address, BlockNumber, U256,
+};
+use futures::TryStreamExt;
+use parking_lot::RwLock;
+use reth_evm::{execute::BlockExecutor, ConfigureEvm};
+use reth_node_api::{BlockBody as _, BlockTy, FullNodeComponents};
+use reth_primitives_traits::RecoveredBlock;
+use reth_provider::{BlockHashReader, ProviderResult, StateProviderFactory, TraceIndexProvider};
+use reth_revm::{
+    database::StateProviderDatabase,
+    inspector::Inspector,
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    State,
+};
+use reth_tracing::tracing::{debug, info, warn};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+    sync::Arc,
+};
+
+/// In-memory index of the blocks in which an address appears in a call trace.
+///
+/// The index covers a contiguous range of blocks. It is shared between the [`TraceIndexExEx`] that
+/// populates it and the consumers that query it through [`TraceIndexProvider`].
+#[derive(Debug, Clone, Default)]
+pub struct TraceIndex {
+    inner: Arc<RwLock<TraceIndexInner>>,
+}
+
+#[derive(Debug, Default)]
+struct TraceIndexInner {
+    /// The contiguous range of indexed blocks.
+    range: Option<RangeInclusive<BlockNumber>>,
+    /// Blocks in which an address appears, keyed by address.
+    addresses: HashMap<Address, BTreeSet<BlockNumber>>,
+    /// Addresses that appear in a block, keyed by block number. Used for unwinding.
+    blocks: BTreeMap<BlockNumber, Vec<Address>>,
+}
+
+impl TraceIndex {
+    /// Returns the range of indexed blocks, if any block has been indexed yet.
+    pub fn indexed_range(&self) -> Option<RangeInclusive<BlockNumber>> {
+        self.inner.read().range.clone()
+    }
+
+    /// Records the addresses that appear in the traces of the given block.
+    ///
+    /// The block is expected to be the successor of the last indexed block. Otherwise, the index
+    /// is cleared and starts over from the given block.
+    fn insert_block(&self, block_number: BlockNumber, addresses: HashSet<Address>) {
+        let mut inner = self.inner.write();
+        let start = match inner.range.clone() {
+            Some(range) if *range.end() + 1 == block_number => *range.start(),
+            Some(range) => {
+                warn!(
+                    target: "exex::trace_index",
+                    ?range,
+                    block_number,
+                    "Non-contiguous block, resetting trace index"
+                );
+                *inner = TraceIndexInner::default();
+                block_number
+            }
+            None => block_number,
+        };
+
+        for address in &addresses {
+            inner.addresses.entry(*address).or_default().insert(block_number);
+        }
+        inner.blocks.insert(block_number, addresses.into_iter().collect());
+        inner.range = Some(start..=block_number);
+    }
+
+    /// Removes all blocks starting from the given block number from the index.
+    fn unwind(&self, from: BlockNumber) {
+        let mut inner = self.inner.write();
+        let removed = inner.blocks.split_off(&from);
+        for (block_number, addresses) in removed {
+            for address in addresses {
+                if let Some(blocks) = inner.addresses.get_mut(&address) {
+                    blocks.remove(&block_number);
+                    if blocks.is_empty() {
+                        inner.addresses.remove(&address);
+                    }
+                }
+            }
+        }
+        inner.range = inner
+            .range
+            .clone()
+            .filter(|range| *range.start() < from)
+            .map(|range| *range.start()..=from - 1);
+    }
+}
+
+impl TraceIndexProvider for TraceIndex {
+    fn blocks_with_addresses(
+        &self,
+        addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<Vec<BlockNumber>>> {
+        let inner = self.inner.read();
+        let Some(indexed) = &inner.range else { return Ok(None) };
+        if !indexed.contains(range.start()) || !indexed.contains(range.end()) {
+            return Ok(None)
+        }
+
+        let mut blocks = BTreeSet::new();
+        for address in addresses {
+            if let Some(address_blocks) = inner.addresses.get(address) {
+                blocks.extend(address_blocks.range(range.clone()));
+            }
+        }
+        Ok(Some(blocks.into_iter().collect()))
+    }
+}
+
+/// An `ExEx` that re-executes committed blocks with an inspector to populate a [`TraceIndex`].
+///
+/// Every address that appears as the sender or recipient of a call, create or selfdestruct trace,
+/// or as the recipient of a block reward, is recorded.
+#[derive(Debug)]
+pub struct TraceIndexExEx<Node: FullNodeComponents> {
+    ctx: ExExContext<Node>,
+    index: TraceIndex,
+    start_block: BlockNumber,
+}
+
+impl<Node: FullNodeComponents> TraceIndexExEx<Node> {
+    /// Creates a new [`TraceIndexExEx`] that indexes all blocks starting from `start_block`.
+    ///
+    /// Blocks between `start_block` and the current head are backfilled before new blocks are
+    /// indexed.
+    pub fn new(
+        mut ctx: ExExContext<Node>,
+        index: TraceIndex,
+        start_block: BlockNumber,
+    ) -> eyre::Result<Self> {
+        // The genesis block can't be delivered through notifications and has no traces
+        if start_block == 0 {
+            index.insert_block(0, HashSet::default());
+        }
+
+        let head_number = start_block.saturating_sub(1);
+        if let Some(head_hash) = ctx.provider().block_hash(head_number)? {
+            ctx.set_notifications_with_head(ExExHead {
+                block: BlockNumHash::new(head_number, head_hash),
+            });
+        }
+
+        Ok(Self { ctx, index, start_block })
+    }
+
+    /// Runs the `ExEx` until the notification stream is exhausted.
+    pub async fn start(mut self) -> eyre::Result<()> {
+        info!(target: "exex::trace_index", start_block = self.start_block, "Starting trace index");
+
+        while let Some(notification) = self.ctx.notifications.try_next().await? {
+            if let Some(reverted) = notification.reverted_chain() {
+                self.index.unwind(reverted.first().number());
+            }
+
+            if let Some(committed) = notification.committed_chain() {
+                for block in committed.blocks_iter() {
+                    if block.number() < self.start_block {
+                        continue
+                    }
+                    let addresses = self.trace_addresses(block)?;
+                    self.index.insert_block(block.number(), addresses);
+                }
+                debug!(target: "exex::trace_index", range = ?committed.range(), "Indexed blocks");
+                self.ctx.events.send(ExExEvent::FinishedHeight(committed.tip().num_hash()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-executes the block on top of its parent state and returns all addresses that appear in
+    /// its traces.
+    fn trace_addresses(
+        &self,
+        block: &RecoveredBlock<BlockTy<Node::Types>>,
+    ) -> eyre::Result<HashSet<Address>> {
+        let state_provider = self.ctx.provider().state_by_block_hash(block.parent_hash())?;
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(state_provider))
+            .with_bundle_update()
+            .without_state_clear()
+            .build();
+
+        let mut inspector = TraceAddressInspector::default();
+        // Block and ommer rewards are traced with the beneficiary as the recipient
+        inspector.addresses.insert(block.beneficiary());
+        if let Some(ommers) = block.body().ommers() {
+            inspector.addresses.extend(ommers.iter().map(|ommer| ommer.beneficiary()));
+        }
+
+        let evm_config = self.ctx.evm_config();
+        let evm = evm_config.evm_with_env_and_inspector(
+            &mut db,
+            evm_config.evm_env(block.header()),
+            &mut inspector,
+        );
+        let ctx = evm_config.context_for_block(block.sealed_block());
+        evm_config.create_executor(evm, ctx).execute_block(block.transactions_recovered())?;
+
+        Ok(inspector.addresses)
+    }
+}
+
+/// An [`Inspector`] that collects the addresses of all senders and recipients of call traces.
+#[derive(Debug, Default)]
+struct TraceAddressInspector {
+    addresses: HashSet<Address>,
+}
+
+impl<CTX> Inspector<CTX> for TraceAddressInspector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        self.addresses.extend([inputs.caller, inputs.target_address, inputs.bytecode_address]);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut CTX,
+        inputs: &CreateInputs,
+        outcome: &mut CreateOutcome,
+    ) {
+        self.addresses.insert(inputs.caller);
+        self.addresses.extend(outcome.address);
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: U256) {
+        self.addresses.extend([contract, target]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_and_unwind() {
+        let index = TraceIndex::default();
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+
+        index.insert_block(1, HashSet::from_iter([a]));
+        index.insert_block(2, HashSet::from_iter([b]));
+        index.insert_block(3, HashSet::from_iter([a, b]));
+        assert_eq!(index.indexed_range(), Some(1..=3));
+
+        assert_eq!(index.blocks_with_addresses(&[a], 1..=3).unwrap(), Some(vec![1, 3]));
+        assert_eq!(index.blocks_with_addresses(&[a, b], 2..=3).unwrap(), Some(vec![2, 3]));
+        // range is not fully covered
+        assert_eq!(index.blocks_with_addresses(&[a], 0..=3).unwrap(), None);
+        assert_eq!(index.blocks_with_addresses(&[a], 1..=4).unwrap(), None);
+
+        index.unwind(3);
+        assert_eq!(index.indexed_range(), Some(1..=2));
+        assert_eq!(index.blocks_with_addresses(&[a, b], 1..=2).unwrap(), Some(vec![1, 2]));
+
+        // a gap resets the index
+        index.insert_block(5, HashSet::from_iter([b]));
+        assert_eq!(index.indexed_range(), Some(5..=5));
+        assert_eq!(index.blocks_with_addresses(&[a], 5..=5).unwrap(), Some(vec![]));
+
+        index.unwind(5);
+        assert_eq!(index.indexed_range(), None);
+    }
+}
@@ -175,6 +175,14 @@ impl<B: FullBlock> FileClient<B> {
         self.bodies.len()
     }
 
+    /// Returns the sealed header and the body of the block with the given number, if the client
+    /// has both.
+    pub fn block_parts(&self, number: BlockNumber) -> Option<(SealedHeader<B::Header>, &B::Body)> {
+        let header = SealedHeader::seal_slow(self.headers.get(&number)?.clone());
+        let body = self.bodies.get(&header.hash())?;
+        Some((header, body))
+    }
+
     /// Returns an iterator over headers in the client.
     pub fn headers_iter(&self) -> impl Iterator<Item = &B::Header> {
         self.headers.values()
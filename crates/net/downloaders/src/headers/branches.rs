@@ -0,0 +1,144 @@
+//! Cache of headers of recently targeted branches.
+
+use alloy_primitives::B256;
+use reth_primitives_traits::{BlockHeader, SealedHeader};
+use std::collections::{HashMap, VecDeque};
+
+/// A bounded cache of validated headers, keyed by hash.
+///
+/// When the sync target flips between tips of competing branches (e.g. due to an unstable
+/// forkchoice), the headers that were already downloaded for the previous tip are kept here, so
+/// they don't need to be requested again once that tip becomes the sync target again.
+///
+/// The cache also tracks the tips of these alternative branches. Once the capacity is reached,
+/// the oldest inserted headers are evicted first.
+#[derive(Debug)]
+pub(crate) struct HeaderBranches<H> {
+    /// The cached headers.
+    headers: HashMap<B256, SealedHeader<H>>,
+    /// The hashes of the cached headers, in insertion order.
+    order: VecDeque<B256>,
+    /// The maximum number of cached headers.
+    max_headers: usize,
+    /// The tips of the tracked alternative branches, oldest first.
+    tips: VecDeque<B256>,
+    /// The maximum number of tracked alternative branches.
+    max_tips: usize,
+}
+
+impl<H: BlockHeader> HeaderBranches<H> {
+    /// Creates a new cache with the given limits.
+    pub(crate) fn new(max_headers: usize, max_tips: usize) -> Self {
+        Self {
+            headers: HashMap::default(),
+            order: VecDeque::default(),
+            max_headers,
+            tips: VecDeque::default(),
+            max_tips,
+        }
+    }
+
+    /// Returns the number of cached headers.
+    pub(crate) fn len(&self) -> usize {
+        self.headers.len()
+    }
+
+    /// Returns `true` if the cache can't hold any more headers without evicting.
+    pub(crate) fn is_full(&self) -> bool {
+        self.headers.len() >= self.max_headers
+    }
+
+    /// Returns `true` if the header with the given hash is cached.
+    pub(crate) fn contains(&self, hash: &B256) -> bool {
+        self.headers.contains_key(hash)
+    }
+
+    /// Returns the cached header with the given hash.
+    pub(crate) fn get(&self, hash: &B256) -> Option<&SealedHeader<H>> {
+        self.headers.get(hash)
+    }
+
+    /// Inserts a validated header, evicting the oldest headers if the cache is full.
+    pub(crate) fn insert(&mut self, header: SealedHeader<H>) {
+        if self.max_headers == 0 {
+            return
+        }
+        let hash = header.hash();
+        if self.headers.insert(hash, header).is_none() {
+            self.order.push_back(hash);
+        }
+        while self.order.len() > self.max_headers {
+            if let Some(evicted) = self.order.pop_front() {
+                self.headers.remove(&evicted);
+            }
+        }
+    }
+
+    /// Returns the lowest cached ancestor of the header with the given hash, following the parent
+    /// hashes of cached headers.
+    ///
+    /// Returns `None` if the header itself isn't cached.
+    pub(crate) fn lowest_ancestor(&self, hash: &B256) -> Option<&SealedHeader<H>> {
+        let mut lowest = self.headers.get(hash)?;
+        while let Some(parent) = self.headers.get(&lowest.parent_hash()) {
+            lowest = parent;
+        }
+        Some(lowest)
+    }
+
+    /// Starts tracking the branch with the given tip, evicting the oldest tracked branch if
+    /// necessary.
+    ///
+    /// Returns `false` if the branch is already tracked or no branches can be tracked at all.
+    pub(crate) fn track_tip(&mut self, tip: B256) -> bool {
+        if self.max_tips == 0 || self.max_headers == 0 || self.is_tracked(&tip) {
+            return false
+        }
+        self.tips.push_back(tip);
+        while self.tips.len() > self.max_tips {
+            self.tips.pop_front();
+        }
+        true
+    }
+
+    /// Stops tracking the branch with the given tip.
+    pub(crate) fn untrack_tip(&mut self, tip: &B256) {
+        self.tips.retain(|tracked| tracked != tip);
+    }
+
+    /// Returns `true` if the branch with the given tip is tracked.
+    pub(crate) fn is_tracked(&self, tip: &B256) -> bool {
+        self.tips.contains(tip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::headers::test_utils::child_header;
+    use alloy_consensus::Header;
+
+    #[test]
+    fn evicts_oldest_headers_and_tips() {
+        let genesis = SealedHeader::<Header>::default();
+        let first = child_header(&genesis);
+        let second = child_header(&first);
+
+        let mut branches = HeaderBranches::new(2, 1);
+        branches.insert(genesis.clone());
+        branches.insert(first.clone());
+        assert_eq!(branches.lowest_ancestor(&first.hash()), Some(&genesis));
+
+        branches.insert(second.clone());
+        assert!(branches.is_full());
+        assert!(!branches.contains(&genesis.hash()));
+        assert_eq!(branches.lowest_ancestor(&second.hash()), Some(&first));
+
+        assert!(branches.track_tip(first.hash()));
+        assert!(!branches.track_tip(first.hash()));
+        assert!(branches.track_tip(second.hash()));
+        assert!(!branches.is_tracked(&first.hash()));
+        branches.untrack_tip(&second.hash());
+        assert!(!branches.is_tracked(&second.hash()));
+    }
+}
@@ -7,5 +7,7 @@ pub mod noop;
 /// A downloader implementation that spawns a downloader to a task
 pub mod task;
 
+mod branches;
+
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
@@ -1,6 +1,6 @@
 //! A headers downloader that can handle multiple requests concurrently.
 
-use super::task::TaskDownloader;
+use super::{branches::HeaderBranches, task::TaskDownloader};
 use crate::metrics::HeaderDownloaderMetrics;
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockHashOrNumber;
@@ -104,6 +104,10 @@ pub struct ReverseHeadersDownloader<H: HeadersClient> {
     ///
     /// Note: headers are sorted from high to low
     queued_validated_headers: Vec<SealedHeader<H::Header>>,
+    /// Validated headers of previously targeted branches.
+    branches: HeaderBranches<H::Header>,
+    /// Requests in progress for the headers of previously targeted branches.
+    branch_requests: FuturesUnordered<BranchRequestFuture<H::Output>>,
     /// Header downloader metrics.
     metrics: HeaderDownloaderMetrics,
 }
@@ -332,10 +336,175 @@ where
         self.next_chain_tip_block_number =
             validated.last().expect("exists").number().saturating_sub(1);
         self.queued_validated_headers.extend(validated);
+        self.extend_from_branches();
 
         Ok(())
     }
 
+    /// Extends the validated headers with cached headers of a previously targeted branch, if the
+    /// lowest validated header is attached to one.
+    ///
+    /// The header right above the local head is never taken from the cache, so that it's always
+    /// downloaded and validated against the local head.
+    fn extend_from_branches(&mut self) {
+        let Some(local_head) = self.local_block_number() else { return };
+
+        let mut reused = 0;
+        while let Some(parent) = self
+            .lowest_validated_header()
+            .and_then(|lowest| self.branches.get(&lowest.parent_hash()))
+            .filter(|parent| parent.number() > local_head + 1)
+            .cloned()
+        {
+            self.queued_validated_headers.push(parent);
+            reused += 1;
+        }
+        if reused == 0 {
+            return
+        }
+
+        let lowest = self.queued_validated_headers.last().expect("exists").number();
+        trace!(target: "downloaders::headers", reused, lowest, "Reused cached headers");
+        self.metrics.reused_headers.increment(reused);
+
+        // continue below the reused headers, responses for the reused range are now obsolete
+        self.next_chain_tip_block_number = lowest - 1;
+        self.next_request_block_number = self.next_request_block_number.min(lowest - 1);
+        let next_chain_tip_block_number = self.next_chain_tip_block_number;
+        self.buffered_responses
+            .retain(|response| response.block_number() <= next_chain_tip_block_number);
+        self.metrics.buffered_responses.set(self.buffered_responses.len() as f64);
+    }
+
+    /// Keeps the validated headers of the current branch and starts tracking its `tip`, so that
+    /// the branch can be resumed from the cache if it becomes the sync target again.
+    ///
+    /// If the branch is not yet connected to the local head or another known branch, the rest
+    /// of it is downloaded concurrently.
+    fn stash_branch(&mut self, tip: B256) {
+        for header in self.lowest_validated_header.iter().chain(&self.queued_validated_headers) {
+            self.branches.insert(header.clone());
+        }
+        self.metrics.cached_headers.set(self.branches.len() as f64);
+
+        if !self.branches.track_tip(tip) {
+            return
+        }
+        match self.branches.lowest_ancestor(&tip) {
+            Some(lowest) => {
+                let (hash, number, parent_hash) =
+                    (lowest.hash(), lowest.number(), lowest.parent_hash());
+                if self.is_branch_incomplete(number, parent_hash) {
+                    self.submit_branch_request(tip, Some(hash), parent_hash, Priority::Normal);
+                }
+            }
+            None => self.submit_branch_request(tip, None, tip, Priority::Normal),
+        }
+    }
+
+    /// Returns `true` if the parent of the lowest known header of a branch still needs to be
+    /// downloaded.
+    ///
+    /// This is not the case if the parent is the local head or it's already known, which means
+    /// the fork point was reached.
+    fn is_branch_incomplete(&self, lowest_number: BlockNumber, parent_hash: B256) -> bool {
+        self.local_block_number().is_some_and(|head| lowest_number > head + 1) &&
+            !self.branches.is_full() &&
+            !self.branches.contains(&parent_hash) &&
+            !self.queued_validated_headers.iter().any(|header| header.hash() == parent_hash)
+    }
+
+    /// Handles the response for the headers of a previously targeted branch.
+    fn on_branch_outcome(
+        &mut self,
+        response: BranchRequestOutcome<H::Header>,
+    ) -> Result<(), Box<HeadersResponseError>> {
+        let BranchRequestOutcome { tip, child, response } = response;
+        let HeadersRequestOutcome { request, outcome } = response;
+        let (peer_id, mut headers) = match outcome {
+            Ok(res) => res.split(),
+            Err(err) => {
+                return Err(Box::new(HeadersResponseError {
+                    request,
+                    peer_id: None,
+                    error: err.into(),
+                }))
+            }
+        };
+
+        // update total downloaded metric
+        self.metrics.total_downloaded.increment(headers.len() as u64);
+
+        if headers.is_empty() {
+            return Err(Box::new(HeadersResponseError {
+                request,
+                peer_id: Some(peer_id),
+                error: DownloadError::EmptyResponse,
+            }))
+        }
+
+        // sort headers from highest to lowest block number
+        headers.sort_unstable_by_key(|h| Reverse(h.number()));
+        let headers = headers.into_par_iter().map(SealedHeader::seal_slow).collect::<Vec<_>>();
+
+        let expected = request.start.as_hash().expect("is hash");
+        if headers[0].hash() != expected {
+            return Err(Box::new(HeadersResponseError {
+                request,
+                peer_id: Some(peer_id),
+                error: DownloadError::InvalidTip(
+                    GotExpected { got: headers[0].hash(), expected }.into(),
+                ),
+            }))
+        }
+
+        // validate the headers against their children, starting with the cached child of the
+        // requested header
+        let child = child.and_then(|hash| self.branches.get(&hash));
+        let validation = child
+            .map(|child| self.validate(child, &headers[0]))
+            .into_iter()
+            .chain(headers.windows(2).map(|pair| self.validate(&pair[0], &pair[1])))
+            .collect::<DownloadResult<()>>();
+        if let Err(error) = validation {
+            trace!(target: "downloaders::headers", %error, "Failed to validate branch headers");
+            return Err(Box::new(HeadersResponseError { request, peer_id: Some(peer_id), error }))
+        }
+
+        let lowest = headers.last().expect("not empty");
+        let (hash, number, parent_hash) = (lowest.hash(), lowest.number(), lowest.parent_hash());
+        trace!(target: "downloaders::headers", ?tip, len=%headers.len(), number, "Received branch headers");
+
+        for header in headers {
+            self.branches.insert(header);
+        }
+        self.metrics.cached_headers.set(self.branches.len() as f64);
+
+        // continue towards the fork point, unless the branch is no longer of interest
+        if self.branches.is_tracked(&tip) && self.is_branch_incomplete(number, parent_hash) {
+            self.submit_branch_request(tip, Some(hash), parent_hash, Priority::Normal);
+        }
+
+        Ok(())
+    }
+
+    /// Starts a request for the headers of the branch with the given `tip`, starting at `start`.
+    fn submit_branch_request(
+        &self,
+        tip: B256,
+        child: Option<B256>,
+        start: B256,
+        priority: Priority,
+    ) {
+        let request = HeadersRequest::falling(start.into(), self.request_limit);
+        trace!(target: "downloaders::headers", ?tip, ?request, "Submitting branch headers request");
+        self.branch_requests.push(BranchRequestFuture {
+            tip,
+            child,
+            inner: self.request_fut(request, priority),
+        });
+    }
+
     /// Updates the state based on the given `target_block_number`
     ///
     /// There are three different outcomes:
@@ -438,6 +607,7 @@ where
                 self.on_block_number_update(target.number(), parent_block_number);
 
                 self.queued_validated_headers.push(target);
+                self.extend_from_branches();
 
                 // try to validate all buffered responses blocked by this successful response
                 self.try_validate_buffered()
@@ -518,7 +688,11 @@ where
                     self.try_validate_buffered()
                         .map(Err::<(), ReverseHeadersDownloaderError<H::Header>>)
                         .transpose()?;
-                } else if highest.number() > self.existing_local_block_number() {
+                } else if highest.number() > self.existing_local_block_number() &&
+                    highest.number() < self.next_chain_tip_block_number
+                {
+                    // responses above the next expected block number are obsolete, e.g. because
+                    // the range was already filled from cached headers
                     self.metrics.buffered_responses.increment(1.);
                     // can't validate yet
                     self.buffered_responses.push(OrderedHeadersResponse {
@@ -732,6 +906,12 @@ where
                         return
                     }
 
+                    // keep the current branch around, in case the sync target flips back to it
+                    self.branches.untrack_tip(&tip);
+                    if let Some(current_tip) = current_tip {
+                        self.stash_branch(current_tip);
+                    }
+
                     trace!(target: "downloaders::headers", new=?target, "Request new sync target");
                     self.metrics.out_of_order_requests.increment(1);
                     self.sync_target = Some(new_sync_target);
@@ -843,6 +1023,22 @@ where
         // 3. return batch, if enough validated
         // 4. return Pending if 2.) did not submit a new request, else continue
         loop {
+            // poll requests for previously targeted branches
+            while let Poll::Ready(Some(outcome)) = this.branch_requests.poll_next_unpin(cx) {
+                let (tip, child) = (outcome.tip, outcome.child);
+                if let Err(error) = this.on_branch_outcome(outcome) {
+                    if error.is_channel_closed() {
+                        // download channel closed which means the network was dropped
+                        return Poll::Ready(None)
+                    }
+                    let HeadersResponseError { request, peer_id, error } = *error;
+                    this.penalize_peer(peer_id, &error);
+                    this.metrics.increment_errors(&error);
+                    let start = request.start.as_hash().expect("is hash");
+                    this.submit_branch_request(tip, child, start, Priority::Normal);
+                }
+            }
+
             // poll requests
             while let Poll::Ready(Some(outcome)) = this.in_progress_queue.poll_next_unpin(cx) {
                 this.metrics.in_flight_requests.decrement(1.);
@@ -951,6 +1147,37 @@ struct HeadersRequestOutcome<H> {
     outcome: PeerRequestResult<Vec<H>>,
 }
 
+/// A future that returns the headers of a previously targeted branch.
+#[derive(Debug)]
+struct BranchRequestFuture<F> {
+    /// The tip of the branch.
+    tip: B256,
+    /// The hash of the cached child of the requested headers, if any.
+    child: Option<B256>,
+    inner: HeadersRequestFuture<F>,
+}
+
+impl<F, H> Future for BranchRequestFuture<F>
+where
+    F: Future<Output = PeerRequestResult<Vec<H>>> + Sync + Send + Unpin,
+{
+    type Output = BranchRequestOutcome<H>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let response = ready!(this.inner.poll_unpin(cx));
+
+        Poll::Ready(BranchRequestOutcome { tip: this.tip, child: this.child, response })
+    }
+}
+
+/// The outcome of the [`BranchRequestFuture`]
+struct BranchRequestOutcome<H> {
+    tip: B256,
+    child: Option<B256>,
+    response: HeadersRequestOutcome<H>,
+}
+
 // === impl OrderedHeadersResponse ===
 
 impl<H> HeadersRequestOutcome<H> {
@@ -1117,6 +1344,10 @@ pub struct ReverseHeadersDownloaderBuilder {
     max_concurrent_requests: usize,
     /// How many responses to buffer
     max_buffered_responses: usize,
+    /// How many headers of previously targeted branches to cache
+    max_cached_headers: usize,
+    /// How many previously targeted branches to keep downloading
+    max_alternative_tips: usize,
 }
 
 impl ReverseHeadersDownloaderBuilder {
@@ -1142,6 +1373,8 @@ impl Default for ReverseHeadersDownloaderBuilder {
             max_concurrent_requests: 100,
             min_concurrent_requests: 5,
             max_buffered_responses: 100,
+            max_cached_headers: 10_000,
+            max_alternative_tips: 2,
         }
     }
 }
@@ -1194,6 +1427,25 @@ impl ReverseHeadersDownloaderBuilder {
         self
     }
 
+    /// How many headers of previously targeted branches to cache.
+    ///
+    /// If the sync target switches to a different tip, the headers that were already downloaded
+    /// for the previous tip are cached, so they can be reused if the sync target switches back.
+    /// Setting this to `0` disables the cache.
+    pub const fn max_cached_headers(mut self, max_cached_headers: usize) -> Self {
+        self.max_cached_headers = max_cached_headers;
+        self
+    }
+
+    /// How many previously targeted tips to track.
+    ///
+    /// The headers of a tracked tip's branch are downloaded concurrently until they connect to
+    /// another known branch or the local head. Setting this to `0` disables these downloads.
+    pub const fn max_alternative_tips(mut self, max_alternative_tips: usize) -> Self {
+        self.max_alternative_tips = max_alternative_tips;
+        self
+    }
+
     /// Build [`ReverseHeadersDownloader`] with provided consensus
     /// and header client implementations
     pub fn build<H>(
@@ -1210,6 +1462,8 @@ impl ReverseHeadersDownloaderBuilder {
             min_concurrent_requests,
             max_concurrent_requests,
             max_buffered_responses,
+            max_cached_headers,
+            max_alternative_tips,
         } = self;
         ReverseHeadersDownloader {
             consensus,
@@ -1230,6 +1484,8 @@ impl ReverseHeadersDownloaderBuilder {
             in_progress_queue: Default::default(),
             buffered_responses: Default::default(),
             queued_validated_headers: Default::default(),
+            branches: HeaderBranches::new(max_cached_headers, max_alternative_tips),
+            branch_requests: Default::default(),
             metrics: Default::default(),
         }
     }
@@ -1473,6 +1729,40 @@ mod tests {
         assert!(downloader.next().await.is_none());
     }
 
+    #[tokio::test]
+    async fn download_reuses_cached_branch() {
+        reth_tracing::init_test_tracing();
+
+        let client = Arc::new(TestHeadersClient::default());
+
+        let p5 = SealedHeader::default();
+        let p4 = child_header(&p5);
+        let p3 = child_header(&p4);
+        let p2 = child_header(&p3);
+        let p1 = child_header(&p2);
+        let p0 = child_header(&p1);
+
+        let mut downloader = ReverseHeadersDownloaderBuilder::default()
+            .stream_batch_size(5)
+            .request_limit(5)
+            .build(Arc::clone(&client), Arc::new(TestConsensus::default()));
+        downloader.update_local_head(p5.clone());
+
+        // headers of the branch that were downloaded while it was targeted before
+        for header in [&p1, &p2, &p3] {
+            downloader.branches.insert(header.clone());
+        }
+        downloader.update_sync_target(SyncTarget::Tip(p0.hash()));
+
+        // only the tip and the header attaching to the local head are requested
+        client.extend(vec![p0.as_ref().clone(), p4.as_ref().clone()]).await;
+
+        let headers = downloader.next().await.unwrap();
+        assert_eq!(headers, Ok(vec![p0, p1, p2, p3, p4]));
+        assert_eq!(client.request_attempts(), 2);
+        assert!(downloader.next().await.is_none());
+    }
+
     #[tokio::test]
     async fn download_one_by_one() {
         reth_tracing::init_test_tracing();
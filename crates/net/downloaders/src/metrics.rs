@@ -120,6 +120,10 @@ pub struct HeaderDownloaderMetrics {
     pub validation_errors: Counter,
     /// Number of unexpected errors while requesting items
     pub unexpected_errors: Counter,
+    /// The number of headers of previously targeted branches that are cached.
+    pub cached_headers: Gauge,
+    /// Number of cached headers that were reused instead of being requested again.
+    pub reused_headers: Counter,
 }
 
 impl HeaderDownloaderMetrics {
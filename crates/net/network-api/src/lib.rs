@@ -25,7 +25,9 @@ use test_utils::PeersHandleProvider;
 
 pub use alloy_rpc_types_admin::EthProtocolInfo;
 pub use reth_network_p2p::{BlockClient, HeadersClient};
-pub use reth_network_types::{PeerKind, Reputation, ReputationChangeKind};
+pub use reth_network_types::{
+    PeerBackoffState, PeerHeadStatus, PeerKind, Reputation, ReputationChangeKind,
+};
 
 pub use downloaders::BlockDownloaderProvider;
 pub use error::NetworkError;
@@ -106,6 +108,11 @@ pub trait PeersInfo: Send + Sync {
 
     /// Returns the local ENR of the node.
     fn local_enr(&self) -> enr::Enr<enr::secp256k1::SecretKey>;
+
+    /// Returns the UDP ports of the discovery services of the node.
+    fn discovery_ports(&self) -> DiscoveryPorts {
+        DiscoveryPorts::default()
+    }
 }
 
 /// Provides an API for managing the peers of the network.
@@ -244,6 +251,10 @@ pub struct PeerInfo {
     pub session_established: Instant,
     /// The peer's connection kind
     pub kind: PeerKind,
+    /// How far the head of the peer lags behind the local head
+    pub head_status: PeerHeadStatus,
+    /// The dial failure history of the peer
+    pub backoff: PeerBackoffState,
 }
 
 /// The direction of the connection.
@@ -289,3 +300,13 @@ pub struct NetworkStatus {
     /// The list of supported capabilities and their versions.
     pub capabilities: Vec<Capability>,
 }
+
+/// The UDP ports of the discovery services of the local node.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiscoveryPorts {
+    /// The port of the discv4 service, `None` if discv4 is disabled.
+    pub discv4: Option<u16>,
+    /// The port of the discv5 service, `None` if discv5 is disabled.
+    pub discv5: Option<u16>,
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// Describes the type of backoff should be applied.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BackoffKind {
@@ -25,3 +27,35 @@ impl BackoffKind {
         matches!(self, Self::Medium | Self::High)
     }
 }
+
+/// The dial failure history of a peer.
+///
+/// This is persisted on shutdown, so that peers that kept failing are not dialed again right away
+/// after a restart.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PeerBackoffState {
+    /// Number of times the peer was backed off due to a severe [`BackoffKind`].
+    pub severe_backoff_counter: u8,
+    /// Unix timestamp in seconds until which the peer is backed off, if it is backed off.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub backed_off_until: Option<u64>,
+}
+
+// === impl PeerBackoffState ===
+
+impl PeerBackoffState {
+    /// Returns true if the peer never failed and is not backed off.
+    pub const fn is_empty(&self) -> bool {
+        self.severe_backoff_counter == 0 && self.backed_off_until.is_none()
+    }
+
+    /// Returns the remaining backoff duration at the given unix timestamp in seconds, if the peer
+    /// is still backed off.
+    pub fn remaining_backoff(&self, now: u64) -> Option<Duration> {
+        self.backed_off_until
+            .filter(|until| *until > now)
+            .map(|until| Duration::from_secs(until - now))
+    }
+}
@@ -16,14 +16,15 @@
 pub mod peers;
 pub mod session;
 
-/// [`BackoffKind`] definition.
+/// [`BackoffKind`] and [`PeerBackoffState`] definitions.
 mod backoff;
 
 pub use peers::reputation::{Reputation, ReputationChangeKind, ReputationChangeWeights};
 
-pub use backoff::BackoffKind;
+pub use backoff::{BackoffKind, PeerBackoffState};
 pub use peers::{
     addr::PeerAddr,
+    head::PeerHeadStatus,
     kind::PeerKind,
     reputation::{
         is_banned_reputation, is_connection_failed_reputation, ReputationChangeOutcome,
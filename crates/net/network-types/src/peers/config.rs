@@ -1,17 +1,17 @@
 //! Configuration for peering.
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     io::{self, ErrorKind},
     path::Path,
     time::Duration,
 };
 
 use reth_net_banlist::BanList;
-use reth_network_peers::{NodeRecord, TrustedPeer};
+use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
 use tracing::info;
 
-use crate::{BackoffKind, ReputationChangeWeights};
+use crate::{BackoffKind, PeerBackoffState, ReputationChangeWeights};
 
 /// Maximum number of available slots for outbound sessions.
 pub const DEFAULT_MAX_COUNT_PEERS_OUTBOUND: u32 = 100;
@@ -27,6 +27,9 @@ pub const DEFAULT_MAX_COUNT_CONCURRENT_OUTBOUND_DIALS: usize = 15;
 /// A temporary timeout for ips on incoming connection attempts.
 pub const INBOUND_IP_THROTTLE_DURATION: Duration = Duration::from_secs(30);
 
+/// Number of blocks the head of a peer may lag behind the local head before the peer is stale.
+pub const DEFAULT_STALE_PEER_THRESHOLD: u64 = 64;
+
 /// The durations to use when a backoff should be applied to a peer.
 ///
 /// See also [`BackoffKind`].
@@ -59,14 +62,20 @@ impl PeerBackoffDurations {
         }
     }
 
+    /// Returns the duration to backoff for.
+    ///
+    /// The backoff duration doubles with every severe backoff of the peer and is capped by the
+    /// configured maximum backoff duration.
+    pub fn backoff_duration(&self, kind: BackoffKind, backoff_counter: u8) -> Duration {
+        let factor = 1u32.checked_shl(backoff_counter as u32).unwrap_or(u32::MAX);
+        self.backoff(kind).saturating_mul(factor).min(self.max)
+    }
+
     /// Returns the timestamp until which we should backoff.
     ///
-    /// The Backoff duration is capped by the configured maximum backoff duration.
+    /// See also [`Self::backoff_duration`].
     pub fn backoff_until(&self, kind: BackoffKind, backoff_counter: u8) -> std::time::Instant {
-        let backoff_time = self.backoff(kind);
-        let backoff_time = backoff_time + backoff_time * backoff_counter as u32;
-        let now = std::time::Instant::now();
-        now + backoff_time.min(self.max)
+        std::time::Instant::now() + self.backoff_duration(kind, backoff_counter)
     }
 
     /// Returns durations for testing.
@@ -106,6 +115,9 @@ pub struct ConnectionsConfig {
     /// Maximum allowed concurrent outbound dials.
     #[cfg_attr(feature = "serde", serde(default))]
     pub max_concurrent_outbound_dials: usize,
+    /// Maximum number of outbound dials per second, unlimited if not set.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_dial_rate: Option<usize>,
 }
 
 impl Default for ConnectionsConfig {
@@ -114,6 +126,7 @@ impl Default for ConnectionsConfig {
             max_outbound: DEFAULT_MAX_COUNT_PEERS_OUTBOUND as usize,
             max_inbound: DEFAULT_MAX_COUNT_PEERS_INBOUND as usize,
             max_concurrent_outbound_dials: DEFAULT_MAX_COUNT_CONCURRENT_OUTBOUND_DIALS,
+            max_dial_rate: None,
         }
     }
 }
@@ -140,13 +153,19 @@ pub struct PeersConfig {
     /// configured backoff duration and the max backoff count.
     ///
     /// With a backoff counter of 5 and a backoff duration of 1h, the minimum time spent of the
-    /// peer in the table is the sum of all backoffs (1h + 2h + 3h + 4h + 5h = 15h).
+    /// peer in the table is the sum of all backoffs (1h + 2h + 4h + 8h + 16h = 31h), unless the
+    /// backoffs are capped by the maximum backoff duration.
     ///
     /// Note: this does not apply to trusted peers.
     pub max_backoff_count: u8,
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// The dial failure history of peers from a previous run.
+    ///
+    /// This is applied once the peer is added to the peer set.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub backoff_states: HashMap<PeerId, PeerBackoffState>,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -166,6 +185,12 @@ pub struct PeersConfig {
     /// This acts as an IP based rate limit.
     #[cfg_attr(feature = "serde", serde(default, with = "humantime_serde"))]
     pub incoming_ip_throttle_duration: Duration,
+    /// Number of blocks the head of a peer may lag behind the local head before the peer is
+    /// considered stale.
+    ///
+    /// Stale peers are not asked for blocks near the local head while other peers are available,
+    /// and are rotated out if they are the only ones left.
+    pub stale_peer_threshold: u64,
 }
 
 impl Default for PeersConfig {
@@ -182,8 +207,10 @@ impl Default for PeersConfig {
             trusted_nodes_only: false,
             trusted_nodes_resolution_interval: Duration::from_secs(60 * 60),
             basic_nodes: Default::default(),
+            backoff_states: Default::default(),
             max_backoff_count: 5,
             incoming_ip_throttle_duration: INBOUND_IP_THROTTLE_DURATION,
+            stale_peer_threshold: DEFAULT_STALE_PEER_THRESHOLD,
         }
     }
 }
@@ -241,6 +268,20 @@ impl PeersConfig {
         self
     }
 
+    /// Maximum number of outbound dials per second.
+    pub const fn with_max_dial_rate(mut self, max_dial_rate: usize) -> Self {
+        self.connection_info.max_dial_rate = Some(max_dial_rate);
+        self
+    }
+
+    /// Maximum number of outbound dials per second with optional update.
+    pub const fn with_max_dial_rate_opt(mut self, max_dial_rate: Option<usize>) -> Self {
+        if let Some(max_dial_rate) = max_dial_rate {
+            self.connection_info.max_dial_rate = Some(max_dial_rate);
+        }
+        self
+    }
+
     /// Nodes to always connect to.
     pub fn with_trusted_nodes(mut self, nodes: Vec<TrustedPeer>) -> Self {
         self.trusted_nodes = nodes;
@@ -259,6 +300,12 @@ impl PeersConfig {
         self
     }
 
+    /// Dial failure history of peers from a previous run.
+    pub fn with_backoff_states(mut self, states: HashMap<PeerId, PeerBackoffState>) -> Self {
+        self.backoff_states = states;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub const fn with_max_backoff_count(mut self, max_backoff_count: u8) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -280,6 +327,13 @@ impl PeersConfig {
         self
     }
 
+    /// Configures the number of blocks the head of a peer may lag behind the local head before
+    /// the peer is considered stale.
+    pub const fn with_stale_peer_threshold(mut self, stale_peer_threshold: u64) -> Self {
+        self.stale_peer_threshold = stale_peer_threshold;
+        self
+    }
+
     /// Returns the maximum number of peers, inbound and outbound.
     pub const fn max_peers(&self) -> usize {
         self.connection_info.max_outbound + self.connection_info.max_inbound
@@ -301,6 +355,23 @@ impl PeersConfig {
         Ok(self.with_basic_nodes(nodes))
     }
 
+    /// Read from file the dial failure history of peers from a previous run. Ignored if None.
+    #[cfg(feature = "serde")]
+    pub fn with_backoff_states_from_file(
+        self,
+        optional_file: Option<impl AsRef<Path>>,
+    ) -> Result<Self, io::Error> {
+        let Some(file_path) = optional_file else { return Ok(self) };
+        let reader = match std::fs::File::open(file_path.as_ref()) {
+            Ok(file) => io::BufReader::new(file),
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(self),
+            Err(e) => Err(e)?,
+        };
+        info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peer backoffs");
+        let states: HashMap<PeerId, PeerBackoffState> = serde_json::from_reader(reader)?;
+        Ok(self.with_backoff_states(states))
+    }
+
     /// Returns settings for testing
     #[cfg(any(test, feature = "test-utils"))]
     pub fn test() -> Self {
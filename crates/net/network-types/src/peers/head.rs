@@ -0,0 +1,35 @@
+//! Classification of a peer based on its head.
+
+/// Represents how far the head of a peer lags behind the local head.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum PeerHeadStatus {
+    /// The head of the peer is unknown, e.g. because it announced a block we don't know yet.
+    #[default]
+    Unknown,
+    /// The head of the peer is close to the local head.
+    Synced,
+    /// The head of the peer lags behind the local head by more than the stale peer threshold.
+    ///
+    /// Stale peers are still used to download blocks below their head, but not to download blocks
+    /// near the local head.
+    Stale,
+}
+
+impl PeerHeadStatus {
+    /// Returns the status of a peer whose head lags behind the local head by the given number of
+    /// blocks, if known.
+    pub const fn from_lag(lag: Option<u64>, stale_peer_threshold: u64) -> Self {
+        match lag {
+            None => Self::Unknown,
+            Some(lag) if lag > stale_peer_threshold => Self::Stale,
+            Some(_) => Self::Synced,
+        }
+    }
+
+    /// Returns `true` if the peer is stale.
+    pub const fn is_stale(&self) -> bool {
+        matches!(self, Self::Stale)
+    }
+}
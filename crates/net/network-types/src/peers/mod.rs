@@ -1,5 +1,6 @@
 pub mod addr;
 pub mod config;
+pub mod head;
 pub mod kind;
 pub mod reputation;
 pub mod state;
@@ -15,7 +15,9 @@ use reth_network_p2p::{
     priority::Priority,
 };
 use reth_network_peers::PeerId;
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{
+    peers::config::DEFAULT_STALE_PEER_THRESHOLD, PeerHeadStatus, ReputationChangeKind,
+};
 use std::{
     collections::{HashMap, VecDeque},
     ops::RangeInclusive,
@@ -24,10 +26,16 @@ use std::{
         Arc,
     },
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc, mpsc::UnboundedSender, oneshot};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
+/// The minimum interval between two disconnects of stale peers.
+///
+/// This prevents dropping all stale peers at once if we're connected to stale peers only.
+const STALE_PEER_ROTATION_INTERVAL: Duration = Duration::from_secs(30);
+
 type InflightHeadersRequest<H> = Request<HeadersRequest, PeerRequestResult<Vec<H>>>;
 type InflightBodiesRequest<B> = Request<Vec<B256>, PeerRequestResult<Vec<B>>>;
 
@@ -55,6 +63,12 @@ pub struct StateFetcher<N: NetworkPrimitives = EthNetworkPrimitives> {
     download_requests_rx: UnboundedReceiverStream<DownloadRequest<N>>,
     /// Sender for download requests, used to detach a [`FetchClient`]
     download_requests_tx: UnboundedSender<DownloadRequest<N>>,
+    /// The number of the local head.
+    local_head: u64,
+    /// Number of blocks the head of a peer may lag behind the local head before it's stale.
+    stale_peer_threshold: u64,
+    /// When a stale peer was last disconnected to make room for a better one.
+    last_stale_peer_rotation: Option<Instant>,
 }
 
 // === impl StateSyncer ===
@@ -71,9 +85,44 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             queued_requests: Default::default(),
             download_requests_rx: UnboundedReceiverStream::new(download_requests_rx),
             download_requests_tx,
+            local_head: 0,
+            stale_peer_threshold: DEFAULT_STALE_PEER_THRESHOLD,
+            last_stale_peer_rotation: None,
         }
     }
 
+    /// Sets the number of blocks the head of a peer may lag behind the local head before the peer
+    /// is considered stale.
+    pub(crate) const fn with_stale_peer_threshold(mut self, stale_peer_threshold: u64) -> Self {
+        self.stale_peer_threshold = stale_peer_threshold;
+        self
+    }
+
+    /// Updates the number of the local head that the heads of the peers are compared to.
+    pub(crate) const fn update_local_head(&mut self, local_head: u64) {
+        self.local_head = local_head;
+    }
+
+    /// Returns the [`PeerHeadStatus`] of the peer, if it's an active peer.
+    pub(crate) fn peer_head_status(&self, peer_id: &PeerId) -> Option<PeerHeadStatus> {
+        self.peers.get(peer_id).map(|peer| self.head_status(peer))
+    }
+
+    /// Returns the [`PeerHeadStatus`] of the peer relative to the local head.
+    fn head_status(&self, peer: &Peer) -> PeerHeadStatus {
+        PeerHeadStatus::from_lag(peer.head_lag(self.local_head), self.stale_peer_threshold)
+    }
+
+    /// Returns `true` if the peer is stale and can't serve a request that reaches up to the given
+    /// block.
+    ///
+    /// Stale peers are still used for requests of blocks below their head, e.g. historical
+    /// downloads.
+    fn is_stale_for(&self, peer: &Peer, highest_block: Option<u64>) -> bool {
+        self.head_status(peer).is_stale() &&
+            highest_block.is_none_or(|block| block > peer.head_number())
+    }
+
     /// Invoked when connected to a new peer.
     pub(crate) fn new_active_peer(
         &mut self,
@@ -133,15 +182,24 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
         }
     }
 
-    /// Returns the _next_ idle peer that's ready to accept a request,
-    /// prioritizing those with the lowest timeout/latency and those that recently responded with
-    /// adequate data.
-    fn next_best_peer(&self) -> Option<PeerId> {
+    /// Returns the _next_ idle peer that's ready to accept a request up to the given block,
+    /// prioritizing those that aren't stale for the request, those with the lowest timeout/latency
+    /// and those that recently responded with adequate data.
+    fn next_best_peer(&self, highest_block: Option<u64>) -> Option<PeerId> {
         let mut idle = self.peers.iter().filter(|(_, peer)| peer.state.is_idle());
 
         let mut best_peer = idle.next()?;
 
         for maybe_better in idle {
+            // replace best peer if our current best peer can't serve the request
+            let best_is_stale = self.is_stale_for(best_peer.1, highest_block);
+            if best_is_stale != self.is_stale_for(maybe_better.1, highest_block) {
+                if best_is_stale {
+                    best_peer = maybe_better;
+                }
+                continue
+            }
+
             // replace best peer if our current best peer sent us a bad response last time
             if best_peer.1.last_response_likely_bad && !maybe_better.1.last_response_likely_bad {
                 best_peer = maybe_better;
@@ -166,7 +224,30 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
             return PollAction::NoRequests
         }
 
-        let Some(peer_id) = self.next_best_peer() else { return PollAction::NoPeersAvailable };
+        let highest_block = self.queued_requests.front().and_then(|req| req.highest_block());
+        let Some(peer_id) = self.next_best_peer(highest_block) else {
+            return PollAction::NoPeersAvailable
+        };
+
+        if self.is_stale_for(&self.peers[&peer_id], highest_block) {
+            // wait for a busy peer that isn't stale instead of asking a stale one
+            if self.peers.values().any(|peer| {
+                !matches!(peer.state, PeerState::Closing) && !self.is_stale_for(peer, highest_block)
+            }) {
+                return PollAction::NoPeersAvailable
+            }
+
+            // all peers are stale, make room for a better one
+            let now = Instant::now();
+            if self
+                .last_stale_peer_rotation
+                .is_none_or(|last| now.duration_since(last) >= STALE_PEER_ROTATION_INTERVAL)
+            {
+                self.last_stale_peer_rotation = Some(now);
+                self.on_pending_disconnect(&peer_id);
+                return PollAction::Ready(FetchAction::DisconnectStalePeer { peer_id })
+            }
+        }
 
         let request = self.queued_requests.pop_front().expect("not empty");
         let request = self.prepare_block_request(peer_id, request);
@@ -248,6 +329,10 @@ impl<N: NetworkPrimitives> StateFetcher<N> {
     ///
     /// Caution: this expects that the peer is _not_ closed.
     fn followup_request(&mut self, peer_id: PeerId) -> Option<BlockResponseOutcome> {
+        let highest_block = self.queued_requests.front()?.highest_block();
+        if self.is_stale_for(&self.peers[&peer_id], highest_block) {
+            return None
+        }
         let req = self.queued_requests.pop_front()?;
         let req = self.prepare_block_request(peer_id, req);
         Some(BlockResponseOutcome::Request(peer_id, req))
@@ -351,7 +436,6 @@ struct Peer {
     /// lowest timeout.
     last_response_likely_bad: bool,
     /// Tracks the range info for the peer.
-    #[allow(dead_code)]
     range_info: Option<BlockRangeInfo>,
 }
 
@@ -359,6 +443,21 @@ impl Peer {
     fn timeout(&self) -> u64 {
         self.timeout.load(Ordering::Relaxed)
     }
+
+    /// Returns the highest block the peer announced.
+    fn head_number(&self) -> u64 {
+        let latest = self.range_info.as_ref().map_or(0, |info| info.latest());
+        latest.max(self.best_number)
+    }
+
+    /// Returns how many blocks the head of the peer lags behind the given local head.
+    ///
+    /// Returns `None` if the head of the peer is unknown, which is the case if the peer announced
+    /// a block hash that we don't know.
+    fn head_lag(&self, local_head: u64) -> Option<u64> {
+        let head = self.head_number();
+        (head != 0).then(|| local_head.saturating_sub(head))
+    }
 }
 
 /// Tracks the state of an individual peer
@@ -420,7 +519,6 @@ pub(crate) enum DownloadRequest<N: NetworkPrimitives> {
         request: Vec<B256>,
         response: oneshot::Sender<PeerRequestResult<Vec<N::BlockBody>>>,
         priority: Priority,
-        #[allow(dead_code)]
         range_hint: Option<RangeInclusive<u64>>,
     },
 }
@@ -449,6 +547,23 @@ impl<N: NetworkPrimitives> DownloadRequest<N> {
     const fn is_normal_priority(&self) -> bool {
         self.get_priority().is_normal()
     }
+
+    /// Returns the highest block number of this request, if known.
+    fn highest_block(&self) -> Option<u64> {
+        match self {
+            Self::GetBlockHeaders { request, .. } => {
+                let start = request.start.as_number()?;
+                if request.direction.is_rising() {
+                    Some(start.saturating_add(request.limit.saturating_sub(1)))
+                } else {
+                    Some(start)
+                }
+            }
+            Self::GetBlockBodies { range_hint, .. } => {
+                range_hint.as_ref().map(|range| *range.end())
+            }
+        }
+    }
 }
 
 /// An action the syncer can emit.
@@ -460,6 +575,12 @@ pub(crate) enum FetchAction {
         /// The request to send
         request: BlockRequest,
     },
+    /// Disconnect a stale peer because no other peers are available to serve requests near the
+    /// local head.
+    DisconnectStalePeer {
+        /// The stale peer
+        peer_id: PeerId,
+    },
 }
 
 /// Outcome of a processed response.
@@ -514,17 +635,17 @@ mod tests {
         fetcher.new_active_peer(peer1, B256::random(), 1, Arc::new(AtomicU64::new(1)), None);
         fetcher.new_active_peer(peer2, B256::random(), 2, Arc::new(AtomicU64::new(1)), None);
 
-        let first_peer = fetcher.next_best_peer().unwrap();
+        let first_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         // Pending disconnect for first_peer
         fetcher.on_pending_disconnect(&first_peer);
         // first_peer now isn't idle, so we should get other peer
-        let second_peer = fetcher.next_best_peer().unwrap();
+        let second_peer = fetcher.next_best_peer(None).unwrap();
         assert!(first_peer == peer1 || first_peer == peer2);
         assert_ne!(first_peer, second_peer);
         // without idle peers, returns None
         fetcher.on_pending_disconnect(&second_peer);
-        assert_eq!(fetcher.next_best_peer(), None);
+        assert_eq!(fetcher.next_best_peer(None), None);
     }
 
     #[tokio::test]
@@ -544,13 +665,43 @@ mod tests {
         fetcher.new_active_peer(peer3, B256::random(), 3, Arc::new(AtomicU64::new(50)), None);
 
         // Must always get peer1 (lowest timeout)
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
-        assert_eq!(fetcher.next_best_peer(), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer1));
         // peer2's timeout changes below peer1's
         peer2_timeout.store(10, Ordering::Relaxed);
         // Then we get peer 2 always (now lowest)
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
-        assert_eq!(fetcher.next_best_peer(), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+        assert_eq!(fetcher.next_best_peer(None), Some(peer2));
+    }
+
+    #[tokio::test]
+    async fn test_stale_peer_deprioritization() {
+        let manager = PeersManager::new(PeersConfig::default());
+        let mut fetcher =
+            StateFetcher::<EthNetworkPrimitives>::new(manager.handle(), Default::default())
+                .with_stale_peer_threshold(10);
+        fetcher.update_local_head(100);
+
+        let stale = B512::random();
+        let synced = B512::random();
+        let unknown = B512::random();
+        fetcher.new_active_peer(stale, B256::random(), 50, Arc::new(AtomicU64::new(1)), None);
+        fetcher.new_active_peer(synced, B256::random(), 95, Arc::new(AtomicU64::new(100)), None);
+        fetcher.new_active_peer(unknown, B256::random(), 0, Arc::new(AtomicU64::new(100)), None);
+
+        assert_eq!(fetcher.peer_head_status(&stale), Some(PeerHeadStatus::Stale));
+        assert_eq!(fetcher.peer_head_status(&synced), Some(PeerHeadStatus::Synced));
+        assert_eq!(fetcher.peer_head_status(&unknown), Some(PeerHeadStatus::Unknown));
+
+        // the stale peer has the lowest timeout but can only serve blocks below its head
+        assert_eq!(fetcher.next_best_peer(Some(40)), Some(stale));
+        assert_ne!(fetcher.next_best_peer(Some(99)), Some(stale));
+        assert_ne!(fetcher.next_best_peer(None), Some(stale));
+
+        // the peer catches up
+        fetcher.update_peer_block(&stale, B256::random(), 99);
+        assert_eq!(fetcher.peer_head_status(&stale), Some(PeerHeadStatus::Synced));
+        assert_eq!(fetcher.next_best_peer(None), Some(stale));
     }
 
     #[tokio::test]
@@ -29,7 +29,7 @@ use crate::{
     peers::PeersManager,
     poll_nested_stream_with_budget,
     protocol::IntoRlpxSubProtocol,
-    session::SessionManager,
+    session::{ActiveSessionHandle, SessionManager},
     state::NetworkState,
     swarm::{Swarm, SwarmEvent},
     transactions::NetworkTransactionEvent,
@@ -252,6 +252,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             handshake,
         } = config;
 
+        let stale_peer_threshold = peers_config.stale_peer_threshold;
         let peers_manager = PeersManager::new(peers_config);
         let peers_handle = peers_manager.handle();
 
@@ -311,6 +312,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             discovery,
             peers_manager,
             Arc::clone(&num_active_peers),
+            stale_peer_threshold,
         );
 
         let swarm = Swarm::new(incoming, sessions, state);
@@ -433,6 +435,19 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         Ok(())
     }
 
+    /// Collect the dial failure history of the peers from the [`NetworkManager`] and write it to
+    /// the given `persistent_backoffs_file`.
+    #[cfg(feature = "serde")]
+    pub fn write_peer_backoffs_to_file(
+        &self,
+        persistent_backoffs_file: &Path,
+    ) -> Result<(), FsPathError> {
+        let backoffs = self.swarm.state().peers().backoff_states();
+        persistent_backoffs_file.parent().map(fs::create_dir_all).transpose()?;
+        reth_fs_util::write_json_file(persistent_backoffs_file, &backoffs)?;
+        Ok(())
+    }
+
     /// Returns a new [`FetchClient`] that can be cloned and shared.
     ///
     /// The [`FetchClient`] is the entrypoint for sending requests to the network.
@@ -703,6 +718,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                 let _ = tx.send(self.status());
             }
             NetworkHandleMessage::StatusUpdate { head } => {
+                self.swarm.state_mut().update_local_head(head.number);
                 if let Some(transition) = self.swarm.sessions_mut().on_status_update(head) {
                     self.swarm.state_mut().update_fork_id(transition.current);
                 }
@@ -988,13 +1004,7 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             .sessions()
             .active_sessions()
             .iter()
-            .filter_map(|(&peer_id, session)| {
-                self.swarm
-                    .state()
-                    .peers()
-                    .peer_by_id(peer_id)
-                    .map(|(record, kind)| session.peer_info(&record, kind))
-            })
+            .filter_map(|(&peer_id, session)| self.peer_info(peer_id, session))
             .collect()
     }
 
@@ -1002,13 +1012,22 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
     ///
     /// Returns `None` if there's no active session to the peer.
     fn get_peer_info_by_id(&self, peer_id: PeerId) -> Option<PeerInfo> {
-        self.swarm.sessions().active_sessions().get(&peer_id).and_then(|session| {
-            self.swarm
-                .state()
-                .peers()
-                .peer_by_id(peer_id)
-                .map(|(record, kind)| session.peer_info(&record, kind))
-        })
+        self.swarm
+            .sessions()
+            .active_sessions()
+            .get(&peer_id)
+            .and_then(|session| self.peer_info(peer_id, session))
+    }
+
+    /// Returns [`PeerInfo`] for the active session of the peer.
+    fn peer_info(&self, peer_id: PeerId, session: &ActiveSessionHandle<N>) -> Option<PeerInfo> {
+        let state = self.swarm.state();
+        let head_status = state.peer_head_status(&peer_id);
+        let backoff = state.peers().backoff_state(&peer_id).unwrap_or_default();
+        state
+            .peers()
+            .peer_by_id(peer_id)
+            .map(|(record, kind)| session.peer_info(&record, kind, head_status, backoff))
     }
 
     /// Returns [`PeerInfo`] for a given peers.
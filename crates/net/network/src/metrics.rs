@@ -331,6 +331,23 @@ pub struct EthRequestHandlerMetrics {
     pub(crate) acc_duration_poll_eth_req_handler: Gauge,
 }
 
+/// Metrics for the [`TxGossipMonitor`](crate::transactions::monitor::TxGossipMonitor).
+#[derive(Metrics)]
+#[metrics(scope = "network.transactions.gossip_monitor")]
+pub struct TxGossipMonitorMetrics {
+    /// Number of transactions currently waiting to be announced by a second peer.
+    pub(crate) tracked_transactions: Gauge,
+    /// Total number of tracked transactions that were announced by a second peer.
+    pub(crate) reannounced_transactions: Counter,
+    /// Total number of tracked transactions that were not announced by a second peer within the
+    /// timeout.
+    pub(crate) lost_transactions: Counter,
+    /// Duration in seconds between the first and the second peer announcing a transaction.
+    pub(crate) propagation_duration_seconds: Histogram,
+    /// Whether gossip is currently considered broken, `1` if so, `0` otherwise.
+    pub(crate) gossip_broken: Gauge,
+}
+
 /// Eth67 announcement metrics, track entries by `TxType`
 #[derive(Metrics)]
 #[metrics(scope = "network.transaction_fetcher")]
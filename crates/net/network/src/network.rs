@@ -16,7 +16,7 @@ use reth_ethereum_forks::Head;
 use reth_network_api::{
     events::{NetworkPeersEvents, PeerEvent, PeerEventStream},
     test_utils::{PeersHandle, PeersHandleProvider},
-    BlockDownloaderProvider, DiscoveryEvent, NetworkError, NetworkEvent,
+    BlockDownloaderProvider, DiscoveryEvent, DiscoveryPorts, NetworkError, NetworkEvent,
     NetworkEventListenerProvider, NetworkInfo, NetworkStatus, PeerInfo, PeerRequest, Peers,
     PeersInfo,
 };
@@ -255,6 +255,18 @@ impl<N: NetworkPrimitives> PeersInfo for NetworkHandle<N> {
         }
     }
 
+    fn discovery_ports(&self) -> DiscoveryPorts {
+        DiscoveryPorts {
+            discv4: self.inner.discv4.as_ref().map(|discv4| discv4.local_addr().port()),
+            discv5: self
+                .inner
+                .discv5
+                .as_ref()
+                .and_then(|discv5| discv5.node_record())
+                .map(|record| record.udp_port),
+        }
+    }
+
     fn local_enr(&self) -> Enr<SecretKey> {
         let local_node_record = self.local_node_record();
         let mut builder = Enr::builder();
@@ -7,6 +7,7 @@ use crate::{
     trusted_peers_resolver::TrustedPeersResolver,
 };
 use futures::StreamExt;
+use rand::Rng;
 
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_ethereum_forks::ForkId;
@@ -19,8 +20,8 @@ use reth_network_types::{
         config::PeerBackoffDurations,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
-    ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
+    ConnectionsConfig, Peer, PeerAddr, PeerBackoffState, PeerConnectionState, PeerKind,
+    PeersConfig, ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
 };
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
@@ -28,7 +29,7 @@ use std::{
     io::{self},
     net::{IpAddr, SocketAddr},
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use thiserror::Error;
 use tokio::{
@@ -72,6 +73,10 @@ pub struct PeersManager {
     ban_list: BanList,
     /// Tracks currently backed off peers.
     backed_off_peers: HashMap<PeerId, std::time::Instant>,
+    /// The dial failure history of peers from a previous run that are not yet in the peer set.
+    ///
+    /// The history is applied once the peer is discovered again.
+    restored_backoffs: HashMap<PeerId, PeerBackoffState>,
     /// Interval at which to check for peers to unban and release from the backoff map.
     release_interval: Interval,
     /// How long to ban bad peers.
@@ -90,6 +95,10 @@ pub struct PeersManager {
     net_connection_state: NetworkConnectionState,
     /// How long to temporarily ban ip on an incoming connection attempt.
     incoming_ip_throttle_duration: Duration,
+    /// Start of the current one second window of the outbound dial rate limit.
+    dial_window_start: Instant,
+    /// Number of outbound dials in the current window.
+    dials_in_window: usize,
 }
 
 impl PeersManager {
@@ -106,8 +115,10 @@ impl PeersManager {
             trusted_nodes_only,
             trusted_nodes_resolution_interval,
             basic_nodes,
+            backoff_states,
             max_backoff_count,
             incoming_ip_throttle_duration,
+            stale_peer_threshold: _,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -138,7 +149,7 @@ impl PeersManager {
             });
         }
 
-        Self {
+        let mut manager = Self {
             peers,
             trusted_peer_ids,
             trusted_peers_resolver: TrustedPeersResolver::new(
@@ -154,6 +165,7 @@ impl PeersManager {
             connection_info: ConnectionInfo::new(connection_info),
             ban_list,
             backed_off_peers: Default::default(),
+            restored_backoffs: Default::default(),
             ban_duration,
             backoff_durations,
             trusted_nodes_only,
@@ -161,7 +173,19 @@ impl PeersManager {
             max_backoff_count,
             net_connection_state: NetworkConnectionState::default(),
             incoming_ip_throttle_duration,
+            dial_window_start: now,
+            dials_in_window: 0,
+        };
+
+        for (peer_id, state) in backoff_states {
+            if manager.peers.contains_key(&peer_id) {
+                manager.restore_backoff(peer_id, state);
+            } else {
+                manager.restored_backoffs.insert(peer_id, state);
+            }
         }
+
+        manager
     }
 
     /// Returns a new [`PeersHandle`] that can send commands to this type.
@@ -231,6 +255,53 @@ impl PeersManager {
         self.backed_off_peers.len()
     }
 
+    /// Returns the dial failure history of the peer, if it's in the peer set.
+    pub(crate) fn backoff_state(&self, peer_id: &PeerId) -> Option<PeerBackoffState> {
+        let peer = self.peers.get(peer_id)?;
+        let backed_off_until = self.backed_off_peers.get(peer_id).map(|until| {
+            unix_timestamp() + until.saturating_duration_since(std::time::Instant::now()).as_secs()
+        });
+        Some(PeerBackoffState {
+            severe_backoff_counter: peer.severe_backoff_counter,
+            backed_off_until,
+        })
+    }
+
+    /// Returns the dial failure history of all peers that failed before, including the restored
+    /// history of peers that were not discovered again.
+    #[cfg(any(test, feature = "serde"))]
+    pub(crate) fn backoff_states(&self) -> HashMap<PeerId, PeerBackoffState> {
+        let now = unix_timestamp();
+        let known = self
+            .peers
+            .keys()
+            .filter_map(|peer_id| Some((*peer_id, self.backoff_state(peer_id)?)))
+            .filter(|(_, state)| !state.is_empty());
+        self.restored_backoffs
+            .iter()
+            .filter(|(_, state)| {
+                state.severe_backoff_counter > 0 || state.remaining_backoff(now).is_some()
+            })
+            .map(|(peer_id, state)| (*peer_id, *state))
+            // the history of known peers takes precedence
+            .chain(known)
+            .collect()
+    }
+
+    /// Applies the dial failure history of the peer from a previous run.
+    fn restore_backoff(&mut self, peer_id: PeerId, state: PeerBackoffState) {
+        let Some(peer) = self.peers.get_mut(&peer_id) else { return };
+        if peer.is_trusted() || peer.is_static() {
+            return
+        }
+        peer.severe_backoff_counter = state.severe_backoff_counter;
+
+        if let Some(remaining) = state.remaining_backoff(unix_timestamp()) {
+            trace!(target: "net::peers", ?peer_id, ?remaining, "restoring backoff");
+            self.backoff_peer_until(peer_id, std::time::Instant::now() + remaining);
+        }
+    }
+
     /// Returns the number of idle trusted peers.
     fn num_idle_trusted_peers(&self) -> usize {
         self.peers.iter().filter(|(_, peer)| peer.kind.is_trusted() && peer.state.is_idle()).count()
@@ -646,13 +717,17 @@ impl PeersManager {
                                 peer.severe_backoff_counter.saturating_add(1);
                         }
 
-                        let backoff_time =
-                            self.backoff_durations.backoff_until(kind, peer.severe_backoff_counter);
+                        // randomize the backoff so that peers that failed at the same time, e.g.
+                        // on a network outage, are not all dialed again at the same time
+                        let backoff = self
+                            .backoff_durations
+                            .backoff_duration(kind, peer.severe_backoff_counter);
+                        let jitter = rand::rng().random_range(Duration::ZERO..=backoff / 4);
 
                         // The peer has signaled that it is currently unable to process any more
                         // connections, so we will hold off on attempting any new connections for a
                         // while
-                        backoff_until = Some(backoff_time);
+                        backoff_until = Some(std::time::Instant::now() + (backoff - jitter));
                     }
                 } else {
                     // If the error was not a backoff error, we reduce the peer's reputation
@@ -769,6 +844,10 @@ impl PeersManager {
                 peer.fork_id = fork_id;
                 entry.insert(peer);
                 self.queued_actions.push_back(PeerAction::PeerAdded(peer_id));
+
+                if let Some(state) = self.restored_backoffs.remove(&peer_id) {
+                    self.restore_backoff(peer_id, state);
+                }
             }
         }
 
@@ -926,8 +1005,14 @@ impl PeersManager {
             return
         }
 
+        let now = Instant::now();
+        if now.duration_since(self.dial_window_start) >= Duration::from_secs(1) {
+            self.dial_window_start = now;
+            self.dials_in_window = 0;
+        }
+
         // as long as there are slots available fill them with the best peers
-        while self.connection_info.has_out_capacity() {
+        while self.connection_info.has_out_capacity() && self.has_dial_rate_capacity() {
             let action = {
                 let (peer_id, peer) = match self.best_unconnected() {
                     Some(peer) => peer,
@@ -941,11 +1026,21 @@ impl PeersManager {
             };
 
             self.connection_info.inc_pending_out();
+            self.dials_in_window += 1;
 
             self.queued_actions.push_back(action);
         }
     }
 
+    /// Returns true if the configured maximum dial rate allows another outbound dial in the
+    /// current window.
+    const fn has_dial_rate_capacity(&self) -> bool {
+        match self.connection_info.config.max_dial_rate {
+            Some(max_dial_rate) => self.dials_in_window < max_dial_rate,
+            None => true,
+        }
+    }
+
     fn on_resolved_peer(&mut self, peer_id: PeerId, new_record: NodeRecord) {
         if let Some(peer) = self.peers.get_mut(&peer_id) {
             let new_addr = PeerAddr::new_with_ports(
@@ -1050,6 +1145,11 @@ impl Default for PeersManager {
     }
 }
 
+/// Returns the current unix timestamp in seconds.
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
 /// Tracks stats about connected nodes
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct ConnectionInfo {
@@ -1216,6 +1316,7 @@ mod tests {
         peers::reputation::DEFAULT_REPUTATION, BackoffKind, Peer, ReputationChangeKind,
     };
     use std::{
+        collections::HashMap,
         future::{poll_fn, Future},
         io,
         net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -1229,8 +1330,8 @@ mod tests {
     use crate::{
         error::SessionError,
         peers::{
-            ConnectionInfo, InboundConnectionError, PeerAction, PeerAddr, PeerBackoffDurations,
-            PeerConnectionState,
+            unix_timestamp, ConnectionInfo, InboundConnectionError, PeerAction, PeerAddr,
+            PeerBackoffDurations, PeerBackoffState, PeerConnectionState,
         },
         session::PendingSessionHandshakeError,
         PeersConfig,
@@ -1520,7 +1621,8 @@ mod tests {
             .backoff_durations
             .backoff_until(BackoffKind::High, peer_struct.severe_backoff_counter);
 
-        // Duration of the backoff should be 2 * 15 minutes = 30 minutes
+        // Duration of the backoff should be at least 2 * 15 minutes = 30 minutes, since the backoff
+        // doubles with every severe backoff
         let backoff_duration = std::time::Duration::new(30 * 60, 0);
 
         // We can't use assert_eq! since there is a very small diff in the nano secs
@@ -1528,6 +1630,74 @@ mod tests {
         assert!(backoff_time.duration_since(now) > backoff_duration);
     }
 
+    #[test]
+    fn test_exponential_backoff_duration() {
+        let durations = PeerBackoffDurations::default();
+        assert_eq!(durations.backoff_duration(BackoffKind::Low, 0), durations.low);
+        assert_eq!(durations.backoff_duration(BackoffKind::Low, 1), durations.low * 2);
+        assert_eq!(durations.backoff_duration(BackoffKind::Low, 3), durations.low * 8);
+        assert_eq!(durations.backoff_duration(BackoffKind::High, 3), durations.max);
+        assert_eq!(durations.backoff_duration(BackoffKind::Low, u8::MAX), durations.max);
+    }
+
+    #[tokio::test]
+    async fn test_restore_backoff_state() {
+        let peer = PeerId::random();
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let backed_off_until = unix_timestamp() + 60;
+        let state = PeerBackoffState {
+            severe_backoff_counter: 2,
+            backed_off_until: Some(backed_off_until),
+        };
+        let config = PeersConfig::test().with_backoff_states(HashMap::from([(peer, state)]));
+        let mut peers = PeersManager::new(config);
+        assert_eq!(peers.backoff_states(), HashMap::from([(peer, state)]));
+
+        peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+        match event!(peers) {
+            PeerAction::PeerAdded(peer_id) => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+
+        // the restored backoff prevents dialing the peer
+        poll_fn(|cx| {
+            assert!(peers.poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        assert!(peers.peers.get(&peer).unwrap().is_backed_off());
+
+        let restored = peers.backoff_state(&peer).unwrap();
+        assert_eq!(restored.severe_backoff_counter, 2);
+        assert!(restored.backed_off_until.unwrap().abs_diff(backed_off_until) <= 1);
+        assert!(peers.restored_backoffs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_max_dial_rate() {
+        let config = PeersConfig::test().with_max_dial_rate(1);
+        let mut peers = PeersManager::new(config);
+        for i in 0..3 {
+            let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, i)), 8008);
+            peers.add_peer(PeerId::random(), PeerAddr::from_tcp(socket_addr), None);
+        }
+
+        let mut num_dials = 0;
+        poll_fn(|cx| {
+            while let Poll::Ready(action) = peers.poll(cx) {
+                if matches!(action, PeerAction::Connect { .. }) {
+                    num_dials += 1;
+                }
+            }
+            Poll::Ready(())
+        })
+        .await;
+        assert_eq!(num_dials, 1);
+        assert_eq!(peers.num_pending_outbound_connections(), 1);
+    }
+
     #[tokio::test]
     async fn test_ban_on_active_drop() {
         let peer = PeerId::random();
@@ -12,7 +12,7 @@ use reth_eth_wire::{
 };
 use reth_network_api::PeerInfo;
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::PeerKind;
+use reth_network_types::{PeerBackoffState, PeerHeadStatus, PeerKind};
 use std::{io, net::SocketAddr, sync::Arc, time::Instant};
 use tokio::sync::{
     mpsc::{self, error::SendError},
@@ -136,7 +136,13 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
     }
 
     /// Extracts the [`PeerInfo`] from the session handle.
-    pub(crate) fn peer_info(&self, record: &NodeRecord, kind: PeerKind) -> PeerInfo {
+    pub(crate) fn peer_info(
+        &self,
+        record: &NodeRecord,
+        kind: PeerKind,
+        head_status: PeerHeadStatus,
+        backoff: PeerBackoffState,
+    ) -> PeerInfo {
         PeerInfo {
             remote_id: self.remote_id,
             direction: self.direction,
@@ -150,6 +156,8 @@ impl<N: NetworkPrimitives> ActiveSessionHandle<N> {
             status: self.status.clone(),
             session_established: self.established,
             kind,
+            head_status,
+            backoff,
         }
     }
 }
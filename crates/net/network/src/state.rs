@@ -19,7 +19,7 @@ use reth_eth_wire::{
 use reth_ethereum_forks::ForkId;
 use reth_network_api::{DiscoveredEvent, DiscoveryEvent, PeerRequest, PeerRequestSender};
 use reth_network_peers::PeerId;
-use reth_network_types::{PeerAddr, PeerKind};
+use reth_network_types::{PeerAddr, PeerHeadStatus, PeerKind};
 use reth_primitives_traits::Block;
 use std::{
     collections::{HashMap, VecDeque},
@@ -102,8 +102,11 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         discovery: Discovery,
         peers_manager: PeersManager,
         num_active_peers: Arc<AtomicUsize>,
+        stale_peer_threshold: u64,
     ) -> Self {
-        let state_fetcher = StateFetcher::new(peers_manager.handle(), num_active_peers);
+        let mut state_fetcher = StateFetcher::new(peers_manager.handle(), num_active_peers)
+            .with_stale_peer_threshold(stale_peer_threshold);
+        state_fetcher.update_local_head(client.best_block_number().unwrap_or_default());
         Self {
             active_peers: Default::default(),
             peers_manager,
@@ -263,6 +266,16 @@ impl<N: NetworkPrimitives> NetworkState<N> {
         self.state_fetcher.update_peer_block(peer_id, hash, number);
     }
 
+    /// Updates the local head that the heads of the peers are compared to.
+    pub(crate) const fn update_local_head(&mut self, number: u64) {
+        self.state_fetcher.update_local_head(number);
+    }
+
+    /// Returns the [`PeerHeadStatus`] of the peer.
+    pub(crate) fn peer_head_status(&self, peer_id: &PeerId) -> PeerHeadStatus {
+        self.state_fetcher.peer_head_status(peer_id).unwrap_or_default()
+    }
+
     /// Invoked when a new [`ForkId`] is activated.
     pub(crate) fn update_fork_id(&self, fork_id: ForkId) {
         self.discovery.update_fork_id(fork_id)
@@ -442,6 +455,13 @@ impl<N: NetworkPrimitives> NetworkState<N> {
                     FetchAction::BlockRequest { peer_id, request } => {
                         self.handle_block_request(peer_id, request)
                     }
+                    FetchAction::DisconnectStalePeer { peer_id } => {
+                        debug!(target: "net", ?peer_id, "Disconnecting stale peer");
+                        self.queued_messages.push_back(StateAction::Disconnect {
+                            peer_id,
+                            reason: Some(DisconnectReason::UselessPeer),
+                        });
+                    }
                 }
             }
 
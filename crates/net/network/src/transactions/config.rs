@@ -1,13 +1,19 @@
-use std::{fmt::Debug, marker::PhantomData, str::FromStr};
+use std::{fmt::Debug, marker::PhantomData, str::FromStr, time::Duration};
 
 use super::{
     PeerMetadata, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
     DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
-use crate::transactions::constants::tx_fetcher::{
-    DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
-    DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
+use crate::transactions::constants::{
+    tx_fetcher::{
+        DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
+        DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
+    },
+    tx_manager::{
+        DEFAULT_GOSSIP_MONITOR_ALERT_THRESHOLD, DEFAULT_GOSSIP_MONITOR_TIMEOUT,
+        DEFAULT_MAX_COUNT_GOSSIP_MONITOR_TRACKED,
+    },
 };
 use alloy_primitives::B256;
 use derive_more::{Constructor, Display};
@@ -25,6 +31,9 @@ pub struct TransactionsManagerConfig {
     /// How new pending transactions are propagated.
     #[cfg_attr(feature = "serde", serde(default))]
     pub propagation_mode: TransactionPropagationMode,
+    /// Configuration for monitoring transaction gossip.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub gossip_monitor: TxGossipMonitorConfig,
 }
 
 impl Default for TransactionsManagerConfig {
@@ -33,6 +42,7 @@ impl Default for TransactionsManagerConfig {
             transaction_fetcher_config: TransactionFetcherConfig::default(),
             max_transactions_seen_by_peer_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             propagation_mode: TransactionPropagationMode::default(),
+            gossip_monitor: TxGossipMonitorConfig::default(),
         }
     }
 }
@@ -100,6 +110,32 @@ impl Default for TransactionFetcherConfig {
     }
 }
 
+/// Configuration for the [`TxGossipMonitor`](super::monitor::TxGossipMonitor).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TxGossipMonitorConfig {
+    /// Whether announced transactions are tracked, disabled by default.
+    pub enabled: bool,
+    /// Duration after which a transaction that was announced by a single peer only is considered
+    /// lost.
+    pub timeout: Duration,
+    /// Max number of announced transactions to keep track of.
+    pub max_tracked: usize,
+    /// Number of consecutive lost transactions after which gossip is considered broken.
+    pub alert_threshold: usize,
+}
+
+impl Default for TxGossipMonitorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout: DEFAULT_GOSSIP_MONITOR_TIMEOUT,
+            max_tracked: DEFAULT_MAX_COUNT_GOSSIP_MONITOR_TRACKED,
+            alert_threshold: DEFAULT_GOSSIP_MONITOR_ALERT_THRESHOLD,
+        }
+    }
+}
+
 /// A policy defining which peers pending transactions are gossiped to.
 pub trait TransactionPropagationPolicy: Send + Sync + Unpin + 'static {
     /// Filter a given peer based on the policy.
@@ -36,6 +36,7 @@ pub const SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE: usize = 2 * 1024 *
 /// Constants used by [`TransactionsManager`](super::TransactionsManager).
 pub mod tx_manager {
     use super::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
+    use std::time::Duration;
 
     /// Default limit for number of transactions to keep track of for a single peer.
     ///
@@ -53,6 +54,20 @@ pub mod tx_manager {
     ///
     /// Default is 100 KiB, i.e. 3 200 transaction hashes.
     pub const DEFAULT_MAX_COUNT_BAD_IMPORTS: u32 = 100 * 1024 / 32;
+
+    /// Default duration after which a transaction that was announced by a single peer only is
+    /// considered lost by the [`TxGossipMonitor`](crate::transactions::monitor::TxGossipMonitor).
+    ///
+    /// Default is 2 minutes.
+    pub const DEFAULT_GOSSIP_MONITOR_TIMEOUT: Duration = Duration::from_secs(120);
+
+    /// Default limit for number of announced transactions the
+    /// [`TxGossipMonitor`](crate::transactions::monitor::TxGossipMonitor) keeps track of.
+    pub const DEFAULT_MAX_COUNT_GOSSIP_MONITOR_TRACKED: usize = 1024;
+
+    /// Default number of consecutive transactions that must be lost before the
+    /// [`TxGossipMonitor`](crate::transactions::monitor::TxGossipMonitor) considers gossip broken.
+    pub const DEFAULT_GOSSIP_MONITOR_ALERT_THRESHOLD: usize = 5;
 }
 
 /// Constants used by [`TransactionFetcher`](super::TransactionFetcher).
@@ -6,6 +6,8 @@ pub mod config;
 pub mod constants;
 /// Component responsible for fetching transactions from [`NewPooledTransactionHashes`].
 pub mod fetcher;
+/// Watchdog for transaction gossip.
+pub mod monitor;
 /// Defines the [`TransactionPolicies`] trait for aggregating transaction-related policies.
 pub mod policy;
 
@@ -16,8 +18,9 @@ pub use self::constants::{
 use config::{AnnouncementAcceptance, StrictEthAnnouncementFilter, TransactionPropagationKind};
 pub use config::{
     AnnouncementFilteringPolicy, TransactionFetcherConfig, TransactionPropagationMode,
-    TransactionPropagationPolicy, TransactionsManagerConfig,
+    TransactionPropagationPolicy, TransactionsManagerConfig, TxGossipMonitorConfig,
 };
+use monitor::TxGossipMonitor;
 use policy::{NetworkPolicies, TransactionPolicies};
 
 pub(crate) use fetcher::{FetchEvent, TransactionFetcher};
@@ -347,6 +350,8 @@ pub struct TransactionsManager<
     metrics: TransactionsManagerMetrics,
     /// `AnnouncedTxTypes` metrics
     announced_tx_types_metrics: AnnouncedTxTypesMetrics,
+    /// Tracks how quickly transactions of other nodes are announced to us by several peers.
+    gossip_monitor: TxGossipMonitor,
 }
 
 impl<Pool: TransactionPool, N: NetworkPrimitives>
@@ -405,6 +410,9 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
             .capacity_pending_pool_imports
             .increment(pending_pool_imports_info.max_pending_pool_imports as u64);
 
+        let gossip_monitor =
+            TxGossipMonitor::new(transactions_manager_config.gossip_monitor.clone());
+
         Self {
             pool,
             network,
@@ -428,6 +436,7 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
             policies,
             metrics,
             announced_tx_types_metrics: AnnouncedTxTypesMetrics::default(),
+            gossip_monitor,
         }
     }
 
@@ -627,6 +636,9 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
             self.report_already_seen(peer_id);
         }
 
+        // measure the propagation of transactions that were announced by another peer before
+        self.gossip_monitor.on_announced(peer_id, msg.iter_hashes(), Instant::now());
+
         // 1. filter out spam
         if msg.is_empty() {
             self.report_peer(peer_id, ReputationChangeKind::BadAnnouncement);
@@ -665,6 +677,13 @@ impl<Pool: TransactionPool, N: NetworkPrimitives, PBundle: TransactionPolicies>
             return
         }
 
+        // the remaining hashes are transactions we didn't originate and see for the first time
+        self.gossip_monitor.on_unknown_announced(
+            peer_id,
+            partially_valid_msg.keys(),
+            Instant::now(),
+        );
+
         // 4. filter out invalid entries (spam)
         //
         // validates messages with respect to the given network, e.g. allowed tx types
@@ -1625,6 +1644,9 @@ where
 
         this.transaction_fetcher.update_metrics();
 
+        // Expire transactions that were never announced by a second peer.
+        this.gossip_monitor.on_tick(Instant::now(), this.peers.len());
+
         // all channels are fully drained and import futures pending
         if maybe_more_network_events ||
             maybe_more_commands ||
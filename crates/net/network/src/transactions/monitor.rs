@@ -0,0 +1,273 @@
+//! Watchdog for transaction gossip.
+
+use super::config::TxGossipMonitorConfig;
+use crate::metrics::TxGossipMonitorMetrics;
+use alloy_primitives::TxHash;
+use reth_network_peers::PeerId;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Instant,
+};
+use tracing::{debug, warn};
+
+/// Measures how quickly transactions of other nodes propagate to us through the network.
+///
+/// Peers never announce a transaction back to the peer they received it from, so the propagation
+/// of our own transactions can't be observed. Instead, every transaction that is announced to us
+/// before we know it, i.e. a transaction we did not originate, is tracked until a second peer
+/// announces it as well. The time between the two announcements is the propagation duration.
+///
+/// In a healthy network, transactions reach us from several peers. If no other peer announces a
+/// transaction within the configured timeout, it is considered lost. Once the configured number of
+/// consecutive transactions got lost, gossip is considered broken and an alert is logged, since
+/// this usually means that we're not well connected to the rest of the network.
+#[derive(Debug)]
+pub struct TxGossipMonitor {
+    /// How the monitor is configured.
+    config: TxGossipMonitorConfig,
+    /// Transactions that were announced by a single peer so far, with that peer and the time of
+    /// the announcement.
+    pending: HashMap<TxHash, (PeerId, Instant)>,
+    /// Tracked transactions in the order they were first announced.
+    ///
+    /// Entries that were already announced by a second peer are removed lazily.
+    queue: VecDeque<(TxHash, Instant)>,
+    /// Number of consecutive transactions that weren't announced by a second peer in time.
+    consecutive_lost: usize,
+    /// Metrics for the monitor.
+    metrics: TxGossipMonitorMetrics,
+}
+
+impl TxGossipMonitor {
+    /// Creates a new monitor with the given config.
+    pub fn new(config: TxGossipMonitorConfig) -> Self {
+        Self {
+            config,
+            pending: Default::default(),
+            queue: Default::default(),
+            consecutive_lost: 0,
+            metrics: Default::default(),
+        }
+    }
+
+    /// Returns `true` if announced transactions are tracked.
+    pub const fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Returns the number of tracked transactions.
+    pub fn tracked(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns `true` if gossip is considered broken, i.e. the configured number of consecutive
+    /// transactions were announced by a single peer only.
+    pub const fn is_gossip_broken(&self) -> bool {
+        self.config.alert_threshold > 0 && self.consecutive_lost >= self.config.alert_threshold
+    }
+
+    /// Starts tracking transactions that were announced by `peer_id` at `now` and that we didn't
+    /// know before.
+    pub fn on_unknown_announced<'a>(
+        &mut self,
+        peer_id: PeerId,
+        hashes: impl IntoIterator<Item = &'a TxHash>,
+        now: Instant,
+    ) {
+        if !self.is_enabled() || self.config.max_tracked == 0 {
+            return
+        }
+
+        for hash in hashes {
+            if self.pending.contains_key(hash) {
+                continue
+            }
+
+            // make room for the new transaction, dropping the oldest one without judging it
+            while self.pending.len() >= self.config.max_tracked {
+                let Some((oldest, announced_at)) = self.queue.pop_front() else { break };
+                if self.pending.get(&oldest).is_some_and(|(_, at)| *at == announced_at) {
+                    self.pending.remove(&oldest);
+                }
+            }
+
+            self.pending.insert(*hash, (peer_id, now));
+            self.queue.push_back((*hash, now));
+        }
+        self.metrics.tracked_transactions.set(self.pending.len() as f64);
+    }
+
+    /// Invoked when `peer_id` announced the given transaction hashes to us at `now`.
+    ///
+    /// Returns the number of tracked transactions that were announced by a second peer.
+    pub fn on_announced<'a>(
+        &mut self,
+        peer_id: PeerId,
+        hashes: impl IntoIterator<Item = &'a TxHash>,
+        now: Instant,
+    ) -> usize {
+        if self.pending.is_empty() {
+            return 0
+        }
+
+        let mut reannounced = 0;
+        for hash in hashes {
+            let Some(&(first_peer, announced_at)) = self.pending.get(hash) else { continue };
+            if first_peer == peer_id {
+                continue
+            }
+            self.pending.remove(hash);
+            self.metrics
+                .propagation_duration_seconds
+                .record(now.saturating_duration_since(announced_at).as_secs_f64());
+            reannounced += 1;
+        }
+
+        if reannounced > 0 {
+            self.metrics.reannounced_transactions.increment(reannounced as u64);
+            self.metrics.tracked_transactions.set(self.pending.len() as f64);
+            if self.is_gossip_broken() {
+                debug!(target: "net::tx::gossip_monitor", "Transaction gossip recovered");
+            }
+            self.consecutive_lost = 0;
+            self.metrics.gossip_broken.set(0.0);
+        }
+
+        reannounced
+    }
+
+    /// Expires all tracked transactions that were first announced more than the configured timeout
+    /// before `now`.
+    ///
+    /// Expired transactions are only considered lost if we're connected to more than one peer,
+    /// otherwise no second peer could have announced them.
+    ///
+    /// Returns the number of transactions that were considered lost.
+    pub fn on_tick(&mut self, now: Instant, peers: usize) -> usize {
+        let mut expired = 0;
+        while let Some(&(hash, announced_at)) = self.queue.front() {
+            if now.saturating_duration_since(announced_at) < self.config.timeout {
+                break
+            }
+            self.queue.pop_front();
+            // skip entries that were already announced by a second peer
+            if self.pending.get(&hash).is_some_and(|(_, at)| *at == announced_at) {
+                self.pending.remove(&hash);
+                expired += 1;
+            }
+        }
+
+        if expired == 0 {
+            return 0
+        }
+
+        self.metrics.tracked_transactions.set(self.pending.len() as f64);
+        if peers < 2 {
+            return 0
+        }
+
+        self.metrics.lost_transactions.increment(expired as u64);
+
+        let was_broken = self.is_gossip_broken();
+        self.consecutive_lost += expired;
+        if !was_broken && self.is_gossip_broken() {
+            self.metrics.gossip_broken.set(1.0);
+            warn!(target: "net::tx::gossip_monitor",
+                consecutive_lost=self.consecutive_lost,
+                timeout=?self.config.timeout,
+                peers,
+                "Transactions are only announced by a single peer, transaction gossip may be broken"
+            );
+        }
+
+        expired
+    }
+}
+
+impl Default for TxGossipMonitor {
+    fn default() -> Self {
+        Self::new(TxGossipMonitorConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use std::time::Duration;
+
+    fn config() -> TxGossipMonitorConfig {
+        TxGossipMonitorConfig {
+            enabled: true,
+            timeout: Duration::from_secs(10),
+            max_tracked: 2,
+            alert_threshold: 2,
+        }
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let mut monitor = TxGossipMonitor::default();
+        monitor.on_unknown_announced(PeerId::random(), &[B256::with_last_byte(1)], Instant::now());
+        assert!(!monitor.is_enabled());
+        assert_eq!(monitor.tracked(), 0);
+    }
+
+    #[test]
+    fn reannounced_transactions_are_not_lost() {
+        let mut monitor = TxGossipMonitor::new(config());
+        let now = Instant::now();
+        let (peer_a, peer_b) = (PeerId::random(), PeerId::random());
+        let hash = B256::with_last_byte(1);
+
+        monitor.on_unknown_announced(peer_a, &[hash], now);
+        assert_eq!(monitor.tracked(), 1);
+
+        // announcements of the same peer don't count
+        assert_eq!(monitor.on_announced(peer_a, &[hash], now + Duration::from_secs(1)), 0);
+        assert_eq!(monitor.on_announced(peer_b, &[hash], now + Duration::from_secs(1)), 1);
+        assert_eq!(monitor.tracked(), 0);
+        assert_eq!(monitor.on_tick(now + Duration::from_secs(20), 2), 0);
+        assert!(!monitor.is_gossip_broken());
+    }
+
+    #[test]
+    fn detects_broken_gossip() {
+        let mut monitor = TxGossipMonitor::new(config());
+        let now = Instant::now();
+        let (peer_a, peer_b) = (PeerId::random(), PeerId::random());
+
+        monitor.on_unknown_announced(
+            peer_a,
+            &[B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3)],
+            now,
+        );
+        // exceeds capacity, oldest is dropped
+        assert_eq!(monitor.tracked(), 2);
+
+        assert_eq!(monitor.on_tick(now + Duration::from_secs(5), 2), 0);
+        assert_eq!(monitor.on_tick(now + Duration::from_secs(10), 2), 2);
+        assert!(monitor.is_gossip_broken());
+
+        // a transaction that is announced by a second peer recovers
+        let later = now + Duration::from_secs(11);
+        monitor.on_unknown_announced(peer_a, &[B256::with_last_byte(4)], later);
+        monitor.on_announced(peer_b, &[B256::with_last_byte(4)], later);
+        assert!(!monitor.is_gossip_broken());
+    }
+
+    #[test]
+    fn single_peer_is_not_broken_gossip() {
+        let mut monitor = TxGossipMonitor::new(config());
+        let now = Instant::now();
+
+        monitor.on_unknown_announced(
+            PeerId::random(),
+            &[B256::with_last_byte(1), B256::with_last_byte(2)],
+            now,
+        );
+        assert_eq!(monitor.on_tick(now + Duration::from_secs(10), 1), 0);
+        assert_eq!(monitor.tracked(), 0);
+        assert!(!monitor.is_gossip_broken());
+    }
+}
@@ -6,16 +6,18 @@ use reth_basic_payload_builder::PayloadBuilder;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
 use reth_engine_primitives::{BeaconConsensusEngineEvent, BeaconConsensusEngineHandle};
-use reth_evm::ConfigureEvm;
+use reth_evm::{precompile_stats::PrecompileStatsStore, ConfigureEvm};
 use reth_network_api::FullNetwork;
 use reth_node_core::node_config::NodeConfig;
 use reth_node_types::{NodeTypes, NodeTypesWithDBAdapter, TxTy};
 use reth_payload_builder::PayloadBuilderHandle;
-use reth_provider::FullProvider;
+use reth_provider::{FullProvider, HistoricalWitnessProvider, TraceIndexProvider};
+use reth_prune_types::PrunePins;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
-use std::{fmt::Debug, future::Future, marker::PhantomData};
+use std::{fmt::Debug, future::Future, marker::PhantomData, sync::Arc};
+use tokio::sync::watch;
 
 /// A helper trait that is downstream of the [`NodeTypes`] trait and adds stateful
 /// components to the node.
@@ -118,6 +120,14 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub engine_events: EventSender<BeaconConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// The prune segment pins of all `ExEx`'s.
+    pub exex_prune_pins: watch::Receiver<PrunePins>,
+    /// The precompile usage of the recently executed blocks.
+    pub precompile_stats: PrecompileStatsStore,
+    /// Provider for computing execution witnesses on top of persisted historical state.
+    pub historical_witness_provider: Arc<dyn HistoricalWitnessProvider>,
+    /// Index consulted by `trace_filter`, if enabled.
+    pub trace_index: Option<Arc<dyn TraceIndexProvider>>,
 }
 
 /// Customizable node add-on types.
@@ -162,6 +172,8 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
 /// - Node configuration
 /// - Engine API handles for consensus layer communication
 /// - JWT secrets for authenticated endpoints
+/// - Prune segment pins of `ExEx`es
+/// - Precompile usage of the recently executed blocks
 ///
 /// This ensures add-ons can integrate deeply with the node while maintaining clean separation
 /// of concerns.
@@ -34,7 +34,7 @@ use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     ChainSpecProvider, FullProvider,
 };
-use reth_tasks::TaskExecutor;
+use reth_tasks::{runtime::RuntimeComponent, TaskExecutor};
 use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
 use secp256k1::SecretKey;
 use std::{fmt::Debug, sync::Arc};
@@ -793,12 +793,16 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
             .request_handler(self.provider().clone())
             .split_with_handle();
 
-        self.executor.spawn_critical("p2p txpool", txpool);
-        self.executor.spawn_critical("p2p eth request handler", eth);
+        let executor = self.executor.for_component(RuntimeComponent::Network);
+        executor.spawn_critical("p2p txpool", txpool);
+        executor.spawn_critical("p2p eth request handler", eth);
 
         let default_peers_path = self.config().datadir().known_peers();
+        let peer_backoffs_file = self.config().network.persistent_peer_backoffs_file(
+            self.config().network.peers_file.clone().unwrap_or_else(|| default_peers_path.clone()),
+        );
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
-        self.executor.spawn_critical_with_graceful_shutdown_signal(
+        executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
             |shutdown| {
                 network.run_until_graceful_shutdown(shutdown, |network| {
@@ -814,6 +818,14 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
                             }
                         }
                     }
+                    if let Some(backoffs_file) = peer_backoffs_file {
+                        trace!(target: "reth::cli", backoffs_file=?backoffs_file, "Saving peer backoffs");
+                        if let Err(err) =
+                            network.write_peer_backoffs_to_file(backoffs_file.as_path())
+                        {
+                            warn!(target: "reth::cli", %err, "Failed to write peer backoffs to file");
+                        }
+                    }
                 })
             },
         );
@@ -849,7 +861,12 @@ impl<Node: FullNodeTypes<Types: NodeTypes<ChainSpec: Hardforks>>> BuilderContext
         N: NetworkPrimitives,
     {
         let network_config = self.network_config()?;
-        let builder = NetworkManager::builder(network_config).await?;
+        // bind the sockets and spawn the discovery tasks on the runtime of the network
+        let builder = self
+            .executor
+            .for_component(RuntimeComponent::Network)
+            .in_runtime(NetworkManager::builder(network_config))
+            .await?;
         Ok(builder)
     }
 
@@ -878,7 +895,7 @@ impl<Node: FullNodeTypes<Types: NodeTypes<ChainSpec: Hardforks>>> BuilderContext
                 secret_key,
                 default_peers_path,
             )
-            .with_task_executor(Box::new(self.executor.clone()))
+            .with_task_executor(Box::new(self.executor.for_component(RuntimeComponent::Network)))
             .set_head(self.head);
 
         Ok(builder)
@@ -54,7 +54,7 @@ use reth_invalid_block_hooks::InvalidBlockWitnessHook;
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
 use reth_node_core::{
-    args::{DefaultEraHost, InvalidBlockHookType},
+    args::{DefaultEraHost, InvalidBlockHookType, RuntimeArgs},
     dirs::{ChainPath, DataDirPath},
     node_config::NodeConfig,
     primitives::BlockHeader,
@@ -85,7 +85,10 @@ use reth_stages::{
     StageId,
 };
 use reth_static_file::StaticFileProducer;
-use reth_tasks::TaskExecutor;
+use reth_tasks::{
+    runtime::{DedicatedRuntimes, RuntimeComponent},
+    TaskExecutor,
+};
 use reth_tracing::tracing::{debug, error, info, warn};
 use reth_transaction_pool::TransactionPool;
 use std::{sync::Arc, thread::available_parallelism};
@@ -95,6 +98,7 @@ use tokio::sync::{
 };
 
 use futures::{future::Either, stream, Stream, StreamExt};
+use reth_node_checkpoints::{CheckpointService, RETH_CHECKPOINT_SIGNERS};
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node::NodeEvent};
 
 /// Reusable setup for launching a node.
@@ -168,6 +172,9 @@ impl LaunchContext {
         Self::save_pruning_config_if_full_node(&mut toml_config, config, &config_path)?;
 
         info!(target: "reth::cli", path = ?config_path, "Configuration loaded");
+        for change in toml_config.diff(&reth_config::Config::default()) {
+            info!(target: "reth::cli", %change, "Configuration differs from default");
+        }
 
         // Update the config with the command line arguments
         toml_config.peers.trusted_nodes_only = config.network.trusted_only;
@@ -196,6 +203,32 @@ impl LaunchContext {
         Ok(())
     }
 
+    /// Runs the components of the node that are configured with `--runtime.*` on dedicated tokio
+    /// runtimes.
+    ///
+    /// The task executor of the context is replaced with one that is aware of the dedicated
+    /// runtimes, see [`TaskExecutor::for_component`]. This also spawns the task that records the
+    /// utilization of all runtimes.
+    pub fn with_dedicated_runtimes(mut self, args: &RuntimeArgs) -> eyre::Result<Self> {
+        let mut runtimes = DedicatedRuntimes::default();
+        for (component, threads) in [
+            (RuntimeComponent::Network, args.network_threads),
+            (RuntimeComponent::Engine, args.engine_threads),
+            (RuntimeComponent::Rpc, args.rpc_threads),
+        ] {
+            if let Some(threads) = threads {
+                runtimes = runtimes
+                    .with_runtime(component, threads)
+                    .wrap_err_with(|| format!("Failed to build dedicated {component} runtime"))?;
+                info!(target: "reth::cli", %component, threads, "Running on dedicated runtime");
+            }
+        }
+
+        self.task_executor = self.task_executor.with_dedicated_runtimes(runtimes);
+        self.task_executor.spawn_runtime_metrics_task();
+        Ok(self)
+    }
+
     /// Convenience function to [`Self::configure_globals`]
     pub fn with_configured_globals(self, reserved_cpu_cores: usize) -> Self {
         self.configure_globals(reserved_cpu_cores);
@@ -472,7 +505,10 @@ where
         let factory = ProviderFactory::new(
             self.right().clone(),
             self.chain_spec(),
-            StaticFileProvider::read_write(self.data_dir().static_files())?,
+            StaticFileProvider::read_write_with_lock_takeover(
+                self.data_dir().static_files(),
+                self.node_config().db.lock_takeover_host(),
+            )?,
         )
         .with_prune_modes(self.prune_modes())
         .with_static_files_metrics();
@@ -870,6 +906,61 @@ where
         Ok(initial_target)
     }
 
+    /// Returns the [`CheckpointService`] if fetching a signed checkpoint manifest is configured.
+    ///
+    /// Manifests are verified against the configured `--checkpoints.signers`, or the embedded
+    /// [`RETH_CHECKPOINT_SIGNERS`] if none are configured.
+    pub fn checkpoint_service(&self) -> Option<CheckpointService<T::Provider>> {
+        let args = &self.node_config().checkpoints;
+        let url = args.url()?.clone();
+
+        let signers = if args.signers.is_empty() {
+            RETH_CHECKPOINT_SIGNERS.to_vec()
+        } else {
+            args.signers.clone()
+        };
+        if signers.is_empty() {
+            warn!(target: "reth::cli", %url, "No trusted checkpoint manifest signers configured, ignoring checkpoint manifest");
+            return None
+        }
+
+        Some(
+            CheckpointService::new(
+                url,
+                self.chain_id().id(),
+                signers,
+                self.blockchain_db().clone(),
+            )
+            .with_interval(args.interval),
+        )
+    }
+
+    /// Returns the hash of the latest checkpoint of the manifest if `--checkpoints.anchor` is set
+    /// and the local chain is behind it.
+    ///
+    /// Fetching the manifest gives up after the timeout of the service, see
+    /// [`CheckpointService::with_timeout`], in which case the node starts without an anchor.
+    pub async fn checkpoint_anchor(
+        &self,
+        service: &CheckpointService<T::Provider>,
+    ) -> Option<B256> {
+        if !self.node_config().checkpoints.anchor {
+            return None
+        }
+
+        let manifest = service
+            .fetch()
+            .await
+            .inspect_err(
+                |err| warn!(target: "reth::cli", %err, "Failed to fetch checkpoint anchor"),
+            )
+            .ok()?;
+        let checkpoint =
+            manifest.latest().filter(|checkpoint| checkpoint.number > self.head().number)?;
+        info!(target: "reth::cli", number = checkpoint.number, hash = %checkpoint.hash, "Using signed checkpoint as sync anchor");
+        Some(checkpoint.hash)
+    }
+
     /// Returns true if the node should terminate after the initial backfill run.
     ///
     /// This is the case if any of these configs are set:
@@ -1013,13 +1104,13 @@ where
     /// Returns `Some(EraImportSource)` if ERA is enabled in the node config, otherwise `None`.
     pub fn era_import_source(&self) -> Option<EraImportSource> {
         let node_config = self.node_config();
-        if !node_config.era.enabled {
+        if !node_config.era.is_enabled() {
             return None;
         }
 
         EraImportSource::maybe_new(
             node_config.era.source.path.clone(),
-            node_config.era.source.url.clone(),
+            node_config.era.url(),
             || node_config.chain.chain().kind().default_era_host(),
             || node_config.datadir().data_dir().join("era").into(),
         )
@@ -8,17 +8,21 @@ use crate::{
     AddOns, AddOnsContext, FullNode, LaunchContext, LaunchNode, NodeAdapter,
     NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle, NodeTypesAdapter,
 };
-use alloy_consensus::BlockHeader;
+use alloy_consensus::{transaction::Recovered, BlockHeader};
 use futures::{stream_select, StreamExt};
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
 use reth_engine_service::service::{ChainEvent, EngineService};
 use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
-    tree::TreeConfig,
+    tree::{PrewarmTransactions, TreeConfig},
 };
 use reth_engine_util::EngineMessageStreamExt;
-use reth_exex::ExExManagerHandle;
+use reth_evm::precompile_stats::PrecompileStatsStore;
+use reth_exex::{
+    trace_index::{TraceIndex, TraceIndexExEx},
+    ExExContext, ExExManagerHandle,
+};
 use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
@@ -27,20 +31,25 @@ use reth_node_api::{
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
     exit::NodeExitFuture,
-    primitives::Head,
+    primitives::{Head, NodePrimitives, SealedHeaderFor},
 };
 use reth_node_events::node;
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
-    BlockNumReader,
+    BlockNumReader, TraceIndexProvider,
 };
-use reth_tasks::TaskExecutor;
+use reth_tasks::{runtime::RuntimeComponent, TaskExecutor};
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info};
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use reth_trie_parallel::witness::ParallelWitnessProvider;
 use std::sync::Arc;
-use tokio::sync::{mpsc::unbounded_channel, oneshot};
+use tokio::sync::{mpsc::unbounded_channel, oneshot, watch};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
+/// The maximum number of pool transactions used to prewarm the caches for the next payload.
+const MAX_PREWARM_TRANSACTIONS: usize = 1_000;
+
 /// The engine node launcher.
 #[derive(Debug)]
 pub struct EngineNodeLauncher {
@@ -86,13 +95,14 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: mut installed_exex, add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
 
         // setup the launch context
         let ctx = ctx
+            .with_dedicated_runtimes(&config.runtime)?
             .with_configured_globals(engine_tree_config.reserved_cpu_cores())
             // load the toml config
             .with_loaded_toml_config(config)?
@@ -126,6 +136,18 @@ where
         // Try to expire pre-merge transaction history if configured
         ctx.expire_pre_merge_transactions()?;
 
+        // install the trace index exex if enabled
+        let trace_index = ctx.node_config().rpc.rpc_trace_index.then(TraceIndex::default);
+        if let Some(trace_index) = trace_index.clone() {
+            let start_block = ctx.node_config().rpc.rpc_trace_index_start_block;
+            installed_exex.push((
+                "trace-index".to_string(),
+                Box::new(move |exex_ctx: ExExContext<NodeAdapter<T, CB::Components>>| async move {
+                    eyre::Ok(TraceIndexExEx::new(exex_ctx, trace_index, start_block)?.start())
+                }),
+            ));
+        }
+
         // spawn exexs if any
         let maybe_exex_manager_handle = ctx.launch_exex(installed_exex).await?;
 
@@ -169,8 +191,9 @@ where
 
         let mut pruner_builder = ctx.pruner_builder();
         if let Some(exex_manager_handle) = &maybe_exex_manager_handle {
-            pruner_builder =
-                pruner_builder.finished_exex_height(exex_manager_handle.finished_height());
+            pruner_builder = pruner_builder
+                .finished_exex_height(exex_manager_handle.finished_height())
+                .exex_prune_pins(exex_manager_handle.prune_pins());
         }
         let pruner = pruner_builder.build_with_provider_factory(ctx.provider_factory().clone());
         let pruner_events = pruner.events();
@@ -183,12 +206,26 @@ where
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
+        let precompile_stats = PrecompileStatsStore::default();
+
         let add_ons_ctx = AddOnsContext {
             node: ctx.node_adapter().clone(),
             config: ctx.node_config(),
             beacon_engine_handle: beacon_engine_handle.clone(),
             jwt_secret,
             engine_events: event_sender.clone(),
+            exex_prune_pins: maybe_exex_manager_handle
+                .as_ref()
+                .map(ExExManagerHandle::prune_pins)
+                .unwrap_or_else(|| watch::channel(Default::default()).1),
+            precompile_stats: precompile_stats.clone(),
+            historical_witness_provider: Arc::new(ParallelWitnessProvider::new(
+                ctx.blockchain_db().clone(),
+                ctx.task_executor().handle().clone(),
+                engine_tree_config.max_proof_task_concurrency() as usize,
+            )),
+            trace_index: trace_index
+                .map(|trace_index| Arc::new(trace_index) as Arc<dyn TraceIndexProvider>),
         };
         let engine_payload_validator = add_ons.engine_validator(&add_ons_ctx).await?;
 
@@ -213,7 +250,7 @@ where
             network_client.clone(),
             Box::pin(consensus_engine_stream),
             pipeline,
-            Box::new(ctx.task_executor().clone()),
+            Box::new(ctx.task_executor().for_component(RuntimeComponent::Engine)),
             ctx.provider_factory().clone(),
             ctx.blockchain_db().clone(),
             pruner,
@@ -221,6 +258,8 @@ where
             engine_payload_validator,
             engine_tree_config,
             ctx.invalid_block_hook().await?,
+            precompile_stats,
+            Box::new(PoolPrewarmTransactions(ctx.components().pool().clone())),
             ctx.sync_metrics_tx(),
             ctx.components().evm_config().clone(),
         );
@@ -244,11 +283,40 @@ where
             ),
         );
 
+        #[cfg(any(feature = "firehose-nats", feature = "firehose-kafka"))]
+        if let Some(config) = ctx.toml_config().firehose.clone() {
+            let (firehose_handle, firehose) = reth_node_firehose::firehose(config.clone());
+            if config.transactions {
+                let pool = ctx.components().pool().clone();
+                ctx.task_executor()
+                    .spawn(Box::pin(firehose_handle.clone().publish_pool_events(pool)));
+            }
+            if config.blocks {
+                let notifications = ctx.blockchain_db().canonical_state_stream();
+                ctx.task_executor()
+                    .spawn(Box::pin(firehose_handle.publish_canonical_events(notifications)));
+            }
+            ctx.task_executor().spawn(Box::pin(firehose.run()));
+        }
+        #[cfg(not(any(feature = "firehose-nats", feature = "firehose-kafka")))]
+        if ctx.toml_config().firehose.is_some() {
+            reth_tracing::tracing::warn!(
+                target: "reth::cli",
+                "Ignoring the firehose config, reth was built without the firehose features"
+            );
+        }
+
         let RpcHandle { rpc_server_handles, rpc_registry, engine_events, beacon_engine_handle } =
             add_ons.launch_add_ons(add_ons_ctx).await?;
 
         // Run consensus engine to completion
-        let initial_target = ctx.initial_backfill_target()?;
+        let mut initial_target = ctx.initial_backfill_target()?;
+        if let Some(checkpoint_service) = ctx.checkpoint_service() {
+            if initial_target.is_none() {
+                initial_target = ctx.checkpoint_anchor(&checkpoint_service).await;
+            }
+            ctx.task_executor().spawn(Box::pin(checkpoint_service.run()));
+        }
         let mut built_payloads = ctx
             .components()
             .payload_builder_handle()
@@ -264,7 +332,8 @@ where
         let terminate_after_backfill = ctx.terminate_after_initial_backfill();
 
         info!(target: "reth::cli", "Starting consensus engine");
-        ctx.task_executor().spawn_critical("consensus engine", async move {
+        let engine_executor = ctx.task_executor().for_component(RuntimeComponent::Engine);
+        engine_executor.spawn_critical("consensus engine", async move {
             if let Some(initial_target) = initial_target {
                 debug!(target: "reth::cli", %initial_target,  "start backfill sync");
                 engine_service.orchestrator_mut().start_backfill_sync(initial_target);
@@ -359,3 +428,28 @@ where
         Ok(handle)
     }
 }
+
+/// Provides the best transactions of the pool, up to the gas limit of the parent block and at most
+/// [`MAX_PREWARM_TRANSACTIONS`], to prewarm the caches for the next payload.
+#[derive(Debug)]
+struct PoolPrewarmTransactions<Pool>(Pool);
+
+impl<N, Pool> PrewarmTransactions<N> for PoolPrewarmTransactions<Pool>
+where
+    N: NodePrimitives,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = N::SignedTx>> + 'static,
+{
+    fn prewarm_transactions(&self, parent: &SealedHeaderFor<N>) -> Vec<Recovered<N::SignedTx>> {
+        let mut gas_left = parent.gas_limit();
+        let mut transactions = Vec::new();
+        for tx in self.0.best_transactions().take(MAX_PREWARM_TRANSACTIONS) {
+            // the payload is full
+            if tx.gas_limit() > gas_left {
+                break
+            }
+            gas_left -= tx.gas_limit();
+            transactions.push(tx.to_consensus());
+        }
+        transactions
+    }
+}
@@ -7,27 +7,44 @@ use crate::{BeaconConsensusEngineEvent, BeaconConsensusEngineHandle};
 use alloy_rpc_types::engine::ClientVersionV1;
 use alloy_rpc_types_engine::ExecutionData;
 use jsonrpsee::{core::middleware::layer::Either, RpcModule};
-use reth_chain_state::CanonStateSubscriptions;
+use reth_chain_state::{CanonStateSubscriptions, ForkChoiceSubscriptions};
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 use reth_node_api::{
     AddOnsContext, BlockTy, EngineTypes, EngineValidator, FullNodeComponents, FullNodeTypes,
     NodeAddOns, NodeTypes, PayloadTypes, ReceiptTy,
 };
 use reth_node_core::{
+    args::RpcServerArgs,
     node_config::NodeConfig,
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadStore};
-use reth_rpc::eth::{EthApiTypes, FullEthApiServer};
-use reth_rpc_api::{eth::helpers::AddDevSigners, IntoEngineApiRpcModule};
+use reth_provider::BlockIdReader;
+use reth_rpc::{
+    eth::{EthApiTypes, EthBundleForwarder, FullEthApiServer, TxForwarderConfig},
+    ArchiveStateFetcher, DebugExecuteBlock, RethPrecompiles, RethPrune,
+};
+use reth_rpc_api::{
+    eth::helpers::AddDevSigners, DebugApiServer, DebugExecuteBlockApiServer, EthBundleApiServer,
+    EthBundleStatusApiServer, IntoEngineApiRpcModule, RethPrecompileApiServer, RethPruneApiServer,
+    RpcUsageApiServer, TraceApiServer,
+};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
+    canonical_epoch::CanonicalEpochTracker,
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, Stack,
+    response_cache::{update_response_cache_task, RpcResponseCache},
+    usage::RpcUsageTracker,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, Stack,
     TransportRpcModules,
 };
-use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
+use reth_rpc_engine_api::{
+    capabilities::EngineCapabilities, EngineApi, ParentBeaconBlockRootVerifier,
+};
 use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
+#[cfg(feature = "graphql")]
+use reth_rpc_graphql::{graphql_schema, GraphQlLayer};
+use reth_tasks::runtime::RuntimeComponent;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, info};
 use std::{
@@ -404,6 +421,9 @@ struct RpcSetupContext<'a, Node: FullNodeComponents, EthApi: EthApiTypes> {
     on_rpc_started: Box<dyn OnRpcStarted<Node, EthApi>>,
     engine_events: EventSender<BeaconConsensusEngineEvent<<Node::Types as NodeTypes>::Primitives>>,
     engine_handle: BeaconConsensusEngineHandle<<Node::Types as NodeTypes>::Payload>,
+    rpc_usage: Option<RpcUsageTracker>,
+    #[cfg(feature = "graphql")]
+    graphql: Option<GraphQlLayer>,
 }
 
 /// Node add-ons containing RPC server configuration, with customizable eth API handler.
@@ -655,10 +675,33 @@ where
             on_rpc_started,
             engine_events,
             engine_handle,
+            rpc_usage,
+            #[cfg(feature = "graphql")]
+            graphql,
         } = setup_ctx;
 
-        let server_config = config.rpc.rpc_server_config().set_rpc_middleware(rpc_middleware);
-        let rpc_server_handle = Self::launch_rpc_server_internal(server_config, &modules).await?;
+        let mut server_config = config
+            .rpc
+            .rpc_server_config()
+            .with_rpc_usage(rpc_usage)
+            .with_rate_limits(config.rpc.rpc_rate_limits()?)
+            .with_access_log(config.rpc.rpc_access_log()?)
+            .with_roles(config.rpc.rpc_roles()?)
+            .with_response_cache(spawn_rpc_response_cache(&node, &config.rpc))
+            .with_canonical_epoch(Some(CanonicalEpochTracker::new(node.provider().clone())))
+            .set_rpc_middleware(rpc_middleware);
+        #[cfg(feature = "graphql")]
+        {
+            server_config = server_config.with_graphql(graphql);
+        }
+        if let Some(domains) = config.rpc.cors_file_domains()? {
+            server_config = server_config.with_cors(Some(domains));
+        }
+        let rpc_server_handle = node
+            .task_executor()
+            .for_component(RuntimeComponent::Rpc)
+            .in_runtime(Self::launch_rpc_server_internal(server_config, &modules))
+            .await?;
 
         let handles =
             RethRpcServerHandles { rpc: rpc_server_handle.clone(), auth: AuthServerHandle::noop() };
@@ -702,15 +745,37 @@ where
             on_rpc_started,
             engine_events,
             engine_handle,
+            rpc_usage,
+            #[cfg(feature = "graphql")]
+            graphql,
         } = setup_ctx;
 
-        let server_config = config.rpc.rpc_server_config().set_rpc_middleware(rpc_middleware);
+        let mut server_config = config
+            .rpc
+            .rpc_server_config()
+            .with_rpc_usage(rpc_usage)
+            .with_rate_limits(config.rpc.rpc_rate_limits()?)
+            .with_access_log(config.rpc.rpc_access_log()?)
+            .with_roles(config.rpc.rpc_roles()?)
+            .with_response_cache(spawn_rpc_response_cache(&node, &config.rpc))
+            .with_canonical_epoch(Some(CanonicalEpochTracker::new(node.provider().clone())))
+            .set_rpc_middleware(rpc_middleware);
+        #[cfg(feature = "graphql")]
+        {
+            server_config = server_config.with_graphql(graphql);
+        }
+        if let Some(domains) = config.rpc.cors_file_domains()? {
+            server_config = server_config.with_cors(Some(domains));
+        }
         let auth_module_clone = auth_module.clone();
+        let rpc_executor = node.task_executor().for_component(RuntimeComponent::Rpc);
+        let engine_executor = node.task_executor().for_component(RuntimeComponent::Engine);
 
-        // launch servers concurrently
+        // launch servers concurrently, each on the runtime of its component
         let (rpc, auth) = futures::future::try_join(
-            Self::launch_rpc_server_internal(server_config, &modules),
-            Self::launch_auth_server_internal(auth_module_clone, auth_config),
+            rpc_executor.in_runtime(Self::launch_rpc_server_internal(server_config, &modules)),
+            engine_executor
+                .in_runtime(Self::launch_auth_server_internal(auth_module_clone, auth_config)),
         )
         .await?;
 
@@ -746,26 +811,42 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            exex_prune_pins,
+            precompile_stats,
+            historical_witness_provider,
+            trace_index,
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
+        let rpc_executor = node.task_executor().for_component(RuntimeComponent::Rpc);
         let cache = EthStateCache::spawn_with(
             node.provider().clone(),
             config.rpc.eth_config().cache,
-            node.task_executor().clone(),
+            rpc_executor.clone(),
         );
 
         let new_canonical_blocks = node.provider().canonical_state_stream();
         let c = cache.clone();
-        node.task_executor().spawn_critical(
+        rpc_executor.spawn_critical(
             "cache canonical blocks task",
             Box::pin(async move {
                 cache_new_blocks_task(c, new_canonical_blocks).await;
             }),
         );
 
-        let ctx = EthApiCtx { components: &node, config: config.rpc.eth_config(), cache };
+        let ctx = EthApiCtx {
+            components: &node,
+            config: config.rpc.eth_config(),
+            cache,
+            tx_forwarder: config.rpc.tx_forwarder_config(),
+        };
         let eth_api = eth_api_builder.build_eth_api(ctx).await?;
 
         let auth_config = config.rpc.auth_server_config(jwt_secret)?;
@@ -776,7 +857,7 @@ where
             .with_provider(node.provider().clone())
             .with_pool(node.pool().clone())
             .with_network(node.network().clone())
-            .with_executor(Box::new(node.task_executor().clone()))
+            .with_executor(Box::new(rpc_executor.clone()))
             .with_evm_config(node.evm_config().clone())
             .with_consensus(node.consensus().clone())
             .build_with_auth_server(module_config, engine_api, eth_api);
@@ -786,6 +867,88 @@ where
             registry.eth_api().with_dev_accounts();
         }
 
+        modules.merge_if_module_configured(
+            RethRpcModule::Reth,
+            RethPrune::new(node.provider().clone(), exex_prune_pins).into_rpc(),
+        )?;
+        modules.merge_if_module_configured(
+            RethRpcModule::Reth,
+            RethPrecompiles::new(node.provider().clone(), precompile_stats).into_rpc(),
+        )?;
+
+        modules.add_or_replace_if_module_configured(
+            RethRpcModule::Debug,
+            registry
+                .debug_api()
+                .with_historical_witness_provider(historical_witness_provider)
+                .with_archive_state(config.rpc.archive_state_config().map(ArchiveStateFetcher::new))
+                .into_rpc(),
+        )?;
+        // re-executing blocks is expensive, so this is only served by the authenticated server
+        auth_module.merge_auth_methods(DebugExecuteBlock::new(registry.debug_api()).into_rpc())?;
+
+        if let Some(trace_index) = trace_index {
+            modules.add_or_replace_if_module_configured(
+                RethRpcModule::Trace,
+                registry.trace_api().with_trace_index(trace_index).into_rpc(),
+            )?;
+        }
+
+        if let Some(forwarder_config) = config.rpc.bundle_forwarder_config()? {
+            let forwarder = EthBundleForwarder::new(
+                registry.bundle_api(),
+                forwarder_config,
+                Box::new(rpc_executor.clone()),
+            );
+            modules.add_or_replace_if_module_configured(
+                RethRpcModule::Eth,
+                EthBundleApiServer::into_rpc(forwarder.clone()),
+            )?;
+            modules.add_or_replace_if_module_configured(
+                RethRpcModule::Eth,
+                EthBundleStatusApiServer::into_rpc(forwarder),
+            )?;
+        }
+
+        let rpc_usage = config.rpc.rpc_usage_tracker();
+        if let Some(tracker) = &rpc_usage {
+            modules.merge_if_module_configured(RethRpcModule::Admin, tracker.clone().into_rpc())?;
+            rpc_executor
+                .spawn(Box::pin(tracker.clone().log_summaries(config.rpc.rpc_usage_log_interval)));
+        }
+
+        #[cfg(feature = "graphql")]
+        let graphql = config.rpc.graphql_config().map(|graphql_config| {
+            GraphQlLayer::new(graphql_schema(
+                std::sync::Arc::new(node.provider().clone()),
+                graphql_config,
+            ))
+        });
+        #[cfg(not(feature = "graphql"))]
+        if config.rpc.graphql {
+            reth_tracing::tracing::warn!(target: "reth::cli", "Ignoring --graphql, reth was built without the graphql feature");
+        }
+
+        #[cfg(feature = "grpc")]
+        if config.rpc.grpc {
+            let grpc_config = reth_rpc_grpc::GrpcConfig {
+                max_block_range: config.rpc.rpc_max_blocks_per_filter.0,
+                max_logs_per_stream: config.rpc.rpc_max_logs_per_stream.0.map(|max| max as usize),
+            };
+            let addr = std::net::SocketAddr::new(config.rpc.grpc_addr, config.rpc.grpc_port);
+            let handle = reth_rpc_grpc::GrpcServer::new(
+                std::sync::Arc::new(node.provider().clone()),
+                grpc_config,
+            )
+            .start(addr)
+            .await?;
+            info!(target: "reth::cli", url = %handle.local_addr(), "gRPC server started");
+        }
+        #[cfg(not(feature = "grpc"))]
+        if config.rpc.grpc {
+            reth_tracing::tracing::warn!(target: "reth::cli", "Ignoring --grpc, reth was built without the grpc feature");
+        }
+
         let mut registry = RpcRegistry { registry };
         let ctx = RpcContext {
             node: node.clone(),
@@ -814,6 +977,9 @@ where
             on_rpc_started,
             engine_events,
             engine_handle: beacon_engine_handle,
+            rpc_usage,
+            #[cfg(feature = "graphql")]
+            graphql,
         })
     }
 
@@ -868,6 +1034,16 @@ where
         on_rpc_started: Box<dyn OnRpcStarted<N, EthB::EthApi>>,
         handles: RethRpcServerHandles,
     ) -> eyre::Result<()> {
+        #[cfg(unix)]
+        if config.rpc.has_reloadable_config() {
+            node.task_executor().for_component(RuntimeComponent::Rpc).spawn(Box::pin(
+                reload_rpc_config_on_sighup(
+                    config.rpc.clone(),
+                    handles.rpc.reload_handle().clone(),
+                ),
+            ));
+        }
+
         let ctx = RpcContext { node: node.clone(), config, registry, modules, auth_module };
 
         on_rpc_started.on_rpc_started(ctx, handles)?;
@@ -875,6 +1051,68 @@ where
     }
 }
 
+/// Creates the RPC response cache if it's enabled, and spawns the task that updates its finalized
+/// block and drops the responses of reorged blocks.
+fn spawn_rpc_response_cache<N: FullNodeComponents>(
+    node: &N,
+    args: &RpcServerArgs,
+) -> Option<RpcResponseCache> {
+    let cache = args.rpc_response_cache()?;
+
+    // subscribe before reading the finalized block, so no update is missed
+    let canonical_state = node.provider().canonical_state_stream();
+    let finalized = node.provider().finalized_block_stream();
+    if let Ok(Some(number)) = node.provider().finalized_block_number() {
+        cache.set_finalized(number);
+    }
+    node.task_executor().for_component(RuntimeComponent::Rpc).spawn_critical(
+        "rpc response cache task",
+        Box::pin(update_response_cache_task(cache.clone(), canonical_state, finalized)),
+    );
+    Some(cache)
+}
+
+/// Applies the CORS domains, rate limits and roles of the config files of the RPC servers to the
+/// running servers whenever the node receives a SIGHUP.
+#[cfg(unix)]
+async fn reload_rpc_config_on_sighup(
+    args: reth_node_core::args::RpcServerArgs,
+    handle: reth_rpc_builder::reload::RpcServerReloadHandle,
+) {
+    use reth_tracing::tracing::warn;
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(err) => {
+            warn!(target: "reth::cli", %err, "Failed to listen for SIGHUP, RPC config can't be reloaded");
+            return
+        }
+    };
+    while hangups.recv().await.is_some() {
+        match reload_rpc_config(&args, &handle) {
+            Ok(()) => info!(target: "reth::cli", "Reloaded RPC config"),
+            Err(err) => warn!(target: "reth::cli", %err, "Failed to reload RPC config"),
+        }
+    }
+}
+
+/// Reads the config files of the RPC servers again and applies them to the running servers.
+#[cfg(unix)]
+fn reload_rpc_config(
+    args: &reth_node_core::args::RpcServerArgs,
+    handle: &reth_rpc_builder::reload::RpcServerReloadHandle,
+) -> eyre::Result<()> {
+    if let Some(domains) = args.cors_file_domains()? {
+        handle.set_cors(Some(&domains))?;
+    }
+    handle.set_rate_limits(args.rate_limit_config()?)?;
+    if let Some(config) = args.roles_config()? {
+        handle.set_roles(config)?;
+    }
+    Ok(())
+}
+
 impl<N, EthB, EV, EB, RpcMiddleware> NodeAddOns<N> for RpcAddOns<N, EthB, EV, EB, RpcMiddleware>
 where
     N: FullNodeComponents,
@@ -926,6 +1164,8 @@ pub struct EthApiCtx<'a, N: FullNodeTypes> {
     pub config: EthConfig,
     /// Cache for eth state
     pub cache: EthStateCache<BlockTy<N::Types>, ReceiptTy<N::Types>>,
+    /// Upstream endpoint `eth_sendRawTransaction` submissions are forwarded to, if configured.
+    pub tx_forwarder: Option<TxForwarderConfig>,
 }
 
 /// A `EthApi` that knows how to build `eth` namespace API from [`FullNodeComponents`].
@@ -1068,11 +1308,12 @@ where
             ctx.beacon_engine_handle.clone(),
             PayloadStore::new(ctx.node.payload_builder_handle().clone()),
             ctx.node.pool().clone(),
-            Box::new(ctx.node.task_executor().clone()),
+            Box::new(ctx.node.task_executor().for_component(RuntimeComponent::Engine)),
             client,
             EngineCapabilities::default(),
             engine_validator,
             ctx.config.engine.accept_execution_requests_hash,
+            ctx.config.engine.beacon_api_url.clone().map(ParentBeaconBlockRootVerifier::new),
         ))
     }
 }
@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use crate::BlockTy;
 use alloy_primitives::{BlockNumber, B256};
+use reth_chainspec::EthChainSpec;
 use reth_config::{config::StageConfig, PruneConfig};
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_downloaders::{
@@ -16,10 +17,10 @@ use reth_network_p2p::{
     bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, BlockClient,
 };
 use reth_node_api::HeaderTy;
-use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
+use reth_provider::{providers::ProviderNodeTypes, ChainSpecProvider, ProviderFactory};
 use reth_stages::{
     prelude::DefaultStages,
-    stages::{EraImportSource, ExecutionStage},
+    stages::{EraImportSource, ExecutionStage, GenesisState},
     Pipeline, StageSet,
 };
 use reth_static_file::StaticFileProducer;
@@ -108,6 +109,19 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    let mut execution_stage = ExecutionStage::new(
+        evm_config.clone(),
+        Arc::clone(&consensus),
+        stage_config.execution.into(),
+        stage_config.execution_external_clean_threshold(),
+        exex_manager_handle,
+    );
+    if stage_config.execution.genesis_resync_unwind {
+        execution_stage = execution_stage.with_genesis_state(Arc::new(GenesisState::new(
+            provider_factory.chain_spec().genesis().alloc.clone(),
+        )));
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
@@ -115,21 +129,15 @@ where
             DefaultStages::new(
                 provider_factory.clone(),
                 tip_rx,
-                Arc::clone(&consensus),
+                consensus,
                 header_downloader,
                 body_downloader,
-                evm_config.clone(),
+                evm_config,
                 stage_config.clone(),
                 prune_modes,
                 era_import_source,
             )
-            .set(ExecutionStage::new(
-                evm_config,
-                consensus,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
-                exex_manager_handle,
-            )),
+            .set(execution_stage),
         )
         .build(provider_factory, static_file_producer);
 
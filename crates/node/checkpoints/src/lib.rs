@@ -0,0 +1,25 @@
+//! Signed checkpoint manifests.
+//!
+//! A checkpoint manifest is a list of finalized blocks, i.e. their number, hash and state root,
+//! that is published at a well known URL and signed by a set of trusted keys. The node fetches
+//! the manifest periodically, verifies its signatures and uses the checkpoints as optional sync
+//! anchors and to sanity check its local chain.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod manifest;
+pub use manifest::{
+    Checkpoint, CheckpointManifest, CheckpointManifestError, SignedCheckpointManifest,
+    RETH_CHECKPOINT_SIGNERS,
+};
+
+mod service;
+pub use service::{
+    CheckpointService, DEFAULT_CHECKPOINT_FETCH_INTERVAL, DEFAULT_CHECKPOINT_FETCH_TIMEOUT,
+};
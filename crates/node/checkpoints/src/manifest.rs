@@ -0,0 +1,145 @@
+use alloy_primitives::{keccak256, Address, BlockNumber, Signature, B256};
+use alloy_rlp::RlpEncodable;
+use reth_primitives_traits::crypto::secp256k1::recover_signer;
+use serde::{Deserialize, Serialize};
+
+/// The keys trusted to sign the published checkpoint manifests, used if no signers are
+/// configured.
+///
+/// No manifest signing keys have been published yet, so `--checkpoints.signers` is currently
+/// required for the manifest to be used.
+pub const RETH_CHECKPOINT_SIGNERS: &[Address] = &[];
+
+/// A finalized block of a [`CheckpointManifest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, RlpEncodable)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    /// The block number.
+    pub number: BlockNumber,
+    /// The block hash.
+    pub hash: B256,
+    /// The state root of the block.
+    pub state_root: B256,
+}
+
+/// A list of finalized blocks of a chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, RlpEncodable)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointManifest {
+    /// The chain the checkpoints belong to.
+    pub chain_id: u64,
+    /// The checkpoints.
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+impl CheckpointManifest {
+    /// Returns the hash that is signed by the manifest signers, i.e. the keccak256 hash of the RLP
+    /// encoded manifest.
+    pub fn signature_hash(&self) -> B256 {
+        keccak256(alloy_rlp::encode(self))
+    }
+
+    /// Returns the checkpoint with the highest block number, if any.
+    pub fn latest(&self) -> Option<&Checkpoint> {
+        self.checkpoints.iter().max_by_key(|checkpoint| checkpoint.number)
+    }
+}
+
+/// A [`CheckpointManifest`] with the signatures of its signers, as published.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedCheckpointManifest {
+    /// The signed manifest.
+    #[serde(flatten)]
+    pub manifest: CheckpointManifest,
+    /// Signatures of the [signature hash](CheckpointManifest::signature_hash) of the manifest.
+    pub signatures: Vec<Signature>,
+}
+
+impl SignedCheckpointManifest {
+    /// Returns the manifest if it belongs to the given chain and is signed by at least one of the
+    /// trusted signers.
+    ///
+    /// Signatures that can't be recovered or belong to unknown signers are ignored.
+    pub fn verify(
+        self,
+        chain_id: u64,
+        signers: &[Address],
+    ) -> Result<CheckpointManifest, CheckpointManifestError> {
+        if self.manifest.chain_id != chain_id {
+            return Err(CheckpointManifestError::ChainIdMismatch {
+                got: self.manifest.chain_id,
+                expected: chain_id,
+            })
+        }
+
+        let hash = self.manifest.signature_hash();
+        let trusted = self
+            .signatures
+            .iter()
+            .filter_map(|signature| recover_signer(signature, hash).ok())
+            .any(|signer| signers.contains(&signer));
+        if !trusted {
+            return Err(CheckpointManifestError::Untrusted)
+        }
+
+        Ok(self.manifest)
+    }
+}
+
+/// Errors of fetching and verifying a [`SignedCheckpointManifest`].
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointManifestError {
+    /// The manifest couldn't be fetched.
+    #[error("failed to fetch checkpoint manifest: {0}")]
+    Fetch(#[from] reqwest::Error),
+    /// The manifest couldn't be decoded.
+    #[error("failed to decode checkpoint manifest: {0}")]
+    Decode(#[from] serde_json::Error),
+    /// The manifest belongs to another chain.
+    #[error("checkpoint manifest is for chain {got}, expected chain {expected}")]
+    ChainIdMismatch {
+        /// The chain of the manifest.
+        got: u64,
+        /// The chain of the node.
+        expected: u64,
+    },
+    /// The manifest isn't signed by any of the trusted signers.
+    #[error("checkpoint manifest is not signed by a trusted key")]
+    Untrusted,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives_traits::crypto::secp256k1::sign_message;
+
+    #[test]
+    fn verifies_signed_manifest() {
+        let manifest = CheckpointManifest {
+            chain_id: 1,
+            checkpoints: vec![
+                Checkpoint { number: 10, hash: B256::with_last_byte(1), state_root: B256::ZERO },
+                Checkpoint { number: 20, hash: B256::with_last_byte(2), state_root: B256::ZERO },
+            ],
+        };
+        assert_eq!(manifest.latest().map(|checkpoint| checkpoint.number), Some(20));
+
+        let signature = sign_message(B256::with_last_byte(7), manifest.signature_hash()).unwrap();
+        let signer = recover_signer(&signature, manifest.signature_hash()).unwrap();
+        let signed = SignedCheckpointManifest { manifest, signatures: vec![signature] };
+
+        // roundtrips through the published format
+        let json = serde_json::to_string(&signed).unwrap();
+        let signed: SignedCheckpointManifest = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(signed.clone().verify(1, &[signer]).unwrap(), signed.manifest);
+        assert!(matches!(
+            signed.clone().verify(1, &[Address::ZERO]),
+            Err(CheckpointManifestError::Untrusted)
+        ));
+        assert!(matches!(
+            signed.verify(2, &[signer]),
+            Err(CheckpointManifestError::ChainIdMismatch { got: 1, expected: 2 })
+        ));
+    }
+}
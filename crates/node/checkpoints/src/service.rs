@@ -0,0 +1,119 @@
+use crate::{Checkpoint, CheckpointManifest, CheckpointManifestError, SignedCheckpointManifest};
+use alloy_primitives::Address;
+use reqwest::Client;
+use reth_primitives_traits::AlloyBlockHeader;
+use reth_storage_api::{errors::provider::ProviderResult, HeaderProvider};
+use std::time::Duration;
+use tracing::{debug, error, warn};
+use url::Url;
+
+/// The default interval at which the checkpoint manifest is fetched.
+pub const DEFAULT_CHECKPOINT_FETCH_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// The default timeout for fetching the checkpoint manifest.
+pub const DEFAULT_CHECKPOINT_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Fetches the signed checkpoint manifest from a URL and checks the local chain against it.
+#[derive(Debug, Clone)]
+pub struct CheckpointService<Provider> {
+    http_client: Client,
+    url: Url,
+    chain_id: u64,
+    signers: Vec<Address>,
+    interval: Duration,
+    provider: Provider,
+}
+
+impl<Provider> CheckpointService<Provider> {
+    /// Creates a new service that fetches the manifest of the given chain from the URL and trusts
+    /// manifests signed by any of the signers.
+    ///
+    /// Fetching the manifest gives up after [`DEFAULT_CHECKPOINT_FETCH_TIMEOUT`].
+    pub fn new(url: Url, chain_id: u64, signers: Vec<Address>, provider: Provider) -> Self {
+        Self {
+            http_client: http_client(DEFAULT_CHECKPOINT_FETCH_TIMEOUT),
+            url,
+            chain_id,
+            signers,
+            interval: DEFAULT_CHECKPOINT_FETCH_INTERVAL,
+            provider,
+        }
+    }
+
+    /// Sets the interval at which the manifest is fetched.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the timeout after which fetching the manifest is given up.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.http_client = http_client(timeout);
+        self
+    }
+
+    /// Fetches the manifest and verifies its signatures.
+    pub async fn fetch(&self) -> Result<CheckpointManifest, CheckpointManifestError> {
+        let resp = self.http_client.get(self.url.clone()).send().await?.error_for_status()?;
+        let manifest: SignedCheckpointManifest = serde_json::from_slice(&resp.bytes().await?)?;
+        manifest.verify(self.chain_id, &self.signers)
+    }
+}
+
+impl<Provider> CheckpointService<Provider>
+where
+    Provider: HeaderProvider,
+{
+    /// Checks the local chain against the checkpoints of the manifest.
+    ///
+    /// Checkpoints above the local chain are skipped. Returns the checkpoints that don't match the
+    /// local chain.
+    pub fn check(&self, manifest: &CheckpointManifest) -> ProviderResult<Vec<Checkpoint>> {
+        let mut mismatches = Vec::new();
+        for checkpoint in &manifest.checkpoints {
+            let Some(header) = self.provider.sealed_header(checkpoint.number)? else { continue };
+            if header.hash() != checkpoint.hash || header.state_root() != checkpoint.state_root {
+                error!(
+                    target: "reth::checkpoints",
+                    number = checkpoint.number,
+                    expected_hash = %checkpoint.hash,
+                    local_hash = %header.hash(),
+                    expected_state_root = %checkpoint.state_root,
+                    local_state_root = %header.state_root(),
+                    "Local chain doesn't match the signed checkpoint"
+                );
+                mismatches.push(*checkpoint);
+            }
+        }
+        Ok(mismatches)
+    }
+
+    /// Endless future that fetches the manifest every `interval` and checks the local chain
+    /// against it.
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(self.interval);
+        loop {
+            interval.tick().await;
+            let manifest = match self.fetch().await {
+                Ok(manifest) => manifest,
+                Err(err) => {
+                    warn!(target: "reth::checkpoints", %err, url = %self.url, "Failed to fetch checkpoint manifest");
+                    continue
+                }
+            };
+            match self.check(&manifest) {
+                Ok(mismatches) => {
+                    debug!(target: "reth::checkpoints", checkpoints = manifest.checkpoints.len(), mismatches = mismatches.len(), "Checked local chain against checkpoint manifest")
+                }
+                Err(err) => {
+                    warn!(target: "reth::checkpoints", %err, "Failed to check local chain against checkpoint manifest")
+                }
+            }
+        }
+    }
+}
+
+/// Returns an HTTP client that gives up on requests that take longer than `timeout`.
+fn http_client(timeout: Duration) -> Client {
+    Client::builder().timeout(timeout).build().unwrap_or_else(|_| Client::new())
+}
@@ -0,0 +1,111 @@
+//! clap [Args](clap::Args) for signed checkpoint manifests
+
+use alloy_primitives::Address;
+use clap::Args;
+use std::time::Duration;
+use url::Url;
+
+/// Parameters for fetching signed checkpoint manifests.
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Checkpoints")]
+pub struct CheckpointArgs {
+    /// The URL of a signed checkpoint manifest.
+    ///
+    /// The manifest lists finalized blocks with their hash and state root. It is fetched
+    /// periodically and the local chain is checked against it.
+    ///
+    /// Requires `--checkpoints.signers`, the manifest is ignored otherwise.
+    #[arg(long = "checkpoints.url", value_name = "URL", verbatim_doc_comment)]
+    pub url: Option<Url>,
+
+    /// Comma separated addresses of the keys trusted to sign the checkpoint manifest.
+    ///
+    /// Required for using the manifest: no signing keys are embedded yet.
+    #[arg(
+        long = "checkpoints.signers",
+        value_name = "ADDRESSES",
+        value_delimiter = ',',
+        verbatim_doc_comment
+    )]
+    pub signers: Vec<Address>,
+
+    /// The interval at which the checkpoint manifest is fetched.
+    #[arg(
+        long = "checkpoints.interval",
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        default_value = "1h"
+    )]
+    pub interval: Duration,
+
+    /// Use the latest checkpoint of the manifest as the initial sync target if the local chain is
+    /// behind it and no other target is set.
+    #[arg(long = "checkpoints.anchor")]
+    pub anchor: bool,
+
+    /// Disable fetching the checkpoint manifest.
+    #[arg(long = "checkpoints.disable")]
+    pub disabled: bool,
+}
+
+impl CheckpointArgs {
+    /// Returns the URL of the checkpoint manifest, if fetching it is enabled.
+    pub fn url(&self) -> Option<&Url> {
+        self.url.as_ref().filter(|_| !self.disabled)
+    }
+}
+
+impl Default for CheckpointArgs {
+    fn default() -> Self {
+        Self {
+            url: None,
+            signers: Vec::new(),
+            interval: Duration::from_secs(60 * 60),
+            anchor: false,
+            disabled: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_checkpoint_args() {
+        let args = CommandParser::<CheckpointArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, CheckpointArgs::default());
+        assert_eq!(args.url(), None);
+
+        let args = CommandParser::<CheckpointArgs>::parse_from([
+            "reth",
+            "--checkpoints.url",
+            "https://example.com/checkpoints.json",
+            "--checkpoints.signers",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+            "--checkpoints.interval",
+            "10m",
+        ])
+        .args;
+        assert!(args.url().is_some());
+        assert_eq!(args.signers, vec![Address::with_last_byte(1), Address::with_last_byte(2)]);
+        assert_eq!(args.interval, Duration::from_secs(600));
+
+        let args = CommandParser::<CheckpointArgs>::parse_from([
+            "reth",
+            "--checkpoints.url",
+            "https://example.com/checkpoints.json",
+            "--checkpoints.disable",
+        ])
+        .args;
+        assert_eq!(args.url(), None);
+    }
+}
@@ -0,0 +1,99 @@
+//! clap [Args](clap::Args) for consensus configuration
+
+use clap::Args;
+use reth_consensus::{HeaderValidatorConfig, DEFAULT_MAX_FUTURE_TIMESTAMP_DRIFT};
+
+/// Parameters for configuring consensus validation rules.
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Consensus")]
+pub struct ConsensusArgs {
+    /// The maximum number of seconds a block timestamp may be ahead of the local clock.
+    ///
+    /// Only applies to pre-merge blocks, post-merge timestamps are validated by the consensus
+    /// layer.
+    #[arg(
+        long = "consensus.max-future-timestamp-drift",
+        value_name = "SECONDS",
+        default_value_t = DEFAULT_MAX_FUTURE_TIMESTAMP_DRIFT,
+        verbatim_doc_comment
+    )]
+    pub max_future_timestamp_drift: u64,
+
+    /// Disable the check for block timestamps that are ahead of the local clock.
+    ///
+    /// Useful for private devnets with skewed clocks.
+    #[arg(
+        long = "consensus.disable-future-timestamp-check",
+        conflicts_with = "max_future_timestamp_drift"
+    )]
+    pub disable_future_timestamp_check: bool,
+
+    /// Log the first divergent receipt of a block whose receipts root doesn't match.
+    ///
+    /// Receipts are compared against the transactions of the block one by one, reporting the
+    /// index, cumulative gas used and number of logs of the first inconsistent receipt.
+    #[arg(long = "consensus.receipt-diagnostics")]
+    pub receipt_diagnostics: bool,
+}
+
+impl ConsensusArgs {
+    /// Returns the [`HeaderValidatorConfig`] configured by these arguments.
+    pub fn header_validator_config(&self) -> HeaderValidatorConfig {
+        let config = HeaderValidatorConfig::default();
+        if self.disable_future_timestamp_check {
+            config.without_future_timestamp_check()
+        } else {
+            config.with_max_future_timestamp_drift(self.max_future_timestamp_drift)
+        }
+    }
+}
+
+impl Default for ConsensusArgs {
+    fn default() -> Self {
+        Self {
+            max_future_timestamp_drift: DEFAULT_MAX_FUTURE_TIMESTAMP_DRIFT,
+            disable_future_timestamp_check: false,
+            receipt_diagnostics: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_consensus_args() {
+        let args = CommandParser::<ConsensusArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ConsensusArgs::default());
+        assert_eq!(args.header_validator_config(), HeaderValidatorConfig::default());
+
+        let args = CommandParser::<ConsensusArgs>::parse_from([
+            "reth",
+            "--consensus.max-future-timestamp-drift",
+            "60",
+        ])
+        .args;
+        assert_eq!(args.header_validator_config().max_future_timestamp_drift, Some(60));
+
+        let args = CommandParser::<ConsensusArgs>::parse_from([
+            "reth",
+            "--consensus.disable-future-timestamp-check",
+        ])
+        .args;
+        assert_eq!(args.header_validator_config().max_future_timestamp_drift, None);
+
+        let args =
+            CommandParser::<ConsensusArgs>::parse_from(["reth", "--consensus.receipt-diagnostics"])
+                .args;
+        assert!(args.receipt_diagnostics);
+    }
+}
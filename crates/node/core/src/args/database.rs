@@ -12,7 +12,7 @@ use reth_db::{mdbx::MaxReadTransactionDuration, ClientVersion};
 use reth_storage_errors::db::LogLevel;
 
 /// Parameters for database configuration
-#[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Debug, Args, PartialEq, Eq, Default, Clone)]
 #[command(next_help_heading = "Database")]
 pub struct DatabaseArgs {
     /// Database logging level. Levels higher than "notice" require a debug build.
@@ -31,6 +31,14 @@ pub struct DatabaseArgs {
     /// Read transaction timeout in seconds, 0 means no timeout.
     #[arg(long = "db.read-transaction-timeout")]
     pub read_transaction_timeout: Option<u64>,
+    /// Take over the datadir lock of a process on the given host.
+    ///
+    /// The lock of a process on this host that is no longer running is always taken over. The
+    /// lock of a process on another host can't be checked, only use this if that process is known
+    /// to be stopped. The lock is only taken over if the process holding it runs on the given
+    /// host.
+    #[arg(long = "force-takeover", value_name = "HOST")]
+    pub force_takeover: Option<String>,
 }
 
 impl DatabaseArgs {
@@ -57,6 +65,13 @@ impl DatabaseArgs {
             .with_max_read_transaction_duration(max_read_transaction_duration)
             .with_geometry_max_size(self.max_size)
             .with_growth_step(self.growth_step)
+            .with_lock_takeover_host(self.lock_takeover_host().map(str::to_string))
+    }
+
+    /// Returns the host of the process whose datadir lock is taken over, if `--force-takeover` is
+    /// set.
+    pub fn lock_takeover_host(&self) -> Option<&str> {
+        self.force_takeover.as_deref()
     }
 }
 
@@ -336,4 +351,23 @@ mod tests {
         let cmd = CommandParser::<DatabaseArgs>::try_parse_from(["reth"]).unwrap();
         assert_eq!(cmd.args.log_level, None);
     }
+
+    #[test]
+    fn test_command_parser_with_force_takeover() {
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from(["reth"]).unwrap();
+        assert_eq!(cmd.args.lock_takeover_host(), None);
+
+        // the lock of a process on another host is only taken over if its host is given
+        assert!(
+            CommandParser::<DatabaseArgs>::try_parse_from(["reth", "--force-takeover"]).is_err()
+        );
+
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from([
+            "reth",
+            "--force-takeover",
+            "other-host",
+        ])
+        .unwrap();
+        assert_eq!(cmd.args.lock_takeover_host(), Some("other-host"));
+    }
 }
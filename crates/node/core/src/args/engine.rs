@@ -2,6 +2,7 @@
 
 use clap::Args;
 use reth_engine_primitives::TreeConfig;
+use url::Url;
 
 use crate::node_config::{
     DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB, DEFAULT_MAX_PROOF_TASK_CONCURRENCY,
@@ -34,6 +35,11 @@ pub struct EngineArgs {
     #[arg(long = "engine.disable-caching-and-prewarming")]
     pub caching_and_prewarming_disabled: bool,
 
+    /// Disable prewarming the caches with the best transactions of the local pool when a
+    /// forkchoice update with payload attributes is received
+    #[arg(long = "engine.disable-speculative-prewarming")]
+    pub speculative_prewarming_disabled: bool,
+
     /// Enable the parallel sparse trie in the engine.
     #[arg(long = "engine.parallel-sparse-trie", default_value = "false")]
     pub parallel_sparse_trie_enabled: bool,
@@ -89,6 +95,16 @@ pub struct EngineArgs {
         default_value = "false"
     )]
     pub always_process_payload_attributes_on_canonical_head: bool,
+
+    /// The URL of the consensus layer's beacon API, used to cross-check the parent beacon block
+    /// root of incoming payloads.
+    ///
+    /// Payloads whose parent beacon block root is unknown to the beacon node are logged and
+    /// counted in the metrics, but processed as usual.
+    ///
+    /// Note: This is a no-op on OP Stack.
+    #[arg(long = "engine.beacon-api-url", value_name = "URL")]
+    pub beacon_api_url: Option<Url>,
 }
 
 #[allow(deprecated)]
@@ -101,6 +117,7 @@ impl Default for EngineArgs {
             state_root_task_compare_updates: false,
             caching_and_prewarming_enabled: true,
             caching_and_prewarming_disabled: false,
+            speculative_prewarming_disabled: false,
             parallel_sparse_trie_enabled: false,
             state_provider_metrics: false,
             cross_block_cache_size: DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB,
@@ -111,6 +128,7 @@ impl Default for EngineArgs {
             precompile_cache_disabled: false,
             state_root_fallback: false,
             always_process_payload_attributes_on_canonical_head: false,
+            beacon_api_url: None,
         }
     }
 }
@@ -123,6 +141,7 @@ impl EngineArgs {
             .with_memory_block_buffer_target(self.memory_block_buffer_target)
             .with_legacy_state_root(self.legacy_state_root_task_enabled)
             .without_caching_and_prewarming(self.caching_and_prewarming_disabled)
+            .without_speculative_prewarming(self.speculative_prewarming_disabled)
             .with_enable_parallel_sparse_trie(self.parallel_sparse_trie_enabled)
             .with_state_provider_metrics(self.state_provider_metrics)
             .with_always_compare_trie_updates(self.state_root_task_compare_updates)
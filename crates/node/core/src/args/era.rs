@@ -18,6 +18,34 @@ pub struct EraArgs {
     /// Describes where to get the ERA files to import from.
     #[clap(flatten)]
     pub source: EraSourceArgs,
+
+    /// The URL to a remote host used as the primary source of historical blocks.
+    ///
+    /// Shorthand for `--era.enable --era.url <ERA_URL>`. The ERA1 files are downloaded and
+    /// checked against the `checksums.txt` of the remote host. Blocks after the last ERA1 file
+    /// are synced from the network by the regular stages, while a file that is missing from the
+    /// host fails the import.
+    #[arg(
+        long = "sync.era-url",
+        value_name = "ERA_URL",
+        conflicts_with = "EraSourceArgs",
+        verbatim_doc_comment
+    )]
+    pub sync_url: Option<Url>,
+}
+
+impl EraArgs {
+    /// Returns `true` if the import from ERA1 files is enabled.
+    ///
+    /// This is the case if either `--era.enable` or `--sync.era-url` is set.
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled || self.sync_url.is_some()
+    }
+
+    /// Returns the configured URL of the remote host to import ERA1 files from, if any.
+    pub fn url(&self) -> Option<Url> {
+        self.sync_url.clone().or_else(|| self.source.url.clone())
+    }
 }
 
 /// Arguments for the block history import based on ERA1 encoded files.
@@ -60,3 +88,41 @@ impl DefaultEraHost for ChainKind {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_sync_era_url() {
+        let args = CommandParser::<EraArgs>::parse_from(["reth"]).args;
+        assert!(!args.is_enabled());
+        assert_eq!(args.url(), None);
+
+        let args = CommandParser::<EraArgs>::parse_from([
+            "reth",
+            "--sync.era-url",
+            "https://era.ithaca.xyz/era1/index.html",
+        ])
+        .args;
+        assert!(args.is_enabled());
+        assert_eq!(args.url(), Some(Url::parse("https://era.ithaca.xyz/era1/index.html").unwrap()));
+
+        assert!(CommandParser::<EraArgs>::try_parse_from([
+            "reth",
+            "--sync.era-url",
+            "https://era.ithaca.xyz/era1/index.html",
+            "--era.path",
+            "era",
+        ])
+        .is_err());
+    }
+}
@@ -68,5 +68,17 @@ pub use ress_args::RessArgs;
 mod era;
 pub use era::{DefaultEraHost, EraArgs, EraSourceArgs};
 
+/// `ConsensusArgs` for configuring consensus validation rules.
+mod consensus;
+pub use consensus::ConsensusArgs;
+
+/// `CheckpointArgs` for configuring signed checkpoint manifests.
+mod checkpoints;
+pub use checkpoints::CheckpointArgs;
+
+/// `RuntimeArgs` for configuring dedicated tokio runtimes.
+mod runtime;
+pub use runtime::RuntimeArgs;
+
 mod error;
 pub mod types;
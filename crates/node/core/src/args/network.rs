@@ -4,6 +4,7 @@ use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     ops::Not,
     path::PathBuf,
+    time::Duration,
 };
 
 use clap::Args;
@@ -24,10 +25,12 @@ use reth_network::{
                 DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
             },
             tx_manager::{
-                DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
+                DEFAULT_GOSSIP_MONITOR_ALERT_THRESHOLD, DEFAULT_GOSSIP_MONITOR_TIMEOUT,
+                DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
+                DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             },
         },
-        TransactionFetcherConfig, TransactionsManagerConfig,
+        TransactionFetcherConfig, TransactionsManagerConfig, TxGossipMonitorConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
@@ -108,6 +111,10 @@ pub struct NetworkArgs {
     #[arg(long)]
     pub max_inbound_peers: Option<usize>,
 
+    /// Maximum number of outbound dials per second. Unlimited by default.
+    #[arg(long, value_name = "COUNT")]
+    pub max_dial_rate: Option<usize>,
+
     /// Max concurrent `GetPooledTransactions` requests.
     #[arg(long = "max-tx-reqs", value_name = "COUNT", default_value_t = DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS, verbatim_doc_comment)]
     pub max_concurrent_tx_requests: u32,
@@ -161,6 +168,34 @@ pub struct NetworkArgs {
     /// The policy determines which peers transactions are gossiped to.
     #[arg(long = "tx-propagation-policy", default_value_t = TransactionPropagationKind::All)]
     pub tx_propagation_policy: TransactionPropagationKind,
+
+    /// Enable the transaction gossip monitor.
+    ///
+    /// Tracks transactions that peers announce to us until a second peer announces them as well,
+    /// and warns if transactions keep being announced by a single peer only.
+    #[arg(long = "tx-gossip-monitor")]
+    pub tx_gossip_monitor: bool,
+
+    /// Duration after which the transaction gossip monitor considers a transaction that was
+    /// announced by a single peer only lost.
+    #[arg(
+        long = "tx-gossip-monitor.timeout",
+        value_name = "DURATION",
+        value_parser = humantime::parse_duration,
+        default_value = "2m",
+        requires = "tx_gossip_monitor"
+    )]
+    pub tx_gossip_monitor_timeout: Duration,
+
+    /// Number of consecutive lost transactions after which the transaction gossip monitor
+    /// considers gossip broken.
+    #[arg(
+        long = "tx-gossip-monitor.alert-threshold",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_GOSSIP_MONITOR_ALERT_THRESHOLD,
+        requires = "tx_gossip_monitor"
+    )]
+    pub tx_gossip_monitor_alert_threshold: usize,
 }
 
 impl NetworkArgs {
@@ -203,6 +238,12 @@ impl NetworkArgs {
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
             propagation_mode: Default::default(),
+            gossip_monitor: TxGossipMonitorConfig {
+                enabled: self.tx_gossip_monitor,
+                timeout: self.tx_gossip_monitor_timeout,
+                alert_threshold: self.tx_gossip_monitor_alert_threshold,
+                ..Default::default()
+            },
         }
     }
 
@@ -235,7 +276,12 @@ impl NetworkArgs {
             .peers
             .clone()
             .with_max_inbound_opt(self.max_inbound_peers)
-            .with_max_outbound_opt(self.max_outbound_peers);
+            .with_max_outbound_opt(self.max_outbound_peers)
+            .with_max_dial_rate_opt(self.max_dial_rate);
+        let peers_config = peers_config
+            .clone()
+            .with_backoff_states_from_file(self.persistent_peer_backoffs_file(peers_file.clone()))
+            .unwrap_or(peers_config);
 
         // Configure basic network stack
         NetworkConfigBuilder::<N>::new(secret_key)
@@ -279,6 +325,12 @@ impl NetworkArgs {
         self.no_persist_peers.not().then_some(peers_file)
     }
 
+    /// If `no_persist_peers` is false then this returns the path to the file the dial failure
+    /// history of the peers is persisted to, next to the persistent peers file.
+    pub fn persistent_peer_backoffs_file(&self, peers_file: PathBuf) -> Option<PathBuf> {
+        self.persistent_peers_file(peers_file).map(|file| file.with_extension("backoffs.json"))
+    }
+
     /// Sets the p2p port to zero, to allow the OS to assign a random unused port when
     /// the network components bind to a socket.
     pub const fn with_unused_p2p_port(mut self) -> Self {
@@ -333,6 +385,7 @@ impl Default for NetworkArgs {
             port: DEFAULT_DISCOVERY_PORT,
             max_outbound_peers: None,
             max_inbound_peers: None,
+            max_dial_rate: None,
             max_concurrent_tx_requests: DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS,
             max_concurrent_tx_requests_per_peer: DEFAULT_MAX_COUNT_CONCURRENT_REQUESTS_PER_PEER,
             soft_limit_byte_size_pooled_transactions_response:
@@ -342,7 +395,10 @@ impl Default for NetworkArgs {
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
             net_if: None,
-            tx_propagation_policy: TransactionPropagationKind::default()
+            tx_propagation_policy: TransactionPropagationKind::default(),
+            tx_gossip_monitor: false,
+            tx_gossip_monitor_timeout: DEFAULT_GOSSIP_MONITOR_TIMEOUT,
+            tx_gossip_monitor_alert_threshold: DEFAULT_GOSSIP_MONITOR_ALERT_THRESHOLD,
         }
     }
 }
@@ -617,6 +673,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_tx_gossip_monitor_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--tx-gossip-monitor",
+            "--tx-gossip-monitor.timeout",
+            "30s",
+        ])
+        .args;
+
+        let config = args.transactions_manager_config().gossip_monitor;
+        assert!(config.enabled);
+        assert_eq!(config.timeout, Duration::from_secs(30));
+        assert_eq!(config.alert_threshold, DEFAULT_GOSSIP_MONITOR_ALERT_THRESHOLD);
+
+        assert!(!NetworkArgs::default().transactions_manager_config().gossip_monitor.enabled);
+    }
+
     #[test]
     fn network_args_default_sanity_test() {
         let default_args = NetworkArgs::default();
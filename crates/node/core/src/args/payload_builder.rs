@@ -1,6 +1,7 @@
 use crate::{cli::config::PayloadBuilderConfig, version::default_extra_data};
 use alloy_consensus::constants::MAXIMUM_EXTRA_DATA_SIZE;
 use alloy_eips::merge::SLOT_DURATION;
+use alloy_primitives::{Address, TxHash};
 use clap::{
     builder::{RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
@@ -35,6 +36,18 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Comma separated list of addresses whose transactions must not be included in built
+    /// payloads, either as sender or recipient.
+    #[arg(long = "builder.deny", value_name = "ADDRESSES", value_delimiter = ',')]
+    pub denied_addresses: Vec<Address>,
+
+    /// Comma separated list of hashes of transactions that must be included in built payloads if
+    /// they are available in the pool.
+    ///
+    /// They are executed in the given order, before any other transaction.
+    #[arg(long = "builder.include", value_name = "TX_HASHES", value_delimiter = ',')]
+    pub mandatory_transactions: Vec<TxHash>,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -45,6 +58,8 @@ impl Default for PayloadBuilderArgs {
             gas_limit: None,
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            denied_addresses: Vec::new(),
+            mandatory_transactions: Vec::new(),
         }
     }
 }
@@ -69,6 +84,14 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn denied_addresses(&self) -> &[Address] {
+        &self.denied_addresses
+    }
+
+    fn mandatory_transactions(&self) -> &[TxHash] {
+        &self.mandatory_transactions
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -158,6 +181,34 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn test_args_with_denied_addresses() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.deny",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+        ])
+        .args;
+        assert_eq!(
+            args.denied_addresses,
+            vec![Address::with_last_byte(1), Address::with_last_byte(2)]
+        );
+    }
+
+    #[test]
+    fn test_args_with_mandatory_transactions() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.include",
+            "0x0000000000000000000000000000000000000000000000000000000000000001,0x0000000000000000000000000000000000000000000000000000000000000002",
+        ])
+        .args;
+        assert_eq!(
+            args.mandatory_transactions,
+            vec![TxHash::with_last_byte(1), TxHash::with_last_byte(2)]
+        );
+    }
+
     #[test]
     fn test_args_with_s_interval() {
         let args =
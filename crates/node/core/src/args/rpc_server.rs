@@ -5,6 +5,7 @@ use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr},
     path::PathBuf,
+    time::Duration,
 };
 
 use alloy_primitives::Address;
@@ -15,7 +16,11 @@ use clap::{
 };
 use rand::Rng;
 use reth_cli_util::parse_ether_value;
-use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+use reth_rpc_eth_types::SubscriptionOverflowPolicy;
+use reth_rpc_server_types::{
+    constants, MethodQuota, MethodRateLimit, RethRpcModule, RpcModuleSelection, RpcRateLimitConfig,
+    RpcRolesConfig,
+};
 
 use crate::args::{
     types::{MaxU32, ZeroAsNoneU64},
@@ -58,6 +63,13 @@ pub struct RpcServerArgs {
     #[arg(long = "http.disable-compression", default_value_t = false)]
     pub http_disable_compression: bool,
 
+    /// Minimum size of HTTP responses that are compressed, in bytes
+    ///
+    /// Responses are compressed with the encoding the client prefers in its `Accept-Encoding`
+    /// header, one of gzip, brotli, deflate and zstd.
+    #[arg(long = "http.compression-min-size", value_name = "BYTES", default_value_t = constants::DEFAULT_HTTP_COMPRESSION_MIN_SIZE)]
+    pub http_compression_min_size: u16,
+
     /// Rpc Modules to be configured for the HTTP server
     #[arg(long = "http.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub http_api: Option<RpcModuleSelection>,
@@ -66,6 +78,32 @@ pub struct RpcServerArgs {
     #[arg(long = "http.corsdomain")]
     pub http_corsdomain: Option<String>,
 
+    /// Serve the EIP-1767 GraphQL API on the `/graphql` path of the HTTP-RPC server, requires the
+    /// `graphql` feature
+    #[arg(long, requires = "http")]
+    pub graphql: bool,
+
+    /// Maximum complexity of a GraphQL query
+    #[arg(long = "graphql.max-complexity", value_name = "COMPLEXITY", default_value_t = constants::DEFAULT_GRAPHQL_MAX_COMPLEXITY)]
+    pub graphql_max_complexity: usize,
+
+    /// Maximum nesting depth of a GraphQL query
+    #[arg(long = "graphql.max-depth", value_name = "DEPTH", default_value_t = constants::DEFAULT_GRAPHQL_MAX_DEPTH)]
+    pub graphql_max_depth: usize,
+
+    /// Enable the gRPC server for blocks, transactions, receipts and logs, requires the `grpc`
+    /// feature
+    #[arg(long)]
+    pub grpc: bool,
+
+    /// gRPC server address to listen on
+    #[arg(long = "grpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub grpc_addr: IpAddr,
+
+    /// gRPC server port to listen on
+    #[arg(long = "grpc.port", default_value_t = constants::DEFAULT_GRPC_PORT)]
+    pub grpc_port: u16,
+
     /// Enable the WS-RPC server
     #[arg(long)]
     pub ws: bool,
@@ -86,6 +124,20 @@ pub struct RpcServerArgs {
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Interval at which `WebSocket` connections are pinged to detect dead connections.
+    ///
+    /// Dead connections are closed and their subscriptions are dropped. Set to 0 to disable pings.
+    #[arg(long = "ws.ping-interval", value_name = "DURATION", value_parser = humantime::parse_duration, default_value = "30s")]
+    pub ws_ping_interval: Duration,
+
+    /// Time without any response after which a `WebSocket` connection is considered inactive.
+    #[arg(long = "ws.idle-timeout", value_name = "DURATION", value_parser = humantime::parse_duration, default_value = "60s")]
+    pub ws_idle_timeout: Duration,
+
+    /// Number of missed pings of an inactive `WebSocket` connection after which it's closed.
+    #[arg(long = "ws.max-missed-pings", default_value_t = constants::DEFAULT_WS_MAX_MISSED_PINGS, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub ws_max_missed_pings: usize,
+
     /// Disable the IPC-RPC server
     #[arg(long)]
     pub ipcdisable: bool,
@@ -94,6 +146,19 @@ pub struct RpcServerArgs {
     #[arg(long, default_value_t = constants::DEFAULT_IPC_ENDPOINT.to_string())]
     pub ipcpath: String,
 
+    /// Set the maximum RPC request payload size for IPC in megabytes.
+    ///
+    /// Defaults to `--rpc.max-request-size`.
+    #[arg(long = "ipc.max-request-size", value_name = "MB")]
+    pub ipc_max_request_size: Option<MaxU32>,
+
+    /// Set the maximum RPC response payload size for IPC in megabytes.
+    ///
+    /// Defaults to `--rpc.max-response-size`. Large responses, e.g. of `debug_traceBlock`, are
+    /// written to the socket in chunks, so this can be raised well above the HTTP limit.
+    #[arg(long = "ipc.max-response-size", value_name = "MB")]
+    pub ipc_max_response_size: Option<MaxU32>,
+
     /// Auth server address to listen on
     #[arg(long = "authrpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
     pub auth_addr: IpAddr,
@@ -119,6 +184,24 @@ pub struct RpcServerArgs {
     #[arg(long = "auth-ipc.path", default_value_t = constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string())]
     pub auth_ipc_path: String,
 
+    /// Path of a unix socket to serve the engine API on over HTTP and WS.
+    ///
+    /// Requests on the socket aren't authenticated with the JWT secret, access to the socket is
+    /// restricted to the user of the node instead.
+    #[arg(long = "authrpc.unix-socket", value_name = "PATH")]
+    pub auth_unix_socket: Option<PathBuf>,
+
+    /// Disable the TCP listener of the engine API, see `--authrpc.addr` and `--authrpc.port`.
+    ///
+    /// The engine API must be served on a unix socket or IPC instead, see
+    /// `--authrpc.unix-socket` and `--auth-ipc`.
+    #[arg(long = "authrpc.disable-tcp")]
+    pub auth_disable_tcp: bool,
+
+    /// Accept HTTP/2 requests on the engine API in addition to HTTP/1.
+    #[arg(long = "authrpc.http2")]
+    pub auth_http2: bool,
+
     /// Hex encoded JWT secret to authenticate the regular RPC server(s), see `--http.api` and
     /// `--ws.api`.
     ///
@@ -127,6 +210,22 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.jwtsecret", value_name = "HEX", global = true, required = false)]
     pub rpc_jwtsecret: Option<JwtSecret>,
 
+    /// Path to a TOML file with role based method allowlists of the regular RPC server(s).
+    ///
+    /// The roles of a client are read from a claim of its JWT, see `--rpc.jwtsecret`. Calls of
+    /// methods that aren't allowed for any role of the client are rejected with error code -32004.
+    #[arg(long = "rpc.roles-config", value_name = "PATH", requires = "rpc_jwtsecret")]
+    pub rpc_roles_config: Option<PathBuf>,
+
+    /// Path to a file with the allowed CORS domains of the HTTP and WS servers, comma or newline
+    /// separated, or `*` to allow any domain. Takes precedence over `--http.corsdomain` and
+    /// `--ws.origins`.
+    ///
+    /// On SIGHUP, this file, `--rpc.rate-limit-config` and `--rpc.roles-config` are read again
+    /// and applied to the running servers.
+    #[arg(long = "rpc.cors-file", value_name = "PATH")]
+    pub rpc_cors_file: Option<PathBuf>,
+
     /// Set the maximum RPC request payload size for both HTTP and WS in megabytes.
     #[arg(long = "rpc.max-request-size", alias = "rpc-max-request-size", default_value_t = RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into())]
     pub rpc_max_request_size: MaxU32,
@@ -139,6 +238,17 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-subscriptions-per-connection", alias = "rpc-max-subscriptions-per-connection", default_value_t = RPC_DEFAULT_MAX_SUBS_PER_CONN.into())]
     pub rpc_max_subscriptions_per_connection: MaxU32,
 
+    /// Maximum number of messages buffered per subscription while the consumer can't keep up.
+    #[arg(long = "rpc.subscription-buffer-size", value_name = "COUNT", default_value_t = constants::DEFAULT_SUBSCRIPTION_BUFFER_SIZE, value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
+    pub rpc_subscription_buffer_size: usize,
+
+    /// What happens to a subscription whose buffer is full.
+    ///
+    /// Either `drop-oldest` or `drop-newest` to drop messages, or `disconnect` to close the
+    /// subscription.
+    #[arg(long = "rpc.subscription-overflow", value_name = "POLICY", default_value_t = SubscriptionOverflowPolicy::default())]
+    pub rpc_subscription_overflow: SubscriptionOverflowPolicy,
+
     /// Maximum number of RPC server connections.
     #[arg(long = "rpc.max-connections", alias = "rpc-max-connections", value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_CONNECTIONS.into())]
     pub rpc_max_connections: MaxU32,
@@ -156,6 +266,24 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-trace-filter-blocks", alias = "rpc-max-trace-filter-blocks", value_name = "COUNT", default_value_t = constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS)]
     pub rpc_max_trace_filter_blocks: u64,
 
+    /// Maintain an in-memory index of the blocks in which an address appears in a trace.
+    ///
+    /// `trace_filter` requests by address consult the index to only trace the matching blocks, in
+    /// which case `--rpc.max-trace-filter-blocks` limits the number of matching blocks instead of
+    /// the size of the requested range.
+    #[arg(long = "rpc.trace-index")]
+    pub rpc_trace_index: bool,
+
+    /// The first block covered by the trace index. All blocks from this block on are re-executed
+    /// on startup to build the index.
+    #[arg(
+        long = "rpc.trace-index-start-block",
+        value_name = "BLOCK",
+        default_value_t = 0,
+        requires = "rpc_trace_index"
+    )]
+    pub rpc_trace_index_start_block: u64,
+
     /// Maximum number of blocks that could be scanned per filter request. (0 = entire chain)
     #[arg(long = "rpc.max-blocks-per-filter", alias = "rpc-max-blocks-per-filter", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_BLOCKS_PER_FILTER))]
     pub rpc_max_blocks_per_filter: ZeroAsNoneU64,
@@ -164,6 +292,11 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-logs-per-response", alias = "rpc-max-logs-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Maximum number of logs that can be streamed by a single `eth_getLogsStream` subscription.
+    /// (0 = no limit)
+    #[arg(long = "rpc.max-logs-per-stream", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_STREAM as u64))]
+    pub rpc_max_logs_per_stream: ZeroAsNoneU64,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long = "rpc.gascap",
@@ -206,11 +339,158 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.proof-permits", alias = "rpc-proof-permits", value_name = "COUNT", default_value_t = constants::DEFAULT_PROOF_PERMITS)]
     pub rpc_proof_permits: usize,
 
+    /// Maximum number of `eth_getProof` targets whose revealed tries are cached and updated with
+    /// every new block, to serve repeated proofs of the same account and storage slots.
+    ///
+    /// Disabled if zero.
+    #[arg(long = "rpc.proof-cache-entries", value_name = "COUNT", default_value_t = 0)]
+    pub rpc_proof_cache_entries: u32,
+
     /// Path to file containing disallowed addresses, json-encoded list of strings. Block
     /// validation API will reject blocks containing transactions from these addresses.
     #[arg(long = "builder.disallow", value_name = "PATH", value_parser = reth_cli_util::parsers::read_json_from_file::<HashSet<Address>>)]
     pub builder_disallow: Option<HashSet<Address>>,
 
+    /// Enable per-connection and per-API-key accounting of RPC method usage.
+    ///
+    /// The usage is exposed via `admin_rpcUsage` and a summary of the heaviest consumers is logged
+    /// periodically.
+    #[arg(long = "rpc.usage-accounting", default_value_t = false)]
+    pub rpc_usage_accounting: bool,
+
+    /// HTTP header that carries the API key of a request for RPC usage accounting.
+    #[arg(long = "rpc.usage-api-key-header", value_name = "HEADER", default_value = constants::DEFAULT_RPC_USAGE_API_KEY_HEADER)]
+    pub rpc_usage_api_key_header: String,
+
+    /// Interval at which a summary of the heaviest RPC consumers is logged.
+    #[arg(long = "rpc.usage-log-interval", value_name = "DURATION", value_parser = humantime::parse_duration, default_value = "5m")]
+    pub rpc_usage_log_interval: Duration,
+
+    /// Log RPC calls that take longer than the given duration, e.g. `500ms`.
+    ///
+    /// Slow calls are logged with their method, a summary of their params, the execution time,
+    /// the response size and the block range they touch. Disabled by default.
+    #[arg(long = "rpc.slow-query-threshold", value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub rpc_slow_query_threshold: Option<Duration>,
+
+    /// Limit the rate of calls of a method, shared by all clients, as `METHOD=RATE[:BURST]`.
+    ///
+    /// `METHOD` is a method name or a prefix followed by `*`, e.g. `debug_*`. `RATE` is the number
+    /// of calls per second and `BURST` the number of calls that can be made at once. Calls that
+    /// exceed the limit are rejected with error code -32005.
+    #[arg(long = "rpc.rate-limit", value_name = "METHOD=RATE[:BURST]", value_delimiter = ',')]
+    pub rpc_rate_limit: Vec<MethodQuota>,
+
+    /// Limit the rate of calls of a method per client IP, as `METHOD=RATE[:BURST]`.
+    ///
+    /// Clients are identified by the IP address of their connection, or by the
+    /// `--rpc.rate-limit-ip-header` of their requests if they are received from one of the
+    /// `--rpc.trusted-proxies`.
+    #[arg(
+        long = "rpc.rate-limit-per-ip",
+        value_name = "METHOD=RATE[:BURST]",
+        value_delimiter = ','
+    )]
+    pub rpc_rate_limit_per_ip: Vec<MethodQuota>,
+
+    /// HTTP header that carries the IP address of the client for per-IP rate limits, e.g.
+    /// `x-forwarded-for` if the server is behind a reverse proxy.
+    #[arg(long = "rpc.rate-limit-ip-header", value_name = "HEADER")]
+    pub rpc_rate_limit_ip_header: Option<String>,
+
+    /// Path to a TOML file with the rate limits of methods.
+    ///
+    /// The limits given on the command line take precedence over the limits of the file.
+    #[arg(long = "rpc.rate-limit-config", value_name = "PATH")]
+    pub rpc_rate_limit_config: Option<PathBuf>,
+
+    /// Log every RPC call with its method, a hash of its params, the client IP, the execution
+    /// time, the response size and the class of its error.
+    ///
+    /// Calls are logged as tracing events with the `rpc::access_log` target, unless
+    /// `--rpc.access-log-file` is set.
+    #[arg(long = "rpc.access-log")]
+    pub rpc_access_log: bool,
+
+    /// Append the RPC access log as JSON lines to the given file. Implies `--rpc.access-log`.
+    #[arg(long = "rpc.access-log-file", value_name = "PATH")]
+    pub rpc_access_log_file: Option<PathBuf>,
+
+    /// Log only one in every N successful RPC calls. Failed calls are always logged.
+    #[arg(long = "rpc.access-log-sample", value_name = "N", default_value_t = 1, value_parser = RangedU64ValueParser::<u64>::new().range(1..))]
+    pub rpc_access_log_sample: u64,
+
+    /// HTTP header that carries the IP address of the client for the RPC access log, e.g.
+    /// `x-forwarded-for` if the server is behind a reverse proxy.
+    #[arg(long = "rpc.access-log-ip-header", value_name = "HEADER")]
+    pub rpc_access_log_ip_header: Option<String>,
+
+    /// IP addresses of the reverse proxies in front of the RPC servers, whose client IP headers
+    /// are trusted.
+    ///
+    /// The `--rpc.rate-limit-ip-header` and `--rpc.access-log-ip-header` of requests are only
+    /// honoured if the requests are received from one of these addresses, in which case the
+    /// rightmost address of the header that isn't a trusted proxy is the client IP.
+    #[arg(long = "rpc.trusted-proxies", value_name = "IP", value_delimiter = ',')]
+    pub rpc_trusted_proxies: Vec<IpAddr>,
+
+    /// Cache the responses of deterministic RPC methods in memory.
+    ///
+    /// Responses of `eth_chainId` and `net_version` are always cached, responses of
+    /// `eth_getBlockByHash` and `eth_getTransactionReceipt` only if their block is finalized.
+    /// Cached responses of blocks that are reorged out are dropped. Batch requests aren't cached.
+    #[arg(long = "rpc.response-cache")]
+    pub rpc_response_cache: bool,
+
+    /// Maximum number of responses in the RPC response cache.
+    #[arg(long = "rpc.response-cache-size", value_name = "N", default_value_t = constants::DEFAULT_RPC_RESPONSE_CACHE_SIZE)]
+    pub rpc_response_cache_size: u32,
+
+    /// Builder or relay endpoints that bundles sent via `eth_sendBundle` are forwarded to.
+    ///
+    /// Bundles are simulated locally first and only forwarded if they are valid. The status of
+    /// forwarded bundles can be queried via `eth_getBundleStatus`.
+    #[arg(long = "rpc.bundle-forward", value_name = "URL", value_delimiter = ',')]
+    pub rpc_bundle_forward: Vec<String>,
+
+    /// Path to a file containing the hex encoded private key that forwarded bundles are signed
+    /// with, see `X-Flashbots-Signature`.
+    #[arg(long = "rpc.bundle-signing-key", value_name = "PATH", requires = "rpc_bundle_forward")]
+    pub rpc_bundle_signing_key: Option<PathBuf>,
+
+    /// Number of retries of a bundle that couldn't be forwarded to an endpoint.
+    #[arg(long = "rpc.bundle-forward-retries", value_name = "COUNT", default_value_t = constants::DEFAULT_BUNDLE_FORWARD_RETRIES)]
+    pub rpc_bundle_forward_retries: u32,
+
+    /// Upstream endpoint, e.g. an L2 sequencer or a private relay, that transactions sent via
+    /// `eth_sendRawTransaction` are forwarded to.
+    ///
+    /// Forwarded transactions are inserted into the local pool as well, unless
+    /// `--rpc.tx-forward-only` is set. Errors returned by the endpoint are passed on to the
+    /// caller.
+    #[arg(long = "rpc.tx-forward", value_name = "URL")]
+    pub rpc_tx_forward: Option<String>,
+
+    /// Only forward transactions to the `--rpc.tx-forward` endpoint, without inserting them into
+    /// the local pool.
+    #[arg(long = "rpc.tx-forward-only", requires = "rpc_tx_forward")]
+    pub rpc_tx_forward_only: bool,
+
+    /// Number of retries of a transaction that couldn't be forwarded to the upstream endpoint.
+    #[arg(long = "rpc.tx-forward-retries", value_name = "COUNT", default_value_t = constants::DEFAULT_TX_FORWARD_RETRIES)]
+    pub rpc_tx_forward_retries: u32,
+
+    /// Archive node that `debug_traceCall` fetches the state from if the state at the requested
+    /// block has been pruned locally.
+    ///
+    /// Only the accounts and storage slots that the call reads are fetched, on demand.
+    #[arg(long = "rpc.archive-fallback", value_name = "URL")]
+    pub rpc_archive_fallback: Option<String>,
+
+    /// Number of blocks whose state fetched from the `--rpc.archive-fallback` node is cached.
+    #[arg(long = "rpc.archive-fallback-cache", value_name = "BLOCKS", default_value_t = constants::DEFAULT_ARCHIVE_STATE_CACHED_BLOCKS, requires = "rpc_archive_fallback")]
+    pub rpc_archive_fallback_cache: usize,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -245,6 +525,24 @@ impl RpcServerArgs {
         self
     }
 
+    /// Serves the engine API on the unix socket at the given path.
+    pub fn with_auth_unix_socket(mut self, path: PathBuf) -> Self {
+        self.auth_unix_socket = Some(path);
+        self
+    }
+
+    /// Disables the TCP listener of the engine API.
+    pub const fn with_auth_tcp_disabled(mut self) -> Self {
+        self.auth_disable_tcp = true;
+        self
+    }
+
+    /// Enables HTTP/2 on the engine API.
+    pub const fn with_auth_http2(mut self) -> Self {
+        self.auth_http2 = true;
+        self
+    }
+
     /// Change rpc port numbers based on the instance number, if provided.
     /// * The `auth_port` is scaled by a factor of `instance * 100`
     /// * The `http_port` is scaled by a factor of `-instance`
@@ -312,6 +610,92 @@ impl RpcServerArgs {
         self = self.with_ipc_random_path();
         self
     }
+
+    /// Returns the configured rate limits of methods, if any.
+    ///
+    /// The limits given on the command line are merged into rules that precede the rules of the
+    /// `--rpc.rate-limit-config` file, so they take precedence.
+    pub fn rate_limit_config(&self) -> eyre::Result<Option<RpcRateLimitConfig>> {
+        let mut config = match &self.rpc_rate_limit_config {
+            Some(path) => {
+                let content = std::fs::read_to_string(path).map_err(|err| {
+                    eyre::eyre!("failed to read rate limit config {}: {err}", path.display())
+                })?;
+                toml::from_str::<RpcRateLimitConfig>(&content).map_err(|err| {
+                    eyre::eyre!("invalid rate limit config {}: {err}", path.display())
+                })?
+            }
+            None => RpcRateLimitConfig::default(),
+        };
+
+        let mut rules = Vec::<MethodRateLimit>::new();
+        for (quota, per_ip) in self
+            .rpc_rate_limit
+            .iter()
+            .map(|quota| (quota, false))
+            .chain(self.rpc_rate_limit_per_ip.iter().map(|quota| (quota, true)))
+        {
+            let index = match rules.iter().position(|rule| rule.method == quota.method) {
+                Some(index) => index,
+                None => {
+                    rules.push(MethodRateLimit {
+                        method: quota.method.clone(),
+                        global: None,
+                        per_ip: None,
+                    });
+                    rules.len() - 1
+                }
+            };
+            if per_ip {
+                rules[index].per_ip = Some(quota.quota);
+            } else {
+                rules[index].global = Some(quota.quota);
+            }
+        }
+        config.methods.splice(0..0, rules);
+
+        if let Some(header) = &self.rpc_rate_limit_ip_header {
+            config.client_ip_header = Some(header.clone());
+        }
+        config.trusted_proxies.extend(self.rpc_trusted_proxies.iter().copied());
+
+        Ok((!config.is_empty()).then_some(config))
+    }
+
+    /// Returns the role based method allowlists of `--rpc.roles-config`, if any.
+    pub fn roles_config(&self) -> eyre::Result<Option<RpcRolesConfig>> {
+        let Some(path) = &self.rpc_roles_config else { return Ok(None) };
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| eyre::eyre!("failed to read roles config {}: {err}", path.display()))?;
+        let config = toml::from_str::<RpcRolesConfig>(&content)
+            .map_err(|err| eyre::eyre!("invalid roles config {}: {err}", path.display()))?;
+        config
+            .validate()
+            .map_err(|err| eyre::eyre!("invalid roles config {}: {err}", path.display()))?;
+        Ok(Some(config))
+    }
+
+    /// Returns the allowed CORS domains of `--rpc.cors-file` as a comma separated list, if any.
+    pub fn cors_file_domains(&self) -> eyre::Result<Option<String>> {
+        let Some(path) = &self.rpc_cors_file else { return Ok(None) };
+        let content = std::fs::read_to_string(path)
+            .map_err(|err| eyre::eyre!("failed to read CORS file {}: {err}", path.display()))?;
+        let domains = content
+            .split([',', '\n'])
+            .map(str::trim)
+            .filter(|domain| !domain.is_empty())
+            .collect::<Vec<_>>()
+            .join(",");
+        Ok(Some(domains))
+    }
+
+    /// Returns true if any configuration of the running servers is read from a file, which is
+    /// read again on SIGHUP.
+    pub const fn has_reloadable_config(&self) -> bool {
+        self.rpc_cors_file.is_some() ||
+            self.rpc_rate_limit_config.is_some() ||
+            self.rpc_roles_config.is_some()
+    }
 }
 
 impl Default for RpcServerArgs {
@@ -321,29 +705,51 @@ impl Default for RpcServerArgs {
             http_addr: Ipv4Addr::LOCALHOST.into(),
             http_port: constants::DEFAULT_HTTP_RPC_PORT,
             http_disable_compression: false,
+            http_compression_min_size: constants::DEFAULT_HTTP_COMPRESSION_MIN_SIZE,
             http_api: None,
             http_corsdomain: None,
+            graphql: false,
+            graphql_max_complexity: constants::DEFAULT_GRAPHQL_MAX_COMPLEXITY,
+            graphql_max_depth: constants::DEFAULT_GRAPHQL_MAX_DEPTH,
+            grpc: false,
+            grpc_addr: Ipv4Addr::LOCALHOST.into(),
+            grpc_port: constants::DEFAULT_GRPC_PORT,
             ws: false,
             ws_addr: Ipv4Addr::LOCALHOST.into(),
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
             ws_api: None,
+            ws_ping_interval: Duration::from_secs(constants::DEFAULT_WS_PING_INTERVAL_SECS),
+            ws_idle_timeout: Duration::from_secs(constants::DEFAULT_WS_IDLE_TIMEOUT_SECS),
+            ws_max_missed_pings: constants::DEFAULT_WS_MAX_MISSED_PINGS,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
+            ipc_max_request_size: None,
+            ipc_max_response_size: None,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
+            auth_unix_socket: None,
+            auth_disable_tcp: false,
+            auth_http2: false,
             rpc_jwtsecret: None,
+            rpc_roles_config: None,
+            rpc_cors_file: None,
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
+            rpc_subscription_buffer_size: constants::DEFAULT_SUBSCRIPTION_BUFFER_SIZE,
+            rpc_subscription_overflow: SubscriptionOverflowPolicy::default(),
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_trace_filter_blocks: constants::DEFAULT_MAX_TRACE_FILTER_BLOCKS,
+            rpc_trace_index: false,
+            rpc_trace_index_start_block: 0,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_max_logs_per_stream: (constants::DEFAULT_MAX_LOGS_PER_STREAM as u64).into(),
             rpc_gas_cap: constants::gas_oracle::RPC_DEFAULT_GAS_CAP,
             rpc_tx_fee_cap: constants::DEFAULT_TX_FEE_CAP_WEI,
             rpc_max_simulate_blocks: constants::DEFAULT_MAX_SIMULATE_BLOCKS,
@@ -351,7 +757,33 @@ impl Default for RpcServerArgs {
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
             rpc_proof_permits: constants::DEFAULT_PROOF_PERMITS,
+            rpc_proof_cache_entries: 0,
             builder_disallow: Default::default(),
+            rpc_usage_accounting: false,
+            rpc_usage_api_key_header: constants::DEFAULT_RPC_USAGE_API_KEY_HEADER.to_string(),
+            rpc_usage_log_interval: Duration::from_secs(
+                constants::DEFAULT_RPC_USAGE_LOG_INTERVAL_SECS,
+            ),
+            rpc_slow_query_threshold: None,
+            rpc_rate_limit: Vec::new(),
+            rpc_rate_limit_per_ip: Vec::new(),
+            rpc_rate_limit_ip_header: None,
+            rpc_rate_limit_config: None,
+            rpc_access_log: false,
+            rpc_access_log_file: None,
+            rpc_access_log_sample: 1,
+            rpc_access_log_ip_header: None,
+            rpc_trusted_proxies: Vec::new(),
+            rpc_response_cache: false,
+            rpc_response_cache_size: constants::DEFAULT_RPC_RESPONSE_CACHE_SIZE,
+            rpc_bundle_forward: Vec::new(),
+            rpc_bundle_signing_key: None,
+            rpc_bundle_forward_retries: constants::DEFAULT_BUNDLE_FORWARD_RETRIES,
+            rpc_tx_forward: None,
+            rpc_tx_forward_only: false,
+            rpc_tx_forward_retries: constants::DEFAULT_TX_FORWARD_RETRIES,
+            rpc_archive_fallback: None,
+            rpc_archive_fallback_cache: constants::DEFAULT_ARCHIVE_STATE_CACHED_BLOCKS,
         }
     }
 }
@@ -392,6 +824,8 @@ impl TypedValueParser for RpcModuleSelectionValueParser {
 mod tests {
     use super::*;
     use clap::{Args, Parser};
+    use reth_rpc_server_types::RateLimitQuota;
+    use std::net::Ipv6Addr;
 
     /// A helper type to parse Args more easily
     #[derive(Parser)]
@@ -467,4 +901,108 @@ mod tests {
         let expected = 1_000_000_000_000_000_000u128;
         assert_eq!(args.rpc_tx_fee_cap, expected); // 1 ETH default cap
     }
+    #[test]
+    fn test_rpc_rate_limit_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.rate_limit_config().unwrap(), None);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.rate-limit",
+            "debug_*=10:20,eth_getLogs=50",
+            "--rpc.rate-limit-per-ip",
+            "debug_*=1",
+            "--rpc.rate-limit-ip-header",
+            "x-forwarded-for",
+            "--rpc.trusted-proxies",
+            "127.0.0.1,::1",
+        ])
+        .args;
+        let config = args.rate_limit_config().unwrap().unwrap();
+        assert_eq!(config.client_ip_header.as_deref(), Some("x-forwarded-for"));
+        assert_eq!(
+            config.trusted_proxies,
+            vec![IpAddr::from(Ipv4Addr::LOCALHOST), IpAddr::from(Ipv6Addr::LOCALHOST)]
+        );
+        assert_eq!(
+            config.methods,
+            vec![
+                MethodRateLimit {
+                    method: "debug_*".to_string(),
+                    global: Some(RateLimitQuota::per_second(10).with_burst(20)),
+                    per_ip: Some(RateLimitQuota::per_second(1)),
+                },
+                MethodRateLimit {
+                    method: "eth_getLogs".to_string(),
+                    global: Some(RateLimitQuota::per_second(50)),
+                    per_ip: None,
+                },
+            ]
+        );
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.rate-limit",
+            "debug_*"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_rpc_roles_config() {
+        // the roles are read from the JWT of clients
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.roles-config",
+            "roles.toml"
+        ])
+        .is_err());
+
+        let path = std::env::temp_dir().join(format!("reth-roles-{}.toml", std::process::id()));
+        std::fs::write(&path, "default_roles = [\"read\"]\n\n[roles]\nread = [\"eth_*\"]\n")
+            .unwrap();
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.jwtsecret",
+            "f79ae8046bc11c9927afe911db7143c51a806c4a537cc08e0d37140b0192f430",
+            "--rpc.roles-config",
+            path.to_str().unwrap(),
+        ])
+        .args;
+        let config = args.roles_config();
+        std::fs::remove_file(&path).unwrap();
+
+        let config = config.unwrap().unwrap();
+        assert_eq!(config.role_claim, "roles");
+        assert!(config.allows(["read"], "eth_call"));
+        assert!(!config.allows(["read"], "admin_addPeer"));
+    }
+
+    #[test]
+    fn test_rpc_cors_file() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.cors_file_domains().unwrap(), None);
+        assert!(!args.has_reloadable_config());
+
+        let path = std::env::temp_dir().join(format!("reth-cors-{}", std::process::id()));
+        std::fs::write(
+            &path,
+            "https://a.example.com, https://b.example.com\nhttps://c.example.com\n",
+        )
+        .unwrap();
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.cors-file",
+            path.to_str().unwrap(),
+        ])
+        .args;
+        let domains = args.cors_file_domains();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(args.has_reloadable_config());
+        assert_eq!(
+            domains.unwrap().as_deref(),
+            Some("https://a.example.com,https://b.example.com,https://c.example.com")
+        );
+    }
 }
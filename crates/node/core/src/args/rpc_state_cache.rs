@@ -1,7 +1,7 @@
 use clap::Args;
 use reth_rpc_server_types::constants::cache::{
     DEFAULT_BLOCK_CACHE_MAX_LEN, DEFAULT_CONCURRENT_DB_REQUESTS, DEFAULT_HEADER_CACHE_MAX_LEN,
-    DEFAULT_RECEIPT_CACHE_MAX_LEN,
+    DEFAULT_RECEIPT_CACHE_MAX_LEN, DEFAULT_STATE_SNAPSHOT_CACHE_MAX_LEN,
 };
 
 /// Parameters to configure RPC state cache.
@@ -36,6 +36,14 @@ pub struct RpcStateCacheArgs {
         default_value_t = DEFAULT_CONCURRENT_DB_REQUESTS,
     )]
     pub max_concurrent_db_requests: usize,
+
+    /// Max number of blocks whose state is shared by `eth_call` and `eth_estimateGas` requests
+    /// against the same block.
+    #[arg(
+        long = "rpc-cache.max-state-snapshots",
+        default_value_t = DEFAULT_STATE_SNAPSHOT_CACHE_MAX_LEN,
+    )]
+    pub max_state_snapshots: u32,
 }
 
 impl RpcStateCacheArgs {
@@ -44,6 +52,7 @@ impl RpcStateCacheArgs {
         self.max_blocks = 0;
         self.max_receipts = 0;
         self.max_headers = 0;
+        self.max_state_snapshots = 0;
     }
 }
 
@@ -54,6 +63,7 @@ impl Default for RpcStateCacheArgs {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_headers: DEFAULT_HEADER_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            max_state_snapshots: DEFAULT_STATE_SNAPSHOT_CACHE_MAX_LEN,
         }
     }
 }
@@ -0,0 +1,81 @@
+//! clap [Args](clap::Args) for dedicated tokio runtimes
+
+use clap::{builder::RangedU64ValueParser, Args};
+
+/// Parameters for running components of the node on dedicated tokio runtimes.
+///
+/// By default all components share the main runtime, so heavy load on one component, e.g. the RPC
+/// server, can delay the processing of engine messages.
+#[derive(Debug, Clone, Copy, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Runtime")]
+pub struct RuntimeArgs {
+    /// Run the network stack on a dedicated runtime with the given number of worker threads.
+    ///
+    /// By default the network stack runs on the main runtime.
+    #[arg(
+        long = "runtime.network-threads",
+        value_name = "THREADS",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub network_threads: Option<usize>,
+
+    /// Run the consensus engine and the engine API server on a dedicated runtime with the given
+    /// number of worker threads.
+    ///
+    /// By default the engine runs on the main runtime.
+    #[arg(
+        long = "runtime.engine-threads",
+        value_name = "THREADS",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub engine_threads: Option<usize>,
+
+    /// Run the RPC servers on a dedicated runtime with the given number of worker threads.
+    ///
+    /// By default the RPC servers run on the main runtime.
+    #[arg(
+        long = "runtime.rpc-threads",
+        value_name = "THREADS",
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub rpc_threads: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_runtime_args() {
+        let args = CommandParser::<RuntimeArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, RuntimeArgs::default());
+
+        let args = CommandParser::<RuntimeArgs>::parse_from([
+            "reth",
+            "--runtime.engine-threads",
+            "2",
+            "--runtime.rpc-threads",
+            "8",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            RuntimeArgs { network_threads: None, engine_threads: Some(2), rpc_threads: Some(8) }
+        );
+
+        assert!(CommandParser::<RuntimeArgs>::try_parse_from([
+            "reth",
+            "--runtime.network-threads",
+            "0"
+        ])
+        .is_err());
+    }
+}
@@ -121,6 +121,13 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.lifetime", value_parser = parse_duration_from_secs_or_ms, default_value = "10800", value_name = "DURATION")]
     pub max_queued_lifetime: Duration,
 
+    /// Maximum amount of time a transaction may stay parked before it's evicted.
+    ///
+    /// Evicted transactions emit an expired event with the reason why they weren't included.
+    /// Disabled by default.
+    #[arg(long = "txpool.max-parked-lifetime", value_parser = parse_duration_from_secs_or_ms, value_name = "DURATION")]
+    pub max_parked_lifetime: Option<Duration>,
+
     /// Path to store the local transaction backup at, to survive node restarts.
     #[arg(long = "txpool.transactions-backup", alias = "txpool.journal", value_name = "PATH")]
     pub transactions_backup_path: Option<std::path::PathBuf>,
@@ -163,6 +170,7 @@ impl Default for TxPoolArgs {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_parked_lifetime: None,
             transactions_backup_path: None,
             disable_transactions_backup: false,
         }
@@ -207,6 +215,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
+            max_parked_lifetime: self.max_parked_lifetime,
         }
     }
 }
@@ -253,6 +262,21 @@ mod tests {
         assert_eq!(args.max_queued_lifetime, Duration::from_secs(3 * 60 * 60)); // Default is 3h
     }
 
+    #[test]
+    fn txpool_parse_max_parked_lifetime() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.max-parked-lifetime",
+            "600",
+        ])
+        .args;
+        assert_eq!(args.max_parked_lifetime, Some(Duration::from_secs(600)));
+
+        // Disabled by default
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.max_parked_lifetime, None);
+    }
+
     #[test]
     fn txpool_parse_max_tx_lifetime_invalid() {
         let result =
@@ -1,7 +1,7 @@
 //! Config traits for various node components.
 
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_36M;
-use alloy_primitives::Bytes;
+use alloy_primitives::{Address, Bytes, TxHash};
 use reth_chainspec::{Chain, ChainKind, NamedChain};
 use reth_network::{protocol::IntoRlpxSubProtocol, NetworkPrimitives};
 use reth_transaction_pool::PoolConfig;
@@ -35,6 +35,18 @@ pub trait PayloadBuilderConfig {
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
 
+    /// Addresses whose transactions must not be included in built payloads, either as sender or
+    /// recipient.
+    fn denied_addresses(&self) -> &[Address] {
+        &[]
+    }
+
+    /// Hashes of transactions that must be included in built payloads if they are available in the
+    /// pool, in the order they are executed.
+    fn mandatory_transactions(&self) -> &[TxHash] {
+        &[]
+    }
+
     /// Returns the configured gas limit if set, or a chain-specific default.
     fn gas_limit_for(&self, chain: Chain) -> u64 {
         if let Some(limit) = self.gas_limit() {
@@ -355,6 +355,13 @@ impl<D> ChainPath<D> {
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
     }
+
+    /// Returns the path to the recovery points directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/recovery_points`
+    pub fn recovery_points(&self) -> PathBuf {
+        self.data_dir().join("recovery_points")
+    }
 }
 
 impl<D> AsRef<Path> for ChainPath<D> {
@@ -2,8 +2,8 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        CheckpointArgs, ConsensusArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs,
+        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, RuntimeArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     utils::get_single_header,
@@ -154,6 +154,15 @@ pub struct NodeConfig<ChainSpec> {
 
     /// All ERA import related arguments with --era prefix
     pub era: EraArgs,
+
+    /// All consensus related arguments with --consensus prefix
+    pub consensus: ConsensusArgs,
+
+    /// All checkpoint manifest related arguments with --checkpoints prefix
+    pub checkpoints: CheckpointArgs,
+
+    /// All dedicated runtime related arguments with --runtime prefix
+    pub runtime: RuntimeArgs,
 }
 
 impl NodeConfig<ChainSpec> {
@@ -184,6 +193,9 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             datadir: DatadirArgs::default(),
             engine: EngineArgs::default(),
             era: EraArgs::default(),
+            consensus: ConsensusArgs::default(),
+            checkpoints: CheckpointArgs::default(),
+            runtime: RuntimeArgs::default(),
         }
     }
 
@@ -272,7 +284,7 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
     }
 
     /// Set the database args for the node
-    pub const fn with_db(mut self, db: DatabaseArgs) -> Self {
+    pub fn with_db(mut self, db: DatabaseArgs) -> Self {
         self.db = db;
         self
     }
@@ -289,6 +301,24 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the consensus args for the node
+    pub const fn with_consensus(mut self, consensus: ConsensusArgs) -> Self {
+        self.consensus = consensus;
+        self
+    }
+
+    /// Set the checkpoint manifest args for the node
+    pub fn with_checkpoints(mut self, checkpoints: CheckpointArgs) -> Self {
+        self.checkpoints = checkpoints;
+        self
+    }
+
+    /// Set the dedicated runtime args for the node
+    pub const fn with_runtime(mut self, runtime: RuntimeArgs) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig>
     where
@@ -490,6 +520,9 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning: self.pruning,
             engine: self.engine,
             era: self.era,
+            consensus: self.consensus,
+            checkpoints: self.checkpoints,
+            runtime: self.runtime,
         }
     }
 
@@ -521,12 +554,15 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             txpool: self.txpool.clone(),
             builder: self.builder.clone(),
             debug: self.debug.clone(),
-            db: self.db,
+            db: self.db.clone(),
             dev: self.dev,
             pruning: self.pruning.clone(),
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
             era: self.era.clone(),
+            consensus: self.consensus,
+            checkpoints: self.checkpoints.clone(),
+            runtime: self.runtime,
         }
     }
 }
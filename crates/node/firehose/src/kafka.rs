@@ -0,0 +1,80 @@
+//! Producing to Kafka brokers with the [`rskafka`] client.
+//!
+//! All records are produced to partition 0 of their topic, in order, and without compression.
+
+use crate::{unix_timestamp_millis, FirehoseMessage};
+use rskafka::{
+    chrono::DateTime,
+    client::{
+        partition::{Compression, PartitionClient, UnknownTopicHandling},
+        Client, ClientBuilder,
+    },
+    record::Record,
+};
+use std::{collections::HashMap, io};
+use url::Url;
+
+/// The default port of Kafka brokers.
+const DEFAULT_PORT: u16 = 9092;
+
+/// The partition records are produced to.
+const PARTITION: i32 = 0;
+
+/// A connection to a Kafka cluster.
+#[derive(Debug)]
+pub(crate) struct KafkaConnection {
+    client: Client,
+    /// The clients of the partitions that were produced to, by topic.
+    partitions: HashMap<String, PartitionClient>,
+}
+
+impl KafkaConnection {
+    /// Connects to the cluster of the bootstrap broker.
+    pub(crate) async fn connect(url: &Url) -> io::Result<Self> {
+        let host = url.host_str().unwrap_or_default();
+        let port = url.port().unwrap_or(DEFAULT_PORT);
+        let client = ClientBuilder::new(vec![format!("{host}:{port}")])
+            .client_id("reth")
+            .build()
+            .await
+            .map_err(io::Error::other)?;
+        Ok(Self { client, partitions: HashMap::new() })
+    }
+
+    /// Produces the messages and waits until the leaders of the partitions acknowledged them.
+    pub(crate) async fn publish(&mut self, messages: &[FirehoseMessage]) -> io::Result<()> {
+        let timestamp =
+            DateTime::from_timestamp_millis(unix_timestamp_millis() as i64).unwrap_or_default();
+
+        // records of the same topic are produced in one request, in order
+        let mut topics = Vec::<(&str, Vec<Record>)>::new();
+        for message in messages {
+            let record = Record {
+                key: None,
+                value: Some(message.payload.clone()),
+                headers: Default::default(),
+                timestamp,
+            };
+            match topics.iter_mut().find(|(topic, _)| *topic == message.topic) {
+                Some((_, records)) => records.push(record),
+                None => topics.push((&message.topic, vec![record])),
+            }
+        }
+
+        for (topic, records) in topics {
+            if !self.partitions.contains_key(topic) {
+                let partition = self
+                    .client
+                    .partition_client(topic, PARTITION, UnknownTopicHandling::Retry)
+                    .await
+                    .map_err(io::Error::other)?;
+                self.partitions.insert(topic.to_string(), partition);
+            }
+            self.partitions[topic]
+                .produce(records, Compression::NoCompression)
+                .await
+                .map_err(io::Error::other)?;
+        }
+        Ok(())
+    }
+}
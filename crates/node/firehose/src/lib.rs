@@ -0,0 +1,430 @@
+//! Publishes transaction pool and canonical chain events to an external message queue.
+//!
+//! The firehose is configured by the [`FirehoseConfig`] section of the configuration file.
+//! Events are encoded as JSON objects with a versioned schema:
+//!
+//! ```json
+//! {"version":1,"kind":"block","timestamp":1700000000000,"data":{"number":"0x1", ...}}
+//! ```
+//!
+//! where `timestamp` is the time the event was observed in milliseconds since the epoch. Events
+//! are published to the subjects (NATS) or topics (Kafka) `<prefix>.transactions`,
+//! `<prefix>.blocks` and `<prefix>.reorgs`.
+//!
+//! Producers never wait for the message queue: events are buffered up to the configured size and
+//! dropped once the buffer is full, e.g. while the message queue is unreachable.
+//!
+//! ## Feature Flags
+//!
+//! - `nats`: Publishes to NATS servers with the [`async_nats`] client.
+//! - `kafka`: Publishes to Kafka brokers with the [`rskafka`] client.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+#[cfg(feature = "kafka")]
+mod kafka;
+#[cfg(feature = "nats")]
+mod nats;
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use futures::{Stream, StreamExt};
+use reth_chain_state::CanonStateNotification;
+use reth_config::FirehoseConfig;
+use reth_primitives_traits::{BlockBody, NodePrimitives};
+use reth_transaction_pool::{FullTransactionEvent, NotIncludedReason, TransactionPool};
+use serde_json::{json, Value};
+use std::{
+    io,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+/// The version of the schema of the published events.
+pub const FIREHOSE_SCHEMA_VERSION: u64 = 1;
+
+/// The maximum delay between two attempts to publish a batch.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// An event that is published to the message queue.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirehoseMessage {
+    /// The subject or topic the event is published to.
+    pub topic: String,
+    /// The JSON encoded event.
+    pub payload: Vec<u8>,
+}
+
+/// The kind of a published event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FirehoseEventKind {
+    /// A transaction pool event.
+    Transaction,
+    /// A block that became canonical.
+    Block,
+    /// A reorg of the canonical chain.
+    Reorg,
+}
+
+impl FirehoseEventKind {
+    /// Returns the name of the kind, as it appears in the events.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Transaction => "transaction",
+            Self::Block => "block",
+            Self::Reorg => "reorg",
+        }
+    }
+
+    /// Returns the suffix of the subject or topic of events of this kind.
+    pub const fn topic(&self) -> &'static str {
+        match self {
+            Self::Transaction => "transactions",
+            Self::Block => "blocks",
+            Self::Reorg => "reorgs",
+        }
+    }
+}
+
+/// Creates a [`Firehose`] publisher and its [`FirehoseHandle`] from the config.
+pub fn firehose(config: FirehoseConfig) -> (FirehoseHandle, Firehose) {
+    let (tx, rx) = mpsc::channel(config.buffer_size.max(1));
+    let dropped = Arc::new(AtomicU64::new(0));
+    let handle =
+        FirehoseHandle { prefix: config.prefix.clone().into(), tx, dropped: dropped.clone() };
+    (handle, Firehose { config, rx, dropped })
+}
+
+/// Submits events to the [`Firehose`] without waiting for them to be published.
+#[derive(Debug, Clone)]
+pub struct FirehoseHandle {
+    /// The prefix of the topics.
+    prefix: Arc<str>,
+    /// Sends events to the publisher.
+    tx: mpsc::Sender<FirehoseMessage>,
+    /// The number of events that were dropped because the buffer was full.
+    dropped: Arc<AtomicU64>,
+}
+
+impl FirehoseHandle {
+    /// Encodes the event and submits it to the publisher.
+    ///
+    /// The event is dropped if the buffer of the publisher is full.
+    pub fn publish(&self, kind: FirehoseEventKind, data: Value) {
+        let message = FirehoseMessage {
+            topic: format!("{}.{}", self.prefix, kind.topic()),
+            payload: encode_event(kind, data, unix_timestamp_millis()),
+        };
+        if self.tx.try_send(message).is_err() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Publishes the transaction pool events until the pool is dropped.
+    pub async fn publish_pool_events<Pool>(self, pool: Pool)
+    where
+        Pool: TransactionPool,
+    {
+        let mut events = pool.all_transactions_event_listener();
+        while let Some(event) = events.next().await {
+            if let Some(data) = pool_event_data(&pool, event) {
+                self.publish(FirehoseEventKind::Transaction, data);
+            }
+        }
+    }
+
+    /// Publishes the canonical chain events of the stream until it ends.
+    ///
+    /// A reorg is published as a [`FirehoseEventKind::Reorg`] event with the reverted blocks,
+    /// followed by the blocks of the new chain.
+    pub async fn publish_canonical_events<N, St>(self, notifications: St)
+    where
+        N: NodePrimitives,
+        St: Stream<Item = CanonStateNotification<N>>,
+    {
+        let mut notifications = std::pin::pin!(notifications);
+        while let Some(notification) = notifications.next().await {
+            if let Some(reverted) = notification.reverted() {
+                let reverted = reverted
+                    .blocks()
+                    .values()
+                    .rev()
+                    .map(
+                        |block| json!({ "number": quantity(block.number()), "hash": block.hash() }),
+                    )
+                    .collect::<Vec<_>>();
+                self.publish(FirehoseEventKind::Reorg, json!({ "reverted": reverted }));
+            }
+
+            for block in notification.committed().blocks_iter() {
+                let data = json!({
+                    "number": quantity(block.number()),
+                    "hash": block.hash(),
+                    "parentHash": block.parent_hash(),
+                    "timestamp": quantity(block.timestamp()),
+                    "gasUsed": quantity(block.gas_used()),
+                    "transactionCount": block.body().transaction_count(),
+                });
+                self.publish(FirehoseEventKind::Block, data);
+            }
+        }
+    }
+}
+
+/// Publishes the submitted events in batches to the configured message queue.
+#[derive(Debug)]
+pub struct Firehose {
+    config: FirehoseConfig,
+    /// Receives the submitted events.
+    rx: mpsc::Receiver<FirehoseMessage>,
+    /// The number of events that were dropped because the buffer was full.
+    dropped: Arc<AtomicU64>,
+}
+
+impl Firehose {
+    /// Publishes the submitted events until all handles are dropped.
+    ///
+    /// Batches that can't be published are retried with an exponential backoff after reconnecting,
+    /// so events are published in order and at least once.
+    pub async fn run(mut self) {
+        let mut sink = None;
+        let mut batch = Vec::with_capacity(self.config.batch_size);
+        let mut flush = tokio::time::interval(self.config.flush_interval);
+        flush.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut closed = false;
+
+        info!(target: "reth::firehose", url = %self.config.url, "Starting firehose");
+
+        while !closed || !batch.is_empty() {
+            if !closed && batch.len() < self.config.batch_size {
+                tokio::select! {
+                    message = self.rx.recv() => match message {
+                        Some(message) => {
+                            batch.push(message);
+                            if batch.len() < self.config.batch_size {
+                                continue
+                            }
+                        }
+                        None => closed = true,
+                    },
+                    _ = flush.tick() => {}
+                }
+            }
+
+            let dropped = self.dropped.swap(0, Ordering::Relaxed);
+            if dropped > 0 {
+                warn!(
+                    target: "reth::firehose",
+                    dropped,
+                    "Dropped events, the firehose buffer is full"
+                );
+            }
+
+            let mut backoff = Duration::from_secs(1);
+            loop {
+                match self.publish(&mut sink, &batch).await {
+                    Ok(()) => break,
+                    Err(err) => {
+                        sink = None;
+                        warn!(
+                            target: "reth::firehose",
+                            %err,
+                            events = batch.len(),
+                            ?backoff,
+                            "Failed to publish events"
+                        );
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(MAX_RETRY_BACKOFF);
+                    }
+                }
+            }
+            if !batch.is_empty() {
+                debug!(target: "reth::firehose", events = batch.len(), "Published events");
+            }
+            batch.clear();
+        }
+    }
+
+    /// Publishes the batch, connecting to the message queue first if necessary.
+    async fn publish(
+        &self,
+        sink: &mut Option<FirehoseSink>,
+        batch: &[FirehoseMessage],
+    ) -> io::Result<()> {
+        let sink = match sink {
+            Some(sink) => sink,
+            None => sink.insert(FirehoseSink::connect(&self.config).await?),
+        };
+        sink.publish(batch).await
+    }
+}
+
+/// A connection to the message queue.
+#[derive(Debug)]
+enum FirehoseSink {
+    /// A connection to a NATS server.
+    #[cfg(feature = "nats")]
+    Nats(nats::NatsConnection),
+    /// A connection to a Kafka cluster.
+    #[cfg(feature = "kafka")]
+    Kafka(kafka::KafkaConnection),
+}
+
+impl FirehoseSink {
+    /// Connects to the configured message queue.
+    ///
+    /// Fails if the client of the message queue wasn't enabled with its feature.
+    async fn connect(config: &FirehoseConfig) -> io::Result<Self> {
+        match config.url.scheme() {
+            #[cfg(feature = "nats")]
+            "nats" => Ok(Self::Nats(nats::NatsConnection::connect(&config.url).await?)),
+            #[cfg(feature = "kafka")]
+            "kafka" => Ok(Self::Kafka(kafka::KafkaConnection::connect(&config.url).await?)),
+            scheme @ ("nats" | "kafka") => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("reth was built without the {scheme} feature"),
+            )),
+            scheme => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unsupported firehose scheme {scheme}"),
+            )),
+        }
+    }
+
+    /// Publishes the messages and waits until the message queue acknowledged them.
+    #[cfg_attr(
+        not(any(feature = "nats", feature = "kafka")),
+        allow(clippy::needless_pass_by_ref_mut)
+    )]
+    async fn publish(&mut self, messages: &[FirehoseMessage]) -> io::Result<()> {
+        match self {
+            #[cfg(feature = "nats")]
+            Self::Nats(connection) => connection.publish(messages).await,
+            #[cfg(feature = "kafka")]
+            Self::Kafka(connection) => connection.publish(messages).await,
+            #[cfg(not(any(feature = "nats", feature = "kafka")))]
+            _ => {
+                let _ = messages;
+                unreachable!("no message queue client was enabled")
+            }
+        }
+    }
+}
+
+/// Returns the data of a transaction pool event, if it's published.
+fn pool_event_data<Pool: TransactionPool>(
+    pool: &Pool,
+    event: FullTransactionEvent<Pool::Transaction>,
+) -> Option<Value> {
+    let data = match event {
+        FullTransactionEvent::Pending(hash) => pooled_transaction_data(pool, "pending", hash),
+        FullTransactionEvent::Queued(hash) => pooled_transaction_data(pool, "queued", hash),
+        FullTransactionEvent::Mined { tx_hash, block_hash } => {
+            json!({ "event": "mined", "hash": tx_hash, "blockHash": block_hash })
+        }
+        FullTransactionEvent::Replaced { transaction, replaced_by } => {
+            json!({ "event": "replaced", "hash": transaction.hash(), "replacedBy": replaced_by })
+        }
+        FullTransactionEvent::Discarded(hash) => json!({ "event": "discarded", "hash": hash }),
+        FullTransactionEvent::Invalid(hash) => json!({ "event": "invalid", "hash": hash }),
+        FullTransactionEvent::Expired { tx_hash, reason } => {
+            json!({ "event": "expired", "hash": tx_hash, "reason": not_included_reason(&reason) })
+        }
+        // propagation is a networking detail
+        FullTransactionEvent::Propagated(_) => return None,
+    };
+    Some(data)
+}
+
+/// Returns the data of an event of a transaction that was added to the pool.
+fn pooled_transaction_data<Pool: TransactionPool>(pool: &Pool, event: &str, hash: B256) -> Value {
+    let mut data = json!({ "event": event, "hash": hash });
+    if let Some(tx) = pool.get(&hash) {
+        data["sender"] = json!(tx.sender());
+        data["nonce"] = quantity(tx.nonce());
+        data["gasLimit"] = quantity(tx.gas_limit());
+        data["maxFeePerGas"] = json!(format!("{:#x}", tx.max_fee_per_gas()));
+    }
+    data
+}
+
+/// Returns the name of the reason a transaction expired.
+const fn not_included_reason(reason: &NotIncludedReason) -> &'static str {
+    match reason {
+        NotIncludedReason::NonceGap { .. } => "nonceGap",
+        NotIncludedReason::ParkedAncestor => "parkedAncestor",
+        NotIncludedReason::InsufficientBalance => "insufficientBalance",
+        NotIncludedReason::ExceedsBlockGasLimit => "exceedsBlockGasLimit",
+        NotIncludedReason::Underpriced { .. } => "underpriced",
+        NotIncludedReason::BlobFeeTooLow { .. } => "blobFeeTooLow",
+    }
+}
+
+/// Encodes the event with its schema version, kind and timestamp.
+fn encode_event(kind: FirehoseEventKind, data: Value, timestamp: u64) -> Vec<u8> {
+    let event = json!({
+        "version": FIREHOSE_SCHEMA_VERSION,
+        "kind": kind.as_str(),
+        "timestamp": timestamp,
+        "data": data,
+    });
+    serde_json::to_vec(&event).expect("events are serializable")
+}
+
+/// Encodes the number as a hex quantity, like the RPC does.
+fn quantity(value: u64) -> Value {
+    json!(format!("{value:#x}"))
+}
+
+/// Returns the current time in milliseconds since the epoch.
+fn unix_timestamp_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_versioned_events() {
+        let payload = encode_event(FirehoseEventKind::Block, json!({ "number": quantity(1) }), 42);
+        let event: Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(
+            event,
+            json!({ "version": 1, "kind": "block", "timestamp": 42, "data": { "number": "0x1" } })
+        );
+    }
+
+    #[tokio::test]
+    async fn drops_events_when_buffer_is_full() {
+        let config = FirehoseConfig { buffer_size: 2, ..Default::default() };
+        let (handle, mut firehose) = firehose(config);
+
+        for number in 0..3 {
+            handle.publish(FirehoseEventKind::Block, json!({ "number": quantity(number) }));
+        }
+        assert_eq!(firehose.dropped.load(Ordering::Relaxed), 1);
+
+        let message = firehose.rx.recv().await.unwrap();
+        assert_eq!(message.topic, "reth.blocks");
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_scheme() {
+        let config =
+            FirehoseConfig { url: "amqp://localhost:5672".parse().unwrap(), ..Default::default() };
+        let err = FirehoseSink::connect(&config).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,36 @@
+//! Publishing to a NATS server with the [`async_nats`] client.
+
+use crate::FirehoseMessage;
+use std::io;
+use url::Url;
+
+/// A connection to a NATS server.
+#[derive(Debug)]
+pub(crate) struct NatsConnection {
+    client: async_nats::Client,
+}
+
+impl NatsConnection {
+    /// Connects to the server.
+    pub(crate) async fn connect(url: &Url) -> io::Result<Self> {
+        let client = async_nats::ConnectOptions::new()
+            .name("reth")
+            .connect(url.to_string())
+            .await
+            .map_err(io::Error::other)?;
+        Ok(Self { client })
+    }
+
+    /// Publishes the messages and waits until the server processed them.
+    pub(crate) async fn publish(&mut self, messages: &[FirehoseMessage]) -> io::Result<()> {
+        for message in messages {
+            self.client
+                .publish(message.topic.clone(), message.payload.clone().into())
+                .await
+                .map_err(io::Error::other)?;
+        }
+        // the server processes the messages of a connection in order, so the flush, which waits
+        // for the answer to a ping, confirms that all messages were processed
+        self.client.flush().await.map_err(io::Error::other)
+    }
+}
@@ -286,6 +286,10 @@ impl EthChainSpec for OpChainSpec {
     fn final_paris_total_difficulty(&self) -> Option<U256> {
         self.inner.final_paris_total_difficulty()
     }
+
+    fn max_extra_data_size(&self) -> usize {
+        self.inner.max_extra_data_size()
+    }
 }
 
 impl Hardforks for OpChainSpec {
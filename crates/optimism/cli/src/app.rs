@@ -101,6 +101,9 @@ where
             Commands::ReExecute(command) => {
                 runner.run_until_ctrl_c(command.execute::<OpNode>(components))
             }
+            Commands::VerifyChain(command) => {
+                runner.run_until_ctrl_c(command.execute::<OpNode>(components))
+            }
         }
     }
 
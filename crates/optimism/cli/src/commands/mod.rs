@@ -7,7 +7,7 @@ use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     config_cmd, db, dump_genesis, init_cmd,
     node::{self, NoArgs},
-    p2p, prune, re_execute, recover, stage,
+    p2p, prune, re_execute, recover, stage, verify_chain,
 };
 use std::{fmt, sync::Arc};
 
@@ -65,6 +65,9 @@ pub enum Commands<Spec: ChainSpecParser = OpChainSpecParser, Ext: clap::Args + f
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<Spec>),
+    /// Re-verify the receipts and state roots of historical blocks in parallel.
+    #[command(name = "verify-chain")]
+    VerifyChain(verify_chain::Command<Spec>),
 }
 
 impl<
@@ -90,6 +93,7 @@ impl<
             #[cfg(feature = "dev")]
             Self::TestVectors(_) => None,
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::VerifyChain(cmd) => cmd.chain_spec(),
         }
     }
 }
@@ -1,11 +1,12 @@
 //! Optimism consensus errors
 
-use alloy_primitives::B256;
-use reth_consensus::ConsensusError;
+use alloc::boxed::Box;
+use alloy_primitives::{BlockNumber, B256};
+use reth_consensus::{ConsensusError, CustomConsensusError};
 use reth_storage_errors::provider::ProviderError;
 
 /// Optimism consensus error.
-#[derive(Debug, Clone, thiserror::Error)]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 pub enum OpConsensusError {
     /// Block body has non-empty withdrawals list (l1 withdrawals).
     #[error("non-empty block body withdrawals list")]
@@ -27,4 +28,30 @@ pub enum OpConsensusError {
     /// L1 [`ConsensusError`], that also occurs on L2.
     #[error(transparent)]
     Eth(#[from] ConsensusError),
+    /// Validation of a block failed.
+    #[error("failed to verify block {number}: {error}")]
+    InvalidBlock {
+        /// The number of the block.
+        number: BlockNumber,
+        /// Why the block is invalid.
+        error: Box<Self>,
+    },
+}
+
+impl OpConsensusError {
+    /// Wraps the error with the number of the block that failed validation.
+    pub fn with_block_number(self, number: BlockNumber) -> Self {
+        Self::InvalidBlock { number, error: Box::new(self) }
+    }
+}
+
+impl CustomConsensusError for OpConsensusError {}
+
+impl From<OpConsensusError> for ConsensusError {
+    fn from(err: OpConsensusError) -> Self {
+        match err {
+            OpConsensusError::Eth(err) => err,
+            err => Self::custom(err),
+        }
+    }
 }
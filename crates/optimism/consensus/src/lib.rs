@@ -11,7 +11,7 @@
 
 extern crate alloc;
 
-use alloc::{format, sync::Arc};
+use alloc::sync::Arc;
 use alloy_consensus::{BlockHeader as _, EMPTY_OMMER_ROOT_HASH};
 use alloy_primitives::B64;
 use core::fmt::Debug;
@@ -106,9 +106,8 @@ where
 
         // Check empty shanghai-withdrawals
         if self.chain_spec.is_canyon_active_at_timestamp(block.timestamp()) {
-            canyon::ensure_empty_shanghai_withdrawals(block.body()).map_err(|err| {
-                ConsensusError::Other(format!("failed to verify block {}: {err}", block.number()))
-            })?
+            canyon::ensure_empty_shanghai_withdrawals(block.body())
+                .map_err(|err| err.with_block_number(block.number()))?
         } else {
             return Ok(())
         }
@@ -120,9 +119,8 @@ where
         // Check withdrawals root field in header
         if self.chain_spec.is_isthmus_active_at_timestamp(block.timestamp()) {
             // storage root of withdrawals pre-deploy is verified post-execution
-            isthmus::ensure_withdrawals_storage_root_is_some(block.header()).map_err(|err| {
-                ConsensusError::Other(format!("failed to verify block {}: {err}", block.number()))
-            })?
+            isthmus::ensure_withdrawals_storage_root_is_some(block.header())
+                .map_err(|err| err.with_block_number(block.number()))?
         } else {
             // canyon is active, else would have returned already
             canyon::ensure_empty_withdrawals_root(block.header())?
@@ -162,7 +160,7 @@ where
         // is greater than its parent timestamp.
 
         // validate header extra data for all networks post merge
-        validate_header_extra_data(header)?;
+        validate_header_extra_data(header, self.chain_spec.max_extra_data_size())?;
         validate_header_gas(header)?;
         validate_header_base_fee(header, &self.chain_spec)
     }
@@ -204,7 +202,12 @@ where
 
         // ensure that the blob gas fields for this block
         if let Some(blob_params) = self.chain_spec.blob_params_at_timestamp(header.timestamp()) {
-            validate_against_parent_4844(header.header(), parent.header(), blob_params)?;
+            validate_against_parent_4844(
+                header.header(),
+                parent.header(),
+                blob_params,
+                &self.chain_spec,
+            )?;
         }
 
         Ok(())
@@ -152,9 +152,7 @@ where
                 state,
                 block.header(),
             )
-            .map_err(|err| {
-                ConsensusError::Other(format!("failed to verify block post-execution: {err}"))
-            })?
+            .map_err(ConsensusError::from)?
         }
 
         Ok(())
@@ -58,6 +58,7 @@ where
             EngineCapabilities::new(OP_ENGINE_CAPABILITIES.iter().copied()),
             engine_validator,
             ctx.config.engine.accept_execution_requests_hash,
+            None,
         );
 
         Ok(OpEngineApi::new(inner))
@@ -1,6 +1,7 @@
 use super::OpNodeCore;
 use crate::{OpEthApi, OpEthApiError};
 use alloy_rpc_types_eth::TransactionRequest;
+use jsonrpsee::Extensions;
 use op_revm::OpTransaction;
 use reth_evm::{execute::BlockExecutorFactory, ConfigureEvm, EvmFactory, TxEnvFor};
 use reth_node_api::NodePrimitives;
@@ -8,6 +9,7 @@ use reth_rpc_eth_api::{
     helpers::{estimate::EstimateCall, Call, EthCall, LoadBlock, LoadState, SpawnBlocking},
     FromEvmError, FullEthApiTypes, RpcConvert, RpcTypes,
 };
+use reth_rpc_eth_types::CallPolicy;
 use reth_storage_api::{errors::ProviderError, ProviderHeader, ProviderTx};
 use revm::context::TxEnv;
 
@@ -56,4 +58,12 @@ where
     fn max_simulate_blocks(&self) -> u64 {
         self.inner.eth_api.max_simulate_blocks()
     }
+
+    fn call_policy(&self, extensions: &Extensions) -> CallPolicy {
+        self.inner
+            .eth_api
+            .call_policies()
+            .map(|policies| policies.call_policy(extensions))
+            .unwrap_or_default()
+    }
 }
@@ -26,7 +26,7 @@ use reth_rpc_eth_api::{
     },
     EthApiTypes, FromEvmError, FullEthApiServer, RpcConverter, RpcNodeCore, RpcNodeCoreExt,
 };
-use reth_rpc_eth_types::{EthStateCache, FeeHistoryCache, GasPriceOracle};
+use reth_rpc_eth_types::{EthProofCache, EthStateCache, FeeHistoryCache, GasPriceOracle};
 use reth_storage_api::{
     BlockNumReader, BlockReader, BlockReaderIdExt, ProviderBlock, ProviderHeader, ProviderReceipt,
     ProviderTx, StageCheckpointReader, StateProviderFactory,
@@ -47,8 +47,8 @@ pub type EthApiNodeBackend<N> = EthApiInner<
 >;
 
 /// A helper trait with requirements for [`RpcNodeCore`] to be used in [`OpEthApi`].
-pub trait OpNodeCore: RpcNodeCore<Provider: BlockReader> {}
-impl<T> OpNodeCore for T where T: RpcNodeCore<Provider: BlockReader> {}
+pub trait OpNodeCore: RpcNodeCore<Provider: BlockReader + ChainSpecProvider> {}
+impl<T> OpNodeCore for T where T: RpcNodeCore<Provider: BlockReader + ChainSpecProvider> {}
 
 /// OP-Reth `Eth` API implementation.
 ///
@@ -126,6 +126,8 @@ where
     type Evm = <N as RpcNodeCore>::Evm;
     type Network = <N as RpcNodeCore>::Network;
     type PayloadBuilder = ();
+    type Consensus = ();
+    type ChainSpec = <N::Provider as ChainSpecProvider>::ChainSpec;
 
     #[inline]
     fn pool(&self) -> &Self::Pool {
@@ -151,6 +153,16 @@ where
     fn provider(&self) -> &Self::Provider {
         self.inner.eth_api.provider()
     }
+
+    #[inline]
+    fn consensus(&self) -> &Self::Consensus {
+        &()
+    }
+
+    #[inline]
+    fn chain_spec(&self) -> Arc<Self::ChainSpec> {
+        self.inner.eth_api.provider().chain_spec()
+    }
 }
 
 impl<N, NetworkT> RpcNodeCoreExt for OpEthApi<N, NetworkT>
@@ -257,6 +269,11 @@ where
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_api.eth_proof_window()
     }
+
+    #[inline]
+    fn proof_cache(&self) -> Option<&EthProofCache> {
+        self.inner.eth_api.proof_cache()
+    }
 }
 
 impl<N, NetworkT> EthFees for OpEthApi<N, NetworkT>
@@ -410,6 +427,7 @@ where
         .eth_proof_window(ctx.config.eth_proof_window)
         .fee_history_cache_config(ctx.config.fee_history_cache)
         .proof_permits(ctx.config.proof_permits)
+        .proof_cache_max_entries(ctx.config.proof_cache_max_entries)
         .gas_oracle_config(ctx.config.gas_oracle)
         .build_inner();
 
@@ -20,7 +20,7 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::{EthApiError, PendingBlock};
 use reth_storage_api::{
     BlockReader, BlockReaderIdExt, ProviderBlock, ProviderHeader, ProviderReceipt, ProviderTx,
-    ReceiptProvider, StateProviderFactory,
+    ReceiptProvider, StateProviderBox, StateProviderFactory,
 };
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 
@@ -106,4 +106,9 @@ where
 
         Ok(Some((Arc::new(block), Arc::new(receipts))))
     }
+
+    /// The pending block is the latest block, so the pending state is the latest state.
+    async fn local_pending_state(&self) -> Result<Option<StateProviderBox>, Self::Error> {
+        Ok(None)
+    }
 }
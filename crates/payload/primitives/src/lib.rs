@@ -32,6 +32,9 @@ pub use traits::{
 mod payload;
 pub use payload::{ExecutionPayload, PayloadOrAttributes};
 
+mod version;
+pub use version::{EngineApiFork, EngineApiVersionRules, FieldPresence, ENGINE_API_VERSION_RULES};
+
 /// Core trait that defines the associated types for working with execution payloads.
 pub trait PayloadTypes: Send + Sync + Unpin + core::fmt::Debug + Clone + 'static {
     /// The format for execution payload data that can be processed and validated.
@@ -65,101 +68,41 @@ pub trait PayloadTypes: Send + Sync + Unpin + core::fmt::Debug + Clone + 'static
     ) -> Self::ExecutionData;
 }
 
-/// Validates the timestamp depending on the version called:
+/// Validates the timestamp depending on the version called, according to the
+/// [`ENGINE_API_VERSION_RULES`]:
 ///
 /// * If V2, this ensures that the payload timestamp is pre-Cancun.
 /// * If V3, this ensures that the payload timestamp is within the Cancun timestamp.
 /// * If V4, this ensures that the payload timestamp is within the Prague timestamp.
+/// * If V5, this ensures that the payload timestamp is within the Osaka timestamp.
 ///
 /// Otherwise, this will return [`EngineObjectValidationError::UnsupportedFork`].
+///
+/// From the Engine API spec, for the `engine_forkchoiceUpdated`, `engine_newPayload` and
+/// `engine_getPayload` methods of every fork:
+///
+/// 1. Client software **MUST** return `-38005: Unsupported fork` error if the `timestamp` of the
+///    payload or `payloadAttributes` does not fall within the time frame of the fork the method was
+///    introduced for.
+///
+/// The methods of previous forks are updated accordingly, e.g. `engine_newPayloadV2` **MUST**
+/// return `-38005: Unsupported fork` if the `timestamp` is greater or equal to the Cancun
+/// activation timestamp.
 pub fn validate_payload_timestamp(
     chain_spec: impl EthereumHardforks,
     version: EngineApiMessageVersion,
     timestamp: u64,
 ) -> Result<(), EngineObjectValidationError> {
-    let is_cancun = chain_spec.is_cancun_active_at_timestamp(timestamp);
-    if version.is_v2() && is_cancun {
-        // From the Engine API spec:
-        //
-        // ### Update the methods of previous forks
-        //
-        // This document defines how Cancun payload should be handled by the [`Shanghai
-        // API`](https://github.com/ethereum/execution-apis/blob/ff43500e653abde45aec0f545564abfb648317af/src/engine/shanghai.md).
-        //
-        // For the following methods:
-        //
-        // - [`engine_forkchoiceUpdatedV2`](https://github.com/ethereum/execution-apis/blob/ff43500e653abde45aec0f545564abfb648317af/src/engine/shanghai.md#engine_forkchoiceupdatedv2)
-        // - [`engine_newPayloadV2`](https://github.com/ethereum/execution-apis/blob/ff43500e653abde45aec0f545564abfb648317af/src/engine/shanghai.md#engine_newpayloadV2)
-        // - [`engine_getPayloadV2`](https://github.com/ethereum/execution-apis/blob/ff43500e653abde45aec0f545564abfb648317af/src/engine/shanghai.md#engine_getpayloadv2)
-        //
-        // a validation **MUST** be added:
-        //
-        // 1. Client software **MUST** return `-38005: Unsupported fork` error if the `timestamp` of
-        //    payload or payloadAttributes is greater or equal to the Cancun activation timestamp.
-        return Err(EngineObjectValidationError::UnsupportedFork)
-    }
-
-    if version.is_v3() && !is_cancun {
-        // From the Engine API spec:
-        // <https://github.com/ethereum/execution-apis/blob/ff43500e653abde45aec0f545564abfb648317af/src/engine/cancun.md#specification-2>
-        //
-        // For `engine_getPayloadV3`:
-        //
-        // 1. Client software **MUST** return `-38005: Unsupported fork` error if the `timestamp` of
-        //    the built payload does not fall within the time frame of the Cancun fork.
-        //
-        // For `engine_forkchoiceUpdatedV3`:
-        //
-        // 2. Client software **MUST** return `-38005: Unsupported fork` error if the
-        //    `payloadAttributes` is set and the `payloadAttributes.timestamp` does not fall within
-        //    the time frame of the Cancun fork.
-        //
-        // For `engine_newPayloadV3`:
-        //
-        // 2. Client software **MUST** return `-38005: Unsupported fork` error if the `timestamp` of
-        //    the payload does not fall within the time frame of the Cancun fork.
-        return Err(EngineObjectValidationError::UnsupportedFork)
-    }
-
-    let is_prague = chain_spec.is_prague_active_at_timestamp(timestamp);
-    if version.is_v4() && !is_prague {
-        // From the Engine API spec:
-        // <https://github.com/ethereum/execution-apis/blob/7907424db935b93c2fe6a3c0faab943adebe8557/src/engine/prague.md#specification-1>
-        //
-        // For `engine_getPayloadV4`:
-        //
-        // 1. Client software **MUST** return `-38005: Unsupported fork` error if the `timestamp` of
-        //    the built payload does not fall within the time frame of the Prague fork.
-        //
-        // For `engine_forkchoiceUpdatedV4`:
-        //
-        // 2. Client software **MUST** return `-38005: Unsupported fork` error if the
-        //    `payloadAttributes` is set and the `payloadAttributes.timestamp` does not fall within
-        //    the time frame of the Prague fork.
-        //
-        // For `engine_newPayloadV4`:
-        //
-        // 2. Client software **MUST** return `-38005: Unsupported fork` error if the `timestamp` of
-        //    the payload does not fall within the time frame of the Prague fork.
-        return Err(EngineObjectValidationError::UnsupportedFork)
-    }
-
-    let is_osaka = chain_spec.is_osaka_active_at_timestamp(timestamp);
-    if version.is_v5() && !is_osaka {
-        // From the Engine API spec:
-        // <https://github.com/ethereum/execution-apis/blob/15399c2e2f16a5f800bf3f285640357e2c245ad9/src/engine/osaka.md#specification>
-        //
-        // For `engine_getPayloadV5`
-        //
-        // 1. Client software MUST return -38005: Unsupported fork error if the timestamp of the
-        //    built payload does not fall within the time frame of the Osaka fork.
+    let fork = EngineApiFork::at_timestamp(chain_spec, timestamp);
+    if !version.rules().supports_fork(fork) {
         return Err(EngineObjectValidationError::UnsupportedFork)
     }
 
     Ok(())
 }
 
-/// Validates the presence of the `withdrawals` field according to the payload timestamp.
+/// Validates the presence of the `withdrawals` field according to the payload timestamp and the
+/// [`EngineApiVersionRules`] of the version.
 /// After Shanghai, withdrawals field must be [Some].
 /// Before Shanghai, withdrawals field must be [None];
 pub fn validate_withdrawals_presence<T: EthereumHardforks>(
@@ -169,29 +112,22 @@ pub fn validate_withdrawals_presence<T: EthereumHardforks>(
     timestamp: u64,
     has_withdrawals: bool,
 ) -> Result<(), EngineObjectValidationError> {
-    let is_shanghai_active = chain_spec.is_shanghai_active_at_timestamp(timestamp);
-
-    match version {
-        EngineApiMessageVersion::V1 => {
-            if has_withdrawals {
-                return Err(message_validation_kind
-                    .to_error(VersionSpecificValidationError::WithdrawalsNotSupportedInV1))
-            }
-        }
-        EngineApiMessageVersion::V2 |
-        EngineApiMessageVersion::V3 |
-        EngineApiMessageVersion::V4 |
-        EngineApiMessageVersion::V5 => {
-            if is_shanghai_active && !has_withdrawals {
-                return Err(message_validation_kind
-                    .to_error(VersionSpecificValidationError::NoWithdrawalsPostShanghai))
-            }
-            if !is_shanghai_active && has_withdrawals {
-                return Err(message_validation_kind
-                    .to_error(VersionSpecificValidationError::HasWithdrawalsPreShanghai))
-            }
-        }
-    };
+    let withdrawals = version.rules().withdrawals;
+    if withdrawals == FieldPresence::Forbidden && has_withdrawals {
+        return Err(message_validation_kind
+            .to_error(VersionSpecificValidationError::WithdrawalsNotSupportedInV1))
+    }
+
+    let fork = EngineApiFork::at_timestamp(chain_spec, timestamp);
+    let is_required = withdrawals.is_required_at(fork);
+    if is_required && !has_withdrawals {
+        return Err(message_validation_kind
+            .to_error(VersionSpecificValidationError::NoWithdrawalsPostShanghai))
+    }
+    if !is_required && has_withdrawals {
+        return Err(message_validation_kind
+            .to_error(VersionSpecificValidationError::HasWithdrawalsPreShanghai))
+    }
 
     Ok(())
 }
@@ -274,21 +210,16 @@ pub fn validate_parent_beacon_block_root_presence<T: EthereumHardforks>(
     //        failure.
     //     4. If any of the above checks fails, the `forkchoiceState` update **MUST NOT** be rolled
     //        back.
-    match version {
-        EngineApiMessageVersion::V1 | EngineApiMessageVersion::V2 => {
-            if has_parent_beacon_block_root {
-                return Err(validation_kind.to_error(
-                    VersionSpecificValidationError::ParentBeaconBlockRootNotSupportedBeforeV3,
-                ))
-            }
-        }
-        EngineApiMessageVersion::V3 | EngineApiMessageVersion::V4 | EngineApiMessageVersion::V5 => {
-            if !has_parent_beacon_block_root {
-                return Err(validation_kind
-                    .to_error(VersionSpecificValidationError::NoParentBeaconBlockRootPostCancun))
-            }
-        }
-    };
+    let fork = EngineApiFork::at_timestamp(chain_spec, timestamp);
+    let is_required = version.rules().parent_beacon_block_root.is_required_at(fork);
+    if !is_required && has_parent_beacon_block_root {
+        return Err(validation_kind
+            .to_error(VersionSpecificValidationError::ParentBeaconBlockRootNotSupportedBeforeV3))
+    }
+    if is_required && !has_parent_beacon_block_root {
+        return Err(validation_kind
+            .to_error(VersionSpecificValidationError::NoParentBeaconBlockRootPostCancun))
+    }
 
     // For `engine_forkchoiceUpdatedV3`:
     //
@@ -494,6 +425,122 @@ mod tests {
         assert!(EngineApiMessageVersion::V4 > EngineApiMessageVersion::V3);
     }
 
+    #[test]
+    fn version_specific_fields_validation() {
+        let chain_spec = reth_chainspec::MAINNET.clone();
+        let shanghai = 1681338455;
+        let cancun = 1710338135;
+        let kind = MessageValidationKind::PayloadAttributes;
+
+        assert_matches!(
+            validate_payload_timestamp(&chain_spec, EngineApiMessageVersion::V2, shanghai),
+            Ok(())
+        );
+        assert_matches!(
+            validate_payload_timestamp(&chain_spec, EngineApiMessageVersion::V2, cancun),
+            Err(EngineObjectValidationError::UnsupportedFork)
+        );
+        assert_matches!(
+            validate_payload_timestamp(&chain_spec, EngineApiMessageVersion::V3, shanghai),
+            Err(EngineObjectValidationError::UnsupportedFork)
+        );
+
+        assert_matches!(
+            validate_withdrawals_presence(
+                &chain_spec,
+                EngineApiMessageVersion::V1,
+                kind,
+                shanghai,
+                true
+            ),
+            Err(EngineObjectValidationError::PayloadAttributes(
+                VersionSpecificValidationError::WithdrawalsNotSupportedInV1
+            ))
+        );
+        assert_matches!(
+            validate_withdrawals_presence(
+                &chain_spec,
+                EngineApiMessageVersion::V2,
+                kind,
+                shanghai - 1,
+                true
+            ),
+            Err(EngineObjectValidationError::PayloadAttributes(
+                VersionSpecificValidationError::HasWithdrawalsPreShanghai
+            ))
+        );
+        assert_matches!(
+            validate_withdrawals_presence(
+                &chain_spec,
+                EngineApiMessageVersion::V3,
+                kind,
+                cancun,
+                false
+            ),
+            Err(EngineObjectValidationError::PayloadAttributes(
+                VersionSpecificValidationError::NoWithdrawalsPostShanghai
+            ))
+        );
+
+        assert_matches!(
+            validate_parent_beacon_block_root_presence(
+                &chain_spec,
+                EngineApiMessageVersion::V2,
+                kind,
+                shanghai,
+                true
+            ),
+            Err(EngineObjectValidationError::PayloadAttributes(
+                VersionSpecificValidationError::ParentBeaconBlockRootNotSupportedBeforeV3
+            ))
+        );
+        assert_matches!(
+            validate_parent_beacon_block_root_presence(
+                &chain_spec,
+                EngineApiMessageVersion::V3,
+                kind,
+                cancun,
+                false
+            ),
+            Err(EngineObjectValidationError::PayloadAttributes(
+                VersionSpecificValidationError::NoParentBeaconBlockRootPostCancun
+            ))
+        );
+        assert_matches!(
+            validate_parent_beacon_block_root_presence(
+                &chain_spec,
+                EngineApiMessageVersion::V3,
+                kind,
+                cancun,
+                true
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn v5_requires_osaka() {
+        let prague = 1746612311;
+        assert_matches!(
+            validate_payload_timestamp(
+                reth_chainspec::MAINNET.clone(),
+                EngineApiMessageVersion::V5,
+                prague
+            ),
+            Err(EngineObjectValidationError::UnsupportedFork)
+        );
+
+        let osaka = reth_chainspec::ChainSpecBuilder::mainnet().osaka_activated().build();
+        assert_matches!(
+            validate_payload_timestamp(&osaka, EngineApiMessageVersion::V5, prague),
+            Ok(())
+        );
+        assert_matches!(
+            validate_payload_timestamp(&osaka, EngineApiMessageVersion::V4, prague),
+            Ok(())
+        );
+    }
+
     #[test]
     fn execution_requests_validation() {
         assert_matches!(validate_execution_requests(&[]), Ok(()));
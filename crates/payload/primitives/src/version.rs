@@ -0,0 +1,167 @@
+//! Engine API version rules.
+//!
+//! Every [`EngineApiMessageVersion`] is only valid for a range of hardforks and expects a fixed set
+//! of fields in the payload or payload attributes. These rules are defined in a single table,
+//! [`ENGINE_API_VERSION_RULES`], so that rolling out a new fork only requires a new
+//! [`EngineApiFork`] and a new row in the table.
+
+use crate::EngineApiMessageVersion;
+use reth_chainspec::EthereumHardforks;
+
+/// The hardforks that change the shape of the objects exchanged over the engine API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EngineApiFork {
+    /// The Paris hardfork, the first post-merge fork.
+    Paris,
+    /// The Shanghai hardfork, which introduced withdrawals.
+    Shanghai,
+    /// The Cancun hardfork, which introduced the parent beacon block root.
+    Cancun,
+    /// The Prague hardfork, which introduced execution requests.
+    Prague,
+    /// The Osaka hardfork.
+    Osaka,
+}
+
+impl EngineApiFork {
+    /// Returns the latest fork that is active at the given timestamp according to the chain spec.
+    pub fn at_timestamp(chain_spec: impl EthereumHardforks, timestamp: u64) -> Self {
+        if chain_spec.is_osaka_active_at_timestamp(timestamp) {
+            Self::Osaka
+        } else if chain_spec.is_prague_active_at_timestamp(timestamp) {
+            Self::Prague
+        } else if chain_spec.is_cancun_active_at_timestamp(timestamp) {
+            Self::Cancun
+        } else if chain_spec.is_shanghai_active_at_timestamp(timestamp) {
+            Self::Shanghai
+        } else {
+            Self::Paris
+        }
+    }
+}
+
+/// Whether a field must be present in an object of a given engine API version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldPresence {
+    /// The field is not part of this version and must be absent.
+    Forbidden,
+    /// The field must be present.
+    Required,
+    /// The field must be present if the given fork is active, and absent otherwise.
+    SinceFork(EngineApiFork),
+}
+
+impl FieldPresence {
+    /// Returns whether the field is expected to be present at the given fork.
+    pub const fn is_required_at(&self, fork: EngineApiFork) -> bool {
+        match self {
+            Self::Forbidden => false,
+            Self::Required => true,
+            Self::SinceFork(since) => fork as u8 >= *since as u8,
+        }
+    }
+}
+
+/// The rules an object of a given engine API version must satisfy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EngineApiVersionRules {
+    /// The version these rules apply to.
+    pub version: EngineApiMessageVersion,
+    /// The first fork this version may be used for.
+    pub first_fork: EngineApiFork,
+    /// The last fork this version may be used for, if any.
+    pub last_fork: Option<EngineApiFork>,
+    /// The presence of the `withdrawals` field.
+    pub withdrawals: FieldPresence,
+    /// The presence of the `parentBeaconBlockRoot` field.
+    pub parent_beacon_block_root: FieldPresence,
+}
+
+impl EngineApiVersionRules {
+    /// Returns `true` if this version may be used for objects of the given fork.
+    pub fn supports_fork(&self, fork: EngineApiFork) -> bool {
+        fork >= self.first_fork && self.last_fork.is_none_or(|last| fork <= last)
+    }
+}
+
+/// The rules of all engine API versions, indexed by version.
+///
+/// Note that `V1` is not restricted to a range of forks, because `engine_forkchoiceUpdatedV1` and
+/// `engine_newPayloadV1` predate the fork checks of the engine API spec.
+pub const ENGINE_API_VERSION_RULES: [EngineApiVersionRules; 5] = [
+    EngineApiVersionRules {
+        version: EngineApiMessageVersion::V1,
+        first_fork: EngineApiFork::Paris,
+        last_fork: None,
+        withdrawals: FieldPresence::Forbidden,
+        parent_beacon_block_root: FieldPresence::Forbidden,
+    },
+    EngineApiVersionRules {
+        version: EngineApiMessageVersion::V2,
+        first_fork: EngineApiFork::Paris,
+        last_fork: Some(EngineApiFork::Shanghai),
+        withdrawals: FieldPresence::SinceFork(EngineApiFork::Shanghai),
+        parent_beacon_block_root: FieldPresence::Forbidden,
+    },
+    EngineApiVersionRules {
+        version: EngineApiMessageVersion::V3,
+        first_fork: EngineApiFork::Cancun,
+        last_fork: None,
+        withdrawals: FieldPresence::SinceFork(EngineApiFork::Shanghai),
+        parent_beacon_block_root: FieldPresence::Required,
+    },
+    EngineApiVersionRules {
+        version: EngineApiMessageVersion::V4,
+        first_fork: EngineApiFork::Prague,
+        last_fork: None,
+        withdrawals: FieldPresence::SinceFork(EngineApiFork::Shanghai),
+        parent_beacon_block_root: FieldPresence::Required,
+    },
+    EngineApiVersionRules {
+        version: EngineApiMessageVersion::V5,
+        first_fork: EngineApiFork::Osaka,
+        last_fork: None,
+        withdrawals: FieldPresence::SinceFork(EngineApiFork::Shanghai),
+        parent_beacon_block_root: FieldPresence::Required,
+    },
+];
+
+impl EngineApiMessageVersion {
+    /// Returns the rules objects of this version must satisfy.
+    pub const fn rules(&self) -> &'static EngineApiVersionRules {
+        &ENGINE_API_VERSION_RULES[*self as usize - 1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rules_are_indexed_by_version() {
+        for version in [
+            EngineApiMessageVersion::V1,
+            EngineApiMessageVersion::V2,
+            EngineApiMessageVersion::V3,
+            EngineApiMessageVersion::V4,
+            EngineApiMessageVersion::V5,
+        ] {
+            assert_eq!(version.rules().version, version);
+        }
+    }
+
+    #[test]
+    fn supported_forks() {
+        let v2 = EngineApiMessageVersion::V2.rules();
+        assert!(v2.supports_fork(EngineApiFork::Shanghai));
+        assert!(!v2.supports_fork(EngineApiFork::Cancun));
+
+        let v4 = EngineApiMessageVersion::V4.rules();
+        assert!(!v4.supports_fork(EngineApiFork::Cancun));
+        assert!(v4.supports_fork(EngineApiFork::Osaka));
+
+        let withdrawals = FieldPresence::SinceFork(EngineApiFork::Shanghai);
+        assert!(!withdrawals.is_required_at(EngineApiFork::Paris));
+        assert!(withdrawals.is_required_at(EngineApiFork::Prague));
+    }
+}
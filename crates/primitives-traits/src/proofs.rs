@@ -1,4 +1,10 @@
 //! Helper function for calculating Merkle proofs and hashes.
+use alloc::vec::Vec;
+use alloy_consensus::{Eip2718EncodableReceipt, TxReceipt};
+use alloy_primitives::{logs_bloom, Bloom, Log, B256};
+use alloy_rlp::{Encodable, Header};
+use alloy_trie::{HashBuilder, Nibbles};
+
 pub use alloy_trie::root::ordered_trie_root_with_encoder;
 
 pub use alloy_consensus::proofs::calculate_receipt_root;
@@ -17,6 +23,130 @@ pub use alloy_consensus::proofs::calculate_withdrawals_root;
 #[doc(inline)]
 pub use alloy_consensus::proofs::calculate_ommers_root;
 
+/// Computes the receipts root and logs bloom of a block while its receipts are produced.
+///
+/// The receipts trie is keyed by the RLP encoding of the receipt index. All receipts except the
+/// first are added to the [`HashBuilder`] in key order as they are pushed, so only the remaining
+/// branch hashes are left to compute once the block is sealed. The key of the first receipt,
+/// `0x80`, sorts after the keys of the indices `1..=127`, so it is added right before the receipt
+/// at index 128, or when the root is computed.
+///
+/// The root matches [`calculate_receipt_root`] of the receipts with their blooms.
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalReceiptsRoot {
+    /// The receipts trie.
+    hash_builder: HashBuilder,
+    /// The encoding of the first receipt until it's added to the trie.
+    first: Option<Vec<u8>>,
+    /// The number of pushed receipts.
+    len: usize,
+    /// The logs bloom of all pushed receipts.
+    logs_bloom: Bloom,
+    /// Buffer for encoding receipts.
+    buf: Vec<u8>,
+}
+
+impl IncrementalReceiptsRoot {
+    /// Adds the receipt of the next transaction of the block.
+    ///
+    /// Receipts must not be pushed after the root was computed, unless the type is cleared.
+    pub fn push<R: TxReceipt + Eip2718EncodableReceipt>(&mut self, receipt: &R) {
+        let bloom = receipt.bloom();
+        self.buf.clear();
+        receipt.eip2718_encode_with_bloom(&bloom, &mut self.buf);
+        self.add_buf(bloom);
+    }
+
+    /// Adds the receipt of the next transaction of the block from its fields, encoded like an
+    /// Ethereum receipt: the transaction type, unless it's a legacy transaction, followed by the
+    /// RLP list of the status, the cumulative gas used, the logs bloom and the logs.
+    pub fn push_fields(&mut self, ty: u8, success: bool, cumulative_gas_used: u64, logs: &[Log]) {
+        let bloom = logs_bloom(logs);
+        let payload_length = success.length() +
+            cumulative_gas_used.length() +
+            bloom.length() +
+            alloy_rlp::list_length::<_, Log>(logs);
+
+        self.buf.clear();
+        if ty != 0 {
+            self.buf.push(ty);
+        }
+        Header { list: true, payload_length }.encode(&mut self.buf);
+        success.encode(&mut self.buf);
+        cumulative_gas_used.encode(&mut self.buf);
+        bloom.encode(&mut self.buf);
+        alloy_rlp::encode_list::<_, Log>(logs, &mut self.buf);
+        self.add_buf(bloom);
+    }
+
+    /// Adds the receipts of the next transactions of the block.
+    pub fn extend<'a, R>(&mut self, receipts: impl IntoIterator<Item = &'a R>)
+    where
+        R: TxReceipt + Eip2718EncodableReceipt + 'a,
+    {
+        for receipt in receipts {
+            self.push(receipt);
+        }
+    }
+
+    /// Returns the number of pushed receipts.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no receipts were pushed.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the logs bloom of all pushed receipts.
+    pub const fn logs_bloom(&self) -> Bloom {
+        self.logs_bloom
+    }
+
+    /// Computes the receipts root of all pushed receipts.
+    pub fn root(&mut self) -> B256 {
+        self.add_first();
+        self.hash_builder.root()
+    }
+
+    /// Removes all receipts, keeping the allocated encoding buffer.
+    pub fn clear(&mut self) {
+        self.hash_builder = HashBuilder::default();
+        self.first = None;
+        self.len = 0;
+        self.logs_bloom = Bloom::ZERO;
+    }
+
+    /// Adds the receipt encoded in the buffer.
+    fn add_buf(&mut self, bloom: Bloom) {
+        self.logs_bloom |= bloom;
+
+        let index = self.len;
+        self.len += 1;
+        if index == 0 {
+            self.first = Some(core::mem::take(&mut self.buf));
+            return
+        }
+        if index == 0x80 {
+            self.add_first();
+        }
+        self.hash_builder.add_leaf(receipt_key(index), &self.buf);
+    }
+
+    /// Adds the first receipt to the trie, if it's still pending.
+    fn add_first(&mut self) {
+        if let Some(first) = self.first.take() {
+            self.hash_builder.add_leaf(receipt_key(0), &first);
+        }
+    }
+}
+
+/// Returns the key of the receipt at the given index in the receipts trie.
+fn receipt_key(index: usize) -> Nibbles {
+    Nibbles::unpack(alloy_rlp::encode_fixed_size(&index))
+}
+
 #[cfg(test)]
 mod tests {
     use alloy_consensus::EMPTY_ROOT_HASH;
@@ -9,7 +9,7 @@ use reth_provider::{
     providers::StaticFileProvider, BlockReader, DBProvider, DatabaseProviderFactory,
     NodePrimitivesProvider, PruneCheckpointWriter, StaticFileProviderFactory,
 };
-use reth_prune_types::PruneModes;
+use reth_prune_types::{PruneModes, PrunePins};
 use std::time::Duration;
 use tokio::sync::watch;
 
@@ -26,6 +26,8 @@ pub struct PrunerBuilder {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// The prune segment pins of all `ExEx`'s.
+    exex_prune_pins: watch::Receiver<PrunePins>,
 }
 
 impl PrunerBuilder {
@@ -75,6 +77,12 @@ impl PrunerBuilder {
         self
     }
 
+    /// Sets the receiver for the prune segment pins of all `ExEx`'s.
+    pub fn exex_prune_pins(mut self, exex_prune_pins: watch::Receiver<PrunePins>) -> Self {
+        self.exex_prune_pins = exex_prune_pins;
+        self
+    }
+
     /// Builds a [Pruner] from the current configuration with the given provider factory.
     pub fn build_with_provider_factory<PF>(self, provider_factory: PF) -> Pruner<PF::ProviderRW, PF>
     where
@@ -99,6 +107,7 @@ impl PrunerBuilder {
             self.timeout,
             self.finished_exex_height,
         )
+        .with_prune_pins(self.exex_prune_pins)
     }
 
     /// Builds a [Pruner] from the current configuration with the given static file provider.
@@ -121,6 +130,7 @@ impl PrunerBuilder {
             self.timeout,
             self.finished_exex_height,
         )
+        .with_prune_pins(self.exex_prune_pins)
     }
 }
 
@@ -132,6 +142,7 @@ impl Default for PrunerBuilder {
             delete_limit: MAINNET_PRUNE_DELETE_LIMIT,
             timeout: None,
             finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
+            exex_prune_pins: watch::channel(PrunePins::default()).1,
         }
     }
 }
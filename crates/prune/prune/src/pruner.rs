@@ -9,7 +9,7 @@ use reth_exex_types::FinishedExExHeight;
 use reth_provider::{
     DBProvider, DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
 };
-use reth_prune_types::{PruneProgress, PrunedSegmentInfo, PrunerOutput};
+use reth_prune_types::{PrunePins, PruneProgress, PrunedSegmentInfo, PrunerOutput};
 use reth_tokio_util::{EventSender, EventStream};
 use std::time::{Duration, Instant};
 use tokio::sync::watch;
@@ -43,6 +43,8 @@ pub struct Pruner<Provider, PF> {
     timeout: Option<Duration>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// Block ranges of segments that must not be pruned.
+    prune_pins: watch::Receiver<PrunePins>,
     #[doc(hidden)]
     metrics: Metrics,
     event_sender: EventSender<PrunerEvent>,
@@ -65,6 +67,7 @@ impl<Provider> Pruner<Provider, ()> {
             delete_limit,
             timeout,
             finished_exex_height,
+            prune_pins: watch::channel(PrunePins::default()).1,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -92,12 +95,24 @@ where
             delete_limit,
             timeout,
             finished_exex_height,
+            prune_pins: watch::channel(PrunePins::default()).1,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
     }
 }
 
+impl<Provider, S> Pruner<Provider, S> {
+    /// Sets the receiver for block ranges of segments that must not be pruned.
+    ///
+    /// Pins only restrict segments pruned on behalf of the user, because data pruned for static
+    /// files is moved rather than deleted.
+    pub fn with_prune_pins(mut self, prune_pins: watch::Receiver<PrunePins>) -> Self {
+        self.prune_pins = prune_pins;
+        self
+    }
+}
+
 impl<Provider, S> Pruner<Provider, S>
 where
     Provider: PruneCheckpointReader + PruneCheckpointWriter,
@@ -200,6 +215,21 @@ where
                 .transpose()?
                 .flatten()
             {
+                let to_block = if segment.purpose().is_user() {
+                    let pinned_to_block =
+                        self.prune_pins.borrow().prune_target_block(segment.segment(), to_block);
+                    let Some(pinned_to_block) = pinned_to_block else {
+                        debug!(target: "pruner", segment = ?segment.segment(), %to_block, "Segment is pinned from genesis, skipping");
+                        continue
+                    };
+                    if pinned_to_block < to_block {
+                        debug!(target: "pruner", segment = ?segment.segment(), %to_block, %pinned_to_block, "Adjusting prune target block to the lowest pinned block");
+                    }
+                    pinned_to_block
+                } else {
+                    to_block
+                };
+
                 debug!(
                     target: "pruner",
                     segment = ?segment.segment(),
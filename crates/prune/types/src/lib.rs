@@ -14,6 +14,7 @@ extern crate alloc;
 mod checkpoint;
 mod event;
 mod mode;
+mod pin;
 mod pruner;
 mod segment;
 mod target;
@@ -25,6 +26,7 @@ use core::ops::Deref;
 pub use checkpoint::PruneCheckpoint;
 pub use event::PrunerEvent;
 pub use mode::PruneMode;
+pub use pin::{PrunePin, PrunePins};
 pub use pruner::{
     PruneInterruptReason, PruneProgress, PrunedSegmentInfo, PrunerOutput, SegmentOutput,
     SegmentOutputCheckpoint,
@@ -0,0 +1,117 @@
+use crate::PruneSegment;
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use alloy_primitives::BlockNumber;
+use core::ops::RangeInclusive;
+
+/// A block range of a [`PruneSegment`] that must not be pruned.
+///
+/// Pins are used by consumers of historical data, e.g. `ExEx`es that backfill receipts, to keep the
+/// data around until they have processed it. Since segments are pruned from the lowest block up,
+/// a pin prevents pruning of its whole range and all blocks above it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "serde"), serde(rename_all = "camelCase"))]
+pub struct PrunePin {
+    /// The pinned segment.
+    pub segment: PruneSegment,
+    /// The first pinned block, inclusive.
+    pub from_block: BlockNumber,
+    /// The last pinned block, inclusive.
+    pub to_block: BlockNumber,
+}
+
+impl PrunePin {
+    /// Creates a new pin of the given block range of the segment.
+    pub const fn new(segment: PruneSegment, blocks: RangeInclusive<BlockNumber>) -> Self {
+        Self { segment, from_block: *blocks.start(), to_block: *blocks.end() }
+    }
+
+    /// Returns the pinned block range.
+    pub const fn blocks(&self) -> RangeInclusive<BlockNumber> {
+        self.from_block..=self.to_block
+    }
+}
+
+/// Pinned block ranges of prune segments, by the owner of the pins.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "serde"), serde(transparent))]
+pub struct PrunePins(BTreeMap<String, Vec<PrunePin>>);
+
+impl PrunePins {
+    /// Adds a pin of the given owner. Returns `false` if the owner already holds the pin.
+    pub fn insert(&mut self, owner: impl Into<String>, pin: PrunePin) -> bool {
+        let pins = self.0.entry(owner.into()).or_default();
+        if pins.contains(&pin) {
+            return false
+        }
+        pins.push(pin);
+        true
+    }
+
+    /// Removes a pin of the given owner. Returns `false` if the owner doesn't hold the pin.
+    pub fn remove(&mut self, owner: &str, pin: &PrunePin) -> bool {
+        let Some(pins) = self.0.get_mut(owner) else { return false };
+        let len = pins.len();
+        pins.retain(|existing| existing != pin);
+        let removed = pins.len() != len;
+        if pins.is_empty() {
+            self.0.remove(owner);
+        }
+        removed
+    }
+
+    /// Returns `true` if there are no pins.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over all pins and their owners.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &PrunePin)> {
+        self.0.iter().flat_map(|(owner, pins)| pins.iter().map(move |pin| (owner.as_str(), pin)))
+    }
+
+    /// Returns the lowest pinned block of the segment, if any.
+    pub fn lowest_pinned_block(&self, segment: PruneSegment) -> Option<BlockNumber> {
+        self.iter().filter(|(_, pin)| pin.segment == segment).map(|(_, pin)| pin.from_block).min()
+    }
+
+    /// Caps the block the segment is pruned up to, inclusive, below its lowest pinned block.
+    ///
+    /// Returns `None` if nothing can be pruned, i.e. the segment is pinned from block `0`.
+    pub fn prune_target_block(
+        &self,
+        segment: PruneSegment,
+        to_block: BlockNumber,
+    ) -> Option<BlockNumber> {
+        match self.lowest_pinned_block(segment) {
+            Some(pinned) => pinned.checked_sub(1).map(|unpinned| unpinned.min(to_block)),
+            None => Some(to_block),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn caps_prune_target_below_pins() {
+        let mut pins = PrunePins::default();
+        let receipts = PrunePin::new(PruneSegment::Receipts, 100..=200);
+        assert!(pins.insert("backfill", receipts));
+        assert!(!pins.insert("backfill", receipts));
+        assert!(pins.insert("indexer", PrunePin::new(PruneSegment::Receipts, 150..=300)));
+
+        assert_eq!(pins.prune_target_block(PruneSegment::Receipts, 1000), Some(99));
+        assert_eq!(pins.prune_target_block(PruneSegment::Receipts, 50), Some(50));
+        assert_eq!(pins.prune_target_block(PruneSegment::Transactions, 1000), Some(1000));
+
+        assert!(pins.remove("backfill", &receipts));
+        assert!(!pins.remove("backfill", &receipts));
+        assert_eq!(pins.prune_target_block(PruneSegment::Receipts, 1000), Some(149));
+
+        pins.insert("indexer", PrunePin::new(PruneSegment::Receipts, 0..=10));
+        assert_eq!(pins.prune_target_block(PruneSegment::Receipts, 1000), None);
+    }
+}
@@ -1,6 +1,7 @@
 //! An IPC connection.
 
-use crate::stream_codec::StreamCodec;
+use crate::stream_codec::{MessageChunk, StreamCodec};
+use bytes::Bytes;
 use futures::{stream::FuturesUnordered, FutureExt, Sink, Stream};
 use std::{
     collections::VecDeque,
@@ -29,7 +30,7 @@ where
     }
 }
 
-impl<T> Sink<String> for IpcConn<JsonRpcStream<T>>
+impl<T> Sink<MessageChunk> for IpcConn<JsonRpcStream<T>>
 where
     T: AsyncRead + AsyncWrite,
 {
@@ -38,19 +39,19 @@ where
     fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
         // NOTE: we always flush here this prevents buffering in the underlying
         // `Framed` impl that would cause stalled requests
-        self.project().0.poll_flush(cx)
+        Sink::<MessageChunk>::poll_flush(self.project().0, cx)
     }
 
-    fn start_send(self: Pin<&mut Self>, item: String) -> Result<(), Self::Error> {
+    fn start_send(self: Pin<&mut Self>, item: MessageChunk) -> Result<(), Self::Error> {
         self.project().0.start_send(item)
     }
 
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.project().0.poll_flush(cx)
+        Sink::<MessageChunk>::poll_flush(self.project().0, cx)
     }
 
     fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.project().0.poll_close(cx)
+        Sink::<MessageChunk>::poll_close(self.project().0, cx)
     }
 }
 
@@ -70,6 +71,10 @@ pub(crate) struct IpcConnDriver<T, S, Fut> {
     #[pin]
     pub(crate) pending_calls: FuturesUnordered<Fut>,
     pub(crate) items: VecDeque<String>,
+    /// The rest of the response that is currently written
+    pub(crate) writing: Option<Bytes>,
+    /// The maximum number of bytes of a response that are written at once
+    pub(crate) write_chunk_size: usize,
 }
 
 impl<T, S, Fut> IpcConnDriver<T, S, Fut> {
@@ -105,15 +110,25 @@ where
                 return Poll::Pending
             }
 
-            // write all responses to the sink
+            // write all responses to the sink, large responses in chunks so that they are never
+            // buffered as a whole
             while this.conn.as_mut().poll_ready(cx).is_ready() {
-                if let Some(item) = this.items.pop_front() {
-                    if let Err(err) = this.conn.as_mut().start_send(item) {
-                        tracing::warn!("IPC response failed: {:?}", err);
-                        return Poll::Ready(())
-                    }
-                } else {
-                    break
+                let mut bytes = match this.writing.take() {
+                    Some(bytes) => bytes,
+                    None => match this.items.pop_front() {
+                        Some(item) => Bytes::from(item.into_bytes()),
+                        None => break,
+                    },
+                };
+                let chunk = bytes.split_to(bytes.len().min(*this.write_chunk_size));
+                let last = bytes.is_empty();
+                if !last {
+                    *this.writing = Some(bytes);
+                }
+                if let Err(err) = this.conn.as_mut().start_send(MessageChunk { bytes: chunk, last })
+                {
+                    tracing::warn!("IPC response failed: {:?}", err);
+                    return Poll::Ready(())
                 }
             }
 
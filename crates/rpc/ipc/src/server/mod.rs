@@ -42,6 +42,9 @@ mod connection;
 mod ipc;
 mod rpc_service;
 
+/// The default maximum number of bytes of a response that are written to the socket at once.
+pub const DEFAULT_WRITE_CHUNK_SIZE: u32 = 1024 * 1024;
+
 pub use rpc_service::RpcService;
 
 /// Ipc Server implementation
@@ -483,8 +486,9 @@ fn process_connection<RpcMiddleware, HttpMiddleware>(
     };
 
     let service = http_middleware.service(tower_service);
-    tokio::spawn(async {
-        to_ipc_service(ipc, service, stop_handle, rx).in_current_span().await;
+    let write_chunk_size = server_cfg.write_chunk_size.max(1) as usize;
+    tokio::spawn(async move {
+        to_ipc_service(ipc, service, stop_handle, rx, write_chunk_size).in_current_span().await;
         drop(drop_on_completion)
     });
 }
@@ -494,6 +498,7 @@ async fn to_ipc_service<S, T>(
     service: S,
     stop_handle: StopHandle,
     rx: mpsc::Receiver<Box<JsonRawValue>>,
+    write_chunk_size: usize,
 ) where
     S: Service<String, Response = Option<String>> + Send + 'static,
     S::Error: Into<Box<dyn core::error::Error + Send + Sync>>,
@@ -506,6 +511,8 @@ async fn to_ipc_service<S, T>(
         service,
         pending_calls: Default::default(),
         items: Default::default(),
+        writing: None,
+        write_chunk_size,
     };
     let stopped = stop_handle.shutdown();
 
@@ -538,6 +545,8 @@ pub struct Settings {
     max_request_body_size: u32,
     /// Maximum size in bytes of a response.
     max_response_body_size: u32,
+    /// Maximum number of bytes of a response that are written to the socket at once.
+    write_chunk_size: u32,
     /// Max length for logging for requests and responses
     ///
     /// Logs bigger than this limit will be truncated.
@@ -557,6 +566,7 @@ impl Default for Settings {
         Self {
             max_request_body_size: TEN_MB_SIZE_BYTES,
             max_response_body_size: TEN_MB_SIZE_BYTES,
+            write_chunk_size: DEFAULT_WRITE_CHUNK_SIZE,
             max_log_length: 4096,
             max_connections: 100,
             max_subscriptions_per_connection: 1024,
@@ -600,6 +610,16 @@ impl<HttpMiddleware, RpcMiddleware> Builder<HttpMiddleware, RpcMiddleware> {
         self
     }
 
+    /// Set the maximum number of bytes of a response that are written to the socket at once.
+    /// Default is 1 MiB.
+    ///
+    /// Larger responses are written in chunks of this size, so that they are never copied into
+    /// the write buffer of the connection as a whole.
+    pub const fn write_chunk_size(mut self, size: u32) -> Self {
+        self.settings.write_chunk_size = size;
+        self
+    }
+
     /// Set the maximum size of a log
     pub const fn max_log_length(mut self, size: u32) -> Self {
         self.settings.max_log_length = size;
@@ -827,6 +847,25 @@ mod tests {
         assert!(response.unwrap_err().to_string().contains("Exceeded max limit of"));
     }
 
+    #[tokio::test]
+    async fn can_write_responses_in_chunks() {
+        let endpoint = &dummy_name();
+        let server = Builder::default().write_chunk_size(16).build(endpoint.clone());
+        let mut module = RpcModule::new(());
+        module.register_method("large", |_, _, _| "a".repeat(100_000)).unwrap();
+        module.register_method("small", |_, _, _| "b").unwrap();
+        let handle = server.start(module).await.unwrap();
+        tokio::spawn(handle.stopped());
+
+        let client = IpcClientBuilder::default().build(endpoint).await.unwrap();
+        let (large, small) = tokio::join!(
+            client.request::<String, _>("large", rpc_params![]),
+            client.request::<String, _>("small", rpc_params![]),
+        );
+        assert_eq!(large.unwrap(), "a".repeat(100_000));
+        assert_eq!(small.unwrap(), "b");
+    }
+
     #[tokio::test]
     async fn can_set_the_max_request_body_size() {
         init_test_tracing();
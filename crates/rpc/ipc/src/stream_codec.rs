@@ -27,7 +27,7 @@
 // This basis of this file has been taken from the deprecated jsonrpc codebase:
 // https://github.com/paritytech/jsonrpc
 
-use bytes::BytesMut;
+use bytes::{BufMut, Bytes, BytesMut};
 use std::{io, str};
 
 /// Separator for enveloping messages in streaming codecs
@@ -47,11 +47,45 @@ impl Default for Separator {
     }
 }
 
+/// A part of an outgoing message.
+///
+/// Large messages are written in chunks, so that they are never copied into the write buffer as a
+/// whole. The outgoing separator is written after the last chunk of a message.
+#[derive(Debug, Clone)]
+pub struct MessageChunk {
+    /// The bytes of the chunk.
+    pub bytes: Bytes,
+    /// Whether this is the last chunk of the message.
+    pub last: bool,
+}
+
 /// Stream codec for streaming protocols (ipc, tcp)
 #[derive(Debug, Default)]
 pub struct StreamCodec {
     incoming_separator: Separator,
     outgoing_separator: Separator,
+    /// The progress of decoding the incoming message.
+    scan: ScanState,
+}
+
+/// The progress of decoding the message at the start of the buffer, so that bytes that were
+/// already scanned are not scanned again when more bytes of a large message arrive.
+#[derive(Debug, Default, Clone, Copy)]
+struct ScanState {
+    /// The index of the next byte to scan.
+    idx: usize,
+    depth: i32,
+    in_str: bool,
+    is_escaped: bool,
+    start_idx: usize,
+    whitespaces: usize,
+}
+
+impl ScanState {
+    /// The state of a message that wasn't scanned yet.
+    const fn new() -> Self {
+        Self { idx: 0, depth: 0, in_str: false, is_escaped: false, start_idx: 0, whitespaces: 0 }
+    }
 }
 
 impl StreamCodec {
@@ -62,7 +96,7 @@ impl StreamCodec {
 
     /// New custom stream codec
     pub const fn new(incoming_separator: Separator, outgoing_separator: Separator) -> Self {
-        Self { incoming_separator, outgoing_separator }
+        Self { incoming_separator, outgoing_separator, scan: ScanState::new() }
     }
 }
 
@@ -77,8 +111,10 @@ impl tokio_util::codec::Decoder for StreamCodec {
 
     fn decode(&mut self, buf: &mut BytesMut) -> io::Result<Option<Self::Item>> {
         if let Separator::Byte(separator) = self.incoming_separator {
-            if let Some(i) = buf.as_ref().iter().position(|&b| b == separator) {
-                let line = buf.split_to(i);
+            let scanned = self.scan.idx.min(buf.len());
+            if let Some(i) = buf[scanned..].iter().position(|&b| b == separator) {
+                self.scan = ScanState::new();
+                let line = buf.split_to(scanned + i);
                 let _ = buf.split_to(1);
 
                 match str::from_utf8(line.as_ref()) {
@@ -86,16 +122,20 @@ impl tokio_util::codec::Decoder for StreamCodec {
                     Err(_) => Err(io::Error::other("invalid UTF-8")),
                 }
             } else {
+                self.scan.idx = buf.len();
                 Ok(None)
             }
         } else {
-            let mut depth = 0;
-            let mut in_str = false;
-            let mut is_escaped = false;
-            let mut start_idx = 0;
-            let mut whitespaces = 0;
-
-            for idx in 0..buf.as_ref().len() {
+            let ScanState {
+                idx: scanned,
+                mut depth,
+                mut in_str,
+                mut is_escaped,
+                mut start_idx,
+                mut whitespaces,
+            } = self.scan;
+
+            for idx in scanned..buf.as_ref().len() {
                 let byte = buf.as_ref()[idx];
 
                 if (byte == b'{' || byte == b'[') && !in_str {
@@ -113,6 +153,7 @@ impl tokio_util::codec::Decoder for StreamCodec {
                 is_escaped = byte == b'\\' && !is_escaped && in_str;
 
                 if depth == 0 && idx != start_idx && idx - start_idx + 1 > whitespaces {
+                    self.scan = ScanState::new();
                     let bts = buf.split_to(idx + 1);
                     return match String::from_utf8(bts.into()) {
                         Ok(val) => Ok(Some(val)),
@@ -120,6 +161,10 @@ impl tokio_util::codec::Decoder for StreamCodec {
                     }
                 }
             }
+
+            // resume after the scanned bytes once more bytes arrive
+            self.scan =
+                ScanState { idx: buf.len(), depth, in_str, is_escaped, start_idx, whitespaces };
             Ok(None)
         }
     }
@@ -138,11 +183,24 @@ impl tokio_util::codec::Encoder<String> for StreamCodec {
     }
 }
 
+impl tokio_util::codec::Encoder<MessageChunk> for StreamCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, chunk: MessageChunk, buf: &mut BytesMut) -> io::Result<()> {
+        buf.extend_from_slice(&chunk.bytes);
+        if let Separator::Byte(separator) = self.outgoing_separator {
+            if chunk.last {
+                buf.put_u8(separator);
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bytes::BufMut;
-    use tokio_util::codec::Decoder;
+    use tokio_util::codec::{Decoder, Encoder};
 
     #[test]
     fn simple_encode() {
@@ -252,6 +310,43 @@ mod tests {
         assert_eq!(request, "{ test: 3 }");
     }
 
+    #[test]
+    fn resumes_fragmented_message() {
+        let message = format!(r#"{{"result":["{}","\"]}}"]}}"#, "ab".repeat(1000));
+        let mut codec = StreamCodec::stream_incoming();
+        let mut buf = BytesMut::new();
+
+        // feed the message in small fragments, as large responses arrive
+        for fragment in message.as_bytes().chunks(7) {
+            assert_eq!(codec.decode(&mut buf).unwrap(), None);
+            buf.put_slice(fragment);
+        }
+        assert_eq!(codec.decode(&mut buf).unwrap(), Some(message.clone()));
+        assert!(buf.is_empty());
+
+        // the state is reset for the next message
+        buf.put_slice(b"{ test: 1 }");
+        assert_eq!(codec.decode(&mut buf).unwrap().as_deref(), Some("{ test: 1 }"));
+
+        let mut codec = StreamCodec::default();
+        buf.put_slice(b"{ test: ");
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        buf.put_slice(b"2 }\n{");
+        assert_eq!(codec.decode(&mut buf).unwrap().as_deref(), Some("{ test: 2 }"));
+        assert_eq!(&buf[..], b"{");
+    }
+
+    #[test]
+    fn encodes_message_chunks() {
+        let mut codec = StreamCodec::default();
+        let mut buf = BytesMut::new();
+        let chunk = |bytes: &'static [u8], last| MessageChunk { bytes: Bytes::from(bytes), last };
+
+        codec.encode(chunk(b"{ test:", false), &mut buf).unwrap();
+        codec.encode(chunk(b" 1 }", true), &mut buf).unwrap();
+        assert_eq!(&buf[..], b"{ test: 1 }\n");
+    }
+
     #[test]
     fn huge() {
         let request = r#"
@@ -1,6 +1,9 @@
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_network_peers::{AnyNode, NodeRecord};
+use reth_network_peers::{AnyNode, NodeRecord, PeerId};
+use reth_network_types::{PeerBackoffState, PeerHeadStatus};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, net::SocketAddr};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -31,18 +34,89 @@ pub trait AdminApi {
     /// connected remote nodes at the networking granularity. These include general information
     /// about the nodes themselves as participants of the devp2p P2P overlay protocol, as well as
     /// specialized information added by each of the running application protocols
+    ///
+    /// In verbose mode, the dial failure history of each peer is included.
     #[method(name = "peers")]
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
+    async fn peers(&self, verbose: Option<bool>) -> RpcResult<Vec<AdminPeerInfo>>;
 
-    /// Creates an RPC subscription which serves events received from the network.
+    /// Creates an RPC subscription which serves an event whenever a session with a peer is
+    /// established or closed.
     #[subscription(
         name = "peerEvents",
         unsubscribe = "peerEvents_unsubscribe",
-        item = String
+        item = AdminPeerEvent
     )]
     async fn subscribe_peer_events(&self) -> jsonrpsee::core::SubscriptionResult;
 
-    /// Returns the ENR of the node.
+    /// Returns the ENR of the node, the supported protocol versions and the ports it listens on.
     #[method(name = "nodeInfo")]
-    async fn node_info(&self) -> RpcResult<NodeInfo>;
+    async fn node_info(&self) -> RpcResult<AdminNodeInfo>;
+}
+
+/// The local node, see `admin_nodeInfo`.
+///
+/// Extends [`NodeInfo`] by the versions of the supported protocols and the ports of the discovery
+/// services.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminNodeInfo {
+    /// The info of the node.
+    #[serde(flatten)]
+    pub info: NodeInfo,
+    /// The supported versions of the `RLPx` sub-protocols by protocol name, e.g. `eth` or `snap`.
+    pub protocol_versions: BTreeMap<String, Vec<u64>>,
+    /// The UDP ports of the discovery services.
+    pub discovery_ports: AdminDiscoveryPorts,
+}
+
+/// The UDP ports of the discovery services, `None` if the service is disabled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminDiscoveryPorts {
+    /// The port of the discv4 service.
+    pub discv4: Option<u16>,
+    /// The port of the discv5 service.
+    pub discv5: Option<u16>,
+}
+
+/// An event of the session with a peer, see `admin_peerEvents`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminPeerEvent {
+    /// Whether the session was established or closed.
+    #[serde(rename = "type")]
+    pub kind: AdminPeerEventKind,
+    /// The id of the peer.
+    pub peer: PeerId,
+    /// The reason the session was closed, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The remote address of an established session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<SocketAddr>,
+}
+
+/// The kind of an [`AdminPeerEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdminPeerEventKind {
+    /// A session with the peer was established.
+    Add,
+    /// The session with the peer was closed.
+    Drop,
+}
+
+/// A connected peer, see `admin_peers`.
+///
+/// Extends [`PeerInfo`] by the classification of the head of the peer and, in verbose mode, the
+/// dial failure history of the peer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminPeerInfo {
+    /// The info of the peer.
+    #[serde(flatten)]
+    pub info: PeerInfo,
+    /// How far the head of the peer lags behind the local head.
+    pub head_status: PeerHeadStatus,
+    /// The dial failure history of the peer, only included in verbose mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backoff: Option<PeerBackoffState>,
 }
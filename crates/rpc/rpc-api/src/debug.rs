@@ -1,15 +1,25 @@
-use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_eips::{eip7685::Requests, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
+use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Address, Bytes, B256};
 use alloy_rpc_types_debug::ExecutionWitness;
-use alloy_rpc_types_eth::{transaction::TransactionRequest, Block, Bundle, StateContext};
+use alloy_rpc_types_eth::{
+    state::StateOverride, transaction::TransactionRequest, Block, BlockOverrides, Bundle,
+    StateContext,
+};
 use alloy_rpc_types_trace::geth::{
-    BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
+    AccountState as PreStateAccount, CallFrame, GethDebugTracingCallOptions,
+    GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
+use serde::{Deserialize, Serialize};
 
 /// Debug rpc interface.
+///
+/// Note: the `debug_getRaw*` endpoints return the canonical RLP (or EIP-2718) encoding of the
+/// requested data. Headers, bodies and receipts are persisted in the compact storage encoding, so
+/// these bytes are always re-encoded from the stored values rather than read verbatim from disk.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
 pub trait DebugApi {
@@ -40,13 +50,23 @@ pub trait DebugApi {
     async fn bad_blocks(&self) -> RpcResult<Vec<Block>>;
 
     /// Returns the structured logs created during the execution of EVM between two blocks
-    /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
+    /// (excluding start).
+    ///
+    /// The blocks are replayed in order and the traces of every block are sent as a separate
+    /// [`BlockTraceResult`](alloy_rpc_types_trace::geth::BlockTraceResult) notification. The
+    /// subscription ends once the end block has been traced. For the third parameter see
+    /// [`GethDebugTracingOptions`].
+    #[subscription(
+        name = "traceChain" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = alloy_rpc_types_trace::geth::BlockTraceResult
+    )]
     async fn debug_trace_chain(
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
@@ -133,15 +153,29 @@ pub trait DebugApi {
         opts: Option<GethDebugTracingCallOptions>,
     ) -> RpcResult<Vec<Vec<GethTrace>>>;
 
+    /// The `debug_traceCallDiff` method runs the same call at two states and returns the
+    /// differences of their call trees and state changes.
+    ///
+    /// Each state is a block, as per EIP-1898, with optional state and block overrides, see
+    /// [`TraceCallDiffState`]. Tracing the call at two blocks shows how its behavior changed
+    /// across an upgrade or fork boundary, tracing it at the same block with and without state
+    /// overrides shows the effect of the overrides.
+    #[method(name = "traceCallDiff")]
+    async fn debug_trace_call_diff(
+        &self,
+        request: TransactionRequest,
+        base: TraceCallDiffState,
+        other: TraceCallDiffState,
+    ) -> RpcResult<TraceCallDiff>;
+
     /// The `debug_executionWitness` method allows for re-execution of a block with the purpose of
     /// generating an execution witness. The witness comprises of a map of all hashed trie nodes
     /// to their preimages that were required during the execution of the block, including during
     /// state root recomputation.
     ///
-    /// The first argument is the block number or tag.
+    /// The first argument is the block number, tag or hash, as per EIP-1898.
     #[method(name = "executionWitness")]
-    async fn debug_execution_witness(&self, block: BlockNumberOrTag)
-        -> RpcResult<ExecutionWitness>;
+    async fn debug_execution_witness(&self, block: BlockId) -> RpcResult<ExecutionWitness>;
 
     /// The `debug_executionWitnessByBlockHash` method allows for re-execution of a block with the
     /// purpose of generating an execution witness. The witness comprises of a map of all hashed
@@ -444,3 +478,137 @@ pub trait DebugExecutionWitnessApi<Attributes> {
         attributes: Attributes,
     ) -> RpcResult<ExecutionWitness>;
 }
+
+/// An extension to the `debug_` namespace that re-executes canonical blocks.
+///
+/// This is separate from the regular `debug_` api, because re-executing blocks is expensive and
+/// therefore only served by the authenticated engine API server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugExecuteBlockApi<R: RpcObject> {
+    /// Re-executes the given canonical block on top of the state of its parent and returns the
+    /// full execution result, e.g. to compare it against the result of other clients.
+    #[method(name = "executeBlock")]
+    async fn debug_execute_block(&self, block: BlockId) -> RpcResult<ExecuteBlockResult<R>>;
+}
+
+/// The result of the re-execution of a block, see `debug_executeBlock`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecuteBlockResult<R> {
+    /// The hash of the block.
+    pub block_hash: B256,
+    /// The number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The receipts of the transactions of the block.
+    pub receipts: Vec<R>,
+    /// The EIP-7685 requests of the block.
+    pub requests: Requests,
+    /// The gas used by the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
+    /// How long the re-execution took.
+    pub timings: ExecuteBlockTimings,
+}
+
+/// The timings of the re-execution of a block in microseconds, see `debug_executeBlock`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecuteBlockTimings {
+    /// Time spent loading the block and its senders.
+    pub fetch_block: u64,
+    /// Time spent executing the transactions of the block.
+    pub execution: u64,
+    /// Total time of the re-execution.
+    pub total: u64,
+}
+
+/// A state a call is traced at by `debug_traceCallDiff`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCallDiffState {
+    /// The block the call is executed on, the latest block if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block: Option<BlockId>,
+    /// The state overrides applied before the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The block overrides applied before the call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+}
+
+/// The differences of a call traced at two states, see `debug_traceCallDiff`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceCallDiff {
+    /// The differences of the call trees, in depth first order.
+    pub calls: Vec<CallFrameDiff>,
+    /// The differences of the state changes of the call, ordered by address.
+    pub state: Vec<StateChangeDiff>,
+}
+
+/// A difference of the call trees of a call traced at two states, see [`TraceCallDiff`].
+///
+/// Calls are matched by their position in the call tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrameDiff {
+    /// The position of the call, the indices of the subcalls from the root call down to the call.
+    /// Empty for the root call.
+    pub path: Vec<usize>,
+    /// How the call differs.
+    #[serde(flatten)]
+    pub change: CallFrameChange,
+}
+
+/// How a call differs between the two states, see [`CallFrameDiff`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum CallFrameChange {
+    /// The call, including its subcalls, was only made at the other state.
+    Added {
+        /// The call at the other state.
+        call: CallFrame,
+    },
+    /// The call, including its subcalls, was only made at the base state.
+    Removed {
+        /// The call at the base state.
+        call: CallFrame,
+    },
+    /// The call was made at both states, but differs. The subcalls are compared separately.
+    Changed {
+        /// The names of the fields that differ, e.g. `output` or `gasUsed`.
+        fields: Vec<String>,
+        /// The call at the base state, without its subcalls.
+        base: Box<CallFrame>,
+        /// The call at the other state, without its subcalls.
+        other: Box<CallFrame>,
+    },
+}
+
+/// A difference of the changes of an account by a call traced at two states, see
+/// [`TraceCallDiff`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateChangeDiff {
+    /// The address of the account.
+    pub address: Address,
+    /// The changes of the account at the base state, `None` if the call didn't change it.
+    pub base: Option<AccountStateChange>,
+    /// The changes of the account at the other state, `None` if the call didn't change it.
+    pub other: Option<AccountStateChange>,
+}
+
+/// The changes of an account by a call, see [`StateChangeDiff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStateChange {
+    /// Whether the call deleted the account.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub deleted: bool,
+    /// The new values of the changed fields and storage slots of the account.
+    #[serde(flatten)]
+    pub post: PreStateAccount,
+}
@@ -28,9 +28,26 @@ mod reth;
 mod rpc;
 mod trace;
 mod txpool;
+mod usage;
 mod validation;
 mod web3;
 
+pub use admin::{
+    AdminDiscoveryPorts, AdminNodeInfo, AdminPeerEvent, AdminPeerEventKind, AdminPeerInfo,
+};
+pub use debug::{
+    AccountStateChange, CallFrameChange, CallFrameDiff, ExecuteBlockResult, ExecuteBlockTimings,
+    StateChangeDiff, TraceCallDiff, TraceCallDiffState,
+};
+pub use otterscan::{OtsBlockDetails, OtsTransactionsWithReceipts, WithdrawalsSummary};
+pub use reth::{
+    AccountChange, AccountDiff, AccountHistoryPoint, AccountState, BlockStateChanges, CanonicalLog,
+    PrecompileStat, PrecompileStatsReport, PruneCheckpoints, RethSubscriptionKind,
+    RethSubscriptionParams, SponsoredTransaction, StateDiff, StateDiffsParams, StorageChange,
+    StorageSlotChange,
+};
+pub use usage::{RpcUsage, RpcUsageReport};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -38,22 +55,23 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
-        debug::{DebugApiServer, DebugExecutionWitnessApiServer},
+        debug::{DebugApiServer, DebugExecuteBlockApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{RethApiServer, RethPrecompileApiServer, RethPruneApiServer},
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
+        usage::RpcUsageApiServer,
         validation::BlockSubmissionValidationApiServer,
         web3::Web3ApiServer,
     };
     pub use reth_rpc_eth_api::{
-        self as eth, EthApiServer, EthBundleApiServer, EthCallBundleApiServer, EthFilterApiServer,
-        EthPubSubApiServer, L2EthApiExtServer,
+        self as eth, EthApiServer, EthBundleApiServer, EthBundleStatusApiServer,
+        EthCallBundleApiServer, EthFilterApiServer, EthPubSubApiServer, L2EthApiExtServer,
     };
 }
 
@@ -67,7 +85,7 @@ pub mod clients {
     pub use crate::{
         admin::AdminApiClient,
         anvil::AnvilApiClient,
-        debug::{DebugApiClient, DebugExecutionWitnessApiClient},
+        debug::{DebugApiClient, DebugExecuteBlockApiClient, DebugExecutionWitnessApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
         ganache::GanacheApiClient,
         hardhat::HardhatApiClient,
@@ -75,15 +93,16 @@ pub mod clients {
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
-        reth::RethApiClient,
+        reth::{RethApiClient, RethPrecompileApiClient, RethPruneApiClient},
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
+        usage::RpcUsageApiClient,
         validation::BlockSubmissionValidationApiClient,
         web3::Web3ApiClient,
     };
     pub use reth_rpc_eth_api::{
-        EthApiClient, EthBundleApiClient, EthCallBundleApiClient, EthFilterApiClient,
-        L2EthApiExtServer,
+        EthApiClient, EthBundleApiClient, EthBundleStatusApiClient, EthCallBundleApiClient,
+        EthFilterApiClient, L2EthApiExtServer,
     };
 }
@@ -1,11 +1,12 @@
 use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId};
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::{Address, Bytes, TxHash, B256};
+use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
 use alloy_rpc_types_trace::otterscan::{
-    BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, TraceEntry,
-    TransactionsWithReceipts,
+    BlockDetails, ContractCreator, InternalOperation, OtsBlockTransactions, OtsTransactionReceipt,
+    TraceEntry,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
 
 /// Otterscan rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "ots"))]
@@ -53,12 +54,12 @@ pub trait Otterscan<T: RpcObject, H: RpcObject> {
     async fn get_block_details(
         &self,
         block_number: LenientBlockNumberOrTag,
-    ) -> RpcResult<BlockDetails<H>>;
+    ) -> RpcResult<OtsBlockDetails<H>>;
 
     /// Tailor-made and expanded version of `eth_getBlockByHash` for block details page in
     /// Otterscan.
     #[method(name = "getBlockDetailsByHash")]
-    async fn get_block_details_by_hash(&self, block_hash: B256) -> RpcResult<BlockDetails<H>>;
+    async fn get_block_details_by_hash(&self, block_hash: B256) -> RpcResult<OtsBlockDetails<H>>;
 
     /// Get paginated transactions for a certain block. Also remove some verbose fields like logs.
     #[method(name = "getBlockTransactions")]
@@ -70,22 +71,28 @@ pub trait Otterscan<T: RpcObject, H: RpcObject> {
     ) -> RpcResult<OtsBlockTransactions<T, H>>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Returns the transactions of the blocks before the given block, or of the latest blocks if
+    /// the block number is zero, in descending order.
     #[method(name = "searchTransactionsBefore")]
     async fn search_transactions_before(
         &self,
         address: Address,
         block_number: LenientBlockNumberOrTag,
         page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts>;
+    ) -> RpcResult<OtsTransactionsWithReceipts<T>>;
 
     /// Gets paginated inbound/outbound transaction calls for a certain address.
+    ///
+    /// Returns the transactions of the blocks after the given block, or of the earliest blocks if
+    /// the block number is zero, in descending order.
     #[method(name = "searchTransactionsAfter")]
     async fn search_transactions_after(
         &self,
         address: Address,
         block_number: LenientBlockNumberOrTag,
         page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts>;
+    ) -> RpcResult<OtsTransactionsWithReceipts<T>>;
 
     /// Gets the transaction hash for a certain sender address, given its nonce.
     #[method(name = "getTransactionBySenderAndNonce")]
@@ -99,3 +106,45 @@ pub trait Otterscan<T: RpcObject, H: RpcObject> {
     #[method(name = "getContractCreator")]
     async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>>;
 }
+
+/// Response of `ots_getBlockDetails` and `ots_getBlockDetailsByHash`.
+///
+/// Extends [`BlockDetails`] by a summary of the withdrawals of the block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsBlockDetails<H> {
+    /// The block details.
+    #[serde(flatten)]
+    pub details: BlockDetails<H>,
+    /// The summary of the withdrawals of the block, if the block has withdrawals.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub withdrawals_summary: Option<WithdrawalsSummary>,
+}
+
+/// Summary of the withdrawals of a block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalsSummary {
+    /// The number of withdrawals.
+    #[serde(with = "alloy_serde::quantity")]
+    pub count: u64,
+    /// The total amount of all withdrawals, in wei.
+    pub total_amount: U256,
+}
+
+/// Response of `ots_searchTransactionsBefore` and `ots_searchTransactionsAfter`.
+///
+/// Same as [`TransactionsWithReceipts`](alloy_rpc_types_trace::otterscan::TransactionsWithReceipts),
+/// but generic over the transaction type of the network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OtsTransactionsWithReceipts<T> {
+    /// The transactions of the page, in descending order.
+    pub txs: Vec<T>,
+    /// The receipts of the transactions.
+    pub receipts: Vec<OtsTransactionReceipt>,
+    /// Whether the page contains the most recent transactions of the address.
+    pub first_page: bool,
+    /// Whether the page contains the oldest transactions of the address.
+    pub last_page: bool,
+}
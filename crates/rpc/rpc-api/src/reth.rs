@@ -1,7 +1,10 @@
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, BlockNumber, B256, U256, U64};
+use alloy_rpc_types_eth::{Filter, Log};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use std::collections::HashMap;
+use reth_prune_types::{PruneCheckpoint, PrunePins, PruneSegment};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 // Required for the subscription attribute below
 use reth_chain_state as _;
@@ -17,6 +20,65 @@ pub trait RethApi {
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
 
+    /// Returns all account and storage changes of a block, with their values before and after the
+    /// block.
+    ///
+    /// The changes are read from the changesets written by execution, without executing the block.
+    #[method(name = "getBlockStateChanges")]
+    async fn reth_get_block_state_changes(&self, block_id: BlockId)
+        -> RpcResult<BlockStateChanges>;
+
+    /// Returns every block in the given range that changed the storage slot of the account,
+    /// together with the value of the slot before and after that block.
+    #[method(name = "getStorageChanges")]
+    async fn reth_get_storage_changes(
+        &self,
+        address: Address,
+        slot: B256,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<StorageChange>>;
+
+    /// Returns the balance of the account at the end of every `step`-th block in the given range,
+    /// starting with `from_block`.
+    ///
+    /// The balances are read from the account history, without executing any block.
+    #[method(name = "getBalanceHistory")]
+    async fn reth_get_balance_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        step: U64,
+    ) -> RpcResult<Vec<AccountHistoryPoint>>;
+
+    /// Returns the nonce of the account at the end of every `step`-th block in the given range,
+    /// starting with `from_block`.
+    ///
+    /// The nonces are read from the account history, without executing any block.
+    #[method(name = "getNonceHistory")]
+    async fn reth_get_nonce_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        step: U64,
+    ) -> RpcResult<Vec<AccountHistoryPoint>>;
+
+    /// Returns the transactions in the given block range that were sent by `fee_payer` on behalf
+    /// of other accounts.
+    ///
+    /// A transaction is sponsored if it carries EIP-7702 authorizations of accounts other than its
+    /// sender, whose fees are paid by the sender. All other transactions, including EIP-4844 blob
+    /// transactions, are paid for by their sender and are never sponsored.
+    #[method(name = "getSponsoredTransactions")]
+    async fn reth_get_sponsored_transactions(
+        &self,
+        fee_payer: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<SponsoredTransaction>>;
+
     /// Subscribe to json `ChainNotifications`
     #[subscription(
         name = "subscribeChainNotifications",
@@ -24,4 +86,270 @@ pub trait RethApi {
         item = reth_chain_state::CanonStateNotification
     )]
     async fn reth_subscribe_chain_notifications(&self) -> jsonrpsee::core::SubscriptionResult;
+
+    /// Subscribe to reth specific events of the given kind.
+    ///
+    /// `stateDiffs` streams the balance, nonce and storage changes of the given addresses in every
+    /// canonical block that changed any of them, see [`StateDiff`].
+    ///
+    /// `logs` streams the logs matching the given filter, like the `logs` subscription of
+    /// `eth_subscribe`, with every log tagged with the canonical epoch it was emitted in, see
+    /// [`CanonicalLog`].
+    #[subscription(
+        name = "subscribe" => "subscription",
+        unsubscribe = "unsubscribe",
+        item = StateDiff
+    )]
+    async fn reth_subscribe(
+        &self,
+        kind: RethSubscriptionKind,
+        params: Option<RethSubscriptionParams>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+}
+
+/// Reth API namespace for inspecting the state of the pruner.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethPruneApi {
+    /// Returns the prune checkpoints of all segments, together with the block ranges that are
+    /// pinned by `ExEx`es and not pruned.
+    #[method(name = "getPruneCheckpoints")]
+    async fn reth_get_prune_checkpoints(&self) -> RpcResult<PruneCheckpoints>;
+}
+
+/// Reth API namespace for inspecting the precompile usage of executed blocks.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait RethPrecompileApi {
+    /// Returns the number of calls of every precompile and the gas they consumed in the given range
+    /// of canonical blocks, ordered by gas used, highest first.
+    ///
+    /// Only the most recent blocks executed by the node since it started are covered, blocks
+    /// without recorded usage are skipped.
+    #[method(name = "precompileStats")]
+    async fn reth_precompile_stats(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<PrecompileStatsReport>;
+}
+
+/// The pruning state of the node, see `reth_getPruneCheckpoints`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruneCheckpoints {
+    /// The prune checkpoints of all segments that were pruned at least once.
+    pub checkpoints: BTreeMap<PruneSegment, PruneCheckpoint>,
+    /// The pinned block ranges of segments, by `ExEx` id.
+    pub pins: PrunePins,
+}
+
+/// A log emitted by the `logs` subscription of `reth_subscribe`, tagged with the canonical epoch it
+/// was emitted in.
+///
+/// The canonical epoch is bumped on every reorg, so it changes with the first log of a reorg.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanonicalLog {
+    /// The canonical epoch the log was emitted in.
+    #[serde(with = "alloy_serde::quantity")]
+    pub canonical_epoch: u64,
+    /// The log.
+    #[serde(flatten)]
+    pub log: Log,
+}
+
+/// A change of a single storage slot in a block, see `reth_getStorageChanges`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageChange {
+    /// The block that changed the storage slot.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The value of the storage slot before the block.
+    pub pre: B256,
+    /// The value of the storage slot after the block.
+    pub post: B256,
+}
+
+/// The state changes of a block, see `reth_getBlockStateChanges`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockStateChanges {
+    /// The number of the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The hash of the block.
+    pub block_hash: B256,
+    /// The accounts changed by the block, ordered by address.
+    pub accounts: Vec<AccountChange>,
+    /// The storage slots changed by the block, ordered by address and slot.
+    pub storage: Vec<StorageSlotChange>,
+}
+
+/// A change of an account in a block, see [`BlockStateChanges`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountChange {
+    /// The address of the account.
+    pub address: Address,
+    /// The account before the block, `None` if it didn't exist.
+    pub pre: Option<AccountState>,
+    /// The account after the block, `None` if it doesn't exist anymore.
+    pub post: Option<AccountState>,
+}
+
+/// The state of an account, see [`AccountChange`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountState {
+    /// The balance of the account.
+    pub balance: U256,
+    /// The nonce of the account.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// The hash of the code of the account.
+    pub code_hash: B256,
+}
+
+/// A change of a storage slot in a block, see [`BlockStateChanges`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageSlotChange {
+    /// The address of the account.
+    pub address: Address,
+    /// The storage slot.
+    pub slot: B256,
+    /// The value of the storage slot before the block.
+    pub pre: B256,
+    /// The value of the storage slot after the block.
+    pub post: B256,
+}
+
+/// A transaction whose fees were paid by its sender on behalf of other accounts, see
+/// `reth_getSponsoredTransactions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SponsoredTransaction {
+    /// The block that included the transaction.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The hash of the transaction.
+    pub transaction_hash: B256,
+    /// The index of the transaction in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_index: u64,
+    /// The accounts whose EIP-7702 authorizations were sponsored by the transaction.
+    pub sponsored: Vec<Address>,
+}
+
+/// The kind of a `reth_subscribe` subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RethSubscriptionKind {
+    /// The state changes of a set of addresses in every canonical block, see [`StateDiff`].
+    StateDiffs,
+    /// The logs matching a filter, tagged with their canonical epoch, see [`CanonicalLog`].
+    Logs,
+}
+
+/// The params of a `reth_subscribe` subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RethSubscriptionParams {
+    /// The params of a `stateDiffs` subscription.
+    StateDiffs(StateDiffsParams),
+    /// The filter of a `logs` subscription.
+    Logs(Box<Filter>),
+}
+
+/// The params of a `stateDiffs` subscription.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDiffsParams {
+    /// The addresses to watch.
+    pub addresses: Vec<Address>,
+}
+
+/// The state changes of the watched addresses in a canonical block, emitted by the `stateDiffs`
+/// subscription of `reth_subscribe`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDiff {
+    /// The block that changed the accounts.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The hash of the block.
+    pub block_hash: B256,
+    /// Whether the block was reverted by a reorg, in which case the changes were undone.
+    pub removed: bool,
+    /// The changes of the watched accounts that were changed by the block.
+    pub accounts: BTreeMap<Address, AccountDiff>,
+}
+
+/// The changes of a single account in a block, see [`StateDiff`].
+///
+/// Only the values that were changed by the block are set, to their value at the end of the block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDiff {
+    /// The balance of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// The nonce of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub nonce: Option<u64>,
+    /// The changed storage slots of the account.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<B256, B256>,
+}
+
+impl AccountDiff {
+    /// Returns `true` if the account wasn't changed.
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none() && self.nonce.is_none() && self.storage.is_empty()
+    }
+}
+
+/// A value of an account at the end of a block, see `reth_getBalanceHistory` and
+/// `reth_getNonceHistory`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountHistoryPoint {
+    /// The block at the end of which the account had the value.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: BlockNumber,
+    /// The value of the account.
+    pub value: U256,
+}
+
+/// The precompile usage in a range of blocks, see `reth_precompileStats`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrecompileStatsReport {
+    /// The first block of the range.
+    #[serde(with = "alloy_serde::quantity")]
+    pub from_block: BlockNumber,
+    /// The last block of the range.
+    #[serde(with = "alloy_serde::quantity")]
+    pub to_block: BlockNumber,
+    /// The number of blocks in the range with recorded precompile usage.
+    #[serde(with = "alloy_serde::quantity")]
+    pub covered_blocks: u64,
+    /// The usage of every precompile that was called, ordered by gas used, highest first.
+    pub precompiles: Vec<PrecompileStat>,
+}
+
+/// The usage of a single precompile, see `reth_precompileStats`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrecompileStat {
+    /// The address of the precompile.
+    pub address: Address,
+    /// The number of calls.
+    #[serde(with = "alloy_serde::quantity")]
+    pub calls: u64,
+    /// The total gas used by all successful calls.
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_used: u64,
 }
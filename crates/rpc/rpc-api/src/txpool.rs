@@ -31,7 +31,15 @@ pub trait TxPoolApi<T: RpcObject> {
     /// Returns the details of all transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     ///
+    /// The content can be paginated by sender: only the senders after the optional `cursor`
+    /// address are returned, in ascending order, and at most `limit` transactions unless a single
+    /// sender has more. The greatest sender address of a page is the cursor of the next page.
+    ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
-    async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+    async fn txpool_content(
+        &self,
+        cursor: Option<Address>,
+        limit: Option<usize>,
+    ) -> RpcResult<TxpoolContent<T>>;
 }
@@ -0,0 +1,37 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Operator API for inspecting the JSON-RPC method usage of the consumers of the server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait RpcUsageApi {
+    /// Returns the method usage of all open connections and of the most recently used API keys
+    /// since the node started.
+    #[method(name = "rpcUsage")]
+    fn rpc_usage(&self) -> RpcResult<RpcUsageReport>;
+}
+
+/// The JSON-RPC method usage of a single consumer, see `admin_rpcUsage`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcUsage {
+    /// The total number of method calls.
+    pub calls: u64,
+    /// The total size of the serialized responses, in bytes.
+    pub response_bytes: u64,
+    /// The cumulative time spent executing the calls, in microseconds.
+    pub execution_time_micros: u64,
+    /// The number of calls per method.
+    pub methods: BTreeMap<String, u64>,
+}
+
+/// The JSON-RPC method usage of all consumers of the server, see `admin_rpcUsage`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcUsageReport {
+    /// The usage of every open connection, by connection id.
+    pub connections: BTreeMap<u64, RpcUsage>,
+    /// The usage of the most recently used API keys, by the hash of the API key.
+    pub api_keys: BTreeMap<String, RpcUsage>,
+}
@@ -0,0 +1,521 @@
+//! [`jsonrpsee`] helper layer for structured access logging of RPC calls.
+//!
+//! Every call is logged as a [`RpcAccessLogRecord`] with its method, a hash of its params, the IP
+//! of the client, the execution time, the size of the response and the class of its error, if
+//! any. Records are either emitted as tracing events or appended to a file as JSON lines, and
+//! successful calls can be sampled to keep the volume of busy servers manageable.
+
+use crate::rate_limiter::{RpcClientIp, RpcClientIpLayer, RATE_LIMIT_EXCEEDED_CODE};
+use http::{header::InvalidHeaderName, HeaderName};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::Request,
+    MethodResponse,
+};
+use reth_metrics::{metrics::Counter, Metrics};
+use serde::Serialize;
+use serde_json::value::RawValue;
+use std::{
+    fs::{File, OpenOptions},
+    future::Future,
+    io::{self, BufWriter, Write},
+    net::IpAddr,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::{self, Receiver, SyncSender, TrySendError},
+        Arc,
+    },
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+use tower::Layer;
+use tracing::{info, warn};
+
+/// The number of records that can be queued for the file writer before records are dropped.
+const FILE_QUEUE_SIZE: usize = 8192;
+
+/// The method of the records of batch requests.
+const BATCH_METHOD: &str = "batch";
+
+/// Where the records of the access log are written to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum RpcAccessLogOutput {
+    /// Emit the records as tracing events with the `rpc::access_log` target.
+    #[default]
+    Tracing,
+    /// Append the records as JSON lines to the file.
+    File(PathBuf),
+}
+
+/// Configuration of the [`RpcAccessLog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcAccessLogConfig {
+    /// Where the records are written to.
+    pub output: RpcAccessLogOutput,
+    /// Only one in every `sample` successful calls is logged. Failed calls are always logged.
+    pub sample: u64,
+    /// The HTTP header that carries the IP address of the client, e.g. `x-forwarded-for` if the
+    /// server is behind a reverse proxy.
+    pub client_ip_header: Option<String>,
+    /// The IP addresses of the reverse proxies whose client IP header is trusted, see
+    /// [`RpcClientIpLayer`].
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+impl RpcAccessLogConfig {
+    /// Sets where the records are written to.
+    pub fn with_output(mut self, output: RpcAccessLogOutput) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Logs only one in every `sample` successful calls.
+    pub const fn with_sample(mut self, sample: u64) -> Self {
+        self.sample = sample;
+        self
+    }
+
+    /// Sets the HTTP header that carries the IP address of the client.
+    pub fn with_client_ip_header(mut self, header: Option<String>) -> Self {
+        self.client_ip_header = header;
+        self
+    }
+
+    /// Sets the IP addresses of the reverse proxies whose client IP header is trusted.
+    pub fn with_trusted_proxies(mut self, trusted_proxies: Vec<IpAddr>) -> Self {
+        self.trusted_proxies = trusted_proxies;
+        self
+    }
+}
+
+impl Default for RpcAccessLogConfig {
+    fn default() -> Self {
+        Self {
+            output: RpcAccessLogOutput::Tracing,
+            sample: 1,
+            client_ip_header: None,
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+/// Errors that can occur when creating the [`RpcAccessLog`].
+#[derive(Debug, thiserror::Error)]
+pub enum RpcAccessLogError {
+    /// The client IP header is not a valid header name.
+    #[error("invalid access log client IP header: {0}")]
+    InvalidHeader(#[from] InvalidHeaderName),
+    /// The log file can't be opened or its writer can't be started.
+    #[error("failed to set up access log {path}: {error}")]
+    File {
+        /// The path of the log file.
+        path: PathBuf,
+        /// The error that occurred.
+        error: io::Error,
+    },
+}
+
+/// The class of the error of a call, derived from its JSON-RPC error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcErrorClass {
+    /// The request is not valid JSON, `-32700`.
+    ParseError,
+    /// The request is not a valid JSON-RPC request, `-32600`.
+    InvalidRequest,
+    /// The method doesn't exist or is not available, `-32601`.
+    MethodNotFound,
+    /// The params of the call are invalid, `-32602`.
+    InvalidParams,
+    /// An internal error of the server, `-32603`.
+    InternalError,
+    /// The call exceeded a rate limit, `-32005`.
+    RateLimited,
+    /// The executed call reverted, `3`.
+    ExecutionReverted,
+    /// Any other server error, `-32000` to `-32099`.
+    ServerError,
+    /// An error code defined by the method.
+    Application,
+}
+
+impl RpcErrorClass {
+    /// Returns the class of the error code.
+    pub const fn from_code(code: i32) -> Self {
+        match code {
+            -32700 => Self::ParseError,
+            -32600 => Self::InvalidRequest,
+            -32601 => Self::MethodNotFound,
+            -32602 => Self::InvalidParams,
+            -32603 => Self::InternalError,
+            RATE_LIMIT_EXCEEDED_CODE => Self::RateLimited,
+            3 => Self::ExecutionReverted,
+            -32099..=-32000 => Self::ServerError,
+            _ => Self::Application,
+        }
+    }
+
+    /// Returns the name of the class.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ParseError => "parse_error",
+            Self::InvalidRequest => "invalid_request",
+            Self::MethodNotFound => "method_not_found",
+            Self::InvalidParams => "invalid_params",
+            Self::InternalError => "internal_error",
+            Self::RateLimited => "rate_limited",
+            Self::ExecutionReverted => "execution_reverted",
+            Self::ServerError => "server_error",
+            Self::Application => "application",
+        }
+    }
+}
+
+/// The access log record of a call or a batch of calls.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RpcAccessLogRecord {
+    /// When the call was received, in milliseconds since the unix epoch.
+    pub timestamp: u64,
+    /// The method of the call, or `batch` for batch requests.
+    pub method: String,
+    /// The hash of the params of the call, to correlate identical calls without logging the
+    /// params. Not set for batch requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params_hash: Option<String>,
+    /// The number of calls of a batch request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_len: Option<usize>,
+    /// The IP address of the client, if known.
+    pub client_ip: Option<IpAddr>,
+    /// The execution time of the call, in microseconds.
+    pub duration_us: u64,
+    /// The size of the response, in bytes. Not set for batch requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_bytes: Option<usize>,
+    /// The class of the error of the call, if it failed.
+    pub error_class: Option<RpcErrorClass>,
+}
+
+/// A structured access log of RPC calls.
+///
+/// Clones share the same output and sampling. The calls are logged by the [`RpcAccessLogLayer`]
+/// RPC middleware.
+#[derive(Debug, Clone)]
+pub struct RpcAccessLog {
+    inner: Arc<RpcAccessLogInner>,
+}
+
+impl RpcAccessLog {
+    /// Creates a new access log from the given config.
+    ///
+    /// If the records are written to a file, the file is opened in append mode and written to by
+    /// a dedicated thread, so that calls are never blocked by the disk.
+    pub fn new(config: RpcAccessLogConfig) -> Result<Self, RpcAccessLogError> {
+        let client_ip_header =
+            config.client_ip_header.as_deref().map(HeaderName::try_from).transpose()?;
+        let sink = match &config.output {
+            RpcAccessLogOutput::Tracing => AccessLogSink::Tracing,
+            RpcAccessLogOutput::File(path) => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|error| RpcAccessLogError::File { path: path.clone(), error })?;
+                let (tx, rx) = mpsc::sync_channel(FILE_QUEUE_SIZE);
+                std::thread::Builder::new()
+                    .name("rpc-access-log".to_string())
+                    .spawn(move || write_records(rx, file))
+                    .map_err(|error| RpcAccessLogError::File { path: path.clone(), error })?;
+                AccessLogSink::File(tx)
+            }
+        };
+        Ok(Self {
+            inner: Arc::new(RpcAccessLogInner {
+                sample: config.sample.max(1),
+                client_ip_layer: RpcClientIpLayer::new(client_ip_header, config.trusted_proxies),
+                calls: AtomicU64::new(0),
+                sink,
+                metrics: Default::default(),
+            }),
+        })
+    }
+
+    /// Returns the HTTP middleware layer that attaches the client IP of requests.
+    pub fn client_ip_layer(&self) -> RpcClientIpLayer {
+        self.inner.client_ip_layer.clone()
+    }
+
+    /// Returns true if the successful call is sampled.
+    fn sampled(&self) -> bool {
+        self.inner.calls.fetch_add(1, Ordering::Relaxed) % self.inner.sample == 0
+    }
+
+    /// Logs the record of a call, if it failed or is sampled.
+    fn log(&self, record: impl FnOnce() -> RpcAccessLogRecord, failed: bool) {
+        if !failed && !self.sampled() {
+            return
+        }
+        let record = record();
+
+        match &self.inner.sink {
+            AccessLogSink::Tracing => info!(
+                target: "rpc::access_log",
+                method = %record.method,
+                params_hash = record.params_hash.as_deref().unwrap_or_default(),
+                batch_len = ?record.batch_len,
+                client_ip = ?record.client_ip,
+                duration_us = record.duration_us,
+                response_bytes = ?record.response_bytes,
+                error_class = record.error_class.map(|class| class.as_str()).unwrap_or_default(),
+                "RPC call"
+            ),
+            AccessLogSink::File(tx) => match tx.try_send(record) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => self.inner.metrics.dropped_records.increment(1),
+                Err(TrySendError::Disconnected(_)) => {
+                    self.inner.metrics.dropped_records.increment(1);
+                    warn!(target: "rpc::access_log", "Access log writer stopped");
+                }
+            },
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RpcAccessLogInner {
+    /// Only one in every `sample` successful calls is logged.
+    sample: u64,
+    /// The HTTP middleware layer that attaches the client IP of requests.
+    client_ip_layer: RpcClientIpLayer,
+    /// The number of successful calls, used for sampling.
+    calls: AtomicU64,
+    /// Where the records are written to.
+    sink: AccessLogSink,
+    /// The metrics of the access log.
+    metrics: RpcAccessLogMetrics,
+}
+
+/// The output of the records.
+#[derive(Debug)]
+enum AccessLogSink {
+    /// Emit tracing events.
+    Tracing,
+    /// Send the records to the thread that writes the log file.
+    File(SyncSender<RpcAccessLogRecord>),
+}
+
+/// Writes the received records as JSON lines to the file until the access log is dropped.
+fn write_records(rx: Receiver<RpcAccessLogRecord>, file: File) {
+    let mut writer = BufWriter::new(file);
+    while let Ok(record) = rx.recv() {
+        let mut result = write_record(&mut writer, &record);
+        // flush once the queue is drained, so records show up promptly
+        while let Ok(record) = rx.try_recv() {
+            result = result.and_then(|_| write_record(&mut writer, &record));
+        }
+        if let Err(err) = result.and_then(|_| writer.flush()) {
+            warn!(target: "rpc::access_log", %err, "Failed to write access log");
+        }
+    }
+}
+
+/// Writes the record as a JSON line.
+fn write_record(writer: &mut impl Write, record: &RpcAccessLogRecord) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, record)?;
+    writer.write_all(b"\n")
+}
+
+/// Returns the FNV-1a hash of the params, as hex.
+fn params_hash(params: &str) -> String {
+    let hash = params.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// Returns the current time in milliseconds since the unix epoch.
+fn unix_timestamp_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+/// RPC middleware layer that logs calls to the [`RpcAccessLog`], if any.
+#[derive(Debug, Clone, Default)]
+pub struct RpcAccessLogLayer {
+    /// The access log calls are logged to.
+    log: Option<RpcAccessLog>,
+}
+
+impl RpcAccessLogLayer {
+    /// Creates a new layer that logs calls to the given access log, if any.
+    pub const fn new(log: Option<RpcAccessLog>) -> Self {
+        Self { log }
+    }
+}
+
+impl<S> Layer<S> for RpcAccessLogLayer {
+    type Service = RpcAccessLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcAccessLogService { log: self.log.clone(), inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that logs calls to the [`RpcAccessLog`].
+#[derive(Debug, Clone)]
+pub struct RpcAccessLogService<S> {
+    /// The access log calls are logged to, if enabled.
+    log: Option<RpcAccessLog>,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> RpcServiceT for RpcAccessLogService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = S::MethodResponse> + Send + 'a {
+        // the method and params are usually borrowed from the request body, so this is cheap
+        let call = self.log.clone().map(|log| {
+            let client_ip = req.extensions.get::<RpcClientIp>().map(|RpcClientIp(ip)| *ip);
+            (log, req.method.clone(), req.params.clone(), client_ip)
+        });
+        let fut = self.inner.call(req);
+        async move {
+            let timestamp = unix_timestamp_millis();
+            let started_at = Instant::now();
+            let resp = fut.await;
+            if let Some((log, method, params, client_ip)) = call {
+                let duration_us = started_at.elapsed().as_micros() as u64;
+                let error_class = resp.as_error_code().map(RpcErrorClass::from_code);
+                let record = || RpcAccessLogRecord {
+                    timestamp,
+                    method: method.into_owned(),
+                    params_hash: Some(params_hash(params.as_deref().map_or("", RawValue::get))),
+                    batch_len: None,
+                    client_ip,
+                    duration_us,
+                    response_bytes: Some(resp.as_json().get().len()),
+                    error_class,
+                };
+                log.log(record, error_class.is_some());
+            }
+            resp
+        }
+    }
+
+    /// Batches are logged as a single record with the number of calls. The calls of a batch are
+    /// not passed through the middleware individually and the response is not inspected.
+    fn batch<'a>(
+        &self,
+        mut req: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let batch = self.log.clone().map(|log| {
+            let mut batch_len = 0;
+            let mut client_ip = None;
+            for entry in req.iter_mut() {
+                batch_len += 1;
+                if let Ok(BatchEntry::Call(call)) = entry {
+                    client_ip = client_ip.or_else(|| call.extensions.get::<RpcClientIp>().copied());
+                }
+            }
+            (log, batch_len, client_ip.map(|RpcClientIp(ip)| ip))
+        });
+        let fut = self.inner.batch(req);
+        async move {
+            let timestamp = unix_timestamp_millis();
+            let started_at = Instant::now();
+            let resp = fut.await;
+            if let Some((log, batch_len, client_ip)) = batch {
+                let duration_us = started_at.elapsed().as_micros() as u64;
+                let record = || RpcAccessLogRecord {
+                    timestamp,
+                    method: BATCH_METHOD.to_string(),
+                    params_hash: None,
+                    batch_len: Some(batch_len),
+                    client_ip,
+                    duration_us,
+                    response_bytes: None,
+                    error_class: None,
+                };
+                log.log(record, false);
+            }
+            resp
+        }
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Metrics for the RPC access log
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.access_log")]
+struct RpcAccessLogMetrics {
+    /// The number of records dropped because the log file couldn't keep up
+    dropped_records: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    #[test]
+    fn error_classes() {
+        assert_eq!(RpcErrorClass::from_code(-32601), RpcErrorClass::MethodNotFound);
+        assert_eq!(RpcErrorClass::from_code(-32005), RpcErrorClass::RateLimited);
+        assert_eq!(RpcErrorClass::from_code(-32050), RpcErrorClass::ServerError);
+        assert_eq!(RpcErrorClass::from_code(3), RpcErrorClass::ExecutionReverted);
+        assert_eq!(RpcErrorClass::from_code(-38001), RpcErrorClass::Application);
+    }
+
+    #[test]
+    fn hashes_params() {
+        // the FNV-1a test vectors
+        assert_eq!(params_hash(""), "cbf29ce484222325");
+        assert_eq!(params_hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(params_hash(r#"["0x1"]"#), params_hash(r#"["0x2"]"#));
+    }
+
+    #[test]
+    fn samples_one_in_n_calls() {
+        let log = RpcAccessLog::new(RpcAccessLogConfig::default().with_sample(3)).unwrap();
+        let sampled = (0..9).filter(|_| log.sampled()).count();
+        assert_eq!(sampled, 3);
+
+        let log = RpcAccessLog::new(RpcAccessLogConfig::default().with_sample(0)).unwrap();
+        assert!((0..3).all(|_| log.sampled()));
+    }
+
+    #[test]
+    fn serializes_records_as_json_lines() {
+        let record = RpcAccessLogRecord {
+            timestamp: 1,
+            method: "eth_call".to_string(),
+            params_hash: Some(params_hash("[]")),
+            batch_len: None,
+            client_ip: Some(IpAddr::V4(Ipv4Addr::LOCALHOST)),
+            duration_us: 42,
+            response_bytes: Some(64),
+            error_class: Some(RpcErrorClass::ExecutionReverted),
+        };
+        let mut buf = Vec::new();
+        write_record(&mut buf, &record).unwrap();
+        let line = String::from_utf8(buf).unwrap();
+        assert!(line.ends_with("}\n"));
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(json["method"], "eth_call");
+        assert_eq!(json["client_ip"], "127.0.0.1");
+        assert_eq!(json["error_class"], "execution_reverted");
+        assert!(json.get("batch_len").is_none());
+    }
+}
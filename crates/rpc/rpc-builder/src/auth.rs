@@ -1,9 +1,9 @@
 use crate::error::{RpcError, ServerKind};
-use http::header::AUTHORIZATION;
+use http::{header::AUTHORIZATION, StatusCode, Version};
 use jsonrpsee::{
     core::{client::SubscriptionClientT, RegisterMethodError},
     http_client::HeaderMap,
-    server::{AlreadyStoppedError, RpcModule},
+    server::{AlreadyStoppedError, HttpBody, HttpRequest, HttpResponse, RpcModule, ServerHandle},
     Methods,
 };
 use reth_rpc_api::servers::*;
@@ -12,8 +12,14 @@ use reth_rpc_layer::{
     secret_to_bearer_header, AuthClientLayer, AuthLayer, JwtAuthValidator, JwtSecret,
 };
 use reth_rpc_server_types::constants;
-use std::net::{IpAddr, Ipv4Addr, SocketAddr};
-use tower::layer::util::Identity;
+use std::{
+    future::Future,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::{layer::util::Identity, Layer, Service};
 
 pub use jsonrpsee::server::ServerBuilder;
 use jsonrpsee::server::{ServerConfig, ServerConfigBuilder};
@@ -32,6 +38,12 @@ pub struct AuthServerConfig {
     pub(crate) ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     /// IPC endpoint
     pub(crate) ipc_endpoint: Option<String>,
+    /// Whether the server listens on `socket_addr`.
+    pub(crate) tcp: bool,
+    /// The unix socket to serve HTTP and WS on, without JWT authentication.
+    pub(crate) unix_socket: Option<PathBuf>,
+    /// Whether HTTP/2 requests are accepted in addition to HTTP/1.
+    pub(crate) http2: bool,
 }
 
 // === impl AuthServerConfig ===
@@ -47,27 +59,55 @@ impl AuthServerConfig {
         self.socket_addr
     }
 
+    /// Returns the unix socket the server will listen on, if any.
+    pub fn unix_socket(&self) -> Option<&Path> {
+        self.unix_socket.as_deref()
+    }
+
     /// Convenience function to start a server in one step.
     pub async fn start(self, module: AuthRpcModule) -> Result<AuthServerHandle, RpcError> {
-        let Self { socket_addr, secret, server_config, ipc_server_config, ipc_endpoint } = self;
-
-        // Create auth middleware.
-        let middleware =
-            tower::ServiceBuilder::new().layer(AuthLayer::new(JwtAuthValidator::new(secret)));
-
-        // By default, both http and ws are enabled.
-        let server = ServerBuilder::new()
-            .set_config(server_config.build())
-            .set_http_middleware(middleware)
-            .build(socket_addr)
-            .await
-            .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+        let Self {
+            socket_addr,
+            secret,
+            server_config,
+            ipc_server_config,
+            ipc_endpoint,
+            tcp,
+            unix_socket,
+            http2,
+        } = self;
+
+        let mut handle = None;
+        let mut local_addr = socket_addr;
+        if tcp {
+            // Create auth middleware.
+            let middleware = tower::ServiceBuilder::new()
+                .layer(AuthLayer::new(JwtAuthValidator::new(secret)))
+                .layer(HttpVersionLayer::new(http2));
+
+            // By default, both http and ws are enabled.
+            let server = ServerBuilder::new()
+                .set_config(server_config.clone().build())
+                .set_http_middleware(middleware)
+                .build(socket_addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+
+            local_addr = server
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+
+            handle = Some(server.start(module.inner.clone()));
+        }
 
-        let local_addr = server
-            .local_addr()
-            .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
+        let mut unix_socket_handle = None;
+        if let Some(path) = &unix_socket {
+            unix_socket_handle = Some(
+                start_unix_socket_server(path, server_config.build(), http2, module.inner.clone())
+                    .await?,
+            );
+        }
 
-        let handle = server.start(module.inner.clone());
         let mut ipc_handle: Option<jsonrpsee::server::ServerHandle> = None;
 
         if let Some(ipc_server_config) = ipc_server_config {
@@ -79,7 +119,149 @@ impl AuthServerConfig {
             ipc_handle = Some(res);
         }
 
-        Ok(AuthServerHandle { handle: Some(handle), local_addr, secret, ipc_endpoint, ipc_handle })
+        Ok(AuthServerHandle {
+            handle,
+            local_addr,
+            secret,
+            ipc_endpoint,
+            ipc_handle,
+            unix_socket,
+            unix_socket_handle,
+        })
+    }
+}
+
+/// Serves HTTP and WS on the unix socket at the given path, without JWT authentication.
+///
+/// Access to the socket is restricted to the user of the node instead. A socket of a previous run
+/// at the path is replaced.
+#[cfg(unix)]
+async fn start_unix_socket_server(
+    path: &Path,
+    server_config: ServerConfig,
+    http2: bool,
+    methods: impl Into<Methods>,
+) -> Result<ServerHandle, RpcError> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+    use tokio::net::UnixListener;
+
+    let unix_socket_error = |error| RpcError::AuthUnixSocket { path: path.to_path_buf(), error };
+
+    if std::fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_socket()) {
+        std::fs::remove_file(path).map_err(unix_socket_error)?;
+    }
+    let listener = UnixListener::bind(path).map_err(unix_socket_error)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .map_err(unix_socket_error)?;
+
+    let (stop_handle, server_handle) = jsonrpsee::server::stop_channel();
+    let service = ServerBuilder::new()
+        .set_config(server_config)
+        .set_http_middleware(tower::ServiceBuilder::new().layer(HttpVersionLayer::new(http2)))
+        .to_service_builder()
+        .build(methods, stop_handle.clone());
+
+    tokio::spawn(async move {
+        loop {
+            let stream = tokio::select! {
+                _ = stop_handle.clone().shutdown() => break,
+                stream = listener.accept() => match stream {
+                    Ok((stream, _)) => stream,
+                    Err(err) => {
+                        tracing::debug!(target: "rpc::auth", %err, "Failed to accept unix socket connection");
+                        continue
+                    }
+                }
+            };
+
+            let service = service.clone();
+            let stop_handle = stop_handle.clone();
+            tokio::spawn(async move {
+                let _ = jsonrpsee::server::serve_with_graceful_shutdown(
+                    stream,
+                    service,
+                    stop_handle.shutdown(),
+                )
+                .await
+                .inspect_err(
+                    |err| tracing::debug!(target: "rpc::auth", %err, "Failed to serve connection"),
+                );
+            });
+        }
+    });
+
+    Ok(server_handle)
+}
+
+#[cfg(not(unix))]
+async fn start_unix_socket_server(
+    path: &Path,
+    _server_config: ServerConfig,
+    _http2: bool,
+    _methods: impl Into<Methods>,
+) -> Result<ServerHandle, RpcError> {
+    Err(RpcError::AuthUnixSocket {
+        path: path.to_path_buf(),
+        error: std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "unix sockets are not supported on this platform",
+        ),
+    })
+}
+
+/// An HTTP middleware layer that rejects HTTP/2 requests with `505 HTTP Version Not Supported`,
+/// unless HTTP/2 is enabled.
+#[derive(Debug, Clone, Copy)]
+struct HttpVersionLayer {
+    /// Whether HTTP/2 requests are accepted.
+    http2: bool,
+}
+
+impl HttpVersionLayer {
+    const fn new(http2: bool) -> Self {
+        Self { http2 }
+    }
+}
+
+impl<S> Layer<S> for HttpVersionLayer {
+    type Service = HttpVersionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HttpVersionService { http2: self.http2, inner }
+    }
+}
+
+/// The HTTP service of the [`HttpVersionLayer`].
+#[derive(Debug, Clone)]
+struct HttpVersionService<S> {
+    /// Whether HTTP/2 requests are accepted.
+    http2: bool,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S, B> Service<HttpRequest<B>> for HttpVersionService<S>
+where
+    S: Service<HttpRequest<B>, Response = HttpResponse>,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest<B>) -> Self::Future {
+        if !self.http2 && req.version() >= Version::HTTP_2 {
+            let response = HttpResponse::builder()
+                .status(StatusCode::HTTP_VERSION_NOT_SUPPORTED)
+                .body(HttpBody::new("HTTP/2 is not enabled".to_string()))
+                .expect("This should never happen");
+            return Box::pin(async move { Ok(response) })
+        }
+        Box::pin(self.inner.call(req))
     }
 }
 
@@ -91,6 +273,9 @@ pub struct AuthServerConfigBuilder {
     server_config: Option<ServerConfigBuilder>,
     ipc_server_config: Option<IpcServerBuilder<Identity, Identity>>,
     ipc_endpoint: Option<String>,
+    tcp: bool,
+    unix_socket: Option<PathBuf>,
+    http2: bool,
 }
 
 // === impl AuthServerConfigBuilder ===
@@ -104,6 +289,9 @@ impl AuthServerConfigBuilder {
             server_config: None,
             ipc_server_config: None,
             ipc_endpoint: None,
+            tcp: true,
+            unix_socket: None,
+            http2: false,
         }
     }
 
@@ -148,6 +336,38 @@ impl AuthServerConfigBuilder {
         self
     }
 
+    /// Sets whether the server listens on the socket address, enabled by default.
+    ///
+    /// Disabling this isolates the server from the network, if it's served on a unix socket or
+    /// IPC instead.
+    pub const fn with_tcp(mut self, tcp: bool) -> Self {
+        self.tcp = tcp;
+        self
+    }
+
+    /// Serves HTTP and WS on the unix socket at the given path as well.
+    ///
+    /// Requests on the unix socket aren't authenticated with the JWT secret, access is restricted
+    /// to the user of the node by the permissions of the socket instead.
+    pub fn with_unix_socket(mut self, path: PathBuf) -> Self {
+        self.unix_socket = Some(path);
+        self
+    }
+
+    /// Serves HTTP and WS on the unix socket at the given path as well, if any.
+    ///
+    /// See [`Self::with_unix_socket`].
+    pub fn maybe_unix_socket(mut self, path: Option<PathBuf>) -> Self {
+        self.unix_socket = path;
+        self
+    }
+
+    /// Sets whether HTTP/2 requests are accepted in addition to HTTP/1, disabled by default.
+    pub const fn with_http2(mut self, http2: bool) -> Self {
+        self.http2 = http2;
+        self
+    }
+
     /// Build the `AuthServerConfig`.
     pub fn build(self) -> AuthServerConfig {
         AuthServerConfig {
@@ -181,6 +401,9 @@ impl AuthServerConfigBuilder {
                     .set_id_provider(EthSubscriptionIdProvider::default())
             }),
             ipc_endpoint: self.ipc_endpoint,
+            tcp: self.tcp,
+            unix_socket: self.unix_socket,
+            http2: self.http2,
         }
     }
 }
@@ -257,6 +480,8 @@ pub struct AuthServerHandle {
     secret: JwtSecret,
     ipc_endpoint: Option<String>,
     ipc_handle: Option<jsonrpsee::server::ServerHandle>,
+    unix_socket: Option<PathBuf>,
+    unix_socket_handle: Option<ServerHandle>,
 }
 
 // === impl AuthServerHandle ===
@@ -275,6 +500,8 @@ impl AuthServerHandle {
             secret: JwtSecret::random(),
             ipc_endpoint: None,
             ipc_handle: None,
+            unix_socket: None,
+            unix_socket_handle: None,
         }
     }
 
@@ -285,6 +512,9 @@ impl AuthServerHandle {
 
     /// Tell the server to stop without waiting for the server to stop.
     pub fn stop(self) -> Result<(), AlreadyStoppedError> {
+        if let Some(handle) = self.unix_socket_handle {
+            handle.stop()?;
+        }
         let Some(handle) = self.handle else { return Ok(()) };
         handle.stop()
     }
@@ -350,4 +580,32 @@ impl AuthServerHandle {
     pub fn ipc_endpoint(&self) -> Option<String> {
         self.ipc_endpoint.clone()
     }
+
+    /// Returns the unix socket the server serves HTTP and WS on, if any.
+    pub fn unix_socket(&self) -> Option<&Path> {
+        self.unix_socket.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use tower::ServiceExt;
+
+    async fn status(http2: bool, version: Version) -> StatusCode {
+        let inner = tower::service_fn(|_: HttpRequest| async {
+            Ok::<_, Infallible>(HttpResponse::new(HttpBody::new(String::new())))
+        });
+        let request =
+            HttpRequest::builder().version(version).body(HttpBody::new(String::new())).unwrap();
+        HttpVersionLayer::new(http2).layer(inner).oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn rejects_http2_unless_enabled() {
+        assert_eq!(status(false, Version::HTTP_11).await, StatusCode::OK);
+        assert_eq!(status(false, Version::HTTP_2).await, StatusCode::HTTP_VERSION_NOT_SUPPORTED);
+        assert_eq!(status(true, Version::HTTP_2).await, StatusCode::OK);
+    }
 }
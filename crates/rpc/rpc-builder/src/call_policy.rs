@@ -0,0 +1,63 @@
+//! Per API key limits of `eth_call` and `eth_estimateGas`.
+
+use crate::usage::RpcApiKey;
+use http::Extensions;
+use reth_rpc_eth_types::{CallPolicy, CallPolicyProvider};
+use std::collections::HashMap;
+
+/// A [`CallPolicyProvider`] that resolves the [`CallPolicy`] of a request by its [`RpcApiKey`].
+///
+/// Requests without an API key or with an unknown API key get the default policy. The API key is
+/// attached to requests by the [`RpcApiKeyLayer`](crate::usage::RpcApiKeyLayer), which is
+/// installed on the HTTP and WS servers if usage accounting is enabled.
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeyCallPolicies {
+    /// The policies of known API keys.
+    policies: HashMap<String, CallPolicy>,
+    /// The policy of all other requests.
+    default: CallPolicy,
+}
+
+impl ApiKeyCallPolicies {
+    /// Creates a new instance that applies the given policy to all requests.
+    pub fn new(default: CallPolicy) -> Self {
+        Self { policies: HashMap::new(), default }
+    }
+
+    /// Applies the given policy to requests with the given API key.
+    pub fn with_policy(mut self, api_key: impl Into<String>, policy: CallPolicy) -> Self {
+        self.policies.insert(api_key.into(), policy);
+        self
+    }
+}
+
+impl CallPolicyProvider for ApiKeyCallPolicies {
+    fn call_policy(&self, extensions: &Extensions) -> CallPolicy {
+        extensions
+            .get::<RpcApiKey>()
+            .and_then(|RpcApiKey(api_key)| self.policies.get(api_key))
+            .copied()
+            .unwrap_or(self.default)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_policy_by_api_key() {
+        let public = CallPolicy::default().with_gas_cap(10_000_000);
+        let internal = CallPolicy::default().with_gas_cap(100_000_000);
+        let policies = ApiKeyCallPolicies::new(public).with_policy("internal", internal);
+
+        let mut extensions = Extensions::new();
+        assert_eq!(policies.call_policy(&extensions), public);
+
+        extensions.insert(RpcApiKey("unknown".to_string()));
+        assert_eq!(policies.call_policy(&extensions), public);
+
+        extensions.insert(RpcApiKey("internal".to_string()));
+        assert_eq!(policies.call_policy(&extensions), internal);
+    }
+}
@@ -0,0 +1,332 @@
+//! [`jsonrpsee`] helper layers for tagging log queries with the canonical epoch they were served
+//! from.
+//!
+//! The canonical epoch is bumped on every reorg, see [`CanonStateSubscriptions::canonical_epoch`].
+//! The [`CanonicalEpochLayer`] attaches the epoch to the responses of log queries as a
+//! [`CanonicalEpoch`] response extension. Over HTTP, the [`CanonicalEpochHeaderLayer`] exposes it
+//! in the [`CANONICAL_EPOCH_HEADER`] of the response, so clients that page through logs can detect
+//! that two pages were served from different canonical chains.
+
+use futures::future::Either;
+use http::{HeaderValue, Request as HttpRequest, Response as HttpResponse};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::Request,
+    MethodResponse,
+};
+use reth_chain_state::CanonStateSubscriptions;
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// The HTTP response header that carries the canonical epoch of the log queries of a request.
+pub const CANONICAL_EPOCH_HEADER: &str = "reth-canonical-epoch";
+
+/// The methods whose responses are tagged with the canonical epoch.
+const LOG_QUERY_METHODS: [&str; 3] = ["eth_getLogs", "eth_getFilterLogs", "eth_getFilterChanges"];
+
+/// The canonical epoch a log query was served from, attached to the response extensions by the
+/// [`CanonicalEpochLayer`].
+///
+/// This is the epoch at the time the query started, so a query that was served across a reorg is
+/// tagged with the epoch before the reorg.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalEpoch(pub u64);
+
+/// Reads the current canonical epoch of the node.
+#[derive(Clone)]
+pub struct CanonicalEpochTracker {
+    canonical_epoch: Arc<dyn Fn() -> Option<u64> + Send + Sync>,
+}
+
+impl CanonicalEpochTracker {
+    /// Creates a new tracker that reads the canonical epoch of the given provider.
+    pub fn new<Provider>(provider: Provider) -> Self
+    where
+        Provider: CanonStateSubscriptions + 'static,
+    {
+        Self { canonical_epoch: Arc::new(move || provider.canonical_epoch()) }
+    }
+
+    /// Returns the current canonical epoch, if the provider tracks it.
+    pub fn canonical_epoch(&self) -> Option<u64> {
+        (self.canonical_epoch)()
+    }
+
+    /// Returns the HTTP middleware layer that exposes the canonical epoch of log queries in the
+    /// [`CANONICAL_EPOCH_HEADER`].
+    pub const fn header_layer(&self) -> CanonicalEpochHeaderLayer {
+        CanonicalEpochHeaderLayer
+    }
+}
+
+impl fmt::Debug for CanonicalEpochTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CanonicalEpochTracker").finish_non_exhaustive()
+    }
+}
+
+/// RPC middleware layer that tags the responses of log queries with the [`CanonicalEpoch`] read
+/// from the configured [`CanonicalEpochTracker`], if any.
+#[derive(Debug, Clone, Default)]
+pub struct CanonicalEpochLayer {
+    /// The tracker to read the canonical epoch from.
+    tracker: Option<CanonicalEpochTracker>,
+}
+
+impl CanonicalEpochLayer {
+    /// Creates a new layer that reads the canonical epoch from the given tracker, if any.
+    pub const fn new(tracker: Option<CanonicalEpochTracker>) -> Self {
+        Self { tracker }
+    }
+}
+
+impl<S> Layer<S> for CanonicalEpochLayer {
+    type Service = CanonicalEpochService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CanonicalEpochService { tracker: self.tracker.clone(), inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that tags the responses of log queries with their
+/// [`CanonicalEpoch`].
+#[derive(Debug, Clone)]
+pub struct CanonicalEpochService<S> {
+    /// The tracker to read the canonical epoch from.
+    tracker: Option<CanonicalEpochTracker>,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> CanonicalEpochService<S> {
+    /// Returns the current canonical epoch if the method is a log query.
+    fn canonical_epoch_of(&self, method: &str) -> Option<CanonicalEpoch> {
+        if !LOG_QUERY_METHODS.contains(&method) {
+            return None
+        }
+        self.tracker.as_ref()?.canonical_epoch().map(CanonicalEpoch)
+    }
+}
+
+impl<S> RpcServiceT for CanonicalEpochService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse, BatchResponse = MethodResponse>
+        + Send
+        + Sync
+        + Clone
+        + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = S::MethodResponse> + Send + 'a {
+        let Some(canonical_epoch) = self.canonical_epoch_of(req.method_name()) else {
+            return Either::Left(self.inner.call(req))
+        };
+        let slot = req.extensions.get::<CanonicalEpochSlot>().cloned();
+        Either::Right(tag_response(canonical_epoch, slot, self.inner.call(req)))
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        // all log queries of the batch start in the same epoch
+        let tag = req.iter().find_map(|entry| match entry {
+            Ok(BatchEntry::Call(call)) => self
+                .canonical_epoch_of(call.method_name())
+                .map(|epoch| (epoch, call.extensions.get::<CanonicalEpochSlot>().cloned())),
+            _ => None,
+        });
+        let Some((canonical_epoch, slot)) = tag else { return Either::Left(self.inner.batch(req)) };
+        Either::Right(tag_response(canonical_epoch, slot, self.inner.batch(req)))
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Awaits the response and tags it with the canonical epoch, which is also recorded in the slot of
+/// the HTTP request, if any.
+async fn tag_response<F>(
+    canonical_epoch: CanonicalEpoch,
+    slot: Option<CanonicalEpochSlot>,
+    fut: F,
+) -> MethodResponse
+where
+    F: Future<Output = MethodResponse>,
+{
+    let mut resp = fut.await;
+    if let Some(slot) = slot {
+        slot.record(canonical_epoch);
+    }
+    resp.extensions_mut().insert(canonical_epoch);
+    resp
+}
+
+/// Collects the canonical epoch of the log queries of an HTTP request, attached to the request
+/// extensions by the [`CanonicalEpochHeaderLayer`].
+#[derive(Debug, Clone, Default)]
+pub struct CanonicalEpochSlot(Arc<Mutex<Option<CanonicalEpoch>>>);
+
+impl CanonicalEpochSlot {
+    /// Records the canonical epoch of a log query, keeping the earliest epoch of all queries.
+    fn record(&self, canonical_epoch: CanonicalEpoch) {
+        let mut slot = self.0.lock().unwrap();
+        *slot = Some(slot.map_or(canonical_epoch, |epoch| epoch.min(canonical_epoch)));
+    }
+
+    /// Returns the recorded canonical epoch, if any log query was served.
+    fn get(&self) -> Option<CanonicalEpoch> {
+        *self.0.lock().unwrap()
+    }
+}
+
+/// An HTTP middleware layer that exposes the [`CanonicalEpoch`] of the log queries of a request in
+/// the [`CANONICAL_EPOCH_HEADER`] of the response.
+///
+/// If a batch request served log queries in different epochs, the earliest epoch is exposed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CanonicalEpochHeaderLayer;
+
+impl<S> Layer<S> for CanonicalEpochHeaderLayer {
+    type Service = CanonicalEpochHeaderService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CanonicalEpochHeaderService { inner }
+    }
+}
+
+/// The HTTP service of the [`CanonicalEpochHeaderLayer`].
+#[derive(Debug, Clone)]
+pub struct CanonicalEpochHeaderService<S> {
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S, B, ResBody> Service<HttpRequest<B>> for CanonicalEpochHeaderService<S>
+where
+    S: Service<HttpRequest<B>, Response = HttpResponse<ResBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest<B>) -> Self::Future {
+        let slot = CanonicalEpochSlot::default();
+        req.extensions_mut().insert(slot.clone());
+        let fut = self.inner.call(req);
+
+        Box::pin(async move {
+            let mut resp = fut.await?;
+            if let Some(CanonicalEpoch(epoch)) = slot.get() {
+                resp.headers_mut().insert(CANONICAL_EPOCH_HEADER, HeaderValue::from(epoch));
+            }
+            Ok(resp)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::{Id, ResponsePayload};
+    use reth_chain_state::test_utils::TestCanonStateSubscriptions;
+    use reth_provider::Chain;
+    use std::{convert::Infallible, future::ready};
+
+    #[derive(Clone)]
+    struct Echo;
+
+    impl RpcServiceT for Echo {
+        type MethodResponse = MethodResponse;
+        type NotificationResponse = MethodResponse;
+        type BatchResponse = MethodResponse;
+
+        fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = MethodResponse> + Send + 'a {
+            ready(MethodResponse::response(
+                req.id,
+                ResponsePayload::success("[]").into(),
+                usize::MAX,
+            ))
+        }
+
+        fn batch<'a>(&self, _: Batch<'a>) -> impl Future<Output = MethodResponse> + Send + 'a {
+            ready(MethodResponse::response(
+                Id::Null,
+                ResponsePayload::success("[]").into(),
+                usize::MAX,
+            ))
+        }
+
+        fn notification<'a>(
+            &self,
+            _: Notification<'a>,
+        ) -> impl Future<Output = MethodResponse> + Send + 'a {
+            ready(MethodResponse::notification())
+        }
+    }
+
+    fn request<'a>(method: &'a str, slot: &CanonicalEpochSlot) -> Request<'a> {
+        let mut req = Request::borrowed(method, None, Id::Number(1));
+        req.extensions_mut().insert(slot.clone());
+        req
+    }
+
+    #[tokio::test]
+    async fn tags_log_queries_with_canonical_epoch() {
+        let provider = TestCanonStateSubscriptions::default();
+        let service = CanonicalEpochLayer::new(Some(CanonicalEpochTracker::new(provider.clone())))
+            .layer(Echo);
+
+        let slot = CanonicalEpochSlot::default();
+        let resp = service.call(request("eth_blockNumber", &slot)).await;
+        assert_eq!(resp.extensions().get::<CanonicalEpoch>(), None);
+        assert_eq!(slot.get(), None);
+
+        let resp = service.call(request("eth_getLogs", &slot)).await;
+        assert_eq!(resp.extensions().get::<CanonicalEpoch>(), Some(&CanonicalEpoch(0)));
+        assert_eq!(slot.get(), Some(CanonicalEpoch(0)));
+
+        // the slot keeps the earliest epoch, the response is tagged with the current one
+        provider.add_next_reorg(Arc::new(Chain::default()), Arc::new(Chain::default()));
+        let resp = service.call(request("eth_getFilterLogs", &slot)).await;
+        assert_eq!(resp.extensions().get::<CanonicalEpoch>(), Some(&CanonicalEpoch(1)));
+        assert_eq!(slot.get(), Some(CanonicalEpoch(0)));
+    }
+
+    #[tokio::test]
+    async fn exposes_canonical_epoch_in_header() {
+        let service = |epoch: Option<u64>| {
+            CanonicalEpochHeaderLayer.layer(tower::service_fn(move |req: HttpRequest<()>| {
+                if let Some(epoch) = epoch {
+                    req.extensions()
+                        .get::<CanonicalEpochSlot>()
+                        .unwrap()
+                        .record(CanonicalEpoch(epoch));
+                }
+                ready(Ok::<_, Infallible>(HttpResponse::new(())))
+            }))
+        };
+
+        let resp = service(Some(3)).call(HttpRequest::new(())).await.unwrap();
+        assert_eq!(resp.headers()[CANONICAL_EPOCH_HEADER], "3");
+
+        let resp = service(None).call(HttpRequest::new(())).await.unwrap();
+        assert!(!resp.headers().contains_key(CANONICAL_EPOCH_HEADER));
+    }
+}
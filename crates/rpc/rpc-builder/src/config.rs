@@ -1,7 +1,13 @@
-use jsonrpsee::server::ServerConfigBuilder;
+use http::HeaderName;
+use jsonrpsee::server::{PingConfig, ServerConfigBuilder};
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
-use reth_rpc::ValidationApiConfig;
+use reth_rpc::{
+    eth::{BundleForwarderConfig, TxForwardMode, TxForwarderConfig},
+    ArchiveStateConfig, ValidationApiConfig,
+};
 use reth_rpc_eth_types::{EthConfig, EthStateCacheConfig, GasPriceOracleConfig};
+#[cfg(feature = "graphql")]
+use reth_rpc_graphql::GraphQlConfig;
 use reth_rpc_layer::{JwtError, JwtSecret};
 use reth_rpc_server_types::RpcModuleSelection;
 use std::{net::SocketAddr, path::PathBuf};
@@ -9,8 +15,14 @@ use tower::layer::util::Identity;
 use tracing::{debug, warn};
 
 use crate::{
-    auth::AuthServerConfig, error::RpcError, IpcServerBuilder, RpcModuleConfig, RpcServerConfig,
-    TransportRpcModuleConfig,
+    access_log::{RpcAccessLog, RpcAccessLogConfig, RpcAccessLogOutput},
+    auth::AuthServerConfig,
+    error::RpcError,
+    rate_limiter::RpcRateLimits,
+    response_cache::RpcResponseCache,
+    roles::RpcRoles,
+    usage::RpcUsageTracker,
+    IpcServerBuilder, RpcModuleConfig, RpcServerConfig, TransportRpcModuleConfig,
 };
 
 /// A trait that provides a configured RPC server.
@@ -51,6 +63,9 @@ pub trait RethRpcServerConfig {
     /// Returns the default server config for http/ws
     fn http_ws_server_builder(&self) -> ServerConfigBuilder;
 
+    /// Returns the keepalive config of `WebSocket` connections, if pings are enabled.
+    fn ws_ping_config(&self) -> Option<PingConfig>;
+
     /// Returns the default ipc server builder
     fn ipc_server_builder(&self) -> IpcServerBuilder<Identity, Identity>;
 
@@ -79,6 +94,37 @@ pub trait RethRpcServerConfig {
     ///
     /// Note: this is not used for the auth server (engine API).
     fn rpc_secret_key(&self) -> Option<JwtSecret>;
+
+    /// Creates the [`RpcUsageTracker`] from cli args, if RPC usage accounting is enabled.
+    fn rpc_usage_tracker(&self) -> Option<RpcUsageTracker>;
+
+    /// Creates the [`GraphQlConfig`] from cli args, if the GraphQL API is enabled.
+    #[cfg(feature = "graphql")]
+    fn graphql_config(&self) -> Option<GraphQlConfig>;
+
+    /// Creates the [`BundleForwarderConfig`] from cli args, if any bundle endpoints are
+    /// configured.
+    fn bundle_forwarder_config(&self) -> Result<Option<BundleForwarderConfig>, RpcError>;
+
+    /// Creates the [`TxForwarderConfig`] from cli args, if an upstream endpoint for raw
+    /// transactions is configured.
+    fn tx_forwarder_config(&self) -> Option<TxForwarderConfig>;
+
+    /// Creates the [`ArchiveStateConfig`] from cli args, if an archive node is configured that
+    /// pruned state is fetched from.
+    fn archive_state_config(&self) -> Option<ArchiveStateConfig>;
+
+    /// Creates the [`RpcRateLimits`] from cli args, if any method is rate limited.
+    fn rpc_rate_limits(&self) -> Result<Option<RpcRateLimits>, RpcError>;
+
+    /// Creates the [`RpcAccessLog`] from cli args, if the access log is enabled.
+    fn rpc_access_log(&self) -> Result<Option<RpcAccessLog>, RpcError>;
+
+    /// Creates the [`RpcRoles`] from cli args, if role based method allowlists are configured.
+    fn rpc_roles(&self) -> Result<Option<RpcRoles>, RpcError>;
+
+    /// Creates the [`RpcResponseCache`] from cli args, if response caching is enabled.
+    fn rpc_response_cache(&self) -> Option<RpcResponseCache>;
 }
 
 impl RethRpcServerConfig for RpcServerArgs {
@@ -97,12 +143,16 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_trace_filter_blocks(self.rpc_max_trace_filter_blocks)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
+            .max_logs_per_stream(self.rpc_max_logs_per_stream.unwrap_or_max() as usize)
             .eth_proof_window(self.rpc_eth_proof_window)
             .rpc_gas_cap(self.rpc_gas_cap)
             .rpc_max_simulate_blocks(self.rpc_max_simulate_blocks)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
             .proof_permits(self.rpc_proof_permits)
+            .proof_cache_max_entries(self.rpc_proof_cache_entries)
+            .subscription_buffer_size(self.rpc_subscription_buffer_size)
+            .subscription_overflow_policy(self.rpc_subscription_overflow)
     }
 
     fn flashbots_config(&self) -> ValidationApiConfig {
@@ -118,6 +168,7 @@ impl RethRpcServerConfig for RpcServerArgs {
             max_receipts: self.rpc_state_cache.max_receipts,
             max_headers: self.rpc_state_cache.max_headers,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
+            max_state_snapshots: self.rpc_state_cache.max_state_snapshots,
         }
     }
 
@@ -161,23 +212,46 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn http_ws_server_builder(&self) -> ServerConfigBuilder {
-        ServerConfigBuilder::new()
+        let builder = ServerConfigBuilder::new()
             .max_connections(self.rpc_max_connections.get())
             .max_request_body_size(self.rpc_max_request_size_bytes())
             .max_response_body_size(self.rpc_max_response_size_bytes())
-            .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection.get())
+            .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection.get());
+
+        // the http server also serves ws connections if both run on the same port
+        match self.ws_ping_config() {
+            Some(ping_config) => builder.enable_ws_ping(ping_config),
+            None => builder,
+        }
+    }
+
+    fn ws_ping_config(&self) -> Option<PingConfig> {
+        if self.ws_ping_interval.is_zero() {
+            return None
+        }
+
+        Some(
+            PingConfig::new()
+                .ping_interval(self.ws_ping_interval)
+                .inactive_limit(self.ws_idle_timeout)
+                .max_failures(self.ws_max_missed_pings),
+        )
     }
 
     fn ipc_server_builder(&self) -> IpcServerBuilder<Identity, Identity> {
+        let max_request_size = self.ipc_max_request_size.unwrap_or(self.rpc_max_request_size);
+        let max_response_size = self.ipc_max_response_size.unwrap_or(self.rpc_max_response_size);
         IpcServerBuilder::default()
             .max_subscriptions_per_connection(self.rpc_max_subscriptions_per_connection.get())
-            .max_request_body_size(self.rpc_max_request_size_bytes())
-            .max_response_body_size(self.rpc_max_response_size_bytes())
+            .max_request_body_size(max_request_size.get().saturating_mul(1024 * 1024))
+            .max_response_body_size(max_response_size.get().saturating_mul(1024 * 1024))
             .max_connections(self.rpc_max_connections.get())
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_slow_query_threshold(self.rpc_slow_query_threshold);
 
         if self.http_api.is_some() && !self.http {
             warn!(
@@ -193,6 +267,7 @@ impl RethRpcServerConfig for RpcServerArgs {
                 .with_http(self.http_ws_server_builder())
                 .with_http_cors(self.http_corsdomain.clone())
                 .with_http_disable_compression(self.http_disable_compression)
+                .with_http_compression_min_size(self.http_compression_min_size)
                 .with_ws_cors(self.ws_allowed_origins.clone());
         }
 
@@ -212,7 +287,17 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
-        let mut builder = AuthServerConfig::builder(jwt_secret).socket_addr(address);
+        if self.auth_disable_tcp && !self.auth_ipc && self.auth_unix_socket.is_none() {
+            return Err(RpcError::Custom(
+                "--authrpc.disable-tcp requires --authrpc.unix-socket or --auth-ipc".to_string(),
+            ))
+        }
+
+        let mut builder = AuthServerConfig::builder(jwt_secret)
+            .socket_addr(address)
+            .with_tcp(!self.auth_disable_tcp)
+            .maybe_unix_socket(self.auth_unix_socket.clone())
+            .with_http2(self.auth_http2);
         if self.auth_ipc {
             builder = builder
                 .ipc_endpoint(self.auth_ipc_path.clone())
@@ -234,17 +319,131 @@ impl RethRpcServerConfig for RpcServerArgs {
     fn rpc_secret_key(&self) -> Option<JwtSecret> {
         self.rpc_jwtsecret
     }
+
+    fn rpc_usage_tracker(&self) -> Option<RpcUsageTracker> {
+        if !self.rpc_usage_accounting {
+            return None
+        }
+
+        match HeaderName::try_from(self.rpc_usage_api_key_header.as_str()) {
+            Ok(header) => Some(RpcUsageTracker::new(header)),
+            Err(err) => {
+                warn!(
+                    target: "reth::cli",
+                    header = %self.rpc_usage_api_key_header,
+                    %err,
+                    "Invalid --rpc.usage-api-key-header, falling back to the default header"
+                );
+                Some(RpcUsageTracker::default())
+            }
+        }
+    }
+
+    #[cfg(feature = "graphql")]
+    fn graphql_config(&self) -> Option<GraphQlConfig> {
+        self.graphql.then(|| GraphQlConfig {
+            max_complexity: self.graphql_max_complexity,
+            max_depth: self.graphql_max_depth,
+            max_block_range: self.rpc_max_blocks_per_filter.0,
+        })
+    }
+
+    fn bundle_forwarder_config(&self) -> Result<Option<BundleForwarderConfig>, RpcError> {
+        if self.rpc_bundle_forward.is_empty() {
+            return Ok(None)
+        }
+
+        let mut config = BundleForwarderConfig::new(self.rpc_bundle_forward.clone())
+            .with_max_retries(self.rpc_bundle_forward_retries);
+        if let Some(path) = &self.rpc_bundle_signing_key {
+            config = config.with_signing_key_file(path).map_err(|err| {
+                RpcError::Custom(format!(
+                    "Failed to read bundle signing key {}: {err}",
+                    path.display()
+                ))
+            })?;
+        }
+        Ok(Some(config))
+    }
+
+    fn tx_forwarder_config(&self) -> Option<TxForwarderConfig> {
+        let mode = if self.rpc_tx_forward_only {
+            TxForwardMode::Exclusive
+        } else {
+            TxForwardMode::Additional
+        };
+        self.rpc_tx_forward.as_ref().map(|endpoint| {
+            TxForwarderConfig::new(endpoint.clone())
+                .with_mode(mode)
+                .with_max_retries(self.rpc_tx_forward_retries)
+        })
+    }
+
+    fn archive_state_config(&self) -> Option<ArchiveStateConfig> {
+        self.rpc_archive_fallback.as_ref().map(|endpoint| {
+            ArchiveStateConfig::new(endpoint.clone())
+                .with_max_cached_blocks(self.rpc_archive_fallback_cache)
+        })
+    }
+
+    fn rpc_rate_limits(&self) -> Result<Option<RpcRateLimits>, RpcError> {
+        let Some(config) =
+            self.rate_limit_config().map_err(|err| RpcError::Custom(err.to_string()))?
+        else {
+            return Ok(None)
+        };
+        let limits = RpcRateLimits::new(config).map_err(|err| {
+            RpcError::Custom(format!("Invalid rate limit client IP header: {err}"))
+        })?;
+        Ok(Some(limits))
+    }
+
+    fn rpc_access_log(&self) -> Result<Option<RpcAccessLog>, RpcError> {
+        let output = match &self.rpc_access_log_file {
+            Some(path) => RpcAccessLogOutput::File(path.clone()),
+            None if self.rpc_access_log => RpcAccessLogOutput::Tracing,
+            None => return Ok(None),
+        };
+        let config = RpcAccessLogConfig::default()
+            .with_output(output)
+            .with_sample(self.rpc_access_log_sample)
+            .with_client_ip_header(self.rpc_access_log_ip_header.clone())
+            .with_trusted_proxies(self.rpc_trusted_proxies.clone());
+        let access_log =
+            RpcAccessLog::new(config).map_err(|err| RpcError::Custom(err.to_string()))?;
+        Ok(Some(access_log))
+    }
+
+    fn rpc_roles(&self) -> Result<Option<RpcRoles>, RpcError> {
+        let Some(config) = self.roles_config().map_err(|err| RpcError::Custom(err.to_string()))?
+        else {
+            return Ok(None)
+        };
+        let secret = self.rpc_secret_key().ok_or_else(|| {
+            RpcError::Custom("The RPC roles require a JWT secret, see --rpc.jwtsecret".to_string())
+        })?;
+        Ok(Some(RpcRoles::new(config, &secret)))
+    }
+
+    fn rpc_response_cache(&self) -> Option<RpcResponseCache> {
+        self.rpc_response_cache.then(|| RpcResponseCache::new(self.rpc_response_cache_size))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use clap::{Args, Parser};
     use reth_node_core::args::RpcServerArgs;
-    use reth_rpc_eth_types::RPC_DEFAULT_GAS_CAP;
+    use reth_rpc_eth_types::{SubscriptionOverflowPolicy, RPC_DEFAULT_GAS_CAP};
+    use reth_rpc_layer::JwtSecret;
     use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
-    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+    use std::{
+        net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4},
+        path::Path,
+    };
 
     use crate::config::RethRpcServerConfig;
+    use reth_rpc::eth::TxForwardMode;
 
     /// A helper type to parse Args more easily
     #[derive(Parser)]
@@ -268,6 +467,20 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_ws_ping_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.ws_ping_config().is_some());
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--ws.ping-interval", "0s"]).args;
+        assert!(args.ws_ping_config().is_none());
+
+        let args =
+            CommandParser::<RpcServerArgs>::try_parse_from(["reth", "--ws.max-missed-pings", "0"]);
+        assert!(args.is_err());
+    }
+
     #[test]
     fn test_transport_rpc_module_config() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
@@ -378,11 +591,183 @@ mod tests {
             "100",
             "--rpc-max-logs-per-response",
             "200",
+            "--rpc.max-logs-per-stream",
+            "300",
         ])
         .args;
 
         let config = args.eth_config().filter_config();
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
+        assert_eq!(config.max_logs_per_stream, Some(300));
+    }
+
+    #[test]
+    fn test_subscription_buffer() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        let config = args.eth_config().pubsub_config();
+        assert_eq!(config.buffer_size, constants::DEFAULT_SUBSCRIPTION_BUFFER_SIZE);
+        assert_eq!(config.overflow_policy, SubscriptionOverflowPolicy::DropOldest);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.subscription-buffer-size",
+            "16",
+            "--rpc.subscription-overflow",
+            "disconnect",
+        ])
+        .args;
+        let config = args.eth_config().pubsub_config();
+        assert_eq!(config.buffer_size, 16);
+        assert_eq!(config.overflow_policy, SubscriptionOverflowPolicy::Disconnect);
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.subscription-overflow",
+            "block",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_tx_forwarder_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.tx_forwarder_config().is_none());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.tx-forward",
+            "http://localhost:8545",
+            "--rpc.tx-forward-only",
+            "--rpc.tx-forward-retries",
+            "5",
+        ])
+        .args;
+        let config = args.tx_forwarder_config().unwrap();
+        assert_eq!(config.endpoint, "http://localhost:8545");
+        assert_eq!(config.mode, TxForwardMode::Exclusive);
+        assert_eq!(config.max_retries, 5);
+
+        let args =
+            CommandParser::<RpcServerArgs>::try_parse_from(["reth", "--rpc.tx-forward-only"]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_archive_state_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.archive_state_config().is_none());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.archive-fallback",
+            "http://archive:8545",
+            "--rpc.archive-fallback-cache",
+            "8",
+        ])
+        .args;
+        let config = args.archive_state_config().unwrap();
+        assert_eq!(config.endpoint, "http://archive:8545");
+        assert_eq!(config.max_cached_blocks, 8);
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.archive-fallback-cache",
+            "8",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_rpc_rate_limits() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rpc_rate_limits().unwrap().is_none());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.rate-limit-per-ip",
+            "trace_*=5",
+            "--rpc.rate-limit-ip-header",
+            "x-real-ip",
+            "--rpc.trusted-proxies",
+            "127.0.0.1",
+        ])
+        .args;
+        let limits = args.rpc_rate_limits().unwrap().unwrap();
+        assert_eq!(limits.config().methods.len(), 1);
+        let client_ip_layer = limits.client_ip_layer();
+        assert_eq!(client_ip_layer.header().map(|header| header.as_str()), Some("x-real-ip"));
+        assert_eq!(client_ip_layer.trusted_proxies(), [IpAddr::from([127, 0, 0, 1])]);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.rate-limit",
+            "trace_*=5",
+            "--rpc.rate-limit-ip-header",
+            "invalid header",
+        ])
+        .args;
+        assert!(args.rpc_rate_limits().is_err());
+    }
+
+    #[test]
+    fn test_rpc_access_log() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rpc_access_log().unwrap().is_none());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.access-log",
+            "--rpc.access-log-sample",
+            "10",
+            "--rpc.access-log-ip-header",
+            "x-forwarded-for",
+            "--rpc.trusted-proxies",
+            "127.0.0.1",
+        ])
+        .args;
+        let access_log = args.rpc_access_log().unwrap().unwrap();
+        let client_ip_layer = access_log.client_ip_layer();
+        assert_eq!(client_ip_layer.header().map(|header| header.as_str()), Some("x-forwarded-for"));
+        assert_eq!(client_ip_layer.trusted_proxies(), [IpAddr::from([127, 0, 0, 1])]);
+
+        let path = std::env::temp_dir().join(format!("reth-access-log-{}", std::process::id()));
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.access-log-file",
+            path.to_str().unwrap(),
+        ])
+        .args;
+        assert!(args.rpc_access_log().unwrap().is_some());
+        assert!(path.exists());
+        std::fs::remove_file(path).unwrap();
+
+        assert!(CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.access-log-sample",
+            "0"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_auth_server_config() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--authrpc.unix-socket",
+            "/tmp/reth-engine.sock",
+            "--authrpc.disable-tcp",
+            "--authrpc.http2",
+        ])
+        .args;
+        let config = args.auth_server_config(JwtSecret::random()).unwrap();
+        assert_eq!(config.unix_socket(), Some(Path::new("/tmp/reth-engine.sock")));
+        assert!(!config.tcp);
+        assert!(config.http2);
+
+        // the engine API must be served somewhere
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--authrpc.disable-tcp"]).args;
+        assert!(args.auth_server_config(JwtSecret::random()).is_err());
     }
 }
@@ -1,4 +1,11 @@
-use http::{HeaderValue, Method};
+use http::{HeaderValue, Method, Request, Response};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 
 /// Error thrown when parsing cors domains went wrong
@@ -51,3 +58,101 @@ pub(crate) fn create_cors_layer(http_cors_domains: &str) -> Result<CorsLayer, Co
     };
     Ok(cors)
 }
+
+/// A CORS layer whose allowed domains can be replaced while the server is running.
+///
+/// Requests pass through unchanged while no domains are configured. Clones share the same domains.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ReloadableCorsLayer {
+    cors: Arc<RwLock<Option<CorsLayer>>>,
+}
+
+impl ReloadableCorsLayer {
+    /// Creates a new layer that allows the given domains, if any.
+    pub(crate) fn new(domains: Option<&str>) -> Result<Self, CorsDomainError> {
+        let layer = Self::default();
+        layer.set_domains(domains)?;
+        Ok(layer)
+    }
+
+    /// Replaces the allowed domains, requests pass through unchanged if `None`.
+    pub(crate) fn set_domains(&self, domains: Option<&str>) -> Result<(), CorsDomainError> {
+        let cors = domains.map(create_cors_layer).transpose()?;
+        *self.cors.write().unwrap() = cors;
+        Ok(())
+    }
+}
+
+impl<S> Layer<S> for ReloadableCorsLayer {
+    type Service = ReloadableCors<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ReloadableCors { cors: self.cors.clone(), inner }
+    }
+}
+
+/// The HTTP service of the [`ReloadableCorsLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct ReloadableCors<S> {
+    /// The current CORS layer, if any.
+    cors: Arc<RwLock<Option<CorsLayer>>>,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for ReloadableCors<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let cors = self.cors.read().unwrap().clone();
+        match cors {
+            Some(cors) => {
+                // the inner service was polled ready, so it's the one that handles the request
+                let clone = self.inner.clone();
+                let inner = std::mem::replace(&mut self.inner, clone);
+                Box::pin(cors.layer(inner).call(req))
+            }
+            None => Box::pin(self.inner.call(req)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header;
+    use std::convert::Infallible;
+
+    #[tokio::test]
+    async fn reloads_allowed_domains() {
+        let layer = ReloadableCorsLayer::new(None).unwrap();
+        let mut service = layer.layer(tower::service_fn(|_: Request<()>| async {
+            Ok::<_, Infallible>(Response::new(String::new()))
+        }));
+        let request =
+            || Request::builder().header(header::ORIGIN, "https://example.com").body(()).unwrap();
+
+        let response = service.call(request()).await.unwrap();
+        assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        layer.set_domains(Some("https://example.com")).unwrap();
+        let response = service.call(request()).await.unwrap();
+        assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "https://example.com");
+
+        assert!(layer.set_domains(Some("https://example.com,*")).is_err());
+        layer.set_domains(None).unwrap();
+        let response = service.call(request()).await.unwrap();
+        assert!(!response.headers().contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+    }
+}
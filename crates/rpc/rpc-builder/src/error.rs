@@ -4,6 +4,7 @@ use std::{
     collections::HashSet,
     io::{self, ErrorKind},
     net::SocketAddr,
+    path::PathBuf,
 };
 
 /// Rpc server kind.
@@ -70,6 +71,14 @@ pub enum RpcError {
     /// Thrown when IPC server fails to start.
     #[error(transparent)]
     IpcServerError(#[from] IpcServerStartError),
+    /// Thrown when the unix socket of the auth server can't be bound.
+    #[error("Failed to bind auth server to unix socket {}: {error}", path.display())]
+    AuthUnixSocket {
+        /// Path of the socket.
+        path: PathBuf,
+        /// IO error.
+        error: io::Error,
+    },
     /// Custom error.
     #[error("{0}")]
     Custom(String),
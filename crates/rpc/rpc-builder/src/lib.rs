@@ -32,7 +32,7 @@ use jsonrpsee::{
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_evm::ConfigureEvm;
-use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
+use reth_network_api::{events::NetworkPeersEvents, noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc::{
     AdminApi, DebugApi, EngineEthApi, EthApi, EthApiBuilder, EthBundle, MinerApi, NetApi,
@@ -41,14 +41,16 @@ use reth_rpc::{
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
     helpers::{Call, EthApiSpec, EthTransactions, LoadPendingBlock, TraceExt},
-    EthApiServer, EthApiTypes, FullEthApiServer, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
-    RpcTxReq,
+    EthApiServer, EthApiTypes, EthBlockSidecarsApiExtServer, EthReceiptsRangeApiExtServer,
+    FullEthApiServer, RpcBlock, RpcHeader, RpcNodeCore, RpcReceipt, RpcTransaction, RpcTxReq,
 };
 use reth_rpc_eth_types::{EthConfig, EthSubscriptionIdProvider};
+#[cfg(feature = "graphql")]
+use reth_rpc_graphql::GraphQlLayer;
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 use reth_storage_api::{
     AccountReader, BlockReader, BlockReaderIdExt, ChangeSetReader, FullRpcProvider, ProviderBlock,
-    StateProviderFactory,
+    StateProviderFactory, StorageChangeSetReader,
 };
 use reth_tasks::{pool::BlockingTaskGuard, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::{noop::NoopTransactionPool, TransactionPool};
@@ -59,9 +61,10 @@ use std::{
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use tower_http::cors::CorsLayer;
+use tokio::net::TcpListener;
 
 pub use cors::CorsDomainError;
+use cors::ReloadableCorsLayer;
 
 // re-export for convenience
 pub use jsonrpsee::server::ServerBuilder;
@@ -101,6 +104,42 @@ use reth_rpc::eth::sim_bundle::EthSimBundle;
 // Rpc rate limiter
 pub mod rate_limiter;
 
+// Peer addresses of Rpc connections
+pub mod peer_addr;
+
+// Rpc usage accounting
+pub mod usage;
+
+// Rpc slow query log
+pub mod slow_query;
+
+// Rpc call policies
+pub mod call_policy;
+
+// Rpc access log
+pub mod access_log;
+
+// Rpc role based method allowlists
+pub mod roles;
+
+// Runtime reconfiguration of the servers
+pub mod reload;
+
+// Rpc response caching
+pub mod response_cache;
+
+// Canonical epoch of log queries
+pub mod canonical_epoch;
+use access_log::{RpcAccessLog, RpcAccessLogLayer};
+use canonical_epoch::{CanonicalEpochLayer, CanonicalEpochTracker};
+use peer_addr::start_with_peer_addrs;
+use rate_limiter::{RpcRateLimitLayer, RpcRateLimits};
+use reload::RpcServerReloadHandle;
+use response_cache::{RpcResponseCache, RpcResponseCacheLayer};
+use roles::{RpcRoleLayer, RpcRoles};
+use slow_query::SlowQueryLayer;
+use usage::{RpcUsageLayer, RpcUsageTracker};
+
 /// A builder type to configure the RPC module: See [`RpcModule`]
 ///
 /// This is the main entrypoint and the easiest way to configure an RPC server.
@@ -293,9 +332,10 @@ where
     Provider: FullRpcProvider<Block = N::Block, Receipt = N::Receipt, Header = N::BlockHeader>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StorageChangeSetReader,
     Pool: TransactionPool + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + NetworkPeersEvents + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
     Consensus: FullConsensus<N, Error = ConsensusError> + Clone + 'static,
 {
@@ -605,7 +645,7 @@ where
     /// Instantiates `AdminApi`
     pub fn admin_api(&self) -> AdminApi<Network, Provider::ChainSpec>
     where
-        Network: Peers,
+        Network: Peers + NetworkPeersEvents,
     {
         AdminApi::new(self.network.clone(), self.provider.chain_spec())
     }
@@ -618,7 +658,7 @@ where
     /// Register Admin Namespace
     pub fn register_admin(&mut self) -> &mut Self
     where
-        Network: Peers,
+        Network: Peers + NetworkPeersEvents,
     {
         let adminapi = self.admin_api();
         self.modules.insert(RethRpcModule::Admin, adminapi.into_rpc().into());
@@ -644,8 +684,9 @@ where
             Transaction = N::SignedTx,
         > + AccountReader
         + ChangeSetReader
+        + StorageChangeSetReader
         + CanonStateSubscriptions,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + NetworkPeersEvents + Clone + 'static,
     EthApi: EthApiServer<
             RpcTxReq<EthApi::NetworkTypes>,
             RpcTransaction<EthApi::NetworkTypes>,
@@ -673,7 +714,7 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn register_ots(&mut self) -> &mut Self
     where
-        EthApi: TraceExt + EthTransactions,
+        EthApi: TraceExt + EthTransactions + RpcNodeCore<Provider: ChangeSetReader>,
     {
         let otterscan_api = self.otterscan_api();
         self.modules.insert(RethRpcModule::Ots, otterscan_api.into_rpc().into());
@@ -759,8 +800,9 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
-    Network: NetworkInfo + Peers + Clone + 'static,
+        + ChangeSetReader
+        + StorageChangeSetReader,
+    Network: NetworkInfo + Peers + NetworkPeersEvents + Clone + 'static,
     EthApi: EthApiTypes,
     EvmConfig: ConfigureEvm<Primitives = N>,
 {
@@ -832,9 +874,10 @@ where
     Provider: FullRpcProvider<Block = N::Block>
         + CanonStateSubscriptions<Primitives = N>
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + StorageChangeSetReader,
     Pool: TransactionPool + 'static,
-    Network: NetworkInfo + Peers + Clone + 'static,
+    Network: NetworkInfo + Peers + NetworkPeersEvents + Clone + 'static,
     EthApi: FullEthApiServer<Provider = Provider, Pool = Pool>,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
     Consensus: FullConsensus<N, Error = ConsensusError> + Clone + 'static,
@@ -971,9 +1014,17 @@ where
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
                         RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), self.executor.clone())
-                                .into_rpc()
-                                .into()
+                            let mut module =
+                                RethApi::new(self.provider.clone(), self.executor.clone())
+                                    .into_rpc();
+                            module
+                                .merge(EthReceiptsRangeApiExtServer::into_rpc(eth_api.clone()))
+                                .expect("No conflicts");
+                            module
+                                .merge(EthBlockSidecarsApiExtServer::into_rpc(eth_api.clone()))
+                                .expect("No conflicts");
+
+                            module.into()
                         }
                         // only relevant for Ethereum and configured in `EthereumAddOns`
                         // implementation
@@ -1013,6 +1064,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     http_addr: Option<SocketAddr>,
     /// Control whether http responses should be compressed
     http_disable_compression: bool,
+    /// Minimum size of compressed HTTP responses, in bytes
+    http_compression_min_size: u16,
     /// Configs for WS server
     ws_server_config: Option<ServerConfigBuilder>,
     /// Allowed CORS Domains for ws.
@@ -1027,6 +1080,23 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     jwt_secret: Option<JwtSecret>,
     /// Configurable RPC middleware
     rpc_middleware: RpcMiddleware,
+    /// Optional accounting of the method usage of RPC consumers
+    rpc_usage: Option<RpcUsageTracker>,
+    /// Optional GraphQL API served on the http server
+    #[cfg(feature = "graphql")]
+    graphql: Option<GraphQlLayer>,
+    /// Execution time above which calls are logged as slow, if any
+    slow_query_threshold: Option<Duration>,
+    /// Optional per-method and per-client rate limits
+    rate_limits: Option<RpcRateLimits>,
+    /// Optional structured access log of calls
+    access_log: Option<RpcAccessLog>,
+    /// Optional role based method allowlists of JWT authenticated clients
+    roles: Option<RpcRoles>,
+    /// Optional cache of the responses of deterministic methods
+    response_cache: Option<RpcResponseCache>,
+    /// Optional tracker of the canonical epoch log queries are served from
+    canonical_epoch: Option<CanonicalEpochTracker>,
 }
 
 // === impl RpcServerConfig ===
@@ -1039,6 +1109,7 @@ impl Default for RpcServerConfig<Identity> {
             http_cors_domains: None,
             http_addr: None,
             http_disable_compression: false,
+            http_compression_min_size: constants::DEFAULT_HTTP_COMPRESSION_MIN_SIZE,
             ws_server_config: None,
             ws_cors_domains: None,
             ws_addr: None,
@@ -1046,6 +1117,15 @@ impl Default for RpcServerConfig<Identity> {
             ipc_endpoint: None,
             jwt_secret: None,
             rpc_middleware: Default::default(),
+            rpc_usage: None,
+            #[cfg(feature = "graphql")]
+            graphql: None,
+            slow_query_threshold: None,
+            rate_limits: None,
+            access_log: None,
+            roles: None,
+            response_cache: None,
+            canonical_epoch: None,
         }
     }
 }
@@ -1103,6 +1183,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             http_cors_domains: self.http_cors_domains,
             http_addr: self.http_addr,
             http_disable_compression: self.http_disable_compression,
+            http_compression_min_size: self.http_compression_min_size,
             ws_server_config: self.ws_server_config,
             ws_cors_domains: self.ws_cors_domains,
             ws_addr: self.ws_addr,
@@ -1110,9 +1191,90 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_endpoint: self.ipc_endpoint,
             jwt_secret: self.jwt_secret,
             rpc_middleware,
+            rpc_usage: self.rpc_usage,
+            #[cfg(feature = "graphql")]
+            graphql: self.graphql,
+            slow_query_threshold: self.slow_query_threshold,
+            rate_limits: self.rate_limits,
+            access_log: self.access_log,
+            roles: self.roles,
+            response_cache: self.response_cache,
+            canonical_epoch: self.canonical_epoch,
         }
     }
 
+    /// Configures the accounting of the method usage of RPC consumers.
+    ///
+    /// Usage is tracked for all transports, API keys are only available for HTTP requests.
+    pub fn with_rpc_usage(mut self, rpc_usage: Option<RpcUsageTracker>) -> Self {
+        self.rpc_usage = rpc_usage;
+        self
+    }
+
+    /// Configures the GraphQL API that is served on the http server, if any.
+    ///
+    /// See also [`reth_rpc_graphql::GRAPHQL_PATH`].
+    #[cfg(feature = "graphql")]
+    pub fn with_graphql(mut self, graphql: Option<GraphQlLayer>) -> Self {
+        self.graphql = graphql;
+        self
+    }
+
+    /// Configures the execution time above which calls are logged as slow, if any.
+    pub const fn with_slow_query_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.slow_query_threshold = threshold;
+        self
+    }
+
+    /// Configures the per-method and per-client rate limits of calls, if any.
+    ///
+    /// Calls that exceed a limit are rejected with error code
+    /// [`RATE_LIMIT_EXCEEDED_CODE`](rate_limiter::RATE_LIMIT_EXCEEDED_CODE).
+    pub fn with_rate_limits(mut self, rate_limits: Option<RpcRateLimits>) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    /// Configures the structured access log of calls, if any.
+    ///
+    /// See also [`RpcAccessLogLayer`].
+    pub fn with_access_log(mut self, access_log: Option<RpcAccessLog>) -> Self {
+        self.access_log = access_log;
+        self
+    }
+
+    /// Configures the role based method allowlists of the HTTP and WS clients, if any.
+    ///
+    /// The roles are read from the JWT of requests, so this requires a JWT secret, see
+    /// [`Self::with_jwt_secret`]. Calls of methods that aren't allowed for the roles of the client
+    /// are rejected with error code
+    /// [`METHOD_NOT_PERMITTED_CODE`](roles::METHOD_NOT_PERMITTED_CODE).
+    pub fn with_roles(mut self, roles: Option<RpcRoles>) -> Self {
+        self.roles = roles;
+        self
+    }
+
+    /// Configures the cache of the responses of deterministic methods, if any.
+    ///
+    /// The finalized block of the cache must be kept up to date for responses of blocks to be
+    /// cached, see [`update_response_cache_task`](response_cache::update_response_cache_task).
+    pub fn with_response_cache(mut self, response_cache: Option<RpcResponseCache>) -> Self {
+        self.response_cache = response_cache;
+        self
+    }
+
+    /// Configures the tracker of the canonical epoch that the responses of log queries are tagged
+    /// with, if any.
+    ///
+    /// The epoch is attached to the responses as a
+    /// [`CanonicalEpoch`](canonical_epoch::CanonicalEpoch) extension for all transports, and only
+    /// exposed in the [`CANONICAL_EPOCH_HEADER`](canonical_epoch::CANONICAL_EPOCH_HEADER) of HTTP
+    /// responses.
+    pub fn with_canonical_epoch(mut self, canonical_epoch: Option<CanonicalEpochTracker>) -> Self {
+        self.canonical_epoch = canonical_epoch;
+        self
+    }
+
     /// Configure the cors domains for http _and_ ws
     pub fn with_cors(self, cors_domain: Option<String>) -> Self {
         self.with_http_cors(cors_domain.clone()).with_ws_cors(cors_domain)
@@ -1130,6 +1292,14 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configures the minimum size of HTTP responses that are compressed, in bytes.
+    ///
+    /// Default is [`reth_rpc_server_types::constants::DEFAULT_HTTP_COMPRESSION_MIN_SIZE`]
+    pub const fn with_http_compression_min_size(mut self, min_size: u16) -> Self {
+        self.http_compression_min_size = min_size;
+        self
+    }
+
     /// Configure the cors domains for HTTP
     pub fn with_http_cors(mut self, cors_domain: Option<String>) -> Self {
         self.http_cors_domains = cors_domain;
@@ -1212,26 +1382,36 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self.ipc_endpoint.clone()
     }
 
-    /// Creates the [`CorsLayer`] if any
-    fn maybe_cors_layer(cors: Option<String>) -> Result<Option<CorsLayer>, CorsDomainError> {
-        cors.as_deref().map(cors::create_cors_layer).transpose()
-    }
-
     /// Creates the [`AuthLayer`] if any
     fn maybe_jwt_layer(jwt_secret: Option<JwtSecret>) -> Option<AuthLayer<JwtAuthValidator>> {
         jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
     /// Returns a [`CompressionLayer`] that adds compression support (gzip, deflate, brotli, zstd)
-    /// based on the client's `Accept-Encoding` header
-    fn maybe_compression_layer(disable_compression: bool) -> Option<CompressionLayer> {
+    /// based on the client's `Accept-Encoding` header, for responses of at least the minimum size
+    fn maybe_compression_layer(
+        disable_compression: bool,
+        min_size: u16,
+    ) -> Option<CompressionLayer> {
         if disable_compression {
             None
         } else {
-            Some(CompressionLayer::new())
+            Some(CompressionLayer::new().with_min_size(min_size))
         }
     }
 
+    /// Returns the [`GraphQlLayer`] serving the GraphQL API on the http server, if configured.
+    #[cfg(feature = "graphql")]
+    fn graphql_layer(&self) -> Option<GraphQlLayer> {
+        self.graphql.clone()
+    }
+
+    /// Returns no layer, the GraphQL API requires the `graphql` feature.
+    #[cfg(not(feature = "graphql"))]
+    const fn graphql_layer(&self) -> Option<Identity> {
+        None
+    }
+
     /// Builds and starts the configured server(s): http, ws, ipc.
     ///
     /// If both http and ws are on the same port, they are combined into one server.
@@ -1258,10 +1438,19 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
         let ipc_path =
             self.ipc_endpoint.clone().unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
+        let graphql_layer = self.graphql_layer();
 
         if let Some(builder) = self.ipc_server_config {
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new()
+                        .layer(metrics)
+                        .layer(RpcAccessLogLayer::new(self.access_log.clone()))
+                        .layer(RpcRateLimitLayer::new(self.rate_limits.clone()))
+                        .layer(RpcUsageLayer::new(self.rpc_usage.clone()))
+                        .layer(RpcResponseCacheLayer::new(self.response_cache.clone()))
+                        .layer(SlowQueryLayer::new(self.slow_query_threshold)),
+                )
                 .build(ipc_path);
             ipc_handle = Some(ipc.start(modules.ipc.clone().expect("ipc server error")).await?);
         }
@@ -1290,14 +1479,38 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             modules.config.ensure_ws_http_identical()?;
 
             if let Some(config) = self.http_server_config {
+                let cors = ReloadableCorsLayer::new(cors.as_deref())?;
+                let listener = TcpListener::bind(http_socket_addr).await.map_err(|err| {
+                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                })?;
+                let addr = listener.local_addr().map_err(|err| {
+                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
+                })?;
                 let server = ServerBuilder::new()
                     .set_http_middleware(
                         tower::ServiceBuilder::new()
-                            .option_layer(Self::maybe_cors_layer(cors)?)
+                            .layer(cors.clone())
                             .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(self.roles.as_ref().map(RpcRoles::claims_layer))
                             .option_layer(Self::maybe_compression_layer(
                                 self.http_disable_compression,
-                            )),
+                                self.http_compression_min_size,
+                            ))
+                            .option_layer(
+                                self.rpc_usage.as_ref().map(RpcUsageTracker::api_key_layer),
+                            )
+                            .option_layer(
+                                self.rate_limits.as_ref().map(RpcRateLimits::client_ip_layer),
+                            )
+                            .option_layer(
+                                self.access_log.as_ref().map(RpcAccessLog::client_ip_layer),
+                            )
+                            .option_layer(
+                                self.canonical_epoch
+                                    .as_ref()
+                                    .map(CanonicalEpochTracker::header_layer),
+                            )
+                            .option_layer(graphql_layer.clone()),
                     )
                     .set_rpc_middleware(
                         RpcServiceBuilder::default()
@@ -1309,19 +1522,19 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                                     .map(RpcRequestMetrics::same_port)
                                     .unwrap_or_default(),
                             )
+                            .layer(RpcAccessLogLayer::new(self.access_log.clone()))
+                            .layer(RpcRoleLayer::new(self.roles.clone()))
+                            .layer(RpcRateLimitLayer::new(self.rate_limits.clone()))
+                            .layer(RpcUsageLayer::new(self.rpc_usage.clone()))
+                            .layer(CanonicalEpochLayer::new(self.canonical_epoch.clone()))
+                            .layer(RpcResponseCacheLayer::new(self.response_cache.clone()))
+                            .layer(SlowQueryLayer::new(self.slow_query_threshold))
                             .layer(self.rpc_middleware.clone()),
                     )
                     .set_config(config.build())
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
-                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                    })?;
-                let addr = server.local_addr().map_err(|err| {
-                    RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                })?;
+                    .to_service_builder();
                 if let Some(module) = modules.http.as_ref().or(modules.ws.as_ref()) {
-                    let handle = server.start(module.clone());
+                    let handle = start_with_peer_addrs(listener, server, module.clone());
                     http_handle = Some(handle.clone());
                     ws_handle = Some(handle);
                 }
@@ -1333,70 +1546,118 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
                     ipc_endpoint: self.ipc_endpoint.clone(),
                     ipc: ipc_handle,
                     jwt_secret: self.jwt_secret,
+                    reload: RpcServerReloadHandle::new(
+                        Some(cors.clone()),
+                        Some(cors),
+                        self.rate_limits,
+                        self.roles,
+                    ),
                 });
             }
         }
 
         let mut ws_local_addr = None;
         let mut ws_server = None;
+        let mut ws_cors = None;
         let mut http_local_addr = None;
         let mut http_server = None;
+        let mut http_cors = None;
 
         if let Some(config) = self.ws_server_config {
+            let cors = ReloadableCorsLayer::new(self.ws_cors_domains.as_deref())?;
+            let listener = TcpListener::bind(ws_socket_addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+            let addr = listener
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
             let server = ServerBuilder::new()
                 .set_config(config.ws_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                        .layer(cors.clone())
+                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                        .option_layer(self.roles.as_ref().map(RpcRoles::claims_layer))
+                        .option_layer(self.rpc_usage.as_ref().map(RpcUsageTracker::api_key_layer))
+                        .option_layer(self.rate_limits.as_ref().map(RpcRateLimits::client_ip_layer))
+                        .option_layer(self.access_log.as_ref().map(RpcAccessLog::client_ip_layer)),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
                         .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(RpcAccessLogLayer::new(self.access_log.clone()))
+                        .layer(RpcRoleLayer::new(self.roles.clone()))
+                        .layer(RpcRateLimitLayer::new(self.rate_limits.clone()))
+                        .layer(RpcUsageLayer::new(self.rpc_usage.clone()))
+                        .layer(CanonicalEpochLayer::new(self.canonical_epoch.clone()))
+                        .layer(RpcResponseCacheLayer::new(self.response_cache.clone()))
+                        .layer(SlowQueryLayer::new(self.slow_query_threshold))
                         .layer(self.rpc_middleware.clone()),
                 )
-                .build(ws_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
-
-            let addr = server
-                .local_addr()
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+                .to_service_builder();
 
             ws_local_addr = Some(addr);
-            ws_server = Some(server);
+            ws_server = Some((listener, server));
+            ws_cors = Some(cors);
         }
 
         if let Some(config) = self.http_server_config {
+            let cors = ReloadableCorsLayer::new(self.http_cors_domains.as_deref())?;
+            let listener = TcpListener::bind(http_socket_addr)
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
+            let local_addr = listener
+                .local_addr()
+                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
             let server = ServerBuilder::new()
                 .set_config(config.http_only().build())
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
+                        .layer(cors.clone())
                         .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                        .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
+                        .option_layer(self.roles.as_ref().map(RpcRoles::claims_layer))
+                        .option_layer(Self::maybe_compression_layer(
+                            self.http_disable_compression,
+                            self.http_compression_min_size,
+                        ))
+                        .option_layer(self.rpc_usage.as_ref().map(RpcUsageTracker::api_key_layer))
+                        .option_layer(self.rate_limits.as_ref().map(RpcRateLimits::client_ip_layer))
+                        .option_layer(self.access_log.as_ref().map(RpcAccessLog::client_ip_layer))
+                        .option_layer(
+                            self.canonical_epoch.as_ref().map(CanonicalEpochTracker::header_layer),
+                        )
+                        .option_layer(graphql_layer.clone()),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::default()
                         .layer(
                             modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
                         )
+                        .layer(RpcAccessLogLayer::new(self.access_log.clone()))
+                        .layer(RpcRoleLayer::new(self.roles.clone()))
+                        .layer(RpcRateLimitLayer::new(self.rate_limits.clone()))
+                        .layer(RpcUsageLayer::new(self.rpc_usage.clone()))
+                        .layer(CanonicalEpochLayer::new(self.canonical_epoch.clone()))
+                        .layer(RpcResponseCacheLayer::new(self.response_cache.clone()))
+                        .layer(SlowQueryLayer::new(self.slow_query_threshold))
                         .layer(self.rpc_middleware.clone()),
                 )
-                .build(http_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
-            let local_addr = server
-                .local_addr()
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
+                .to_service_builder();
             http_local_addr = Some(local_addr);
-            http_server = Some(server);
+            http_server = Some((listener, server));
+            http_cors = Some(cors);
         }
 
-        http_handle = http_server
-            .map(|http_server| http_server.start(modules.http.clone().expect("http server error")));
-        ws_handle = ws_server
-            .map(|ws_server| ws_server.start(modules.ws.clone().expect("ws server error")));
+        http_handle = http_server.map(|(listener, http_server)| {
+            start_with_peer_addrs(
+                listener,
+                http_server,
+                modules.http.clone().expect("http server error"),
+            )
+        });
+        ws_handle = ws_server.map(|(listener, ws_server)| {
+            start_with_peer_addrs(listener, ws_server, modules.ws.clone().expect("ws server error"))
+        });
         Ok(RpcServerHandle {
             http_local_addr,
             ws_local_addr,
@@ -1405,6 +1666,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             ipc_endpoint: self.ipc_endpoint.clone(),
             ipc: ipc_handle,
             jwt_secret: self.jwt_secret,
+            reload: RpcServerReloadHandle::new(http_cors, ws_cors, self.rate_limits, self.roles),
         })
     }
 }
@@ -1925,6 +2187,26 @@ impl TransportRpcModules {
         self.merge_ipc(other)
     }
 
+    /// Adds or replaces given [`Methods`] in all configured transport modules if the given
+    /// [`RethRpcModule`] is configured for the transport.
+    pub fn add_or_replace_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.module_config().contains_http(&module) {
+            self.add_or_replace_http(other.clone())?;
+        }
+        if self.module_config().contains_ws(&module) {
+            self.add_or_replace_ws(other.clone())?;
+        }
+        if self.module_config().contains_ipc(&module) {
+            self.add_or_replace_ipc(other)?;
+        }
+        Ok(())
+    }
+
     /// Adds or replaces given [`Methods`] in all configured network modules.
     pub fn add_or_replace_configured(
         &mut self,
@@ -1970,6 +2252,7 @@ pub struct RpcServerHandle {
     ipc_endpoint: Option<String>,
     ipc: Option<jsonrpsee::server::ServerHandle>,
     jwt_secret: Option<JwtSecret>,
+    reload: RpcServerReloadHandle,
 }
 
 // === impl RpcServerHandle ===
@@ -2023,6 +2306,12 @@ impl RpcServerHandle {
         self.ipc_endpoint.clone()
     }
 
+    /// Returns the handle to replace the CORS domains, rate limits and roles of the running
+    /// servers.
+    pub const fn reload_handle(&self) -> &RpcServerReloadHandle {
+        &self.reload
+    }
+
     /// Returns the url to the http server
     pub fn http_url(&self) -> Option<String> {
         self.http_local_addr.map(|addr| format!("http://{addr}"))
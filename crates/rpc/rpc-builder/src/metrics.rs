@@ -5,7 +5,7 @@ use jsonrpsee::{
     MethodResponse, RpcModule,
 };
 use reth_metrics::{
-    metrics::{Counter, Histogram},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
 use std::{
@@ -94,13 +94,16 @@ pub struct RpcRequestMetricsService<S> {
     metrics: RpcRequestMetrics,
     /// The inner service being wrapped
     inner: S,
+    /// Time when the connection was opened
+    opened_at: Instant,
 }
 
 impl<S> RpcRequestMetricsService<S> {
     pub(crate) fn new(service: S, metrics: RpcRequestMetrics) -> Self {
         // this instance is kept alive for the duration of the connection
         metrics.inner.connection_metrics.connections_opened_total.increment(1);
-        Self { inner: service, metrics }
+        metrics.inner.connection_metrics.connections_active.increment(1);
+        Self { inner: service, metrics, opened_at: Instant::now() }
     }
 }
 
@@ -141,7 +144,12 @@ where
 impl<S> Drop for RpcRequestMetricsService<S> {
     fn drop(&mut self) {
         // update connection metrics, connection closed
-        self.metrics.inner.connection_metrics.connections_closed_total.increment(1);
+        let connection_metrics = &self.metrics.inner.connection_metrics;
+        connection_metrics.connections_closed_total.increment(1);
+        connection_metrics.connections_active.decrement(1);
+        connection_metrics
+            .connection_duration_seconds
+            .record(self.opened_at.elapsed().as_secs_f64());
     }
 }
 
@@ -226,6 +234,10 @@ struct RpcServerConnectionMetrics {
     connections_opened_total: Counter,
     /// The number of connections closed
     connections_closed_total: Counter,
+    /// The number of currently open connections
+    connections_active: Gauge,
+    /// How long connections were open before they were closed, e.g. because they were dead
+    connection_duration_seconds: Histogram,
     /// The number of requests started
     requests_started_total: Counter,
     /// The number of requests finished
@@ -0,0 +1,159 @@
+//! Serving the http and ws servers with the peer address of connections attached to requests.
+//!
+//! [`jsonrpsee`] servers don't expose the peer address of their connections to middleware, so
+//! the servers accept connections themselves and attach the [`RpcPeerAddr`] of a connection to
+//! the extensions of its requests, from where it's read by the [`RpcClientIpLayer`]. Like the
+//! [`jsonrpsee`] servers, they pass the requests on to the middleware with an [`HttpBody`].
+//!
+//! [`RpcClientIpLayer`]: crate::rate_limiter::RpcClientIpLayer
+
+use bytes::Bytes;
+use http::{Request as HttpRequest, Response as HttpResponse};
+use jsonrpsee::{
+    core::BoxError,
+    server::{
+        serve_with_graceful_shutdown, stop_channel, HttpBody, ServerHandle, TowerService,
+        TowerServiceBuilder,
+    },
+    Methods,
+};
+use std::{
+    net::SocketAddr,
+    task::{Context, Poll},
+};
+use tokio::net::TcpListener;
+use tower::Service;
+use tracing::debug;
+
+/// The peer address of the connection of a request, attached to the request extensions by the
+/// http and ws servers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RpcPeerAddr(pub SocketAddr);
+
+/// An HTTP service that attaches the [`RpcPeerAddr`] of its connection to requests.
+#[derive(Debug, Clone)]
+pub struct RpcPeerAddrService<S> {
+    /// The peer address of the connection.
+    peer_addr: RpcPeerAddr,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> RpcPeerAddrService<S> {
+    /// Creates a new service for the connection of the given peer.
+    pub const fn new(peer_addr: SocketAddr, inner: S) -> Self {
+        Self { peer_addr: RpcPeerAddr(peer_addr), inner }
+    }
+}
+
+impl<S, B> Service<HttpRequest<B>> for RpcPeerAddrService<S>
+where
+    S: Service<HttpRequest<HttpBody>>,
+    B: http_body::Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest<B>) -> Self::Future {
+        let mut req = req.map(HttpBody::new);
+        req.extensions_mut().insert(self.peer_addr);
+        self.inner.call(req)
+    }
+}
+
+/// Accepts the connections of the listener and serves the methods on them, with the services of
+/// the builder wrapped in a [`RpcPeerAddrService`].
+///
+/// This is the equivalent of starting a [`jsonrpsee::server::Server`] that was built with the
+/// same configuration and middleware.
+pub(crate) fn start_with_peer_addrs<RpcMiddleware, HttpMiddleware, B>(
+    listener: TcpListener,
+    builder: TowerServiceBuilder<RpcMiddleware, HttpMiddleware>,
+    methods: impl Into<Methods>,
+) -> ServerHandle
+where
+    TowerServiceBuilder<RpcMiddleware, HttpMiddleware>: Clone + Send + 'static,
+    TowerService<RpcMiddleware, HttpMiddleware>:
+        Service<HttpRequest<HttpBody>, Response = HttpResponse<B>> + Clone + Send + 'static,
+    <TowerService<RpcMiddleware, HttpMiddleware> as Service<HttpRequest<HttpBody>>>::Future: Send,
+    <TowerService<RpcMiddleware, HttpMiddleware> as Service<HttpRequest<HttpBody>>>::Error:
+        Into<BoxError>,
+    B: http_body::Body<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    let methods = methods.into();
+    let (stop_handle, server_handle) = stop_channel();
+
+    tokio::spawn(async move {
+        let stopped = stop_handle.clone().shutdown();
+        tokio::pin!(stopped);
+
+        loop {
+            let (socket, peer_addr) = tokio::select! {
+                res = listener.accept() => match res {
+                    Ok(conn) => conn,
+                    Err(err) => {
+                        debug!(target: "rpc", %err, "Failed to accept connection");
+                        continue
+                    }
+                },
+                _ = &mut stopped => break,
+            };
+            let _ = socket.set_nodelay(true);
+
+            let service = RpcPeerAddrService::new(
+                peer_addr,
+                builder.clone().build(methods.clone(), stop_handle.clone()),
+            );
+            tokio::spawn(serve_with_graceful_shutdown(
+                socket,
+                service,
+                stop_handle.clone().shutdown(),
+            ));
+        }
+    });
+
+    server_handle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        convert::Infallible,
+        future::{ready, Ready},
+        net::{Ipv4Addr, SocketAddrV4},
+    };
+
+    #[derive(Clone)]
+    struct PeerAddrOf;
+
+    impl Service<HttpRequest<HttpBody>> for PeerAddrOf {
+        type Response = Option<RpcPeerAddr>;
+        type Error = Infallible;
+        type Future = Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: HttpRequest<HttpBody>) -> Self::Future {
+            ready(Ok(req.extensions().get::<RpcPeerAddr>().copied()))
+        }
+    }
+
+    #[tokio::test]
+    async fn attaches_peer_addr() {
+        let peer_addr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 1), 30303));
+        let mut service = RpcPeerAddrService::new(peer_addr, PeerAddrOf);
+
+        let resp = service.call(HttpRequest::new(String::new())).await.unwrap();
+        assert_eq!(resp, Some(RpcPeerAddr(peer_addr)));
+    }
+}
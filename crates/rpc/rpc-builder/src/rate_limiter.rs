@@ -1,15 +1,43 @@
-//! [`jsonrpsee`] helper layer for rate limiting certain methods.
+//! [`jsonrpsee`] helper layers for rate limiting certain methods.
+//!
+//! The [`RpcRequestRateLimiter`] limits the number of concurrent `debug_` and `trace_` calls,
+//! while the [`RpcRateLimitLayer`] enforces the configured per-method and per-client request rates
+//! of [`RpcRateLimits`] with token buckets.
 
-use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request};
+use crate::peer_addr::RpcPeerAddr;
+use http::{header::InvalidHeaderName, HeaderMap, HeaderName, Request as HttpRequest};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_rpc_server_types::{RateLimitQuota, RpcRateLimitConfig};
+use schnellru::{ByLength, LruMap};
 use std::{
+    collections::HashMap,
     future::Future,
+    net::IpAddr,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, RwLock,
+    },
     task::{ready, Context, Poll},
+    time::Instant,
 };
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio_util::sync::PollSemaphore;
-use tower::Layer;
+use tower::{Layer, Service};
+use tracing::debug;
+
+/// The error code of calls that exceed a rate limit, `Limit exceeded` as per EIP-1474.
+pub const RATE_LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The maximum number of tracked client buckets, above which the least recently used bucket is
+/// evicted.
+const MAX_TRACKED_CLIENTS: u32 = 10_000;
 
 /// Rate limiter for the RPC server.
 ///
@@ -130,3 +158,642 @@ impl<F: Future> Future for RateLimitingRequestFuture<F> {
         res
     }
 }
+
+/// The IP address of the client of a request, attached to the request extensions by the
+/// [`RpcClientIpLayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RpcClientIp(pub IpAddr);
+
+/// Token bucket rate limits of RPC methods, per method and per client.
+///
+/// Clients are identified by the [`RpcClientIp`] of their requests, and by their connection if
+/// it's unknown, e.g. for IPC. Clones share the same buckets. The limits are enforced by the
+/// [`RpcRateLimitLayer`] RPC middleware, and can be replaced while the servers are running, see
+/// [`Self::set_config`].
+#[derive(Debug, Clone)]
+pub struct RpcRateLimits {
+    inner: Arc<RpcRateLimitsInner>,
+}
+
+impl RpcRateLimits {
+    /// Creates new rate limits from the given config.
+    pub fn new(config: RpcRateLimitConfig) -> Result<Self, InvalidHeaderName> {
+        let client_ip_header =
+            config.client_ip_header.as_deref().map(HeaderName::try_from).transpose()?;
+        Ok(Self {
+            inner: Arc::new(RpcRateLimitsInner {
+                client_ip_layer: RpcClientIpLayer::new(
+                    client_ip_header,
+                    config.trusted_proxies.clone(),
+                ),
+                config: RwLock::new(Arc::new(config)),
+                next_connection_id: AtomicU64::new(0),
+                buckets: Default::default(),
+            }),
+        })
+    }
+
+    /// Returns the current config of the rate limits.
+    pub fn config(&self) -> Arc<RpcRateLimitConfig> {
+        self.inner.config.read().unwrap().clone()
+    }
+
+    /// Replaces the limits with the given config and resets all buckets.
+    ///
+    /// The client IP header and the trusted proxies can't be changed, because the HTTP middleware
+    /// that reads them is installed when the servers are started.
+    pub fn set_config(&self, config: RpcRateLimitConfig) -> Result<(), RpcRateLimitConfigError> {
+        let client_ip_layer = &self.inner.client_ip_layer;
+        let current = client_ip_layer.header.as_ref().map(|header| header.as_str());
+        if config.client_ip_header.as_deref().map(str::to_ascii_lowercase).as_deref() != current {
+            return Err(RpcRateLimitConfigError::ClientIpHeaderChanged {
+                current: current.map(str::to_string),
+                new: config.client_ip_header,
+            })
+        }
+        if *config.trusted_proxies != *client_ip_layer.trusted_proxies {
+            return Err(RpcRateLimitConfigError::TrustedProxiesChanged {
+                current: client_ip_layer.trusted_proxies.to_vec(),
+                new: config.trusted_proxies,
+            })
+        }
+
+        // the buckets are keyed by the index of their rule
+        let mut buckets = self.inner.buckets.lock().unwrap();
+        *self.inner.config.write().unwrap() = Arc::new(config);
+        *buckets = Buckets::default();
+        Ok(())
+    }
+
+    /// Returns the HTTP middleware layer that attaches the client IP of requests.
+    pub fn client_ip_layer(&self) -> RpcClientIpLayer {
+        self.inner.client_ip_layer.clone()
+    }
+
+    /// Takes a token for a call of the method by the client from the matching buckets.
+    ///
+    /// Returns false if the call exceeds a rate limit.
+    fn try_acquire(&self, method: &str, client: RpcClient, now: Instant) -> bool {
+        let config = self.config();
+        let Some((rule, limit)) = config.rule(method) else { return true };
+        let mut buckets = self.inner.buckets.lock().unwrap();
+        let buckets = &mut *buckets;
+
+        let client_bucket = match &limit.per_ip {
+            Some(quota) => {
+                let bucket = buckets
+                    .clients
+                    .get_or_insert((rule, client), || TokenBucket::full(quota, now))
+                    .expect("length limited map accepts new entries");
+                if !bucket.try_acquire(quota, now) {
+                    return false
+                }
+                Some(bucket)
+            }
+            None => None,
+        };
+
+        if let Some(quota) = &limit.global {
+            let bucket =
+                buckets.global.entry(rule).or_insert_with(|| TokenBucket::full(quota, now));
+            if !bucket.try_acquire(quota, now) {
+                // the call is rejected, so it doesn't count against the limit of the client
+                if let Some(bucket) = client_bucket {
+                    bucket.refund();
+                }
+                return false
+            }
+        }
+
+        true
+    }
+
+    /// Removes the buckets of a closed connection.
+    fn remove_connection(&self, id: u64) {
+        let mut buckets = self.inner.buckets.lock().unwrap();
+        let keys = buckets
+            .clients
+            .iter()
+            .map(|(key, _)| *key)
+            .filter(|(_, client)| *client == RpcClient::Connection(id))
+            .collect::<Vec<_>>();
+        for key in keys {
+            buckets.clients.remove(&key);
+        }
+    }
+}
+
+/// Error of replacing the config of running [`RpcRateLimits`].
+#[derive(Debug, thiserror::Error)]
+pub enum RpcRateLimitConfigError {
+    /// The client IP header differs from the header the servers were started with.
+    #[error("the client IP header can't be changed from {current:?} to {new:?} at runtime")]
+    ClientIpHeaderChanged {
+        /// The header the servers were started with.
+        current: Option<String>,
+        /// The header of the new config.
+        new: Option<String>,
+    },
+    /// The trusted proxies differ from the proxies the servers were started with.
+    #[error("the trusted proxies can't be changed from {current:?} to {new:?} at runtime")]
+    TrustedProxiesChanged {
+        /// The proxies the servers were started with.
+        current: Vec<IpAddr>,
+        /// The proxies of the new config.
+        new: Vec<IpAddr>,
+    },
+}
+
+#[derive(Debug)]
+struct RpcRateLimitsInner {
+    /// The configured limits.
+    config: RwLock<Arc<RpcRateLimitConfig>>,
+    /// The HTTP middleware layer that attaches the client IP of requests.
+    client_ip_layer: RpcClientIpLayer,
+    /// The id of the next connection.
+    next_connection_id: AtomicU64,
+    /// The token buckets.
+    buckets: Mutex<Buckets>,
+}
+
+#[derive(Debug)]
+struct Buckets {
+    /// The buckets shared by all clients, by rule.
+    global: HashMap<usize, TokenBucket>,
+    /// The buckets of clients, by rule and client.
+    ///
+    /// The least recently used bucket is evicted once [`MAX_TRACKED_CLIENTS`] are tracked. It
+    /// has most likely been refilled, so evicting it rarely resets a limit.
+    clients: LruMap<(usize, RpcClient), TokenBucket, ByLength>,
+}
+
+impl Default for Buckets {
+    fn default() -> Self {
+        Self { global: HashMap::new(), clients: LruMap::new(ByLength::new(MAX_TRACKED_CLIENTS)) }
+    }
+}
+
+/// The client a call is accounted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RpcClient {
+    /// The client IP of the request.
+    Ip(IpAddr),
+    /// The connection of the request, if its client IP is unknown, e.g. for IPC.
+    Connection(u64),
+}
+
+/// A token bucket that is refilled continuously at the rate of its quota.
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    /// The available tokens.
+    tokens: f64,
+    /// When the tokens were last refilled.
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a new bucket that holds the burst of the quota.
+    fn full(quota: &RateLimitQuota, now: Instant) -> Self {
+        Self { tokens: quota.burst() as f64, refilled_at: now }
+    }
+
+    /// Adds the tokens accrued since the last refill.
+    fn refill(&mut self, quota: &RateLimitQuota, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = elapsed
+            .mul_add(quota.requests_per_second as f64, self.tokens)
+            .min(quota.burst() as f64);
+        self.refilled_at = now;
+    }
+
+    /// Takes a token, returns false if the bucket is empty.
+    fn try_acquire(&mut self, quota: &RateLimitQuota, now: Instant) -> bool {
+        self.refill(quota, now);
+        if self.tokens < 1.0 {
+            return false
+        }
+        self.tokens -= 1.0;
+        true
+    }
+
+    /// Returns a token taken by [`Self::try_acquire`].
+    fn refund(&mut self) {
+        self.tokens += 1.0;
+    }
+}
+
+/// Returns the error of a call that exceeds a rate limit.
+fn rate_limit_exceeded(method: &str) -> ErrorObject<'static> {
+    ErrorObject::owned(
+        RATE_LIMIT_EXCEEDED_CODE,
+        format!("rate limit exceeded for {method}"),
+        None::<()>,
+    )
+}
+
+/// RPC middleware layer that enforces the configured [`RpcRateLimits`], if any.
+#[derive(Debug, Clone, Default)]
+pub struct RpcRateLimitLayer {
+    /// The enforced limits.
+    limits: Option<RpcRateLimits>,
+}
+
+impl RpcRateLimitLayer {
+    /// Creates a new layer that enforces the given limits, if any.
+    pub const fn new(limits: Option<RpcRateLimits>) -> Self {
+        Self { limits }
+    }
+}
+
+impl<S> Layer<S> for RpcRateLimitLayer {
+    type Service = RpcRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        // this instance is kept alive for the duration of the connection
+        let connection = self.limits.clone().map(|limits| {
+            let id = limits.inner.next_connection_id.fetch_add(1, Ordering::Relaxed);
+            Arc::new(RateLimitedConnection { id, limits })
+        });
+        RpcRateLimitService { connection, inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that rejects the calls of a connection that exceed a rate limit.
+#[derive(Debug, Clone)]
+pub struct RpcRateLimitService<S> {
+    /// The connection this service was created for, if limits are enforced.
+    connection: Option<Arc<RateLimitedConnection>>,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> RpcRateLimitService<S> {
+    /// Returns true if the call doesn't exceed a rate limit.
+    fn allows(&self, req: &Request<'_>) -> bool {
+        let Some(connection) = &self.connection else { return true };
+        let client = req
+            .extensions
+            .get::<RpcClientIp>()
+            .map_or(RpcClient::Connection(connection.id), |RpcClientIp(ip)| RpcClient::Ip(*ip));
+        let method = req.method_name();
+        if connection.limits.try_acquire(method, client, Instant::now()) {
+            return true
+        }
+
+        debug!(target: "rpc::rate_limit", method, ?client, "Rejected rate limited call");
+        RpcRateLimitMetrics::new_with_labels(&[("method", method.to_string())])
+            .rejected_calls
+            .increment(1);
+        false
+    }
+}
+
+impl<S> RpcServiceT for RpcRateLimitService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = S::MethodResponse> + Send + 'a {
+        let call = if self.allows(&req) {
+            Ok(self.inner.call(req))
+        } else {
+            Err(MethodResponse::error(req.id(), rate_limit_exceeded(req.method_name())))
+        };
+        async move {
+            match call {
+                Ok(fut) => fut.await,
+                Err(rejected) => rejected,
+            }
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut req: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        for entry in req.iter_mut() {
+            let rejected = match entry {
+                Ok(BatchEntry::Call(call)) if !self.allows(call) => {
+                    Some(BatchEntryErr::new(call.id(), rate_limit_exceeded(call.method_name())))
+                }
+                _ => None,
+            };
+            if let Some(rejected) = rejected {
+                *entry = Err(rejected);
+            }
+        }
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Removes the buckets of a connection once the connection is closed.
+#[derive(Debug)]
+struct RateLimitedConnection {
+    /// The id of the connection.
+    id: u64,
+    /// The limits enforced on the connection.
+    limits: RpcRateLimits,
+}
+
+impl Drop for RateLimitedConnection {
+    fn drop(&mut self) {
+        self.limits.remove_connection(self.id);
+    }
+}
+
+/// Metrics for rate limited RPC calls
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.rate_limit")]
+struct RpcRateLimitMetrics {
+    /// The number of calls rejected because they exceeded a rate limit
+    rejected_calls: Counter,
+}
+
+/// An HTTP middleware layer that attaches the client IP of requests as [`RpcClientIp`] to the
+/// request extensions.
+///
+/// The client IP is the IP of the [`RpcPeerAddr`] of the request, unless the peer is one of the
+/// trusted proxies and the request carries the configured client IP header. Every proxy appends
+/// the address it received the request from to the header, as it's done for `x-forwarded-for`,
+/// so the client IP is the rightmost address of the header that isn't a trusted proxy. The
+/// addresses left of it can be forged by the client.
+#[derive(Debug, Clone)]
+pub struct RpcClientIpLayer {
+    /// The HTTP header that carries the client IP.
+    header: Option<HeaderName>,
+    /// The IP addresses of the proxies whose client IP header is trusted.
+    trusted_proxies: Arc<[IpAddr]>,
+}
+
+impl RpcClientIpLayer {
+    /// Creates a new layer that reads the client IP from the given header of requests of the
+    /// trusted proxies.
+    pub fn new(header: Option<HeaderName>, trusted_proxies: impl Into<Arc<[IpAddr]>>) -> Self {
+        Self { header, trusted_proxies: trusted_proxies.into() }
+    }
+
+    /// Returns the HTTP header that carries the client IP, if any.
+    pub const fn header(&self) -> Option<&HeaderName> {
+        self.header.as_ref()
+    }
+
+    /// Returns the IP addresses of the proxies whose client IP header is trusted.
+    pub fn trusted_proxies(&self) -> &[IpAddr] {
+        &self.trusted_proxies
+    }
+
+    /// Returns the client IP of a request of the given peer with the given headers.
+    fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let Some(header) = &self.header else { return peer };
+        if !self.trusted_proxies.contains(&peer) {
+            return peer
+        }
+
+        let forwarded = headers
+            .get_all(header)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .collect::<Vec<_>>();
+        let mut client = peer;
+        for addr in forwarded.into_iter().rev() {
+            let Ok(ip) = addr.trim().parse() else { break };
+            client = ip;
+            if !self.trusted_proxies.contains(&ip) {
+                break
+            }
+        }
+        client
+    }
+}
+
+impl<S> Layer<S> for RpcClientIpLayer {
+    type Service = RpcClientIpService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcClientIpService { layer: self.clone(), inner }
+    }
+}
+
+/// The HTTP service of the [`RpcClientIpLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcClientIpService<S> {
+    /// The layer that created this service.
+    layer: RpcClientIpLayer,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S, B> Service<HttpRequest<B>> for RpcClientIpService<S>
+where
+    S: Service<HttpRequest<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest<B>) -> Self::Future {
+        if let Some(RpcPeerAddr(peer_addr)) = req.extensions().get::<RpcPeerAddr>().copied() {
+            let client_ip = self.layer.client_ip(peer_addr.ip(), req.headers());
+            req.extensions_mut().insert(RpcClientIp(client_ip));
+        }
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_server_types::MethodRateLimit;
+    use std::time::Duration;
+
+    fn limits(global: Option<RateLimitQuota>, per_ip: Option<RateLimitQuota>) -> RpcRateLimits {
+        RpcRateLimits::new(RpcRateLimitConfig {
+            methods: vec![MethodRateLimit { method: "debug_*".to_string(), global, per_ip }],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn token_bucket_refills_at_quota_rate() {
+        let quota = RateLimitQuota::per_second(2).with_burst(3);
+        let now = Instant::now();
+        let mut bucket = TokenBucket::full(&quota, now);
+
+        assert!((0..3).all(|_| bucket.try_acquire(&quota, now)));
+        assert!(!bucket.try_acquire(&quota, now));
+
+        let later = now + Duration::from_millis(500);
+        assert!(bucket.try_acquire(&quota, later));
+        assert!(!bucket.try_acquire(&quota, later));
+        // the bucket doesn't hold more than the burst
+        let much_later = later + Duration::from_secs(10);
+        assert!((0..3).all(|_| bucket.try_acquire(&quota, much_later)));
+        assert!(!bucket.try_acquire(&quota, much_later));
+    }
+
+    #[test]
+    fn limits_per_client() {
+        let limits = limits(None, Some(RateLimitQuota::per_second(1)));
+        let now = Instant::now();
+        let first = RpcClient::Ip(IpAddr::from([10, 0, 0, 1]));
+        let second = RpcClient::Ip(IpAddr::from([10, 0, 0, 2]));
+
+        assert!(limits.try_acquire("debug_traceCall", first, now));
+        assert!(!limits.try_acquire("debug_traceTransaction", first, now));
+        assert!(limits.try_acquire("debug_traceCall", second, now));
+        // methods without a rule aren't limited
+        assert!(limits.try_acquire("eth_call", first, now));
+    }
+
+    #[test]
+    fn limits_shared_by_all_clients() {
+        let limits = limits(Some(RateLimitQuota::per_second(1)), None);
+        let now = Instant::now();
+
+        assert!(limits.try_acquire("debug_traceCall", RpcClient::Connection(0), now));
+        assert!(!limits.try_acquire("debug_traceCall", RpcClient::Connection(1), now));
+    }
+
+    #[test]
+    fn global_rejection_keeps_client_token() {
+        let limits = limits(
+            Some(RateLimitQuota::per_second(2).with_burst(1)),
+            Some(RateLimitQuota::per_second(1).with_burst(2)),
+        );
+        let now = Instant::now();
+        let client = RpcClient::Ip(IpAddr::from([10, 0, 0, 1]));
+
+        assert!(limits.try_acquire("debug_traceCall", client, now));
+        assert!(!limits.try_acquire("debug_traceCall", client, now));
+        // the global bucket is refilled, the client still has its second token
+        let later = now + Duration::from_millis(500);
+        assert!(limits.try_acquire("debug_traceCall", client, later));
+    }
+
+    #[test]
+    fn replaces_config() {
+        let limits = limits(Some(RateLimitQuota::per_second(1)), None);
+        let now = Instant::now();
+        let client = RpcClient::Connection(0);
+
+        assert!(limits.try_acquire("debug_traceCall", client, now));
+        assert!(!limits.try_acquire("debug_traceCall", client, now));
+
+        limits
+            .set_config(RpcRateLimitConfig {
+                methods: vec![MethodRateLimit {
+                    method: "trace_*".to_string(),
+                    global: Some(RateLimitQuota::per_second(1)),
+                    per_ip: None,
+                }],
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(limits.try_acquire("debug_traceCall", client, now));
+        assert!(limits.try_acquire("trace_block", client, now));
+        assert!(!limits.try_acquire("trace_block", client, now));
+
+        let config = RpcRateLimitConfig {
+            client_ip_header: Some("x-forwarded-for".to_string()),
+            ..Default::default()
+        };
+        assert!(limits.set_config(config).is_err());
+        let config = RpcRateLimitConfig {
+            trusted_proxies: vec![IpAddr::from([127, 0, 0, 1])],
+            ..Default::default()
+        };
+        assert!(limits.set_config(config).is_err());
+    }
+
+    #[test]
+    fn evicts_least_recently_used_clients() {
+        let limits = limits(None, Some(RateLimitQuota::per_second(1)));
+        let now = Instant::now();
+        let first = RpcClient::Connection(0);
+
+        assert!(limits.try_acquire("debug_traceCall", first, now));
+        for id in 1..=MAX_TRACKED_CLIENTS as u64 {
+            assert!(limits.try_acquire("debug_traceCall", RpcClient::Connection(id), now));
+        }
+        assert_eq!(
+            limits.inner.buckets.lock().unwrap().clients.len(),
+            MAX_TRACKED_CLIENTS as usize
+        );
+
+        // the bucket of the first client was evicted
+        assert!(limits.try_acquire("debug_traceCall", first, now));
+        // the bucket of the most recent client wasn't
+        let last = RpcClient::Connection(MAX_TRACKED_CLIENTS as u64);
+        assert!(!limits.try_acquire("debug_traceCall", last, now));
+    }
+
+    #[test]
+    fn client_ip_of_peer() {
+        let proxy = IpAddr::from([127, 0, 0, 1]);
+        let peer = IpAddr::from([10, 0, 0, 1]);
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", "1.1.1.1, 2.2.2.2".parse().unwrap());
+
+        // without a header, or if the peer isn't a trusted proxy, the peer is the client
+        let layer = RpcClientIpLayer::new(None, vec![proxy]);
+        assert_eq!(layer.client_ip(proxy, &headers), proxy);
+        let layer = RpcClientIpLayer::new(Some(HeaderName::from_static("x-forwarded-for")), []);
+        assert_eq!(layer.client_ip(peer, &headers), peer);
+        assert_eq!(layer.client_ip(proxy, &headers), proxy);
+    }
+
+    #[test]
+    fn client_ip_of_trusted_proxy_header() {
+        let proxy = IpAddr::from([127, 0, 0, 1]);
+        let inner_proxy = IpAddr::from([10, 0, 0, 2]);
+        let layer = RpcClientIpLayer::new(
+            Some(HeaderName::from_static("x-forwarded-for")),
+            vec![proxy, inner_proxy],
+        );
+        let headers = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert("x-forwarded-for", value.parse().unwrap());
+            headers
+        };
+
+        // the rightmost address that isn't a trusted proxy is the client, the addresses left of
+        // it can be forged
+        assert_eq!(
+            layer.client_ip(proxy, &headers("6.6.6.6, 1.1.1.1, 10.0.0.2")),
+            IpAddr::from([1, 1, 1, 1])
+        );
+        // a malformed address ends the chain of trusted proxies
+        assert_eq!(layer.client_ip(proxy, &headers("1.1.1.1, unknown, 10.0.0.2")), inner_proxy);
+        // without the header, the proxy is the client
+        assert_eq!(layer.client_ip(proxy, &HeaderMap::new()), proxy);
+    }
+
+    #[test]
+    fn removes_buckets_of_closed_connections() {
+        let limits = limits(None, Some(RateLimitQuota::per_second(1)));
+        let service = RpcRateLimitLayer::new(Some(limits.clone())).layer(());
+        let id = service.connection.as_ref().unwrap().id;
+
+        assert!(limits.try_acquire("debug_traceCall", RpcClient::Connection(id), Instant::now()));
+        assert_eq!(limits.inner.buckets.lock().unwrap().clients.len(), 1);
+
+        drop(service);
+        assert!(limits.inner.buckets.lock().unwrap().clients.is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+//! Reconfiguration of running RPC servers.
+//!
+//! The [`RpcServerReloadHandle`] of a [`RpcServerHandle`](crate::RpcServerHandle) replaces the
+//! CORS domains, the rate limits and the role based method allowlists of the running servers,
+//! without restarting them.
+
+use crate::{
+    cors::{CorsDomainError, ReloadableCorsLayer},
+    rate_limiter::{RpcRateLimitConfigError, RpcRateLimits},
+    roles::RpcRoles,
+};
+use reth_rpc_server_types::{RpcRateLimitConfig, RpcRolesConfig};
+
+/// Errors of reconfiguring running RPC servers.
+#[derive(Debug, thiserror::Error)]
+pub enum RpcReloadError {
+    /// The new CORS domains are invalid.
+    #[error(transparent)]
+    Cors(#[from] CorsDomainError),
+    /// The new rate limits can't be applied.
+    #[error(transparent)]
+    RateLimits(#[from] RpcRateLimitConfigError),
+    /// The feature wasn't enabled when the servers were started.
+    #[error("{0} can't be enabled at runtime, they were not configured when the servers started")]
+    NotEnabled(&'static str),
+}
+
+/// A handle to replace the configuration of the running HTTP and WS servers.
+///
+/// The CORS domains can always be replaced. The rate limits and the roles can only be replaced if
+/// they were configured when the servers were started, because their middleware is only installed
+/// in that case. Changes apply to all subsequent requests, including requests of open connections.
+#[derive(Debug, Clone, Default)]
+pub struct RpcServerReloadHandle {
+    /// The CORS layer of the http server, if started.
+    http_cors: Option<ReloadableCorsLayer>,
+    /// The CORS layer of the ws server, if started.
+    ws_cors: Option<ReloadableCorsLayer>,
+    /// The rate limits of all transports, if configured.
+    rate_limits: Option<RpcRateLimits>,
+    /// The roles of the http and ws clients, if configured.
+    roles: Option<RpcRoles>,
+}
+
+impl RpcServerReloadHandle {
+    /// Creates a new handle for the given layers of the started servers.
+    pub(crate) const fn new(
+        http_cors: Option<ReloadableCorsLayer>,
+        ws_cors: Option<ReloadableCorsLayer>,
+        rate_limits: Option<RpcRateLimits>,
+        roles: Option<RpcRoles>,
+    ) -> Self {
+        Self { http_cors, ws_cors, rate_limits, roles }
+    }
+
+    /// Replaces the allowed CORS domains of the http server, CORS is disabled if `None`.
+    ///
+    /// If http and ws share the same port, this also applies to ws.
+    pub fn set_http_cors(&self, domains: Option<&str>) -> Result<(), RpcReloadError> {
+        if let Some(cors) = &self.http_cors {
+            cors.set_domains(domains)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the allowed CORS domains of the ws server, CORS is disabled if `None`.
+    ///
+    /// If http and ws share the same port, this also applies to http.
+    pub fn set_ws_cors(&self, domains: Option<&str>) -> Result<(), RpcReloadError> {
+        if let Some(cors) = &self.ws_cors {
+            cors.set_domains(domains)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the allowed CORS domains of both the http and the ws server.
+    pub fn set_cors(&self, domains: Option<&str>) -> Result<(), RpcReloadError> {
+        self.set_http_cors(domains)?;
+        self.set_ws_cors(domains)
+    }
+
+    /// Replaces the rate limits of all transports, methods aren't limited anymore if `None`.
+    ///
+    /// The token buckets of all clients are reset.
+    pub fn set_rate_limits(
+        &self,
+        config: Option<RpcRateLimitConfig>,
+    ) -> Result<(), RpcReloadError> {
+        match (&self.rate_limits, config) {
+            (Some(limits), Some(config)) => limits.set_config(config)?,
+            (Some(limits), None) => {
+                let current = limits.config();
+                limits.set_config(RpcRateLimitConfig {
+                    client_ip_header: current.client_ip_header.clone(),
+                    trusted_proxies: current.trusted_proxies.clone(),
+                    methods: Vec::new(),
+                })?
+            }
+            (None, Some(config)) if !config.is_empty() => {
+                return Err(RpcReloadError::NotEnabled("rate limits"))
+            }
+            (None, _) => {}
+        }
+        Ok(())
+    }
+
+    /// Replaces the role based method allowlists of the http and ws clients.
+    pub fn set_roles(&self, config: RpcRolesConfig) -> Result<(), RpcReloadError> {
+        let roles = self.roles.as_ref().ok_or(RpcReloadError::NotEnabled("roles"))?;
+        roles.set_config(config);
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+//! Caching of the responses of deterministic RPC methods.
+//!
+//! The [`RpcResponseCacheLayer`] serves calls of the methods of [`cache_policy`] from a
+//! [`RpcResponseCache`], keyed by the method and its params. Responses that depend on a block are
+//! only cached if the block is finalized, and are dropped if the block is reorged out, see
+//! [`update_response_cache_task`].
+
+use futures::{
+    future::{ready, Either},
+    Stream, StreamExt,
+};
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{Request, ResponsePayload},
+    MethodResponse,
+};
+use reth_chain_state::CanonStateNotification;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives_traits::{AlloyBlockHeader, NodePrimitives, SealedHeader};
+use schnellru::{ByLength, LruMap};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::{
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+use tower::Layer;
+use tracing::trace;
+
+/// How the responses of a method are cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// The response never changes.
+    Always,
+    /// The response is cached if the block it belongs to is finalized.
+    Finalized,
+}
+
+/// Returns the [`CachePolicy`] of the method, `None` if its responses aren't cached.
+pub fn cache_policy(method: &str) -> Option<CachePolicy> {
+    match method {
+        "eth_chainId" | "net_version" => Some(CachePolicy::Always),
+        "eth_getBlockByHash" | "eth_getTransactionReceipt" => Some(CachePolicy::Finalized),
+        _ => None,
+    }
+}
+
+/// The key of a cached response.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    /// The name of the method.
+    method: String,
+    /// The raw params of the call.
+    params: Option<String>,
+}
+
+impl CacheKey {
+    /// Returns the key of the call, if the responses of its method are cached.
+    fn new(req: &Request<'_>) -> Option<(Self, CachePolicy)> {
+        let policy = cache_policy(req.method_name())?;
+        let key = Self {
+            method: req.method_name().to_string(),
+            params: req.params().as_str().map(str::to_string),
+        };
+        Some((key, policy))
+    }
+}
+
+/// A cached response.
+#[derive(Debug)]
+struct CachedResponse {
+    /// The result of the response.
+    result: Box<RawValue>,
+    /// The number of the block the result belongs to, if any.
+    block_number: Option<u64>,
+}
+
+/// The fields of a block or a receipt that identify its block.
+#[derive(Deserialize)]
+struct BlockNumberFields<'a> {
+    /// The number of a block.
+    #[serde(borrow, default)]
+    number: Option<&'a str>,
+    /// The block number of a receipt.
+    #[serde(borrow, default, rename = "blockNumber")]
+    block_number: Option<&'a str>,
+}
+
+/// Returns the number of the block the result of a method with the [`CachePolicy::Finalized`]
+/// policy belongs to.
+fn result_block_number(result: &RawValue) -> Option<u64> {
+    let fields = serde_json::from_str::<Option<BlockNumberFields<'_>>>(result.get()).ok()??;
+    let number = fields.number.or(fields.block_number)?;
+    u64::from_str_radix(number.strip_prefix("0x")?, 16).ok()
+}
+
+/// The result of a successful response.
+#[derive(Deserialize)]
+struct SuccessResponse {
+    /// The result of the call.
+    result: Box<RawValue>,
+}
+
+/// An in-memory LRU cache of the responses of deterministic RPC methods.
+///
+/// Clones share the same cache.
+#[derive(Clone)]
+pub struct RpcResponseCache {
+    inner: Arc<Mutex<RpcResponseCacheInner>>,
+    metrics: RpcResponseCacheMetrics,
+}
+
+impl RpcResponseCache {
+    /// Creates a new cache that holds at most `max_entries` responses.
+    pub fn new(max_entries: u32) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(RpcResponseCacheInner {
+                entries: LruMap::new(ByLength::new(max_entries)),
+                finalized: None,
+            })),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Returns the number of cached responses.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+
+    /// Returns true if no responses are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of the finalized block, if known.
+    pub fn finalized(&self) -> Option<u64> {
+        self.inner.lock().unwrap().finalized
+    }
+
+    /// Sets the number of the finalized block, responses of blocks up to it are cached.
+    pub fn set_finalized(&self, number: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.finalized = Some(inner.finalized.map_or(number, |finalized| finalized.max(number)));
+    }
+
+    /// Drops the cached responses of all blocks after the given block.
+    ///
+    /// This is called for reorgs, which also resets the finalized block to the fork block if it
+    /// was after it.
+    pub fn remove_blocks_after(&self, number: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        let reorged = inner
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.block_number.is_some_and(|block| block > number))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in reorged {
+            inner.entries.remove(&key);
+        }
+        if let Some(finalized) = &mut inner.finalized {
+            *finalized = (*finalized).min(number);
+        }
+    }
+
+    /// Applies a canonical state notification to the cache.
+    ///
+    /// Responses of blocks that are reorged out are dropped.
+    pub fn on_canonical_state<N: NodePrimitives>(&self, notification: &CanonStateNotification<N>) {
+        if let Some(reverted) = notification.reverted() {
+            let fork_block = reverted.fork_block().number;
+            trace!(target: "rpc::response_cache", fork_block, "Dropping responses of reorged blocks");
+            self.remove_blocks_after(fork_block);
+        }
+    }
+
+    /// Returns the cached response to the call with the given key, if any.
+    fn get(&self, key: &CacheKey, req: &Request<'_>) -> Option<MethodResponse> {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(entry) = inner.entries.get(key) else {
+            self.metrics.misses.increment(1);
+            return None
+        };
+        self.metrics.hits.increment(1);
+        let payload = ResponsePayload::success(&*entry.result).into();
+        Some(MethodResponse::response(req.id(), payload, usize::MAX))
+    }
+
+    /// Caches the response to the call with the given key, if it's cacheable.
+    fn insert(&self, key: CacheKey, policy: CachePolicy, response: &MethodResponse) {
+        if !response.is_success() {
+            return
+        }
+        let Ok(SuccessResponse { result }) = serde_json::from_str(response.as_json().get()) else {
+            return
+        };
+        let block_number = match policy {
+            CachePolicy::Always => None,
+            CachePolicy::Finalized => {
+                // unknown blocks and pending transactions aren't cached
+                let Some(number) = result_block_number(&result) else { return };
+                Some(number)
+            }
+        };
+
+        let mut inner = self.inner.lock().unwrap();
+        if block_number.is_some_and(|number| inner.finalized.is_none_or(|f| number > f)) {
+            return
+        }
+        inner.entries.insert(key, CachedResponse { result, block_number });
+    }
+}
+
+impl fmt::Debug for RpcResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.lock().unwrap();
+        f.debug_struct("RpcResponseCache")
+            .field("entries", &inner.entries.len())
+            .field("finalized", &inner.finalized)
+            .finish_non_exhaustive()
+    }
+}
+
+struct RpcResponseCacheInner {
+    /// The cached responses.
+    entries: LruMap<CacheKey, CachedResponse, ByLength>,
+    /// The number of the finalized block, if known.
+    finalized: Option<u64>,
+}
+
+/// Awaits canonical state notifications and new finalized blocks and applies them to the cache.
+///
+/// Responses of blocks are cached once they are finalized, and dropped if they are reorged out.
+pub async fn update_response_cache_task<N, H, St, Fin>(
+    cache: RpcResponseCache,
+    mut events: St,
+    mut finalized: Fin,
+) where
+    N: NodePrimitives,
+    H: AlloyBlockHeader,
+    St: Stream<Item = CanonStateNotification<N>> + Unpin + 'static,
+    Fin: Stream<Item = SealedHeader<H>> + Unpin + 'static,
+{
+    loop {
+        tokio::select! {
+            event = events.next() => {
+                let Some(event) = event else { break };
+                cache.on_canonical_state(&event);
+            }
+            header = finalized.next() => {
+                let Some(header) = header else { break };
+                cache.set_finalized(header.number());
+            }
+        }
+    }
+}
+
+/// RPC middleware layer that serves calls from a [`RpcResponseCache`], if any.
+#[derive(Debug, Clone, Default)]
+pub struct RpcResponseCacheLayer {
+    /// The cache of the responses.
+    cache: Option<RpcResponseCache>,
+}
+
+impl RpcResponseCacheLayer {
+    /// Creates a new layer that caches responses in the given cache, if any.
+    pub const fn new(cache: Option<RpcResponseCache>) -> Self {
+        Self { cache }
+    }
+}
+
+impl<S> Layer<S> for RpcResponseCacheLayer {
+    type Service = RpcResponseCacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcResponseCacheService { cache: self.cache.clone(), inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that serves calls of deterministic methods from a
+/// [`RpcResponseCache`].
+///
+/// Calls of batch requests are always passed to the inner service.
+#[derive(Debug, Clone)]
+pub struct RpcResponseCacheService<S> {
+    /// The cache of the responses, if any.
+    cache: Option<RpcResponseCache>,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> RpcServiceT for RpcResponseCacheService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = S::MethodResponse> + Send + 'a {
+        let cached = self.cache.as_ref().and_then(|cache| {
+            CacheKey::new(&req).map(|(key, policy)| (cache.clone(), key, policy))
+        });
+        if let Some((cache, key, _)) = &cached {
+            if let Some(response) = cache.get(key, &req) {
+                return Either::Left(ready(response))
+            }
+        }
+
+        let call = self.inner.call(req);
+        Either::Right(async move {
+            let response = call.await;
+            if let Some((cache, key, policy)) = cached {
+                cache.insert(key, policy, &response);
+            }
+            response
+        })
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Metrics of the RPC response cache
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.response_cache")]
+struct RpcResponseCacheMetrics {
+    /// The number of calls served from the cache
+    hits: Counter,
+    /// The number of calls of cached methods that weren't cached
+    misses: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::types::Id;
+    use serde_json::json;
+
+    fn request<'a>(method: &'a str, params: &'a RawValue) -> Request<'a> {
+        Request::borrowed(method, Some(params), Id::Number(1))
+    }
+
+    fn response(id: u64, result: serde_json::Value) -> MethodResponse {
+        MethodResponse::response(
+            Id::Number(id),
+            ResponsePayload::success(result).into(),
+            usize::MAX,
+        )
+    }
+
+    #[test]
+    fn caches_deterministic_methods() {
+        let cache = RpcResponseCache::new(10);
+        let params = RawValue::from_string("[]".to_string()).unwrap();
+        let req = request("eth_chainId", &params);
+        let (key, policy) = CacheKey::new(&req).unwrap();
+        assert_eq!(policy, CachePolicy::Always);
+        assert!(cache.get(&key, &req).is_none());
+
+        cache.insert(key.clone(), policy, &response(7, json!("0x1")));
+        let cached = cache.get(&key, &req).unwrap();
+        let cached = serde_json::from_str::<serde_json::Value>(cached.as_json().get()).unwrap();
+        assert_eq!(cached, json!({ "jsonrpc": "2.0", "id": 1, "result": "0x1" }));
+
+        let req = request("eth_blockNumber", &params);
+        assert!(CacheKey::new(&req).is_none());
+    }
+
+    #[test]
+    fn caches_finalized_blocks_only() {
+        let cache = RpcResponseCache::new(10);
+        let params = RawValue::from_string(r#"["0x01", false]"#.to_string()).unwrap();
+        let req = request("eth_getBlockByHash", &params);
+        let (key, policy) = CacheKey::new(&req).unwrap();
+        let block = response(1, json!({ "hash": "0x01", "number": "0x10" }));
+
+        // the finalized block is unknown
+        cache.insert(key.clone(), policy, &block);
+        assert!(cache.is_empty());
+
+        cache.set_finalized(0xf);
+        cache.insert(key.clone(), policy, &block);
+        assert!(cache.is_empty());
+
+        // unknown blocks aren't cached
+        cache.set_finalized(0x10);
+        cache.insert(key.clone(), policy, &response(1, serde_json::Value::Null));
+        assert!(cache.is_empty());
+
+        cache.insert(key.clone(), policy, &block);
+        assert!(cache.get(&key, &req).is_some());
+
+        // reorgs drop the responses of reverted blocks
+        cache.remove_blocks_after(0x10);
+        assert_eq!(cache.len(), 1);
+        cache.remove_blocks_after(0xf);
+        assert!(cache.is_empty());
+        assert_eq!(cache.finalized(), Some(0xf));
+    }
+
+    #[test]
+    fn reads_block_number_of_receipts() {
+        let receipt = RawValue::from_string(
+            json!({ "blockHash": "0x01", "blockNumber": "0x2a", "status": "0x1" }).to_string(),
+        )
+        .unwrap();
+        assert_eq!(result_block_number(&receipt), Some(42));
+
+        let null = RawValue::from_string("null".to_string()).unwrap();
+        assert_eq!(result_block_number(&null), None);
+    }
+}
@@ -0,0 +1,375 @@
+//! Role based method allowlists of JWT authenticated clients.
+//!
+//! The [`RpcRoleClaimsLayer`] reads the roles of the JWT of HTTP and WS requests, and the
+//! [`RpcRoleLayer`] rejects calls of methods that aren't allowed for any of these roles, as
+//! configured by [`RpcRoles`].
+
+use http::{header, HeaderMap, Request as HttpRequest};
+use jsonrpsee::{
+    core::middleware::{Batch, BatchEntry, BatchEntryErr, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_rpc_layer::JwtSecret;
+use reth_rpc_server_types::RpcRolesConfig;
+use serde_json::{Map, Value};
+use std::{
+    fmt,
+    future::Future,
+    sync::{Arc, RwLock},
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+use tracing::debug;
+
+/// The error code of calls of methods that aren't allowed for the roles of the client, `Method not
+/// supported` as per EIP-1474.
+pub const METHOD_NOT_PERMITTED_CODE: i32 = -32004;
+
+/// The roles of the JWT of a request, attached to the request extensions by the
+/// [`RpcRoleClaimsLayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcClientRoles(pub Arc<[String]>);
+
+/// Role based method allowlists of the clients of the regular RPC servers.
+///
+/// The roles of a client are read from a claim of its JWT, which must be signed with the JWT secret
+/// of the servers. Requests are expected to be authenticated by the JWT auth layer of the servers
+/// before the roles are read. Clones share the same config, which can be replaced while the servers
+/// are running, see [`Self::set_config`].
+#[derive(Clone)]
+pub struct RpcRoles {
+    inner: Arc<RpcRolesInner>,
+}
+
+impl RpcRoles {
+    /// Creates new role allowlists from the given config, for tokens signed with the given secret.
+    pub fn new(config: RpcRolesConfig, secret: &JwtSecret) -> Self {
+        let mut validation = Validation::new(Algorithm::HS256);
+        // the expiration is validated if the token has one, the `iat` claim is validated by the
+        // auth layer
+        validation.required_spec_claims.clear();
+        validation.validate_aud = false;
+        Self {
+            inner: Arc::new(RpcRolesInner {
+                config: RwLock::new(Arc::new(config)),
+                key: DecodingKey::from_secret(secret.as_bytes()),
+                validation,
+            }),
+        }
+    }
+
+    /// Returns the current config of the roles.
+    pub fn config(&self) -> Arc<RpcRolesConfig> {
+        self.inner.config.read().unwrap().clone()
+    }
+
+    /// Replaces the config of the roles, which applies to all subsequent requests.
+    pub fn set_config(&self, config: RpcRolesConfig) {
+        *self.inner.config.write().unwrap() = Arc::new(config);
+    }
+
+    /// Returns the HTTP middleware layer that attaches the roles of the JWT of requests.
+    pub fn claims_layer(&self) -> RpcRoleClaimsLayer {
+        RpcRoleClaimsLayer::new(self.clone())
+    }
+
+    /// Returns the roles of the bearer token of the headers, if it's valid.
+    ///
+    /// Tokens without the role claim have the default roles.
+    fn roles(&self, headers: &HeaderMap) -> Option<RpcClientRoles> {
+        let token = headers.get(header::AUTHORIZATION)?.to_str().ok()?.strip_prefix("Bearer ")?;
+        let claims = jsonwebtoken::decode::<Map<String, Value>>(
+            token,
+            &self.inner.key,
+            &self.inner.validation,
+        )
+        .inspect_err(|err| debug!(target: "rpc::roles", %err, "Failed to decode JWT claims"))
+        .ok()?
+        .claims;
+
+        let config = self.config();
+        let roles = match claims.get(&config.role_claim) {
+            None => config.default_roles.clone(),
+            Some(Value::String(role)) => vec![role.clone()],
+            Some(Value::Array(roles)) => {
+                roles.iter().filter_map(|role| role.as_str().map(str::to_string)).collect()
+            }
+            Some(_) => Vec::new(),
+        };
+        Some(RpcClientRoles(roles.into()))
+    }
+
+    /// Returns true if the roles of the client allow the method.
+    fn allows(&self, roles: Option<&RpcClientRoles>, method: &str) -> bool {
+        roles.is_some_and(|RpcClientRoles(roles)| {
+            self.config().allows(roles.iter().map(String::as_str), method)
+        })
+    }
+}
+
+impl fmt::Debug for RpcRoles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcRoles").field("config", &self.config()).finish_non_exhaustive()
+    }
+}
+
+struct RpcRolesInner {
+    /// The configured roles.
+    config: RwLock<Arc<RpcRolesConfig>>,
+    /// The key the tokens are signed with.
+    key: DecodingKey,
+    /// The validation of the tokens.
+    validation: Validation,
+}
+
+/// Returns the error of a call of a method that isn't allowed for the roles of the client.
+fn method_not_permitted(method: &str) -> ErrorObject<'static> {
+    ErrorObject::owned(
+        METHOD_NOT_PERMITTED_CODE,
+        format!("method {method} is not permitted for the roles of the token"),
+        None::<()>,
+    )
+}
+
+/// RPC middleware layer that enforces the configured [`RpcRoles`], if any.
+#[derive(Debug, Clone, Default)]
+pub struct RpcRoleLayer {
+    /// The enforced roles.
+    roles: Option<RpcRoles>,
+}
+
+impl RpcRoleLayer {
+    /// Creates a new layer that enforces the given roles, if any.
+    pub const fn new(roles: Option<RpcRoles>) -> Self {
+        Self { roles }
+    }
+}
+
+impl<S> Layer<S> for RpcRoleLayer {
+    type Service = RpcRoleService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcRoleService { roles: self.roles.clone(), inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that rejects calls of methods that aren't allowed for the roles of
+/// the client.
+#[derive(Debug, Clone)]
+pub struct RpcRoleService<S> {
+    /// The enforced roles, if any.
+    roles: Option<RpcRoles>,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> RpcRoleService<S> {
+    /// Returns true if the roles of the client allow the call.
+    fn allows(&self, req: &Request<'_>) -> bool {
+        let Some(roles) = &self.roles else { return true };
+        let client_roles = req.extensions.get::<RpcClientRoles>();
+        let method = req.method_name();
+        if roles.allows(client_roles, method) {
+            return true
+        }
+
+        debug!(target: "rpc::roles", method, ?client_roles, "Rejected call of a forbidden method");
+        RpcRoleMetrics::new_with_labels(&[("method", method.to_string())])
+            .rejected_calls
+            .increment(1);
+        false
+    }
+}
+
+impl<S> RpcServiceT for RpcRoleService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = S::MethodResponse> + Send + 'a {
+        let call = if self.allows(&req) {
+            Ok(self.inner.call(req))
+        } else {
+            Err(MethodResponse::error(req.id(), method_not_permitted(req.method_name())))
+        };
+        async move {
+            match call {
+                Ok(fut) => fut.await,
+                Err(rejected) => rejected,
+            }
+        }
+    }
+
+    fn batch<'a>(
+        &self,
+        mut req: Batch<'a>,
+    ) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        for entry in req.iter_mut() {
+            let rejected = match entry {
+                Ok(BatchEntry::Call(call)) if !self.allows(call) => {
+                    Some(BatchEntryErr::new(call.id(), method_not_permitted(call.method_name())))
+                }
+                _ => None,
+            };
+            if let Some(rejected) = rejected {
+                *entry = Err(rejected);
+            }
+        }
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Metrics for calls rejected by role
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.roles")]
+struct RpcRoleMetrics {
+    /// The number of calls rejected because no role of the client permits the method
+    rejected_calls: Counter,
+}
+
+/// An HTTP middleware layer that attaches the roles of the JWT of requests as [`RpcClientRoles`] to
+/// the request extensions.
+#[derive(Debug, Clone)]
+pub struct RpcRoleClaimsLayer {
+    /// The configured roles.
+    roles: RpcRoles,
+}
+
+impl RpcRoleClaimsLayer {
+    /// Creates a new layer that reads the roles of requests as configured.
+    pub const fn new(roles: RpcRoles) -> Self {
+        Self { roles }
+    }
+}
+
+impl<S> Layer<S> for RpcRoleClaimsLayer {
+    type Service = RpcRoleClaimsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcRoleClaimsService { roles: self.roles.clone(), inner }
+    }
+}
+
+/// The HTTP service of the [`RpcRoleClaimsLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcRoleClaimsService<S> {
+    /// The configured roles.
+    roles: RpcRoles,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S, B> Service<HttpRequest<B>> for RpcRoleClaimsService<S>
+where
+    S: Service<HttpRequest<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest<B>) -> Self::Future {
+        if let Some(roles) = self.roles.roles(req.headers()) {
+            req.extensions_mut().insert(roles);
+        }
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde_json::json;
+    use std::collections::BTreeMap;
+
+    fn roles(secret: &JwtSecret) -> RpcRoles {
+        let config = RpcRolesConfig {
+            default_roles: vec!["read".to_string()],
+            roles: BTreeMap::from([
+                ("read".to_string(), vec!["eth_*".to_string()]),
+                ("trace".to_string(), vec!["trace_*".to_string()]),
+            ]),
+            ..Default::default()
+        };
+        RpcRoles::new(config, secret)
+    }
+
+    fn headers(secret: &JwtSecret, claims: Value) -> HeaderMap {
+        let token = jsonwebtoken::encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(secret.as_bytes()),
+        )
+        .unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, format!("Bearer {token}").parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn reads_roles_of_token() {
+        let secret = JwtSecret::random();
+        let roles = roles(&secret);
+
+        let client = roles.roles(&headers(&secret, json!({ "iat": 0, "roles": "trace" })));
+        assert!(roles.allows(client.as_ref(), "trace_block"));
+        assert!(!roles.allows(client.as_ref(), "eth_call"));
+
+        let client =
+            roles.roles(&headers(&secret, json!({ "iat": 0, "roles": ["read", "trace"] })));
+        assert!(roles.allows(client.as_ref(), "trace_block"));
+        assert!(roles.allows(client.as_ref(), "eth_call"));
+
+        // tokens without the claim have the default roles
+        let client = roles.roles(&headers(&secret, json!({ "iat": 0 })));
+        assert!(roles.allows(client.as_ref(), "eth_call"));
+        assert!(!roles.allows(client.as_ref(), "trace_block"));
+    }
+
+    #[test]
+    fn rejects_invalid_tokens() {
+        let secret = JwtSecret::random();
+        let roles = roles(&secret);
+
+        let client = roles.roles(&headers(&JwtSecret::random(), json!({ "roles": "trace" })));
+        assert_eq!(client, None);
+        assert!(!roles.allows(client.as_ref(), "eth_call"));
+
+        let client = roles.roles(&headers(&secret, json!({ "exp": 1, "roles": "trace" })));
+        assert_eq!(client, None);
+
+        assert_eq!(roles.roles(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn replaces_config() {
+        let secret = JwtSecret::random();
+        let roles = roles(&secret);
+        let client = roles.roles(&headers(&secret, json!({ "iat": 0, "roles": "trace" })));
+        assert!(!roles.allows(client.as_ref(), "debug_traceCall"));
+
+        let mut config = (*roles.config()).clone();
+        config.roles.insert("trace".to_string(), vec!["debug_trace*".to_string()]);
+        roles.set_config(config);
+        assert!(roles.allows(client.as_ref(), "debug_traceCall"));
+        assert!(!roles.allows(client.as_ref(), "trace_block"));
+    }
+}
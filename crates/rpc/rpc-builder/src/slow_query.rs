@@ -0,0 +1,194 @@
+//! [`jsonrpsee`] helper layer for logging slow RPC calls.
+//!
+//! Calls that take longer than the configured threshold are logged with their method, a summary
+//! of their params, the execution time, the size of the response and the block range the params
+//! refer to, so that operators can find the queries that degrade the node.
+
+use jsonrpsee::{
+    core::middleware::{Batch, Notification},
+    server::middleware::rpc::RpcServiceT,
+    types::Request,
+    MethodResponse,
+};
+use reth_metrics::{metrics::Counter, Metrics};
+use serde_json::{value::RawValue, Value};
+use std::{
+    future::Future,
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+use tower::Layer;
+use tracing::warn;
+
+/// The maximum length of the params summary of a slow call.
+const MAX_PARAMS_SUMMARY_LEN: usize = 256;
+
+/// The fields of object params that refer to a block.
+const BLOCK_FIELDS: [&str; 3] = ["fromBlock", "toBlock", "blockNumber"];
+
+/// RPC middleware layer that logs calls that took longer than the configured threshold, if any.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SlowQueryLayer {
+    /// The execution time above which calls are logged.
+    threshold: Option<Duration>,
+}
+
+impl SlowQueryLayer {
+    /// Creates a new layer that logs calls slower than the given threshold, if any.
+    pub const fn new(threshold: Option<Duration>) -> Self {
+        Self { threshold }
+    }
+}
+
+impl<S> Layer<S> for SlowQueryLayer {
+    type Service = SlowQueryService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SlowQueryService { threshold: self.threshold, inner }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that logs slow calls.
+#[derive(Debug, Clone)]
+pub struct SlowQueryService<S> {
+    /// The execution time above which calls are logged.
+    threshold: Option<Duration>,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> RpcServiceT for SlowQueryService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = S::MethodResponse> + Send + 'a {
+        // the method and params are usually borrowed from the request body, so this is cheap
+        let call =
+            self.threshold.map(|threshold| (threshold, req.method.clone(), req.params.clone()));
+        let fut = self.inner.call(req);
+        async move {
+            let started_at = Instant::now();
+            let resp = fut.await;
+            if let Some((threshold, method, params)) = call {
+                let elapsed = started_at.elapsed();
+                if elapsed > threshold {
+                    RpcSlowCallMetrics::new_with_labels(&[("method", method.to_string())])
+                        .slow_total
+                        .increment(1);
+                    warn!(
+                        target: "rpc::slow_query",
+                        %method,
+                        params = params_summary(params.as_deref()),
+                        ?elapsed,
+                        response_bytes = resp.as_json().get().len(),
+                        block_range = ?block_range(params.as_deref()),
+                        "Slow RPC call"
+                    );
+                }
+            }
+            resp
+        }
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        self.inner.batch(req)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// Returns the params of a call, truncated to [`MAX_PARAMS_SUMMARY_LEN`] bytes.
+fn params_summary(params: Option<&RawValue>) -> &str {
+    let Some(params) = params.map(RawValue::get) else { return "" };
+    if params.len() <= MAX_PARAMS_SUMMARY_LEN {
+        return params
+    }
+    let mut end = MAX_PARAMS_SUMMARY_LEN;
+    while !params.is_char_boundary(end) {
+        end -= 1;
+    }
+    &params[..end]
+}
+
+/// Returns the range of block numbers the params of a call refer to, if any.
+///
+/// Block numbers are hex quantities that are either positional params or one of the
+/// [`BLOCK_FIELDS`] of object params, e.g. the filter of `eth_getLogs`. Block tags such as
+/// `latest` are ignored.
+fn block_range(params: Option<&RawValue>) -> Option<RangeInclusive<u64>> {
+    let params = serde_json::from_str::<Value>(params?.get()).ok()?;
+    let Value::Array(params) = params else { return None };
+
+    let mut blocks = Vec::new();
+    for param in &params {
+        match param {
+            Value::String(_) => blocks.push(param),
+            Value::Object(fields) => {
+                blocks.extend(BLOCK_FIELDS.iter().filter_map(|field| fields.get(*field)))
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+        .into_iter()
+        .filter_map(|block| {
+            let hex = block.as_str()?.strip_prefix("0x")?;
+            // longer values are addresses or hashes
+            if hex.len() > 16 {
+                return None
+            }
+            u64::from_str_radix(hex, 16).ok()
+        })
+        .fold(None, |range, block| match range {
+            Some(range) => Some(block.min(*range.start())..=block.max(*range.end())),
+            None => Some(block..=block),
+        })
+}
+
+/// Metrics for slow RPC calls
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.calls")]
+struct RpcSlowCallMetrics {
+    /// The number of calls that took longer than the slow query threshold
+    slow_total: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(params: &str) -> Box<RawValue> {
+        RawValue::from_string(params.to_string()).unwrap()
+    }
+
+    #[test]
+    fn block_range_of_params() {
+        let params = raw(r#"[{"fromBlock":"0x10","toBlock":"0x20","address":"0x01"}]"#);
+        assert_eq!(block_range(Some(&*params)), Some(16..=32));
+
+        let params = raw(r#"["0x0000000000000000000000000000000000000001","0x5"]"#);
+        assert_eq!(block_range(Some(&*params)), Some(5..=5));
+
+        let params = raw(r#"["latest",true]"#);
+        assert_eq!(block_range(Some(&*params)), None);
+        assert_eq!(block_range(None), None);
+    }
+
+    #[test]
+    fn truncates_params_summary() {
+        let params = raw(&format!(r#"["{}"]"#, "ä".repeat(MAX_PARAMS_SUMMARY_LEN)));
+        let summary = params_summary(Some(&*params));
+        assert!(summary.len() <= MAX_PARAMS_SUMMARY_LEN);
+        assert!(params.get().starts_with(summary));
+    }
+}
@@ -0,0 +1,509 @@
+//! [`jsonrpsee`] helper layers for accounting the method usage of RPC consumers.
+//!
+//! Usage is tracked per connection and per API key. API keys are read from a configurable HTTP
+//! header by the [`RpcApiKeyLayer`] and attached to the requests, which are then accounted by the
+//! [`RpcUsageTracker`] layer. API keys are only reported and logged by their hash.
+
+use alloy_primitives::{hex, keccak256};
+use http::{HeaderName, Request as HttpRequest};
+use jsonrpsee::{
+    core::{
+        middleware::{Batch, BatchEntry, Notification},
+        RpcResult,
+    },
+    server::middleware::rpc::RpcServiceT,
+    types::Request,
+    BatchResponse, MethodResponse,
+};
+use reth_rpc_api::{RpcUsage, RpcUsageApiServer, RpcUsageReport};
+use reth_rpc_server_types::constants::DEFAULT_RPC_USAGE_API_KEY_HEADER;
+use schnellru::{ByLength, LruMap};
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::{Layer, Service};
+use tracing::info;
+
+/// The number of consumers included in the periodic usage summary.
+const SUMMARY_TOP_CONSUMERS: usize = 5;
+
+/// The maximum number of tracked API keys, above which the least recently used API key is evicted.
+const MAX_TRACKED_API_KEYS: u32 = 10_000;
+
+/// The maximum number of tracked methods per consumer, above which the least recently used method
+/// is evicted.
+///
+/// Method names are chosen by the caller, so the number of distinct methods is unbounded.
+const MAX_TRACKED_METHODS: u32 = 256;
+
+/// The API key of a request, attached to the request extensions by the [`RpcApiKeyLayer`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RpcApiKey(pub String);
+
+impl RpcApiKey {
+    /// Returns the hash of the API key, which identifies the API key in usage reports and logs
+    /// without revealing it.
+    pub fn redacted(&self) -> String {
+        hex::encode(&keccak256(self.0.as_bytes())[..8])
+    }
+}
+
+/// Tracks the method usage of RPC consumers, per connection and per API key.
+///
+/// Clones share the same usage. The usage is recorded by the [`RpcUsageLayer`] RPC middleware.
+#[derive(Debug, Clone)]
+pub struct RpcUsageTracker {
+    inner: Arc<RpcUsageTrackerInner>,
+}
+
+impl RpcUsageTracker {
+    /// Creates a new tracker that reads API keys from the given HTTP header.
+    pub fn new(api_key_header: HeaderName) -> Self {
+        Self {
+            inner: Arc::new(RpcUsageTrackerInner {
+                api_key_header,
+                next_connection_id: AtomicU64::new(0),
+                usage: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the HTTP middleware layer that attaches the API key of requests.
+    pub fn api_key_layer(&self) -> RpcApiKeyLayer {
+        RpcApiKeyLayer::new(self.inner.api_key_header.clone())
+    }
+
+    /// Returns the usage of all open connections and of the most recently used API keys, by the
+    /// hash of the API key.
+    pub fn report(&self) -> RpcUsageReport {
+        let usage = self.inner.usage.lock().unwrap();
+        RpcUsageReport {
+            connections: usage
+                .connections
+                .iter()
+                .map(|(id, usage)| (*id, usage.report()))
+                .collect(),
+            api_keys: usage
+                .api_keys
+                .iter()
+                .map(|(key, usage)| (key.clone(), usage.report()))
+                .collect(),
+        }
+    }
+
+    /// Endless future that logs a summary of the heaviest consumers every `interval`.
+    pub async fn log_summaries(self, interval: Duration) {
+        let mut interval =
+            tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+        loop {
+            interval.tick().await;
+            self.log_summary();
+        }
+    }
+
+    /// Logs the consumers with the highest cumulative execution time.
+    fn log_summary(&self) {
+        let RpcUsageReport { connections, api_keys } = self.report();
+        for (connection, usage) in heaviest(connections) {
+            info!(target: "rpc::usage", connection, calls=usage.calls, response_bytes=usage.response_bytes, execution_time=?Duration::from_micros(usage.execution_time_micros), "Heavy RPC connection");
+        }
+        for (api_key_hash, usage) in heaviest(api_keys) {
+            info!(target: "rpc::usage", %api_key_hash, calls=usage.calls, response_bytes=usage.response_bytes, execution_time=?Duration::from_micros(usage.execution_time_micros), "Heavy RPC API key");
+        }
+    }
+
+    /// Records finished calls of the given methods, e.g. a single call or the calls of a batch,
+    /// with their combined response size and execution time.
+    fn record(
+        &self,
+        connection: u64,
+        api_key: Option<&RpcApiKey>,
+        methods: &[String],
+        response_bytes: usize,
+        elapsed: Duration,
+    ) {
+        let mut usage = self.inner.usage.lock().unwrap();
+        let UsageState { connections, api_keys } = &mut *usage;
+        if let Some(usage) = connections.get_mut(&connection) {
+            usage.record(methods, response_bytes, elapsed);
+        }
+        if let Some(api_key) = api_key {
+            if let Some(usage) = api_keys.get_or_insert(api_key.redacted(), ConsumerUsage::default)
+            {
+                usage.record(methods, response_bytes, elapsed);
+            }
+        }
+    }
+}
+
+impl Default for RpcUsageTracker {
+    fn default() -> Self {
+        Self::new(HeaderName::from_static(DEFAULT_RPC_USAGE_API_KEY_HEADER))
+    }
+}
+
+impl RpcUsageApiServer for RpcUsageTracker {
+    /// Handler for `admin_rpcUsage`
+    fn rpc_usage(&self) -> RpcResult<RpcUsageReport> {
+        Ok(self.report())
+    }
+}
+
+#[derive(Debug)]
+struct RpcUsageTrackerInner {
+    /// The HTTP header that carries the API key of a request.
+    api_key_header: HeaderName,
+    /// The id of the next connection.
+    next_connection_id: AtomicU64,
+    /// The tracked usage.
+    usage: Mutex<UsageState>,
+}
+
+#[derive(Debug)]
+struct UsageState {
+    /// The usage of open connections.
+    connections: BTreeMap<u64, ConsumerUsage>,
+    /// The usage of API keys, by the hash of the API key.
+    ///
+    /// The least recently used API key is evicted once [`MAX_TRACKED_API_KEYS`] are tracked.
+    api_keys: LruMap<String, ConsumerUsage, ByLength>,
+}
+
+impl Default for UsageState {
+    fn default() -> Self {
+        Self {
+            connections: BTreeMap::new(),
+            api_keys: LruMap::new(ByLength::new(MAX_TRACKED_API_KEYS)),
+        }
+    }
+}
+
+/// The usage of a single consumer.
+#[derive(Debug)]
+struct ConsumerUsage {
+    /// The total number of method calls.
+    calls: u64,
+    /// The total size of the serialized responses, in bytes.
+    response_bytes: u64,
+    /// The cumulative time spent executing the calls, in microseconds.
+    execution_time_micros: u64,
+    /// The number of calls per method.
+    ///
+    /// The least recently called method is evicted once [`MAX_TRACKED_METHODS`] are tracked.
+    methods: LruMap<String, u64, ByLength>,
+}
+
+impl ConsumerUsage {
+    /// Adds finished calls of the given methods to the usage.
+    fn record(&mut self, methods: &[String], response_bytes: usize, elapsed: Duration) {
+        self.calls += methods.len() as u64;
+        self.response_bytes += response_bytes as u64;
+        self.execution_time_micros += elapsed.as_micros() as u64;
+        for method in methods {
+            match self.methods.get(method) {
+                Some(calls) => *calls += 1,
+                None => {
+                    self.methods.insert(method.clone(), 1);
+                }
+            }
+        }
+    }
+
+    /// Returns the usage as reported by `admin_rpcUsage`.
+    fn report(&self) -> RpcUsage {
+        RpcUsage {
+            calls: self.calls,
+            response_bytes: self.response_bytes,
+            execution_time_micros: self.execution_time_micros,
+            methods: self.methods.iter().map(|(method, calls)| (method.clone(), *calls)).collect(),
+        }
+    }
+}
+
+impl Default for ConsumerUsage {
+    fn default() -> Self {
+        Self {
+            calls: 0,
+            response_bytes: 0,
+            execution_time_micros: 0,
+            methods: LruMap::new(ByLength::new(MAX_TRACKED_METHODS)),
+        }
+    }
+}
+
+/// Returns the consumers with the highest cumulative execution time, heaviest first.
+fn heaviest<K>(usage: BTreeMap<K, RpcUsage>) -> impl Iterator<Item = (K, RpcUsage)> {
+    let mut usage = usage.into_iter().collect::<Vec<_>>();
+    usage.sort_unstable_by_key(|(_, usage)| std::cmp::Reverse(usage.execution_time_micros));
+    usage.into_iter().take(SUMMARY_TOP_CONSUMERS)
+}
+
+/// RPC middleware layer that records the method usage of every connection in the configured
+/// [`RpcUsageTracker`], if any.
+#[derive(Debug, Clone, Default)]
+pub struct RpcUsageLayer {
+    /// The tracker to record the usage in.
+    tracker: Option<RpcUsageTracker>,
+}
+
+impl RpcUsageLayer {
+    /// Creates a new layer that records usage in the given tracker, if any.
+    pub const fn new(tracker: Option<RpcUsageTracker>) -> Self {
+        Self { tracker }
+    }
+}
+
+impl<S> Layer<S> for RpcUsageLayer {
+    type Service = RpcUsageService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcUsageService::new(inner, self.tracker.clone())
+    }
+}
+
+/// A [`RpcServiceT`] middleware that records the method usage of a single connection.
+#[derive(Debug, Clone)]
+pub struct RpcUsageService<S> {
+    /// The connection this service was created for, if usage is tracked.
+    connection: Option<Arc<ConnectionGuard>>,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> RpcUsageService<S> {
+    fn new(inner: S, tracker: Option<RpcUsageTracker>) -> Self {
+        // this instance is kept alive for the duration of the connection
+        let connection = tracker.map(|tracker| {
+            let id = tracker.inner.next_connection_id.fetch_add(1, Ordering::Relaxed);
+            tracker.inner.usage.lock().unwrap().connections.insert(id, ConsumerUsage::default());
+            Arc::new(ConnectionGuard { id, tracker })
+        });
+        Self { connection, inner }
+    }
+}
+
+impl<S> RpcServiceT for RpcUsageService<S>
+where
+    S: RpcServiceT<MethodResponse = MethodResponse> + Send + Sync + Clone + 'static,
+    S::BatchResponse: ResponseSize,
+{
+    type MethodResponse = S::MethodResponse;
+    type NotificationResponse = S::NotificationResponse;
+    type BatchResponse = S::BatchResponse;
+
+    fn call<'a>(&self, req: Request<'a>) -> impl Future<Output = S::MethodResponse> + Send + 'a {
+        let call = self.connection.clone().map(|connection| {
+            let api_key = req.extensions.get::<RpcApiKey>().cloned();
+            (connection, vec![req.method_name().to_string()], api_key)
+        });
+        let fut = self.inner.call(req);
+        record_response(call, fut)
+    }
+
+    fn batch<'a>(&self, req: Batch<'a>) -> impl Future<Output = Self::BatchResponse> + Send + 'a {
+        let calls = self.connection.clone().map(|connection| {
+            let mut api_key = None;
+            let mut methods = Vec::new();
+            for entry in req.iter() {
+                if let Ok(BatchEntry::Call(call)) = entry {
+                    api_key = api_key.or_else(|| call.extensions.get::<RpcApiKey>().cloned());
+                    methods.push(call.method_name().to_string());
+                }
+            }
+            (connection, methods, api_key)
+        });
+        let fut = self.inner.batch(req);
+        record_response(calls, fut)
+    }
+
+    fn notification<'a>(
+        &self,
+        n: Notification<'a>,
+    ) -> impl Future<Output = Self::NotificationResponse> + Send + 'a {
+        self.inner.notification(n)
+    }
+}
+
+/// A response whose serialized size is recorded by the [`RpcUsageService`].
+///
+/// The http and ws servers answer batches with a [`MethodResponse`], the ipc server with a
+/// [`BatchResponse`].
+pub trait ResponseSize {
+    /// Returns the size of the serialized response in bytes.
+    fn response_size(&self) -> usize;
+}
+
+impl ResponseSize for MethodResponse {
+    fn response_size(&self) -> usize {
+        self.as_json().get().len()
+    }
+}
+
+impl ResponseSize for BatchResponse {
+    fn response_size(&self) -> usize {
+        MethodResponse::from_batch(self.clone()).response_size()
+    }
+}
+
+/// Awaits the response to the given calls and records them with the size of the response and the
+/// time it took.
+async fn record_response<F, R>(
+    calls: Option<(Arc<ConnectionGuard>, Vec<String>, Option<RpcApiKey>)>,
+    fut: F,
+) -> R
+where
+    F: Future<Output = R>,
+    R: ResponseSize,
+{
+    let started_at = Instant::now();
+    let resp = fut.await;
+    if let Some((connection, methods, api_key)) = calls {
+        connection.tracker.record(
+            connection.id,
+            api_key.as_ref(),
+            &methods,
+            resp.response_size(),
+            started_at.elapsed(),
+        );
+    }
+    resp
+}
+
+/// Removes the usage of a connection once the connection is closed.
+#[derive(Debug)]
+struct ConnectionGuard {
+    /// The id of the connection.
+    id: u64,
+    /// The tracker of the connection's usage.
+    tracker: RpcUsageTracker,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.tracker.inner.usage.lock().unwrap().connections.remove(&self.id);
+    }
+}
+
+/// An HTTP middleware layer that attaches the API key header of requests as [`RpcApiKey`] to the
+/// request extensions.
+#[derive(Debug, Clone)]
+pub struct RpcApiKeyLayer {
+    /// The HTTP header that carries the API key.
+    header: HeaderName,
+}
+
+impl RpcApiKeyLayer {
+    /// Creates a new layer that reads the API key from the given header.
+    pub const fn new(header: HeaderName) -> Self {
+        Self { header }
+    }
+}
+
+impl<S> Layer<S> for RpcApiKeyLayer {
+    type Service = RpcApiKeyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcApiKeyService { header: self.header.clone(), inner }
+    }
+}
+
+/// The HTTP service of the [`RpcApiKeyLayer`].
+#[derive(Debug, Clone)]
+pub struct RpcApiKeyService<S> {
+    /// The HTTP header that carries the API key.
+    header: HeaderName,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S, B> Service<HttpRequest<B>> for RpcApiKeyService<S>
+where
+    S: Service<HttpRequest<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: HttpRequest<B>) -> Self::Future {
+        if let Some(api_key) = req.headers().get(&self.header).and_then(|v| v.to_str().ok()) {
+            let api_key = RpcApiKey(api_key.to_string());
+            req.extensions_mut().insert(api_key);
+        }
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_usage_per_connection_and_api_key() {
+        let tracker = RpcUsageTracker::default();
+        let service = RpcUsageLayer::new(Some(tracker.clone())).layer(());
+        let api_key = RpcApiKey("key".to_string());
+
+        let call = ["eth_call".to_string()];
+
+        tracker.record(0, Some(&api_key), &call, 10, Duration::from_micros(5));
+        tracker.record(0, None, &call, 10, Duration::from_micros(5));
+
+        let report = tracker.report();
+        assert_eq!(report.connections[&0].calls, 2);
+        assert_eq!(report.connections[&0].response_bytes, 20);
+        assert_eq!(report.connections[&0].methods["eth_call"], 2);
+
+        // API keys are only reported by their hash
+        assert!(!report.api_keys.contains_key("key"));
+        let key = api_key.redacted();
+        assert_eq!(report.api_keys[&key].calls, 1);
+        assert_eq!(report.api_keys[&key].execution_time_micros, 5);
+
+        // closing the connection drops its usage, but keeps the API key usage
+        drop(service);
+        let report = tracker.report();
+        assert!(report.connections.is_empty());
+        assert_eq!(report.api_keys[&key].calls, 1);
+    }
+
+    #[test]
+    fn records_every_call_of_a_batch() {
+        let tracker = RpcUsageTracker::default();
+        let _service = RpcUsageLayer::new(Some(tracker.clone())).layer(());
+        let batch = ["eth_call".to_string(), "eth_call".to_string(), "eth_getLogs".to_string()];
+
+        tracker.record(0, None, &batch, 30, Duration::from_micros(5));
+
+        let report = tracker.report();
+        assert_eq!(report.connections[&0].calls, 3);
+        assert_eq!(report.connections[&0].response_bytes, 30);
+        assert_eq!(report.connections[&0].methods["eth_call"], 2);
+        assert_eq!(report.connections[&0].methods["eth_getLogs"], 1);
+    }
+
+    #[test]
+    fn bounds_tracked_methods() {
+        let tracker = RpcUsageTracker::default();
+        let _service = RpcUsageLayer::new(Some(tracker.clone())).layer(());
+
+        for i in 0..MAX_TRACKED_METHODS + 1 {
+            tracker.record(0, None, &[format!("method_{i}")], 0, Duration::ZERO);
+        }
+
+        let report = tracker.report();
+        assert_eq!(report.connections[&0].calls, MAX_TRACKED_METHODS as u64 + 1);
+        assert_eq!(report.connections[&0].methods.len(), MAX_TRACKED_METHODS as usize);
+        assert!(!report.connections[&0].methods.contains_key("method_0"));
+    }
+}
@@ -22,8 +22,8 @@ use reth_ethereum_primitives::Receipt;
 use reth_network_peers::NodeRecord;
 use reth_rpc_api::{
     clients::{AdminApiClient, EthApiClient},
-    DebugApiClient, EthCallBundleApiClient, EthFilterApiClient, NetApiClient, OtterscanClient,
-    TraceApiClient, Web3ApiClient,
+    AdminDiscoveryPorts, DebugApiClient, EthCallBundleApiClient, EthFilterApiClient, NetApiClient,
+    OtterscanClient, TraceApiClient, Web3ApiClient,
 };
 use reth_rpc_server_types::RethRpcModule;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -145,7 +145,8 @@ where
     AdminApiClient::remove_peer(client, node.into()).await.unwrap();
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
-    AdminApiClient::node_info(client).await.unwrap();
+    let info = AdminApiClient::node_info(client).await.unwrap();
+    assert_eq!(info.discovery_ports, AdminDiscoveryPorts::default());
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -311,6 +312,8 @@ where
         call_request.clone(),
         Some(block_number.into()),
         None,
+        None,
+        None,
     )
     .await
     .unwrap_err();
@@ -524,28 +527,24 @@ where
     .err()
     .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_before(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::<Transaction, Header>::search_transactions_after(
-            client,
-            address,
-            LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
-            page_size,
-        )
-        .await
-        .err()
-        .unwrap()
-    ));
+    let before = OtterscanClient::<Transaction, Header>::search_transactions_before(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .unwrap();
+    assert!(before.txs.is_empty() && before.last_page);
+    let after = OtterscanClient::<Transaction, Header>::search_transactions_after(
+        client,
+        address,
+        LenientBlockNumberOrTag::new(BlockNumberOrTag::Number(block_number)),
+        page_size,
+    )
+    .await
+    .unwrap();
+    assert!(after.txs.is_empty() && after.first_page);
     assert!(OtterscanClient::<Transaction, Header>::get_transaction_by_sender_and_nonce(
         client, sender, nonce
     )
@@ -583,6 +582,7 @@ async fn test_call_admin_functions_ws() {
     let handle = launch_ws(vec![RethRpcModule::Admin]).await;
     let client = handle.ws_client().await.unwrap();
     test_basic_admin_calls(&client).await;
+    AdminApiClient::subscribe_peer_events(&client).await.unwrap();
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -54,6 +54,7 @@ pub async fn launch_auth(secret: JwtSecret) -> AuthServerHandle {
         EngineCapabilities::default(),
         EthereumEngineValidator::new(MAINNET.clone()),
         false,
+        None,
     );
     let module = AuthRpcModule::new(engine_api);
     module.start_server(config).await.unwrap()
@@ -0,0 +1,144 @@
+use alloy_primitives::B256;
+use reqwest::{Client, StatusCode};
+use std::time::Duration;
+use tracing::warn;
+use url::Url;
+
+/// The default timeout of beacon API requests.
+///
+/// The verification runs before a payload is processed, so this bounds the latency it adds to
+/// `engine_newPayload` if the beacon node is slow or unreachable.
+pub const DEFAULT_BEACON_API_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The outcome of cross-checking a parent beacon block root against the beacon API, see
+/// [`ParentBeaconBlockRootVerifier::verify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParentBeaconBlockRootStatus {
+    /// The beacon node knows the beacon block.
+    Known,
+    /// The beacon node doesn't know the beacon block.
+    Unknown,
+    /// The beacon API returned an error, couldn't be reached or didn't respond in time.
+    Unavailable,
+}
+
+/// Cross-checks the parent beacon block root of incoming payloads against the consensus layer's
+/// view, using the beacon API of the beacon node.
+///
+/// This helps to detect engine API confusions, e.g. a misconfigured consensus client sending
+/// payloads that are not part of the beacon chain of the beacon node.
+///
+/// The result is only reported and never affects the status of a payload: a beacon node that is
+/// lagging behind or follows a different fork may not know the parent beacon block of a valid
+/// payload, and invalid payloads are final for the consensus layer.
+#[derive(Debug, Clone)]
+pub struct ParentBeaconBlockRootVerifier {
+    http_client: Client,
+    beacon_api_url: Url,
+}
+
+impl ParentBeaconBlockRootVerifier {
+    /// Creates a new verifier that queries the beacon API at the given URL, with the
+    /// [`DEFAULT_BEACON_API_TIMEOUT`].
+    pub fn new(beacon_api_url: Url) -> Self {
+        Self::with_timeout(beacon_api_url, DEFAULT_BEACON_API_TIMEOUT)
+    }
+
+    /// Creates a new verifier that queries the beacon API at the given URL, giving up on requests
+    /// that take longer than `timeout`.
+    pub fn with_timeout(beacon_api_url: Url, timeout: Duration) -> Self {
+        let http_client =
+            Client::builder().timeout(timeout).build().unwrap_or_else(|_| Client::new());
+        Self { http_client, beacon_api_url }
+    }
+
+    /// Queries the beacon node for the beacon block with the given root.
+    pub async fn verify(&self, parent_beacon_block_root: B256) -> ParentBeaconBlockRootStatus {
+        let Ok(url) =
+            self.beacon_api_url.join(&format!("eth/v1/beacon/headers/{parent_beacon_block_root}"))
+        else {
+            return ParentBeaconBlockRootStatus::Unavailable
+        };
+
+        match self.http_client.get(url).send().await {
+            Ok(resp) if resp.status() == StatusCode::NOT_FOUND => {
+                ParentBeaconBlockRootStatus::Unknown
+            }
+            Ok(resp) => match resp.error_for_status() {
+                Ok(_) => ParentBeaconBlockRootStatus::Known,
+                Err(err) => {
+                    warn!(target: "rpc::engine", %err, %parent_beacon_block_root, "Failed to verify parent beacon block root");
+                    ParentBeaconBlockRootStatus::Unavailable
+                }
+            },
+            Err(err) => {
+                warn!(target: "rpc::engine", %err, %parent_beacon_block_root, "Failed to reach beacon API to verify parent beacon block root");
+                ParentBeaconBlockRootStatus::Unavailable
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    /// Spawns a beacon API that answers every request with the given status line, or never
+    /// answers if `None`.
+    async fn spawn_beacon_api(status: Option<&'static str>) -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap()).parse().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let _ = stream.read(&mut buf).await;
+                    match status {
+                        Some(status) => {
+                            let response =
+                                format!("HTTP/1.1 {status}\r\ncontent-length: 2\r\n\r\n{{}}");
+                            let _ = stream.write_all(response.as_bytes()).await;
+                        }
+                        None => std::future::pending().await,
+                    }
+                });
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn known_root() {
+        let verifier = ParentBeaconBlockRootVerifier::new(spawn_beacon_api(Some("200 OK")).await);
+        assert_eq!(verifier.verify(B256::ZERO).await, ParentBeaconBlockRootStatus::Known);
+    }
+
+    #[tokio::test]
+    async fn unknown_root() {
+        let verifier =
+            ParentBeaconBlockRootVerifier::new(spawn_beacon_api(Some("404 Not Found")).await);
+        assert_eq!(verifier.verify(B256::ZERO).await, ParentBeaconBlockRootStatus::Unknown);
+    }
+
+    #[tokio::test]
+    async fn beacon_api_error() {
+        let verifier = ParentBeaconBlockRootVerifier::new(
+            spawn_beacon_api(Some("500 Internal Server Error")).await,
+        );
+        assert_eq!(verifier.verify(B256::ZERO).await, ParentBeaconBlockRootStatus::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn beacon_api_timeout() {
+        let verifier = ParentBeaconBlockRootVerifier::with_timeout(
+            spawn_beacon_api(None).await,
+            Duration::from_millis(50),
+        );
+        assert_eq!(verifier.verify(B256::ZERO).await, ParentBeaconBlockRootStatus::Unavailable);
+    }
+}
@@ -15,15 +15,17 @@ pub const CAPABILITIES: &[&str] = &[
     "engine_newPayloadV2",
     "engine_newPayloadV3",
     "engine_newPayloadV4",
+    "engine_newPayloadV5",
     "engine_getPayloadBodiesByHashV1",
     "engine_getPayloadBodiesByRangeV1",
     "engine_getBlobsV1",
     "engine_getBlobsV2",
+    "engine_getBlobsV3",
 ];
 
 // The list of all supported Engine capabilities available over the engine endpoint.
 ///
-/// Latest spec: Prague
+/// Latest spec: Osaka
 #[derive(Debug, Clone)]
 pub struct EngineCapabilities {
     inner: HashSet<String>,
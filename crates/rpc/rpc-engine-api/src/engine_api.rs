@@ -1,5 +1,6 @@
 use crate::{
     capabilities::EngineCapabilities, metrics::EngineApiMetrics, EngineApiError, EngineApiResult,
+    ParentBeaconBlockRootStatus, ParentBeaconBlockRootVerifier,
 };
 use alloy_eips::{
     eip1898::BlockHashOrNumber,
@@ -83,6 +84,7 @@ where
         capabilities: EngineCapabilities,
         validator: Validator,
         accept_execution_requests_hash: bool,
+        parent_beacon_block_root_verifier: Option<ParentBeaconBlockRootVerifier>,
     ) -> Self {
         let inner = Arc::new(EngineApiInner {
             provider,
@@ -97,6 +99,7 @@ where
             validator,
             latest_new_payload_response: Mutex::new(None),
             accept_execution_requests_hash,
+            parent_beacon_block_root_verifier,
         });
         Self { inner }
     }
@@ -211,6 +214,8 @@ where
             .validator
             .validate_version_specific_fields(EngineApiMessageVersion::V3, payload_or_attrs)?;
 
+        self.inner.spawn_parent_beacon_block_root_verification(&payload);
+
         Ok(self
             .inner
             .beacon_consensus
@@ -248,6 +253,8 @@ where
             .validator
             .validate_version_specific_fields(EngineApiMessageVersion::V4, payload_or_attrs)?;
 
+        self.inner.spawn_parent_beacon_block_root_verification(&payload);
+
         Ok(self
             .inner
             .beacon_consensus
@@ -271,6 +278,46 @@ where
         self.inner.metrics.new_payload_response.update_response_metrics(&res, gas_used, elapsed);
         Ok(res?)
     }
+
+    /// Same as `new_payload_v4`, but only accepts payloads of the Osaka fork.
+    pub async fn new_payload_v5(
+        &self,
+        payload: PayloadT::ExecutionData,
+    ) -> EngineApiResult<PayloadStatus> {
+        let payload_or_attrs = PayloadOrAttributes::<
+            '_,
+            PayloadT::ExecutionData,
+            PayloadT::PayloadAttributes,
+        >::from_execution_payload(&payload);
+        self.inner
+            .validator
+            .validate_version_specific_fields(EngineApiMessageVersion::V5, payload_or_attrs)?;
+
+        self.inner.spawn_parent_beacon_block_root_verification(&payload);
+
+        Ok(self
+            .inner
+            .beacon_consensus
+            .new_payload(payload)
+            .await
+            .inspect(|_| self.inner.on_new_payload_response())?)
+    }
+
+    /// Metrics version of `new_payload_v5`
+    pub async fn new_payload_v5_metered(
+        &self,
+        payload: PayloadT::ExecutionData,
+    ) -> RpcResult<PayloadStatus> {
+        let start = Instant::now();
+        let gas_used = payload.gas_used();
+
+        let res = Self::new_payload_v5(self, payload).await;
+
+        let elapsed = start.elapsed();
+        self.inner.metrics.latency.new_payload_v5.record(elapsed);
+        self.inner.metrics.new_payload_response.update_response_metrics(&res, gas_used, elapsed);
+        Ok(res?)
+    }
 }
 
 impl<Provider, EngineT, Pool, Validator, ChainSpec>
@@ -822,6 +869,40 @@ where
 
         res
     }
+
+    fn get_blobs_v3(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> EngineApiResult<Vec<Option<BlobAndProofV2>>> {
+        if versioned_hashes.len() > MAX_BLOB_LIMIT {
+            return Err(EngineApiError::BlobRequestTooLarge { len: versioned_hashes.len() })
+        }
+
+        self.inner
+            .tx_pool
+            .get_blobs_for_versioned_hashes_v3(&versioned_hashes)
+            .map_err(|err| EngineApiError::Internal(Box::new(err)))
+    }
+
+    fn get_blobs_v3_metered(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> EngineApiResult<Vec<Option<BlobAndProofV2>>> {
+        let hashes_len = versioned_hashes.len();
+        let start = Instant::now();
+        let res = Self::get_blobs_v3(self, versioned_hashes);
+        self.inner.metrics.latency.get_blobs_v3.record(start.elapsed());
+
+        if let Ok(blobs) = &res {
+            let blobs_found = blobs.iter().flatten().count();
+            let blobs_missed = hashes_len - blobs_found;
+
+            self.inner.metrics.blob_metrics.blob_count.increment(blobs_found as u64);
+            self.inner.metrics.blob_metrics.blob_misses.increment(blobs_missed as u64);
+        }
+
+        res
+    }
 }
 
 // This is the concrete ethereum engine API implementation.
@@ -904,6 +985,34 @@ where
         Ok(self.new_payload_v4_metered(payload).await?)
     }
 
+    /// Handler for `engine_newPayloadV5`
+    ///
+    /// Same as `engine_newPayloadV4`, but only accepts payloads of the Osaka fork.
+    async fn new_payload_v5(
+        &self,
+        payload: ExecutionPayloadV3,
+        versioned_hashes: Vec<B256>,
+        parent_beacon_block_root: B256,
+        requests: RequestsOrHash,
+    ) -> RpcResult<PayloadStatus> {
+        trace!(target: "rpc::engine", "Serving engine_newPayloadV5");
+
+        // Accept requests as a hash only if it is explicitly allowed
+        if requests.is_hash() && !self.inner.accept_execution_requests_hash {
+            return Err(EngineApiError::UnexpectedRequestsHash.into());
+        }
+
+        let payload = ExecutionData {
+            payload: payload.into(),
+            sidecar: ExecutionPayloadSidecar::v4(
+                CancunPayloadFields { versioned_hashes, parent_beacon_block_root },
+                PraguePayloadFields { requests },
+            ),
+        };
+
+        Ok(self.new_payload_v5_metered(payload).await?)
+    }
+
     /// Handler for `engine_forkchoiceUpdatedV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_forkchoiceupdatedv1>
     ///
@@ -1094,6 +1203,14 @@ where
         trace!(target: "rpc::engine", "Serving engine_getBlobsV2");
         Ok(self.get_blobs_v2_metered(versioned_hashes)?)
     }
+
+    async fn get_blobs_v3(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<BlobAndProofV2>>> {
+        trace!(target: "rpc::engine", "Serving engine_getBlobsV3");
+        Ok(self.get_blobs_v3_metered(versioned_hashes)?)
+    }
 }
 
 impl<Provider, EngineT, Pool, Validator, ChainSpec> IntoEngineApiRpcModule
@@ -1152,6 +1269,8 @@ struct EngineApiInner<Provider, PayloadT: PayloadTypes, Pool, Validator, ChainSp
     /// Start time of the latest payload request
     latest_new_payload_response: Mutex<Option<Instant>>,
     accept_execution_requests_hash: bool,
+    /// Verifies the parent beacon block root of incoming payloads against the consensus layer.
+    parent_beacon_block_root_verifier: Option<ParentBeaconBlockRootVerifier>,
 }
 
 impl<Provider, PayloadT, Pool, Validator, ChainSpec>
@@ -1172,6 +1291,35 @@ where
     fn on_new_payload_response(&self) {
         self.latest_new_payload_response.lock().replace(Instant::now());
     }
+
+    /// Spawns the verification of the parent beacon block root of the payload against the
+    /// consensus layer, if a [`ParentBeaconBlockRootVerifier`] is configured.
+    ///
+    /// Roots that are unknown to the consensus layer are only reported, the payload is processed
+    /// without waiting for the beacon node.
+    fn spawn_parent_beacon_block_root_verification(&self, payload: &PayloadT::ExecutionData) {
+        let (Some(verifier), Some(parent_beacon_block_root)) =
+            (&self.parent_beacon_block_root_verifier, payload.parent_beacon_block_root())
+        else {
+            return
+        };
+
+        let verifier = verifier.clone();
+        let block_hash = payload.block_hash();
+        let metrics = self.metrics.parent_beacon_block_root.clone();
+        self.task_spawner.spawn(Box::pin(async move {
+            match verifier.verify(parent_beacon_block_root).await {
+                ParentBeaconBlockRootStatus::Known => {}
+                ParentBeaconBlockRootStatus::Unknown => {
+                    warn!(target: "rpc::engine", %block_hash, %parent_beacon_block_root, "Parent beacon block root of payload is unknown to the beacon node");
+                    metrics.unknown.increment(1);
+                }
+                ParentBeaconBlockRootStatus::Unavailable => {
+                    metrics.unavailable.increment(1);
+                }
+            }
+        }));
+    }
 }
 
 #[cfg(test)]
@@ -1223,6 +1371,7 @@ mod tests {
             EngineCapabilities::default(),
             EthereumEngineValidator::new(chain_spec.clone()),
             false,
+            None,
         );
         let handle = EngineApiTestHandle { chain_spec, provider, from_api: engine_rx };
         (handle, api)
@@ -22,6 +22,12 @@ mod error;
 /// Engine API metrics.
 mod metrics;
 
+/// Verification of parent beacon block roots against the beacon API.
+mod beacon;
+pub use beacon::{
+    ParentBeaconBlockRootStatus, ParentBeaconBlockRootVerifier, DEFAULT_BEACON_API_TIMEOUT,
+};
+
 pub use engine_api::{EngineApi, EngineApiSender};
 pub use error::*;
 
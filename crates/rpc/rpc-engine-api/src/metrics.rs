@@ -16,6 +16,8 @@ pub(crate) struct EngineApiMetrics {
     pub(crate) new_payload_response: NewPayloadStatusResponseMetrics,
     /// Blob-related metrics
     pub(crate) blob_metrics: BlobMetrics,
+    /// Parent beacon block root verification metrics
+    pub(crate) parent_beacon_block_root: ParentBeaconBlockRootMetrics,
 }
 
 /// Beacon consensus engine latency metrics.
@@ -30,6 +32,8 @@ pub(crate) struct EngineApiLatencyMetrics {
     pub(crate) new_payload_v3: Histogram,
     /// Latency for `engine_newPayloadV4`
     pub(crate) new_payload_v4: Histogram,
+    /// Latency for `engine_newPayloadV5`
+    pub(crate) new_payload_v5: Histogram,
     /// Latency for `engine_forkchoiceUpdatedV1`
     pub(crate) fork_choice_updated_v1: Histogram,
     /// Latency for `engine_forkchoiceUpdatedV2`
@@ -56,6 +60,8 @@ pub(crate) struct EngineApiLatencyMetrics {
     pub(crate) get_blobs_v1: Histogram,
     /// Latency for `engine_getBlobsV2`
     pub(crate) get_blobs_v2: Histogram,
+    /// Latency for `engine_getBlobsV3`
+    pub(crate) get_blobs_v3: Histogram,
 }
 
 /// Metrics for engine API forkchoiceUpdated responses.
@@ -127,6 +133,17 @@ pub(crate) struct BlobMetrics {
     pub(crate) get_blobs_requests_failure_total: Counter,
 }
 
+/// Metrics for the verification of parent beacon block roots against the beacon API.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "engine.rpc.parent_beacon_block_root")]
+pub(crate) struct ParentBeaconBlockRootMetrics {
+    /// Count of parent beacon block roots that are unknown to the beacon node
+    pub(crate) unknown: Counter,
+    /// Count of parent beacon block roots that couldn't be verified, e.g. because the beacon API
+    /// returned an error or timed out
+    pub(crate) unavailable: Counter,
+}
+
 impl NewPayloadStatusResponseMetrics {
     /// Increment the newPayload counter based on the given rpc result
     pub(crate) fn update_response_metrics(
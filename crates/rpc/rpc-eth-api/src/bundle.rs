@@ -8,6 +8,7 @@ use alloy_rpc_types_mev::{
     EthCancelBundle, EthSendBundle, PrivateTransactionRequest,
 };
 use jsonrpsee::proc_macros::rpc;
+use reth_rpc_eth_types::bundle::ForwardedBundleStatus;
 
 /// A subset of the [EthBundleApi] API interface that only supports `eth_callBundle`.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
@@ -70,3 +71,18 @@ pub trait EthBundleApi {
         request: CancelPrivateTransactionRequest,
     ) -> jsonrpsee::core::RpcResult<bool>;
 }
+
+/// Status of the bundles that were forwarded to external builders via `eth_sendBundle`.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthBundleStatusApi {
+    /// Returns the local simulation result and the forwarding status at every endpoint of a
+    /// bundle that was sent via `eth_sendBundle`.
+    ///
+    /// Returns `None` if the bundle is unknown, e.g. because it was evicted.
+    #[method(name = "getBundleStatus")]
+    async fn bundle_status(
+        &self,
+        bundle_hash: B256,
+    ) -> jsonrpsee::core::RpcResult<Option<ForwardedBundleStatus>>;
+}
@@ -1,22 +1,28 @@
 //! Implementation of the [`jsonrpsee`] generated [`EthApiServer`] trait. Handles RPC requests for
 //! the `eth_` namespace.
 use crate::{
-    helpers::{EthApiSpec, EthBlocks, EthCall, EthFees, EthState, EthTransactions, FullEthApi},
+    helpers::{
+        Call, EthApiSpec, EthBlocks, EthCall, EthFees, EthState, EthTransactions, FullEthApi,
+    },
     RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
 };
 use alloy_dyn_abi::TypedData;
-use alloy_eips::{eip2930::AccessListResult, BlockId, BlockNumberOrTag};
+use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Address, Bytes, B256, B64, U256, U64};
 use alloy_rpc_types_eth::{
     simulate::{SimulatePayload, SimulatedBlock},
     state::{EvmOverrides, StateOverride},
-    BlockOverrides, Bundle, EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Index,
-    StateContext, SyncStatus, TransactionRequest, Work,
+    BlockOverrides, Bundle, EthCallResponse, FeeHistory, Index, StateContext, SyncStatus,
+    TransactionRequest, Work,
 };
 use alloy_serde::JsonStorageKey;
-use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, Extensions};
 use reth_rpc_convert::RpcTxReq;
+use reth_rpc_eth_types::{
+    access_list::{AccessListOptions, AccessListResponse},
+    proof::AccountProofResponse,
+};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use tracing::trace;
 
@@ -219,7 +225,7 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
     ) -> RpcResult<Vec<SimulatedBlock<B>>>;
 
     /// Executes a new message call immediately without creating a transaction on the block chain.
-    #[method(name = "call")]
+    #[method(name = "call", with_extensions)]
     async fn call(
         &self,
         request: TransactionRequest,
@@ -251,18 +257,23 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
     /// list is included. Like `eth_estimateGas`, this is an estimation; the list could change
     /// when the transaction is actually mined. Adding an accessList to your transaction does
     /// not necessary result in lower gas usage compared to a transaction without an access
-    /// list.
+    /// list, which is why the gas used without any access list is returned as well.
+    ///
+    /// With [`AccessListOptions::until_stable`] the transaction is re-executed with the
+    /// generated list until the list no longer changes.
     #[method(name = "createAccessList")]
     async fn create_access_list(
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
-    ) -> RpcResult<AccessListResult>;
+        block_overrides: Option<Box<BlockOverrides>>,
+        options: Option<AccessListOptions>,
+    ) -> RpcResult<AccessListResponse>;
 
     /// Generates and returns an estimate of how much gas is necessary to allow the transaction to
     /// complete.
-    #[method(name = "estimateGas")]
+    #[method(name = "estimateGas", with_extensions)]
     async fn estimate_gas(
         &self,
         request: TransactionRequest,
@@ -361,13 +372,16 @@ pub trait EthApi<TxReq: RpcObject, T: RpcObject, B: RpcObject, R: RpcObject, H:
 
     /// Returns the account and storage values of the specified account including the Merkle-proof.
     /// This call can be used to verify that the data you are pulling from is not tampered with.
+    ///
+    /// The response is extended by the hash and number of the block the proof was generated at,
+    /// which is useful for tags such as `safe` and `finalized`.
     #[method(name = "getProof")]
     async fn get_proof(
         &self,
         address: Address,
         keys: Vec<JsonStorageKey>,
         block_number: Option<BlockId>,
-    ) -> RpcResult<EIP1186AccountProofResponse>;
+    ) -> RpcResult<AccountProofResponse>;
 
     /// Returns the account's balance, nonce, and code.
     ///
@@ -667,17 +681,19 @@ where
     /// Handler for: `eth_call`
     async fn call(
         &self,
+        ext: &Extensions,
         request: TransactionRequest,
         block_number: Option<BlockId>,
         state_overrides: Option<StateOverride>,
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> RpcResult<Bytes> {
         trace!(target: "rpc::eth", ?request, ?block_number, ?state_overrides, ?block_overrides, "Serving eth_call");
-        Ok(EthCall::call(
+        Ok(EthCall::call_with_policy(
             self,
             request,
             block_number,
             EvmOverrides::new(state_overrides, block_overrides),
+            Call::call_policy(self, ext),
         )
         .await?)
     }
@@ -699,24 +715,35 @@ where
         request: TransactionRequest,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
-    ) -> RpcResult<AccessListResult> {
-        trace!(target: "rpc::eth", ?request, ?block_number, ?state_override, "Serving eth_createAccessList");
-        Ok(EthCall::create_access_list_at(self, request, block_number, state_override).await?)
+        block_overrides: Option<Box<BlockOverrides>>,
+        options: Option<AccessListOptions>,
+    ) -> RpcResult<AccessListResponse> {
+        trace!(target: "rpc::eth", ?request, ?block_number, ?state_override, ?block_overrides, ?options, "Serving eth_createAccessList");
+        Ok(EthCall::create_access_list_at(
+            self,
+            request,
+            block_number,
+            EvmOverrides::new(state_override, block_overrides),
+            options.unwrap_or_default(),
+        )
+        .await?)
     }
 
     /// Handler for: `eth_estimateGas`
     async fn estimate_gas(
         &self,
+        ext: &Extensions,
         request: TransactionRequest,
         block_number: Option<BlockId>,
         state_override: Option<StateOverride>,
     ) -> RpcResult<U256> {
         trace!(target: "rpc::eth", ?request, ?block_number, "Serving eth_estimateGas");
-        Ok(EthCall::estimate_gas_at(
+        Ok(EthCall::estimate_gas_with_policy(
             self,
             request,
             block_number.unwrap_or_default(),
             state_override,
+            Call::call_policy(self, ext),
         )
         .await?)
     }
@@ -840,7 +867,7 @@ where
         address: Address,
         keys: Vec<JsonStorageKey>,
         block_number: Option<BlockId>,
-    ) -> RpcResult<EIP1186AccountProofResponse> {
+    ) -> RpcResult<AccountProofResponse> {
         trace!(target: "rpc::eth", ?address, ?keys, ?block_number, "Serving eth_getProof");
         Ok(EthState::get_proof(self, address, keys, block_number)?.await?)
     }
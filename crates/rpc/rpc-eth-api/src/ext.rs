@@ -1,8 +1,19 @@
 //! `eth_` Extension traits.
 
+use crate::{helpers::FullEthApi, FromEthApiError, RpcBlock, RpcReceipt};
+use alloy_consensus::Transaction;
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_json_rpc::RpcObject;
 use alloy_primitives::{Bytes, B256};
 use alloy_rpc_types_eth::erc4337::TransactionConditional;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives_traits::{BlockBody, SignedTransaction};
+use reth_rpc_eth_types::{BlockWithSidecars, EthApiError, TransactionSidecar};
+use reth_rpc_server_types::{constants::MAX_BLOCK_RECEIPTS_RANGE, result::internal_rpc_err};
+use reth_storage_api::BlockIdReader;
+use reth_transaction_pool::TransactionPool;
+use std::{collections::HashMap, sync::Arc};
+use tracing::trace;
 
 /// Extension trait for `eth_` namespace for L2s.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
@@ -16,3 +27,119 @@ pub trait L2EthApiExt {
         condition: TransactionConditional,
     ) -> RpcResult<B256>;
 }
+
+/// Extension of the `eth_` API with methods that are not part of the spec, served under the
+/// `reth_` namespace.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait EthReceiptsRangeApiExt<R: RpcObject> {
+    /// Returns the receipts of all blocks in the given inclusive range, one list per block.
+    ///
+    /// At most [`MAX_BLOCK_RECEIPTS_RANGE`] blocks can be requested at once.
+    #[method(name = "getBlockReceiptsByRange")]
+    async fn block_receipts_by_range(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<Vec<R>>>;
+}
+
+#[async_trait::async_trait]
+impl<T> EthReceiptsRangeApiExtServer<RpcReceipt<T::NetworkTypes>> for T
+where
+    T: FullEthApi,
+{
+    /// Handler for: `reth_getBlockReceiptsByRange`
+    async fn block_receipts_by_range(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<Vec<RpcReceipt<T::NetworkTypes>>>> {
+        trace!(target: "rpc::eth", ?from_block, ?to_block, "Serving reth_getBlockReceiptsByRange");
+        let resolve = |block: BlockNumberOrTag| {
+            self.provider()
+                .convert_block_number(block)
+                .map_err(T::Error::from_eth_err)?
+                .ok_or_else(|| T::Error::from_eth_err(EthApiError::HeaderNotFound(block.into())))
+        };
+        let from = resolve(from_block).map_err(Into::into)?;
+        let to = resolve(to_block).map_err(Into::into)?;
+
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange.into())
+        }
+        if to - from >= MAX_BLOCK_RECEIPTS_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range too large, at most {MAX_BLOCK_RECEIPTS_RANGE} blocks are allowed"
+            ))
+            .into())
+        }
+
+        self.block_receipts_range(from..=to).await.map_err(Into::into)
+    }
+}
+
+/// Extension of the `eth_` API that serves blocks together with the blob sidecars of their
+/// transactions, under the `reth_` namespace.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
+pub trait EthBlockSidecarsApiExt<B: RpcObject> {
+    /// Returns the block with the given id, like `eth_getBlockByNumber` and `eth_getBlockByHash`,
+    /// together with the blob sidecars of its transactions.
+    ///
+    /// The sidecars are read from the blob store of the transaction pool, which keeps the sidecars
+    /// of included transactions until their block is finalized. Sidecars that are no longer held
+    /// are returned as `null`.
+    #[method(name = "getBlockWithSidecars")]
+    async fn block_with_sidecars(
+        &self,
+        block_id: BlockId,
+        full: bool,
+    ) -> RpcResult<Option<BlockWithSidecars<B>>>;
+}
+
+#[async_trait::async_trait]
+impl<T> EthBlockSidecarsApiExtServer<RpcBlock<T::NetworkTypes>> for T
+where
+    T: FullEthApi,
+{
+    /// Handler for: `reth_getBlockWithSidecars`
+    async fn block_with_sidecars(
+        &self,
+        block_id: BlockId,
+        full: bool,
+    ) -> RpcResult<Option<BlockWithSidecars<RpcBlock<T::NetworkTypes>>>> {
+        trace!(target: "rpc::eth", ?block_id, ?full, "Serving reth_getBlockWithSidecars");
+        let Some(block) = self.recovered_block(block_id).await.map_err(Into::into)? else {
+            return Ok(None)
+        };
+
+        let mut blob_transactions = Vec::new();
+        for (index, tx) in block.body().transactions().iter().enumerate() {
+            if tx.blob_versioned_hashes().is_some() {
+                blob_transactions.push((index as u64, *tx.tx_hash()));
+            }
+        }
+        let mut sidecars = if blob_transactions.is_empty() {
+            HashMap::new()
+        } else {
+            self.pool()
+                .get_all_blobs(blob_transactions.iter().map(|(_, hash)| *hash).collect())
+                .map_err(|err| internal_rpc_err(err.to_string()))?
+                .into_iter()
+                .collect()
+        };
+        let sidecars = blob_transactions
+            .into_iter()
+            .map(|(transaction_index, transaction_hash)| TransactionSidecar {
+                transaction_hash,
+                transaction_index,
+                sidecar: sidecars.remove(&transaction_hash).map(Arc::unwrap_or_clone),
+            })
+            .collect();
+
+        // the block is resolved by hash, so the sidecars are always those of the returned block
+        let block = self.rpc_block(block.hash().into(), full).await.map_err(Into::into)?;
+        Ok(block.map(|block| BlockWithSidecars { block, sidecars }))
+    }
+}
@@ -39,6 +39,18 @@ pub trait EthFilterApi<T: RpcObject> {
     /// Returns logs matching given filter object.
     #[method(name = "getLogs")]
     async fn logs(&self, filter: Filter) -> RpcResult<Vec<Log>>;
+
+    /// Streams logs matching given filter object in chunks of consecutive blocks.
+    ///
+    /// Every chunk is sent as a separate notification, so unlike `eth_getLogs` the range is not
+    /// limited by the maximum number of logs per response. The subscription ends once the whole
+    /// range has been scanned.
+    #[subscription(
+        name = "getLogsStream" => "logsStream",
+        unsubscribe = "unsubscribeLogsStream",
+        item = Vec<Log>
+    )]
+    async fn logs_stream(&self, filter: Filter) -> jsonrpsee::core::SubscriptionResult;
 }
 
 /// Limits for logs queries
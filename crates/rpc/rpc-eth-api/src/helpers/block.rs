@@ -6,7 +6,7 @@ use crate::{
     RpcReceipt,
 };
 use alloy_eips::BlockId;
-use alloy_primitives::{Sealable, U256};
+use alloy_primitives::{BlockNumber, Sealable, U256};
 use alloy_rlp::Encodable;
 use alloy_rpc_types_eth::{Block, BlockTransactions, Header, Index};
 use futures::Future;
@@ -14,9 +14,12 @@ use reth_evm::ConfigureEvm;
 use reth_node_api::BlockBody;
 use reth_primitives_traits::{NodePrimitives, RecoveredBlock};
 use reth_rpc_convert::RpcConvert;
-use reth_storage_api::{BlockIdReader, BlockReader, ProviderHeader, ProviderReceipt, ProviderTx};
+use reth_storage_api::{
+    BlockIdReader, BlockReader, ProviderBlock, ProviderHeader, ProviderReceipt, ProviderTx,
+    ReceiptProvider,
+};
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
-use std::sync::Arc;
+use std::{ops::RangeInclusive, sync::Arc};
 
 /// Result type of the fetched block receipts.
 pub type BlockReceiptsResult<N, E> = Result<Option<Vec<RpcReceipt<N>>>, E>;
@@ -109,7 +112,53 @@ pub trait EthBlocks: LoadBlock {
         block_id: BlockId,
     ) -> impl Future<Output = BlockReceiptsResult<Self::NetworkTypes, Self::Error>> + Send
     where
-        Self: LoadReceipt;
+        Self: LoadReceipt,
+    {
+        async move {
+            if let Some((block, receipts)) = self.load_block_and_receipts(block_id).await? {
+                return self.convert_block_receipts(&block, &receipts).map(Some)
+            }
+            Ok(None)
+        }
+    }
+
+    /// Helper function for `reth_getBlockReceiptsByRange`.
+    ///
+    /// Returns the transaction receipts of all blocks in the given range, read directly from
+    /// storage without going through the cache. Returns fewer entries than requested if the range
+    /// extends past the last stored block.
+    #[expect(clippy::type_complexity)]
+    fn block_receipts_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> impl Future<Output = Result<Vec<Vec<RpcReceipt<Self::NetworkTypes>>>, Self::Error>> + Send
+    where
+        Self: LoadReceipt,
+    {
+        self.spawn_blocking_io(move |this| {
+            let blocks = this
+                .provider()
+                .recovered_block_range(range.clone())
+                .map_err(Self::Error::from_eth_err)?;
+            let receipts = this
+                .provider()
+                .receipts_by_block_range(range)
+                .map_err(Self::Error::from_eth_err)?;
+
+            blocks
+                .iter()
+                .zip(receipts.iter())
+                .map(|(block, receipts)| this.convert_block_receipts(block, receipts))
+                .collect()
+        })
+    }
+
+    /// Converts the receipts of the given block into their RPC representation.
+    fn convert_block_receipts(
+        &self,
+        block: &RecoveredBlock<ProviderBlock<Self::Provider>>,
+        receipts: &[ProviderReceipt<Self::Provider>],
+    ) -> Result<Vec<RpcReceipt<Self::NetworkTypes>>, Self::Error>;
 
     /// Helper method that loads a block and all its receipts.
     fn load_block_and_receipts(
@@ -259,7 +308,23 @@ pub trait LoadBlock:
                 None => return Ok(None),
             };
 
-            self.cache().get_recovered_block(block_hash).await.map_err(Self::Error::from_eth_err)
+            let block = self
+                .cache()
+                .get_recovered_block(block_hash)
+                .await
+                .map_err(Self::Error::from_eth_err)?;
+            if block.is_some() {
+                return Ok(block)
+            }
+
+            // The block may be the pending block, which is known but not canonical. Canonical
+            // hashes are already required by `block_hash_for_id`.
+            Ok(self
+                .provider()
+                .pending_block()
+                .map_err(Self::Error::from_eth_err)?
+                .filter(|pending_block| pending_block.hash() == block_hash)
+                .map(Arc::new))
         }
     }
 }
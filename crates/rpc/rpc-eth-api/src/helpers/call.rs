@@ -8,7 +8,7 @@ use crate::{
     helpers::estimate::EstimateCall, FromEvmError, FullEthApiTypes, RpcBlock, RpcNodeCore,
 };
 use alloy_consensus::BlockHeader;
-use alloy_eips::eip2930::AccessListResult;
+use alloy_eips::eip2930::{AccessList, AccessListResult};
 use alloy_evm::{
     call::caller_gas_allowance,
     overrides::{apply_block_overrides, apply_state_overrides, OverrideBlockHashes},
@@ -21,6 +21,7 @@ use alloy_rpc_types_eth::{
     BlockId, Bundle, EthCallResponse, StateContext, TransactionInfo,
 };
 use futures::Future;
+use jsonrpsee::Extensions;
 use reth_errors::{ProviderError, RethError};
 use reth_evm::{
     ConfigureEvm, Evm, EvmEnv, EvmEnvFor, HaltReasonFor, InspectorFor, SpecFor, TransactionEnv,
@@ -34,12 +35,18 @@ use reth_revm::{
 };
 use reth_rpc_convert::{RpcConvert, RpcTypes};
 use reth_rpc_eth_types::{
-    cache::db::{StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
+    access_list::{
+        is_same_access_list, AccessListOptions, AccessListResponse, MAX_ACCESS_LIST_ITERATIONS,
+    },
+    cache::{
+        db::{StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
+        state::SnapshotStateProvider,
+    },
     error::{api::FromEvmHalt, ensure_success, FromEthApiError},
     simulate::{self, EthSimulateError},
-    EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
+    CallPolicy, EthApiError, RevertError, RpcInvalidTransactionError, StateCacheDb,
 };
-use reth_storage_api::{BlockIdReader, ProviderHeader, ProviderTx};
+use reth_storage_api::{BlockIdReader, ProviderHeader, ProviderTx, StateProviderBox};
 use revm::{
     context_interface::{
         result::{ExecutionResult, ResultAndState},
@@ -48,6 +55,7 @@ use revm::{
     Database, DatabaseCommit,
 };
 use revm_inspectors::{access_list::AccessListInspector, transfer::TransferInspector};
+use std::time::Duration;
 use tracing::trace;
 
 /// Result type for `eth_simulateV1` RPC method.
@@ -66,9 +74,37 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
         EstimateCall::estimate_gas_at(self, request, at, state_override)
     }
 
+    /// Estimate gas needed for execution of the `request` at the [`BlockId`] within the limits of
+    /// the given [`CallPolicy`].
+    fn estimate_gas_with_policy(
+        &self,
+        request: TransactionRequest,
+        at: BlockId,
+        state_override: Option<StateOverride>,
+        policy: CallPolicy,
+    ) -> impl Future<Output = Result<U256, Self::Error>> + Send {
+        async move {
+            policy
+                .ensure_state_override(state_override.as_ref())
+                .map_err(Self::Error::from_eth_err)?;
+
+            let estimate = EstimateCall::estimate_gas_at_with_gas_cap(
+                self,
+                request,
+                at,
+                state_override,
+                policy.gas_cap,
+            );
+            with_timeout(policy.timeout, estimate).await
+        }
+    }
+
     /// `eth_simulateV1` executes an arbitrary number of transactions on top of the requested state.
     /// The transactions are packed into individual blocks. Overrides can be provided.
     ///
+    /// Block numbers and timestamps must increase from block to block. Block numbers that are
+    /// skipped by the block overrides are filled with empty blocks.
+    ///
     /// See also: <https://github.com/ethereum/go-ethereum/pull/27720>
     fn simulate_v1(
         &self,
@@ -97,6 +133,14 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
                 self.recovered_block(block).await?.ok_or(EthApiError::HeaderNotFound(block))?;
             let mut parent = base_block.sealed_header().clone();
 
+            // blocks skipped by the number overrides are simulated as empty blocks
+            let block_state_calls = simulate::fill_block_gaps(
+                parent.number(),
+                block_state_calls,
+                self.max_simulate_blocks(),
+            )
+            .map_err(|err| Self::Error::from_eth_err(EthApiError::other(err)))?;
+
             let this = self.clone();
             self.spawn_with_state_at_block(block, move |state| {
                 let mut db =
@@ -135,6 +179,13 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
                         }
                         apply_block_overrides(block_overrides, &mut db, &mut evm_env.block_env);
                     }
+                    if evm_env.block_env.timestamp <= U256::from(parent.timestamp()) {
+                        return Err(EthApiError::other(EthSimulateError::BlockTimestampInvalid {
+                            got: evm_env.block_env.timestamp.saturating_to(),
+                            parent: parent.timestamp(),
+                        })
+                        .into())
+                    }
                     if let Some(state_overrides) = state_overrides {
                         apply_state_overrides(state_overrides, &mut db)
                             .map_err(Self::Error::from_eth_err)?;
@@ -219,10 +270,33 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
         request: TransactionRequest,
         block_number: Option<BlockId>,
         overrides: EvmOverrides,
+    ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
+        self.call_with_policy(request, block_number, overrides, CallPolicy::default())
+    }
+
+    /// Executes the call request (`eth_call`) within the limits of the given [`CallPolicy`] and
+    /// returns the output
+    fn call_with_policy(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+        overrides: EvmOverrides,
+        policy: CallPolicy,
     ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
         async move {
-            let res =
-                self.transact_call_at(request, block_number.unwrap_or_default(), overrides).await?;
+            policy
+                .ensure_state_override(overrides.state.as_ref())
+                .map_err(Self::Error::from_eth_err)?;
+
+            let this = self.clone();
+            let call = self.spawn_with_call_at_with_gas_cap(
+                request,
+                block_number.unwrap_or_default(),
+                overrides,
+                policy.gas_cap_or(self.call_gas_limit()),
+                move |db, evm_env, tx_env| this.transact(db, evm_env, tx_env),
+            );
+            let res = with_timeout(policy.timeout, call).await?;
 
             ensure_success(res.result)
         }
@@ -348,14 +422,15 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
         }
     }
 
-    /// Creates [`AccessListResult`] for the [`TransactionRequest`] at the given
+    /// Creates [`AccessListResponse`] for the [`TransactionRequest`] at the given
     /// [`BlockId`], or latest block.
     fn create_access_list_at(
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-        state_override: Option<StateOverride>,
-    ) -> impl Future<Output = Result<AccessListResult, Self::Error>> + Send
+        overrides: EvmOverrides,
+        options: AccessListOptions,
+    ) -> impl Future<Output = Result<AccessListResponse, Self::Error>> + Send
     where
         Self: Trace,
     {
@@ -364,28 +439,37 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
             let (evm_env, at) = self.evm_env_at(block_id).await?;
 
             self.spawn_blocking_io(move |this| {
-                this.create_access_list_with(evm_env, at, request, state_override)
+                this.create_access_list_with(evm_env, at, request, overrides, options)
             })
             .await
         }
     }
 
-    /// Creates [`AccessListResult`] for the [`TransactionRequest`] at the given
+    /// Creates [`AccessListResponse`] for the [`TransactionRequest`] at the given
     /// [`BlockId`].
+    ///
+    /// The transaction is first executed without any access list to collect the touched
+    /// addresses and storage keys. If [`AccessListOptions::until_stable`] is set, it's then
+    /// re-executed with the collected list until the list no longer changes, at most
+    /// [`MAX_ACCESS_LIST_ITERATIONS`] times.
     fn create_access_list_with(
         &self,
         mut evm_env: EvmEnvFor<Self::Evm>,
         at: BlockId,
         mut request: TransactionRequest,
-        state_override: Option<StateOverride>,
-    ) -> Result<AccessListResult, Self::Error>
+        overrides: EvmOverrides,
+        options: AccessListOptions,
+    ) -> Result<AccessListResponse, Self::Error>
     where
         Self: Trace,
     {
         let state = self.state_at_block_id(at)?;
         let mut db = CacheDB::new(StateProviderDatabase::new(state));
 
-        if let Some(state_overrides) = state_override {
+        if let Some(block_overrides) = overrides.block {
+            apply_block_overrides(*block_overrides, &mut db, &mut evm_env.block_env);
+        }
+        if let Some(state_overrides) = overrides.state {
             apply_state_overrides(state_overrides, &mut db).map_err(Self::Error::from_eth_err)?;
         }
 
@@ -409,24 +493,51 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
             tx_env.set_gas_limit(cap.min(evm_env.block_env.gas_limit));
         }
 
-        // can consume the list since we're not using the request anymore
+        // can consume the list since we're not using the request anymore, the addresses and
+        // storage keys of the requested list are always part of the generated one
         let initial = request.access_list.take().unwrap_or_default();
 
+        // the first execution is without any access list
+        tx_env.set_access_list(AccessList::default());
         let mut inspector = AccessListInspector::new(initial);
-
-        let (result, (evm_env, mut tx_env)) =
+        let (mut result, (mut evm_env, mut tx_env)) =
             self.inspect(&mut db, evm_env, tx_env, &mut inspector)?;
-        let access_list = inspector.into_access_list();
+        let gas_used_without_access_list = U256::from(result.result.gas_used());
+        let mut access_list = inspector.into_access_list();
+
+        if options.until_stable {
+            for _ in 1..MAX_ACCESS_LIST_ITERATIONS {
+                if !result.result.is_success() {
+                    break
+                }
+
+                tx_env.set_access_list(access_list.clone());
+                let mut inspector = AccessListInspector::new(access_list.clone());
+                (result, (evm_env, tx_env)) =
+                    self.inspect(&mut db, evm_env, tx_env, &mut inspector)?;
+                let next = inspector.into_access_list();
+                if is_same_access_list(&access_list, &next) {
+                    break
+                }
+                access_list = next;
+            }
+        }
+
         tx_env.set_access_list(access_list.clone());
+        let response =
+            |access_list: AccessList, gas_used: u64, error: Option<String>| AccessListResponse {
+                result: AccessListResult { access_list, gas_used: U256::from(gas_used), error },
+                gas_used_without_access_list,
+            };
         match result.result {
             ExecutionResult::Halt { reason, gas_used } => {
                 let error =
                     Some(Self::Error::from_evm_halt(reason, tx_env.gas_limit()).to_string());
-                return Ok(AccessListResult { access_list, gas_used: U256::from(gas_used), error })
+                return Ok(response(access_list, gas_used, error))
             }
             ExecutionResult::Revert { output, gas_used } => {
                 let error = Some(RevertError::new(output).to_string());
-                return Ok(AccessListResult { access_list, gas_used: U256::from(gas_used), error })
+                return Ok(response(access_list, gas_used, error))
             }
             ExecutionResult::Success { .. } => {}
         };
@@ -437,15 +548,13 @@ pub trait EthCall: EstimateCall + Call + LoadPendingBlock + LoadBlock + FullEthA
         let res = match result.result {
             ExecutionResult::Halt { reason, gas_used } => {
                 let error = Some(Self::Error::from_evm_halt(reason, gas_limit).to_string());
-                AccessListResult { access_list, gas_used: U256::from(gas_used), error }
+                response(access_list, gas_used, error)
             }
             ExecutionResult::Revert { output, gas_used } => {
                 let error = Some(RevertError::new(output).to_string());
-                AccessListResult { access_list, gas_used: U256::from(gas_used), error }
-            }
-            ExecutionResult::Success { gas_used, .. } => {
-                AccessListResult { access_list, gas_used: U256::from(gas_used), error: None }
+                response(access_list, gas_used, error)
             }
+            ExecutionResult::Success { gas_used, .. } => response(access_list, gas_used, None),
         };
 
         Ok(res)
@@ -478,6 +587,28 @@ pub trait Call:
     /// Returns the maximum number of blocks accepted for `eth_simulateV1`.
     fn max_simulate_blocks(&self) -> u64;
 
+    /// Returns the [`CallPolicy`] of the request with the given extensions.
+    ///
+    /// By default, all requests are subject to the node's configuration.
+    fn call_policy(&self, extensions: &Extensions) -> CallPolicy {
+        let _ = extensions;
+        CallPolicy::default()
+    }
+
+    /// Returns the state at the given [`BlockId`] to execute calls against.
+    ///
+    /// Calls against the same block hash share the state they read via the
+    /// [`StateSnapshot`](reth_rpc_eth_types::cache::state::StateSnapshot) of the block, so repeated
+    /// calls don't hit the database again.
+    fn call_state_at_block_id(
+        &self,
+        at: BlockId,
+    ) -> Result<SnapshotStateProvider<StateProviderBox>, Self::Error> {
+        let state = self.state_at_block_id(at)?;
+        let snapshot = at.as_block_hash().and_then(|hash| self.cache().state_snapshot(hash));
+        Ok(SnapshotStateProvider::new(state, snapshot))
+    }
+
     /// Executes the closure with the state that corresponds to the given [`BlockId`].
     fn with_state_at_block<F, R>(&self, at: BlockId, f: F) -> Result<R, Self::Error>
     where
@@ -577,6 +708,30 @@ pub trait Call:
         overrides: EvmOverrides,
         f: F,
     ) -> impl Future<Output = Result<R, Self::Error>> + Send
+    where
+        Self: LoadPendingBlock,
+        F: FnOnce(
+                StateCacheDbRefMutWrapper<'_, '_>,
+                EvmEnvFor<Self::Evm>,
+                TxEnvFor<Self::Evm>,
+            ) -> Result<R, Self::Error>
+            + Send
+            + 'static,
+        R: Send + 'static,
+    {
+        self.spawn_with_call_at_with_gas_cap(request, at, overrides, self.call_gas_limit(), f)
+    }
+
+    /// Same as [`Self::spawn_with_call_at`], but with the given gas cap instead of the configured
+    /// [`Self::call_gas_limit`].
+    fn spawn_with_call_at_with_gas_cap<F, R>(
+        &self,
+        request: TransactionRequest,
+        at: BlockId,
+        overrides: EvmOverrides,
+        gas_cap: u64,
+        f: F,
+    ) -> impl Future<Output = Result<R, Self::Error>> + Send
     where
         Self: LoadPendingBlock,
         F: FnOnce(
@@ -589,15 +744,21 @@ pub trait Call:
         R: Send + 'static,
     {
         async move {
+            // calls against the pending tag are executed on top of the locally built pending block
+            let pending_state =
+                if at.is_pending() { self.local_pending_state().await? } else { None };
             let (evm_env, at) = self.evm_env_at(at).await?;
             let this = self.clone();
             self.spawn_blocking_io(move |_| {
-                let state = this.state_at_block_id(at)?;
+                let state = match pending_state {
+                    Some(state) => SnapshotStateProvider::new(state, None),
+                    None => this.call_state_at_block_id(at)?,
+                };
                 let mut db =
                     CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(&state)));
 
-                let (evm_env, tx_env) =
-                    this.prepare_call_env(evm_env, request, &mut db, overrides)?;
+                let (evm_env, tx_env) = this
+                    .prepare_call_env_with_gas_cap(evm_env, request, &mut db, overrides, gas_cap)?;
 
                 f(StateCacheDbRefMutWrapper(&mut db), evm_env, tx_env)
             })
@@ -731,17 +892,35 @@ pub trait Call:
     /// In addition, this changes the block's gas limit to the configured [`Self::call_gas_limit`].
     #[expect(clippy::type_complexity)]
     fn prepare_call_env<DB>(
+        &self,
+        evm_env: EvmEnvFor<Self::Evm>,
+        request: TransactionRequest,
+        db: &mut DB,
+        overrides: EvmOverrides,
+    ) -> Result<(EvmEnvFor<Self::Evm>, TxEnvFor<Self::Evm>), Self::Error>
+    where
+        DB: Database + DatabaseCommit + OverrideBlockHashes,
+        EthApiError: From<<DB as Database>::Error>,
+    {
+        self.prepare_call_env_with_gas_cap(evm_env, request, db, overrides, self.call_gas_limit())
+    }
+
+    /// Same as [`Self::prepare_call_env`], but with the given gas cap instead of the configured
+    /// [`Self::call_gas_limit`].
+    #[expect(clippy::type_complexity)]
+    fn prepare_call_env_with_gas_cap<DB>(
         &self,
         mut evm_env: EvmEnvFor<Self::Evm>,
         mut request: TransactionRequest,
         db: &mut DB,
         overrides: EvmOverrides,
+        gas_cap: u64,
     ) -> Result<(EvmEnvFor<Self::Evm>, TxEnvFor<Self::Evm>), Self::Error>
     where
         DB: Database + DatabaseCommit + OverrideBlockHashes,
         EthApiError: From<<DB as Database>::Error>,
     {
-        if request.gas > Some(self.call_gas_limit()) {
+        if request.gas > Some(gas_cap) {
             // configured gas exceeds limit
             return Err(
                 EthApiError::InvalidTransaction(RpcInvalidTransactionError::GasTooHigh).into()
@@ -749,7 +928,7 @@ pub trait Call:
         }
 
         // apply configured gas cap
-        evm_env.block_env.gas_limit = self.call_gas_limit();
+        evm_env.block_env.gas_limit = gas_cap;
 
         // Disabled because eth_call is sometimes used with eoa senders
         // See <https://github.com/paradigmxyz/reth/issues/1959>
@@ -788,3 +967,18 @@ pub trait Call:
         Ok((evm_env, tx_env))
     }
 }
+
+/// Awaits the future, failing with [`EthApiError::ExecutionTimedOut`] if a timeout is set and the
+/// future doesn't complete in time.
+///
+/// Note that work the future already spawned on a blocking task runs to completion regardless.
+async fn with_timeout<F, R, E>(timeout: Option<Duration>, fut: F) -> Result<R, E>
+where
+    F: Future<Output = Result<R, E>>,
+    E: FromEthApiError,
+{
+    let Some(timeout) = timeout else { return fut.await };
+    tokio::time::timeout(timeout, fut)
+        .await
+        .unwrap_or_else(|_elapsed| Err(E::from_eth_err(EthApiError::ExecutionTimedOut(timeout))))
+}
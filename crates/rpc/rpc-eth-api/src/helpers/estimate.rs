@@ -11,6 +11,7 @@ use reth_errors::ProviderError;
 use reth_evm::{ConfigureEvm, Database, Evm, EvmEnvFor, EvmFor, TransactionEnv, TxEnvFor};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
 use reth_rpc_eth_types::{
+    cache::state::SnapshotStateProvider,
     error::{api::FromEvmHalt, FromEvmError},
     EthApiError, RevertError, RpcInvalidTransactionError,
 };
@@ -23,7 +24,8 @@ use tracing::trace;
 pub trait EstimateCall: Call {
     /// Estimates the gas usage of the `request` with the state.
     ///
-    /// This will execute the [`TransactionRequest`] and find the best gas limit via binary search.
+    /// This will execute the [`TransactionRequest`] with the highest possible gas limit and derive
+    /// the best gas limit from the gas it used, see [`optimistic_gas_limit_search`].
     ///
     /// ## EVM settings
     ///
@@ -106,7 +108,7 @@ pub trait EstimateCall: Call {
         // Create EVM instance once and reuse it throughout the entire estimation process
         let mut evm = self.evm_config().evm_with_env(&mut db, evm_env);
 
-        // For basic transfers, try using minimum gas before running full binary search
+        // For basic transfers, try using minimum gas before running the full estimation
         if is_basic_transfer {
             // If the tx is a simple transfer (call to an account with no code) we can
             // shortcircuit. But simply returning
@@ -127,7 +129,7 @@ pub trait EstimateCall: Call {
         trace!(target: "rpc::eth::estimate", ?tx_env, gas_limit = tx_env.gas_limit(), is_basic_transfer, "Starting gas estimation");
 
         // Execute the transaction with the highest possible gas limit.
-        let mut res = match evm.transact(tx_env.clone()).map_err(Self::Error::from_evm_err) {
+        let res = match evm.transact(tx_env.clone()).map_err(Self::Error::from_evm_err) {
             // Handle the exceptional case where the transaction initialization uses too much
             // gas. If the gas price or gas limit was specified in the request,
             // retry the transaction with the block's gas limit to determine if
@@ -172,97 +174,20 @@ pub trait EstimateCall: Call {
         };
 
         // At this point we know the call succeeded but want to find the _best_ (lowest) gas the
-        // transaction succeeds with. We find this by doing a binary search over the possible range.
-
-        // we know the tx succeeded with the configured gas limit, so we can use that as the
-        // highest, in case we applied a gas cap due to caller allowance above
-        highest_gas_limit = tx_env.gas_limit();
-
-        // NOTE: this is the gas the transaction used, which is less than the
-        // transaction requires to succeed.
-        let mut gas_used = res.result.gas_used();
-        // the lowest value is capped by the gas used by the unconstrained transaction
-        let mut lowest_gas_limit = gas_used.saturating_sub(1);
-
-        // As stated in Geth, there is a good chance that the transaction will pass if we set the
-        // gas limit to the execution gas used plus the gas refund, so we check this first
-        // <https://github.com/ethereum/go-ethereum/blob/a5a4fa7032bb248f5a7c40f4e8df2b131c4186a4/eth/gasestimator/gasestimator.go#L135
-        //
-        // Calculate the optimistic gas limit by adding gas used and gas refund,
-        // then applying a 64/63 multiplier to account for gas forwarding rules.
-        let optimistic_gas_limit = (gas_used + gas_refund + CALL_STIPEND_GAS) * 64 / 63;
-        if optimistic_gas_limit < highest_gas_limit {
-            // Set the transaction's gas limit to the calculated optimistic gas limit.
-            let mut optimistic_tx_env = tx_env.clone();
-            optimistic_tx_env.set_gas_limit(optimistic_gas_limit);
-
-            // Re-execute the transaction with the new gas limit and update the result and
-            // environment.
-            res = evm.transact(optimistic_tx_env).map_err(Self::Error::from_evm_err)?;
-
-            // Update the gas used based on the new result.
-            gas_used = res.result.gas_used();
-            // Update the gas limit estimates (highest and lowest) based on the execution result.
-            update_estimated_gas_range(
-                res.result,
-                optimistic_gas_limit,
-                &mut highest_gas_limit,
-                &mut lowest_gas_limit,
-            )?;
-        };
-
-        // Pick a point that's close to the estimated gas
-        let mut mid_gas_limit = std::cmp::min(
-            gas_used * 3,
-            ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64,
-        );
-
-        trace!(target: "rpc::eth::estimate", ?highest_gas_limit, ?lowest_gas_limit, ?mid_gas_limit, "Starting binary search for gas");
-
-        // Binary search narrows the range to find the minimum gas limit needed for the transaction
-        // to succeed.
-        while lowest_gas_limit + 1 < highest_gas_limit {
-            // An estimation error is allowed once the current gas limit range used in the binary
-            // search is small enough (less than 1.5% of the highest gas limit)
-            // <https://github.com/ethereum/go-ethereum/blob/a5a4fa7032bb248f5a7c40f4e8df2b131c4186a4/eth/gasestimator/gasestimator.go#L152
-            if (highest_gas_limit - lowest_gas_limit) as f64 / (highest_gas_limit as f64) <
-                ESTIMATE_GAS_ERROR_RATIO
-            {
-                break
-            };
-
-            let mut mid_tx_env = tx_env.clone();
-            mid_tx_env.set_gas_limit(mid_gas_limit);
-
-            // Execute transaction and handle potential gas errors, adjusting limits accordingly.
-            match evm.transact(mid_tx_env).map_err(Self::Error::from_evm_err) {
-                Err(err) if err.is_gas_too_high() => {
-                    // Decrease the highest gas limit if gas is too high
-                    highest_gas_limit = mid_gas_limit;
-                }
-                Err(err) if err.is_gas_too_low() => {
-                    // Increase the lowest gas limit if gas is too low
-                    lowest_gas_limit = mid_gas_limit;
-                }
-                // Handle other cases, including successful transactions.
-                ethres => {
-                    // Unpack the result and environment if the transaction was successful.
-                    res = ethres?;
-                    // Update the estimated gas range based on the transaction result.
-                    update_estimated_gas_range(
-                        res.result,
-                        mid_gas_limit,
-                        &mut highest_gas_limit,
-                        &mut lowest_gas_limit,
-                    )?;
-                }
-            }
-
-            // New midpoint
-            mid_gas_limit = ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64;
-        }
-
-        Ok(U256::from(highest_gas_limit))
+        // transaction succeeds with. We know the tx succeeded with the configured gas limit, so we
+        // can use that as the highest, in case we applied a gas cap due to caller allowance above.
+        let gas_limit = optimistic_gas_limit_search(
+            res.result.gas_used(),
+            gas_refund,
+            tx_env.gas_limit(),
+            |gas_limit| {
+                let mut tx_env = tx_env.clone();
+                tx_env.set_gas_limit(gas_limit);
+                evm.transact(tx_env).map(|res| res.result).map_err(Self::Error::from_evm_err)
+            },
+        )?;
+
+        Ok(U256::from(gas_limit))
     }
 
     /// Estimate gas needed for execution of the `request` at the [`BlockId`].
@@ -272,14 +197,37 @@ pub trait EstimateCall: Call {
         at: BlockId,
         state_override: Option<StateOverride>,
     ) -> impl Future<Output = Result<U256, Self::Error>> + Send
+    where
+        Self: LoadPendingBlock,
+    {
+        self.estimate_gas_at_with_gas_cap(request, at, state_override, None)
+    }
+
+    /// Estimate gas needed for execution of the `request` at the [`BlockId`], with the highest
+    /// possible gas limit additionally capped at the given gas cap, if any.
+    fn estimate_gas_at_with_gas_cap(
+        &self,
+        request: TransactionRequest,
+        at: BlockId,
+        state_override: Option<StateOverride>,
+        gas_cap: Option<u64>,
+    ) -> impl Future<Output = Result<U256, Self::Error>> + Send
     where
         Self: LoadPendingBlock,
     {
         async move {
-            let (evm_env, at) = self.evm_env_at(at).await?;
+            let pending_state =
+                if at.is_pending() { self.local_pending_state().await? } else { None };
+            let (mut evm_env, at) = self.evm_env_at(at).await?;
+            if let Some(gas_cap) = gas_cap {
+                evm_env.block_env.gas_limit = evm_env.block_env.gas_limit.min(gas_cap);
+            }
 
             self.spawn_blocking_io(move |this| {
-                let state = this.state_at_block_id(at)?;
+                let state = match pending_state {
+                    Some(state) => SnapshotStateProvider::new(state, None),
+                    None => this.call_state_at_block_id(at)?,
+                };
                 EstimateCall::estimate_gas_with(&this, evm_env, request, state, state_override)
             })
             .await
@@ -320,6 +268,135 @@ pub trait EstimateCall: Call {
     }
 }
 
+/// Finds the lowest gas limit a transaction succeeds with, given the gas it used and the gas that
+/// was refunded when it was executed with `highest_gas_limit`.
+///
+/// Instead of searching the entire range, the estimate is derived from the execution that already
+/// happened:
+///
+///  1. The transaction is executed with the gas it spent before refunds were applied, which is
+///     exactly the gas limit it requires unless it performs calls.
+///  2. If that fails, the gas limit is padded by 64/63 and the call stipend, which accounts for the
+///     gas that is withheld from calls by EIP-150.
+///
+/// Only if the padded gas limit fails as well, e.g. because the transaction checks `gasleft()`
+/// itself, the gas limit is determined via [`binary_search_gas_limit`].
+///
+/// `transact` executes the transaction with the given gas limit.
+pub fn optimistic_gas_limit_search<Halt, E>(
+    gas_used: u64,
+    gas_refund: u64,
+    mut highest_gas_limit: u64,
+    mut transact: impl FnMut(u64) -> Result<ExecutionResult<Halt>, E>,
+) -> Result<u64, E>
+where
+    E: AsEthApiError + FromEthApiError,
+{
+    // the lowest value is capped by the gas used by the unconstrained transaction
+    let mut lowest_gas_limit = gas_used.saturating_sub(1);
+
+    // The gas used is net of refunds, but refunds are only applied after the execution, so the
+    // transaction needs at least the gas it spent.
+    let gas_spent = gas_used.saturating_add(gas_refund);
+    // Calls only forward 63/64 of the available gas, see EIP-150
+    let padded_gas_limit = gas_spent.saturating_add(CALL_STIPEND_GAS).saturating_mul(64) / 63;
+
+    for gas_limit in [gas_spent, padded_gas_limit] {
+        if gas_limit <= lowest_gas_limit || gas_limit >= highest_gas_limit {
+            continue
+        }
+
+        trace!(target: "rpc::eth::estimate", gas_limit, "Trying optimistic gas limit");
+
+        if try_gas_limit(&mut transact, gas_limit, &mut highest_gas_limit, &mut lowest_gas_limit)? {
+            return Ok(gas_limit)
+        }
+    }
+
+    // Pick a point that's close to the estimated gas
+    let mid_gas_limit = std::cmp::min(
+        gas_used.saturating_mul(3),
+        ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64,
+    );
+
+    binary_search_gas_limit(lowest_gas_limit, highest_gas_limit, mid_gas_limit, transact)
+}
+
+/// Finds the lowest gas limit a transaction succeeds with via binary search over
+/// `(lowest_gas_limit, highest_gas_limit]`, starting with `mid_gas_limit`.
+///
+/// The transaction must succeed with `highest_gas_limit`. The search stops once the range is within
+/// [`ESTIMATE_GAS_ERROR_RATIO`] of the highest gas limit.
+///
+/// `transact` executes the transaction with the given gas limit.
+pub fn binary_search_gas_limit<Halt, E>(
+    mut lowest_gas_limit: u64,
+    mut highest_gas_limit: u64,
+    mut mid_gas_limit: u64,
+    mut transact: impl FnMut(u64) -> Result<ExecutionResult<Halt>, E>,
+) -> Result<u64, E>
+where
+    E: AsEthApiError + FromEthApiError,
+{
+    trace!(target: "rpc::eth::estimate", ?highest_gas_limit, ?lowest_gas_limit, ?mid_gas_limit, "Starting binary search for gas");
+
+    // Binary search narrows the range to find the minimum gas limit needed for the transaction
+    // to succeed.
+    while lowest_gas_limit + 1 < highest_gas_limit {
+        // An estimation error is allowed once the current gas limit range used in the binary
+        // search is small enough (less than 1.5% of the highest gas limit)
+        // <https://github.com/ethereum/go-ethereum/blob/a5a4fa7032bb248f5a7c40f4e8df2b131c4186a4/eth/gasestimator/gasestimator.go#L152
+        if (highest_gas_limit - lowest_gas_limit) as f64 / (highest_gas_limit as f64) <
+            ESTIMATE_GAS_ERROR_RATIO
+        {
+            break
+        };
+
+        try_gas_limit(&mut transact, mid_gas_limit, &mut highest_gas_limit, &mut lowest_gas_limit)?;
+
+        // New midpoint
+        mid_gas_limit = ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64;
+    }
+
+    Ok(highest_gas_limit)
+}
+
+/// Executes the transaction with the given gas limit and updates the estimated gas range
+/// accordingly.
+///
+/// Returns whether the transaction succeeded.
+fn try_gas_limit<Halt, E>(
+    transact: &mut impl FnMut(u64) -> Result<ExecutionResult<Halt>, E>,
+    gas_limit: u64,
+    highest_gas_limit: &mut u64,
+    lowest_gas_limit: &mut u64,
+) -> Result<bool, E>
+where
+    E: AsEthApiError + FromEthApiError,
+{
+    // Execute transaction and handle potential gas errors, adjusting limits accordingly.
+    match transact(gas_limit) {
+        Err(err) if err.is_gas_too_high() => {
+            // Decrease the highest gas limit if gas is too high
+            *highest_gas_limit = gas_limit;
+            Ok(false)
+        }
+        Err(err) if err.is_gas_too_low() => {
+            // Increase the lowest gas limit if gas is too low
+            *lowest_gas_limit = gas_limit;
+            Ok(false)
+        }
+        // Handle other cases, including successful transactions.
+        res => {
+            let result = res?;
+            let success = result.is_success();
+            // Update the estimated gas range based on the transaction result.
+            update_estimated_gas_range(result, gas_limit, highest_gas_limit, lowest_gas_limit)?;
+            Ok(success)
+        }
+    }
+}
+
 /// Updates the highest and lowest gas limits for binary search based on the execution result.
 ///
 /// This function refines the gas limit estimates used in a binary search to find the optimal
@@ -352,3 +429,190 @@ pub fn update_estimated_gas_range<Halt>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+    use revm::context_interface::result::{HaltReason, Output, SuccessReason};
+
+    const BLOCK_GAS_LIMIT: u64 = 30_000_000;
+
+    /// A transaction that spends gas before and after an optional call.
+    #[derive(Debug, Default, Clone, Copy)]
+    struct MockTx {
+        gas_before_call: u64,
+        call_gas: Option<u64>,
+        gas_after_call: u64,
+        refund: u64,
+        /// The transaction reverts if the gas limit is lower, like a `gasleft()` check.
+        required_gas_limit: u64,
+    }
+
+    impl MockTx {
+        fn transact(&self, gas_limit: u64) -> ExecutionResult<HaltReason> {
+            let failure = ExecutionResult::Revert { gas_used: gas_limit, output: Bytes::new() };
+            if gas_limit < self.required_gas_limit {
+                return failure
+            }
+            let Some(mut remaining) = gas_limit.checked_sub(self.gas_before_call) else {
+                return failure
+            };
+            let mut spent = self.gas_before_call;
+            if let Some(call_gas) = self.call_gas {
+                // at most 63/64 of the remaining gas is forwarded to the call
+                if remaining - remaining / 64 < call_gas {
+                    return failure
+                }
+                remaining -= call_gas;
+                spent += call_gas;
+            }
+            if remaining < self.gas_after_call {
+                return failure
+            }
+            spent += self.gas_after_call;
+
+            let gas_refunded = self.refund.min(spent / 5);
+            ExecutionResult::Success {
+                reason: SuccessReason::Stop,
+                gas_used: spent - gas_refunded,
+                gas_refunded,
+                logs: vec![],
+                output: Output::Call(Bytes::new()),
+            }
+        }
+
+        /// Returns the exact lowest gas limit the transaction succeeds with.
+        fn min_gas_limit(&self) -> u64 {
+            let (mut low, mut high) = (0, BLOCK_GAS_LIMIT);
+            while low < high {
+                let mid = (low + high) / 2;
+                if self.transact(mid).is_success() {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+            low
+        }
+
+        /// Returns the gas used and refunded when executed with the block gas limit.
+        fn unconstrained(&self) -> (u64, u64) {
+            match self.transact(BLOCK_GAS_LIMIT) {
+                ExecutionResult::Success { gas_used, gas_refunded, .. } => (gas_used, gas_refunded),
+                res => panic!("unexpected result {res:?}"),
+            }
+        }
+
+        /// Returns the estimate of [`optimistic_gas_limit_search`] and the number of executions,
+        /// including the unconstrained one.
+        fn optimistic_estimate(&self) -> (u64, usize) {
+            let (gas_used, gas_refund) = self.unconstrained();
+            let mut executions = 1;
+            let estimate =
+                optimistic_gas_limit_search(gas_used, gas_refund, BLOCK_GAS_LIMIT, |gas_limit| {
+                    executions += 1;
+                    Ok::<_, EthApiError>(self.transact(gas_limit))
+                })
+                .unwrap();
+            (estimate, executions)
+        }
+
+        /// Returns the estimate of the plain binary search and the number of executions, including
+        /// the unconstrained one.
+        fn binary_search_estimate(&self) -> (u64, usize) {
+            let (gas_used, _) = self.unconstrained();
+            let lowest = gas_used - 1;
+            let mid = (gas_used * 3).min((BLOCK_GAS_LIMIT + lowest) / 2);
+            let mut executions = 1;
+            let estimate = binary_search_gas_limit(lowest, BLOCK_GAS_LIMIT, mid, |gas_limit| {
+                executions += 1;
+                Ok::<_, EthApiError>(self.transact(gas_limit))
+            })
+            .unwrap();
+            (estimate, executions)
+        }
+
+        /// Asserts that the optimistic estimate is as accurate as the binary search and returns
+        /// the number of executions of both.
+        fn assert_accurate(&self) -> (usize, usize) {
+            let min = self.min_gas_limit();
+            let (binary_search, binary_search_executions) = self.binary_search_estimate();
+            let (optimistic, optimistic_executions) = self.optimistic_estimate();
+
+            assert!(self.transact(binary_search).is_success());
+            assert!(self.transact(optimistic).is_success());
+            assert!(binary_search >= min);
+            assert!(optimistic >= min);
+            // the optimistic estimate exceeds the exact one by at most the call padding
+            assert!(
+                optimistic <= (binary_search + CALL_STIPEND_GAS) * 64 / 63,
+                "{optimistic} exceeds {binary_search}"
+            );
+
+            (optimistic_executions, binary_search_executions)
+        }
+    }
+
+    #[test]
+    fn estimate_without_calls() {
+        let tx = MockTx { gas_before_call: 51_000, ..Default::default() };
+        assert_eq!(tx.optimistic_estimate().0, tx.min_gas_limit());
+
+        let (optimistic, binary_search) = tx.assert_accurate();
+        assert_eq!(optimistic, 2);
+        assert!(binary_search > optimistic);
+    }
+
+    #[test]
+    fn estimate_with_refund() {
+        let tx = MockTx { gas_before_call: 100_000, refund: 19_900, ..Default::default() };
+        assert_eq!(tx.optimistic_estimate().0, tx.min_gas_limit());
+
+        let (optimistic, binary_search) = tx.assert_accurate();
+        assert_eq!(optimistic, 2);
+        assert!(binary_search > optimistic);
+    }
+
+    #[test]
+    fn estimate_with_call() {
+        let tx = MockTx {
+            gas_before_call: 30_000,
+            call_gas: Some(200_000),
+            refund: 4_800,
+            ..Default::default()
+        };
+        // the gas spent isn't sufficient, because not all gas is forwarded to the call
+        let (gas_used, gas_refund) = tx.unconstrained();
+        assert!(!tx.transact(gas_used + gas_refund).is_success());
+
+        let (optimistic, binary_search) = tx.assert_accurate();
+        assert_eq!(optimistic, 3);
+        assert!(binary_search > optimistic);
+
+        let tx = MockTx { gas_after_call: 10_000, ..tx };
+        let (optimistic, _) = tx.assert_accurate();
+        assert_eq!(optimistic, 2);
+    }
+
+    #[test]
+    fn estimate_with_gas_left_check() {
+        let tx =
+            MockTx { gas_before_call: 60_000, required_gas_limit: 500_000, ..Default::default() };
+        tx.assert_accurate();
+
+        let min = tx.min_gas_limit();
+        let (estimate, _) = tx.optimistic_estimate();
+        assert!(((estimate - min) as f64 / estimate as f64) < ESTIMATE_GAS_ERROR_RATIO);
+
+        // the binary search starts close to the gas used
+        let (gas_used, gas_refund) = tx.unconstrained();
+        let mut gas_limits = Vec::new();
+        optimistic_gas_limit_search(gas_used, gas_refund, BLOCK_GAS_LIMIT, |gas_limit| {
+            gas_limits.push(gas_limit);
+            Ok::<_, EthApiError>(tx.transact(gas_limit))
+        })
+        .unwrap();
+        assert_eq!(gas_limits[2], gas_used * 3);
+    }
+}
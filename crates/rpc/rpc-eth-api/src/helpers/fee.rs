@@ -165,7 +165,11 @@ pub trait EthFees:
                         .unwrap_or_default() as u128,
                 );
 
-                base_fee_per_blob_gas.push(last_entry.next_block_blob_fee().unwrap_or_default());
+                base_fee_per_blob_gas.push(
+                    last_entry
+                        .next_block_blob_fee(&self.provider().chain_spec())
+                        .unwrap_or_default(),
+                );
             } else {
                 // read the requested header range
                 let headers = self.provider()
@@ -184,7 +188,14 @@ pub trait EthFees:
                         .blob_params_at_timestamp(header.timestamp())
                         .unwrap_or_else(BlobParams::cancun);
 
-                    base_fee_per_blob_gas.push(header.blob_fee(blob_params).unwrap_or_default());
+                    base_fee_per_blob_gas.push(
+                        header
+                            .excess_blob_gas()
+                            .map(|excess_blob_gas| {
+                                chain_spec.blob_base_fee(blob_params, excess_blob_gas)
+                            })
+                            .unwrap_or_default(),
+                    );
                     blob_gas_used_ratio.push(
                         header.blob_gas_used().unwrap_or_default() as f64
                             / blob_params.max_blob_gas_per_block() as f64,
@@ -224,10 +235,12 @@ pub trait EthFees:
                 // Same goes for the `base_fee_per_blob_gas`:
                 // > "[..] includes the next block after the newest of the returned range, because this value can be derived from the newest block.
                 base_fee_per_blob_gas.push(
-                    last_header
-                    .maybe_next_block_blob_fee(
-                        chain_spec.blob_params_at_timestamp(last_header.timestamp())
-                    ).unwrap_or_default()
+                    chain_spec
+                        .next_block_blob_fee(
+                            last_header.header(),
+                            chain_spec.blob_params_at_timestamp(last_header.timestamp()),
+                        )
+                        .unwrap_or_default(),
                 );
             };
 
@@ -361,9 +374,14 @@ where
                 .latest_header()
                 .map_err(Self::Error::from_eth_err)?
                 .and_then(|h| {
-                    h.maybe_next_block_blob_fee(
-                        self.provider().chain_spec().blob_params_at_timestamp(h.timestamp()),
-                    )
+                    let chain_spec = self.provider().chain_spec();
+                    let blob_params = chain_spec.blob_params_at_timestamp(h.timestamp())?;
+                    let excess_blob_gas = chain_spec.next_block_excess_blob_gas(
+                        blob_params,
+                        h.excess_blob_gas()?,
+                        h.blob_gas_used()?,
+                    );
+                    Some(chain_spec.blob_base_fee(blob_params, excess_blob_gas))
                 })
                 .ok_or(EthApiError::ExcessBlobGasNotSet.into())
                 .map(U256::from)
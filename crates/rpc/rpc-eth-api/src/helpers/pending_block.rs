@@ -5,13 +5,15 @@ use super::SpawnBlocking;
 use crate::{types::RpcTypes, EthApiTypes, FromEthApiError, FromEvmError, RpcNodeCore};
 use alloy_consensus::{BlockHeader, Transaction};
 use alloy_eips::eip7840::BlobParams;
-use alloy_primitives::U256;
 use alloy_rpc_types_eth::BlockNumberOrTag;
 use futures::Future;
+use reth_chain_state::{
+    ExecutedBlockWithTrieUpdates, ExecutedTrieUpdates, MemoryOverlayStateProvider,
+};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_errors::{BlockExecutionError, BlockValidationError, ProviderError, RethError};
 use reth_evm::{
-    execute::{BlockBuilder, BlockBuilderOutcome},
+    execute::{BlockBuilder, BlockBuilderOutcome, ExecutionOutcome},
     ConfigureEvm, Evm, SpecFor,
 };
 use reth_node_api::NodePrimitives;
@@ -23,7 +25,7 @@ use reth_rpc_convert::RpcConvert;
 use reth_rpc_eth_types::{EthApiError, PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
 use reth_storage_api::{
     BlockReader, BlockReaderIdExt, ProviderBlock, ProviderHeader, ProviderReceipt, ProviderTx,
-    ReceiptProvider, StateProviderFactory,
+    ReceiptProvider, StateProviderBox, StateProviderFactory,
 };
 use reth_transaction_pool::{
     error::InvalidPoolTransactionError, BestTransactionsAttributes, PoolTransaction,
@@ -51,6 +53,7 @@ pub trait LoadPendingBlock:
         Provider: BlockReaderIdExt<Receipt: Receipt>
                       + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
                       + StateProviderFactory,
+        Pool: TransactionPool<Transaction: PoolTransaction<Consensus = ProviderTx<Self::Provider>>>,
         Evm: ConfigureEvm<Primitives = <Self as RpcNodeCore>::Primitives>,
         Primitives: NodePrimitives<
             BlockHeader = ProviderHeader<Self::Provider>,
@@ -138,8 +141,6 @@ pub trait LoadPendingBlock:
     > + Send
     where
         Self: SpawnBlocking,
-        Self::Pool:
-            TransactionPool<Transaction: PoolTransaction<Consensus = ProviderTx<Self::Provider>>>,
     {
         async move {
             let pending = self.pending_block_env_and_cfg()?;
@@ -151,6 +152,67 @@ pub trait LoadPendingBlock:
             };
 
             // we couldn't find the real pending block, so we need to build it ourselves
+            Ok(self
+                .local_pending_block_on(parent)
+                .await?
+                .map(|pending_block| (pending_block.block, pending_block.receipts)))
+        }
+    }
+
+    /// Returns the state of the locally built pending block.
+    ///
+    /// Returns `None` if there's an actual pending block from the CL, whose state is provided by
+    /// [`StateProviderFactory::pending`], or if the pending block couldn't be built.
+    fn local_pending_state(
+        &self,
+    ) -> impl Future<Output = Result<Option<StateProviderBox>, Self::Error>> + Send
+    where
+        Self: SpawnBlocking,
+    {
+        async move {
+            // without a latest block there's nothing to build the pending block on
+            if self.provider().latest_header().map_err(Self::Error::from_eth_err)?.is_none() {
+                return Ok(None)
+            }
+
+            let PendingBlockEnvOrigin::DerivedFromLatest(parent) =
+                self.pending_block_env_and_cfg()?.origin
+            else {
+                return Ok(None)
+            };
+
+            let parent_hash = parent.hash();
+            let Some(pending_block) = self.local_pending_block_on(parent).await? else {
+                return Ok(None)
+            };
+
+            let historical = self
+                .provider()
+                .history_by_block_hash(parent_hash)
+                .map_err(Self::Error::from_eth_err)?;
+            let executed_block = pending_block.to_executed_block::<Self::Primitives>();
+            Ok(Some(MemoryOverlayStateProvider::new(historical, vec![executed_block]).boxed()))
+        }
+    }
+
+    /// Returns the pending block that was built locally on top of the given parent, building it
+    /// from the txpool if there's no up to date one.
+    ///
+    /// Returns `None` if the pending block couldn't be built.
+    #[expect(clippy::type_complexity)]
+    fn local_pending_block_on(
+        &self,
+        parent: SealedHeader<ProviderHeader<Self::Provider>>,
+    ) -> impl Future<
+        Output = Result<
+            Option<PendingBlock<ProviderBlock<Self::Provider>, ProviderReceipt<Self::Provider>>>,
+            Self::Error,
+        >,
+    > + Send
+    where
+        Self: SpawnBlocking,
+    {
+        async move {
             let mut lock = self.pending_block().lock().await;
 
             let now = Instant::now();
@@ -158,16 +220,16 @@ pub trait LoadPendingBlock:
             // check if the block is still good
             if let Some(pending_block) = lock.as_ref() {
                 // this is guaranteed to be the `latest` header
-                if pending.evm_env.block_env.number == U256::from(pending_block.block.number()) &&
+                if parent.number() + 1 == pending_block.block.number() &&
                     parent.hash() == pending_block.block.parent_hash() &&
                     now <= pending_block.expires_at
                 {
-                    return Ok(Some((pending_block.block.clone(), pending_block.receipts.clone())));
+                    return Ok(Some(pending_block.clone()));
                 }
             }
 
             // no pending block from the CL yet, so we need to build it ourselves via txpool
-            let (sealed_block, receipts) = match self
+            let executed_block = match self
                 .spawn_blocking_io(move |this| {
                     // we rebuild the block
                     this.build_block(&parent)
@@ -181,17 +243,12 @@ pub trait LoadPendingBlock:
                 }
             };
 
-            let sealed_block = Arc::new(sealed_block);
-            let receipts = Arc::new(receipts);
-
             let now = Instant::now();
-            *lock = Some(PendingBlock::new(
-                now + Duration::from_secs(1),
-                sealed_block.clone(),
-                receipts.clone(),
-            ));
+            let pending_block =
+                PendingBlock::from_executed_block(now + Duration::from_secs(1), executed_block);
+            *lock = Some(pending_block.clone());
 
-            Ok(Some((sealed_block, receipts)))
+            Ok(Some(pending_block))
         }
     }
 
@@ -201,17 +258,11 @@ pub trait LoadPendingBlock:
     ///
     /// After Cancun, if the origin is the actual pending block, the block includes the EIP-4788 pre
     /// block contract call using the parent beacon block root received from the CL.
-    #[expect(clippy::type_complexity)]
     fn build_block(
         &self,
         parent: &SealedHeader<ProviderHeader<Self::Provider>>,
-    ) -> Result<
-        (RecoveredBlock<ProviderBlock<Self::Provider>>, Vec<ProviderReceipt<Self::Provider>>),
-        Self::Error,
-    >
+    ) -> Result<ExecutedBlockWithTrieUpdates<Self::Primitives>, Self::Error>
     where
-        Self::Pool:
-            TransactionPool<Transaction: PoolTransaction<Consensus = ProviderTx<Self::Provider>>>,
         EthApiError: From<ProviderError>,
     {
         let state_provider = self
@@ -335,9 +386,21 @@ pub trait LoadPendingBlock:
             cumulative_gas_used += gas_used;
         }
 
-        let BlockBuilderOutcome { execution_result, block, .. } =
+        let BlockBuilderOutcome { execution_result, hashed_state, trie_updates, block } =
             builder.finish(&state_provider).map_err(Self::Error::from_eth_err)?;
 
-        Ok((block, execution_result.receipts))
+        let execution_outcome = ExecutionOutcome::new(
+            db.take_bundle(),
+            vec![execution_result.receipts],
+            block.number(),
+            vec![execution_result.requests],
+        );
+
+        Ok(ExecutedBlockWithTrieUpdates::new(
+            Arc::new(block),
+            Arc::new(execution_outcome),
+            Arc::new(hashed_state),
+            ExecutedTrieUpdates::Present(Arc::new(trie_updates)),
+        ))
     }
 }
@@ -3,25 +3,63 @@
 use super::{EthApiSpec, LoadPendingBlock, SpawnBlocking};
 use crate::{EthApiTypes, FromEthApiError, RpcNodeCore, RpcNodeCoreExt};
 use alloy_consensus::constants::KECCAK_EMPTY;
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, Bytes, B256, U256};
-use alloy_rpc_types_eth::{Account, AccountInfo, EIP1186AccountProofResponse};
+use alloy_eips::{BlockId, BlockNumberOrTag};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy_rpc_types_eth::{Account, AccountInfo};
 use alloy_serde::JsonStorageKey;
 use futures::Future;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
-use reth_errors::RethError;
+use reth_errors::{ProviderError, RethError};
 use reth_evm::{ConfigureEvm, EvmEnvFor};
-use reth_rpc_eth_types::{EthApiError, PendingBlockEnv, RpcInvalidTransactionError};
+use reth_rpc_eth_types::{
+    proof::AccountProofResponse, EthApiError, EthProofCache, PendingBlockEnv,
+    RpcInvalidTransactionError,
+};
 use reth_storage_api::{
     BlockIdReader, BlockNumReader, StateProvider, StateProviderBox, StateProviderFactory,
 };
 use reth_transaction_pool::TransactionPool;
+use reth_trie_common::MultiProofTargets;
 
 /// Helper methods for `eth_` methods relating to state (accounts).
 pub trait EthState: LoadState + SpawnBlocking {
     /// Returns the maximum number of blocks into the past for generating state proofs.
     fn max_proof_window(&self) -> u64;
 
+    /// Returns the cache of the revealed tries of repeated `eth_getProof` targets, if configured.
+    fn proof_cache(&self) -> Option<&EthProofCache> {
+        None
+    }
+
+    /// Returns the state after the locally built pending block, that's used for queries against
+    /// [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag).
+    ///
+    /// Returns `None` by default, in which case these queries are served from
+    /// [`LoadState::state_at_block_id`].
+    fn pending_state(
+        &self,
+    ) -> impl Future<Output = Result<Option<StateProviderBox>, Self::Error>> + Send {
+        futures::future::ready(Ok(None))
+    }
+
+    /// Returns the state at the given [`BlockId`] or the latest.
+    ///
+    /// Unlike [`LoadState::state_at_block_id_or_latest`], this returns the [`Self::pending_state`]
+    /// for [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag), if there is one.
+    fn state_at_block_id_or_pending(
+        &self,
+        block_id: Option<BlockId>,
+    ) -> impl Future<Output = Result<StateProviderBox, Self::Error>> + Send {
+        async move {
+            if block_id.is_some_and(|block_id| block_id.is_pending()) {
+                if let Some(state) = self.pending_state().await? {
+                    return Ok(state)
+                }
+            }
+            self.state_at_block_id_or_latest(block_id)
+        }
+    }
+
     /// Returns the number of transactions sent from an address at the given block identifier.
     ///
     /// If this is [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag) then this will
@@ -31,7 +69,13 @@ pub trait EthState: LoadState + SpawnBlocking {
         address: Address,
         block_id: Option<BlockId>,
     ) -> impl Future<Output = Result<U256, Self::Error>> + Send {
-        LoadState::transaction_count(self, address, block_id)
+        async move {
+            let state = self.state_at_block_id_or_pending(block_id).await?;
+            self.spawn_blocking_io(move |this| {
+                this.transaction_count_at(&state, address, block_id == Some(BlockId::pending()))
+            })
+            .await
+        }
     }
 
     /// Returns code of given account, at given blocknumber.
@@ -40,7 +84,17 @@ pub trait EthState: LoadState + SpawnBlocking {
         address: Address,
         block_id: Option<BlockId>,
     ) -> impl Future<Output = Result<Bytes, Self::Error>> + Send {
-        LoadState::get_code(self, address, block_id)
+        async move {
+            let state = self.state_at_block_id_or_pending(block_id).await?;
+            self.spawn_blocking_io(move |_| {
+                Ok(state
+                    .account_code(&address)
+                    .map_err(Self::Error::from_eth_err)?
+                    .unwrap_or_default()
+                    .original_bytes())
+            })
+            .await
+        }
     }
 
     /// Returns balance of given account, at given blocknumber.
@@ -49,13 +103,16 @@ pub trait EthState: LoadState + SpawnBlocking {
         address: Address,
         block_id: Option<BlockId>,
     ) -> impl Future<Output = Result<U256, Self::Error>> + Send {
-        self.spawn_blocking_io(move |this| {
-            Ok(this
-                .state_at_block_id_or_latest(block_id)?
-                .account_balance(&address)
-                .map_err(Self::Error::from_eth_err)?
-                .unwrap_or_default())
-        })
+        async move {
+            let state = self.state_at_block_id_or_pending(block_id).await?;
+            self.spawn_blocking_io(move |_| {
+                Ok(state
+                    .account_balance(&address)
+                    .map_err(Self::Error::from_eth_err)?
+                    .unwrap_or_default())
+            })
+            .await
+        }
     }
 
     /// Returns values stored of given account, at given blocknumber.
@@ -65,15 +122,19 @@ pub trait EthState: LoadState + SpawnBlocking {
         index: JsonStorageKey,
         block_id: Option<BlockId>,
     ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
-        self.spawn_blocking_io(move |this| {
-            Ok(B256::new(
-                this.state_at_block_id_or_latest(block_id)?
-                    .storage(address, index.as_b256())
-                    .map_err(Self::Error::from_eth_err)?
-                    .unwrap_or_default()
-                    .to_be_bytes(),
-            ))
-        })
+        async move {
+            let state = self.state_at_block_id_or_pending(block_id).await?;
+            self.spawn_blocking_io(move |_| {
+                Ok(B256::new(
+                    state
+                        .storage(address, index.as_b256())
+                        .map_err(Self::Error::from_eth_err)?
+                        .unwrap_or_default()
+                        .to_be_bytes(),
+                ))
+            })
+            .await
+        }
     }
 
     /// Returns values stored of given account, with Merkle-proof, at given blocknumber.
@@ -82,10 +143,7 @@ pub trait EthState: LoadState + SpawnBlocking {
         address: Address,
         keys: Vec<JsonStorageKey>,
         block_id: Option<BlockId>,
-    ) -> Result<
-        impl Future<Output = Result<EIP1186AccountProofResponse, Self::Error>> + Send,
-        Self::Error,
-    >
+    ) -> Result<impl Future<Output = Result<AccountProofResponse, Self::Error>> + Send, Self::Error>
     where
         Self: EthApiSpec,
     {
@@ -99,10 +157,30 @@ pub trait EthState: LoadState + SpawnBlocking {
             let chain_info = self.chain_info().map_err(Self::Error::from_eth_err)?;
             let block_id = block_id.unwrap_or_default();
 
+            // Resolve the `safe` and `finalized` tags to a block hash once, so that the proof and
+            // the returned hash belong to the same block even if the tag moves meanwhile. The
+            // hashes of blocks that are not persisted yet are served from the in-memory state.
+            let block_hash = if block_id.is_pending() {
+                None
+            } else {
+                self.provider().block_hash_for_id(block_id).map_err(Self::Error::from_eth_err)?
+            };
+            let at = match (block_id, block_hash) {
+                (
+                    BlockId::Number(BlockNumberOrTag::Safe | BlockNumberOrTag::Finalized),
+                    Some(block_hash),
+                ) => block_hash.into(),
+                _ => block_id,
+            };
+            let anchor = match at {
+                BlockId::Hash(hash) => Some(hash.block_hash),
+                BlockId::Number(_) => None,
+            };
+
             // Check whether the distance to the block exceeds the maximum configured window.
             let block_number = self
                 .provider()
-                .block_number_for_id(block_id)
+                .block_number_for_id(at)
                 .map_err(Self::Error::from_eth_err)?
                 .ok_or(EthApiError::HeaderNotFound(block_id))?;
             let max_window = self.max_proof_window();
@@ -111,12 +189,43 @@ pub trait EthState: LoadState + SpawnBlocking {
             }
 
             self.spawn_blocking_io(move |this| {
-                let state = this.state_at_block_id(block_id)?;
                 let storage_keys = keys.iter().map(|key| key.as_b256()).collect::<Vec<_>>();
-                let proof = state
-                    .proof(Default::default(), address, &storage_keys)
-                    .map_err(Self::Error::from_eth_err)?;
-                Ok(proof.into_eip1186_response(keys))
+
+                // Proofs of blocks that are identified by hash can be served from the cached
+                // tries of the same targets.
+                let proof_cache = anchor.zip(this.proof_cache());
+                let cached = proof_cache
+                    .and_then(|(block_hash, cache)| cache.get(block_hash, address, &storage_keys));
+
+                let proof = match (cached, proof_cache) {
+                    (Some(proof), _) => proof,
+                    (None, Some((block_hash, cache))) => {
+                        let state = this.state_at_block_id(at)?;
+                        let targets = MultiProofTargets::account_with_slots(
+                            keccak256(address),
+                            storage_keys.iter().map(keccak256),
+                        );
+                        let multiproof = state
+                            .multiproof(Default::default(), targets)
+                            .map_err(Self::Error::from_eth_err)?;
+                        let proof = multiproof
+                            .account_proof(address, &storage_keys)
+                            .map_err(|err| Self::Error::from_eth_err(ProviderError::from(err)))?;
+                        cache.insert(block_hash, address, &storage_keys, multiproof);
+                        proof
+                    }
+                    (None, None) => {
+                        let state = this.state_at_block_id(at)?;
+                        state
+                            .proof(Default::default(), address, &storage_keys)
+                            .map_err(Self::Error::from_eth_err)?
+                    }
+                };
+                Ok(AccountProofResponse {
+                    proof: proof.into_eip1186_response(keys),
+                    block_hash,
+                    block_number,
+                })
             })
             .await
         })
@@ -207,10 +316,17 @@ pub trait LoadState:
 
     /// Returns the state at the given [`BlockId`] enum.
     ///
-    /// Note: if not [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag) then this
-    /// will only return canonical state. See also <https://github.com/paradigmxyz/reth/issues/4515>
+    /// Note: if not [`BlockNumberOrTag::Pending`](alloy_eips::BlockNumberOrTag) or a block hash
+    /// without `requireCanonical` then this will only return canonical state. See also
+    /// <https://github.com/paradigmxyz/reth/issues/4515>
     fn state_at_block_id(&self, at: BlockId) -> Result<StateProviderBox, Self::Error> {
-        self.provider().state_by_block_id(at).map_err(Self::Error::from_eth_err)
+        self.provider().state_by_block_id(at).map_err(|err| match err {
+            // keep the requested block id, e.g. whether a canonical block was required
+            ProviderError::StateForHashNotFound(_) => {
+                Self::Error::from_eth_err(EthApiError::HeaderNotFound(at))
+            }
+            err => Self::Error::from_eth_err(err),
+        })
     }
 
     /// Returns the _latest_ state
@@ -314,39 +430,50 @@ pub trait LoadState:
         Self: SpawnBlocking,
     {
         self.spawn_blocking_io(move |this| {
-            // first fetch the on chain nonce of the account
-            let on_chain_account_nonce = this
-                .state_at_block_id_or_latest(block_id)?
-                .account_nonce(&address)
-                .map_err(Self::Error::from_eth_err)?
-                .unwrap_or_default();
+            let state = this.state_at_block_id_or_latest(block_id)?;
+            this.transaction_count_at(&state, address, block_id == Some(BlockId::pending()))
+        })
+    }
 
-            if block_id == Some(BlockId::pending()) {
-                // for pending tag we need to find the highest nonce of txn in the pending state.
-                if let Some(highest_pool_tx) = this
-                    .pool()
-                    .get_highest_consecutive_transaction_by_sender(address, on_chain_account_nonce)
+    /// Returns the number of transactions sent from an address in the given state.
+    ///
+    /// If `pending` is true, this also accounts for the consecutive transactions of the address in
+    /// the pool.
+    fn transaction_count_at(
+        &self,
+        state: &dyn StateProvider,
+        address: Address,
+        pending: bool,
+    ) -> Result<U256, Self::Error> {
+        // first fetch the on chain nonce of the account
+        let on_chain_account_nonce =
+            state.account_nonce(&address).map_err(Self::Error::from_eth_err)?.unwrap_or_default();
+
+        if pending {
+            // for pending tag we need to find the highest nonce of txn in the pending state.
+            if let Some(highest_pool_tx) = self
+                .pool()
+                .get_highest_consecutive_transaction_by_sender(address, on_chain_account_nonce)
+            {
                 {
-                    {
-                        // and the corresponding txcount is nonce + 1 of the highest tx in the pool
-                        // (on chain nonce is increased after tx)
-                        let next_tx_nonce =
-                            highest_pool_tx.nonce().checked_add(1).ok_or_else(|| {
-                                Self::Error::from(EthApiError::InvalidTransaction(
-                                    RpcInvalidTransactionError::NonceMaxValue,
-                                ))
-                            })?;
-
-                        // guard against drifts in the pool
-                        let next_tx_nonce = on_chain_account_nonce.max(next_tx_nonce);
-
-                        let tx_count = on_chain_account_nonce.max(next_tx_nonce);
-                        return Ok(U256::from(tx_count));
-                    }
+                    // and the corresponding txcount is nonce + 1 of the highest tx in the pool
+                    // (on chain nonce is increased after tx)
+                    let next_tx_nonce =
+                        highest_pool_tx.nonce().checked_add(1).ok_or_else(|| {
+                            Self::Error::from(EthApiError::InvalidTransaction(
+                                RpcInvalidTransactionError::NonceMaxValue,
+                            ))
+                        })?;
+
+                    // guard against drifts in the pool
+                    let next_tx_nonce = on_chain_account_nonce.max(next_tx_nonce);
+
+                    let tx_count = on_chain_account_nonce.max(next_tx_nonce);
+                    return Ok(U256::from(tx_count));
                 }
             }
-            Ok(U256::from(on_chain_account_nonce))
-        })
+        }
+        Ok(U256::from(on_chain_account_nonce))
     }
 
     /// Returns code of given account, at the given identifier.
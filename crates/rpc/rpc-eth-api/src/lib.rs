@@ -21,9 +21,9 @@ pub mod node;
 pub mod pubsub;
 pub mod types;
 
-pub use bundle::{EthBundleApiServer, EthCallBundleApiServer};
+pub use bundle::{EthBundleApiServer, EthBundleStatusApiServer, EthCallBundleApiServer};
 pub use core::{EthApiServer, FullEthApiServer};
-pub use ext::L2EthApiExtServer;
+pub use ext::{EthBlockSidecarsApiExtServer, EthReceiptsRangeApiExtServer, L2EthApiExtServer};
 pub use filter::{EngineEthFilter, EthFilterApiServer, QueryLimits};
 pub use node::{RpcNodeCore, RpcNodeCoreExt};
 pub use pubsub::EthPubSubApiServer;
@@ -34,11 +34,11 @@ pub use reth_rpc_eth_types::error::{
 pub use types::{EthApiTypes, FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction};
 
 #[cfg(feature = "client")]
-pub use bundle::{EthBundleApiClient, EthCallBundleApiClient};
+pub use bundle::{EthBundleApiClient, EthBundleStatusApiClient, EthCallBundleApiClient};
 #[cfg(feature = "client")]
 pub use core::EthApiClient;
 #[cfg(feature = "client")]
-pub use ext::L2EthApiExtClient;
+pub use ext::{EthBlockSidecarsApiExtClient, EthReceiptsRangeApiExtClient, L2EthApiExtClient};
 #[cfg(feature = "client")]
 pub use filter::EthFilterApiClient;
 
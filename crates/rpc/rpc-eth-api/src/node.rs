@@ -1,9 +1,11 @@
 //! Helper trait for interfacing with [`FullNodeComponents`].
 
+use reth_chainspec::ChainSpecProvider;
 use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_rpc_eth_types::EthStateCache;
 use reth_storage_api::{BlockReader, ProviderBlock, ProviderReceipt};
+use std::sync::Arc;
 
 /// Helper trait that provides the same interface as [`FullNodeComponents`] but without requiring
 /// implementation of trait bounds.
@@ -29,6 +31,12 @@ pub trait RpcNodeCore: Clone + Send + Sync {
     /// Builds new blocks.
     type PayloadBuilder: Send + Sync + Clone;
 
+    /// The consensus implementation of the node, to validate blocks and headers.
+    type Consensus: Send + Sync + Clone;
+
+    /// The chain spec of the node.
+    type ChainSpec: Send + Sync;
+
     /// Returns the transaction pool of the node.
     fn pool(&self) -> &Self::Pool;
 
@@ -43,6 +51,12 @@ pub trait RpcNodeCore: Clone + Send + Sync {
 
     /// Returns the provider of the node.
     fn provider(&self) -> &Self::Provider;
+
+    /// Returns the consensus implementation of the node.
+    fn consensus(&self) -> &Self::Consensus;
+
+    /// Returns the chain spec of the node.
+    fn chain_spec(&self) -> Arc<Self::ChainSpec>;
 }
 
 impl<T> RpcNodeCore for T
@@ -55,6 +69,8 @@ where
     type Evm = T::Evm;
     type Network = T::Network;
     type PayloadBuilder = PayloadBuilderHandle<<T::Types as NodeTypes>::Payload>;
+    type Consensus = T::Consensus;
+    type ChainSpec = <T::Types as NodeTypes>::ChainSpec;
 
     #[inline]
     fn pool(&self) -> &Self::Pool {
@@ -80,6 +96,16 @@ where
     fn provider(&self) -> &Self::Provider {
         FullNodeComponents::provider(self)
     }
+
+    #[inline]
+    fn consensus(&self) -> &Self::Consensus {
+        FullNodeComponents::consensus(self)
+    }
+
+    #[inline]
+    fn chain_spec(&self) -> Arc<Self::ChainSpec> {
+        FullNodeComponents::provider(self).chain_spec()
+    }
 }
 
 /// Additional components, asides the core node components, needed to run `eth_` namespace API
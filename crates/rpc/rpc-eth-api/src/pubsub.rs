@@ -8,6 +8,10 @@ use jsonrpsee::proc_macros::rpc;
 #[rpc(server, namespace = "eth")]
 pub trait EthPubSubApi<T: RpcObject> {
     /// Create an ethereum subscription for the given params
+    ///
+    /// Every subscription buffers a bounded number of messages for a consumer that can't keep up.
+    /// Once the buffer is full, messages are dropped or the subscription is closed, depending on
+    /// the configured overflow policy.
     #[subscription(
         name = "subscribe" => "subscription",
         unsubscribe = "unsubscribe",
@@ -0,0 +1,85 @@
+//! Types of the reth specific extensions of `eth_createAccessList`.
+
+use alloy_eips::eip2930::{AccessList, AccessListResult};
+use alloy_primitives::{Address, B256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The maximum number of times the transaction is executed to find a stable access list.
+pub const MAX_ACCESS_LIST_ITERATIONS: usize = 10;
+
+/// Options of `eth_createAccessList`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListOptions {
+    /// Whether to re-execute the transaction with the generated access list until the list no
+    /// longer changes, like geth does.
+    ///
+    /// The access list itself changes the gas costs of the transaction, so a transaction that
+    /// branches on the remaining gas can touch different accounts and slots once the list is
+    /// included.
+    #[serde(default)]
+    pub until_stable: bool,
+}
+
+/// The result of `eth_createAccessList`, extended by the gas used without any access list.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListResponse {
+    /// The generated access list and the gas used when it's included.
+    #[serde(flatten)]
+    pub result: AccessListResult,
+    /// The gas used by the transaction without any access list.
+    pub gas_used_without_access_list: U256,
+}
+
+/// Returns `true` if both access lists contain the same addresses and storage keys, regardless of
+/// their order.
+pub fn is_same_access_list(a: &AccessList, b: &AccessList) -> bool {
+    access_list_entries(a) == access_list_entries(b)
+}
+
+fn access_list_entries(access_list: &AccessList) -> BTreeMap<Address, BTreeSet<B256>> {
+    let mut entries = BTreeMap::<_, BTreeSet<_>>::new();
+    for item in access_list.iter() {
+        entries.entry(item.address).or_default().extend(item.storage_keys.iter().copied());
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip2930::AccessListItem;
+
+    #[test]
+    fn compares_access_lists_regardless_of_order() {
+        let item = |address: u8, keys: &[u8]| AccessListItem {
+            address: Address::with_last_byte(address),
+            storage_keys: keys.iter().map(|key| B256::with_last_byte(*key)).collect(),
+        };
+
+        let a = AccessList(vec![item(1, &[1, 2]), item(2, &[])]);
+        let b = AccessList(vec![item(2, &[]), item(1, &[2, 1])]);
+        assert!(is_same_access_list(&a, &b));
+
+        let c = AccessList(vec![item(1, &[1]), item(2, &[])]);
+        assert!(!is_same_access_list(&a, &c));
+    }
+
+    #[test]
+    fn serializes_flattened_result() {
+        let response = AccessListResponse {
+            result: AccessListResult {
+                access_list: AccessList::default(),
+                gas_used: U256::from(30_000),
+                error: None,
+            },
+            gas_used_without_access_list: U256::from(21_000),
+        };
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["gasUsed"], "0x7530");
+        assert_eq!(json["gasUsedWithoutAccessList"], "0x5208");
+        assert_eq!(serde_json::from_value::<AccessListResponse>(json).unwrap(), response);
+    }
+}
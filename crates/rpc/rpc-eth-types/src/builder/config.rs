@@ -1,14 +1,14 @@
 //! Configuration for `eth` namespace APIs.
 
-use std::time::Duration;
+use std::{fmt, str::FromStr, time::Duration};
 
 use crate::{
     EthStateCacheConfig, FeeHistoryCacheConfig, GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP,
 };
 use reth_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_BLOCKS_PER_FILTER,
-    DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_MAX_TRACE_FILTER_BLOCKS,
-    DEFAULT_PROOF_PERMITS,
+    DEFAULT_MAX_LOGS_PER_RESPONSE, DEFAULT_MAX_LOGS_PER_STREAM, DEFAULT_MAX_SIMULATE_BLOCKS,
+    DEFAULT_MAX_TRACE_FILTER_BLOCKS, DEFAULT_PROOF_PERMITS, DEFAULT_SUBSCRIPTION_BUFFER_SIZE,
 };
 use serde::{Deserialize, Serialize};
 
@@ -32,6 +32,8 @@ pub struct EthConfig {
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum number of logs that can be streamed by a single `eth_getLogsStream` subscription.
+    pub max_logs_per_stream: usize,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [`RPC_DEFAULT_GAS_CAP`]
@@ -45,6 +47,12 @@ pub struct EthConfig {
     pub fee_history_cache: FeeHistoryCacheConfig,
     /// The maximum number of getproof calls that can be executed concurrently.
     pub proof_permits: usize,
+    /// The maximum number of getproof targets whose revealed tries are cached, disabled if zero.
+    pub proof_cache_max_entries: u32,
+    /// Maximum number of messages buffered per `eth_subscribe` subscription.
+    pub subscription_buffer_size: usize,
+    /// What happens to a subscription whose buffer is full.
+    pub subscription_overflow_policy: SubscriptionOverflowPolicy,
 }
 
 impl EthConfig {
@@ -53,8 +61,17 @@ impl EthConfig {
         EthFilterConfig::default()
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
+            .max_logs_per_stream(self.max_logs_per_stream)
             .stale_filter_ttl(self.stale_filter_ttl)
     }
+
+    /// Returns the config for the `eth_subscribe` handler.
+    pub const fn pubsub_config(&self) -> EthPubSubConfig {
+        EthPubSubConfig {
+            buffer_size: self.subscription_buffer_size,
+            overflow_policy: self.subscription_overflow_policy,
+        }
+    }
 }
 
 impl Default for EthConfig {
@@ -67,11 +84,15 @@ impl Default for EthConfig {
             max_trace_filter_blocks: DEFAULT_MAX_TRACE_FILTER_BLOCKS,
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            max_logs_per_stream: DEFAULT_MAX_LOGS_PER_STREAM,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             rpc_max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
+            proof_cache_max_entries: 0,
+            subscription_buffer_size: DEFAULT_SUBSCRIPTION_BUFFER_SIZE,
+            subscription_overflow_policy: SubscriptionOverflowPolicy::default(),
         }
     }
 }
@@ -113,6 +134,12 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of logs per `eth_getLogsStream` subscription
+    pub const fn max_logs_per_stream(mut self, max_logs: usize) -> Self {
+        self.max_logs_per_stream = max_logs;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub const fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
@@ -136,6 +163,27 @@ impl EthConfig {
         self.proof_permits = permits;
         self
     }
+
+    /// Configures the maximum number of getproof targets whose revealed tries are cached
+    pub const fn proof_cache_max_entries(mut self, max_entries: u32) -> Self {
+        self.proof_cache_max_entries = max_entries;
+        self
+    }
+
+    /// Configures the maximum number of messages buffered per `eth_subscribe` subscription
+    pub const fn subscription_buffer_size(mut self, size: usize) -> Self {
+        self.subscription_buffer_size = size;
+        self
+    }
+
+    /// Configures what happens to a subscription whose buffer is full
+    pub const fn subscription_overflow_policy(
+        mut self,
+        policy: SubscriptionOverflowPolicy,
+    ) -> Self {
+        self.subscription_overflow_policy = policy;
+        self
+    }
 }
 
 /// Config for the filter
@@ -149,6 +197,10 @@ pub struct EthFilterConfig {
     ///
     /// If `None` then no limit is enforced.
     pub max_logs_per_response: Option<usize>,
+    /// Maximum number of logs that can be streamed by a single `eth_getLogsStream` subscription.
+    ///
+    /// If `None` then no limit is enforced.
+    pub max_logs_per_stream: Option<usize>,
     /// How long a filter remains valid after the last poll.
     ///
     /// A filter is considered stale if it has not been polled for longer than this duration and
@@ -170,6 +222,13 @@ impl EthFilterConfig {
         self
     }
 
+    /// Sets the maximum number of logs that can be streamed by a single `eth_getLogsStream`
+    /// subscription.
+    pub const fn max_logs_per_stream(mut self, num: usize) -> Self {
+        self.max_logs_per_stream = Some(num);
+        self
+    }
+
     /// Sets how long a filter remains valid after the last poll before it will be removed.
     pub const fn stale_filter_ttl(mut self, duration: Duration) -> Self {
         self.stale_filter_ttl = duration;
@@ -182,8 +241,88 @@ impl Default for EthFilterConfig {
         Self {
             max_blocks_per_filter: None,
             max_logs_per_response: None,
+            max_logs_per_stream: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
         }
     }
 }
+
+/// Config for the `eth_subscribe` handler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EthPubSubConfig {
+    /// Maximum number of messages that are buffered per subscription while the connection can't
+    /// keep up with them.
+    pub buffer_size: usize,
+    /// What happens to a subscription whose buffer is full.
+    pub overflow_policy: SubscriptionOverflowPolicy,
+}
+
+impl EthPubSubConfig {
+    /// Sets the maximum number of messages that are buffered per subscription.
+    pub const fn buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = size;
+        self
+    }
+
+    /// Sets what happens to a subscription whose buffer is full.
+    pub const fn overflow_policy(mut self, policy: SubscriptionOverflowPolicy) -> Self {
+        self.overflow_policy = policy;
+        self
+    }
+}
+
+impl Default for EthPubSubConfig {
+    fn default() -> Self {
+        Self {
+            buffer_size: DEFAULT_SUBSCRIPTION_BUFFER_SIZE,
+            overflow_policy: SubscriptionOverflowPolicy::default(),
+        }
+    }
+}
+
+/// What happens to a subscription if its consumer is too slow and the buffer of the subscription
+/// is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SubscriptionOverflowPolicy {
+    /// The oldest buffered message is dropped to make room for the new one.
+    #[default]
+    DropOldest,
+    /// The new message is dropped.
+    DropNewest,
+    /// The subscription is closed.
+    Disconnect,
+}
+
+impl SubscriptionOverflowPolicy {
+    /// Returns the name of the policy.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::DropOldest => "drop-oldest",
+            Self::DropNewest => "drop-newest",
+            Self::Disconnect => "disconnect",
+        }
+    }
+}
+
+impl fmt::Display for SubscriptionOverflowPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SubscriptionOverflowPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drop-oldest" => Ok(Self::DropOldest),
+            "drop-newest" => Ok(Self::DropNewest),
+            "disconnect" => Ok(Self::Disconnect),
+            _ => Err(format!(
+                "invalid overflow policy {s}, expected drop-oldest, drop-newest or disconnect"
+            )),
+        }
+    }
+}
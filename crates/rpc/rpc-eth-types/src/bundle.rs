@@ -0,0 +1,57 @@
+//! Types of bundles that are forwarded to external builders.
+
+use alloy_primitives::{B256, U256};
+use serde::{Deserialize, Serialize};
+
+/// The state of the forwarding of a bundle to a single endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BundleForwardState {
+    /// The bundle wasn't accepted by the endpoint yet, but will be retried.
+    Pending,
+    /// The bundle was accepted by the endpoint.
+    Forwarded,
+    /// The endpoint rejected the bundle or all attempts failed.
+    Failed,
+}
+
+/// The status of a forwarded bundle at a single endpoint.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleEndpointStatus {
+    /// The URL of the endpoint.
+    pub url: String,
+    /// The state of the forwarding.
+    pub state: BundleForwardState,
+    /// The number of attempts made so far.
+    pub attempts: u32,
+    /// The error of the last failed attempt, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The status of a bundle that was sent via `eth_sendBundle` and forwarded to the configured
+/// endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardedBundleStatus {
+    /// The hash of the bundle.
+    pub bundle_hash: B256,
+    /// The block the bundle targets.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The gas used by the bundle in the local simulation.
+    #[serde(with = "alloy_serde::quantity")]
+    pub simulated_gas_used: u64,
+    /// The coinbase payment of the bundle in the local simulation.
+    pub simulated_coinbase_diff: U256,
+    /// The status of the bundle at every endpoint.
+    pub endpoints: Vec<BundleEndpointStatus>,
+}
+
+impl ForwardedBundleStatus {
+    /// Returns `true` if at least one endpoint accepted the bundle.
+    pub fn is_forwarded(&self) -> bool {
+        self.endpoints.iter().any(|endpoint| endpoint.state == BundleForwardState::Forwarded)
+    }
+}
@@ -1,6 +1,7 @@
 //! Async caching support for eth RPC
 
 use super::{EthStateCacheConfig, MultiConsumerLruCache};
+use crate::cache::state::{StateSnapshot, StateSnapshotCache};
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockHashOrNumber;
 use alloy_primitives::B256;
@@ -28,6 +29,7 @@ pub mod config;
 pub mod db;
 pub mod metrics;
 pub mod multi_consumer;
+pub mod state;
 
 /// The type that can send the response to a requested [`RecoveredBlock`]
 type BlockTransactionsResponseSender<T> = oneshot::Sender<ProviderResult<Option<Vec<T>>>>;
@@ -72,11 +74,13 @@ type HeaderLruCache<H, L> = MultiConsumerLruCache<B256, H, L, HeaderResponseSend
 #[derive(Debug)]
 pub struct EthStateCache<B: Block, R> {
     to_service: UnboundedSender<CacheAction<B, R>>,
+    /// Snapshots of the state at recently called blocks.
+    state_snapshots: Arc<StateSnapshotCache>,
 }
 
 impl<B: Block, R> Clone for EthStateCache<B, R> {
     fn clone(&self) -> Self {
-        Self { to_service: self.to_service.clone() }
+        Self { to_service: self.to_service.clone(), state_snapshots: self.state_snapshots.clone() }
     }
 }
 
@@ -89,6 +93,7 @@ impl<B: Block, R: Send + Sync> EthStateCache<B, R> {
         max_receipts: u32,
         max_headers: u32,
         max_concurrent_db_operations: usize,
+        max_state_snapshots: u32,
     ) -> (Self, EthStateCacheService<Provider, Tasks>)
     where
         Provider: BlockReader<Block = B, Receipt = R>,
@@ -104,7 +109,10 @@ impl<B: Block, R: Send + Sync> EthStateCache<B, R> {
             action_task_spawner,
             rate_limiter: Arc::new(Semaphore::new(max_concurrent_db_operations)),
         };
-        let cache = Self { to_service };
+        let cache = Self {
+            to_service,
+            state_snapshots: Arc::new(StateSnapshotCache::new(max_state_snapshots)),
+        };
         (cache, service)
     }
 
@@ -137,6 +145,7 @@ impl<B: Block, R: Send + Sync> EthStateCache<B, R> {
             max_receipts,
             max_headers,
             max_concurrent_db_requests,
+            max_state_snapshots,
         } = config;
         let (this, service) = Self::create(
             provider,
@@ -145,11 +154,19 @@ impl<B: Block, R: Send + Sync> EthStateCache<B, R> {
             max_receipts,
             max_headers,
             max_concurrent_db_requests,
+            max_state_snapshots,
         );
         executor.spawn_critical("eth state cache", Box::pin(service));
         this
     }
 
+    /// Returns the [`StateSnapshot`] shared by all calls against the given block.
+    ///
+    /// Returns `None` if state snapshots are disabled.
+    pub fn state_snapshot(&self, block_hash: B256) -> Option<Arc<StateSnapshot>> {
+        self.state_snapshots.get_or_create(block_hash)
+    }
+
     /// Requests the  [`RecoveredBlock`] for the block hash
     ///
     /// Returns `None` if the block does not exist.
@@ -0,0 +1,291 @@
+//! Snapshots of the state at a block that are shared by all calls against that block.
+//!
+//! Calls such as `eth_call` and `eth_estimateGas` are frequently made against the same block, e.g.
+//! `latest`, in quick succession. Every call opens a new state provider and reads the same
+//! accounts, storage slots and bytecodes from disk again. A [`StateSnapshot`] remembers everything
+//! that was read at a block so subsequent calls against the same block hash are served from memory.
+//!
+//! The state at a block hash never changes, so snapshots are never invalidated, only evicted.
+
+use alloy_primitives::{map::HashMap, Address, BlockNumber, StorageKey, StorageValue, B256};
+use parking_lot::{Mutex, RwLock};
+use reth_errors::ProviderResult;
+use reth_primitives_traits::{Account, Bytecode};
+use reth_storage_api::{
+    AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider, StateProofProvider,
+    StateProvider, StateRootProvider, StorageRootProvider,
+};
+use reth_trie::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+};
+use revm::database::BundleState;
+use schnellru::{ByLength, LruMap};
+use std::{
+    hash::Hash,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// The maximum number of entries a single [`StateSnapshot`] holds.
+///
+/// Once reached, further reads are served by the underlying state provider without being cached.
+pub const MAX_STATE_SNAPSHOT_ENTRIES: usize = 100_000;
+
+/// The state that was read at a block, shared by all calls against that block.
+#[derive(Debug, Default)]
+pub struct StateSnapshot {
+    accounts: RwLock<HashMap<Address, Option<Account>>>,
+    storage: RwLock<HashMap<(Address, StorageKey), Option<StorageValue>>>,
+    bytecodes: RwLock<HashMap<B256, Option<Bytecode>>>,
+    block_hashes: RwLock<HashMap<BlockNumber, Option<B256>>>,
+    /// Total number of entries in all maps.
+    len: AtomicUsize,
+}
+
+impl StateSnapshot {
+    /// Returns the total number of cached entries.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if nothing was cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the cached value for the key, or loads it and caches it if there's room left.
+    fn get_or_load<K, V>(
+        &self,
+        map: &RwLock<HashMap<K, V>>,
+        key: K,
+        load: impl FnOnce() -> ProviderResult<V>,
+    ) -> ProviderResult<V>
+    where
+        K: Eq + Hash,
+        V: Clone,
+    {
+        if let Some(value) = map.read().get(&key) {
+            return Ok(value.clone())
+        }
+
+        let value = load()?;
+        if self.len() < MAX_STATE_SNAPSHOT_ENTRIES &&
+            map.write().insert(key, value.clone()).is_none()
+        {
+            self.len.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(value)
+    }
+}
+
+/// An LRU cache of [`StateSnapshot`]s keyed by block hash.
+#[derive(Debug)]
+pub struct StateSnapshotCache {
+    snapshots: Mutex<LruMap<B256, Arc<StateSnapshot>, ByLength>>,
+    max_len: u32,
+}
+
+impl StateSnapshotCache {
+    /// Creates a new cache that holds at most `max_len` snapshots.
+    ///
+    /// A `max_len` of zero disables snapshots.
+    pub fn new(max_len: u32) -> Self {
+        Self { snapshots: Mutex::new(LruMap::new(ByLength::new(max_len))), max_len }
+    }
+
+    /// Returns the snapshot of the given block, creating an empty one if it doesn't exist yet.
+    ///
+    /// Returns `None` if snapshots are disabled.
+    pub fn get_or_create(&self, block_hash: B256) -> Option<Arc<StateSnapshot>> {
+        if self.max_len == 0 {
+            return None
+        }
+        self.snapshots.lock().get_or_insert(block_hash, Default::default).cloned()
+    }
+}
+
+/// A [`StateProvider`] that serves reads from a [`StateSnapshot`] if present, and records all reads
+/// of the underlying provider into it.
+#[derive(Debug)]
+pub struct SnapshotStateProvider<S> {
+    inner: S,
+    snapshot: Option<Arc<StateSnapshot>>,
+}
+
+impl<S> SnapshotStateProvider<S> {
+    /// Wraps the given provider.
+    ///
+    /// The snapshot must belong to the block the provider was opened at.
+    pub const fn new(inner: S, snapshot: Option<Arc<StateSnapshot>>) -> Self {
+        Self { inner, snapshot }
+    }
+}
+
+impl<S: StateProvider> AccountReader for SnapshotStateProvider<S> {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        match &self.snapshot {
+            Some(snapshot) => snapshot
+                .get_or_load(&snapshot.accounts, *address, || self.inner.basic_account(address)),
+            None => self.inner.basic_account(address),
+        }
+    }
+}
+
+impl<S: StateProvider> BytecodeReader for SnapshotStateProvider<S> {
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        match &self.snapshot {
+            Some(snapshot) => snapshot.get_or_load(&snapshot.bytecodes, *code_hash, || {
+                self.inner.bytecode_by_hash(code_hash)
+            }),
+            None => self.inner.bytecode_by_hash(code_hash),
+        }
+    }
+}
+
+impl<S: StateProvider> BlockHashReader for SnapshotStateProvider<S> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        match &self.snapshot {
+            Some(snapshot) => snapshot
+                .get_or_load(&snapshot.block_hashes, number, || self.inner.block_hash(number)),
+            None => self.inner.block_hash(number),
+        }
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.inner.canonical_hashes_range(start, end)
+    }
+}
+
+impl<S: StateProvider> StateRootProvider for SnapshotStateProvider<S> {
+    fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
+        self.inner.state_root(hashed_state)
+    }
+
+    fn state_root_from_nodes(&self, input: TrieInput) -> ProviderResult<B256> {
+        self.inner.state_root_from_nodes(input)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        hashed_state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_with_updates(hashed_state)
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.inner.state_root_from_nodes_with_updates(input)
+    }
+}
+
+impl<S: StateProvider> StorageRootProvider for SnapshotStateProvider<S> {
+    fn storage_root(
+        &self,
+        address: Address,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        self.inner.storage_root(address, hashed_storage)
+    }
+
+    fn storage_proof(
+        &self,
+        address: Address,
+        slot: B256,
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageProof> {
+        self.inner.storage_proof(address, slot, hashed_storage)
+    }
+
+    fn storage_multiproof(
+        &self,
+        address: Address,
+        slots: &[B256],
+        hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        self.inner.storage_multiproof(address, slots, hashed_storage)
+    }
+}
+
+impl<S: StateProvider> StateProofProvider for SnapshotStateProvider<S> {
+    fn proof(
+        &self,
+        input: TrieInput,
+        address: Address,
+        slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        self.inner.proof(input, address, slots)
+    }
+
+    fn multiproof(
+        &self,
+        input: TrieInput,
+        targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        self.inner.multiproof(input, targets)
+    }
+
+    fn witness(
+        &self,
+        input: TrieInput,
+        target: HashedPostState,
+    ) -> ProviderResult<Vec<alloy_primitives::Bytes>> {
+        self.inner.witness(input, target)
+    }
+}
+
+impl<S: StateProvider> HashedPostStateProvider for SnapshotStateProvider<S> {
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.inner.hashed_post_state(bundle_state)
+    }
+}
+
+impl<S: StateProvider> StateProvider for SnapshotStateProvider<S> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        match &self.snapshot {
+            Some(snapshot) => {
+                snapshot.get_or_load(&snapshot.storage, (account, storage_key), || {
+                    self.inner.storage(account, storage_key)
+                })
+            }
+            None => self.inner.storage(account, storage_key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_storage_api::noop::NoopProvider;
+
+    #[test]
+    fn snapshot_records_reads() {
+        let cache = StateSnapshotCache::new(1);
+        let block_hash = B256::with_last_byte(1);
+        let snapshot = cache.get_or_create(block_hash).unwrap();
+
+        let provider = SnapshotStateProvider::new(NoopProvider::default(), Some(snapshot.clone()));
+        assert_eq!(provider.basic_account(&Address::ZERO).unwrap(), None);
+        assert_eq!(provider.storage(Address::ZERO, B256::ZERO).unwrap(), None);
+        assert_eq!(snapshot.len(), 2);
+
+        // the same block shares the snapshot, another block evicts it
+        assert!(Arc::ptr_eq(&snapshot, &cache.get_or_create(block_hash).unwrap()));
+        assert!(cache.get_or_create(B256::with_last_byte(2)).unwrap().is_empty());
+        assert!(cache.get_or_create(block_hash).unwrap().is_empty());
+
+        assert!(StateSnapshotCache::new(0).get_or_create(block_hash).is_none());
+    }
+}
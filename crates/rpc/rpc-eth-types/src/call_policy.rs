@@ -0,0 +1,92 @@
+//! Per request limits of `eth_call` and `eth_estimateGas`.
+//!
+//! Operators serving internal and public traffic from the same node can apply different limits to
+//! different consumers. A [`CallPolicyProvider`] resolves the [`CallPolicy`] of a request from the
+//! request's [`Extensions`], which are populated by the HTTP middleware of the server, e.g. with
+//! the API key of the request.
+
+use crate::EthApiError;
+use alloy_rpc_types_eth::state::StateOverride;
+use http::Extensions;
+use std::{fmt, time::Duration};
+
+/// The limits applied to a single `eth_call` or `eth_estimateGas` request.
+///
+/// Unset limits fall back to the node's configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CallPolicy {
+    /// The gas cap of the call, replaces the configured `--rpc.gascap`.
+    pub gas_cap: Option<u64>,
+    /// The maximum time the call may take.
+    pub timeout: Option<Duration>,
+    /// The maximum number of accounts in the state override of the call.
+    pub max_state_override_accounts: Option<usize>,
+}
+
+impl CallPolicy {
+    /// Sets the gas cap.
+    pub const fn with_gas_cap(mut self, gas_cap: u64) -> Self {
+        self.gas_cap = Some(gas_cap);
+        self
+    }
+
+    /// Sets the timeout.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum number of accounts in the state override.
+    pub const fn with_max_state_override_accounts(mut self, max: usize) -> Self {
+        self.max_state_override_accounts = Some(max);
+        self
+    }
+
+    /// Returns the gas cap of the policy, or the given default if the policy doesn't set one.
+    pub fn gas_cap_or(&self, default: u64) -> u64 {
+        self.gas_cap.unwrap_or(default)
+    }
+
+    /// Returns an error if the state override exceeds the allowed number of accounts.
+    pub fn ensure_state_override(
+        &self,
+        state_override: Option<&StateOverride>,
+    ) -> Result<(), EthApiError> {
+        let Some(max) = self.max_state_override_accounts else { return Ok(()) };
+        let accounts = state_override.map_or(0, |state_override| state_override.len());
+        if accounts > max {
+            return Err(EthApiError::InvalidParams(format!(
+                "state override too large: {accounts} accounts, at most {max} are allowed"
+            )))
+        }
+        Ok(())
+    }
+}
+
+/// Resolves the [`CallPolicy`] of a request.
+pub trait CallPolicyProvider: fmt::Debug + Send + Sync {
+    /// Returns the policy for the request with the given extensions.
+    fn call_policy(&self, extensions: &Extensions) -> CallPolicy;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+
+    #[test]
+    fn state_override_limit() {
+        let state_override = StateOverride::from_iter([
+            (Address::with_last_byte(1), Default::default()),
+            (Address::with_last_byte(2), Default::default()),
+        ]);
+
+        assert!(CallPolicy::default().ensure_state_override(Some(&state_override)).is_ok());
+
+        let policy = CallPolicy::default().with_max_state_override_accounts(1);
+        assert!(policy.ensure_state_override(None).is_ok());
+        assert!(policy.ensure_state_override(Some(&state_override)).is_err());
+        assert_eq!(policy.gas_cap_or(100), 100);
+        assert_eq!(policy.with_gas_cap(50).gas_cap_or(100), 50);
+    }
+}
@@ -380,9 +380,9 @@ impl From<reth_errors::ProviderError> for EthApiError {
         use reth_errors::ProviderError;
         match error {
             ProviderError::HeaderNotFound(hash) => Self::HeaderNotFound(hash.into()),
-            ProviderError::BlockHashNotFound(hash) | ProviderError::UnknownBlockHash(hash) => {
-                Self::HeaderNotFound(hash.into())
-            }
+            ProviderError::BlockHashNotFound(hash) |
+            ProviderError::UnknownBlockHash(hash) |
+            ProviderError::StateForHashNotFound(hash) => Self::HeaderNotFound(hash.into()),
             ProviderError::BestBlockNotFound => Self::HeaderNotFound(BlockId::latest()),
             ProviderError::BlockNumberForTransactionIndexNotFound => Self::UnknownBlockOrTxIndex,
             ProviderError::TotalDifficultyNotFound(num) => Self::HeaderNotFound(num.into()),
@@ -59,19 +59,30 @@ where
         self.config().resolution
     }
 
-    /// Returns all blocks that are missing in the cache in the [`lower_bound`, `upper_bound`]
-    /// range.
+    /// Returns all blocks that are missing in the rolling window of `max_blocks` blocks that ends
+    /// at the `upper_bound`, newest first.
     ///
     /// This function is used to populate the cache with missing blocks, which can happen if the
-    /// node switched to stage sync node.
+    /// node switched to stage sync node, or to backfill the window after startup.
     async fn missing_consecutive_blocks(&self) -> VecDeque<u64> {
         let entries = self.inner.entries.read().await;
-        (self.lower_bound()..self.upper_bound())
+        let upper_bound = self.upper_bound();
+        let window_start =
+            upper_bound.saturating_sub(self.inner.config.max_blocks.saturating_sub(1));
+        (window_start..upper_bound)
             .rev()
             .filter(|&block_number| !entries.contains_key(&block_number))
             .collect()
     }
 
+    /// Removes all entries of blocks above the given block number, e.g. the blocks of a chain that
+    /// was reorged out.
+    async fn remove_blocks_above(&self, block_number: u64) {
+        let mut entries = self.inner.entries.write().await;
+        entries.retain(|&number, _| number <= block_number);
+        self.update_bounds(&entries);
+    }
+
     /// Insert block data into the cache.
     async fn insert_blocks<'a, I, B, R, C>(&self, blocks: I, chain_spec: &C)
     where
@@ -85,10 +96,7 @@ where
         let percentiles = self.predefined_percentiles();
         // Insert all new blocks and calculate approximated rewards
         for (block, receipts) in blocks {
-            let mut fee_history_entry = FeeHistoryEntry::<H>::new(
-                block,
-                chain_spec.blob_params_at_timestamp(block.header().timestamp()),
-            );
+            let mut fee_history_entry = FeeHistoryEntry::<H>::new(block, chain_spec);
             fee_history_entry.rewards = calculate_reward_percentiles_for_block(
                 &percentiles,
                 fee_history_entry.header.gas_used(),
@@ -105,21 +113,21 @@ where
             entries.pop_first();
         }
 
-        if entries.is_empty() {
-            self.inner.upper_bound.store(0, SeqCst);
-            self.inner.lower_bound.store(0, SeqCst);
-            return
+        if let Some((&upper_bound, _)) = entries.last_key_value() {
+            // also enforce proper lower bound in case we have gaps
+            let target_lower = upper_bound.saturating_sub(self.inner.config.max_blocks);
+            while entries.len() > 1 && *entries.first_key_value().unwrap().0 < target_lower {
+                entries.pop_first();
+            }
         }
 
-        let upper_bound = *entries.last_entry().expect("Contains at least one entry").key();
-
-        // also enforce proper lower bound in case we have gaps
-        let target_lower = upper_bound.saturating_sub(self.inner.config.max_blocks);
-        while entries.len() > 1 && *entries.first_key_value().unwrap().0 < target_lower {
-            entries.pop_first();
-        }
+        self.update_bounds(&entries);
+    }
 
-        let lower_bound = *entries.first_entry().expect("Contains at least one entry").key();
+    /// Updates the bounds to the oldest and newest entries.
+    fn update_bounds(&self, entries: &BTreeMap<u64, FeeHistoryEntry<H>>) {
+        let lower_bound = entries.first_key_value().map(|(&number, _)| number).unwrap_or_default();
+        let upper_bound = entries.last_key_value().map(|(&number, _)| number).unwrap_or_default();
         self.inner.upper_bound.store(upper_bound, SeqCst);
         self.inner.lower_bound.store(lower_bound, SeqCst);
     }
@@ -137,8 +145,8 @@ where
     /// Collect fee history for the given range (inclusive `start_block..=end_block`).
     ///
     /// This function retrieves fee history entries from the cache for the specified range.
-    /// If the requested range (`start_block` to `end_block`) is within the cache bounds,
-    /// it returns the corresponding entries.
+    /// If the requested range (`start_block` to `end_block`) is within the cache bounds and the
+    /// cache holds an entry for every block of the range, it returns the corresponding entries.
     /// Otherwise it returns None.
     pub async fn get_history(
         &self,
@@ -158,7 +166,8 @@ where
                 .map(|(_, fee_entry)| fee_entry.clone())
                 .collect::<Vec<_>>();
 
-            if result.is_empty() {
+            // the range can't be served from memory if the cache has gaps
+            if result.len() as u64 != end_block - start_block + 1 {
                 return None
             }
 
@@ -214,6 +223,10 @@ struct FeeHistoryCacheInner<H> {
 
 /// Awaits for new chain events and directly inserts them into the cache so they're available
 /// immediately before they need to be fetched from disk.
+///
+/// On startup, and whenever the node skipped blocks, the rolling window of the cache is
+/// backfilled in the background, newest block first, so that `eth_feeHistory` requests for recent
+/// ranges are served from memory.
 pub async fn fee_history_cache_new_blocks_task<St, Provider, N>(
     fee_history_cache: FeeHistoryCache<N::BlockHeader>,
     mut events: St,
@@ -227,8 +240,9 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider, N>(
     N::BlockHeader: BlockHeader + Clone,
 {
     // We're listening for new blocks emitted when the node is in live sync.
-    // If the node transitions to stage sync, we need to fetch the missing blocks
-    let mut missing_blocks = VecDeque::new();
+    // If the node transitions to stage sync, we need to fetch the missing blocks.
+    // The current tip is fetched first, the rest of the window is backfilled once it's inserted.
+    let mut missing_blocks = provider.best_block_number().into_iter().collect::<VecDeque<_>>();
     let mut fetch_missing_block = Fuse::terminated();
 
     loop {
@@ -251,6 +265,10 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider, N>(
                         .map(|(b, r)| (b.sealed_block(), r.as_slice()));
                     fee_history_cache.insert_blocks(res, &chain_spec).await;
                 }
+
+                if missing_blocks.is_empty() {
+                    missing_blocks = fee_history_cache.missing_consecutive_blocks().await;
+                }
             }
             event = events.next() =>  {
                 let Some(event) = event else {
@@ -258,6 +276,11 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider, N>(
                     break
                 };
 
+                if event.reverted().is_some() {
+                    // drop the entries of the reorged out blocks above the new tip
+                    fee_history_cache.remove_blocks_above(event.tip().number()).await;
+                }
+
                 let committed = event.committed();
                 let blocks_and_receipts = committed
                     .blocks_and_receipts()
@@ -362,20 +385,22 @@ impl<H> FeeHistoryEntry<H>
 where
     H: BlockHeader + Clone,
 {
-    /// Creates a new entry from a sealed block.
+    /// Creates a new entry from a sealed block, with the blob base fee of the chain spec.
     ///
     /// Note: This does not calculate the rewards for the block.
-    pub fn new<B>(block: &SealedBlock<B>, blob_params: Option<BlobParams>) -> Self
+    pub fn new<B, C>(block: &SealedBlock<B>, chain_spec: &C) -> Self
     where
         B: Block<Header = H>,
+        C: EthChainSpec,
     {
         let header = block.header();
+        let blob_params = chain_spec.blob_params_at_timestamp(header.timestamp());
         Self {
             header: block.header().clone(),
             gas_used_ratio: header.gas_used() as f64 / header.gas_limit() as f64,
-            base_fee_per_blob_gas: header
-                .excess_blob_gas()
-                .and_then(|excess_blob_gas| Some(blob_params?.calc_blob_fee(excess_blob_gas))),
+            base_fee_per_blob_gas: header.excess_blob_gas().and_then(|excess_blob_gas| {
+                Some(chain_spec.blob_base_fee(blob_params?, excess_blob_gas))
+            }),
             blob_gas_used_ratio: block.body().blob_gas_used() as f64 /
                 blob_params
                     .as_ref()
@@ -387,25 +412,61 @@ where
         }
     }
 
-    /// Returns the blob fee for the next block according to the EIP-4844 spec.
+    /// Returns the blob fee for the next block, see [`EthChainSpec::blob_base_fee`].
     ///
     /// Returns `None` if `excess_blob_gas` is None.
     ///
     /// See also [`Self::next_block_excess_blob_gas`]
-    pub fn next_block_blob_fee(&self) -> Option<u128> {
-        self.next_block_excess_blob_gas()
-            .and_then(|excess_blob_gas| Some(self.blob_params?.calc_blob_fee(excess_blob_gas)))
+    pub fn next_block_blob_fee<C: EthChainSpec>(&self, chain_spec: &C) -> Option<u128> {
+        self.next_block_excess_blob_gas(chain_spec).and_then(|excess_blob_gas| {
+            Some(chain_spec.blob_base_fee(self.blob_params?, excess_blob_gas))
+        })
     }
 
-    /// Calculate excess blob gas for the next block according to the EIP-4844 spec.
+    /// Calculate excess blob gas for the next block, see
+    /// [`EthChainSpec::next_block_excess_blob_gas`].
     ///
     /// Returns a `None` if no excess blob gas is set, no EIP-4844 support
-    pub fn next_block_excess_blob_gas(&self) -> Option<u64> {
+    pub fn next_block_excess_blob_gas<C: EthChainSpec>(&self, chain_spec: &C) -> Option<u64> {
         self.header.excess_blob_gas().and_then(|excess_blob_gas| {
-            Some(
-                self.blob_params?
-                    .next_block_excess_blob_gas(excess_blob_gas, self.header.blob_gas_used()?),
-            )
+            Some(chain_spec.next_block_excess_blob_gas(
+                self.blob_params?,
+                excess_blob_gas,
+                self.header.blob_gas_used()?,
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_chainspec::MAINNET;
+    use reth_ethereum_primitives::{Block, Receipt};
+
+    fn sealed_block(number: u64) -> SealedBlock<Block> {
+        SealedBlock::seal_slow(Block {
+            header: Header { number, gas_limit: 30_000_000, ..Default::default() },
+            body: Default::default(),
         })
     }
+
+    #[tokio::test]
+    async fn serves_only_complete_ranges() {
+        let cache =
+            FeeHistoryCache::<Header>::new(FeeHistoryCacheConfig { max_blocks: 10, resolution: 1 });
+        let blocks = [1, 2, 4, 5].map(sealed_block);
+        cache
+            .insert_blocks(blocks.iter().map(|block| (block, &[] as &[Receipt])), &**MAINNET)
+            .await;
+
+        assert_eq!(cache.get_history(4, 5).await.map(|entries| entries.len()), Some(2));
+        assert!(cache.get_history(2, 4).await.is_none());
+        assert_eq!(cache.missing_consecutive_blocks().await, VecDeque::from([3, 0]));
+
+        // a reorg to block 4 drops the entries above it
+        cache.remove_blocks_above(4).await;
+        assert_eq!(cache.upper_bound(), 4);
+        assert!(cache.get_history(5, 5).await.is_none());
+    }
 }
@@ -8,24 +8,33 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+pub mod access_list;
 pub mod builder;
+pub mod bundle;
 pub mod cache;
+pub mod call_policy;
 pub mod error;
 pub mod fee_history;
 pub mod gas_oracle;
 pub mod id_provider;
 pub mod logs_utils;
 pub mod pending_block;
+pub mod proof;
+pub mod proof_cache;
 pub mod receipt;
+pub mod sidecar;
 pub mod simulate;
 pub mod transaction;
 pub mod utils;
 
-pub use builder::config::{EthConfig, EthFilterConfig};
+pub use builder::config::{
+    EthConfig, EthFilterConfig, EthPubSubConfig, SubscriptionOverflowPolicy,
+};
 pub use cache::{
     config::EthStateCacheConfig, db::StateCacheDb, multi_consumer::MultiConsumerLruCache,
     EthStateCache,
 };
+pub use call_policy::{CallPolicy, CallPolicyProvider};
 pub use error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError, SignError};
 pub use fee_history::{FeeHistoryCache, FeeHistoryCacheConfig, FeeHistoryEntry};
 pub use gas_oracle::{
@@ -33,5 +42,7 @@ pub use gas_oracle::{
 };
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin};
+pub use proof_cache::EthProofCache;
 pub use receipt::EthReceiptBuilder;
+pub use sidecar::{BlockWithSidecars, TransactionSidecar};
 pub use transaction::TransactionSource;
@@ -8,9 +8,12 @@ use alloy_consensus::BlockHeader;
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_primitives::B256;
 use derive_more::Constructor;
+use reth_chain_state::{ExecutedBlockWithTrieUpdates, ExecutedTrieUpdates};
 use reth_ethereum_primitives::Receipt;
 use reth_evm::EvmEnv;
-use reth_primitives_traits::{Block, RecoveredBlock, SealedHeader};
+use reth_execution_types::ExecutionOutcome;
+use reth_primitives_traits::{Block, NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_trie::HashedPostState;
 
 /// Configured [`EvmEnv`] for a pending block.
 #[derive(Debug, Clone, Constructor)]
@@ -74,7 +77,7 @@ impl<B: Block, R> PendingBlockEnvOrigin<B, R> {
 }
 
 /// Locally built pending block for `pending` tag.
-#[derive(Debug, Constructor)]
+#[derive(Debug, Clone, Constructor)]
 pub struct PendingBlock<B: Block, R> {
     /// Timestamp when the pending block is considered outdated.
     pub expires_at: Instant,
@@ -82,4 +85,46 @@ pub struct PendingBlock<B: Block, R> {
     pub block: Arc<RecoveredBlock<B>>,
     /// The receipts for the pending block
     pub receipts: Arc<Vec<R>>,
+    /// The state changes of the pending block.
+    pub execution_outcome: Arc<ExecutionOutcome<R>>,
+    /// The hashed state changes of the pending block.
+    pub hashed_state: Arc<HashedPostState>,
+    /// The trie updates of the pending block.
+    pub trie: ExecutedTrieUpdates,
+}
+
+impl<B: Block, R: Clone> PendingBlock<B, R> {
+    /// Creates a new pending block from the executed block, which is outdated at `expires_at`.
+    pub fn from_executed_block<N>(
+        expires_at: Instant,
+        executed_block: ExecutedBlockWithTrieUpdates<N>,
+    ) -> Self
+    where
+        N: NodePrimitives<Block = B, Receipt = R>,
+    {
+        let ExecutedBlockWithTrieUpdates { block, trie } = executed_block;
+        let receipts = block.execution_output.receipts.first().cloned().unwrap_or_default();
+        Self {
+            expires_at,
+            block: block.recovered_block,
+            receipts: Arc::new(receipts),
+            execution_outcome: block.execution_output,
+            hashed_state: block.hashed_state,
+            trie,
+        }
+    }
+
+    /// Returns the pending block as an executed block, so that its state can be overlaid on top of
+    /// the state of its parent.
+    pub fn to_executed_block<N>(&self) -> ExecutedBlockWithTrieUpdates<N>
+    where
+        N: NodePrimitives<Block = B, Receipt = R>,
+    {
+        ExecutedBlockWithTrieUpdates::new(
+            self.block.clone(),
+            self.execution_outcome.clone(),
+            self.hashed_state.clone(),
+            self.trie.clone(),
+        )
+    }
 }
@@ -0,0 +1,42 @@
+//! Types of the reth specific extensions of `eth_getProof`.
+
+use alloy_primitives::B256;
+use alloy_rpc_types_eth::EIP1186AccountProofResponse;
+use serde::{Deserialize, Serialize};
+
+/// The result of `eth_getProof`, extended by the block the proof was generated at.
+///
+/// Tags such as `safe` and `finalized` are resolved to a block hash before the proof is
+/// generated, so the proof can be verified against the state root of exactly this block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProofResponse {
+    /// The account and storage proofs.
+    #[serde(flatten)]
+    pub proof: EIP1186AccountProofResponse,
+    /// The hash of the block the proof was generated at, unless it was the pending block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_hash: Option<B256>,
+    /// The number of the block the proof was generated at.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_block_fields_next_to_proof() {
+        let response = AccountProofResponse {
+            proof: Default::default(),
+            block_hash: Some(B256::with_last_byte(1)),
+            block_number: 16,
+        };
+        let value = serde_json::to_value(&response).unwrap();
+        assert_eq!(value["blockNumber"], "0x10");
+        assert_eq!(value["blockHash"], serde_json::to_value(B256::with_last_byte(1)).unwrap());
+        assert!(value.get("accountProof").is_some());
+        assert_eq!(serde_json::from_value::<AccountProofResponse>(value).unwrap(), response);
+    }
+}
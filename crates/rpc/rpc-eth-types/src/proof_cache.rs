@@ -0,0 +1,236 @@
+//! Reuse of revealed sparse tries across `eth_getProof` calls for the same targets.
+//!
+//! Clients such as bridges request proofs of the same account and storage slots at every block.
+//! Instead of walking the trie from the root for each of these requests, the [`EthProofCache`]
+//! keeps the sparse tries revealed by the first proof and updates them with the state changes of
+//! every new canonical block.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, B256};
+use futures::{Stream, StreamExt};
+use metrics::Counter;
+use parking_lot::Mutex;
+use reth_chain_state::CanonStateNotification;
+use reth_metrics::Metrics;
+use reth_primitives_traits::NodePrimitives;
+use reth_trie::{AccountProof, KeccakKeyHasher, MultiProof};
+use reth_trie_sparse::{blinded::DefaultBlindedProviderFactory, SparseStateTrie};
+use schnellru::{ByLength, LruMap};
+use std::{fmt, sync::Arc};
+use tracing::trace;
+
+/// The targets of a proof: the account and its sorted, deduplicated storage slots.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ProofTargets {
+    address: Address,
+    slots: Vec<B256>,
+}
+
+impl ProofTargets {
+    fn new(address: Address, slots: &[B256]) -> Self {
+        let mut slots = slots.to_vec();
+        slots.sort_unstable();
+        slots.dedup();
+        Self { address, slots }
+    }
+}
+
+/// The tries revealed for the targets of a proof.
+#[derive(Debug)]
+struct CachedTries {
+    /// The block the tries belong to.
+    block_hash: B256,
+    /// The revealed account trie and storage trie.
+    trie: SparseStateTrie,
+}
+
+/// An in-memory LRU cache of the sparse tries revealed for `eth_getProof` calls, per account and
+/// set of storage slots.
+///
+/// The tries of a block are updated to its canonical child block as soon as that block is
+/// committed, using the trie updates of the block to replace the hashes of changed nodes that were
+/// not revealed. Tries that can't be updated this way are dropped and revealed again from a new
+/// proof on the next request.
+///
+/// Clones share the same cache.
+#[derive(Clone)]
+pub struct EthProofCache {
+    inner: Arc<Mutex<LruMap<ProofTargets, CachedTries, ByLength>>>,
+    metrics: ProofCacheMetrics,
+}
+
+impl EthProofCache {
+    /// Creates a new cache that holds the tries of at most `max_entries` proof targets.
+    pub fn new(max_entries: u32) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LruMap::new(ByLength::new(max_entries)))),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Returns the number of cached proof targets.
+    pub fn len(&self) -> usize {
+        self.inner.lock().len()
+    }
+
+    /// Returns `true` if no tries are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the proof of the account and the storage slots at the given block, if the tries of
+    /// these targets are cached at the block.
+    pub fn get(&self, block_hash: B256, address: Address, slots: &[B256]) -> Option<AccountProof> {
+        let proof = self
+            .inner
+            .lock()
+            .get(&ProofTargets::new(address, slots))
+            .filter(|entry| entry.block_hash == block_hash)
+            .and_then(|entry| entry.trie.account_proof(address, slots).ok());
+
+        if proof.is_some() {
+            self.metrics.hits.increment(1);
+        } else {
+            self.metrics.misses.increment(1);
+        }
+        proof
+    }
+
+    /// Caches the tries revealed by the multiproof of the account and the storage slots at the
+    /// given block.
+    pub fn insert(&self, block_hash: B256, address: Address, slots: &[B256], proof: MultiProof) {
+        let mut trie = SparseStateTrie::default();
+        if let Err(err) = trie.reveal_multiproof(proof) {
+            trace!(target: "rpc::eth::proof_cache", %address, %err, "Failed to reveal proof");
+            return
+        }
+        self.inner
+            .lock()
+            .insert(ProofTargets::new(address, slots), CachedTries { block_hash, trie });
+    }
+
+    /// Applies the state changes of newly committed canonical blocks to the tries cached at the
+    /// parent of the first block.
+    ///
+    /// Only notifications that include the trie updates of the blocks can be applied. The tries of
+    /// other blocks are left as they are, since the state of a block never changes.
+    pub fn on_canonical_state<N: NodePrimitives>(&self, notification: &CanonStateNotification<N>) {
+        let CanonStateNotification::Commit { new } = notification else { return };
+        let Some(trie_updates) = new.trie_updates() else { return };
+
+        let parent_hash = new.first().header().parent_hash();
+        if !self.inner.lock().iter().any(|(_, entry)| entry.block_hash == parent_hash) {
+            return
+        }
+
+        let tip = new.tip();
+        let state = new.execution_outcome().hash_state_slow::<KeccakKeyHasher>();
+
+        let mut entries = self.inner.lock();
+        let mut failed = Vec::new();
+        for (targets, entry) in entries.iter_mut() {
+            if entry.block_hash != parent_hash {
+                continue
+            }
+
+            let root = entry
+                .trie
+                .apply_block_changes(&state, trie_updates)
+                .and_then(|()| entry.trie.root(&DefaultBlindedProviderFactory));
+            match root {
+                Ok(root) if root == tip.header().state_root() => {
+                    entry.block_hash = tip.hash();
+                    self.metrics.incremental_updates.increment(1);
+                }
+                root => {
+                    trace!(target: "rpc::eth::proof_cache", address = %targets.address, ?root, "Failed to update revealed tries");
+                    failed.push(targets.clone());
+                }
+            }
+        }
+
+        for targets in failed {
+            entries.remove(&targets);
+            self.metrics.failed_updates.increment(1);
+        }
+    }
+}
+
+impl fmt::Debug for EthProofCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EthProofCache").field("entries", &self.len()).finish_non_exhaustive()
+    }
+}
+
+/// Awaits canonical state notifications and applies the committed blocks to the cached tries.
+pub async fn proof_cache_new_blocks_task<St, N>(cache: EthProofCache, mut events: St)
+where
+    St: Stream<Item = CanonStateNotification<N>> + Unpin + 'static,
+    N: NodePrimitives,
+{
+    while let Some(event) = events.next().await {
+        cache.on_canonical_state(&event);
+    }
+}
+
+/// Metrics of the [`EthProofCache`].
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc.eth_proof_cache")]
+struct ProofCacheMetrics {
+    /// Number of proofs served from cached tries.
+    hits: Counter,
+    /// Number of proofs that had to be computed from the database.
+    misses: Counter,
+    /// Number of cached tries updated with the changes of a new block.
+    incremental_updates: Counter,
+    /// Number of cached tries dropped because they couldn't be updated to a new block.
+    failed_updates: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, U256};
+    use reth_primitives_traits::Account;
+    use reth_trie::{HashBuilder, Nibbles, EMPTY_ROOT_HASH};
+    use reth_trie_common::proof::ProofRetainer;
+
+    #[test]
+    fn cached_proof_matches_multiproof() {
+        let mut accounts = (1..=3u8)
+            .map(|i| (keccak256(Address::with_last_byte(i)), Address::with_last_byte(i)))
+            .collect::<Vec<_>>();
+        accounts.sort_unstable();
+        let (target_hashed, target) = accounts[0];
+
+        let mut hash_builder = HashBuilder::default()
+            .with_proof_retainer(ProofRetainer::from_iter([Nibbles::unpack(target_hashed)]));
+        for (hashed_address, _) in &accounts {
+            let account = Account { balance: U256::from(1), ..Default::default() };
+            hash_builder.add_leaf(
+                Nibbles::unpack(hashed_address),
+                &alloy_rlp::encode(account.into_trie_account(EMPTY_ROOT_HASH)),
+            );
+        }
+        hash_builder.root();
+        let proof =
+            MultiProof { account_subtree: hash_builder.take_proof_nodes(), ..Default::default() };
+
+        let cache = EthProofCache::new(10);
+        let block_hash = B256::with_last_byte(1);
+        let slots = [B256::with_last_byte(2), B256::with_last_byte(1)];
+        assert!(cache.get(block_hash, target, &slots).is_none());
+
+        cache.insert(block_hash, target, &slots, proof.clone());
+        assert_eq!(cache.len(), 1);
+        assert_eq!(
+            cache.get(block_hash, target, &slots),
+            Some(proof.account_proof(target, &slots).unwrap())
+        );
+        // the same targets in a different order
+        assert!(cache.get(block_hash, target, &[slots[1], slots[0], slots[1]]).is_some());
+
+        assert!(cache.get(B256::with_last_byte(2), target, &slots).is_none());
+        assert!(cache.get(block_hash, target, &slots[..1]).is_none());
+    }
+}
@@ -0,0 +1,28 @@
+//! Blocks served together with the blob sidecars of their transactions.
+
+use alloy_eips::eip7594::BlobTransactionSidecarVariant;
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+
+/// A block together with the blob sidecars of its transactions, see `reth_getBlockWithSidecars`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockWithSidecars<B> {
+    /// The block.
+    pub block: B,
+    /// The sidecars of all blob transactions of the block, in the order of the transactions.
+    pub sidecars: Vec<TransactionSidecar>,
+}
+
+/// The blob sidecar of a transaction, see [`BlockWithSidecars`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionSidecar {
+    /// The hash of the transaction.
+    pub transaction_hash: B256,
+    /// The index of the transaction in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub transaction_index: u64,
+    /// The sidecar of the transaction, `None` if the node doesn't hold it anymore.
+    pub sidecar: Option<BlobTransactionSidecarVariant>,
+}
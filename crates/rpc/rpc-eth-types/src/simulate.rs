@@ -10,7 +10,7 @@ use crate::{
 use alloy_consensus::{BlockHeader, Transaction as _, TxType};
 use alloy_eips::eip2718::WithEncoded;
 use alloy_rpc_types_eth::{
-    simulate::{SimCallResult, SimulateError, SimulatedBlock},
+    simulate::{SimBlock, SimCallResult, SimulateError, SimulatedBlock},
     transaction::TransactionRequest,
     Block, BlockTransactionsKind, Header,
 };
@@ -40,13 +40,35 @@ pub enum EthSimulateError {
     /// Max gas limit for entire operation exceeded.
     #[error("Client adjustable limit reached")]
     GasLimitReached,
+    /// The number of blocks to simulate, including the empty blocks that fill gaps between the
+    /// requested block numbers, exceeds the limit.
+    #[error("too many blocks, the limit is {0}")]
+    TooManyBlocks(u64),
+    /// The block number doesn't increase over the number of the previous block.
+    #[error("block numbers must be in order: {got} <= {parent}")]
+    BlockNumberInvalid {
+        /// The requested block number.
+        got: u64,
+        /// The number of the previous block.
+        parent: u64,
+    },
+    /// The block timestamp doesn't increase over the timestamp of the previous block.
+    #[error("block timestamps must be in order: {got} <= {parent}")]
+    BlockTimestampInvalid {
+        /// The requested block timestamp.
+        got: u64,
+        /// The timestamp of the previous block.
+        parent: u64,
+    },
 }
 
 impl EthSimulateError {
     const fn error_code(&self) -> i32 {
         match self {
             Self::BlockGasLimitExceeded => -38015,
-            Self::GasLimitReached => -38026,
+            Self::BlockNumberInvalid { .. } => -38020,
+            Self::BlockTimestampInvalid { .. } => -38021,
+            Self::GasLimitReached | Self::TooManyBlocks(_) => -38026,
         }
     }
 }
@@ -57,6 +79,38 @@ impl ToRpcError for EthSimulateError {
     }
 }
 
+/// Inserts an empty block for every block number that is skipped by the number overrides of the
+/// requested blocks, so that consecutive simulated blocks always have consecutive numbers.
+///
+/// Fails if a requested block number doesn't increase over the previous block or if the total
+/// number of blocks exceeds `max_blocks`.
+pub fn fill_block_gaps(
+    parent_number: u64,
+    blocks: Vec<SimBlock>,
+    max_blocks: u64,
+) -> Result<Vec<SimBlock>, EthSimulateError> {
+    let mut filled = Vec::with_capacity(blocks.len());
+    let mut number = parent_number;
+    for block in blocks {
+        let next = block
+            .block_overrides
+            .as_ref()
+            .and_then(|overrides| overrides.number)
+            .map_or(number + 1, |number| number.saturating_to());
+        if next <= number {
+            return Err(EthSimulateError::BlockNumberInvalid { got: next, parent: number })
+        }
+        if (filled.len() as u64).saturating_add(next - number) > max_blocks {
+            return Err(EthSimulateError::TooManyBlocks(max_blocks))
+        }
+
+        filled.extend((number + 1..next).map(|_| SimBlock::default()));
+        filled.push(block);
+        number = next;
+    }
+    Ok(filled)
+}
+
 /// Converts all [`TransactionRequest`]s into [`Recovered`] transactions and applies them to the
 /// given [`BlockExecutor`].
 ///
@@ -265,3 +319,33 @@ where
     let block = block.into_rpc_block(txs_kind, |tx, tx_info| tx_resp_builder.fill(tx, tx_info))?;
     Ok(SimulatedBlock { inner: block, calls })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use alloy_rpc_types_eth::BlockOverrides;
+
+    fn block_with_number(number: Option<u64>) -> SimBlock {
+        let block_overrides =
+            number.map(|n| BlockOverrides { number: Some(U256::from(n)), ..Default::default() });
+        SimBlock { block_overrides, ..Default::default() }
+    }
+
+    #[test]
+    fn fills_skipped_block_numbers() {
+        let blocks = vec![block_with_number(None), block_with_number(Some(14))];
+        let filled = fill_block_gaps(10, blocks, 256).unwrap();
+        assert_eq!(filled.len(), 4);
+        assert!(filled[1].block_overrides.is_none() && filled[2].block_overrides.is_none());
+
+        assert!(matches!(
+            fill_block_gaps(10, vec![block_with_number(Some(10))], 256),
+            Err(EthSimulateError::BlockNumberInvalid { got: 10, parent: 10 })
+        ));
+        assert!(matches!(
+            fill_block_gaps(10, vec![block_with_number(Some(300))], 256),
+            Err(EthSimulateError::TooManyBlocks(256))
+        ));
+    }
+}
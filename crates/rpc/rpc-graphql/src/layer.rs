@@ -0,0 +1,133 @@
+//! HTTP middleware that serves GraphQL requests next to the JSON-RPC server.
+
+use crate::GraphQlSchema;
+use http::{header, Method, StatusCode};
+use http_body_util::{BodyExt, Limited};
+use jsonrpsee_http_client::{HttpBody, HttpRequest, HttpResponse};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+use tracing::trace;
+
+/// The path on which GraphQL requests are served.
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+/// The maximum size of the body of a GraphQL request.
+const MAX_REQUEST_BODY_SIZE: usize = 1024 * 1024;
+
+/// An HTTP middleware layer that serves GraphQL requests on [`GRAPHQL_PATH`].
+///
+/// Queries can be sent as JSON body of a `POST` request, or as query string of a `GET` request.
+/// All other requests are dispatched to the next layer along the chain.
+#[derive(Clone)]
+pub struct GraphQlLayer {
+    schema: GraphQlSchema,
+}
+
+impl GraphQlLayer {
+    /// Creates a new layer that executes requests against the given schema.
+    pub const fn new(schema: GraphQlSchema) -> Self {
+        Self { schema }
+    }
+}
+
+impl std::fmt::Debug for GraphQlLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GraphQlLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for GraphQlLayer {
+    type Service = GraphQlService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GraphQlService { schema: self.schema.clone(), inner }
+    }
+}
+
+/// The HTTP service of the [`GraphQlLayer`].
+#[derive(Clone)]
+pub struct GraphQlService<S> {
+    /// The schema requests are executed against.
+    schema: GraphQlSchema,
+    /// The inner service being wrapped
+    inner: S,
+}
+
+impl<S> std::fmt::Debug for GraphQlService<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GraphQlService").finish_non_exhaustive()
+    }
+}
+
+impl<S> Service<HttpRequest> for GraphQlService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        if req.uri().path() != GRAPHQL_PATH {
+            return Box::pin(self.inner.call(req));
+        }
+
+        let schema = self.schema.clone();
+        Box::pin(async move { Ok(execute(schema, req).await) })
+    }
+}
+
+/// Executes the GraphQL request and returns the HTTP response.
+async fn execute(schema: GraphQlSchema, req: HttpRequest) -> HttpResponse {
+    let request = match *req.method() {
+        Method::GET => {
+            let query = req.uri().query().unwrap_or_default();
+            match async_graphql::http::parse_query_string(query) {
+                Ok(request) => request,
+                Err(err) => return response(StatusCode::BAD_REQUEST, err.to_string()),
+            }
+        }
+        Method::POST => {
+            let body = match Limited::new(req.into_body(), MAX_REQUEST_BODY_SIZE).collect().await {
+                Ok(body) => body.to_bytes(),
+                Err(err) => return response(StatusCode::BAD_REQUEST, err.to_string()),
+            };
+            match serde_json::from_slice::<async_graphql::Request>(&body) {
+                Ok(request) => request,
+                Err(err) => return response(StatusCode::BAD_REQUEST, err.to_string()),
+            }
+        }
+        _ => return response(StatusCode::METHOD_NOT_ALLOWED, "expected GET or POST".to_string()),
+    };
+    trace!(target: "rpc::graphql", query = %request.query, "Serving GraphQL request");
+
+    // Resolvers read from the database, hence the request is executed on a blocking thread.
+    let handle = tokio::runtime::Handle::current();
+    let result =
+        tokio::task::spawn_blocking(move || handle.block_on(schema.execute(request))).await;
+    let body = match result.map(|response| serde_json::to_string(&response)) {
+        Ok(Ok(body)) => body,
+        Ok(Err(err)) => return response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+        Err(err) => return response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    };
+
+    http::Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(HttpBody::new(body))
+        .expect("response is valid")
+}
+
+/// Returns a plain text response with the given status.
+fn response(status: StatusCode, body: String) -> HttpResponse {
+    http::Response::builder().status(status).body(HttpBody::new(body)).expect("response is valid")
+}
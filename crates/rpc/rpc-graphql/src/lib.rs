@@ -0,0 +1,100 @@
+//! GraphQL API implementing the [EIP-1767] schema.
+//!
+//! Blocks, transactions, logs and accounts are resolved against the provider. The API is served
+//! on the HTTP-RPC server by the [`GraphQlLayer`], with limits on the complexity and depth of
+//! queries.
+//!
+//! [EIP-1767]: https://eips.ethereum.org/EIPS/eip-1767
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use async_graphql::{EmptyMutation, EmptySubscription, Schema};
+use reth_rpc_server_types::constants;
+use std::sync::Arc;
+
+mod layer;
+pub use layer::{GraphQlLayer, GraphQlService, GRAPHQL_PATH};
+
+mod provider;
+pub use provider::{BlockData, BodyData, GraphQlProvider, ReceiptData, TransactionData};
+
+pub mod scalars;
+
+mod schema;
+pub use schema::{
+    AccessTuple, Account, Block, BlockFilterCriteria, FilterCriteria, Log, Query, Transaction,
+    Withdrawal,
+};
+
+/// The executable GraphQL schema.
+pub type GraphQlSchema = Schema<Query, EmptyMutation, EmptySubscription>;
+
+/// Limits of the GraphQL API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphQlConfig {
+    /// The maximum complexity of a query.
+    pub max_complexity: usize,
+    /// The maximum nesting depth of a query.
+    pub max_depth: usize,
+    /// The maximum number of blocks that can be scanned by a single field, `None` for no limit.
+    pub max_block_range: Option<u64>,
+}
+
+impl Default for GraphQlConfig {
+    fn default() -> Self {
+        Self {
+            max_complexity: constants::DEFAULT_GRAPHQL_MAX_COMPLEXITY,
+            max_depth: constants::DEFAULT_GRAPHQL_MAX_DEPTH,
+            max_block_range: Some(constants::DEFAULT_MAX_BLOCKS_PER_FILTER),
+        }
+    }
+}
+
+/// Builds the [`GraphQlSchema`] that resolves queries against the given provider.
+pub fn graphql_schema(provider: Arc<dyn GraphQlProvider>, config: GraphQlConfig) -> GraphQlSchema {
+    Schema::build(Query, EmptyMutation, EmptySubscription)
+        .data(schema::GraphQlContext::new(provider, config.max_block_range))
+        .limit_complexity(config.max_complexity)
+        .limit_depth(config.max_depth)
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::MockEthProvider;
+
+    fn schema(config: GraphQlConfig) -> GraphQlSchema {
+        graphql_schema(Arc::new(MockEthProvider::default()), config)
+    }
+
+    #[tokio::test]
+    async fn execute_query() {
+        let response = schema(GraphQlConfig::default())
+            .execute("{ chainID block(number: 1) { number } }")
+            .await;
+        assert!(response.errors.is_empty(), "{:?}", response.errors);
+        assert_eq!(
+            response.data.into_json().unwrap(),
+            serde_json::json!({ "chainID": "0x1", "block": null })
+        );
+    }
+
+    #[tokio::test]
+    async fn reject_queries_exceeding_limits() {
+        let config = GraphQlConfig { max_depth: 2, ..Default::default() };
+        let response = schema(config).execute("{ block { parent { parent { number } } } }").await;
+        assert!(!response.errors.is_empty());
+
+        // the complexity of fields resolved for a block range scales with the range
+        let config = GraphQlConfig { max_complexity: 10, ..Default::default() };
+        let response = schema(config).execute("{ blocks(from: 0, to: 9) { number hash } }").await;
+        assert!(!response.errors.is_empty());
+    }
+}
@@ -0,0 +1,299 @@
+//! Access to the chain data that is served by the GraphQL API.
+
+use alloy_consensus::{
+    transaction::to_eip155_value, BlockHeader as _, Header, Transaction as _, TxEnvelope, TxReceipt,
+};
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    eip2930::AccessList,
+    eip4895::Withdrawals,
+    BlockHashOrNumber,
+};
+use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes, Log, TxHash, B256, U256};
+use reth_chainspec::{ChainSpecProvider, EthChainSpec};
+use reth_primitives_traits::{
+    Account, BlockBody as _, ReceiptWithBloom, RecoveredBlock, SignedTransaction,
+};
+use reth_storage_api::{
+    AccountReader, BlockHashReader, BlockNumReader, BlockReader, StateProvider,
+    StateProviderFactory, TransactionVariant,
+};
+use reth_storage_errors::provider::ProviderResult;
+
+/// A block, or the header of an ommer, as served by the GraphQL API.
+#[derive(Debug)]
+pub struct BlockData {
+    /// The hash of the block.
+    pub hash: BlockHash,
+    /// The header of the block.
+    pub header: Header,
+    /// The RLP encoding of the header.
+    pub raw_header: Bytes,
+    /// The body of the block, `None` for ommers.
+    pub body: Option<BodyData>,
+}
+
+/// The body of a [`BlockData`].
+#[derive(Debug)]
+pub struct BodyData {
+    /// The RLP encoding of the whole block.
+    pub raw: Bytes,
+    /// The transactions of the block.
+    pub transactions: Vec<TransactionData>,
+    /// The ommer headers of the block.
+    pub ommers: Vec<Header>,
+    /// The withdrawals of the block, if withdrawals are active.
+    pub withdrawals: Option<Withdrawals>,
+}
+
+/// A transaction of a block, as served by the GraphQL API.
+#[derive(Debug)]
+pub struct TransactionData {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The recovered sender of the transaction.
+    pub from: Address,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// The transferred value.
+    pub value: U256,
+    /// The gas limit of the transaction.
+    pub gas_limit: u64,
+    /// The gas price of legacy and EIP-2930 transactions.
+    pub gas_price: Option<u128>,
+    /// The max fee per gas of dynamic fee transactions.
+    pub max_fee_per_gas: Option<u128>,
+    /// The max priority fee per gas of dynamic fee transactions.
+    pub max_priority_fee_per_gas: Option<u128>,
+    /// The max fee per blob gas of blob transactions.
+    pub max_fee_per_blob_gas: Option<u128>,
+    /// The versioned hashes of the blobs of blob transactions.
+    pub blob_versioned_hashes: Option<Vec<B256>>,
+    /// The price per gas paid by the sender, given the base fee of the block.
+    pub effective_gas_price: u128,
+    /// The tip per gas received by the block producer, given the base fee of the block.
+    pub effective_tip: Option<u128>,
+    /// The input data of the transaction.
+    pub input: Bytes,
+    /// The EIP-2718 type of the transaction.
+    pub ty: u8,
+    /// The access list of the transaction.
+    pub access_list: Option<AccessList>,
+    /// The `r`, `s` and `v` values of the signature.
+    ///
+    /// This is `None` for transactions that are not signed by the sender, e.g. deposit
+    /// transactions.
+    pub signature: Option<(U256, U256, u128)>,
+    /// The EIP-2718 encoding of the transaction.
+    pub raw: Bytes,
+}
+
+/// The receipt of a transaction, as served by the GraphQL API.
+#[derive(Debug)]
+pub struct ReceiptData {
+    /// Whether the transaction succeeded.
+    pub success: bool,
+    /// The gas used by the block, including this transaction.
+    pub cumulative_gas_used: u64,
+    /// The logs emitted by the transaction.
+    pub logs: Vec<Log>,
+    /// The EIP-2718 encoding of the receipt.
+    pub raw: Bytes,
+}
+
+/// Provides the chain data that is served by the GraphQL API.
+///
+/// This is an object safe abstraction over the provider, which converts the node's primitive types
+/// into the representations used by the API. It is implemented for all providers that can read
+/// blocks and state.
+pub trait GraphQlProvider: Send + Sync + 'static {
+    /// Returns the chain id.
+    fn chain_id(&self) -> u64;
+
+    /// Returns the number of the latest canonical block.
+    fn best_block_number(&self) -> ProviderResult<BlockNumber>;
+
+    /// Returns the hash of the canonical block with the given number.
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<BlockHash>>;
+
+    /// Returns the block with the given hash or number.
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<BlockData>>;
+
+    /// Returns the hash of the block that includes the given transaction and the index of the
+    /// transaction in that block.
+    fn transaction_location(&self, hash: TxHash) -> ProviderResult<Option<(BlockHash, usize)>>;
+
+    /// Returns the receipts of the block with the given hash.
+    fn receipts(&self, block_hash: BlockHash) -> ProviderResult<Option<Vec<ReceiptData>>>;
+
+    /// Returns the account at the state after the block with the given hash.
+    fn account(&self, address: Address, block_hash: BlockHash) -> ProviderResult<Option<Account>>;
+
+    /// Returns the code of the account at the state after the block with the given hash.
+    fn code(&self, address: Address, block_hash: BlockHash) -> ProviderResult<Option<Bytes>>;
+
+    /// Returns the value of the storage slot at the state after the block with the given hash.
+    fn storage(&self, address: Address, slot: B256, block_hash: BlockHash) -> ProviderResult<U256>;
+}
+
+impl<P> GraphQlProvider for P
+where
+    P: BlockReader + StateProviderFactory + ChainSpecProvider + 'static,
+{
+    fn chain_id(&self) -> u64 {
+        self.chain_spec().chain_id()
+    }
+
+    fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        BlockNumReader::best_block_number(self)
+    }
+
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<BlockHash>> {
+        BlockHashReader::block_hash(self, number)
+    }
+
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<BlockData>> {
+        Ok(self.recovered_block(id, TransactionVariant::WithHash)?.map(block_data))
+    }
+
+    fn transaction_location(&self, hash: TxHash) -> ProviderResult<Option<(BlockHash, usize)>> {
+        Ok(self
+            .transaction_by_hash_with_meta(hash)?
+            .map(|(_, meta)| (meta.block_hash, meta.index as usize)))
+    }
+
+    fn receipts(&self, block_hash: BlockHash) -> ProviderResult<Option<Vec<ReceiptData>>> {
+        Ok(self.receipts_by_block(block_hash.into())?.map(|receipts| {
+            receipts
+                .into_iter()
+                .map(|receipt| {
+                    let receipt = ReceiptWithBloom::from(receipt);
+                    ReceiptData {
+                        success: receipt.receipt.status(),
+                        cumulative_gas_used: receipt.receipt.cumulative_gas_used(),
+                        logs: receipt.receipt.logs().to_vec(),
+                        raw: receipt.encoded_2718().into(),
+                    }
+                })
+                .collect()
+        }))
+    }
+
+    fn account(&self, address: Address, block_hash: BlockHash) -> ProviderResult<Option<Account>> {
+        self.state_by_block_hash(block_hash)?.basic_account(&address)
+    }
+
+    fn code(&self, address: Address, block_hash: BlockHash) -> ProviderResult<Option<Bytes>> {
+        Ok(self
+            .state_by_block_hash(block_hash)?
+            .account_code(&address)?
+            .map(|code| code.original_bytes()))
+    }
+
+    fn storage(&self, address: Address, slot: B256, block_hash: BlockHash) -> ProviderResult<U256> {
+        Ok(self.state_by_block_hash(block_hash)?.storage(address, slot)?.unwrap_or_default())
+    }
+}
+
+/// Converts a recovered block into the representation used by the GraphQL API.
+fn block_data<B: reth_primitives_traits::Block>(block: RecoveredBlock<B>) -> BlockData {
+    let base_fee = block.header().base_fee_per_gas();
+    let transactions = block
+        .transactions_with_sender()
+        .map(|(from, tx)| transaction_data(tx, *from, base_fee))
+        .collect();
+    let body = block.body();
+    let body = BodyData {
+        raw: alloy_rlp::encode(block.sealed_block().clone_block()).into(),
+        transactions,
+        ommers: body.ommers().unwrap_or_default().iter().map(header).collect(),
+        withdrawals: body.withdrawals().cloned(),
+    };
+
+    BlockData {
+        hash: block.hash(),
+        header: header(block.header()),
+        raw_header: alloy_rlp::encode(block.header()).into(),
+        body: Some(body),
+    }
+}
+
+/// Converts an ommer header into the representation used by the GraphQL API.
+pub(crate) fn ommer_data(header: &Header) -> BlockData {
+    BlockData {
+        hash: header.hash_slow(),
+        header: header.clone(),
+        raw_header: alloy_rlp::encode(header).into(),
+        body: None,
+    }
+}
+
+/// Converts a transaction into the representation used by the GraphQL API.
+fn transaction_data<T: SignedTransaction>(
+    tx: &T,
+    from: Address,
+    base_fee: Option<u64>,
+) -> TransactionData {
+    let raw: Bytes = tx.encoded_2718().into();
+    // The signature isn't exposed by the primitive transaction type, so it's decoded from the
+    // encoding, which fails for unsigned transaction types.
+    let signature = TxEnvelope::decode_2718(&mut raw.as_ref()).ok().map(|envelope| {
+        let signature = envelope.signature();
+        let v = if envelope.is_legacy() {
+            to_eip155_value(signature.v(), envelope.chain_id())
+        } else {
+            signature.v() as u128
+        };
+        (signature.r(), signature.s(), v)
+    });
+
+    TransactionData {
+        hash: *tx.tx_hash(),
+        from,
+        nonce: tx.nonce(),
+        to: tx.to(),
+        value: tx.value(),
+        gas_limit: tx.gas_limit(),
+        gas_price: tx.gas_price(),
+        max_fee_per_gas: tx.is_dynamic_fee().then(|| tx.max_fee_per_gas()),
+        max_priority_fee_per_gas: tx.max_priority_fee_per_gas(),
+        max_fee_per_blob_gas: tx.max_fee_per_blob_gas(),
+        blob_versioned_hashes: tx.blob_versioned_hashes().map(<[B256]>::to_vec),
+        effective_gas_price: tx.effective_gas_price(base_fee),
+        effective_tip: base_fee.and_then(|base_fee| tx.effective_tip_per_gas(base_fee)),
+        input: tx.input().clone(),
+        ty: tx.ty(),
+        access_list: tx.access_list().cloned(),
+        signature,
+        raw,
+    }
+}
+
+/// Converts a header into an [`alloy_consensus::Header`].
+fn header<H: alloy_consensus::BlockHeader>(header: &H) -> Header {
+    Header {
+        parent_hash: header.parent_hash(),
+        ommers_hash: header.ommers_hash(),
+        beneficiary: header.beneficiary(),
+        state_root: header.state_root(),
+        transactions_root: header.transactions_root(),
+        receipts_root: header.receipts_root(),
+        withdrawals_root: header.withdrawals_root(),
+        logs_bloom: header.logs_bloom(),
+        difficulty: header.difficulty(),
+        number: header.number(),
+        gas_limit: header.gas_limit(),
+        gas_used: header.gas_used(),
+        timestamp: header.timestamp(),
+        mix_hash: header.mix_hash().unwrap_or_default(),
+        nonce: header.nonce().unwrap_or_default(),
+        base_fee_per_gas: header.base_fee_per_gas(),
+        blob_gas_used: header.blob_gas_used(),
+        excess_blob_gas: header.excess_blob_gas(),
+        parent_beacon_block_root: header.parent_beacon_block_root(),
+        requests_hash: header.requests_hash(),
+        extra_data: header.extra_data().clone(),
+    }
+}
@@ -0,0 +1,140 @@
+//! The custom scalars of the EIP-1767 schema.
+
+use alloy_primitives::{hex, U256};
+use async_graphql::{InputValueError, InputValueResult, Number, Scalar, ScalarType, Value};
+
+/// A 32 byte binary string, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bytes32(pub alloy_primitives::B256);
+
+#[Scalar]
+impl ScalarType for Bytes32 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => s.parse().map(Self).map_err(InputValueError::custom),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(hex::encode_prefixed(self.0))
+    }
+}
+
+/// A 20 byte Ethereum address, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Address(pub alloy_primitives::Address);
+
+#[Scalar]
+impl ScalarType for Address {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => s.parse().map(Self).map_err(InputValueError::custom),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(hex::encode_prefixed(self.0))
+    }
+}
+
+/// An arbitrary length binary string, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bytes(pub alloy_primitives::Bytes);
+
+#[Scalar]
+impl ScalarType for Bytes {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => s.parse().map(Self).map_err(InputValueError::custom),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(hex::encode_prefixed(&self.0))
+    }
+}
+
+/// A 256 bit unsigned integer, represented as 0x-prefixed hexadecimal.
+///
+/// Decimal strings and numbers are accepted as input as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigInt(pub U256);
+
+#[Scalar]
+impl ScalarType for BigInt {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => s.parse().map(Self).map_err(InputValueError::custom),
+            Value::Number(n) => n
+                .as_u64()
+                .map(|n| Self(U256::from(n)))
+                .ok_or_else(|| InputValueError::custom("expected an unsigned integer")),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(value: u128) -> Self {
+        Self(U256::from(value))
+    }
+}
+
+/// A 64 bit unsigned integer, represented as a number.
+///
+/// Hexadecimal and decimal strings are accepted as input as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Long(pub u64);
+
+#[Scalar]
+impl ScalarType for Long {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::Number(n) => n
+                .as_u64()
+                .map(Self)
+                .ok_or_else(|| InputValueError::custom("expected an unsigned integer")),
+            Value::String(s) => {
+                let parsed = match s.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => s.parse(),
+                };
+                parsed.map(Self).map_err(InputValueError::custom)
+            }
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Number(Number::from(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_long() {
+        assert_eq!(Long::parse(Value::Number(10.into())).unwrap(), Long(10));
+        assert_eq!(Long::parse(Value::String("0xa".into())).unwrap(), Long(10));
+        assert_eq!(Long::parse(Value::String("10".into())).unwrap(), Long(10));
+        assert!(Long::parse(Value::String("0xz".into())).is_err());
+        assert!(Long::parse(Value::Boolean(true)).is_err());
+    }
+
+    #[test]
+    fn big_int_roundtrip() {
+        let value = BigInt::parse(Value::String("1000".into())).unwrap();
+        assert_eq!(value, BigInt(U256::from(1000)));
+        assert_eq!(value.to_value(), Value::String("0x3e8".into()));
+        assert_eq!(BigInt::parse(value.to_value()).unwrap(), value);
+    }
+}
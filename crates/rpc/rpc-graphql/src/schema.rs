@@ -0,0 +1,823 @@
+//! The object types of the EIP-1767 schema and their resolvers.
+//!
+//! Nested objects are resolved lazily: a [`Block`] only loads its receipts once a field that
+//! depends on them is queried, and an [`Account`] only reads the state that is queried.
+
+use crate::{
+    provider::{ommer_data, BlockData, BodyData, GraphQlProvider, ReceiptData, TransactionData},
+    scalars::{Address, BigInt, Bytes, Bytes32, Long},
+};
+use alloy_eips::BlockHashOrNumber;
+use alloy_primitives::{BlockHash, Bloom, BloomInput, B256, U256};
+use async_graphql::{Context, InputObject, Object, Result, SimpleObject};
+use std::sync::{Arc, OnceLock};
+
+/// The data shared by all resolvers of a query.
+pub(crate) struct GraphQlContext {
+    /// The provider queries are resolved against.
+    provider: Arc<dyn GraphQlProvider>,
+    /// The maximum number of blocks that can be scanned by a single field.
+    max_block_range: Option<u64>,
+}
+
+impl GraphQlContext {
+    /// Creates a new context for the given provider.
+    pub(crate) const fn new(
+        provider: Arc<dyn GraphQlProvider>,
+        max_block_range: Option<u64>,
+    ) -> Self {
+        Self { provider, max_block_range }
+    }
+
+    /// Returns the context of the query that is being resolved.
+    fn get<'a>(ctx: &Context<'a>) -> &'a Self {
+        ctx.data_unchecked::<Self>()
+    }
+
+    /// Returns the block with the given hash or number.
+    fn block(&self, id: BlockHashOrNumber) -> Result<Option<Block>> {
+        Ok(self.provider.block(id)?.map(Block::new))
+    }
+
+    /// Resolves the given block range, where a missing end refers to the latest block.
+    ///
+    /// Returns an error if the range exceeds the maximum block range.
+    fn block_range(&self, from: u64, to: Option<Long>) -> Result<(u64, u64)> {
+        let latest = self.provider.best_block_number()?;
+        let to = to.map_or(latest, |to| to.0.min(latest));
+        if to < from {
+            return Err("to block number must be greater than or equal to from block number".into())
+        }
+        if let Some(max) = self.max_block_range.filter(|max| to - from >= *max) {
+            return Err(format!("query exceeds max block range {max}").into())
+        }
+        Ok((from, to))
+    }
+
+    /// Returns the account with the given address at the given block, or at the latest block if
+    /// no block is given.
+    fn account(&self, address: alloy_primitives::Address, block: Option<Long>) -> Result<Account> {
+        let number = match block {
+            Some(number) => number.0,
+            None => self.provider.best_block_number()?,
+        };
+        let block_hash = self.provider.block_hash(number)?.ok_or("block not found")?;
+        Ok(Account { address, block_hash })
+    }
+}
+
+impl std::fmt::Debug for GraphQlContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GraphQlContext").field("max_block_range", &self.max_block_range).finish()
+    }
+}
+
+/// Returns the complexity of a field that resolves `child_complexity` for every block in a range.
+fn range_complexity(child_complexity: usize, from: Option<Long>, to: Option<Long>) -> usize {
+    let blocks = match (from, to) {
+        (Some(from), Some(to)) => to.0.saturating_sub(from.0).saturating_add(1),
+        _ => 1,
+    };
+    child_complexity.saturating_mul(usize::try_from(blocks).unwrap_or(usize::MAX))
+}
+
+/// The root query type.
+#[derive(Debug, Default)]
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// Returns the block with the given number or hash, or the latest block if neither is given.
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<Long>,
+        hash: Option<Bytes32>,
+    ) -> Result<Option<Block>> {
+        let gql = GraphQlContext::get(ctx);
+        let id = match (number, hash) {
+            (Some(_), Some(_)) => return Err("only one of number or hash must be specified".into()),
+            (None, Some(hash)) => hash.0.into(),
+            (Some(number), None) => number.0.into(),
+            (None, None) => gql.provider.best_block_number()?.into(),
+        };
+        gql.block(id)
+    }
+
+    /// Returns all blocks in the given inclusive range. The range ends at the latest block if no
+    /// end is given.
+    #[graphql(complexity = "range_complexity(child_complexity, from, to)")]
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        from: Option<Long>,
+        to: Option<Long>,
+    ) -> Result<Vec<Block>> {
+        let gql = GraphQlContext::get(ctx);
+        let from = from.ok_or("from block number must be specified")?;
+        let (from, to) = gql.block_range(from.0, to)?;
+
+        let mut blocks = Vec::new();
+        for number in from..=to {
+            blocks.extend(gql.block(number.into())?);
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the transaction with the given hash, if it is included in a block.
+    async fn transaction(&self, ctx: &Context<'_>, hash: Bytes32) -> Result<Option<Transaction>> {
+        let gql = GraphQlContext::get(ctx);
+        let Some((block_hash, index)) = gql.provider.transaction_location(hash.0)? else {
+            return Ok(None)
+        };
+        Ok(gql.block(block_hash.into())?.and_then(|block| Transaction::new(block, index)))
+    }
+
+    /// Returns all logs matching the given filter criteria. The range starts and ends at the
+    /// latest block if no start or end is given.
+    #[graphql(
+        complexity = "range_complexity(child_complexity, filter.from_block, filter.to_block)"
+    )]
+    async fn logs(&self, ctx: &Context<'_>, filter: FilterCriteria) -> Result<Vec<Log>> {
+        let gql = GraphQlContext::get(ctx);
+        let from = match filter.from_block {
+            Some(from) => from.0,
+            None => gql.provider.best_block_number()?,
+        };
+        let (from, to) = gql.block_range(from, filter.to_block)?;
+        let filter = LogFilter::new(filter.addresses, filter.topics);
+
+        let mut logs = Vec::new();
+        for number in from..=to {
+            if let Some(block) = gql.block(number.into())? {
+                logs.extend(block.filter_logs(gql, &filter)?);
+            }
+        }
+        Ok(logs)
+    }
+
+    /// Returns the chain id.
+    #[graphql(name = "chainID")]
+    async fn chain_id(&self, ctx: &Context<'_>) -> BigInt {
+        BigInt(U256::from(GraphQlContext::get(ctx).provider.chain_id()))
+    }
+}
+
+/// A block, or an ommer.
+///
+/// Ommers don't have a body, hence all fields that depend on the body are `null` for ommers.
+#[derive(Debug, Clone)]
+pub struct Block {
+    inner: Arc<BlockInner>,
+}
+
+#[derive(Debug)]
+struct BlockInner {
+    /// The block data.
+    data: BlockData,
+    /// The receipts of the block, loaded on first access.
+    receipts: OnceLock<Vec<ReceiptData>>,
+}
+
+impl Block {
+    fn new(data: BlockData) -> Self {
+        Self { inner: Arc::new(BlockInner { data, receipts: OnceLock::new() }) }
+    }
+
+    fn data(&self) -> &BlockData {
+        &self.inner.data
+    }
+
+    fn body(&self) -> Option<&BodyData> {
+        self.inner.data.body.as_ref()
+    }
+
+    /// Returns the receipts of the block, loading them if they haven't been loaded yet.
+    fn receipts(&self, gql: &GraphQlContext) -> Result<&[ReceiptData]> {
+        if let Some(receipts) = self.inner.receipts.get() {
+            return Ok(receipts)
+        }
+        let receipts = gql.provider.receipts(self.data().hash)?.ok_or("receipts not found")?;
+        Ok(self.inner.receipts.get_or_init(|| receipts))
+    }
+
+    /// Returns all logs of the block that match the filter.
+    fn filter_logs(&self, gql: &GraphQlContext, filter: &LogFilter) -> Result<Vec<Log>> {
+        if self.body().is_none() || !filter.matches_bloom(&self.data().header.logs_bloom) {
+            return Ok(Vec::new())
+        }
+
+        let mut logs = Vec::new();
+        let mut log_index = 0;
+        for (index, receipt) in self.receipts(gql)?.iter().enumerate() {
+            for log in &receipt.logs {
+                if filter.matches(log) {
+                    logs.push(Log {
+                        transaction: Transaction { block: self.clone(), index },
+                        index: log_index,
+                        log: log.clone(),
+                    });
+                }
+                log_index += 1;
+            }
+        }
+        Ok(logs)
+    }
+}
+
+#[Object]
+impl Block {
+    /// The block number.
+    async fn number(&self) -> Long {
+        Long(self.data().header.number)
+    }
+
+    /// The block hash.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.data().hash)
+    }
+
+    /// The parent block, `null` for the genesis block.
+    async fn parent(&self, ctx: &Context<'_>) -> Result<Option<Self>> {
+        if self.data().header.number == 0 {
+            return Ok(None)
+        }
+        GraphQlContext::get(ctx).block(self.data().header.parent_hash.into())
+    }
+
+    /// The proof-of-work nonce of the block.
+    async fn nonce(&self) -> Bytes {
+        Bytes(self.data().header.nonce.into())
+    }
+
+    /// The root of the transaction trie of the block.
+    async fn transactions_root(&self) -> Bytes32 {
+        Bytes32(self.data().header.transactions_root)
+    }
+
+    /// The number of transactions in the block, `null` for ommers.
+    async fn transaction_count(&self) -> Option<Long> {
+        self.body().map(|body| Long(body.transactions.len() as u64))
+    }
+
+    /// The root of the state trie after the block.
+    async fn state_root(&self) -> Bytes32 {
+        Bytes32(self.data().header.state_root)
+    }
+
+    /// The root of the receipt trie of the block.
+    async fn receipts_root(&self) -> Bytes32 {
+        Bytes32(self.data().header.receipts_root)
+    }
+
+    /// The account that mined the block, at the given block or at the latest block.
+    async fn miner(&self, ctx: &Context<'_>, block: Option<Long>) -> Result<Account> {
+        GraphQlContext::get(ctx).account(self.data().header.beneficiary, block)
+    }
+
+    /// The extra data of the block.
+    async fn extra_data(&self) -> Bytes {
+        Bytes(self.data().header.extra_data.clone())
+    }
+
+    /// The gas limit of the block.
+    async fn gas_limit(&self) -> Long {
+        Long(self.data().header.gas_limit)
+    }
+
+    /// The gas used by the transactions of the block.
+    async fn gas_used(&self) -> Long {
+        Long(self.data().header.gas_used)
+    }
+
+    /// The base fee per gas of the block, `null` before London.
+    async fn base_fee_per_gas(&self) -> Option<BigInt> {
+        self.data().header.base_fee_per_gas.map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The unix timestamp of the block.
+    async fn timestamp(&self) -> Long {
+        Long(self.data().header.timestamp)
+    }
+
+    /// The bloom filter of the logs of the block.
+    async fn logs_bloom(&self) -> Bytes {
+        Bytes(self.data().header.logs_bloom.0.into())
+    }
+
+    /// The mix hash of the block.
+    async fn mix_hash(&self) -> Bytes32 {
+        Bytes32(self.data().header.mix_hash)
+    }
+
+    /// The proof-of-work difficulty of the block.
+    async fn difficulty(&self) -> BigInt {
+        BigInt(self.data().header.difficulty)
+    }
+
+    /// The number of ommers of the block, `null` for ommers.
+    async fn ommer_count(&self) -> Option<Long> {
+        self.body().map(|body| Long(body.ommers.len() as u64))
+    }
+
+    /// The ommers of the block, `null` for ommers.
+    async fn ommers(&self) -> Option<Vec<Self>> {
+        self.body()
+            .map(|body| body.ommers.iter().map(|ommer| Self::new(ommer_data(ommer))).collect())
+    }
+
+    /// The ommer at the given index, `null` if there is no such ommer.
+    async fn ommer_at(&self, index: Long) -> Option<Self> {
+        let ommer = self.body()?.ommers.get(usize::try_from(index.0).ok()?)?;
+        Some(Self::new(ommer_data(ommer)))
+    }
+
+    /// The hash of the ommers of the block.
+    async fn ommer_hash(&self) -> Bytes32 {
+        Bytes32(self.data().header.ommers_hash)
+    }
+
+    /// The transactions of the block, `null` for ommers.
+    async fn transactions(&self) -> Option<Vec<Transaction>> {
+        let count = self.body()?.transactions.len();
+        Some((0..count).map(|index| Transaction { block: self.clone(), index }).collect())
+    }
+
+    /// The transaction at the given index, `null` if there is no such transaction.
+    async fn transaction_at(&self, index: Long) -> Option<Transaction> {
+        Transaction::new(self.clone(), usize::try_from(index.0).ok()?)
+    }
+
+    /// The logs of the block that match the given filter criteria.
+    async fn logs(&self, ctx: &Context<'_>, filter: BlockFilterCriteria) -> Result<Vec<Log>> {
+        let filter = LogFilter::new(filter.addresses, filter.topics);
+        self.filter_logs(GraphQlContext::get(ctx), &filter)
+    }
+
+    /// The account with the given address at the state after the block.
+    async fn account(&self, address: Address) -> Account {
+        Account { address: address.0, block_hash: self.data().hash }
+    }
+
+    /// The RLP encoding of the header of the block.
+    async fn raw_header(&self) -> Bytes {
+        Bytes(self.data().raw_header.clone())
+    }
+
+    /// The RLP encoding of the block, only the header is encoded for ommers.
+    async fn raw(&self) -> Bytes {
+        Bytes(self.body().map_or_else(|| self.data().raw_header.clone(), |body| body.raw.clone()))
+    }
+
+    /// The root of the withdrawals trie of the block, `null` before Shanghai.
+    async fn withdrawals_root(&self) -> Option<Bytes32> {
+        self.data().header.withdrawals_root.map(Bytes32)
+    }
+
+    /// The withdrawals of the block, `null` before Shanghai and for ommers.
+    async fn withdrawals(&self) -> Option<Vec<Withdrawal>> {
+        let withdrawals = self.body()?.withdrawals.as_ref()?;
+        Some(
+            withdrawals
+                .iter()
+                .map(|withdrawal| Withdrawal {
+                    index: Long(withdrawal.index),
+                    validator: Long(withdrawal.validator_index),
+                    address: Address(withdrawal.address),
+                    amount: Long(withdrawal.amount),
+                })
+                .collect(),
+        )
+    }
+
+    /// The blob gas used by the transactions of the block, `null` before Cancun.
+    async fn blob_gas_used(&self) -> Option<Long> {
+        self.data().header.blob_gas_used.map(Long)
+    }
+
+    /// The excess blob gas of the block, `null` before Cancun.
+    async fn excess_blob_gas(&self) -> Option<Long> {
+        self.data().header.excess_blob_gas.map(Long)
+    }
+}
+
+/// A transaction that is included in a block.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    /// The block that includes the transaction.
+    block: Block,
+    /// The index of the transaction in the block.
+    index: usize,
+}
+
+impl Transaction {
+    /// Returns the transaction at the given index of the block, if it exists.
+    fn new(block: Block, index: usize) -> Option<Self> {
+        (index < block.body()?.transactions.len()).then_some(Self { block, index })
+    }
+
+    fn tx(&self) -> &TransactionData {
+        &self
+            .block
+            .body()
+            .expect("transactions are only created for blocks with a body")
+            .transactions[self.index]
+    }
+
+    fn receipt<'a>(&'a self, gql: &GraphQlContext) -> Result<&'a ReceiptData> {
+        Ok(self.block.receipts(gql)?.get(self.index).ok_or("receipt not found")?)
+    }
+
+    fn signature(&self) -> (U256, U256, u128) {
+        self.tx().signature.unwrap_or_default()
+    }
+}
+
+#[Object]
+impl Transaction {
+    /// The hash of the transaction.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.tx().hash)
+    }
+
+    /// The nonce of the transaction.
+    async fn nonce(&self) -> Long {
+        Long(self.tx().nonce)
+    }
+
+    /// The index of the transaction in the block.
+    async fn index(&self) -> Option<Long> {
+        Some(Long(self.index as u64))
+    }
+
+    /// The account that sent the transaction, at the given block or at the latest block.
+    async fn from(&self, ctx: &Context<'_>, block: Option<Long>) -> Result<Account> {
+        GraphQlContext::get(ctx).account(self.tx().from, block)
+    }
+
+    /// The recipient of the transaction, at the given block or at the latest block. `null` for
+    /// contract creations.
+    async fn to(&self, ctx: &Context<'_>, block: Option<Long>) -> Result<Option<Account>> {
+        self.tx().to.map(|to| GraphQlContext::get(ctx).account(to, block)).transpose()
+    }
+
+    /// The value transferred by the transaction.
+    async fn value(&self) -> BigInt {
+        BigInt(self.tx().value)
+    }
+
+    /// The price per gas paid by the sender.
+    async fn gas_price(&self) -> BigInt {
+        self.tx().effective_gas_price.into()
+    }
+
+    /// The max fee per gas of the transaction, `null` for transactions without dynamic fees.
+    async fn max_fee_per_gas(&self) -> Option<BigInt> {
+        self.tx().max_fee_per_gas.map(Into::into)
+    }
+
+    /// The max priority fee per gas of the transaction, `null` for transactions without dynamic
+    /// fees.
+    async fn max_priority_fee_per_gas(&self) -> Option<BigInt> {
+        self.tx().max_priority_fee_per_gas.map(Into::into)
+    }
+
+    /// The max fee per blob gas of the transaction, `null` for transactions without blobs.
+    async fn max_fee_per_blob_gas(&self) -> Option<BigInt> {
+        self.tx().max_fee_per_blob_gas.map(Into::into)
+    }
+
+    /// The versioned hashes of the blobs of the transaction, `null` for transactions without
+    /// blobs.
+    async fn blob_versioned_hashes(&self) -> Option<Vec<Bytes32>> {
+        self.tx()
+            .blob_versioned_hashes
+            .as_ref()
+            .map(|hashes| hashes.iter().copied().map(Bytes32).collect())
+    }
+
+    /// The tip per gas received by the block producer, `null` before London.
+    async fn effective_tip(&self) -> Option<BigInt> {
+        self.tx().effective_tip.map(Into::into)
+    }
+
+    /// The gas limit of the transaction.
+    async fn gas(&self) -> Long {
+        Long(self.tx().gas_limit)
+    }
+
+    /// The input data of the transaction.
+    async fn input_data(&self) -> Bytes {
+        Bytes(self.tx().input.clone())
+    }
+
+    /// The block that includes the transaction.
+    async fn block(&self) -> Option<Block> {
+        Some(self.block.clone())
+    }
+
+    /// The status of the transaction, `1` on success and `0` on failure.
+    async fn status(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(Some(Long(u64::from(self.receipt(GraphQlContext::get(ctx))?.success))))
+    }
+
+    /// The gas used by the transaction.
+    async fn gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        let receipts = self.block.receipts(GraphQlContext::get(ctx))?;
+        let cumulative = receipts.get(self.index).ok_or("receipt not found")?.cumulative_gas_used;
+        let previous =
+            self.index.checked_sub(1).map_or(0, |index| receipts[index].cumulative_gas_used);
+        Ok(Some(Long(cumulative - previous)))
+    }
+
+    /// The gas used by the block up to and including the transaction.
+    async fn cumulative_gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(Some(Long(self.receipt(GraphQlContext::get(ctx))?.cumulative_gas_used)))
+    }
+
+    /// The price per gas paid by the sender.
+    async fn effective_gas_price(&self) -> Option<BigInt> {
+        Some(self.tx().effective_gas_price.into())
+    }
+
+    /// The contract created by the transaction, at the given block or at the latest block.
+    /// `null` for transactions that are not contract creations.
+    async fn created_contract(
+        &self,
+        ctx: &Context<'_>,
+        block: Option<Long>,
+    ) -> Result<Option<Account>> {
+        let tx = self.tx();
+        if tx.to.is_some() {
+            return Ok(None)
+        }
+        GraphQlContext::get(ctx).account(tx.from.create(tx.nonce), block).map(Some)
+    }
+
+    /// The logs emitted by the transaction.
+    async fn logs(&self, ctx: &Context<'_>) -> Result<Option<Vec<Log>>> {
+        let receipts = self.block.receipts(GraphQlContext::get(ctx))?;
+        let first_index =
+            receipts.iter().take(self.index).map(|receipt| receipt.logs.len() as u64).sum::<u64>();
+        let receipt = receipts.get(self.index).ok_or("receipt not found")?;
+        Ok(Some(
+            receipt
+                .logs
+                .iter()
+                .zip(first_index..)
+                .map(|(log, index)| Log { transaction: self.clone(), index, log: log.clone() })
+                .collect(),
+        ))
+    }
+
+    /// The `r` value of the signature.
+    async fn r(&self) -> BigInt {
+        BigInt(self.signature().0)
+    }
+
+    /// The `s` value of the signature.
+    async fn s(&self) -> BigInt {
+        BigInt(self.signature().1)
+    }
+
+    /// The `v` value of the signature, the y-parity for typed transactions.
+    async fn v(&self) -> BigInt {
+        self.signature().2.into()
+    }
+
+    /// The EIP-2718 type of the transaction.
+    #[graphql(name = "type")]
+    async fn ty(&self) -> Option<Long> {
+        Some(Long(u64::from(self.tx().ty)))
+    }
+
+    /// The access list of the transaction, `null` for transactions without an access list.
+    async fn access_list(&self) -> Option<Vec<AccessTuple>> {
+        let access_list = self.tx().access_list.as_ref()?;
+        Some(
+            access_list
+                .iter()
+                .map(|item| AccessTuple {
+                    address: Address(item.address),
+                    storage_keys: item.storage_keys.iter().copied().map(Bytes32).collect(),
+                })
+                .collect(),
+        )
+    }
+
+    /// The EIP-2718 encoding of the transaction.
+    async fn raw(&self) -> Bytes {
+        Bytes(self.tx().raw.clone())
+    }
+
+    /// The EIP-2718 encoding of the receipt of the transaction.
+    async fn raw_receipt(&self, ctx: &Context<'_>) -> Result<Bytes> {
+        Ok(Bytes(self.receipt(GraphQlContext::get(ctx))?.raw.clone()))
+    }
+}
+
+/// A log emitted by a transaction.
+#[derive(Debug, Clone)]
+pub struct Log {
+    /// The transaction that emitted the log.
+    transaction: Transaction,
+    /// The index of the log in the block.
+    index: u64,
+    /// The log.
+    log: alloy_primitives::Log,
+}
+
+#[Object]
+impl Log {
+    /// The index of the log in the block.
+    async fn index(&self) -> Long {
+        Long(self.index)
+    }
+
+    /// The account that emitted the log, at the given block or at the latest block.
+    async fn account(&self, ctx: &Context<'_>, block: Option<Long>) -> Result<Account> {
+        GraphQlContext::get(ctx).account(self.log.address, block)
+    }
+
+    /// The topics of the log.
+    async fn topics(&self) -> Vec<Bytes32> {
+        self.log.topics().iter().copied().map(Bytes32).collect()
+    }
+
+    /// The data of the log.
+    async fn data(&self) -> Bytes {
+        Bytes(self.log.data.data.clone())
+    }
+
+    /// The transaction that emitted the log.
+    async fn transaction(&self) -> Transaction {
+        self.transaction.clone()
+    }
+}
+
+/// An account at the state after a specific block.
+#[derive(Debug, Clone)]
+pub struct Account {
+    /// The address of the account.
+    address: alloy_primitives::Address,
+    /// The hash of the block whose state the account is read from.
+    block_hash: BlockHash,
+}
+
+#[Object]
+impl Account {
+    /// The address of the account.
+    async fn address(&self) -> Address {
+        Address(self.address)
+    }
+
+    /// The balance of the account.
+    async fn balance(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        let account = GraphQlContext::get(ctx).provider.account(self.address, self.block_hash)?;
+        Ok(BigInt(account.map(|account| account.balance).unwrap_or_default()))
+    }
+
+    /// The number of transactions sent by the account, i.e. its nonce.
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<Long> {
+        let account = GraphQlContext::get(ctx).provider.account(self.address, self.block_hash)?;
+        Ok(Long(account.map(|account| account.nonce).unwrap_or_default()))
+    }
+
+    /// The code of the account.
+    async fn code(&self, ctx: &Context<'_>) -> Result<Bytes> {
+        let code = GraphQlContext::get(ctx).provider.code(self.address, self.block_hash)?;
+        Ok(Bytes(code.unwrap_or_default()))
+    }
+
+    /// The value of the given storage slot of the account.
+    async fn storage(&self, ctx: &Context<'_>, slot: Bytes32) -> Result<Bytes32> {
+        let value =
+            GraphQlContext::get(ctx).provider.storage(self.address, slot.0, self.block_hash)?;
+        Ok(Bytes32(B256::from(value)))
+    }
+}
+
+/// A withdrawal of a validator.
+#[derive(Debug, SimpleObject)]
+pub struct Withdrawal {
+    /// The index of the withdrawal.
+    index: Long,
+    /// The index of the validator that withdrew.
+    validator: Long,
+    /// The recipient of the withdrawal.
+    address: Address,
+    /// The amount of the withdrawal, in Gwei.
+    amount: Long,
+}
+
+/// An entry of the access list of a transaction.
+#[derive(Debug, SimpleObject)]
+pub struct AccessTuple {
+    /// The address that is accessed.
+    address: Address,
+    /// The storage keys that are accessed.
+    storage_keys: Vec<Bytes32>,
+}
+
+/// Criteria for filtering the logs of a range of blocks.
+#[derive(Debug, InputObject)]
+pub struct FilterCriteria {
+    /// The first block of the range, the latest block if not given.
+    from_block: Option<Long>,
+    /// The last block of the range, the latest block if not given.
+    to_block: Option<Long>,
+    /// Only logs emitted by one of these addresses match, any address matches if not given.
+    addresses: Option<Vec<Address>>,
+    /// Only logs with a matching topic at each position match.
+    ///
+    /// An empty list of topics matches any topic at that position.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// Criteria for filtering the logs of a single block.
+#[derive(Debug, InputObject)]
+pub struct BlockFilterCriteria {
+    /// Only logs emitted by one of these addresses match, any address matches if not given.
+    addresses: Option<Vec<Address>>,
+    /// Only logs with a matching topic at each position match.
+    ///
+    /// An empty list of topics matches any topic at that position.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// Matches logs against the addresses and topics of filter criteria.
+#[derive(Debug, Default)]
+struct LogFilter {
+    addresses: Vec<alloy_primitives::Address>,
+    topics: Vec<Vec<B256>>,
+}
+
+impl LogFilter {
+    fn new(addresses: Option<Vec<Address>>, topics: Option<Vec<Vec<Bytes32>>>) -> Self {
+        Self {
+            addresses: addresses.unwrap_or_default().into_iter().map(|address| address.0).collect(),
+            topics: topics
+                .unwrap_or_default()
+                .into_iter()
+                .map(|topics| topics.into_iter().map(|topic| topic.0).collect())
+                .collect(),
+        }
+    }
+
+    /// Returns whether a block with the given logs bloom may contain matching logs.
+    fn matches_bloom(&self, bloom: &Bloom) -> bool {
+        let contains = |input: &[u8]| bloom.contains_input(BloomInput::Raw(input));
+        (self.addresses.is_empty() ||
+            self.addresses.iter().any(|address| contains(address.as_slice()))) &&
+            self.topics.iter().all(|topics| {
+                topics.is_empty() || topics.iter().any(|topic| contains(topic.as_slice()))
+            })
+    }
+
+    /// Returns whether the log matches.
+    fn matches(&self, log: &alloy_primitives::Log) -> bool {
+        (self.addresses.is_empty() || self.addresses.contains(&log.address)) &&
+            self.topics.iter().enumerate().all(|(position, topics)| {
+                topics.is_empty() ||
+                    log.topics().get(position).is_some_and(|topic| topics.contains(topic))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address as EthAddress, LogData};
+
+    #[test]
+    fn log_filter_matches() {
+        let (a, b) = (EthAddress::with_last_byte(1), EthAddress::with_last_byte(2));
+        let (t1, t2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let log = alloy_primitives::Log {
+            address: a,
+            data: LogData::new_unchecked(vec![t1, t2], Default::default()),
+        };
+
+        let filter =
+            |addresses: Vec<EthAddress>, topics: Vec<Vec<B256>>| LogFilter { addresses, topics };
+        assert!(filter(vec![], vec![]).matches(&log));
+        assert!(filter(vec![a, b], vec![]).matches(&log));
+        assert!(!filter(vec![b], vec![]).matches(&log));
+        assert!(filter(vec![], vec![vec![], vec![t1, t2]]).matches(&log));
+        assert!(!filter(vec![], vec![vec![t2]]).matches(&log));
+        // the log has no third topic
+        assert!(!filter(vec![], vec![vec![], vec![], vec![t1]]).matches(&log));
+
+        let mut bloom = Bloom::default();
+        bloom.accrue_log(&log);
+        assert!(filter(vec![a], vec![vec![t1]]).matches_bloom(&bloom));
+        assert!(!filter(vec![b], vec![]).matches_bloom(&bloom));
+    }
+
+    #[test]
+    fn range_complexity_scales_with_blocks() {
+        assert_eq!(range_complexity(5, Some(Long(10)), Some(Long(19))), 50);
+        assert_eq!(range_complexity(5, Some(Long(10)), None), 5);
+        assert_eq!(range_complexity(5, Some(Long(10)), Some(Long(0))), 5);
+    }
+}
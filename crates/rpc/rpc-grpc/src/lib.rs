@@ -0,0 +1,214 @@
+//! gRPC server for reading the chain.
+//!
+//! Serves blocks, transactions, receipts and logs from the same provider as the `eth_` namespace,
+//! with server streaming methods for ranges of blocks and logs. This is meant for indexers that
+//! prefer a binary streaming interface over polling the JSON-RPC API.
+//!
+//! The services are defined in `proto/reth/v1/chain.proto`, which clients can compile their stubs
+//! from. Blocks, transactions and receipts are served in their consensus encodings, together with
+//! the fields that are required to locate them in the chain.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use hyper::{server::conn::http2, service::service_fn};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use reth_rpc_server_types::constants;
+use service::GrpcService;
+use std::{convert::Infallible, io, net::SocketAddr, sync::Arc};
+use tokio::{net::TcpListener, task::JoinHandle};
+use tracing::debug;
+
+pub mod proto;
+
+mod provider;
+pub use provider::GrpcProvider;
+
+mod service;
+
+/// Configuration of the gRPC server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrpcConfig {
+    /// The maximum number of blocks of a streamed range, `None` for no limit.
+    pub max_block_range: Option<u64>,
+    /// The maximum number of logs of a single stream, `None` for no limit.
+    pub max_logs_per_stream: Option<usize>,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            max_block_range: Some(constants::DEFAULT_MAX_BLOCKS_PER_FILTER),
+            max_logs_per_stream: Some(constants::DEFAULT_MAX_LOGS_PER_STREAM),
+        }
+    }
+}
+
+/// The gRPC server.
+#[derive(Debug)]
+pub struct GrpcServer {
+    service: GrpcService,
+}
+
+impl GrpcServer {
+    /// Creates a new server that serves the chain data of the provider.
+    pub const fn new(provider: Arc<dyn GrpcProvider>, config: GrpcConfig) -> Self {
+        Self { service: GrpcService::new(provider, config) }
+    }
+
+    /// Binds the server to the given address and serves connections in the background.
+    pub async fn start(self, addr: SocketAddr) -> io::Result<GrpcServerHandle> {
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let task = tokio::spawn(serve(listener, self.service));
+        Ok(GrpcServerHandle { local_addr, task })
+    }
+}
+
+/// A handle to a started [`GrpcServer`].
+///
+/// Dropping the handle doesn't stop the server.
+#[derive(Debug)]
+pub struct GrpcServerHandle {
+    local_addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl GrpcServerHandle {
+    /// Returns the address the server is bound to.
+    pub const fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Stops accepting new connections.
+    pub fn stop(self) {
+        self.task.abort();
+    }
+}
+
+/// Accepts connections and serves the gRPC requests of every connection over HTTP/2.
+async fn serve(listener: TcpListener, service: GrpcService) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(err) => {
+                debug!(target: "rpc::grpc", %err, "Failed to accept connection");
+                continue
+            }
+        };
+
+        let service = service.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let service = service.clone();
+                async move { Ok::<_, Infallible>(service.handle(request).await) }
+            });
+            if let Err(err) = http2::Builder::new(TokioExecutor::new())
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                debug!(target: "rpc::grpc", %err, "Connection closed with error");
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use http_body_util::{BodyExt, Full};
+    use prost::{bytes::Bytes, Message};
+    use reth_provider::test_utils::MockEthProvider;
+    use tonic::Code;
+
+    fn service() -> GrpcService {
+        let provider = MockEthProvider::default();
+        let header = Header { number: 1, ..Default::default() };
+        let block = reth_ethereum_primitives::Block { header, body: Default::default() };
+        provider.add_block(block.header.hash_slow(), block);
+        provider.add_receipts(1, Vec::new());
+        GrpcService::new(Arc::new(provider), GrpcConfig::default())
+    }
+
+    /// Calls a unary method and returns the decoded response or the status code of the error.
+    async fn call<Res: Message + Default>(
+        service: &GrpcService,
+        path: &str,
+        request: impl Message,
+    ) -> Result<Res, Code> {
+        let message = request.encode_to_vec();
+        let mut body = vec![0];
+        body.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        body.extend_from_slice(&message);
+        let request = http::Request::builder()
+            .method("POST")
+            .uri(path)
+            .header(http::header::CONTENT_TYPE, "application/grpc")
+            .body(Full::new(Bytes::from(body)))
+            .unwrap();
+
+        let response = service.handle(request).await;
+        let status = response.headers().get("grpc-status").cloned();
+        let body = response.into_body().collect().await.unwrap();
+        let status = status.or_else(|| body.trailers()?.get("grpc-status").cloned());
+        match status.map(|status| Code::from_bytes(status.as_bytes())) {
+            Some(Code::Ok) => Ok(Res::decode(&body.to_bytes()[5..]).unwrap()),
+            Some(code) => Err(code),
+            None => panic!("missing grpc-status"),
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_unary_methods() {
+        let service = service();
+
+        let request = proto::GetReceiptsRequest {
+            block: Some(proto::BlockId { id: Some(proto::block_id::Id::Number(1)) }),
+        };
+        let response: proto::GetReceiptsResponse =
+            call(&service, "/reth.v1.ReceiptService/GetReceipts", request).await.unwrap();
+        assert!(response.receipts.is_empty());
+
+        let request = proto::GetReceiptsRequest {
+            block: Some(proto::BlockId { id: Some(proto::block_id::Id::Number(2)) }),
+        };
+        let response = call::<proto::GetReceiptsResponse>(
+            &service,
+            "/reth.v1.ReceiptService/GetReceipts",
+            request,
+        )
+        .await;
+        assert_eq!(response, Err(Code::NotFound));
+
+        let request = proto::GetTransactionRequest { hash: vec![0; 31].into() };
+        let response = call::<proto::Transaction>(
+            &service,
+            "/reth.v1.TransactionService/GetTransaction",
+            request,
+        )
+        .await;
+        assert_eq!(response, Err(Code::InvalidArgument));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_requests() {
+        let service = service();
+
+        // the range ends after the latest block
+        let request = proto::BlockRangeRequest { from_block: 0, to_block: Some(2) };
+        let response =
+            call::<proto::Block>(&service, "/reth.v1.BlockService/StreamBlocks", request).await;
+        assert_eq!(response, Err(Code::InvalidArgument));
+
+        let request = proto::GetBlockRequest::default();
+        let response =
+            call::<proto::Block>(&service, "/reth.v1.BlockService/GetBlocks", request).await;
+        assert_eq!(response, Err(Code::Unimplemented));
+    }
+}
@@ -0,0 +1,196 @@
+//! The messages of the gRPC API, see `proto/reth/v1/chain.proto`.
+//!
+//! These are written by hand instead of being generated by `prost-build`, so that building the
+//! crate doesn't require `protoc`.
+
+use prost::{bytes::Bytes, Message, Oneof};
+
+/// The number or hash of a block.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct BlockId {
+    /// The number or hash.
+    #[prost(oneof = "block_id::Id", tags = "1, 2")]
+    pub id: Option<block_id::Id>,
+}
+
+/// Nested types of [`BlockId`].
+pub mod block_id {
+    use super::*;
+
+    /// The number or hash of a block.
+    #[derive(Clone, PartialEq, Eq, Oneof)]
+    pub enum Id {
+        /// The number of the block.
+        #[prost(uint64, tag = "1")]
+        Number(u64),
+        /// The hash of the block.
+        #[prost(bytes = "bytes", tag = "2")]
+        Hash(Bytes),
+    }
+}
+
+/// Request of `BlockService/GetBlock`.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct GetBlockRequest {
+    /// The requested block.
+    #[prost(message, optional, tag = "1")]
+    pub block: Option<BlockId>,
+}
+
+/// Request of `BlockService/StreamBlocks`.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct BlockRangeRequest {
+    /// The first block of the range.
+    #[prost(uint64, tag = "1")]
+    pub from_block: u64,
+    /// The last block of the range, inclusive, defaults to the latest block.
+    #[prost(uint64, optional, tag = "2")]
+    pub to_block: Option<u64>,
+}
+
+/// Request of `TransactionService/GetTransaction`.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct GetTransactionRequest {
+    /// The hash of the transaction.
+    #[prost(bytes = "bytes", tag = "1")]
+    pub hash: Bytes,
+}
+
+/// Request of `ReceiptService/GetReceipts`.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct GetReceiptsRequest {
+    /// The block of the receipts.
+    #[prost(message, optional, tag = "1")]
+    pub block: Option<BlockId>,
+}
+
+/// Response of `ReceiptService/GetReceipts`.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct GetReceiptsResponse {
+    /// The receipts, in the order of the transactions of the block.
+    #[prost(message, repeated, tag = "1")]
+    pub receipts: Vec<Receipt>,
+}
+
+/// The filter of a single topic position of a [`StreamLogsRequest`].
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct TopicFilter {
+    /// The accepted topics, any topic is accepted if empty.
+    #[prost(bytes = "bytes", repeated, tag = "1")]
+    pub topics: Vec<Bytes>,
+}
+
+/// Request of `LogService/StreamLogs`.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct StreamLogsRequest {
+    /// The first block of the range.
+    #[prost(uint64, tag = "1")]
+    pub from_block: u64,
+    /// The last block of the range, inclusive, defaults to the latest block.
+    #[prost(uint64, optional, tag = "2")]
+    pub to_block: Option<u64>,
+    /// The accepted addresses, any address is accepted if empty.
+    #[prost(bytes = "bytes", repeated, tag = "3")]
+    pub addresses: Vec<Bytes>,
+    /// The filters of the topics, by position.
+    #[prost(message, repeated, tag = "4")]
+    pub topics: Vec<TopicFilter>,
+}
+
+/// A block with its transactions.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct Block {
+    /// The number of the block.
+    #[prost(uint64, tag = "1")]
+    pub number: u64,
+    /// The hash of the block.
+    #[prost(bytes = "bytes", tag = "2")]
+    pub hash: Bytes,
+    /// The hash of the parent block.
+    #[prost(bytes = "bytes", tag = "3")]
+    pub parent_hash: Bytes,
+    /// The timestamp of the block.
+    #[prost(uint64, tag = "4")]
+    pub timestamp: u64,
+    /// The RLP encoding of the header.
+    #[prost(bytes = "bytes", tag = "5")]
+    pub header: Bytes,
+    /// The transactions of the block.
+    #[prost(message, repeated, tag = "6")]
+    pub transactions: Vec<Transaction>,
+}
+
+/// A transaction with its location in the chain.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct Transaction {
+    /// The hash of the transaction.
+    #[prost(bytes = "bytes", tag = "1")]
+    pub hash: Bytes,
+    /// The number of the block that includes the transaction.
+    #[prost(uint64, tag = "2")]
+    pub block_number: u64,
+    /// The hash of the block that includes the transaction.
+    #[prost(bytes = "bytes", tag = "3")]
+    pub block_hash: Bytes,
+    /// The index of the transaction in the block.
+    #[prost(uint64, tag = "4")]
+    pub index: u64,
+    /// The sender of the transaction.
+    #[prost(bytes = "bytes", tag = "5")]
+    pub sender: Bytes,
+    /// The EIP-2718 encoding of the transaction.
+    #[prost(bytes = "bytes", tag = "6")]
+    pub raw: Bytes,
+}
+
+/// The receipt of a transaction.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct Receipt {
+    /// The hash of the transaction.
+    #[prost(bytes = "bytes", tag = "1")]
+    pub transaction_hash: Bytes,
+    /// The index of the transaction in the block.
+    #[prost(uint64, tag = "2")]
+    pub transaction_index: u64,
+    /// Whether the transaction succeeded.
+    #[prost(bool, tag = "3")]
+    pub success: bool,
+    /// The gas used by the block, including this transaction.
+    #[prost(uint64, tag = "4")]
+    pub cumulative_gas_used: u64,
+    /// The EIP-2718 encoding of the receipt, including its logs bloom.
+    #[prost(bytes = "bytes", tag = "5")]
+    pub raw: Bytes,
+    /// The logs emitted by the transaction.
+    #[prost(message, repeated, tag = "6")]
+    pub logs: Vec<Log>,
+}
+
+/// A log with its location in the chain.
+#[derive(Clone, PartialEq, Eq, Message)]
+pub struct Log {
+    /// The address of the emitting contract.
+    #[prost(bytes = "bytes", tag = "1")]
+    pub address: Bytes,
+    /// The topics of the log.
+    #[prost(bytes = "bytes", repeated, tag = "2")]
+    pub topics: Vec<Bytes>,
+    /// The data of the log.
+    #[prost(bytes = "bytes", tag = "3")]
+    pub data: Bytes,
+    /// The number of the block that includes the log.
+    #[prost(uint64, tag = "4")]
+    pub block_number: u64,
+    /// The hash of the block that includes the log.
+    #[prost(bytes = "bytes", tag = "5")]
+    pub block_hash: Bytes,
+    /// The hash of the emitting transaction.
+    #[prost(bytes = "bytes", tag = "6")]
+    pub transaction_hash: Bytes,
+    /// The index of the emitting transaction in the block.
+    #[prost(uint64, tag = "7")]
+    pub transaction_index: u64,
+    /// The index of the log in the block.
+    #[prost(uint64, tag = "8")]
+    pub log_index: u64,
+}
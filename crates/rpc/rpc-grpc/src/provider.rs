@@ -0,0 +1,139 @@
+//! Access to the chain data that is served by the gRPC API.
+
+use crate::proto;
+use alloy_consensus::{BlockHeader as _, TxReceipt};
+use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
+use alloy_primitives::{BlockNumber, Bloom, Sealable, TxHash};
+use prost::bytes::Bytes;
+use reth_primitives_traits::{
+    Block as _, BlockBody as _, ReceiptWithBloom, SignedTransaction, SignerRecoverable,
+};
+use reth_storage_api::{BlockNumReader, BlockReader, TransactionVariant};
+use reth_storage_errors::provider::ProviderResult;
+
+/// Provides the chain data that is served by the gRPC API.
+///
+/// This is an object safe abstraction over the provider, which converts the node's primitive types
+/// into the messages of the API. It is implemented for all providers that can read blocks.
+pub trait GrpcProvider: Send + Sync + 'static {
+    /// Returns the number of the latest canonical block.
+    fn best_block_number(&self) -> ProviderResult<BlockNumber>;
+
+    /// Returns the block with the given hash or number.
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<proto::Block>>;
+
+    /// Returns the transaction with the given hash.
+    fn transaction(&self, hash: TxHash) -> ProviderResult<Option<proto::Transaction>>;
+
+    /// Returns the receipts of the block with the given hash or number.
+    fn receipts(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<proto::Receipt>>>;
+
+    /// Returns the logs bloom of the block with the given number.
+    fn logs_bloom(&self, number: BlockNumber) -> ProviderResult<Option<Bloom>>;
+}
+
+impl<P> GrpcProvider for P
+where
+    P: BlockReader + 'static,
+{
+    fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        BlockNumReader::best_block_number(self)
+    }
+
+    fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<proto::Block>> {
+        let Some(block) = self.recovered_block(id, TransactionVariant::WithHash)? else {
+            return Ok(None)
+        };
+
+        let hash = block.hash();
+        let number = block.header().number();
+        let transactions = block
+            .transactions_with_sender()
+            .enumerate()
+            .map(|(index, (sender, tx))| proto::Transaction {
+                hash: bytes(tx.tx_hash()),
+                block_number: number,
+                block_hash: bytes(hash),
+                index: index as u64,
+                sender: bytes(sender),
+                raw: tx.encoded_2718().into(),
+            })
+            .collect();
+
+        Ok(Some(proto::Block {
+            number,
+            hash: bytes(hash),
+            parent_hash: bytes(block.header().parent_hash()),
+            timestamp: block.header().timestamp(),
+            header: alloy_rlp::encode(block.header()).into(),
+            transactions,
+        }))
+    }
+
+    fn transaction(&self, hash: TxHash) -> ProviderResult<Option<proto::Transaction>> {
+        Ok(self.transaction_by_hash_with_meta(hash)?.map(|(tx, meta)| proto::Transaction {
+            hash: bytes(meta.tx_hash),
+            block_number: meta.block_number,
+            block_hash: bytes(meta.block_hash),
+            index: meta.index,
+            sender: tx.recover_signer().map(bytes).unwrap_or_default(),
+            raw: tx.encoded_2718().into(),
+        }))
+    }
+
+    fn receipts(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Vec<proto::Receipt>>> {
+        let Some(block) = BlockReader::block(self, id)? else { return Ok(None) };
+        let Some(receipts) = self.receipts_by_block(id)? else { return Ok(None) };
+
+        let block_hash = bytes(block.header().hash_slow());
+        let block_number = block.header().number();
+        let mut log_index = 0;
+        let receipts = receipts
+            .into_iter()
+            .zip(block.body().transactions())
+            .enumerate()
+            .map(|(index, (receipt, tx))| {
+                let transaction_hash = bytes(tx.tx_hash());
+                let logs = receipt
+                    .logs()
+                    .iter()
+                    .map(|log| {
+                        let log = proto::Log {
+                            address: bytes(log.address),
+                            topics: log.topics().iter().map(bytes).collect(),
+                            data: log.data.data.clone().into(),
+                            block_number,
+                            block_hash: block_hash.clone(),
+                            transaction_hash: transaction_hash.clone(),
+                            transaction_index: index as u64,
+                            log_index,
+                        };
+                        log_index += 1;
+                        log
+                    })
+                    .collect();
+
+                let receipt = ReceiptWithBloom::from(receipt);
+                proto::Receipt {
+                    transaction_hash,
+                    transaction_index: index as u64,
+                    success: receipt.receipt.status(),
+                    cumulative_gas_used: receipt.receipt.cumulative_gas_used(),
+                    raw: receipt.encoded_2718().into(),
+                    logs,
+                }
+            })
+            .collect();
+
+        Ok(Some(receipts))
+    }
+
+    fn logs_bloom(&self, number: BlockNumber) -> ProviderResult<Option<Bloom>> {
+        Ok(self.header_by_number(number)?.map(|header| header.logs_bloom()))
+    }
+}
+
+/// Copies a hash or an address into a message field.
+fn bytes(value: impl AsRef<[u8]>) -> Bytes {
+    Bytes::copy_from_slice(value.as_ref())
+}
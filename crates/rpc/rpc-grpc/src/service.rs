@@ -0,0 +1,409 @@
+//! Routing of gRPC requests to the handlers of the services.
+
+use crate::{proto, GrpcConfig, GrpcProvider};
+use alloy_eips::BlockHashOrNumber;
+use alloy_primitives::{Address, Bloom, BloomInput, B256};
+use core::{future::Future, pin::Pin};
+use hyper::body::Body;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{ops::RangeInclusive, sync::Arc};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{
+    body::{empty_body, BoxBody},
+    codec::ProstCodec,
+    codegen::StdError,
+    server::{Grpc, ServerStreamingService, UnaryService},
+    Code, Status,
+};
+
+/// The number of messages of a stream that are buffered before the provider is paused.
+const STREAM_BUFFER: usize = 64;
+
+/// The maximum number of topic positions of a log filter.
+const MAX_TOPICS: usize = 4;
+
+/// The stream of messages returned by the streaming methods.
+type ResponseStream<T> = ReceiverStream<Result<T, Status>>;
+
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// Serves the gRPC services from the provider.
+#[derive(Clone)]
+pub(crate) struct GrpcService {
+    provider: Arc<dyn GrpcProvider>,
+    config: GrpcConfig,
+}
+
+impl GrpcService {
+    /// Creates a new service that serves the chain data of the provider.
+    pub(crate) const fn new(provider: Arc<dyn GrpcProvider>, config: GrpcConfig) -> Self {
+        Self { provider, config }
+    }
+
+    /// Dispatches the request to the handler of its method.
+    pub(crate) async fn handle<B>(&self, request: http::Request<B>) -> http::Response<BoxBody>
+    where
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send,
+    {
+        let this = self.clone();
+        match request.uri().path() {
+            "/reth.v1.BlockService/GetBlock" => {
+                let service = Unary(move |request| this.clone().get_block(request));
+                Grpc::new(ProstCodec::default()).unary(service, request).await
+            }
+            "/reth.v1.BlockService/StreamBlocks" => {
+                let service = ServerStreaming(move |request| this.clone().stream_blocks(request));
+                Grpc::new(ProstCodec::default()).server_streaming(service, request).await
+            }
+            "/reth.v1.TransactionService/GetTransaction" => {
+                let service = Unary(move |request| this.clone().get_transaction(request));
+                Grpc::new(ProstCodec::default()).unary(service, request).await
+            }
+            "/reth.v1.ReceiptService/GetReceipts" => {
+                let service = Unary(move |request| this.clone().get_receipts(request));
+                Grpc::new(ProstCodec::default()).unary(service, request).await
+            }
+            "/reth.v1.LogService/StreamLogs" => {
+                let service = ServerStreaming(move |request| this.clone().stream_logs(request));
+                Grpc::new(ProstCodec::default()).server_streaming(service, request).await
+            }
+            _ => {
+                let mut response = http::Response::new(empty_body());
+                let headers = response.headers_mut();
+                headers.insert("grpc-status", (Code::Unimplemented as i32).into());
+                headers.insert(http::header::CONTENT_TYPE, tonic::metadata::GRPC_CONTENT_TYPE);
+                response
+            }
+        }
+    }
+
+    async fn get_block(self, request: proto::GetBlockRequest) -> Result<proto::Block, Status> {
+        let id = block_id(request.block)?;
+        self.blocking(move |provider| provider.block(id))
+            .await?
+            .ok_or_else(|| Status::not_found("block not found"))
+    }
+
+    async fn stream_blocks(
+        self,
+        request: proto::BlockRangeRequest,
+    ) -> Result<ResponseStream<proto::Block>, Status> {
+        let range = self.block_range(request.from_block, request.to_block).await?;
+        Ok(self.stream(move |provider, tx| {
+            for number in range {
+                let Some(block) = provider.block(number.into())? else {
+                    let _ = tx.blocking_send(Err(block_not_found(number)));
+                    return Ok(())
+                };
+                if tx.blocking_send(Ok(block)).is_err() {
+                    // the client closed the stream
+                    return Ok(())
+                }
+            }
+            Ok(())
+        }))
+    }
+
+    async fn get_transaction(
+        self,
+        request: proto::GetTransactionRequest,
+    ) -> Result<proto::Transaction, Status> {
+        let hash = B256::try_from(request.hash.as_ref())
+            .map_err(|_| Status::invalid_argument("transaction hash must be 32 bytes"))?;
+        self.blocking(move |provider| provider.transaction(hash))
+            .await?
+            .ok_or_else(|| Status::not_found("transaction not found"))
+    }
+
+    async fn get_receipts(
+        self,
+        request: proto::GetReceiptsRequest,
+    ) -> Result<proto::GetReceiptsResponse, Status> {
+        let id = block_id(request.block)?;
+        self.blocking(move |provider| provider.receipts(id))
+            .await?
+            .map(|receipts| proto::GetReceiptsResponse { receipts })
+            .ok_or_else(|| Status::not_found("block not found"))
+    }
+
+    async fn stream_logs(
+        self,
+        request: proto::StreamLogsRequest,
+    ) -> Result<ResponseStream<proto::Log>, Status> {
+        let filter = LogFilter::new(&request)?;
+        let range = self.block_range(request.from_block, request.to_block).await?;
+        let max_logs = self.config.max_logs_per_stream;
+        Ok(self.stream(move |provider, tx| {
+            let mut streamed = 0;
+            for number in range {
+                match provider.logs_bloom(number)? {
+                    Some(bloom) if filter.matches_bloom(&bloom) => {}
+                    Some(_) => continue,
+                    None => {
+                        let _ = tx.blocking_send(Err(block_not_found(number)));
+                        return Ok(())
+                    }
+                }
+                let Some(receipts) = provider.receipts(number.into())? else {
+                    let _ = tx.blocking_send(Err(block_not_found(number)));
+                    return Ok(())
+                };
+
+                for log in receipts.into_iter().flat_map(|receipt| receipt.logs) {
+                    if !filter.matches(&log) {
+                        continue
+                    }
+                    streamed += 1;
+                    if let Some(max_logs) = max_logs.filter(|max_logs| streamed > *max_logs) {
+                        let _ = tx.blocking_send(Err(Status::resource_exhausted(format!(
+                            "stream exceeds the maximum of {max_logs} logs"
+                        ))));
+                        return Ok(())
+                    }
+                    if tx.blocking_send(Ok(log)).is_err() {
+                        // the client closed the stream
+                        return Ok(())
+                    }
+                }
+            }
+            Ok(())
+        }))
+    }
+
+    /// Resolves the requested range of blocks and checks it against the configured limit.
+    async fn block_range(&self, from: u64, to: Option<u64>) -> Result<RangeInclusive<u64>, Status> {
+        let best = self.blocking(|provider| provider.best_block_number()).await?;
+        let to = to.unwrap_or(best);
+        if from > to {
+            return Err(Status::invalid_argument("from_block is greater than to_block"))
+        }
+        if to > best {
+            return Err(Status::invalid_argument(format!(
+                "to_block {to} is greater than the latest block {best}"
+            )))
+        }
+        if let Some(max_block_range) = self.config.max_block_range {
+            if to - from >= max_block_range {
+                return Err(Status::invalid_argument(format!(
+                    "block range exceeds the maximum of {max_block_range} blocks"
+                )))
+            }
+        }
+        Ok(from..=to)
+    }
+
+    /// Runs the provider call on a blocking thread.
+    async fn blocking<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&dyn GrpcProvider) -> ProviderResult<T> + Send + 'static,
+    ) -> Result<T, Status> {
+        let provider = self.provider.clone();
+        tokio::task::spawn_blocking(move || f(&*provider))
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?
+            .map_err(provider_error)
+    }
+
+    /// Spawns the producer of a stream on a blocking thread.
+    ///
+    /// The producer is paused while the stream is full and should stop once sending fails, which
+    /// means that the client closed the stream.
+    fn stream<T: Send + 'static>(
+        &self,
+        f: impl FnOnce(&dyn GrpcProvider, &mpsc::Sender<Result<T, Status>>) -> ProviderResult<()>
+            + Send
+            + 'static,
+    ) -> ResponseStream<T> {
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+        let provider = self.provider.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Err(err) = f(&*provider, &tx) {
+                let _ = tx.blocking_send(Err(provider_error(err)));
+            }
+        });
+        ReceiverStream::new(rx)
+    }
+}
+
+impl core::fmt::Debug for GrpcService {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GrpcService").field("config", &self.config).finish_non_exhaustive()
+    }
+}
+
+/// The filter of a [`proto::StreamLogsRequest`].
+#[derive(Debug)]
+struct LogFilter {
+    addresses: Vec<Address>,
+    topics: Vec<Vec<B256>>,
+}
+
+impl LogFilter {
+    fn new(request: &proto::StreamLogsRequest) -> Result<Self, Status> {
+        if request.topics.len() > MAX_TOPICS {
+            return Err(Status::invalid_argument(format!(
+                "at most {MAX_TOPICS} topic filters are supported"
+            )))
+        }
+
+        let addresses = request
+            .addresses
+            .iter()
+            .map(|address| {
+                Address::try_from(address.as_ref())
+                    .map_err(|_| Status::invalid_argument("addresses must be 20 bytes"))
+            })
+            .collect::<Result<_, _>>()?;
+        let topics = request
+            .topics
+            .iter()
+            .map(|filter| {
+                filter
+                    .topics
+                    .iter()
+                    .map(|topic| {
+                        B256::try_from(topic.as_ref())
+                            .map_err(|_| Status::invalid_argument("topics must be 32 bytes"))
+                    })
+                    .collect()
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { addresses, topics })
+    }
+
+    /// Returns `false` if the bloom rules out that a block contains matching logs.
+    fn matches_bloom(&self, bloom: &Bloom) -> bool {
+        bloom_contains_any(bloom, &self.addresses) &&
+            self.topics.iter().all(|topics| bloom_contains_any(bloom, topics))
+    }
+
+    /// Returns `true` if the log matches the filter.
+    fn matches(&self, log: &proto::Log) -> bool {
+        let address_matches = self.addresses.is_empty() ||
+            self.addresses.iter().any(|address| address.as_slice() == log.address.as_ref());
+        address_matches &&
+            self.topics.iter().enumerate().all(|(position, topics)| {
+                topics.is_empty() ||
+                    log.topics.get(position).is_some_and(|log_topic| {
+                        topics.iter().any(|topic| topic.as_slice() == log_topic.as_ref())
+                    })
+            })
+    }
+}
+
+/// Adapts an async handler to a [`UnaryService`].
+struct Unary<F>(F);
+
+impl<Req, Res, F, Fut> UnaryService<Req> for Unary<F>
+where
+    F: FnMut(Req) -> Fut,
+    Fut: Future<Output = Result<Res, Status>> + Send + 'static,
+    Res: Send + 'static,
+{
+    type Response = Res;
+    type Future = BoxFuture<Result<tonic::Response<Res>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Req>) -> Self::Future {
+        let response = (self.0)(request.into_inner());
+        Box::pin(async move { response.await.map(tonic::Response::new) })
+    }
+}
+
+/// Adapts an async handler to a [`ServerStreamingService`].
+struct ServerStreaming<F>(F);
+
+impl<Req, Res, F, Fut> ServerStreamingService<Req> for ServerStreaming<F>
+where
+    F: FnMut(Req) -> Fut,
+    Fut: Future<Output = Result<ResponseStream<Res>, Status>> + Send + 'static,
+    Res: Send + 'static,
+{
+    type Response = Res;
+    type ResponseStream = ResponseStream<Res>;
+    type Future = BoxFuture<Result<tonic::Response<ResponseStream<Res>>, Status>>;
+
+    fn call(&mut self, request: tonic::Request<Req>) -> Self::Future {
+        let response = (self.0)(request.into_inner());
+        Box::pin(async move { response.await.map(tonic::Response::new) })
+    }
+}
+
+/// Returns `true` if any of the values is in the bloom, or if there are no values.
+fn bloom_contains_any(bloom: &Bloom, values: &[impl AsRef<[u8]>]) -> bool {
+    values.is_empty() ||
+        values.iter().any(|value| bloom.contains_input(BloomInput::Raw(value.as_ref())))
+}
+
+/// Converts the requested block id.
+fn block_id(id: Option<proto::BlockId>) -> Result<BlockHashOrNumber, Status> {
+    match id.and_then(|id| id.id) {
+        Some(proto::block_id::Id::Number(number)) => Ok(number.into()),
+        Some(proto::block_id::Id::Hash(hash)) => B256::try_from(hash.as_ref())
+            .map(Into::into)
+            .map_err(|_| Status::invalid_argument("block hash must be 32 bytes")),
+        None => Err(Status::invalid_argument("missing block id")),
+    }
+}
+
+fn block_not_found(number: u64) -> Status {
+    Status::not_found(format!("block {number} not found"))
+}
+
+fn provider_error(err: ProviderError) -> Status {
+    Status::internal(err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(address: Address, topics: &[B256]) -> proto::Log {
+        proto::Log {
+            address: address.to_vec().into(),
+            topics: topics.iter().map(|topic| topic.to_vec().into()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn log_filter_matches() {
+        let address = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(2);
+        let request = proto::StreamLogsRequest {
+            addresses: vec![address.to_vec().into()],
+            topics: vec![
+                proto::TopicFilter::default(),
+                proto::TopicFilter { topics: vec![topic.to_vec().into()] },
+            ],
+            ..Default::default()
+        };
+        let filter = LogFilter::new(&request).unwrap();
+
+        assert!(filter.matches(&log(address, &[B256::ZERO, topic])));
+        assert!(!filter.matches(&log(address, &[B256::ZERO])));
+        assert!(!filter.matches(&log(address, &[topic, B256::ZERO])));
+        assert!(!filter.matches(&log(Address::ZERO, &[B256::ZERO, topic])));
+
+        let mut bloom = Bloom::default();
+        bloom.accrue(BloomInput::Raw(address.as_slice()));
+        assert!(!filter.matches_bloom(&bloom));
+        bloom.accrue(BloomInput::Raw(topic.as_slice()));
+        assert!(filter.matches_bloom(&bloom));
+    }
+
+    #[test]
+    fn log_filter_rejects_invalid_values() {
+        let request =
+            proto::StreamLogsRequest { addresses: vec![vec![0; 19].into()], ..Default::default() };
+        assert_eq!(LogFilter::new(&request).unwrap_err().code(), Code::InvalidArgument);
+
+        let request = proto::StreamLogsRequest {
+            topics: vec![proto::TopicFilter::default(); MAX_TOPICS + 1],
+            ..Default::default()
+        };
+        assert_eq!(LogFilter::new(&request).unwrap_err().code(), Code::InvalidArgument);
+    }
+}
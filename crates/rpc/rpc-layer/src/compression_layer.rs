@@ -5,23 +5,47 @@ use std::{
     task::{Context, Poll},
 };
 use tower::{Layer, Service};
-use tower_http::compression::{Compression, CompressionLayer as TowerCompressionLayer};
+use tower_http::compression::{
+    predicate::SizeAbove, Compression, CompressionLayer as TowerCompressionLayer,
+};
+
+/// The default minimum size of compressed responses, in bytes.
+const DEFAULT_MIN_SIZE: u16 = 32;
 
 /// This layer is a wrapper around [`tower_http::compression::CompressionLayer`] that integrates
 /// with jsonrpsee's HTTP types. It automatically compresses responses based on the client's
 /// Accept-Encoding header.
+///
+/// The encoding is negotiated from the quality values of the Accept-Encoding header, and responses
+/// smaller than the minimum size are sent uncompressed, see [`CompressionLayer::with_min_size`].
 #[expect(missing_debug_implementations)]
 #[derive(Clone)]
 pub struct CompressionLayer {
-    inner_layer: TowerCompressionLayer,
+    inner_layer: TowerCompressionLayer<SizeAbove>,
 }
 
 impl CompressionLayer {
     /// Creates a new compression layer with zstd, gzip, brotli and deflate enabled.
     pub fn new() -> Self {
-        Self {
-            inner_layer: TowerCompressionLayer::new().gzip(true).br(true).deflate(true).zstd(true),
-        }
+        Self { inner_layer: Self::inner_layer(DEFAULT_MIN_SIZE) }
+    }
+
+    /// Sets the minimum size of responses that are compressed, in bytes.
+    ///
+    /// Responses with a smaller body are sent uncompressed, responses of unknown size are always
+    /// compressed.
+    pub fn with_min_size(self, min_size: u16) -> Self {
+        Self { inner_layer: Self::inner_layer(min_size) }
+    }
+
+    /// Returns the wrapped layer, which compresses responses of at least `min_size` bytes.
+    fn inner_layer(min_size: u16) -> TowerCompressionLayer<SizeAbove> {
+        TowerCompressionLayer::new()
+            .gzip(true)
+            .br(true)
+            .deflate(true)
+            .zstd(true)
+            .compress_when(SizeAbove::new(min_size))
     }
 }
 
@@ -47,7 +71,7 @@ impl<S> Layer<S> for CompressionLayer {
 #[expect(missing_debug_implementations)]
 #[derive(Clone)]
 pub struct CompressionService<S> {
-    compression: Compression<S>,
+    compression: Compression<S, SizeAbove>,
 }
 
 impl<S> Service<HttpRequest> for CompressionService<S>
@@ -145,6 +169,39 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_brotli_compression_negotiation() {
+        let mut service = setup_compression_service();
+        let request = HttpRequest::builder()
+            .header(ACCEPT_ENCODING, "gzip;q=0.5, br;q=1.0")
+            .body(HttpBody::empty())
+            .unwrap();
+
+        let response = service.call(request).await.unwrap();
+        assert_eq!(
+            response.headers().get(CONTENT_ENCODING).unwrap(),
+            "br",
+            "Response should be encoded with the preferred encoding"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_compression_below_min_size() {
+        let uncompressed_len = TEST_DATA.repeat(REPEAT_COUNT).len();
+        let mut service = CompressionLayer::new()
+            .with_min_size(uncompressed_len as u16 + 1)
+            .layer(MockRequestService);
+        let request =
+            HttpRequest::builder().header(ACCEPT_ENCODING, "gzip").body(HttpBody::empty()).unwrap();
+
+        let response = service.call(request).await.unwrap();
+        assert!(
+            response.headers().get(CONTENT_ENCODING).is_none(),
+            "Response should not be compressed below the minimum size"
+        );
+        assert_eq!(get_response_size(response).await, uncompressed_len);
+    }
+
     #[tokio::test]
     async fn test_no_compression_when_not_requested() {
         // Create a service with compression
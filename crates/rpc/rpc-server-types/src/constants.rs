@@ -9,12 +9,38 @@ pub const DEFAULT_WS_RPC_PORT: u16 = 8546;
 /// The default port for the auth server.
 pub const DEFAULT_AUTH_PORT: u16 = 8551;
 
+/// The default port for the gRPC server.
+pub const DEFAULT_GRPC_PORT: u16 = 50051;
+
 /// The default maximum block range allowed to filter
 pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default maximum of logs streamed by a single `eth_getLogsStream` subscription.
+pub const DEFAULT_MAX_LOGS_PER_STREAM: usize = 1_000_000;
+
+/// The default number of messages buffered per `eth_subscribe` subscription for a slow consumer.
+pub const DEFAULT_SUBSCRIPTION_BUFFER_SIZE: usize = 1024;
+
+/// The default maximum complexity of a GraphQL query.
+///
+/// Every queried field adds one, fields that are resolved for a range of blocks are counted once
+/// per block.
+pub const DEFAULT_GRAPHQL_MAX_COMPLEXITY: usize = 10_000;
+
+/// The default minimum size of HTTP responses that are compressed, in bytes.
+///
+/// Compressing smaller responses costs more CPU than it saves bandwidth.
+pub const DEFAULT_HTTP_COMPRESSION_MIN_SIZE: u16 = 1024;
+
+/// The default maximum number of responses in the RPC response cache.
+pub const DEFAULT_RPC_RESPONSE_CACHE_SIZE: u32 = 10_000;
+
+/// The default maximum nesting depth of a GraphQL query.
+pub const DEFAULT_GRAPHQL_MAX_DEPTH: usize = 16;
+
 /// The default maximum number of blocks for `trace_filter` requests.
 pub const DEFAULT_MAX_TRACE_FILTER_BLOCKS: u64 = 100;
 
@@ -57,6 +83,34 @@ pub const DEFAULT_ETH_PROOF_WINDOW: u64 = 0;
 /// The default eth tx fee cap is 1 ETH
 pub const DEFAULT_TX_FEE_CAP_WEI: u128 = 1_000_000_000_000_000_000u128;
 
+/// The default HTTP header that carries the API key of a request for RPC usage accounting.
+pub const DEFAULT_RPC_USAGE_API_KEY_HEADER: &str = "x-api-key";
+
+/// The default interval at which a summary of the heaviest RPC consumers is logged, in seconds.
+pub const DEFAULT_RPC_USAGE_LOG_INTERVAL_SECS: u64 = 300;
+
+/// The default number of retries of a bundle that couldn't be forwarded to a builder.
+pub const DEFAULT_BUNDLE_FORWARD_RETRIES: u32 = 3;
+
+/// The default number of retries of a raw transaction that couldn't be forwarded upstream.
+pub const DEFAULT_TX_FORWARD_RETRIES: u32 = 3;
+
+/// The default number of blocks whose state fetched from an archive node is cached.
+pub const DEFAULT_ARCHIVE_STATE_CACHED_BLOCKS: usize = 32;
+
+/// The default interval at which `WebSocket` connections are pinged, in seconds.
+pub const DEFAULT_WS_PING_INTERVAL_SECS: u64 = 30;
+
+/// The default time after which an unresponsive `WebSocket` connection is considered inactive, in
+/// seconds.
+pub const DEFAULT_WS_IDLE_TIMEOUT_SECS: u64 = 60;
+
+/// The default number of missed pings after which an inactive `WebSocket` connection is closed.
+pub const DEFAULT_WS_MAX_MISSED_PINGS: usize = 3;
+
+/// The maximum number of blocks that can be requested with `reth_getBlockReceiptsByRange`.
+pub const MAX_BLOCK_RECEIPTS_RANGE: u64 = 1_000;
+
 /// Maximum eth historical proof window. Equivalent to roughly 6 months of data on a 12
 /// second block time, and a month on a 2 second block time.
 pub const MAX_ETH_PROOF_WINDOW: u64 = 28 * 24 * 60 * 60 / 2;
@@ -127,4 +181,7 @@ pub mod cache {
 
     /// Default number of concurrent database requests.
     pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
+
+    /// Default cache size for the state snapshot cache: 4 blocks.
+    pub const DEFAULT_STATE_SNAPSHOT_CACHE_MAX_LEN: u32 = 4;
 }
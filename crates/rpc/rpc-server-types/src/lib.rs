@@ -10,9 +10,22 @@
 
 /// Common RPC constants.
 pub mod constants;
+pub mod rate_limit;
 pub mod result;
+pub mod roles;
 
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
+pub use rate_limit::{MethodQuota, MethodRateLimit, RateLimitQuota, RpcRateLimitConfig};
+pub use roles::RpcRolesConfig;
 
 pub use result::ToRpcResult;
+
+/// Returns true if the pattern matches the method, the pattern is either a method name or a prefix
+/// of method names followed by `*`.
+pub(crate) fn matches_method(pattern: &str, method: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => method.starts_with(prefix),
+        None => pattern == method,
+    }
+}
@@ -0,0 +1,186 @@
+//! Configuration of the rate limits of RPC methods.
+
+use serde::{Deserialize, Serialize};
+use std::{fmt, net::IpAddr, str::FromStr};
+
+/// Rate limits of RPC methods, enforced with token buckets.
+///
+/// Every rule can limit a method globally, shared by all clients, and per client IP. A TOML file
+/// with this configuration can be passed with `--rpc.rate-limit-config`:
+///
+/// ```toml
+/// client_ip_header = "x-forwarded-for"
+/// trusted_proxies = ["127.0.0.1"]
+///
+/// [[methods]]
+/// method = "debug_*"
+/// global = { requests_per_second = 10, burst = 20 }
+/// per_ip = { requests_per_second = 1, burst = 5 }
+///
+/// [[methods]]
+/// method = "eth_getLogs"
+/// per_ip = { requests_per_second = 20 }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcRateLimitConfig {
+    /// The HTTP header that carries the IP address of the client, e.g. `x-forwarded-for` if the
+    /// server is behind a reverse proxy.
+    ///
+    /// The header is only honoured for requests of the [`Self::trusted_proxies`], clients are
+    /// identified by the peer address of their connection otherwise.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_ip_header: Option<String>,
+    /// The IP addresses of the reverse proxies whose client IP header is trusted.
+    ///
+    /// Every proxy appends the address it received a request from to the header, so the client
+    /// IP is the rightmost address of the header that isn't a trusted proxy. The addresses left of
+    /// it can be forged by the client.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub trusted_proxies: Vec<IpAddr>,
+    /// The rate limits of methods. The first rule that matches a method applies to it.
+    pub methods: Vec<MethodRateLimit>,
+}
+
+impl RpcRateLimitConfig {
+    /// Returns true if no method is rate limited.
+    pub fn is_empty(&self) -> bool {
+        self.methods.is_empty()
+    }
+
+    /// Returns the first rule that matches the method, if any.
+    pub fn rule(&self, method: &str) -> Option<(usize, &MethodRateLimit)> {
+        self.methods.iter().enumerate().find(|(_, rule)| rule.matches(method))
+    }
+}
+
+/// The rate limits of a method or a group of methods.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MethodRateLimit {
+    /// The name of the method, or a prefix of method names followed by `*`, e.g. `trace_*`.
+    pub method: String,
+    /// The quota shared by all clients.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub global: Option<RateLimitQuota>,
+    /// The quota of every client IP.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub per_ip: Option<RateLimitQuota>,
+}
+
+impl MethodRateLimit {
+    /// Returns true if the rule applies to the method.
+    pub fn matches(&self, method: &str) -> bool {
+        crate::matches_method(&self.method, method)
+    }
+}
+
+/// The quota of a token bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitQuota {
+    /// The sustained number of requests per second.
+    pub requests_per_second: u32,
+    /// The number of requests that can be made at once. Defaults to `requests_per_second`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+}
+
+impl RateLimitQuota {
+    /// Creates a new quota with a burst of `requests_per_second`.
+    pub const fn per_second(requests_per_second: u32) -> Self {
+        Self { requests_per_second, burst: None }
+    }
+
+    /// Sets the number of requests that can be made at once.
+    pub const fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = Some(burst);
+        self
+    }
+
+    /// Returns the number of requests that can be made at once.
+    pub fn burst(&self) -> u32 {
+        self.burst.unwrap_or(self.requests_per_second).max(1)
+    }
+}
+
+/// A rate limit of a method given on the command line, as `METHOD=RATE[:BURST]`.
+///
+/// `METHOD` is a method name or a prefix followed by `*`, `RATE` the number of requests per
+/// second and `BURST` the number of requests that can be made at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodQuota {
+    /// The method or method prefix.
+    pub method: String,
+    /// The quota of the method.
+    pub quota: RateLimitQuota,
+}
+
+impl FromStr for MethodQuota {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid rate limit {s}, expected METHOD=RATE[:BURST]");
+        let (method, quota) = s.split_once('=').ok_or_else(invalid)?;
+        if method.is_empty() {
+            return Err(invalid())
+        }
+        let (rate, burst) = match quota.split_once(':') {
+            Some((rate, burst)) => (rate, Some(burst.parse().map_err(|_| invalid())?)),
+            None => (quota, None),
+        };
+        let requests_per_second = rate.parse().map_err(|_| invalid())?;
+        Ok(Self {
+            method: method.to_string(),
+            quota: RateLimitQuota { requests_per_second, burst },
+        })
+    }
+}
+
+impl fmt::Display for MethodQuota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.method, self.quota.requests_per_second)?;
+        if let Some(burst) = self.quota.burst {
+            write!(f, ":{burst}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_method_quota() {
+        let quota: MethodQuota = "debug_*=10:20".parse().unwrap();
+        assert_eq!(quota.method, "debug_*");
+        assert_eq!(quota.quota, RateLimitQuota::per_second(10).with_burst(20));
+        assert_eq!(quota.to_string(), "debug_*=10:20");
+
+        let quota: MethodQuota = "eth_getLogs=5".parse().unwrap();
+        assert_eq!(quota.quota.burst(), 5);
+
+        assert!("eth_getLogs".parse::<MethodQuota>().is_err());
+        assert!("=5".parse::<MethodQuota>().is_err());
+        assert!("eth_getLogs=fast".parse::<MethodQuota>().is_err());
+    }
+
+    #[test]
+    fn first_matching_rule() {
+        let rule = |method: &str| MethodRateLimit {
+            method: method.to_string(),
+            global: Some(RateLimitQuota::per_second(1)),
+            per_ip: None,
+        };
+        let config = RpcRateLimitConfig {
+            methods: vec![rule("debug_traceCall"), rule("debug_*")],
+            ..Default::default()
+        };
+
+        assert_eq!(config.rule("debug_traceCall").unwrap().0, 0);
+        assert_eq!(config.rule("debug_traceTransaction").unwrap().0, 1);
+        assert!(config.rule("eth_call").is_none());
+        assert!(config.rule("debug").is_none());
+    }
+}
@@ -0,0 +1,111 @@
+//! Configuration of the role based method allowlists of JWT authenticated clients.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The default claim of a JWT that holds the roles of the client.
+pub const DEFAULT_ROLE_CLAIM: &str = "roles";
+
+/// Role based method allowlists of the clients of the regular RPC servers, which are authenticated
+/// with a JWT signed with `--rpc.jwtsecret`.
+///
+/// Every role allows a list of methods, and a client may call the methods that are allowed by any
+/// of the roles of its token. A TOML file with this configuration can be passed with
+/// `--rpc.roles-config`:
+///
+/// ```toml
+/// # the claim that holds a role or an array of roles
+/// role_claim = "roles"
+/// # the roles of tokens without the claim
+/// default_roles = ["read"]
+///
+/// [roles]
+/// read = ["eth_*", "net_*", "web3_*"]
+/// trace = ["trace_*", "debug_trace*"]
+/// admin = ["*"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcRolesConfig {
+    /// The claim of the JWT that holds the roles of the client, either a single role or an array
+    /// of roles.
+    pub role_claim: String,
+    /// The roles of tokens without the role claim. Such tokens can't call any method if empty.
+    pub default_roles: Vec<String>,
+    /// The allowed methods of every role, by role name. A method is either a method name or a
+    /// prefix of method names followed by `*`, e.g. `trace_*`.
+    pub roles: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for RpcRolesConfig {
+    fn default() -> Self {
+        Self {
+            role_claim: DEFAULT_ROLE_CLAIM.to_string(),
+            default_roles: Vec::new(),
+            roles: BTreeMap::new(),
+        }
+    }
+}
+
+impl RpcRolesConfig {
+    /// Returns true if any of the roles allows the method.
+    ///
+    /// Roles that aren't defined don't allow any method.
+    pub fn allows<'a>(&self, roles: impl IntoIterator<Item = &'a str>, method: &str) -> bool {
+        roles
+            .into_iter()
+            .filter_map(|role| self.roles.get(role))
+            .any(|methods| methods.iter().any(|pattern| crate::matches_method(pattern, method)))
+    }
+
+    /// Checks that the default roles are defined.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.role_claim.is_empty() {
+            return Err("the role claim is empty".to_string())
+        }
+        if let Some(role) = self.default_roles.iter().find(|role| !self.roles.contains_key(*role)) {
+            return Err(format!("the default role {role} is not defined"))
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> RpcRolesConfig {
+        RpcRolesConfig {
+            default_roles: vec!["read".to_string()],
+            roles: BTreeMap::from([
+                ("read".to_string(), vec!["eth_*".to_string(), "net_version".to_string()]),
+                ("trace".to_string(), vec!["trace_*".to_string()]),
+                ("admin".to_string(), vec!["*".to_string()]),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn allows_methods_of_any_role() {
+        let config = config();
+
+        assert!(config.allows(["read"], "eth_call"));
+        assert!(config.allows(["read"], "net_version"));
+        assert!(!config.allows(["read"], "net_peerCount"));
+        assert!(!config.allows(["read"], "trace_block"));
+        assert!(config.allows(["read", "trace"], "trace_block"));
+        assert!(config.allows(["admin"], "admin_addPeer"));
+        assert!(!config.allows(["unknown"], "eth_call"));
+        assert!(!config.allows([], "eth_call"));
+    }
+
+    #[test]
+    fn rejects_undefined_default_roles() {
+        let mut config = config();
+        assert_eq!(config.validate(), Ok(()));
+
+        config.default_roles.push("write".to_string());
+        assert!(config.validate().is_err());
+    }
+}
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
 use alloy_genesis::ChainConfig;
 use alloy_rpc_types_admin::{
@@ -6,12 +6,19 @@ use alloy_rpc_types_admin::{
     Ports, ProtocolInfo,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage};
 use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, ForkCondition};
-use reth_network_api::{NetworkInfo, Peers};
+use reth_network_api::{
+    events::{NetworkPeersEvents, PeerEvent},
+    NetworkInfo, Peers,
+};
 use reth_network_peers::{id2pk, AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
-use reth_rpc_api::AdminApiServer;
+use reth_rpc_api::{
+    AdminApiServer, AdminDiscoveryPorts, AdminNodeInfo, AdminPeerEvent, AdminPeerEventKind,
+    AdminPeerInfo,
+};
 use reth_rpc_server_types::ToRpcResult;
 
 /// `admin` API implementation.
@@ -34,7 +41,7 @@ impl<N, ChainSpec> AdminApi<N, ChainSpec> {
 #[async_trait]
 impl<N, ChainSpec> AdminApiServer for AdminApi<N, ChainSpec>
 where
-    N: NetworkInfo + Peers + 'static,
+    N: NetworkInfo + Peers + NetworkPeersEvents + 'static,
     ChainSpec: EthChainSpec + EthereumHardforks + Send + Sync + 'static,
 {
     /// Handler for `admin_addPeer`
@@ -65,13 +72,14 @@ where
     }
 
     /// Handler for `admin_peers`
-    async fn peers(&self) -> RpcResult<Vec<PeerInfo>> {
+    async fn peers(&self, verbose: Option<bool>) -> RpcResult<Vec<AdminPeerInfo>> {
+        let verbose = verbose.unwrap_or_default();
         let peers = self.network.get_all_peers().await.to_rpc_result()?;
         let mut infos = Vec::with_capacity(peers.len());
 
         for peer in peers {
             if let Ok(pk) = id2pk(peer.remote_id) {
-                infos.push(PeerInfo {
+                let info = PeerInfo {
                     id: pk.to_string(),
                     name: peer.client_version.to_string(),
                     enode: peer.enode,
@@ -96,6 +104,11 @@ where
                         snap: None,
                         other: Default::default(),
                     },
+                };
+                infos.push(AdminPeerInfo {
+                    info,
+                    head_status: peer.head_status,
+                    backoff: verbose.then_some(peer.backoff),
                 })
             }
         }
@@ -104,7 +117,7 @@ where
     }
 
     /// Handler for `admin_nodeInfo`
-    async fn node_info(&self) -> RpcResult<NodeInfo> {
+    async fn node_info(&self) -> RpcResult<AdminNodeInfo> {
         let enode = self.network.local_node_record();
         let status = self.network.network_status().await.to_rpc_result()?;
         let mut config = ChainConfig {
@@ -158,7 +171,7 @@ where
             prague_time => Prague,
         ]);
 
-        Ok(NodeInfo {
+        let info = NodeInfo {
             id: id2pk(enode.id)
                 .map(|pk| pk.to_string())
                 .unwrap_or_else(|_| alloy_primitives::hex::encode(enode.id.as_slice())),
@@ -179,15 +192,69 @@ where
                 }),
                 snap: None,
             },
+        };
+
+        let mut protocol_versions = BTreeMap::<String, Vec<u64>>::new();
+        for capability in &status.capabilities {
+            protocol_versions
+                .entry(capability.name.to_string())
+                .or_default()
+                .push(capability.version as u64);
+        }
+
+        let ports = self.network.discovery_ports();
+        Ok(AdminNodeInfo {
+            info,
+            protocol_versions,
+            discovery_ports: AdminDiscoveryPorts { discv4: ports.discv4, discv5: ports.discv5 },
         })
     }
 
     /// Handler for `admin_peerEvents`
     async fn subscribe_peer_events(
         &self,
-        _pending: jsonrpsee::PendingSubscriptionSink,
+        pending: PendingSubscriptionSink,
     ) -> jsonrpsee::core::SubscriptionResult {
-        Err("admin_peerEvents is not implemented yet".into())
+        let sink = pending.accept().await?;
+        let mut events = self
+            .network
+            .peer_events()
+            .filter_map(|event| futures::future::ready(admin_peer_event(event)));
+
+        loop {
+            tokio::select! {
+                _ = sink.closed() => break,
+                event = events.next() => {
+                    let Some(event) = event else { break };
+                    let msg =
+                        SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &event)?;
+                    if sink.send(msg).await.is_err() {
+                        break
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts a [`PeerEvent`] into the event served by `admin_peerEvents`, if it concerns a session.
+fn admin_peer_event(event: PeerEvent) -> Option<AdminPeerEvent> {
+    match event {
+        PeerEvent::SessionEstablished(info) => Some(AdminPeerEvent {
+            kind: AdminPeerEventKind::Add,
+            peer: info.peer_id,
+            error: None,
+            remote: Some(info.remote_addr),
+        }),
+        PeerEvent::SessionClosed { peer_id, reason } => Some(AdminPeerEvent {
+            kind: AdminPeerEventKind::Drop,
+            peer: peer_id,
+            error: reason.map(|reason| reason.to_string()),
+            remote: None,
+        }),
+        PeerEvent::PeerAdded(_) | PeerEvent::PeerRemoved(_) => None,
     }
 }
 
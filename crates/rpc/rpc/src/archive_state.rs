@@ -0,0 +1,614 @@
+//! Fetching of historical state from an archive node, for tracing calls on top of state that has
+//! been pruned locally.
+
+use crate::eth::bundle_forwarder::JsonRpcRequest;
+use alloy_primitives::{Address, BlockNumber, Bytes, StorageKey, StorageValue, B256, U256, U64};
+use parking_lot::{Mutex, RwLock};
+use reth_errors::{ProviderError, ProviderResult};
+use reth_metrics::{
+    metrics,
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_primitives_traits::{Account, Bytecode};
+use reth_rpc_server_types::constants::DEFAULT_ARCHIVE_STATE_CACHED_BLOCKS;
+use reth_storage_api::{
+    AccountReader, BlockHashReader, BytecodeReader, HashedPostStateProvider, StateProofProvider,
+    StateProvider, StateProviderBox, StateRootProvider, StorageRootProvider,
+};
+use reth_trie_common::{
+    updates::TrieUpdates, AccountProof, HashedPostState, HashedStorage, MultiProof,
+    MultiProofTargets, StorageMultiProof, StorageProof, TrieInput,
+};
+use revm::database::BundleState;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{json, Value};
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::runtime::Handle;
+use tracing::debug;
+
+/// The default timeout of a request to the archive node.
+pub const DEFAULT_ARCHIVE_STATE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returns true if reading from the state fails because the state has been pruned.
+pub(crate) fn is_state_pruned(state: &dyn StateProvider) -> bool {
+    matches!(state.basic_account(&Address::ZERO), Err(ProviderError::StateAtBlockPruned(_)))
+}
+
+/// Configuration of an [`ArchiveStateFetcher`].
+#[derive(Debug, Clone)]
+pub struct ArchiveStateConfig {
+    /// The URL of the archive node the state is fetched from.
+    pub endpoint: String,
+    /// The number of blocks whose fetched state is cached.
+    pub max_cached_blocks: usize,
+    /// The timeout of a request to the archive node.
+    pub timeout: Duration,
+}
+
+impl ArchiveStateConfig {
+    /// Creates a new configuration that fetches the state from the given endpoint.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            max_cached_blocks: DEFAULT_ARCHIVE_STATE_CACHED_BLOCKS,
+            timeout: DEFAULT_ARCHIVE_STATE_TIMEOUT,
+        }
+    }
+
+    /// Sets the number of blocks whose fetched state is cached.
+    pub const fn with_max_cached_blocks(mut self, max_cached_blocks: usize) -> Self {
+        self.max_cached_blocks = max_cached_blocks;
+        self
+    }
+
+    /// Sets the timeout of a request to the archive node.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Errors that occur when fetching state from the archive node.
+#[derive(Debug, thiserror::Error)]
+pub enum ArchiveStateError {
+    /// The archive node couldn't be reached.
+    #[error("failed to fetch state from archive node: {0}")]
+    Unavailable(String),
+    /// The archive node returned an error.
+    #[error("archive node returned error {code}: {message}")]
+    Rpc {
+        /// The code of the error.
+        code: i64,
+        /// The message of the error.
+        message: String,
+    },
+    /// The response of the archive node couldn't be decoded.
+    #[error("invalid archive node response: {0}")]
+    InvalidResponse(String),
+}
+
+/// Fetches the state that calls read from an archive node, for blocks whose state has been pruned
+/// locally.
+///
+/// Only the accounts and storage slots that are actually read are fetched, on demand. The fetched
+/// state of the most recently traced blocks is cached, so repeated calls against the same block
+/// don't hit the archive node again.
+#[derive(Clone)]
+pub struct ArchiveStateFetcher {
+    inner: Arc<ArchiveStateInner>,
+}
+
+impl ArchiveStateFetcher {
+    /// Creates a new fetcher with the given configuration.
+    pub fn new(config: ArchiveStateConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self {
+            inner: Arc::new(ArchiveStateInner {
+                cache: Mutex::new(ArchiveStateCache::new(config.max_cached_blocks)),
+                config,
+                client,
+                metrics: ArchiveStateMetrics::default(),
+            }),
+        }
+    }
+
+    /// Returns the URL of the archive node.
+    pub fn endpoint(&self) -> &str {
+        &self.inner.config.endpoint
+    }
+
+    /// Returns a [`StateProvider`] that reads the state at the given block from the archive node.
+    ///
+    /// Block hashes and hashed post states are still served by the local state provider, which
+    /// don't depend on the pruned history.
+    ///
+    /// The provider blocks on the requests to the archive node, so it must only be used from a
+    /// blocking task of the tokio runtime.
+    pub fn state_provider(
+        &self,
+        block_hash: B256,
+        local: StateProviderBox,
+    ) -> ArchiveStateProvider {
+        ArchiveStateProvider {
+            fetcher: self.clone(),
+            block_hash,
+            state: self.inner.cache.lock().block(block_hash),
+            local,
+            handle: Handle::current(),
+        }
+    }
+}
+
+impl std::fmt::Debug for ArchiveStateFetcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArchiveStateFetcher")
+            .field("config", &self.inner.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Container type for `ArchiveStateFetcher` internals
+struct ArchiveStateInner {
+    config: ArchiveStateConfig,
+    client: reqwest::Client,
+    cache: Mutex<ArchiveStateCache>,
+    metrics: ArchiveStateMetrics,
+}
+
+impl ArchiveStateInner {
+    /// Fetches the account and its code at the block.
+    async fn fetch_account(
+        &self,
+        block_hash: B256,
+        address: Address,
+    ) -> Result<(Option<Account>, Option<Bytecode>), ArchiveStateError> {
+        let block = json!({ "blockHash": block_hash });
+        let requests = [
+            JsonRpcRequest::new("eth_getBalance", json!([address, block])).with_id(0),
+            JsonRpcRequest::new("eth_getTransactionCount", json!([address, block])).with_id(1),
+            JsonRpcRequest::new("eth_getCode", json!([address, block])).with_id(2),
+        ];
+        let [balance, nonce, code] = <[Value; 3]>::try_from(self.call(&requests).await?)
+            .map_err(|_| ArchiveStateError::InvalidResponse("incomplete batch".to_string()))?;
+        let balance: U256 = decode(balance)?;
+        let nonce: U64 = decode(nonce)?;
+        let code: Bytes = decode(code)?;
+        Ok(into_account(balance, nonce.to(), code))
+    }
+
+    /// Fetches the value of the storage slot at the block.
+    async fn fetch_storage(
+        &self,
+        block_hash: B256,
+        address: Address,
+        key: StorageKey,
+    ) -> Result<StorageValue, ArchiveStateError> {
+        let block = json!({ "blockHash": block_hash });
+        let request = JsonRpcRequest::new("eth_getStorageAt", json!([address, key, block]));
+        let [value] = <[Value; 1]>::try_from(self.call(&[request]).await?)
+            .map_err(|_| ArchiveStateError::InvalidResponse("incomplete batch".to_string()))?;
+        decode(value)
+    }
+
+    /// Sends the requests as a batch and returns their results, ordered by request id.
+    async fn call(
+        &self,
+        requests: &[JsonRpcRequest<Value>],
+    ) -> Result<Vec<Value>, ArchiveStateError> {
+        let start = Instant::now();
+        let result = self.post(requests).await;
+        self.metrics.requests.increment(1);
+        self.metrics.request_latency.record(start.elapsed().as_secs_f64());
+        if let Err(err) = &result {
+            self.metrics.failed_requests.increment(1);
+            debug!(
+                target: "rpc::debug::archive_state",
+                url = %self.config.endpoint,
+                %err,
+                "Failed to fetch state from archive node"
+            );
+        }
+        result
+    }
+
+    /// Sends the batch to the archive node and parses the response.
+    async fn post(
+        &self,
+        requests: &[JsonRpcRequest<Value>],
+    ) -> Result<Vec<Value>, ArchiveStateError> {
+        let body = serde_json::to_vec(requests)
+            .map_err(|err| ArchiveStateError::Unavailable(err.to_string()))?;
+        let response = self
+            .client
+            .post(&self.config.endpoint)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await
+            .map_err(|err| ArchiveStateError::Unavailable(err.to_string()))?;
+        let http_status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|err| ArchiveStateError::Unavailable(err.to_string()))?;
+
+        match parse_batch_response(&body, requests.len()) {
+            Err(ArchiveStateError::InvalidResponse(_)) if !http_status.is_success() => {
+                Err(ArchiveStateError::Unavailable(format!("unexpected status {http_status}")))
+            }
+            result => result,
+        }
+    }
+}
+
+/// Converts the fetched fields of an account, returning `None` for empty accounts, which the
+/// JSON-RPC API doesn't distinguish from accounts that don't exist.
+fn into_account(balance: U256, nonce: u64, code: Bytes) -> (Option<Account>, Option<Bytecode>) {
+    if balance.is_zero() && nonce == 0 && code.is_empty() {
+        return (None, None)
+    }
+    let code = (!code.is_empty()).then(|| Bytecode::new_raw(code));
+    let account =
+        Account { nonce, balance, bytecode_hash: code.as_ref().map(|code| code.hash_slow()) };
+    (Some(account), code)
+}
+
+/// Parses the response to a batch of `len` requests with the ids `0..len`.
+fn parse_batch_response(body: &[u8], len: usize) -> Result<Vec<Value>, ArchiveStateError> {
+    let responses: Vec<JsonRpcResponse> = serde_json::from_slice(body)
+        .map_err(|err| ArchiveStateError::InvalidResponse(err.to_string()))?;
+    let mut results = vec![None; len];
+    for response in responses {
+        if let Some(error) = response.error {
+            return Err(ArchiveStateError::Rpc { code: error.code, message: error.message })
+        }
+        let id = response.id;
+        let slot = usize::try_from(id)
+            .ok()
+            .and_then(|id| results.get_mut(id))
+            .ok_or_else(|| ArchiveStateError::InvalidResponse(format!("unexpected id {id}")))?;
+        *slot = response.result;
+    }
+    results
+        .into_iter()
+        .map(|result| {
+            result.ok_or_else(|| {
+                ArchiveStateError::InvalidResponse("response without result".to_string())
+            })
+        })
+        .collect()
+}
+
+/// Decodes the result of a request.
+fn decode<T: DeserializeOwned>(value: Value) -> Result<T, ArchiveStateError> {
+    serde_json::from_value(value).map_err(|err| ArchiveStateError::InvalidResponse(err.to_string()))
+}
+
+/// A response of the archive node to one request of a batch.
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    id: u64,
+    #[serde(default)]
+    result: Option<Value>,
+    #[serde(default)]
+    error: Option<JsonRpcError>,
+}
+
+/// The error object of a JSON-RPC response.
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i64,
+    message: String,
+}
+
+/// The fetched state of the most recently traced blocks.
+#[derive(Debug)]
+struct ArchiveStateCache {
+    blocks: HashMap<B256, Arc<CachedBlockState>>,
+    /// The cached blocks, from least to most recently used.
+    order: VecDeque<B256>,
+    capacity: usize,
+}
+
+impl ArchiveStateCache {
+    fn new(capacity: usize) -> Self {
+        Self { blocks: HashMap::new(), order: VecDeque::new(), capacity: capacity.max(1) }
+    }
+
+    /// Returns the cached state of the block, evicting the least recently used block if a new
+    /// block exceeds the capacity.
+    fn block(&mut self, block_hash: B256) -> Arc<CachedBlockState> {
+        if let Some(state) = self.blocks.get(&block_hash) {
+            self.order.retain(|hash| *hash != block_hash);
+            self.order.push_back(block_hash);
+            return state.clone()
+        }
+
+        let state = Arc::<CachedBlockState>::default();
+        self.blocks.insert(block_hash, state.clone());
+        self.order.push_back(block_hash);
+        while self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.blocks.remove(&evicted);
+            }
+        }
+        state
+    }
+}
+
+/// The state of a block that has been fetched from the archive node.
+#[derive(Debug, Default)]
+struct CachedBlockState {
+    accounts: RwLock<HashMap<Address, Option<Account>>>,
+    storage: RwLock<HashMap<(Address, StorageKey), StorageValue>>,
+    bytecodes: RwLock<HashMap<B256, Bytecode>>,
+}
+
+/// A [`StateProvider`] that reads the state at a block from an archive node.
+///
+/// See [`ArchiveStateFetcher::state_provider`].
+pub struct ArchiveStateProvider {
+    fetcher: ArchiveStateFetcher,
+    block_hash: B256,
+    state: Arc<CachedBlockState>,
+    local: StateProviderBox,
+    handle: Handle,
+}
+
+impl ArchiveStateProvider {
+    /// Runs the request to the archive node on the runtime and blocks until it completes.
+    fn block_on<T, F>(&self, f: impl FnOnce(Arc<ArchiveStateInner>) -> F) -> ProviderResult<T>
+    where
+        F: Future<Output = Result<T, ArchiveStateError>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let task = self.handle.spawn(f(self.fetcher.inner.clone()));
+        futures::executor::block_on(task)
+            .map_err(ProviderError::other)?
+            .map_err(ProviderError::other)
+    }
+}
+
+impl std::fmt::Debug for ArchiveStateProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArchiveStateProvider")
+            .field("endpoint", &self.fetcher.endpoint())
+            .field("block_hash", &self.block_hash)
+            .finish_non_exhaustive()
+    }
+}
+
+impl StateProvider for ArchiveStateProvider {
+    fn storage(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(value) = self.state.storage.read().get(&(address, storage_key)) {
+            self.fetcher.inner.metrics.cache_hits.increment(1);
+            return Ok(Some(*value).filter(|value| !value.is_zero()))
+        }
+
+        let block_hash = self.block_hash;
+        let value = self.block_on(|inner| async move {
+            inner.fetch_storage(block_hash, address, storage_key).await
+        })?;
+        self.state.storage.write().insert((address, storage_key), value);
+        Ok(Some(value).filter(|value| !value.is_zero()))
+    }
+}
+
+impl AccountReader for ArchiveStateProvider {
+    fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
+        if let Some(account) = self.state.accounts.read().get(address) {
+            self.fetcher.inner.metrics.cache_hits.increment(1);
+            return Ok(*account)
+        }
+
+        let (block_hash, address) = (self.block_hash, *address);
+        let (account, code) =
+            self.block_on(|inner| async move { inner.fetch_account(block_hash, address).await })?;
+        if let Some(code) = code {
+            self.state.bytecodes.write().insert(code.hash_slow(), code);
+        }
+        self.state.accounts.write().insert(address, account);
+        Ok(account)
+    }
+}
+
+impl BytecodeReader for ArchiveStateProvider {
+    fn bytecode_by_hash(&self, code_hash: &B256) -> ProviderResult<Option<Bytecode>> {
+        // the code of every fetched account is cached along with it, and bytecodes aren't pruned
+        match self.state.bytecodes.read().get(code_hash) {
+            Some(code) => Ok(Some(code.clone())),
+            None => self.local.bytecode_by_hash(code_hash),
+        }
+    }
+}
+
+impl BlockHashReader for ArchiveStateProvider {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.local.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.local.canonical_hashes_range(start, end)
+    }
+}
+
+impl HashedPostStateProvider for ArchiveStateProvider {
+    fn hashed_post_state(&self, bundle_state: &BundleState) -> HashedPostState {
+        self.local.hashed_post_state(bundle_state)
+    }
+}
+
+impl StateRootProvider for ArchiveStateProvider {
+    fn state_root(&self, _hashed_state: HashedPostState) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn state_root_from_nodes(&self, _input: TrieInput) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        _hashed_state: HashedPostState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn state_root_from_nodes_with_updates(
+        &self,
+        _input: TrieInput,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl StorageRootProvider for ArchiveStateProvider {
+    fn storage_root(
+        &self,
+        _address: Address,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<B256> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn storage_proof(
+        &self,
+        _address: Address,
+        _slot: B256,
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageProof> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn storage_multiproof(
+        &self,
+        _address: Address,
+        _slots: &[B256],
+        _hashed_storage: HashedStorage,
+    ) -> ProviderResult<StorageMultiProof> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+impl StateProofProvider for ArchiveStateProvider {
+    fn proof(
+        &self,
+        _input: TrieInput,
+        _address: Address,
+        _slots: &[B256],
+    ) -> ProviderResult<AccountProof> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn multiproof(
+        &self,
+        _input: TrieInput,
+        _targets: MultiProofTargets,
+    ) -> ProviderResult<MultiProof> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn witness(&self, _input: TrieInput, _target: HashedPostState) -> ProviderResult<Vec<Bytes>> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+}
+
+/// Metrics for the state fetched from the archive node.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.debug.archive_state")]
+struct ArchiveStateMetrics {
+    /// The number of requests sent to the archive node.
+    requests: Counter,
+    /// The number of requests that failed.
+    failed_requests: Counter,
+    /// The number of reads served from the cache.
+    cache_hits: Counter,
+    /// The time it took to fetch state from the archive node.
+    request_latency: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_batch_response_by_id() {
+        let body = br#"[
+            {"jsonrpc":"2.0","id":1,"result":"0x2"},
+            {"jsonrpc":"2.0","id":0,"result":"0x1"}
+        ]"#;
+        let results = parse_batch_response(body, 2).unwrap();
+        assert_eq!(results, [json!("0x1"), json!("0x2")]);
+
+        let body =
+            br#"[{"jsonrpc":"2.0","id":0,"error":{"code":-32000,"message":"missing trie node"}}]"#;
+        assert!(matches!(
+            parse_batch_response(body, 1),
+            Err(ArchiveStateError::Rpc { code: -32000, .. })
+        ));
+
+        let body = br#"[{"jsonrpc":"2.0","id":0,"result":"0x1"}]"#;
+        assert!(matches!(
+            parse_batch_response(body, 2),
+            Err(ArchiveStateError::InvalidResponse(_))
+        ));
+        assert!(matches!(
+            parse_batch_response(body, 0),
+            Err(ArchiveStateError::InvalidResponse(_))
+        ));
+        assert!(matches!(
+            parse_batch_response(b"not json", 1),
+            Err(ArchiveStateError::InvalidResponse(_))
+        ));
+    }
+
+    #[test]
+    fn converts_fetched_account() {
+        assert_eq!(into_account(U256::ZERO, 0, Bytes::new()), (None, None));
+
+        let (account, code) = into_account(U256::from(1), 2, Bytes::new());
+        assert_eq!(
+            account,
+            Some(Account { nonce: 2, balance: U256::from(1), bytecode_hash: None })
+        );
+        assert!(code.is_none());
+
+        let (account, code) = into_account(U256::ZERO, 1, Bytes::from_static(&[0x60, 0x00]));
+        let code = code.unwrap();
+        assert_eq!(account.unwrap().bytecode_hash, Some(code.hash_slow()));
+    }
+
+    #[test]
+    fn evicts_least_recently_used_block() {
+        let mut cache = ArchiveStateCache::new(2);
+        let [a, b, c] = [B256::repeat_byte(1), B256::repeat_byte(2), B256::repeat_byte(3)];
+
+        let state = cache.block(a);
+        state.accounts.write().insert(Address::ZERO, None);
+        cache.block(b);
+        // using `a` again makes `b` the least recently used block
+        assert!(Arc::ptr_eq(&cache.block(a), &state));
+        cache.block(c);
+
+        assert_eq!(cache.order, [a, c]);
+        assert!(cache.blocks.contains_key(&a));
+        assert!(!cache.blocks.contains_key(&b));
+    }
+}
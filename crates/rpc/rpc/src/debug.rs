@@ -1,7 +1,8 @@
+use crate::archive_state::{is_state_pruned, ArchiveStateFetcher};
 use alloy_consensus::{transaction::SignerRecoverable, BlockHeader};
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
-use alloy_primitives::{uint, Address, Bytes, B256};
+use alloy_primitives::{uint, Address, Bytes, B256, U256};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
@@ -9,12 +10,16 @@ use alloy_rpc_types_eth::{
     StateContext, TransactionInfo,
 };
 use alloy_rpc_types_trace::geth::{
-    call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
-    GethDebugTracerType, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
-    NoopFrame, TraceResult,
+    call::FlatCallFrame, mux::MuxFrame, BlockTraceResult, CallFrame, DiffMode, FourByteFrame,
+    GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+    GethDebugTracingOptions, GethTrace, NoopFrame, PreStateFrame, TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
 use reth_primitives_traits::{
@@ -25,16 +30,23 @@ use reth_revm::{
     db::{CacheDB, State},
     witness::ExecutionWitnessRecord,
 };
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    AccountStateChange, CallFrameChange, CallFrameDiff, DebugApiServer, DebugExecuteBlockApiServer,
+    ExecuteBlockResult, ExecuteBlockTimings, StateChangeDiff, TraceCallDiff, TraceCallDiffState,
+};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
     EthApiTypes, FromEthApiError, RpcNodeCore,
 };
-use reth_rpc_eth_types::{EthApiError, StateCacheDb};
+use reth_rpc_eth_types::{
+    cache::db::{StateCacheDbRefMutWrapper, StateProviderTraitObjWrapper},
+    EthApiError, StateCacheDb,
+};
 use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
 use reth_storage_api::{
-    BlockIdReader, BlockReaderIdExt, HeaderProvider, ProviderBlock, ReceiptProviderIdExt,
-    StateProofProvider, StateProviderFactory, StateRootProvider, TransactionVariant,
+    BlockIdReader, BlockReaderIdExt, HeaderProvider, HistoricalWitnessProvider, ProviderBlock,
+    ReceiptProviderIdExt, StateProofProvider, StateProviderBox, StateProviderFactory,
+    StateRootProvider, TransactionVariant,
 };
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_trie_common::{updates::TrieUpdates, HashedPostState};
@@ -42,14 +54,25 @@ use revm::{context_interface::Transaction, state::EvmState, DatabaseCommit};
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+    time::Instant,
+};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+/// The number of blocks that are traced concurrently by a `debug_traceChain` subscription.
+const TRACE_CHAIN_CONCURRENCY: usize = 4;
+
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
 pub struct DebugApi<Eth, BlockExecutor> {
     inner: Arc<DebugApiInner<Eth, BlockExecutor>>,
+    /// Optional provider for computing witnesses on top of persisted historical state.
+    historical_witness_provider: Option<Arc<dyn HistoricalWitnessProvider>>,
+    /// Optional archive node that traced calls fetch the state from if it's pruned locally.
+    archive_state: Option<ArchiveStateFetcher>,
 }
 
 // === impl DebugApi ===
@@ -58,7 +81,24 @@ impl<Eth, Evm> DebugApi<Eth, Evm> {
     /// Create a new instance of the [`DebugApi`]
     pub fn new(eth: Eth, blocking_task_guard: BlockingTaskGuard, evm_config: Evm) -> Self {
         let inner = Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, evm_config });
-        Self { inner }
+        Self { inner, historical_witness_provider: None, archive_state: None }
+    }
+
+    /// Configures the [`HistoricalWitnessProvider`] used for generating execution witnesses of
+    /// blocks whose parent state has already been persisted.
+    pub fn with_historical_witness_provider(
+        mut self,
+        provider: Arc<dyn HistoricalWitnessProvider>,
+    ) -> Self {
+        self.historical_witness_provider = Some(provider);
+        self
+    }
+
+    /// Configures the archive node that `debug_traceCall` fetches the state from if the state at
+    /// the requested block has been pruned locally.
+    pub fn with_archive_state(mut self, archive_state: Option<ArchiveStateFetcher>) -> Self {
+        self.archive_state = archive_state;
+        self
     }
 
     /// Access the underlying `Eth` API.
@@ -86,6 +126,54 @@ where
         self.inner.blocking_task_guard.clone().acquire_owned().await
     }
 
+    /// Prepares the call at the given block and executes the closure with its environment, like
+    /// [`Call::spawn_with_call_at`](reth_rpc_eth_api::helpers::Call::spawn_with_call_at).
+    ///
+    /// If an archive node is configured and the state at the block has been pruned locally, the
+    /// state the call reads is fetched from the archive node instead.
+    async fn spawn_call_at<F, R>(
+        &self,
+        request: TransactionRequest,
+        at: BlockId,
+        overrides: EvmOverrides,
+        f: F,
+    ) -> Result<R, Eth::Error>
+    where
+        F: FnOnce(
+                StateCacheDbRefMutWrapper<'_, '_>,
+                EvmEnvFor<Eth::Evm>,
+                TxEnvFor<Eth::Evm>,
+            ) -> Result<R, Eth::Error>
+            + Send
+            + 'static,
+        R: Send + 'static,
+    {
+        // the locally built pending block is always on top of the latest state
+        let Some(archive_state) = self.archive_state.clone().filter(|_| !at.is_pending()) else {
+            return self.eth_api().spawn_with_call_at(request, at, overrides, f).await
+        };
+
+        let (evm_env, at) = self.eth_api().evm_env_at(at).await?;
+        let block_hash = at.as_block_hash().ok_or(EthApiError::HeaderNotFound(at))?;
+        self.eth_api()
+            .spawn_blocking_io(move |eth| {
+                let state = eth.call_state_at_block_id(at)?;
+                let state: StateProviderBox = if is_state_pruned(&state) {
+                    Box::new(archive_state.state_provider(block_hash, Box::new(state)))
+                } else {
+                    Box::new(state)
+                };
+                let mut db =
+                    CacheDB::new(StateProviderDatabase::new(StateProviderTraitObjWrapper(&*state)));
+
+                let (evm_env, tx_env) =
+                    eth.prepare_call_env(evm_env, request, &mut db, overrides)?;
+
+                f(StateCacheDbRefMutWrapper(&mut db), evm_env, tx_env)
+            })
+            .await
+    }
+
     /// Trace the entire block asynchronously
     async fn trace_block(
         &self,
@@ -200,6 +288,68 @@ where
         self.trace_block(block, evm_env, opts).await
     }
 
+    /// Replays all blocks between `start_exclusive` and `end_inclusive` and sends the traces of
+    /// each block to the sink, in order.
+    ///
+    /// Up to [`TRACE_CHAIN_CONCURRENCY`] blocks are traced ahead of the block that is sent next.
+    /// Returns once the end block has been sent or the subscription was closed.
+    async fn debug_trace_chain(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: GethDebugTracingOptions,
+        sink: SubscriptionSink,
+    ) -> Result<(), Eth::Error> {
+        let start = self
+            .provider()
+            .convert_block_number(start_exclusive)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(start_exclusive.into()))?;
+        let end = self
+            .provider()
+            .convert_block_number(end_inclusive)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(end_inclusive.into()))?;
+        if end <= start {
+            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                "end block (#{end}) needs to come after start block (#{start})"
+            ))))
+        }
+
+        let mut traces = futures::stream::iter(start + 1..=end)
+            .map(|number| {
+                let opts = opts.clone();
+                async move {
+                    let _permit = self.acquire_trace_permit().await;
+                    let block_id = BlockNumberOrTag::Number(number).into();
+                    let block_hash = self
+                        .provider()
+                        .block_hash_for_id(block_id)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(block_id))?;
+                    let traces = self.debug_trace_block(block_hash.into(), opts).await?;
+                    Ok::<_, Eth::Error>(BlockTraceResult {
+                        block: U256::from(number),
+                        hash: block_hash,
+                        traces,
+                    })
+                }
+            })
+            .buffered(TRACE_CHAIN_CONCURRENCY);
+
+        while let Some(result) = traces.next().await {
+            let result = result?;
+            let msg = SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &result)
+                .map_err(|_| EthApiError::InternalEthError)?;
+            if sink.send(msg).await.is_err() {
+                // the subscription was closed
+                break
+            }
+        }
+
+        Ok(())
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -283,8 +433,7 @@ where
                     GethDebugBuiltInTracerType::FourByteTracer => {
                         let mut inspector = FourByteInspector::default();
                         let inspector = self
-                            .eth_api()
-                            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
+                            .spawn_call_at(call, at, overrides, move |db, evm_env, tx_env| {
                                 this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
                                 Ok(inspector)
                             })
@@ -301,8 +450,7 @@ where
                         );
 
                         let frame = self
-                            .eth_api()
-                            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
+                            .spawn_call_at(call, at, overrides, move |db, evm_env, tx_env| {
                                 let (res, (_, tx_env)) =
                                     this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
                                 let frame = inspector
@@ -323,8 +471,7 @@ where
                         );
 
                         let frame = self
-                            .eth_api()
-                            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
+                            .spawn_call_at(call, at, overrides, move |db, evm_env, tx_env| {
                                 // wrapper is hack to get around 'higher-ranked lifetime error',
                                 // see <https://github.com/rust-lang/rust/issues/100013>
                                 let db = db.0;
@@ -355,9 +502,7 @@ where
                             .map_err(Eth::Error::from_eth_err)?;
 
                         let frame = self
-                            .inner
-                            .eth_api
-                            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
+                            .spawn_call_at(call, at, overrides, move |db, evm_env, tx_env| {
                                 // wrapper is hack to get around 'higher-ranked lifetime error', see
                                 // <https://github.com/rust-lang/rust/issues/100013>
                                 let db = db.0;
@@ -394,9 +539,7 @@ where
                         );
 
                         let frame: FlatCallFrame = self
-                            .inner
-                            .eth_api
-                            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
+                            .spawn_call_at(call, at, overrides, move |db, evm_env, tx_env| {
                                 let (_res, (_, tx_env)) =
                                     this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
                                 let tx_info = TransactionInfo::default();
@@ -422,8 +565,7 @@ where
                     let (_, at) = self.eth_api().evm_env_at(at).await?;
 
                     let res = self
-                        .eth_api()
-                        .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
+                        .spawn_call_at(call, at, overrides, move |db, evm_env, tx_env| {
                             // wrapper is hack to get around 'higher-ranked lifetime error', see
                             // <https://github.com/rust-lang/rust/issues/100013>
                             let db = db.0;
@@ -459,8 +601,7 @@ where
         let mut inspector = TracingInspector::new(inspector_config);
 
         let (res, tx_gas_limit, inspector) = self
-            .eth_api()
-            .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
+            .spawn_call_at(call, at, overrides, move |db, evm_env, tx_env| {
                 let (res, (_, tx_env)) =
                     this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
                 Ok((res, tx_env.gas_limit(), inspector))
@@ -612,18 +753,65 @@ where
     /// root recomputation.
     pub async fn debug_execution_witness(
         &self,
-        block_id: BlockNumberOrTag,
+        block_id: BlockId,
     ) -> Result<ExecutionWitness, Eth::Error> {
         let this = self.clone();
         let block = this
             .eth_api()
-            .recovered_block(block_id.into())
+            .recovered_block(block_id)
             .await?
-            .ok_or(EthApiError::HeaderNotFound(block_id.into()))?;
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
 
         self.debug_execution_witness_for_block(block).await
     }
 
+    /// The `debug_executeBlock` method re-executes the given canonical block on top of the state of
+    /// its parent and returns the receipts, requests and gas used of the execution, together with
+    /// how long the re-execution took.
+    pub async fn debug_execute_block(
+        &self,
+        block_id: BlockId,
+    ) -> Result<ExecuteBlockResult<<Evm::Primitives as NodePrimitives>::Receipt>, Eth::Error> {
+        let start = Instant::now();
+        let block = self
+            .eth_api()
+            .recovered_block(block_id)
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let fetch_block = start.elapsed();
+
+        let this = self.clone();
+        let block_hash = block.hash();
+        let block_number = block.header().number();
+        let (result, execution) = self
+            .eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state_provider| {
+                let db = StateProviderDatabase::new(&state_provider);
+                let execution_start = Instant::now();
+                let output = this
+                    .inner
+                    .evm_config
+                    .batch_executor(db)
+                    .execute(&block)
+                    .map_err(|err| EthApiError::Internal(err.into()))?;
+                Ok((output.result, execution_start.elapsed()))
+            })
+            .await?;
+
+        Ok(ExecuteBlockResult {
+            block_hash,
+            block_number,
+            receipts: result.receipts,
+            requests: result.requests,
+            gas_used: result.gas_used,
+            timings: ExecuteBlockTimings {
+                fetch_block: fetch_block.as_micros() as u64,
+                execution: execution.as_micros() as u64,
+                total: start.elapsed().as_micros() as u64,
+            },
+        })
+    }
+
     /// Generates an execution witness, using the given recovered block.
     pub async fn debug_execution_witness_for_block(
         &self,
@@ -631,10 +819,11 @@ where
     ) -> Result<ExecutionWitness, Eth::Error> {
         let this = self.clone();
         let block_number = block.header().number();
+        let parent_hash = block.parent_hash();
 
         let (mut exec_witness, lowest_block_number) = self
             .eth_api()
-            .spawn_with_state_at_block(block.parent_hash().into(), move |state_provider| {
+            .spawn_with_state_at_block(parent_hash.into(), move |state_provider| {
                 let db = StateProviderDatabase::new(&state_provider);
                 let block_executor = this.inner.evm_config.batch_executor(db);
 
@@ -649,9 +838,20 @@ where
                 let ExecutionWitnessRecord { hashed_state, codes, keys, lowest_block_number } =
                     witness_record;
 
-                let state = state_provider
-                    .witness(Default::default(), hashed_state)
-                    .map_err(EthApiError::from)?;
+                // Prefer collecting the witness in parallel on top of the persisted state and fall
+                // back to the state provider if the parent block has not been persisted yet.
+                let historical_witness = match &this.historical_witness_provider {
+                    Some(provider) => provider
+                        .historical_witness(parent_hash, hashed_state.clone())
+                        .map_err(EthApiError::from)?,
+                    None => None,
+                };
+                let state = match historical_witness {
+                    Some(state) => state,
+                    None => state_provider
+                        .witness(Default::default(), hashed_state)
+                        .map_err(EthApiError::from)?,
+                };
                 Ok((
                     ExecutionWitness { state, codes, keys, ..Default::default() },
                     lowest_block_number,
@@ -876,6 +1076,64 @@ where
         Ok((frame.into(), res.state))
     }
 
+    /// The `debug_traceCallDiff` method runs the same call at two states and returns the
+    /// differences of their call trees and state changes.
+    pub async fn debug_trace_call_diff(
+        &self,
+        call: TransactionRequest,
+        base: TraceCallDiffState,
+        other: TraceCallDiffState,
+    ) -> Result<TraceCallDiff, Eth::Error> {
+        let ((base_calls, base_state), (other_calls, other_state)) = futures::try_join!(
+            self.trace_call_changes(call.clone(), base),
+            self.trace_call_changes(call, other)
+        )?;
+
+        let mut calls = Vec::new();
+        diff_call_frames(&mut Vec::new(), base_calls, other_calls, &mut calls);
+        Ok(TraceCallDiff { calls, state: diff_state_changes(base_state, other_state) })
+    }
+
+    /// Traces the call tree and the state changes of a call at the given state.
+    async fn trace_call_changes(
+        &self,
+        call: TransactionRequest,
+        state: TraceCallDiffState,
+    ) -> Result<(CallFrame, DiffMode), Eth::Error> {
+        let TraceCallDiffState { block, state_overrides, block_overrides } = state;
+        let opts = GethDebugTracingCallOptions {
+            tracing_options: GethDebugTracingOptions {
+                tracer: Some(GethDebugTracerType::BuiltInTracer(
+                    GethDebugBuiltInTracerType::MuxTracer,
+                )),
+                tracer_config: serde_json::json!({
+                    "callTracer": { "withLog": true },
+                    "prestateTracer": { "diffMode": true },
+                })
+                .into(),
+                ..Default::default()
+            },
+            state_overrides,
+            block_overrides,
+        };
+
+        let GethTrace::MuxTracer(MuxFrame(mut frames)) =
+            self.debug_trace_call(call, block, opts).await?
+        else {
+            return Err(EthApiError::InternalEthError.into())
+        };
+        match (
+            frames.remove(&GethDebugBuiltInTracerType::CallTracer),
+            frames.remove(&GethDebugBuiltInTracerType::PreStateTracer),
+        ) {
+            (
+                Some(GethTrace::CallTracer(calls)),
+                Some(GethTrace::PreStateTracer(PreStateFrame::Diff(state))),
+            ) => Ok((calls, state)),
+            _ => Err(EthApiError::InternalEthError.into()),
+        }
+    }
+
     /// Returns the state root of the `HashedPostState` on top of the state for the given block with
     /// trie updates.
     async fn debug_state_root_with_updates(
@@ -978,10 +1236,22 @@ where
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        // errors after the subscription was accepted are sent as the final notification
+        Self::debug_trace_chain(
+            self,
+            start_exclusive,
+            end_inclusive,
+            opts.unwrap_or_default(),
+            sink,
+        )
+        .await?;
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -1055,11 +1325,19 @@ where
         Self::debug_trace_call_many(self, bundles, state_context, opts).await.map_err(Into::into)
     }
 
-    /// Handler for `debug_executionWitness`
-    async fn debug_execution_witness(
+    /// Handler for `debug_traceCallDiff`
+    async fn debug_trace_call_diff(
         &self,
-        block: BlockNumberOrTag,
-    ) -> RpcResult<ExecutionWitness> {
+        request: TransactionRequest,
+        base: TraceCallDiffState,
+        other: TraceCallDiffState,
+    ) -> RpcResult<TraceCallDiff> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_trace_call_diff(self, request, base, other).await.map_err(Into::into)
+    }
+
+    /// Handler for `debug_executionWitness`
+    async fn debug_execution_witness(&self, block: BlockId) -> RpcResult<ExecutionWitness> {
         let _permit = self.acquire_trace_permit().await;
         Self::debug_execution_witness(self, block).await.map_err(Into::into)
     }
@@ -1305,6 +1583,52 @@ where
     }
 }
 
+/// The handler of `debug_executeBlock`, see [`DebugExecuteBlockApiServer`].
+///
+/// This wraps the [`DebugApi`], because the method is served by the authenticated server only.
+pub struct DebugExecuteBlock<Eth, Evm>(DebugApi<Eth, Evm>);
+
+impl<Eth, Evm> DebugExecuteBlock<Eth, Evm> {
+    /// Creates a new handler that re-executes blocks with the given [`DebugApi`].
+    pub const fn new(debug_api: DebugApi<Eth, Evm>) -> Self {
+        Self(debug_api)
+    }
+}
+
+#[async_trait]
+impl<Eth, Evm> DebugExecuteBlockApiServer<<Evm::Primitives as NodePrimitives>::Receipt>
+    for DebugExecuteBlock<Eth, Evm>
+where
+    Eth: EthApiTypes + TraceExt + 'static,
+    Evm: ConfigureEvm<
+            Primitives: NodePrimitives<
+                Block = ProviderBlock<Eth::Provider>,
+                Receipt: serde::Serialize + serde::de::DeserializeOwned,
+            >,
+        > + 'static,
+{
+    /// Handler for `debug_executeBlock`
+    async fn debug_execute_block(
+        &self,
+        block: BlockId,
+    ) -> RpcResult<ExecuteBlockResult<<Evm::Primitives as NodePrimitives>::Receipt>> {
+        let _permit = self.0.acquire_trace_permit().await;
+        self.0.debug_execute_block(block).await.map_err(Into::into)
+    }
+}
+
+impl<Eth, Evm> std::fmt::Debug for DebugExecuteBlock<Eth, Evm> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugExecuteBlock").finish_non_exhaustive()
+    }
+}
+
+impl<Eth, Evm> Clone for DebugExecuteBlock<Eth, Evm> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
 impl<Eth, Evm> std::fmt::Debug for DebugApi<Eth, Evm> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -1313,7 +1637,11 @@ impl<Eth, Evm> std::fmt::Debug for DebugApi<Eth, Evm> {
 
 impl<Eth, Evm> Clone for DebugApi<Eth, Evm> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            historical_witness_provider: self.historical_witness_provider.clone(),
+            archive_state: self.archive_state.clone(),
+        }
     }
 }
 
@@ -1325,3 +1653,167 @@ struct DebugApiInner<Eth, Evm> {
     /// block executor for debug & trace apis
     evm_config: Evm,
 }
+
+/// Compares the calls of two call trees by their position and appends their differences in depth
+/// first order.
+fn diff_call_frames(
+    path: &mut Vec<usize>,
+    mut base: CallFrame,
+    mut other: CallFrame,
+    diffs: &mut Vec<CallFrameDiff>,
+) {
+    let base_calls = std::mem::take(&mut base.calls);
+    let other_calls = std::mem::take(&mut other.calls);
+
+    let fields = changed_call_fields(&base, &other);
+    if !fields.is_empty() {
+        diffs.push(CallFrameDiff {
+            path: path.clone(),
+            change: CallFrameChange::Changed {
+                fields,
+                base: Box::new(base),
+                other: Box::new(other),
+            },
+        });
+    }
+
+    let len = base_calls.len().max(other_calls.len());
+    let mut base_calls = base_calls.into_iter();
+    let mut other_calls = other_calls.into_iter();
+    for index in 0..len {
+        path.push(index);
+        match (base_calls.next(), other_calls.next()) {
+            (Some(base), Some(other)) => diff_call_frames(path, base, other, diffs),
+            (Some(call), None) => diffs.push(CallFrameDiff {
+                path: path.clone(),
+                change: CallFrameChange::Removed { call },
+            }),
+            (None, Some(call)) => diffs.push(CallFrameDiff {
+                path: path.clone(),
+                change: CallFrameChange::Added { call },
+            }),
+            (None, None) => {}
+        }
+        path.pop();
+    }
+}
+
+/// Returns the names of the fields of two calls that differ, ignoring their subcalls.
+fn changed_call_fields(base: &CallFrame, other: &CallFrame) -> Vec<String> {
+    let fields = [
+        ("type", base.typ != other.typ),
+        ("from", base.from != other.from),
+        ("to", base.to != other.to),
+        ("value", base.value != other.value),
+        ("gas", base.gas != other.gas),
+        ("gasUsed", base.gas_used != other.gas_used),
+        ("input", base.input != other.input),
+        ("output", base.output != other.output),
+        ("error", base.error != other.error),
+        ("revertReason", base.revert_reason != other.revert_reason),
+        ("logs", base.logs != other.logs),
+    ];
+    fields.into_iter().filter(|(_, changed)| *changed).map(|(field, _)| field.to_string()).collect()
+}
+
+/// Returns the accounts of the two state changes that were changed differently, ordered by
+/// address.
+fn diff_state_changes(base: DiffMode, other: DiffMode) -> Vec<StateChangeDiff> {
+    let mut base = account_changes(base);
+    let mut other = account_changes(other);
+    let addresses = base.keys().chain(other.keys()).copied().collect::<BTreeSet<_>>();
+
+    addresses
+        .into_iter()
+        .filter_map(|address| {
+            let base = base.remove(&address);
+            let other = other.remove(&address);
+            (base != other).then_some(StateChangeDiff { address, base, other })
+        })
+        .collect()
+}
+
+/// Returns the changes of the accounts of the pre and post state of a call.
+///
+/// Accounts of the pre state that aren't part of the post state were deleted by the call.
+fn account_changes(state: DiffMode) -> BTreeMap<Address, AccountStateChange> {
+    let DiffMode { pre, post } = state;
+    let mut changes = post
+        .into_iter()
+        .map(|(address, post)| (address, AccountStateChange { deleted: false, post }))
+        .collect::<BTreeMap<_, _>>();
+    for address in pre.into_keys() {
+        changes
+            .entry(address)
+            .or_insert_with(|| AccountStateChange { deleted: true, ..Default::default() });
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use alloy_rpc_types_trace::geth::AccountState;
+
+    fn call(to: u8, output: u8, calls: Vec<CallFrame>) -> CallFrame {
+        CallFrame {
+            to: Some(Address::with_last_byte(to)),
+            output: Some(Bytes::from(vec![output])),
+            calls,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn diffs_call_trees_by_position() {
+        let base = call(1, 0, vec![call(2, 0, vec![]), call(3, 0, vec![call(4, 0, vec![])])]);
+        let other = call(1, 0, vec![call(2, 1, vec![]), call(3, 0, vec![])]);
+
+        let mut diffs = Vec::new();
+        diff_call_frames(&mut Vec::new(), base, other, &mut diffs);
+
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].path, vec![0]);
+        assert!(matches!(
+            &diffs[0].change,
+            CallFrameChange::Changed { fields, .. } if fields == &["output"]
+        ));
+        assert_eq!(diffs[1].path, vec![1, 0]);
+        let CallFrameChange::Removed { call } = &diffs[1].change else {
+            panic!("call not removed")
+        };
+        assert_eq!(call.to, Some(Address::with_last_byte(4)));
+    }
+
+    #[test]
+    fn diffs_state_changes() {
+        let changed = |balance: u64| AccountState {
+            balance: Some(U256::from(balance)),
+            ..Default::default()
+        };
+        let base = DiffMode {
+            pre: BTreeMap::from([
+                (Address::with_last_byte(1), changed(0)),
+                (Address::with_last_byte(2), changed(0)),
+                (Address::with_last_byte(3), changed(0)),
+            ]),
+            post: BTreeMap::from([
+                (Address::with_last_byte(1), changed(1)),
+                (Address::with_last_byte(2), changed(1)),
+            ]),
+        };
+        let other = DiffMode {
+            pre: BTreeMap::from([(Address::with_last_byte(1), changed(5))]),
+            post: BTreeMap::from([(Address::with_last_byte(1), changed(1))]),
+        };
+
+        let diffs = diff_state_changes(base, other);
+        assert_eq!(diffs.len(), 2);
+        assert_eq!(diffs[0].address, Address::with_last_byte(2));
+        assert_eq!(diffs[0].base.as_ref().unwrap().post, changed(1));
+        assert_eq!(diffs[0].other, None);
+        assert_eq!(diffs[1].address, Address::with_last_byte(3));
+        assert!(diffs[1].base.as_ref().unwrap().deleted);
+    }
+}
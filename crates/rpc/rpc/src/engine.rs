@@ -5,7 +5,7 @@ use alloy_rpc_types_eth::{
     EIP1186AccountProofResponse, Filter, Log, SyncStatus,
 };
 use alloy_serde::JsonStorageKey;
-use jsonrpsee::core::RpcResult as Result;
+use jsonrpsee::{core::RpcResult as Result, Extensions};
 use reth_rpc_api::{EngineEthApiServer, EthApiServer};
 use reth_rpc_convert::RpcTxReq;
 /// Re-export for convenience
@@ -79,7 +79,7 @@ where
         block_overrides: Option<Box<BlockOverrides>>,
     ) -> Result<Bytes> {
         self.eth
-            .call(request, block_id, state_overrides, block_overrides)
+            .call(&Extensions::new(), request, block_id, state_overrides, block_overrides)
             .instrument(engine_span!())
             .await
     }
@@ -138,6 +138,8 @@ where
         keys: Vec<JsonStorageKey>,
         block_number: Option<BlockId>,
     ) -> Result<EIP1186AccountProofResponse> {
-        self.eth.get_proof(address, keys, block_number).instrument(engine_span!()).await
+        let response =
+            self.eth.get_proof(address, keys, block_number).instrument(engine_span!()).await?;
+        Ok(response.proof)
     }
 }
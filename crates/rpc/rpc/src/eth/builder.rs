@@ -1,12 +1,19 @@
 //! `EthApiBuilder` implementation
 
-use crate::{eth::core::EthApiInner, EthApi};
+use crate::{
+    eth::{
+        core::EthApiInner,
+        tx_forwarder::{TxForwarder, TxForwarderConfig},
+    },
+    EthApi,
+};
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::ChainSpecProvider;
 use reth_node_api::NodePrimitives;
 use reth_rpc_eth_types::{
-    fee_history::fee_history_cache_new_blocks_task, EthStateCache, EthStateCacheConfig,
-    FeeHistoryCache, FeeHistoryCacheConfig, GasCap, GasPriceOracle, GasPriceOracleConfig,
+    fee_history::fee_history_cache_new_blocks_task, proof_cache::proof_cache_new_blocks_task,
+    CallPolicyProvider, EthProofCache, EthStateCache, EthStateCacheConfig, FeeHistoryCache,
+    FeeHistoryCacheConfig, GasCap, GasPriceOracle, GasPriceOracleConfig,
 };
 use reth_rpc_server_types::constants::{
     DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
@@ -30,9 +37,12 @@ where
     evm_config: EvmConfig,
     gas_cap: GasCap,
     max_simulate_blocks: u64,
+    call_policies: Option<Arc<dyn CallPolicyProvider>>,
+    tx_forwarder: Option<TxForwarderConfig>,
     eth_proof_window: u64,
     fee_history_cache_config: FeeHistoryCacheConfig,
     proof_permits: usize,
+    proof_cache_max_entries: u32,
     eth_state_cache_config: EthStateCacheConfig,
     eth_cache: Option<EthStateCache<Provider::Block, Provider::Receipt>>,
     gas_oracle_config: GasPriceOracleConfig,
@@ -59,10 +69,13 @@ where
             gas_oracle: None,
             gas_cap: GasCap::default(),
             max_simulate_blocks: DEFAULT_MAX_SIMULATE_BLOCKS,
+            call_policies: None,
+            tx_forwarder: None,
             eth_proof_window: DEFAULT_ETH_PROOF_WINDOW,
             blocking_task_pool: None,
             fee_history_cache_config: FeeHistoryCacheConfig::default(),
             proof_permits: DEFAULT_PROOF_PERMITS,
+            proof_cache_max_entries: 0,
             task_spawner: TokioTaskExecutor::default().boxed(),
             gas_oracle_config: Default::default(),
             eth_state_cache_config: Default::default(),
@@ -119,6 +132,19 @@ where
         self
     }
 
+    /// Sets the provider that resolves the limits of `eth_call` and `eth_estimateGas` per request,
+    /// e.g. from the API key of the request.
+    pub fn call_policies(mut self, call_policies: impl CallPolicyProvider + 'static) -> Self {
+        self.call_policies = Some(Arc::new(call_policies));
+        self
+    }
+
+    /// Relays `eth_sendRawTransaction` submissions to the configured upstream endpoint, if any.
+    pub fn tx_forwarder(mut self, tx_forwarder: Option<TxForwarderConfig>) -> Self {
+        self.tx_forwarder = tx_forwarder;
+        self
+    }
+
     /// Sets the maximum number of blocks into the past for generating state proofs.
     pub const fn eth_proof_window(mut self, eth_proof_window: u64) -> Self {
         self.eth_proof_window = eth_proof_window;
@@ -146,6 +172,13 @@ where
         self
     }
 
+    /// Sets the maximum number of getproof targets whose revealed tries are cached, the cache is
+    /// disabled if zero.
+    pub const fn proof_cache_max_entries(mut self, proof_cache_max_entries: u32) -> Self {
+        self.proof_cache_max_entries = proof_cache_max_entries;
+        self
+    }
+
     /// Builds the [`EthApiInner`] instance.
     ///
     /// If not configured, this will spawn the cache backend: [`EthStateCache::spawn`].
@@ -180,10 +213,13 @@ where
             gas_oracle,
             gas_cap,
             max_simulate_blocks,
+            call_policies,
+            tx_forwarder,
             eth_proof_window,
             blocking_task_pool,
             fee_history_cache_config,
             proof_permits,
+            proof_cache_max_entries,
             task_spawner,
         } = self;
 
@@ -204,6 +240,19 @@ where
             }),
         );
 
+        let proof_cache = (proof_cache_max_entries > 0).then(|| {
+            let proof_cache = EthProofCache::new(proof_cache_max_entries);
+            let new_canonical_blocks = provider.canonical_state_stream();
+            let pc = proof_cache.clone();
+            task_spawner.spawn_critical(
+                "cache canonical blocks for proof cache task",
+                Box::pin(async move {
+                    proof_cache_new_blocks_task(pc, new_canonical_blocks).await;
+                }),
+            );
+            proof_cache
+        });
+
         EthApiInner::new(
             provider,
             pool,
@@ -221,6 +270,9 @@ where
             task_spawner,
             proof_permits,
         )
+        .with_call_policies(call_policies)
+        .with_tx_forwarder(tx_forwarder.map(TxForwarder::new))
+        .with_proof_cache(proof_cache)
     }
 
     /// Builds the [`EthApi`] instance.
@@ -0,0 +1,507 @@
+//! Forwarding of `eth_sendBundle` requests to external builders and relays.
+
+use crate::eth::bundle::{EthBundle, EthBundleError};
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{hex, keccak256, Bytes, B256};
+use alloy_rpc_types_mev::{
+    CancelPrivateTransactionRequest, EthBundleHash, EthCallBundle, EthCallBundleResponse,
+    EthCancelBundle, EthSendBundle, PrivateTransactionRequest,
+};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use jsonrpsee::core::RpcResult;
+use parking_lot::Mutex;
+use reth_rpc_eth_api::{
+    helpers::{Call, EthTransactions, LoadPendingBlock},
+    EthBundleApiServer, EthBundleStatusApiServer,
+};
+use reth_rpc_eth_types::{
+    bundle::{BundleEndpointStatus, BundleForwardState, ForwardedBundleStatus},
+    EthApiError,
+};
+use reth_rpc_server_types::{constants::DEFAULT_BUNDLE_FORWARD_RETRIES, result::internal_rpc_err};
+use reth_tasks::TaskSpawner;
+use serde::Serialize;
+use std::{
+    collections::{HashMap, VecDeque},
+    io,
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+use tracing::{debug, warn};
+
+/// The header that carries the signature of a request to a builder.
+pub const FLASHBOTS_SIGNATURE_HEADER: &str = "X-Flashbots-Signature";
+
+/// The default delay before the first retry of a failed forwarding attempt.
+pub const DEFAULT_BUNDLE_FORWARD_BACKOFF: Duration = Duration::from_millis(200);
+
+/// The default number of bundles whose status is kept.
+pub const DEFAULT_MAX_TRACKED_BUNDLES: usize = 10_000;
+
+/// Configuration of an [`EthBundleForwarder`].
+#[derive(Debug, Clone)]
+pub struct BundleForwarderConfig {
+    /// The URLs of the builders and relays bundles are forwarded to.
+    pub endpoints: Vec<String>,
+    /// The key requests are signed with, see [`FLASHBOTS_SIGNATURE_HEADER`].
+    pub signer: Option<PrivateKeySigner>,
+    /// The number of retries of a failed forwarding attempt.
+    pub max_retries: u32,
+    /// The delay before the first retry, doubled with every further retry.
+    pub retry_backoff: Duration,
+    /// The number of bundles whose status is kept.
+    pub max_tracked_bundles: usize,
+}
+
+impl BundleForwarderConfig {
+    /// Creates a new configuration that forwards bundles to the given endpoints.
+    pub fn new(endpoints: Vec<String>) -> Self {
+        Self {
+            endpoints,
+            signer: None,
+            max_retries: DEFAULT_BUNDLE_FORWARD_RETRIES,
+            retry_backoff: DEFAULT_BUNDLE_FORWARD_BACKOFF,
+            max_tracked_bundles: DEFAULT_MAX_TRACKED_BUNDLES,
+        }
+    }
+
+    /// Signs forwarded requests with the given key.
+    pub fn with_signer(mut self, signer: PrivateKeySigner) -> Self {
+        self.signer = Some(signer);
+        self
+    }
+
+    /// Signs forwarded requests with the key in the given file, which must contain the hex encoded
+    /// private key.
+    pub fn with_signing_key_file(self, path: impl AsRef<Path>) -> io::Result<Self> {
+        let key = std::fs::read_to_string(path)?;
+        let signer = key
+            .trim()
+            .parse::<PrivateKeySigner>()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(self.with_signer(signer))
+    }
+
+    /// Sets the number of retries of a failed forwarding attempt.
+    pub const fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+}
+
+/// `eth_sendBundle` implementation that simulates bundles locally and forwards the valid ones to
+/// the configured builders and relays.
+///
+/// This gives searchers a single endpoint to submit bundles to. The forwarding happens in the
+/// background with retries, its progress can be queried via `eth_getBundleStatus`.
+pub struct EthBundleForwarder<Eth> {
+    inner: Arc<EthBundleForwarderInner<Eth>>,
+}
+
+impl<Eth> EthBundleForwarder<Eth> {
+    /// Creates a new forwarder that simulates bundles with the given [`EthBundle`].
+    pub fn new(
+        bundle: EthBundle<Eth>,
+        config: BundleForwarderConfig,
+        task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let statuses = Mutex::new(BundleStatuses::new(config.max_tracked_bundles));
+        Self {
+            inner: Arc::new(EthBundleForwarderInner {
+                bundle,
+                config,
+                client: reqwest::Client::new(),
+                task_spawner,
+                statuses,
+            }),
+        }
+    }
+
+    /// Returns the status of the bundle with the given hash, if it's tracked.
+    pub fn bundle_status(&self, bundle_hash: &B256) -> Option<ForwardedBundleStatus> {
+        self.inner.statuses.lock().get(bundle_hash).cloned()
+    }
+}
+
+impl<Eth> EthBundleForwarder<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    /// Simulates the bundle at the top of its target block and forwards it to all endpoints if
+    /// the simulation succeeded.
+    ///
+    /// A bundle is rejected if any of its transactions reverts, unless the transaction is listed
+    /// in the reverting transaction hashes of the bundle.
+    pub async fn send_bundle(&self, bundle: EthSendBundle) -> Result<EthBundleHash, Eth::Error> {
+        if bundle.block_number == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            )
+            .into())
+        }
+
+        let simulation = self
+            .inner
+            .bundle
+            .call_bundle(EthCallBundle {
+                txs: bundle.txs.clone(),
+                block_number: bundle.block_number,
+                state_block_number: BlockNumberOrTag::Latest,
+                ..Default::default()
+            })
+            .await?;
+        if let Some(reverted) = simulation.results.iter().find(|result| {
+            result.revert.is_some() && !bundle.reverting_tx_hashes.contains(&result.tx_hash)
+        }) {
+            return Err(EthApiError::InvalidParams(format!(
+                "bundle transaction {} reverted in simulation",
+                reverted.tx_hash
+            ))
+            .into())
+        }
+
+        let bundle_hash = simulation.bundle_hash;
+        let status = ForwardedBundleStatus {
+            bundle_hash,
+            block_number: bundle.block_number,
+            simulated_gas_used: simulation.total_gas_used,
+            simulated_coinbase_diff: simulation.coinbase_diff,
+            endpoints: self
+                .inner
+                .config
+                .endpoints
+                .iter()
+                .map(|url| BundleEndpointStatus {
+                    url: url.clone(),
+                    state: BundleForwardState::Pending,
+                    attempts: 0,
+                    error: None,
+                })
+                .collect(),
+        };
+        if !self.inner.statuses.lock().insert(status) {
+            // the bundle was already forwarded
+            return Ok(EthBundleHash { bundle_hash })
+        }
+
+        let request = JsonRpcRequest::new("eth_sendBundle", (bundle,));
+        let body = match serde_json::to_vec(&request) {
+            Ok(body) => Bytes::from(body),
+            Err(err) => return Err(EthApiError::InvalidParams(err.to_string()).into()),
+        };
+        for (index, url) in self.inner.config.endpoints.iter().enumerate() {
+            let inner = Arc::clone(&self.inner);
+            let url = url.clone();
+            let body = body.clone();
+            self.inner.task_spawner.spawn(Box::pin(async move {
+                inner.forward_with_retries(bundle_hash, index, &url, body).await;
+            }));
+        }
+
+        Ok(EthBundleHash { bundle_hash })
+    }
+}
+
+impl<Eth> EthBundleForwarder<Eth> {
+    /// Forwards the cancellation of a bundle to all endpoints.
+    ///
+    /// Succeeds if at least one endpoint accepted the cancellation.
+    pub async fn cancel_bundle(&self, request: EthCancelBundle) -> Result<(), EthApiError> {
+        let body = serde_json::to_vec(&JsonRpcRequest::new("eth_cancelBundle", (request,)))
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+        let body = Bytes::from(body);
+
+        let mut last_error = None;
+        for url in &self.inner.config.endpoints {
+            match self.inner.post(url, body.clone()).await {
+                Ok(()) => return Ok(()),
+                Err(err) => last_error = Some(err),
+            }
+        }
+        let err = last_error.unwrap_or_else(|| "no bundle endpoints configured".to_string());
+        Err(EthApiError::other(internal_rpc_err(format!("failed to cancel bundle: {err}"))))
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthBundleApiServer for EthBundleForwarder<Eth>
+where
+    Eth: EthTransactions + LoadPendingBlock + Call + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Self::send_bundle(self, bundle).await.map_err(Into::into)
+    }
+
+    async fn call_bundle(&self, request: EthCallBundle) -> RpcResult<EthCallBundleResponse> {
+        self.inner.bundle.call_bundle(request).await.map_err(Into::into)
+    }
+
+    async fn cancel_bundle(&self, request: EthCancelBundle) -> RpcResult<()> {
+        Self::cancel_bundle(self, request).await.map_err(Into::into)
+    }
+
+    async fn send_private_transaction(
+        &self,
+        _request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        Err(EthApiError::Unsupported("eth_sendPrivateTransaction is not supported").into())
+    }
+
+    async fn send_private_raw_transaction(&self, _bytes: Bytes) -> RpcResult<B256> {
+        Err(EthApiError::Unsupported("eth_sendPrivateRawTransaction is not supported").into())
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        _request: CancelPrivateTransactionRequest,
+    ) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("eth_cancelPrivateTransaction is not supported").into())
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthBundleStatusApiServer for EthBundleForwarder<Eth>
+where
+    Eth: Send + Sync + 'static,
+{
+    async fn bundle_status(&self, bundle_hash: B256) -> RpcResult<Option<ForwardedBundleStatus>> {
+        Ok(Self::bundle_status(self, &bundle_hash))
+    }
+}
+
+impl<Eth> std::fmt::Debug for EthBundleForwarder<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthBundleForwarder")
+            .field("endpoints", &self.inner.config.endpoints)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for EthBundleForwarder<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+/// Container type for `EthBundleForwarder` internals
+struct EthBundleForwarderInner<Eth> {
+    /// Simulates bundles before they are forwarded.
+    bundle: EthBundle<Eth>,
+    config: BundleForwarderConfig,
+    client: reqwest::Client,
+    /// Spawns the forwarding tasks.
+    task_spawner: Box<dyn TaskSpawner>,
+    statuses: Mutex<BundleStatuses>,
+}
+
+impl<Eth> EthBundleForwarderInner<Eth> {
+    /// Forwards the request body to the endpoint at the given index, retrying with exponential
+    /// backoff, and records the outcome in the status of the bundle.
+    async fn forward_with_retries(&self, bundle_hash: B256, index: usize, url: &str, body: Bytes) {
+        let mut backoff = self.config.retry_backoff;
+        for attempt in 1..=self.config.max_retries.saturating_add(1) {
+            let result = self.post(url, body.clone()).await;
+            let done = result.is_ok() || attempt > self.config.max_retries;
+            let state = match &result {
+                Ok(()) => BundleForwardState::Forwarded,
+                Err(_) if done => BundleForwardState::Failed,
+                Err(_) => BundleForwardState::Pending,
+            };
+            if let Err(err) = &result {
+                debug!(
+                    target: "rpc::eth::bundle",
+                    %bundle_hash,
+                    url,
+                    attempt,
+                    %err,
+                    "Failed to forward bundle"
+                );
+            }
+            self.statuses.lock().update(&bundle_hash, index, |status| {
+                status.state = state;
+                status.attempts = attempt;
+                status.error = result.err();
+            });
+
+            if done {
+                if state == BundleForwardState::Failed {
+                    warn!(
+                        target: "rpc::eth::bundle",
+                        %bundle_hash,
+                        url,
+                        "Giving up forwarding bundle"
+                    );
+                }
+                return
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+        }
+    }
+
+    /// Sends the JSON-RPC request body to the endpoint, signing it if a signer is configured.
+    async fn post(&self, url: &str, body: Bytes) -> Result<(), String> {
+        let mut request = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.0.clone());
+        if let Some(signer) = &self.config.signer {
+            request = request.header(FLASHBOTS_SIGNATURE_HEADER, sign_request(signer, &body)?);
+        }
+
+        let response = request.send().await.map_err(|err| err.to_string())?;
+        let http_status = response.status();
+        let response = response.bytes().await.map_err(|err| err.to_string())?;
+        let response: serde_json::Value =
+            serde_json::from_slice(&response).map_err(|err| err.to_string())?;
+        if let Some(error) = response.get("error") {
+            return Err(error.to_string())
+        }
+        if !http_status.is_success() {
+            return Err(format!("unexpected status {http_status}"))
+        }
+        Ok(())
+    }
+}
+
+/// Returns the value of the [`FLASHBOTS_SIGNATURE_HEADER`] for the given request body.
+///
+/// The header consists of the address of the signer and its signature of the hex encoded hash of
+/// the body, separated by a colon.
+fn sign_request(signer: &PrivateKeySigner, body: &[u8]) -> Result<String, String> {
+    let message = format!("{:?}", keccak256(body));
+    let signature = signer.sign_message_sync(message.as_bytes()).map_err(|err| err.to_string())?;
+    Ok(format!("{:?}:{}", signer.address(), hex::encode_prefixed(signature.as_bytes())))
+}
+
+/// The status of the most recently forwarded bundles.
+///
+/// Once the capacity is reached, the status of the oldest bundle is evicted.
+#[derive(Debug)]
+struct BundleStatuses {
+    capacity: usize,
+    bundles: HashMap<B256, ForwardedBundleStatus>,
+    order: VecDeque<B256>,
+}
+
+impl BundleStatuses {
+    fn new(capacity: usize) -> Self {
+        Self { capacity, bundles: HashMap::new(), order: VecDeque::new() }
+    }
+
+    fn get(&self, bundle_hash: &B256) -> Option<&ForwardedBundleStatus> {
+        self.bundles.get(bundle_hash)
+    }
+
+    /// Starts tracking the status of a bundle.
+    ///
+    /// Returns `false` if the bundle is already tracked.
+    fn insert(&mut self, status: ForwardedBundleStatus) -> bool {
+        if self.bundles.contains_key(&status.bundle_hash) {
+            return false
+        }
+        self.order.push_back(status.bundle_hash);
+        self.bundles.insert(status.bundle_hash, status);
+        while self.order.len() > self.capacity {
+            if let Some(evicted) = self.order.pop_front() {
+                self.bundles.remove(&evicted);
+            }
+        }
+        true
+    }
+
+    /// Updates the status of the bundle at the endpoint with the given index, if it's tracked.
+    fn update(
+        &mut self,
+        bundle_hash: &B256,
+        index: usize,
+        f: impl FnOnce(&mut BundleEndpointStatus),
+    ) {
+        if let Some(endpoint) =
+            self.bundles.get_mut(bundle_hash).and_then(|status| status.endpoints.get_mut(index))
+        {
+            f(endpoint);
+        }
+    }
+}
+
+/// A JSON-RPC request that is forwarded to an endpoint.
+#[derive(Debug, Serialize)]
+pub(crate) struct JsonRpcRequest<P> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'static str,
+    params: P,
+}
+
+impl<P> JsonRpcRequest<P> {
+    pub(crate) const fn new(method: &'static str, params: P) -> Self {
+        Self { jsonrpc: "2.0", id: 1, method, params }
+    }
+
+    /// Sets the id of the request, to tell apart the responses to a batch of requests.
+    pub(crate) const fn with_id(mut self, id: u64) -> Self {
+        self.id = id;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(bundle_hash: B256) -> ForwardedBundleStatus {
+        ForwardedBundleStatus {
+            bundle_hash,
+            block_number: 1,
+            simulated_gas_used: 21_000,
+            simulated_coinbase_diff: Default::default(),
+            endpoints: vec![BundleEndpointStatus {
+                url: "http://localhost:8545".to_string(),
+                state: BundleForwardState::Pending,
+                attempts: 0,
+                error: None,
+            }],
+        }
+    }
+
+    #[test]
+    fn statuses_evict_oldest_bundle() {
+        let mut statuses = BundleStatuses::new(1);
+        let first = B256::with_last_byte(1);
+        let second = B256::with_last_byte(2);
+        assert!(statuses.insert(status(first)));
+        assert!(!statuses.insert(status(first)));
+
+        statuses.update(&first, 0, |endpoint| {
+            endpoint.state = BundleForwardState::Forwarded;
+            endpoint.attempts = 1;
+        });
+        assert!(statuses.get(&first).unwrap().is_forwarded());
+
+        assert!(statuses.insert(status(second)));
+        assert!(statuses.get(&first).is_none());
+        assert!(!statuses.get(&second).unwrap().is_forwarded());
+    }
+
+    #[test]
+    fn signs_request_body() {
+        let signer: PrivateKeySigner =
+            "4646464646464646464646464646464646464646464646464646464646464646".parse().unwrap();
+        let body = br#"{"jsonrpc":"2.0","id":1,"method":"eth_sendBundle","params":[]}"#;
+        let header = sign_request(&signer, body).unwrap();
+
+        let (address, signature) = header.split_once(':').unwrap();
+        assert_eq!(address, format!("{:?}", signer.address()));
+        let signature = hex::decode(signature).unwrap();
+        let signature = alloy_primitives::Signature::try_from(signature.as_slice()).unwrap();
+        let message = format!("{:?}", keccak256(body));
+        assert_eq!(
+            signature.recover_address_from_msg(message.as_bytes()).unwrap(),
+            signer.address()
+        );
+    }
+}
@@ -3,12 +3,16 @@
 
 use std::sync::Arc;
 
-use crate::{eth::helpers::types::EthRpcConverter, EthApiBuilder};
+use crate::{
+    eth::{helpers::types::EthRpcConverter, tx_forwarder::TxForwarder},
+    EthApiBuilder,
+};
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumberOrTag;
 use alloy_network::Ethereum;
 use alloy_primitives::{Bytes, U256};
 use derive_more::Deref;
+use reth_chainspec::ChainSpecProvider;
 use reth_node_api::{FullNodeComponents, FullNodeTypes};
 use reth_rpc_eth_api::{
     helpers::{EthSigner, SpawnBlocking},
@@ -16,7 +20,8 @@ use reth_rpc_eth_api::{
     EthApiTypes, RpcNodeCore,
 };
 use reth_rpc_eth_types::{
-    EthApiError, EthStateCache, FeeHistoryCache, GasCap, GasPriceOracle, PendingBlock,
+    CallPolicyProvider, EthApiError, EthProofCache, EthStateCache, FeeHistoryCache, GasCap,
+    GasPriceOracle, PendingBlock,
 };
 use reth_storage_api::{
     BlockReader, BlockReaderIdExt, NodePrimitivesProvider, ProviderBlock, ProviderHeader,
@@ -168,7 +173,7 @@ where
 
 impl<Provider, Pool, Network, EvmConfig> RpcNodeCore for EthApi<Provider, Pool, Network, EvmConfig>
 where
-    Provider: BlockReader + NodePrimitivesProvider + Clone + Unpin,
+    Provider: BlockReader + NodePrimitivesProvider + ChainSpecProvider + Clone + Unpin,
     Pool: Send + Sync + Clone + Unpin,
     Network: Send + Sync + Clone,
     EvmConfig: Send + Sync + Clone + Unpin,
@@ -179,6 +184,8 @@ where
     type Evm = EvmConfig;
     type Network = Network;
     type PayloadBuilder = ();
+    type Consensus = ();
+    type ChainSpec = Provider::ChainSpec;
 
     fn pool(&self) -> &Self::Pool {
         self.inner.pool()
@@ -199,12 +206,20 @@ where
     fn provider(&self) -> &Self::Provider {
         self.inner.provider()
     }
+
+    fn consensus(&self) -> &Self::Consensus {
+        &()
+    }
+
+    fn chain_spec(&self) -> Arc<Self::ChainSpec> {
+        self.inner.provider().chain_spec()
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> RpcNodeCoreExt
     for EthApi<Provider, Pool, Network, EvmConfig>
 where
-    Provider: BlockReader + NodePrimitivesProvider + Clone + Unpin,
+    Provider: BlockReader + NodePrimitivesProvider + ChainSpecProvider + Clone + Unpin,
     Pool: Send + Sync + Clone + Unpin,
     Network: Send + Sync + Clone,
     EvmConfig: Send + Sync + Clone + Unpin,
@@ -266,6 +281,12 @@ pub struct EthApiInner<Provider: BlockReader, Pool, Network, EvmConfig> {
     gas_cap: u64,
     /// Maximum number of blocks for `eth_simulateV1`.
     max_simulate_blocks: u64,
+    /// Resolves the limits of `eth_call` and `eth_estimateGas` per request, if configured.
+    call_policies: Option<Arc<dyn CallPolicyProvider>>,
+    /// Relays `eth_sendRawTransaction` submissions upstream, if configured.
+    tx_forwarder: Option<TxForwarder>,
+    /// Revealed tries of repeated `eth_getProof` targets, if configured.
+    proof_cache: Option<EthProofCache>,
     /// The maximum number of blocks into the past for generating state proofs.
     eth_proof_window: u64,
     /// The block number at which the node started
@@ -331,6 +352,9 @@ where
             gas_oracle,
             gas_cap: gas_cap.into().into(),
             max_simulate_blocks,
+            call_policies: None,
+            tx_forwarder: None,
+            proof_cache: None,
             eth_proof_window,
             starting_block,
             task_spawner,
@@ -342,6 +366,27 @@ where
             raw_tx_sender,
         }
     }
+
+    /// Sets the provider that resolves the limits of `eth_call` and `eth_estimateGas` per request.
+    pub fn with_call_policies(
+        mut self,
+        call_policies: Option<Arc<dyn CallPolicyProvider>>,
+    ) -> Self {
+        self.call_policies = call_policies;
+        self
+    }
+
+    /// Sets the forwarder that relays `eth_sendRawTransaction` submissions upstream.
+    pub fn with_tx_forwarder(mut self, tx_forwarder: Option<TxForwarder>) -> Self {
+        self.tx_forwarder = tx_forwarder;
+        self
+    }
+
+    /// Sets the cache of the revealed tries of repeated `eth_getProof` targets.
+    pub fn with_proof_cache(mut self, proof_cache: Option<EthProofCache>) -> Self {
+        self.proof_cache = proof_cache;
+        self
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApiInner<Provider, Pool, Network, EvmConfig>
@@ -404,6 +449,26 @@ where
         self.max_simulate_blocks
     }
 
+    /// Returns the provider that resolves the limits of `eth_call` and `eth_estimateGas` per
+    /// request, if configured.
+    #[inline]
+    pub fn call_policies(&self) -> Option<&dyn CallPolicyProvider> {
+        self.call_policies.as_deref()
+    }
+
+    /// Returns the forwarder that relays `eth_sendRawTransaction` submissions upstream, if
+    /// configured.
+    #[inline]
+    pub const fn tx_forwarder(&self) -> Option<&TxForwarder> {
+        self.tx_forwarder.as_ref()
+    }
+
+    /// Returns the cache of the revealed tries of repeated `eth_getProof` targets, if configured.
+    #[inline]
+    pub const fn proof_cache(&self) -> Option<&EthProofCache> {
+        self.proof_cache.as_ref()
+    }
+
     /// Returns a handle to the gas oracle.
     #[inline]
     pub const fn gas_oracle(&self) -> &GasPriceOracle<Provider> {
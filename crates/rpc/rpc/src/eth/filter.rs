@@ -8,7 +8,11 @@ use alloy_rpc_types_eth::{
 };
 use async_trait::async_trait;
 use futures::future::TryFutureExt;
-use jsonrpsee::{core::RpcResult, server::IdProvider};
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    server::IdProvider,
+    PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
 use reth_errors::ProviderError;
 use reth_primitives_traits::{NodePrimitives, SealedHeader};
 use reth_rpc_eth_api::{
@@ -120,8 +124,12 @@ where
     /// let filter = EthFilter::new(eth_api, Default::default(), TokioTaskExecutor::default().boxed());
     /// ```
     pub fn new(eth_api: Eth, config: EthFilterConfig, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            max_logs_per_stream,
+            stale_filter_ttl,
+        } = config;
         let inner = EthFilterInner {
             eth_api,
             active_filters: ActiveFilters::new(),
@@ -130,6 +138,7 @@ where
             task_spawner,
             stale_filter_ttl,
             query_limits: QueryLimits { max_blocks_per_filter, max_logs_per_response },
+            max_logs_per_stream,
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -395,6 +404,19 @@ where
         trace!(target: "rpc::eth", "Serving eth_getLogs");
         Ok(self.logs_for_filter(filter, self.inner.query_limits).await?)
     }
+
+    /// Handler for `eth_getLogsStream`
+    async fn logs_stream(
+        &self,
+        pending: PendingSubscriptionSink,
+        filter: Filter,
+    ) -> SubscriptionResult {
+        trace!(target: "rpc::eth", "Serving eth_getLogsStream");
+        let sink = pending.accept().await?;
+        // errors after the subscription was accepted are sent as the final notification
+        self.inner.clone().stream_logs(filter, sink).await?;
+        Ok(())
+    }
 }
 
 impl<Eth> std::fmt::Debug for EthFilter<Eth>
@@ -417,6 +439,8 @@ struct EthFilterInner<Eth: EthApiTypes> {
     id_provider: Arc<dyn IdProvider>,
     /// limits for logs queries
     query_limits: QueryLimits,
+    /// maximum number of logs streamed by a single `eth_getLogsStream` subscription
+    max_logs_per_stream: Option<usize>,
     /// maximum number of headers to read at once for range filter
     max_headers_range: u64,
     /// The type that can spawn tasks.
@@ -505,6 +529,77 @@ where
         }
     }
 
+    /// Streams all logs matching the filter to the sink, in chunks of at most
+    /// `max_headers_range` blocks.
+    ///
+    /// Only the logs of a single chunk are held in memory at once. Chunks without any matching
+    /// logs are skipped. The range is not limited by [`QueryLimits`], instead streaming fails once
+    /// more than `max_logs_per_stream` logs would have been sent.
+    async fn stream_logs(
+        self: Arc<Self>,
+        filter: Filter,
+        sink: SubscriptionSink,
+    ) -> Result<(), EthFilterError> {
+        let FilterBlockOption::Range { from_block, to_block } = filter.block_option else {
+            // the logs of a single block are sent as a single chunk
+            let logs = self.logs_for_filter(filter, QueryLimits::no_limits()).await?;
+            send_logs_chunk(&sink, &logs).await?;
+            return Ok(())
+        };
+
+        let info = self.provider().chain_info()?;
+        let from = from_block.map(|num| self.provider().convert_block_number(num)).transpose()?;
+        let to = to_block.map(|num| self.provider().convert_block_number(num)).transpose()?;
+        let (from_block, to_block) = logs_utils::get_filter_block_range(
+            from.flatten(),
+            to.flatten(),
+            info.best_number,
+            info,
+        );
+        if to_block < from_block {
+            return Err(EthFilterError::InvalidBlockRangeParams)
+        }
+
+        let mut streamed = 0;
+        for (from, to) in
+            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+        {
+            if sink.is_closed() {
+                break
+            }
+
+            let logs = self
+                .clone()
+                .get_logs_in_block_range(filter.clone(), from, to, QueryLimits::no_limits())
+                .await?;
+            if logs.is_empty() {
+                continue
+            }
+
+            streamed += logs.len();
+            if let Some(max_logs) = self.max_logs_per_stream.filter(|max| streamed > *max) {
+                debug!(
+                    target: "rpc::eth::filter",
+                    max_logs,
+                    from_block,
+                    to_block = from.saturating_sub(1),
+                    "Stream exceeded max logs per stream limit"
+                );
+                return Err(EthFilterError::QueryExceedsMaxResults {
+                    max_logs,
+                    from_block,
+                    to_block: from.saturating_sub(1),
+                })
+            }
+
+            if !send_logs_chunk(&sink, &logs).await? {
+                break
+            }
+        }
+
+        Ok(())
+    }
+
     /// Installs a new filter and returns the new identifier.
     async fn install_filter(
         &self,
@@ -800,6 +895,15 @@ enum FilterKind<T> {
     PendingTransaction(PendingTransactionKind<T>),
 }
 
+/// Sends a chunk of logs to the subscription sink.
+///
+/// Returns `false` if the subscription was closed.
+async fn send_logs_chunk(sink: &SubscriptionSink, logs: &[Log]) -> Result<bool, EthFilterError> {
+    let msg = SubscriptionMessage::new(sink.method_name(), sink.subscription_id(), &logs)
+        .map_err(|_| EthFilterError::InternalError)?;
+    Ok(sink.send(msg).await.is_ok())
+}
+
 /// An iterator that yields _inclusive_ block ranges of a given step size
 #[derive(Debug)]
 struct BlockRangeInclusiveIter {
@@ -3,6 +3,7 @@
 use crate::EthApi;
 use alloy_evm::block::BlockExecutorFactory;
 use alloy_rpc_types_eth::TransactionRequest;
+use jsonrpsee::Extensions;
 use reth_errors::ProviderError;
 use reth_evm::{ConfigureEvm, EvmFactory, TxEnvFor};
 use reth_node_api::NodePrimitives;
@@ -11,6 +12,7 @@ use reth_rpc_eth_api::{
     helpers::{estimate::EstimateCall, Call, EthCall, LoadPendingBlock, LoadState, SpawnBlocking},
     FromEvmError, FullEthApiTypes, RpcNodeCore, RpcNodeCoreExt,
 };
+use reth_rpc_eth_types::CallPolicy;
 use reth_storage_api::{BlockReader, ProviderHeader, ProviderTx};
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
 use revm::context::TxEnv;
@@ -59,6 +61,13 @@ where
     fn max_simulate_blocks(&self) -> u64 {
         self.inner.max_simulate_blocks()
     }
+
+    fn call_policy(&self, extensions: &Extensions) -> CallPolicy {
+        self.inner
+            .call_policies()
+            .map(|policies| policies.call_policy(extensions))
+            .unwrap_or_default()
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> EstimateCall for EthApi<Provider, Pool, Network, EvmConfig>
@@ -1,24 +1,36 @@
 //! Contains RPC handler implementations specific to state.
 
+use futures::Future;
 use reth_chainspec::{ChainSpecProvider, EthereumHardforks};
-use reth_storage_api::{BlockReader, StateProviderFactory};
+use reth_storage_api::{BlockReader, StateProviderBox, StateProviderFactory};
 use reth_transaction_pool::TransactionPool;
 
 use reth_rpc_eth_api::{
-    helpers::{EthState, LoadState, SpawnBlocking},
+    helpers::{EthState, LoadPendingBlock, LoadState, SpawnBlocking},
     RpcNodeCoreExt,
 };
+use reth_rpc_eth_types::EthProofCache;
 
 use crate::EthApi;
 
 impl<Provider, Pool, Network, EvmConfig> EthState for EthApi<Provider, Pool, Network, EvmConfig>
 where
-    Self: LoadState + SpawnBlocking,
+    Self: LoadState + LoadPendingBlock + SpawnBlocking,
     Provider: BlockReader,
 {
     fn max_proof_window(&self) -> u64 {
         self.inner.eth_proof_window()
     }
+
+    fn proof_cache(&self) -> Option<&EthProofCache> {
+        self.inner.proof_cache()
+    }
+
+    fn pending_state(
+        &self,
+    ) -> impl Future<Output = Result<Option<StateProviderBox>, Self::Error>> + Send {
+        self.local_pending_state()
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> LoadState for EthApi<Provider, Pool, Network, EvmConfig>
@@ -36,13 +48,19 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alloy_consensus::Header;
-    use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M;
-    use alloy_primitives::{Address, StorageKey, StorageValue, U256};
+    use alloy_consensus::{Header, Transaction};
+    use alloy_eips::{
+        eip1559::{ETHEREUM_BLOCK_GAS_LIMIT_30M, INITIAL_BASE_FEE},
+        BlockId,
+    };
+    use alloy_primitives::{Address, Bytes, StorageKey, StorageValue, B256, U256};
+    use alloy_rpc_types_eth::{state::EvmOverrides, TransactionRequest};
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_ethereum_primitives::Block;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider, NoopProvider};
-    use reth_rpc_eth_api::helpers::EthState;
+    use reth_rpc_eth_api::helpers::{EthCall, EthState};
     use reth_rpc_eth_types::{
         EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
     };
@@ -50,7 +68,10 @@ mod tests {
         DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
     };
     use reth_tasks::pool::BlockingTaskPool;
-    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction, TestPool},
+        TransactionOrigin,
+    };
     use std::collections::HashMap;
 
     fn noop_eth_api() -> EthApi<NoopProvider, TestPool, NoopNetwork, EthEvmConfig> {
@@ -100,6 +121,109 @@ mod tests {
         )
     }
 
+    /// Returns an [`EthApi`] on top of a London genesis block, whose pool holds the given
+    /// transactions.
+    async fn pending_eth_api(
+        accounts: HashMap<Address, ExtendedAccount>,
+        transactions: Vec<MockTransaction>,
+    ) -> EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig> {
+        let chain_spec = ChainSpecBuilder::mainnet().london_activated().build();
+        let mock_provider = MockEthProvider::default().with_chain_spec(chain_spec);
+
+        let genesis = Header {
+            gas_limit: ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            base_fee_per_gas: Some(INITIAL_BASE_FEE),
+            ..Default::default()
+        };
+        mock_provider
+            .add_block(genesis.hash_slow(), Block { header: genesis, body: Default::default() });
+        mock_provider.extend_accounts(accounts);
+
+        let pool = testing_pool();
+        for transaction in transactions {
+            pool.add_transaction(TransactionOrigin::External, transaction).await.unwrap();
+        }
+
+        let evm_config = EthEvmConfig::new(mock_provider.chain_spec());
+        let cache = EthStateCache::spawn(mock_provider.clone(), Default::default());
+        EthApi::new(
+            mock_provider.clone(),
+            pool,
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(mock_provider, Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT_30M,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::<Header>::new(FeeHistoryCacheConfig::default()),
+            evm_config,
+            DEFAULT_PROOF_PERMITS,
+        )
+    }
+
+    /// Returns a transfer of one wei from the sender, that fits into the pending block.
+    fn transfer(sender: Address) -> MockTransaction {
+        MockTransaction::eip1559()
+            .with_sender(sender)
+            .with_gas_limit(21_000)
+            .with_max_fee(INITIAL_BASE_FEE as u128)
+            .with_value(U256::from(1))
+    }
+
+    #[tokio::test]
+    async fn test_transaction_count_of_pending_block() {
+        let sender = Address::random();
+        let transfer = transfer(sender);
+        let recipient = transfer.to().unwrap();
+
+        let accounts = HashMap::from([(sender, ExtendedAccount::new(0, U256::from(u64::MAX)))]);
+        let eth_api = pending_eth_api(accounts, vec![transfer]).await;
+
+        let count = EthState::transaction_count(&eth_api, sender, None).await.unwrap();
+        assert_eq!(count, U256::ZERO);
+        let count =
+            EthState::transaction_count(&eth_api, sender, Some(BlockId::pending())).await.unwrap();
+        assert_eq!(count, U256::from(1));
+
+        // the pending state is the state after the transfer
+        let balance = eth_api.balance(recipient, None).await.unwrap();
+        assert_eq!(balance, U256::ZERO);
+        let balance = eth_api.balance(recipient, Some(BlockId::pending())).await.unwrap();
+        assert_eq!(balance, U256::from(1));
+    }
+
+    #[tokio::test]
+    async fn test_call_on_pending_block() {
+        let sender = Address::random();
+        let transfer = transfer(sender);
+        let recipient = transfer.to().unwrap();
+
+        // returns the balance of the recipient:
+        // PUSH20 <recipient> BALANCE PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+        let contract = Address::random();
+        let code: Bytes = [
+            &[0x73][..],
+            recipient.as_slice(),
+            &[0x31, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3],
+        ]
+        .concat()
+        .into();
+
+        let accounts = HashMap::from([
+            (sender, ExtendedAccount::new(0, U256::from(u64::MAX))),
+            (contract, ExtendedAccount::new(0, U256::ZERO).with_bytecode(code)),
+        ]);
+        let eth_api = pending_eth_api(accounts, vec![transfer]).await;
+
+        let request = TransactionRequest::default().to(contract);
+        let output = eth_api.call(request.clone(), None, EvmOverrides::default()).await.unwrap();
+        assert_eq!(B256::from_slice(&output), B256::ZERO);
+        let output =
+            eth_api.call(request, Some(BlockId::pending()), EvmOverrides::default()).await.unwrap();
+        assert_eq!(B256::from_slice(&output), B256::from(U256::from(1)));
+    }
+
     #[tokio::test]
     async fn test_storage() {
         // === Noop ===
@@ -6,9 +6,10 @@ use reth_rpc_eth_api::{
     helpers::{EthSigner, EthTransactions, LoadTransaction, SpawnBlocking},
     FromEthApiError, FullEthApiTypes, RpcNodeCore, RpcNodeCoreExt,
 };
-use reth_rpc_eth_types::utils::recover_raw_transaction;
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
 use reth_storage_api::{BlockReader, BlockReaderIdExt, ProviderTx, TransactionsProvider};
 use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use tracing::{debug, warn};
 
 impl<Provider, Pool, Network, EvmConfig> EthTransactions
     for EthApi<Provider, Pool, Network, EvmConfig>
@@ -23,15 +24,39 @@ where
 
     /// Decodes and recovers the transaction and submits it to the pool.
     ///
+    /// If a [`TxForwarder`](crate::eth::TxForwarder) is configured, the transaction is relayed
+    /// upstream first and only inserted into the pool if the forwarding mode asks for it.
+    ///
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256, Self::Error> {
         let recovered = recover_raw_transaction(&tx)?;
 
         // broadcast raw transaction to subscribers if there is any.
-        self.broadcast_raw_transaction(tx);
+        self.broadcast_raw_transaction(tx.clone());
 
         let pool_transaction = <Self::Pool as TransactionPool>::Transaction::from_pooled(recovered);
 
+        if let Some(forwarder) = self.inner.tx_forwarder() {
+            debug!(target: "rpc::eth", hash = %pool_transaction.hash(), url = forwarder.endpoint(), "forwarding raw transaction");
+            let hash = forwarder
+                .forward_raw_transaction(&tx)
+                .await
+                .map_err(|err| Self::Error::from_eth_err(EthApiError::from(err)))?;
+
+            if forwarder.mode().is_additional() {
+                // the transaction was accepted upstream, so a local failure isn't reported
+                let _ = self
+                    .pool()
+                    .add_transaction(TransactionOrigin::Local, pool_transaction)
+                    .await
+                    .inspect_err(|err| {
+                        warn!(target: "rpc::eth", %err, %hash, "forwarded transaction, but failed to add it to the local pool");
+                    });
+            }
+
+            return Ok(hash)
+        }
+
         // submit the transaction to the pool with a `Local` origin
         let hash = self
             .pool()
@@ -2,18 +2,22 @@
 
 pub mod builder;
 pub mod bundle;
+pub mod bundle_forwarder;
 pub mod core;
 pub mod filter;
 pub mod helpers;
 pub mod pubsub;
 pub mod sim_bundle;
+pub mod tx_forwarder;
 
 /// Implementation of `eth` namespace API.
 pub use builder::EthApiBuilder;
 pub use bundle::EthBundle;
+pub use bundle_forwarder::{BundleForwarderConfig, EthBundleForwarder};
 pub use core::{EthApi, EthApiFor};
 pub use filter::EthFilter;
 pub use pubsub::EthPubSub;
+pub use tx_forwarder::{TxForwardMode, TxForwarder, TxForwarderConfig};
 
 pub use helpers::{signer::DevSigner, sync_listener::SyncListener};
 
@@ -1,23 +1,24 @@
 //! `eth_` `PubSub` RPC handler implementation
 
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
 
 use alloy_primitives::{TxHash, U256};
 use alloy_rpc_types_eth::{
     pubsub::{Params, PubSubSyncStatus, SubscriptionKind, SyncStatusMetadata},
     Filter, Header, Log,
 };
-use futures::StreamExt;
+use futures::{future::BoxFuture, FutureExt, StreamExt};
 use jsonrpsee::{
     server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
 };
 use reth_chain_state::CanonStateSubscriptions;
+use reth_metrics::{metrics, metrics::Counter, Metrics};
 use reth_network_api::NetworkInfo;
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc_eth_api::{
     pubsub::EthPubSubApiServer, EthApiTypes, RpcConvert, RpcNodeCore, RpcTransaction,
 };
-use reth_rpc_eth_types::logs_utils;
+use reth_rpc_eth_types::{logs_utils, EthPubSubConfig, SubscriptionOverflowPolicy};
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_storage_api::BlockNumReader;
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
@@ -27,7 +28,7 @@ use tokio_stream::{
     wrappers::{BroadcastStream, ReceiverStream},
     Stream,
 };
-use tracing::error;
+use tracing::{debug, error};
 
 /// `Eth` pubsub RPC implementation.
 ///
@@ -50,7 +51,16 @@ impl<Eth> EthPubSub<Eth> {
 
     /// Creates a new, shareable instance.
     pub fn with_spawner(eth_api: Eth, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = EthPubSubInner { eth_api, subscription_task_spawner };
+        Self::with_config(eth_api, EthPubSubConfig::default(), subscription_task_spawner)
+    }
+
+    /// Creates a new, shareable instance with the given config.
+    pub fn with_config(
+        eth_api: Eth,
+        config: EthPubSubConfig,
+        subscription_task_spawner: Box<dyn TaskSpawner>,
+    ) -> Self {
+        let inner = EthPubSubInner { eth_api, config, subscription_task_spawner };
         Self { inner: Arc::new(inner) }
     }
 }
@@ -101,9 +111,11 @@ where
         kind: SubscriptionKind,
         params: Option<Params>,
     ) -> Result<(), ErrorObject<'static>> {
+        let config = self.inner.config;
+        let metrics = PubSubMetrics::new_with_labels(&[("kind", subscription_kind_name(kind))]);
         match kind {
             SubscriptionKind::NewHeads => {
-                pipe_from_stream(accepted_sink, self.new_headers_stream()).await
+                pipe_from_stream(accepted_sink, self.new_headers_stream(), config, &metrics).await
             }
             SubscriptionKind::Logs => {
                 // if no params are provided, used default filter params
@@ -114,7 +126,7 @@ where
                     }
                     _ => Default::default(),
                 };
-                pipe_from_stream(accepted_sink, self.log_stream(filter)).await
+                pipe_from_stream(accepted_sink, self.log_stream(filter), config, &metrics).await
             }
             SubscriptionKind::NewPendingTransactions => {
                 if let Some(params) = params {
@@ -139,7 +151,7 @@ where
                                 };
                                 std::future::ready(tx_value)
                             });
-                            return pipe_from_stream(accepted_sink, stream).await
+                            return pipe_from_stream(accepted_sink, stream, config, &metrics).await
                         }
                         Params::Bool(false) | Params::None => {
                             // only hashes requested
@@ -152,7 +164,13 @@ where
                     }
                 }
 
-                pipe_from_stream(accepted_sink, self.pending_transaction_hashes_stream()).await
+                pipe_from_stream(
+                    accepted_sink,
+                    self.pending_transaction_hashes_stream(),
+                    config,
+                    &metrics,
+                )
+                .await
             }
             SubscriptionKind::Syncing => {
                 // get new block subscription
@@ -251,27 +269,53 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
 }
 
 /// Pipes all stream items to the subscription sink.
+///
+/// Items that the connection can't take yet are buffered, at most
+/// [`EthPubSubConfig::buffer_size`] of them. Once the buffer is full, the
+/// [`SubscriptionOverflowPolicy`] decides whether items are dropped or the subscription is closed,
+/// so that a slow consumer can't hold back the stream or grow the buffer without bounds.
 async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
     mut stream: St,
+    config: EthPubSubConfig,
+    metrics: &PubSubMetrics,
 ) -> Result<(), ErrorObject<'static>>
 where
     St: Stream<Item = T> + Unpin,
     T: Serialize,
 {
-    loop {
+    let mut buffer = SubscriptionBuffer::new(config);
+    // the message that is currently handed to the connection
+    let mut sending: Option<BoxFuture<'_, _>> = None;
+    let mut stream_ended = false;
+
+    let result = loop {
+        if sending.is_none() {
+            match buffer.pop() {
+                Some(msg) => sending = Some(sink.send(msg).boxed()),
+                None if stream_ended => break Ok(()),
+                None => {}
+            }
+        }
+
         tokio::select! {
             _ = sink.closed() => {
                 // connection dropped
                 break Ok(())
             },
-            maybe_item = stream.next() => {
-                let item = match maybe_item {
-                    Some(item) => item,
-                    None => {
-                        // stream ended
-                        break  Ok(())
-                    },
+            sent = async { sending.as_mut().expect("message is being sent").await },
+                if sending.is_some() =>
+            {
+                sending = None;
+                if sent.is_err() {
+                    break Ok(())
+                }
+            }
+            maybe_item = stream.next(), if !stream_ended => {
+                let Some(item) = maybe_item else {
+                    // stream ended, send the remaining buffered items
+                    stream_ended = true;
+                    continue
                 };
                 let msg = SubscriptionMessage::new(
                     sink.method_name(),
@@ -279,12 +323,112 @@ where
                     &item
                 ).map_err(SubscriptionSerializeError::new)?;
 
-                if sink.send(msg).await.is_err() {
-                    break Ok(());
+                match buffer.push(msg) {
+                    BufferOutcome::Buffered => {}
+                    BufferOutcome::Dropped => metrics.dropped_messages.increment(1),
+                    BufferOutcome::Overflow => {
+                        metrics.overflowed_subscriptions.increment(1);
+                        break Err(internal_rpc_err("subscription buffer is full"))
+                    }
                 }
             }
         }
+    };
+
+    if buffer.dropped() > 0 || result.is_err() {
+        debug!(
+            target: "rpc::eth::pubsub",
+            subscription_id = ?sink.subscription_id(),
+            dropped = buffer.dropped(),
+            closed = result.is_err(),
+            "Subscription consumer couldn't keep up"
+        );
     }
+    result
+}
+
+/// The outcome of buffering a subscription message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BufferOutcome {
+    /// The message was buffered, possibly by dropping an older one.
+    Buffered,
+    /// The message was dropped.
+    Dropped,
+    /// The buffer is full and the subscription must be closed.
+    Overflow,
+}
+
+/// Bounded buffer of the messages of a subscription that haven't been sent yet.
+#[derive(Debug)]
+struct SubscriptionBuffer<T> {
+    messages: VecDeque<T>,
+    capacity: usize,
+    overflow_policy: SubscriptionOverflowPolicy,
+    /// The number of messages dropped so far.
+    dropped: u64,
+}
+
+impl<T> SubscriptionBuffer<T> {
+    fn new(config: EthPubSubConfig) -> Self {
+        Self {
+            messages: VecDeque::new(),
+            capacity: config.buffer_size.max(1),
+            overflow_policy: config.overflow_policy,
+            dropped: 0,
+        }
+    }
+
+    /// Buffers the message according to the overflow policy.
+    fn push(&mut self, msg: T) -> BufferOutcome {
+        if self.messages.len() < self.capacity {
+            self.messages.push_back(msg);
+            return BufferOutcome::Buffered
+        }
+
+        match self.overflow_policy {
+            SubscriptionOverflowPolicy::DropOldest => {
+                self.messages.pop_front();
+                self.messages.push_back(msg);
+                self.dropped += 1;
+                BufferOutcome::Dropped
+            }
+            SubscriptionOverflowPolicy::DropNewest => {
+                self.dropped += 1;
+                BufferOutcome::Dropped
+            }
+            SubscriptionOverflowPolicy::Disconnect => BufferOutcome::Overflow,
+        }
+    }
+
+    /// Returns the oldest buffered message.
+    fn pop(&mut self) -> Option<T> {
+        self.messages.pop_front()
+    }
+
+    /// Returns the number of messages dropped so far.
+    const fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+/// Returns the name of the subscription kind, as used in `eth_subscribe`.
+const fn subscription_kind_name(kind: SubscriptionKind) -> &'static str {
+    match kind {
+        SubscriptionKind::NewHeads => "newHeads",
+        SubscriptionKind::Logs => "logs",
+        SubscriptionKind::NewPendingTransactions => "newPendingTransactions",
+        SubscriptionKind::Syncing => "syncing",
+    }
+}
+
+/// Metrics for `eth_subscribe` subscriptions, labeled by the kind of subscription.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.eth.pubsub")]
+struct PubSubMetrics {
+    /// The number of messages dropped because the consumer couldn't keep up.
+    dropped_messages: Counter,
+    /// The number of subscriptions closed because the consumer couldn't keep up.
+    overflowed_subscriptions: Counter,
 }
 
 impl<Eth> std::fmt::Debug for EthPubSub<Eth> {
@@ -298,6 +442,8 @@ impl<Eth> std::fmt::Debug for EthPubSub<Eth> {
 struct EthPubSubInner<EthApi> {
     /// The `eth` API.
     eth_api: EthApi,
+    /// The config of the subscriptions.
+    config: EthPubSubConfig,
     /// The type that's used to spawn subscription tasks.
     subscription_task_spawner: Box<dyn TaskSpawner>,
 }
@@ -387,3 +533,45 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer(policy: SubscriptionOverflowPolicy) -> SubscriptionBuffer<u64> {
+        let mut buffer = SubscriptionBuffer::new(
+            EthPubSubConfig::default().buffer_size(2).overflow_policy(policy),
+        );
+        assert_eq!(buffer.push(1), BufferOutcome::Buffered);
+        assert_eq!(buffer.push(2), BufferOutcome::Buffered);
+        buffer
+    }
+
+    #[test]
+    fn drop_oldest_on_overflow() {
+        let mut buffer = buffer(SubscriptionOverflowPolicy::DropOldest);
+        assert_eq!(buffer.push(3), BufferOutcome::Dropped);
+        assert_eq!(buffer.dropped(), 1);
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(3));
+        assert_eq!(buffer.pop(), None);
+    }
+
+    #[test]
+    fn drop_newest_on_overflow() {
+        let mut buffer = buffer(SubscriptionOverflowPolicy::DropNewest);
+        assert_eq!(buffer.push(3), BufferOutcome::Dropped);
+        assert_eq!(buffer.dropped(), 1);
+        assert_eq!(buffer.pop(), Some(1));
+        assert_eq!(buffer.push(4), BufferOutcome::Buffered);
+        assert_eq!(buffer.pop(), Some(2));
+        assert_eq!(buffer.pop(), Some(4));
+    }
+
+    #[test]
+    fn disconnect_on_overflow() {
+        let mut buffer = buffer(SubscriptionOverflowPolicy::Disconnect);
+        assert_eq!(buffer.push(3), BufferOutcome::Overflow);
+        assert_eq!(buffer.dropped(), 0);
+    }
+}
@@ -3,7 +3,7 @@
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumberOrTag;
 use alloy_evm::overrides::apply_block_overrides;
-use alloy_primitives::U256;
+use alloy_primitives::{Bytes, U256};
 use alloy_rpc_types_eth::BlockId;
 use alloy_rpc_types_mev::{
     BundleItem, Inclusion, Privacy, RefundConfig, SendBundleRequest, SimBundleLogs,
@@ -18,11 +18,14 @@ use reth_rpc_eth_api::{
     helpers::{block::LoadBlock, Call, EthTransactions},
     FromEthApiError, FromEvmError,
 };
-use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError, RevertError};
 use reth_storage_api::ProviderTx;
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
-use revm::{context_interface::result::ResultAndState, DatabaseCommit, DatabaseRef};
+use revm::{
+    context_interface::result::{ExecutionResult, ResultAndState},
+    DatabaseCommit, DatabaseRef,
+};
 use std::{sync::Arc, time::Duration};
 use tracing::trace;
 
@@ -283,10 +286,24 @@ where
                         .map_err(Eth::Error::from_evm_err)?;
 
                     if !result.is_success() && !item.can_revert {
-                        return Err(EthApiError::InvalidParams(
-                            EthSimBundleError::BundleTransactionFailed.to_string(),
-                        )
-                        .into());
+                        // the failed transaction is reported in the response, so searchers can
+                        // tell which transaction of the bundle failed and why
+                        let (exec_error, revert) = execution_failure(result);
+                        return Ok(SimBundleResponse {
+                            success: false,
+                            state_block: current_block_number,
+                            error: Some(format!(
+                                "{} at index {tx_index}",
+                                EthSimBundleError::BundleTransactionFailed
+                            )),
+                            logs: Some(body_logs),
+                            gas_used: total_gas_used,
+                            mev_gas_price: U256::ZERO,
+                            profit: U256::ZERO,
+                            refundable_value: U256::ZERO,
+                            exec_error: Some(exec_error),
+                            revert,
+                        })
                     }
 
                     let gas_used = result.gas_used();
@@ -438,6 +455,18 @@ where
     }
 }
 
+/// Returns the error message and, if the transaction reverted, the revert data of a failed
+/// execution.
+fn execution_failure<H: std::fmt::Debug>(result: ExecutionResult<H>) -> (String, Option<Bytes>) {
+    match result {
+        ExecutionResult::Halt { reason, .. } => (format!("execution halted: {reason:?}"), None),
+        result => {
+            let output = result.into_output().unwrap_or_default();
+            (RevertError::new(output.clone()).to_string(), Some(output))
+        }
+    }
+}
+
 /// Container type for `EthSimBundle` internals
 #[derive(Debug)]
 struct EthSimBundleInner<Eth> {
@@ -484,10 +513,36 @@ pub enum EthSimBundleError {
     /// Thrown when a bundle simulation times out
     #[error("bundle simulation timed out")]
     BundleTimeout,
-    /// Thrown when a transaction is reverted in a bundle
+    /// Thrown when a transaction that isn't allowed to revert fails in a bundle
     #[error("bundle transaction failed")]
     BundleTransactionFailed,
     /// Thrown when a bundle simulation returns negative profit
     #[error("bundle simulation returned negative profit")]
     NegativeProfit,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+    use revm::context_interface::result::{HaltReason, OutOfGasError};
+
+    #[test]
+    fn reports_revert_data() {
+        // `Error("nope")`
+        let output = Bytes::from(hex!("08c379a0000000000000000000000000000000000000000000000000000000000000002000000000000000000000000000000000000000000000000000000000000000046e6f706500000000000000000000000000000000000000000000000000000000"));
+        let result =
+            ExecutionResult::<HaltReason>::Revert { gas_used: 21_000, output: output.clone() };
+        let (exec_error, revert) = execution_failure(result);
+        assert_eq!(exec_error, "execution reverted: nope");
+        assert_eq!(revert, Some(output));
+
+        let result = ExecutionResult::Halt {
+            reason: HaltReason::OutOfGas(OutOfGasError::Basic),
+            gas_used: 21_000,
+        };
+        let (exec_error, revert) = execution_failure(result);
+        assert_eq!(exec_error, "execution halted: OutOfGas(Basic)");
+        assert_eq!(revert, None);
+    }
+}
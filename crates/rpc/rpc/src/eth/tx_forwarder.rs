@@ -0,0 +1,298 @@
+//! Forwarding of `eth_sendRawTransaction` submissions to an upstream endpoint.
+
+use crate::eth::bundle_forwarder::JsonRpcRequest;
+use alloy_primitives::{hex, Bytes, B256};
+use jsonrpsee_types::ErrorObject;
+use reth_metrics::{
+    metrics,
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_rpc_eth_types::EthApiError;
+use reth_rpc_server_types::{constants::DEFAULT_TX_FORWARD_RETRIES, result::internal_rpc_err};
+use serde::Deserialize;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+
+/// The default delay before the first retry of a failed forwarding attempt.
+pub const DEFAULT_TX_FORWARD_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Whether forwarded transactions are also inserted into the local pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TxForwardMode {
+    /// Transactions are forwarded and, once accepted upstream, inserted into the local pool.
+    #[default]
+    Additional,
+    /// Transactions are only forwarded and never enter the local pool.
+    Exclusive,
+}
+
+impl TxForwardMode {
+    /// Returns true if forwarded transactions are inserted into the local pool.
+    pub const fn is_additional(&self) -> bool {
+        matches!(self, Self::Additional)
+    }
+}
+
+/// Configuration of a [`TxForwarder`].
+#[derive(Debug, Clone)]
+pub struct TxForwarderConfig {
+    /// The URL of the sequencer or relay transactions are forwarded to.
+    pub endpoint: String,
+    /// Whether forwarded transactions are also inserted into the local pool.
+    pub mode: TxForwardMode,
+    /// The number of retries of a transaction that couldn't be delivered.
+    pub max_retries: u32,
+    /// The delay before the first retry, doubled with every further retry.
+    pub retry_backoff: Duration,
+}
+
+impl TxForwarderConfig {
+    /// Creates a new configuration that forwards transactions to the given endpoint.
+    pub fn new(endpoint: impl Into<String>) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            mode: TxForwardMode::default(),
+            max_retries: DEFAULT_TX_FORWARD_RETRIES,
+            retry_backoff: DEFAULT_TX_FORWARD_BACKOFF,
+        }
+    }
+
+    /// Sets whether forwarded transactions are also inserted into the local pool.
+    pub const fn with_mode(mut self, mode: TxForwardMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the number of retries of a transaction that couldn't be delivered.
+    pub const fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+}
+
+/// Errors that occur when forwarding a transaction.
+#[derive(Debug, thiserror::Error)]
+pub enum TxForwardError {
+    /// The upstream endpoint rejected the transaction.
+    #[error("upstream rejected transaction: {}", .0.message())]
+    Rejected(ErrorObject<'static>),
+    /// The transaction couldn't be delivered to the upstream endpoint.
+    #[error("failed to forward transaction: {0}")]
+    Unavailable(String),
+}
+
+impl From<TxForwardError> for EthApiError {
+    fn from(err: TxForwardError) -> Self {
+        match err {
+            // the error of the upstream endpoint is returned to the caller as is
+            TxForwardError::Rejected(err) => Self::other(err),
+            err @ TxForwardError::Unavailable(_) => Self::other(internal_rpc_err(err.to_string())),
+        }
+    }
+}
+
+/// Relays raw transactions to an upstream endpoint, e.g. an L2 sequencer or a private relay.
+///
+/// Transactions that couldn't be delivered are retried with exponential backoff, while errors
+/// returned by the endpoint are handed back to the caller right away.
+#[derive(Clone)]
+pub struct TxForwarder {
+    inner: Arc<TxForwarderInner>,
+}
+
+impl TxForwarder {
+    /// Creates a new forwarder with the given configuration.
+    pub fn new(config: TxForwarderConfig) -> Self {
+        Self {
+            inner: Arc::new(TxForwarderInner {
+                config,
+                client: reqwest::Client::new(),
+                metrics: TxForwarderMetrics::default(),
+            }),
+        }
+    }
+
+    /// Returns the URL transactions are forwarded to.
+    pub fn endpoint(&self) -> &str {
+        &self.inner.config.endpoint
+    }
+
+    /// Returns whether forwarded transactions are also inserted into the local pool.
+    pub fn mode(&self) -> TxForwardMode {
+        self.inner.config.mode
+    }
+
+    /// Forwards the encoded transaction via `eth_sendRawTransaction` and returns the hash
+    /// reported by the upstream endpoint.
+    pub async fn forward_raw_transaction(&self, tx: &[u8]) -> Result<B256, TxForwardError> {
+        let request = JsonRpcRequest::new("eth_sendRawTransaction", (hex::encode_prefixed(tx),));
+        let body = serde_json::to_vec(&request)
+            .map_err(|err| TxForwardError::Unavailable(err.to_string()))?;
+        let body = Bytes::from(body);
+
+        let config = &self.inner.config;
+        let metrics = &self.inner.metrics;
+        let start = Instant::now();
+        let mut backoff = config.retry_backoff;
+        let mut attempt = 1;
+        let result = loop {
+            match self.inner.post(body.clone()).await {
+                Err(TxForwardError::Unavailable(err)) if attempt <= config.max_retries => {
+                    debug!(
+                        target: "rpc::eth::tx_forwarder",
+                        url = %config.endpoint,
+                        attempt,
+                        %err,
+                        "Failed to forward transaction, retrying"
+                    );
+                    metrics.retries.increment(1);
+                    tokio::time::sleep(backoff).await;
+                    backoff = backoff.saturating_mul(2);
+                    attempt += 1;
+                }
+                result => break result,
+            }
+        };
+        metrics.forward_latency.record(start.elapsed().as_secs_f64());
+
+        match &result {
+            Ok(_) => metrics.forwarded_transactions.increment(1),
+            Err(TxForwardError::Rejected(_)) => metrics.rejected_transactions.increment(1),
+            Err(TxForwardError::Unavailable(err)) => {
+                metrics.failed_transactions.increment(1);
+                warn!(
+                    target: "rpc::eth::tx_forwarder",
+                    url = %config.endpoint,
+                    attempt,
+                    %err,
+                    "Giving up forwarding transaction"
+                );
+            }
+        }
+        result
+    }
+}
+
+impl std::fmt::Debug for TxForwarder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxForwarder").field("config", &self.inner.config).finish_non_exhaustive()
+    }
+}
+
+/// Container type for `TxForwarder` internals
+struct TxForwarderInner {
+    config: TxForwarderConfig,
+    client: reqwest::Client,
+    metrics: TxForwarderMetrics,
+}
+
+impl TxForwarderInner {
+    /// Sends the JSON-RPC request body to the endpoint and returns the transaction hash.
+    async fn post(&self, body: Bytes) -> Result<B256, TxForwardError> {
+        let response = self
+            .client
+            .post(&self.config.endpoint)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.0)
+            .send()
+            .await
+            .map_err(|err| TxForwardError::Unavailable(err.to_string()))?;
+        let http_status = response.status();
+        let response =
+            response.bytes().await.map_err(|err| TxForwardError::Unavailable(err.to_string()))?;
+
+        match parse_response(&response) {
+            Err(TxForwardError::Unavailable(_)) if !http_status.is_success() => {
+                Err(TxForwardError::Unavailable(format!("unexpected status {http_status}")))
+            }
+            result => result,
+        }
+    }
+}
+
+/// Parses the response of the upstream endpoint to `eth_sendRawTransaction`.
+fn parse_response(body: &[u8]) -> Result<B256, TxForwardError> {
+    let response: JsonRpcResponse = serde_json::from_slice(body)
+        .map_err(|err| TxForwardError::Unavailable(format!("invalid response: {err}")))?;
+    if let Some(error) = response.error {
+        return Err(TxForwardError::Rejected(ErrorObject::owned(
+            error.code,
+            error.message,
+            error.data,
+        )))
+    }
+    response
+        .result
+        .ok_or_else(|| TxForwardError::Unavailable("response without result".to_string()))
+}
+
+/// The response of the upstream endpoint.
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<B256>,
+    error: Option<JsonRpcError>,
+}
+
+/// The error object of a JSON-RPC response.
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    code: i32,
+    message: String,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+}
+
+/// Metrics for the forwarding of raw transactions.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.eth.tx_forwarder")]
+struct TxForwarderMetrics {
+    /// The number of transactions accepted by the upstream endpoint.
+    forwarded_transactions: Counter,
+    /// The number of transactions rejected by the upstream endpoint.
+    rejected_transactions: Counter,
+    /// The number of transactions that couldn't be delivered to the upstream endpoint.
+    failed_transactions: Counter,
+    /// The number of retried forwarding attempts.
+    retries: Counter,
+    /// The time it took to forward a transaction, including retries.
+    forward_latency: Histogram,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee_types::error::INVALID_PARAMS_CODE;
+
+    #[test]
+    fn parses_accepted_transaction() {
+        let hash = B256::repeat_byte(0x11);
+        let body = format!(r#"{{"jsonrpc":"2.0","id":1,"result":"{hash}"}}"#);
+        assert_eq!(parse_response(body.as_bytes()).unwrap(), hash);
+    }
+
+    #[test]
+    fn maps_upstream_error_to_caller() {
+        let body = br#"{"jsonrpc":"2.0","id":1,"error":{"code":-32602,"message":"nonce too low","data":"0x01"}}"#;
+        let err = parse_response(body).unwrap_err();
+        assert!(matches!(err, TxForwardError::Rejected(_)));
+
+        let EthApiError::Other(err) = EthApiError::from(err) else { panic!("expected other") };
+        let err = err.to_rpc_error();
+        assert_eq!(err.code(), INVALID_PARAMS_CODE);
+        assert_eq!(err.message(), "nonce too low");
+        assert_eq!(err.data().unwrap().get(), r#""0x01""#);
+    }
+
+    #[test]
+    fn rejects_malformed_response() {
+        assert!(matches!(parse_response(b"not json"), Err(TxForwardError::Unavailable(_))));
+        assert!(matches!(
+            parse_response(br#"{"jsonrpc":"2.0","id":1}"#),
+            Err(TxForwardError::Unavailable(_))
+        ));
+    }
+}
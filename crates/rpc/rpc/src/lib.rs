@@ -33,6 +33,7 @@ use pin_project as _;
 use tower as _;
 
 mod admin;
+mod archive_state;
 mod debug;
 mod engine;
 pub mod eth;
@@ -47,13 +48,17 @@ mod validation;
 mod web3;
 
 pub use admin::AdminApi;
-pub use debug::DebugApi;
+pub use archive_state::{
+    ArchiveStateConfig, ArchiveStateError, ArchiveStateFetcher, ArchiveStateProvider,
+    DEFAULT_ARCHIVE_STATE_TIMEOUT,
+};
+pub use debug::{DebugApi, DebugExecuteBlock};
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{helpers::SyncListener, EthApi, EthApiBuilder, EthBundle, EthFilter, EthPubSub};
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
-pub use reth::RethApi;
+pub use reth::{AccountHistoryValue, RethApi, RethPrecompiles, RethPrune};
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
 pub use txpool::TxPoolApi;
@@ -1,33 +1,52 @@
 use alloy_consensus::{BlockHeader, Typed2718};
 use alloy_eips::{eip1898::LenientBlockNumberOrTag, BlockId};
 use alloy_network::{ReceiptResponse, TransactionResponse};
-use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxHash, B256, U256};
 use alloy_rpc_types_eth::{BlockTransactions, TransactionReceipt};
 use alloy_rpc_types_trace::{
     otterscan::{
         BlockDetails, ContractCreator, InternalOperation, OperationType, OtsBlockTransactions,
-        OtsReceipt, OtsTransactionReceipt, TraceEntry, TransactionsWithReceipts,
+        OtsReceipt, OtsTransactionReceipt, TraceEntry,
     },
     parity::{Action, CreateAction, CreateOutput, TraceOutput},
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
-use reth_rpc_api::{EthApiServer, OtterscanServer};
+use reth_errors::ProviderError;
+use reth_rpc_api::{
+    EthApiServer, OtsBlockDetails, OtsTransactionsWithReceipts, OtterscanServer, WithdrawalsSummary,
+};
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction,
+    FromEthApiError, FullEthApiTypes, RpcBlock, RpcHeader, RpcNodeCore, RpcReceipt, RpcTransaction,
 };
 use reth_rpc_eth_types::{utils::binary_search, EthApiError};
 use reth_rpc_server_types::result::internal_rpc_err;
+use reth_storage_api::{BlockNumReader, ChangeSetReader};
 use revm::context_interface::result::ExecutionResult;
 use revm_inspectors::{
     tracing::{types::CallTraceNode, TracingInspectorConfig},
     transfer::{TransferInspector, TransferKind},
 };
+use std::ops::RangeInclusive;
 
 const API_LEVEL: u64 = 8;
 
+/// The number of blocks whose account history is read at once when searching the transactions of
+/// an address.
+const SEARCH_WINDOW: u64 = 100_000;
+
+/// The direction in which the transactions of an address are searched, see
+/// `ots_searchTransactionsBefore` and `ots_searchTransactionsAfter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchDirection {
+    /// Towards the genesis block.
+    Before,
+    /// Towards the latest block.
+    After,
+}
+
 /// Otterscan API.
 #[derive(Debug)]
 pub struct OtterscanApi<Eth> {
@@ -50,7 +69,7 @@ where
         &self,
         block: RpcBlock<Eth::NetworkTypes>,
         receipts: Vec<RpcReceipt<Eth::NetworkTypes>>,
-    ) -> RpcResult<BlockDetails<RpcHeader<Eth::NetworkTypes>>> {
+    ) -> RpcResult<OtsBlockDetails<RpcHeader<Eth::NetworkTypes>>> {
         // blob fee is burnt, so we don't need to calculate it
         let total_fees = receipts
             .iter()
@@ -59,7 +78,176 @@ where
             })
             .sum::<u128>();
 
-        Ok(BlockDetails::new(block, Default::default(), U256::from(total_fees)))
+        let withdrawals_summary =
+            block.withdrawals.as_ref().map(|withdrawals| WithdrawalsSummary {
+                count: withdrawals.len() as u64,
+                total_amount: withdrawals
+                    .iter()
+                    .fold(U256::ZERO, |total, withdrawal| total + withdrawal.amount_wei()),
+            });
+
+        Ok(OtsBlockDetails {
+            details: BlockDetails::new(block, Default::default(), U256::from(total_fees)),
+            withdrawals_summary,
+        })
+    }
+}
+
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer<
+            RpcTxReq<Eth::NetworkTypes>,
+            RpcTransaction<Eth::NetworkTypes>,
+            RpcBlock<Eth::NetworkTypes>,
+            RpcReceipt<Eth::NetworkTypes>,
+            RpcHeader<Eth::NetworkTypes>,
+        > + EthTransactions
+        + TraceExt
+        + RpcNodeCore<Provider: ChangeSetReader>
+        + 'static,
+{
+    /// Searches the transactions of the address, starting at the given block in the given
+    /// direction, see `ots_searchTransactionsBefore` and `ots_searchTransactionsAfter`.
+    ///
+    /// The blocks that changed the account of the address according to the account history are
+    /// traced to find the transactions in which the address is the sender, the recipient or the
+    /// target of an internal call. Transactions that only call the address without changing its
+    /// account, e.g. calls that only write to its storage, are not found.
+    ///
+    /// A page always ends with the last transaction of a block, so the block of the last
+    /// transaction of a page can be used to request the next page.
+    async fn search_transactions(
+        &self,
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+        direction: SearchDirection,
+    ) -> RpcResult<OtsTransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        // Otterscan passes zero to search from the latest or the earliest block respectively
+        let block_number = block_number.into_inner().as_number().unwrap_or_default();
+        let head = self.eth.provider().best_block_number().map_err(EthApiError::from)?;
+
+        let windows = match direction {
+            SearchDirection::Before => {
+                let high = if block_number == 0 { head } else { (block_number - 1).min(head) };
+                (0..=high)
+                    .rev()
+                    .step_by(SEARCH_WINDOW as usize)
+                    .map(|high| high.saturating_sub(SEARCH_WINDOW - 1)..=high)
+                    .collect::<Vec<_>>()
+            }
+            SearchDirection::After => (block_number + 1..=head)
+                .step_by(SEARCH_WINDOW as usize)
+                .map(|low| low..=low.saturating_add(SEARCH_WINDOW - 1).min(head))
+                .collect(),
+        };
+
+        let mut transactions = Vec::new();
+        let mut has_more = false;
+        'search: for window in windows {
+            let Some(mut blocks) = self.changed_blocks(address, window).await? else {
+                // the history of the window is pruned, and so is the history of all older windows
+                match direction {
+                    SearchDirection::Before => break,
+                    SearchDirection::After => continue,
+                }
+            };
+            if direction == SearchDirection::Before {
+                blocks.reverse();
+            }
+
+            for block in blocks {
+                if transactions.len() >= page_size {
+                    has_more = true;
+                    break 'search
+                }
+
+                let mut block_transactions = self.address_transactions(address, block).await?;
+                if direction == SearchDirection::Before {
+                    block_transactions.reverse();
+                }
+                transactions.extend(block_transactions);
+            }
+        }
+
+        // transactions are always returned in descending order
+        if direction == SearchDirection::After {
+            transactions.reverse();
+        }
+        let (txs, receipts) = transactions.into_iter().unzip();
+        let (first_page, last_page) = match direction {
+            SearchDirection::Before => (block_number == 0, !has_more),
+            SearchDirection::After => (!has_more, block_number == 0),
+        };
+        Ok(OtsTransactionsWithReceipts { txs, receipts, first_page, last_page })
+    }
+
+    /// Returns the blocks in the range that changed the account of the address, in ascending
+    /// order, or `None` if the account history of the range is pruned.
+    async fn changed_blocks(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> RpcResult<Option<Vec<BlockNumber>>> {
+        self.eth
+            .spawn_blocking_io(move |this| match this.provider().account_changes(address, range) {
+                Ok(changes) => Ok(Some(changes.into_iter().map(|(block, _)| block).collect())),
+                Err(ProviderError::StateAtBlockPruned(_)) => Ok(None),
+                Err(err) => Err(Eth::Error::from_eth_err(err)),
+            })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Returns the transactions of the block in which the address is the sender, the recipient
+    /// or the target of an internal call, in ascending order.
+    async fn address_transactions(
+        &self,
+        address: Address,
+        block_number: BlockNumber,
+    ) -> RpcResult<Vec<(RpcTransaction<Eth::NetworkTypes>, OtsTransactionReceipt)>> {
+        let block_id = BlockId::from(block_number);
+        let config = TracingInspectorConfig::none();
+        let indices = self
+            .eth
+            .trace_block_with(block_id, None, config, move |tx_info, ctx| {
+                let nodes = ctx.inspector.into_traces().into_nodes();
+                let touched = nodes
+                    .iter()
+                    .any(|node| node.trace.caller == address || node.trace.address == address);
+                Ok(tx_info.index.filter(|_| touched))
+            })
+            .await
+            .map_err(Into::into)?
+            .unwrap_or_default()
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        if indices.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let block = self.eth.block_by_number(block_number.into(), true);
+        let receipts = self.eth.block_receipts(block_id);
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+        let block = block.ok_or(EthApiError::HeaderNotFound(block_id))?;
+        let receipts = receipts.ok_or(EthApiError::ReceiptsNotFound(block_id))?;
+
+        let timestamp = Some(block.header.timestamp());
+        let BlockTransactions::Full(transactions) = block.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .enumerate()
+            .filter(|(index, _)| indices.contains(&(*index as u64)))
+            .map(|(_, (tx, receipt))| {
+                let receipt = ots_receipt(receipt, tx.ty(), timestamp);
+                (tx, receipt)
+            })
+            .collect())
     }
 }
 
@@ -75,6 +263,7 @@ where
             RpcHeader<Eth::NetworkTypes>,
         > + EthTransactions
         + TraceExt
+        + RpcNodeCore<Provider: ChangeSetReader>
         + 'static,
 {
     /// Handler for `ots_getHeaderByNumber` and `erigon_getHeaderByNumber`
@@ -176,7 +365,7 @@ where
     async fn get_block_details(
         &self,
         block_number: LenientBlockNumberOrTag,
-    ) -> RpcResult<BlockDetails<RpcHeader<Eth::NetworkTypes>>> {
+    ) -> RpcResult<OtsBlockDetails<RpcHeader<Eth::NetworkTypes>>> {
         let block_number = block_number.into_inner();
         let block = self.eth.block_by_number(block_number, true);
         let block_id = block_number.into();
@@ -192,7 +381,7 @@ where
     async fn get_block_details_by_hash(
         &self,
         block_hash: B256,
-    ) -> RpcResult<BlockDetails<RpcHeader<Eth::NetworkTypes>>> {
+    ) -> RpcResult<OtsBlockDetails<RpcHeader<Eth::NetworkTypes>>> {
         let block = self.eth.block_by_hash(block_hash, true);
         let block_id = block_hash.into();
         let receipts = self.eth.block_receipts(block_id);
@@ -247,32 +436,7 @@ where
         let receipts = receipts
             .drain(page_start..page_end)
             .zip(transactions.iter().map(Typed2718::ty))
-            .map(|(receipt, tx_ty)| {
-                let inner = OtsReceipt {
-                    status: receipt.status(),
-                    cumulative_gas_used: receipt.cumulative_gas_used(),
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: tx_ty,
-                };
-
-                let receipt = TransactionReceipt {
-                    inner,
-                    transaction_hash: receipt.transaction_hash(),
-                    transaction_index: receipt.transaction_index(),
-                    block_hash: receipt.block_hash(),
-                    block_number: receipt.block_number(),
-                    gas_used: receipt.gas_used(),
-                    effective_gas_price: receipt.effective_gas_price(),
-                    blob_gas_used: receipt.blob_gas_used(),
-                    blob_gas_price: receipt.blob_gas_price(),
-                    from: receipt.from(),
-                    to: receipt.to(),
-                    contract_address: receipt.contract_address(),
-                };
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|(receipt, tx_ty)| ots_receipt(receipt, tx_ty, timestamp))
             .collect();
 
         // use `transaction_count` to indicate the paginate information
@@ -284,21 +448,21 @@ where
     /// Handler for `ots_searchTransactionsBefore`
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<OtsTransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        self.search_transactions(address, block_number, page_size, SearchDirection::Before).await
     }
 
     /// Handler for `ots_searchTransactionsAfter`
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: LenientBlockNumberOrTag,
-        _page_size: usize,
-    ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        address: Address,
+        block_number: LenientBlockNumberOrTag,
+        page_size: usize,
+    ) -> RpcResult<OtsTransactionsWithReceipts<RpcTransaction<Eth::NetworkTypes>>> {
+        self.search_transactions(address, block_number, page_size, SearchDirection::After).await
     }
 
     /// Handler for `ots_getTransactionBySenderAndNonce`
@@ -382,3 +546,35 @@ where
         Ok(found)
     }
 }
+
+/// Converts a receipt into the receipt returned by Otterscan endpoints, which omits the logs.
+fn ots_receipt(
+    receipt: impl ReceiptResponse,
+    tx_ty: u8,
+    timestamp: Option<u64>,
+) -> OtsTransactionReceipt {
+    let inner = OtsReceipt {
+        status: receipt.status(),
+        cumulative_gas_used: receipt.cumulative_gas_used(),
+        logs: None,
+        logs_bloom: None,
+        r#type: tx_ty,
+    };
+
+    let receipt = TransactionReceipt {
+        inner,
+        transaction_hash: receipt.transaction_hash(),
+        transaction_index: receipt.transaction_index(),
+        block_hash: receipt.block_hash(),
+        block_number: receipt.block_number(),
+        gas_used: receipt.gas_used(),
+        effective_gas_price: receipt.effective_gas_price(),
+        blob_gas_used: receipt.blob_gas_used(),
+        blob_gas_price: receipt.blob_gas_price(),
+        from: receipt.from(),
+        to: receipt.to(),
+        contract_address: receipt.contract_address(),
+    };
+
+    OtsTransactionReceipt { receipt, timestamp }
+}
@@ -1,20 +1,72 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+};
 
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_consensus::{BlockHeader, Transaction};
+use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
+use alloy_primitives::{Address, B256, U256, U64};
+use alloy_rpc_types_eth::Filter;
 use async_trait::async_trait;
-use futures::StreamExt;
+use futures::{Stream, StreamExt};
 use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
 use jsonrpsee_types::ErrorObject;
-use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
-use reth_errors::RethResult;
-use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::RethApiServer;
-use reth_rpc_eth_types::{EthApiError, EthResult};
-use reth_rpc_server_types::result::internal_rpc_err;
-use reth_storage_api::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_chain_state::{CanonStateSubscriptions, CanonicalEpochNotification};
+use reth_errors::{ProviderResult, RethResult};
+use reth_evm::precompile_stats::PrecompileStatsStore;
+use reth_execution_types::Chain;
+use reth_primitives_traits::{Account, Block, NodePrimitives, RecoveredBlock, SignedTransaction};
+use reth_prune_types::PrunePins;
+use reth_rpc_api::{
+    AccountChange, AccountDiff, AccountHistoryPoint, AccountState, BlockStateChanges, CanonicalLog,
+    PrecompileStat, PrecompileStatsReport, PruneCheckpoints, RethApiServer,
+    RethPrecompileApiServer, RethPruneApiServer, RethSubscriptionKind, RethSubscriptionParams,
+    SponsoredTransaction, StateDiff, StorageChange, StorageSlotChange,
+};
+use reth_rpc_eth_types::{logs_utils, EthApiError, EthResult};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_storage_api::{
+    AccountReader, BlockIdReader, BlockReaderIdExt, ChangeSetReader, PruneCheckpointReader,
+    StateProvider, StateProviderFactory, StorageChangeSetReader,
+};
 use reth_tasks::TaskSpawner;
-use tokio::sync::oneshot;
+use revm::database::states::reverts::AccountInfoRevert;
+use serde::Serialize;
+use tokio::sync::{oneshot, watch};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tracing::debug;
+
+/// The maximum number of blocks `reth_getStorageChanges` scans at once.
+const MAX_STORAGE_CHANGES_RANGE: u64 = 10_000;
+
+/// The maximum number of points returned by `reth_getBalanceHistory` and `reth_getNonceHistory`.
+const MAX_ACCOUNT_HISTORY_POINTS: u64 = 10_000;
+
+/// The maximum number of blocks `reth_getSponsoredTransactions` scans at once.
+const MAX_SPONSORED_TRANSACTIONS_RANGE: u64 = 1_000;
+
+/// The maximum number of addresses a single `stateDiffs` subscription can watch.
+const MAX_STATE_DIFF_ADDRESSES: usize = 1_000;
+
+/// The value of an account that is tracked by an account history query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountHistoryValue {
+    /// The balance of the account, see `reth_getBalanceHistory`.
+    Balance,
+    /// The nonce of the account, see `reth_getNonceHistory`.
+    Nonce,
+}
+
+impl AccountHistoryValue {
+    /// Returns the value of the account, zero if the account doesn't exist.
+    fn of(self, account: Option<Account>) -> U256 {
+        account.map_or(U256::ZERO, |account| match self {
+            Self::Balance => account.balance,
+            Self::Nonce => U256::from(account.nonce),
+        })
+    }
+}
 
 /// `reth` API implementation.
 ///
@@ -40,7 +92,11 @@ impl<Provider> RethApi<Provider> {
 
 impl<Provider> RethApi<Provider>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + StateProviderFactory
+        + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -88,6 +144,459 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns all account and storage changes of the given block.
+    pub async fn block_state_changes(&self, block_id: BlockId) -> EthResult<BlockStateChanges> {
+        self.on_blocking_task(|this| async move { this.try_block_state_changes(block_id) }).await
+    }
+
+    fn try_block_state_changes(&self, block_id: BlockId) -> EthResult<BlockStateChanges> {
+        let Some(block_number) = self.provider().block_number_for_id(block_id)? else {
+            return Err(EthApiError::HeaderNotFound(block_id))
+        };
+        let Some(block_hash) = self.provider().block_hash(block_number)? else {
+            return Err(EthApiError::HeaderNotFound(block_id))
+        };
+
+        // The changesets hold the values before the block, the values after the block are read
+        // from the state at the end of the block.
+        let state = self.provider().history_by_block_number(block_number)?;
+
+        let mut accounts = self
+            .provider()
+            .account_block_changeset(block_number)?
+            .into_iter()
+            .map(|account_before| {
+                let post = AccountReader::basic_account(&state, &account_before.address)?;
+                Ok(AccountChange {
+                    address: account_before.address,
+                    pre: account_before.info.map(account_state),
+                    post: post.map(account_state),
+                })
+            })
+            .collect::<RethResult<Vec<_>>>()?;
+        accounts.sort_unstable_by_key(|change| change.address);
+
+        let mut storage = self
+            .provider()
+            .storage_changeset(block_number)?
+            .into_iter()
+            .map(|(block_address, entry)| {
+                let address = block_address.address();
+                let post = state.storage(address, entry.key)?.unwrap_or_default();
+                Ok(StorageSlotChange {
+                    address,
+                    slot: entry.key,
+                    pre: entry.value.into(),
+                    post: post.into(),
+                })
+            })
+            .collect::<RethResult<Vec<_>>>()?;
+        storage.sort_unstable_by_key(|change| (change.address, change.slot));
+
+        Ok(BlockStateChanges { block_number, block_hash, accounts, storage })
+    }
+
+    /// Returns all changes of the storage slot of the account in the given block range.
+    pub async fn storage_changes(
+        &self,
+        address: Address,
+        slot: B256,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<StorageChange>> {
+        self.on_blocking_task(|this| async move {
+            this.try_storage_changes(address, slot, from_block, to_block)
+        })
+        .await
+    }
+
+    fn try_storage_changes(
+        &self,
+        address: Address,
+        slot: B256,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<StorageChange>> {
+        let Some(from) = self.provider().convert_block_number(from_block)? else {
+            return Err(EthApiError::HeaderNotFound(from_block.into()))
+        };
+        let Some(to) = self.provider().convert_block_number(to_block)? else {
+            return Err(EthApiError::HeaderNotFound(to_block.into()))
+        };
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to - from >= MAX_STORAGE_CHANGES_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds the maximum of {MAX_STORAGE_CHANGES_RANGE} blocks"
+            )))
+        }
+
+        let changes = self.provider().storage_slot_changes(address, slot, from..=to)?;
+
+        // The value after a change is the value before the next change. For the last change it is
+        // the value at the end of that block.
+        let last_post = match changes.last() {
+            Some((block_number, _)) => self
+                .provider()
+                .history_by_block_number(*block_number)?
+                .storage(address, slot)?
+                .unwrap_or_default(),
+            None => return Ok(Vec::new()),
+        };
+
+        let posts = changes.iter().skip(1).map(|(_, pre)| *pre).chain(core::iter::once(last_post));
+        Ok(changes
+            .iter()
+            .zip(posts)
+            .map(|((block_number, pre), post)| StorageChange {
+                block_number: *block_number,
+                pre: (*pre).into(),
+                post: post.into(),
+            })
+            .collect())
+    }
+
+    /// Returns the value of the account at the end of every `step`-th block in the given range,
+    /// starting with `from_block`.
+    pub async fn account_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        step: u64,
+        value: AccountHistoryValue,
+    ) -> EthResult<Vec<AccountHistoryPoint>> {
+        self.on_blocking_task(|this| async move {
+            this.try_account_history(address, from_block, to_block, step, value)
+        })
+        .await
+    }
+
+    fn try_account_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        step: u64,
+        value: AccountHistoryValue,
+    ) -> EthResult<Vec<AccountHistoryPoint>> {
+        let Some(from) = self.provider().convert_block_number(from_block)? else {
+            return Err(EthApiError::HeaderNotFound(from_block.into()))
+        };
+        let Some(to) = self.provider().convert_block_number(to_block)? else {
+            return Err(EthApiError::HeaderNotFound(to_block.into()))
+        };
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if step == 0 {
+            return Err(EthApiError::InvalidParams("step must be greater than zero".to_string()))
+        }
+        let len = (to - from) / step + 1;
+        if len > MAX_ACCOUNT_HISTORY_POINTS {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range and step yield more than {MAX_ACCOUNT_HISTORY_POINTS} points"
+            )))
+        }
+
+        // The account at the end of a block is the account before the next change. After the last
+        // change it is the account at the end of the range.
+        let changes = if from < to {
+            self.provider().account_changes(address, from + 1..=to)?
+        } else {
+            Vec::new()
+        };
+        let state = self.provider().history_by_block_number(to)?;
+        let last = AccountReader::basic_account(&state, &address)?;
+
+        let mut changes = changes.into_iter().peekable();
+        let mut points = Vec::with_capacity(len as usize);
+        for block_number in (from..=to).step_by(step as usize) {
+            while changes.next_if(|(changed_in, _)| *changed_in <= block_number).is_some() {}
+            let account = changes.peek().map_or(last, |(_, before)| *before);
+            points.push(AccountHistoryPoint { block_number, value: value.of(account) });
+        }
+        Ok(points)
+    }
+
+    /// Returns the transactions in the given block range that were sent by the fee payer on behalf
+    /// of other accounts.
+    pub async fn sponsored_transactions(
+        &self,
+        fee_payer: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<SponsoredTransaction>> {
+        self.on_blocking_task(|this| async move {
+            this.try_sponsored_transactions(fee_payer, from_block, to_block)
+        })
+        .await
+    }
+
+    fn try_sponsored_transactions(
+        &self,
+        fee_payer: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<SponsoredTransaction>> {
+        let Some(from) = self.provider().convert_block_number(from_block)? else {
+            return Err(EthApiError::HeaderNotFound(from_block.into()))
+        };
+        let Some(to) = self.provider().convert_block_number(to_block)? else {
+            return Err(EthApiError::HeaderNotFound(to_block.into()))
+        };
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        if to - from >= MAX_SPONSORED_TRANSACTIONS_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds the maximum of {MAX_SPONSORED_TRANSACTIONS_RANGE} blocks"
+            )))
+        }
+
+        let mut transactions = Vec::new();
+        for block in self.provider().recovered_block_range(from..=to)? {
+            let sponsors = block.transactions_with_sender().any(|(sender, tx)| {
+                *sender == fee_payer && tx.authorization_list().is_some_and(|list| !list.is_empty())
+            });
+            if !sponsors {
+                continue
+            }
+
+            let state = self
+                .provider()
+                .history_by_block_number(block.header().number().saturating_sub(1))?;
+            transactions.extend(sponsored_transactions(&block, fee_payer, &state)?);
+        }
+        Ok(transactions)
+    }
+}
+
+impl<Provider> RethApi<Provider>
+where
+    Provider: CanonStateSubscriptions,
+{
+    /// Returns a stream that yields the state changes of the given addresses in every canonical
+    /// block that changed any of them.
+    ///
+    /// The changes of reverted blocks are emitted again with `removed` set.
+    pub fn state_diff_stream(&self, addresses: HashSet<Address>) -> impl Stream<Item = StateDiff> {
+        BroadcastStream::new(self.provider().subscribe_to_canonical_state()).flat_map(
+            move |notification| {
+                let diffs = match notification {
+                    Ok(notification) => {
+                        let mut diffs = Vec::new();
+                        if let Some(reverted) = notification.reverted() {
+                            diffs.extend(state_diffs(&*reverted, &addresses, true));
+                        }
+                        diffs.extend(state_diffs(&*notification.committed(), &addresses, false));
+                        diffs
+                    }
+                    Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                        debug!(target: "rpc::reth", missed, "State diff subscription lagged behind");
+                        Vec::new()
+                    }
+                };
+                futures::stream::iter(diffs)
+            },
+        )
+    }
+}
+
+impl<Provider> RethApi<Provider>
+where
+    Provider: CanonStateSubscriptions<Primitives: NodePrimitives<SignedTx: Encodable2718>>,
+{
+    /// Returns a stream that yields all logs that match the given filter, tagged with the canonical
+    /// epoch they were emitted in, or `None` if the provider doesn't track the canonical epoch.
+    ///
+    /// Logs of reverted blocks are emitted with `removed` set, tagged with the epoch of the reorg
+    /// that reverted them. The stream ends if it lags behind the canonical state notifications.
+    pub fn canonical_log_stream(&self, filter: Filter) -> Option<impl Stream<Item = CanonicalLog>> {
+        let notifications = self.provider().subscribe_to_canonical_epochs()?;
+        Some(notifications.flat_map(
+            move |CanonicalEpochNotification { canonical_epoch, notification }| {
+                let logs = notification
+                    .block_receipts()
+                    .into_iter()
+                    .flat_map(|(block_receipts, removed)| {
+                        logs_utils::matching_block_logs_with_tx_hashes(
+                            &filter,
+                            block_receipts.block,
+                            block_receipts.timestamp,
+                            block_receipts.tx_receipts.iter().map(|(tx, receipt)| (*tx, receipt)),
+                            removed,
+                        )
+                    })
+                    .map(|log| CanonicalLog { canonical_epoch, log })
+                    .collect::<Vec<_>>();
+                futures::stream::iter(logs)
+            },
+        ))
+    }
+}
+
+/// Converts the account into its RPC representation.
+fn account_state(account: Account) -> AccountState {
+    AccountState {
+        balance: account.balance,
+        nonce: account.nonce,
+        code_hash: account.get_bytecode_hash(),
+    }
+}
+
+/// Returns the transactions of the block that were sent by the fee payer with authorizations of
+/// other accounts that were applied, given the state before the block.
+///
+/// Per [EIP-7702], an authorization is skipped during execution if its chain id doesn't match, its
+/// signature doesn't recover, the authority has code that isn't a delegation or its nonce doesn't
+/// match the nonce of the authority at that point of the block. The nonces are tracked through the
+/// transactions and authorizations of the block, nonce changes by contract creations aren't.
+///
+/// [EIP-7702]: https://eips.ethereum.org/EIPS/eip-7702
+fn sponsored_transactions<B: Block>(
+    block: &RecoveredBlock<B>,
+    fee_payer: Address,
+    state: &dyn StateProvider,
+) -> ProviderResult<Vec<SponsoredTransaction>> {
+    let mut nonces = HashMap::<Address, u64>::new();
+    let mut delegatable = HashMap::<Address, bool>::new();
+    let mut transactions = Vec::new();
+
+    for (index, (sender, tx)) in block.transactions_with_sender().enumerate() {
+        nonces.insert(*sender, tx.nonce().saturating_add(1));
+        let Some(authorizations) = tx.authorization_list() else { continue };
+
+        let mut sponsored = Vec::new();
+        for auth in authorizations {
+            let chain_id = U256::from(tx.chain_id().unwrap_or_default());
+            if !auth.chain_id().is_zero() && *auth.chain_id() != chain_id {
+                continue
+            }
+            let Ok(authority) = auth.recover_authority() else { continue };
+
+            let can_delegate = match delegatable.get(&authority) {
+                Some(can_delegate) => *can_delegate,
+                None => {
+                    let can_delegate = state
+                        .account_code(&authority)?
+                        .is_none_or(|code| code.is_empty() || code.is_eip7702());
+                    *delegatable.entry(authority).or_insert(can_delegate)
+                }
+            };
+            if !can_delegate {
+                continue
+            }
+
+            let nonce = match nonces.get(&authority) {
+                Some(nonce) => *nonce,
+                None => state.basic_account(&authority)?.map_or(0, |account| account.nonce),
+            };
+            if nonce != auth.nonce() || nonce == u64::MAX {
+                continue
+            }
+            nonces.insert(authority, nonce + 1);
+
+            if *sender == fee_payer && authority != fee_payer && !sponsored.contains(&authority) {
+                sponsored.push(authority);
+            }
+        }
+
+        if !sponsored.is_empty() {
+            transactions.push(SponsoredTransaction {
+                block_number: block.header().number(),
+                transaction_hash: *tx.tx_hash(),
+                transaction_index: index as u64,
+                sponsored,
+            });
+        }
+    }
+
+    Ok(transactions)
+}
+
+/// Returns the state changes of the watched addresses in every block of the chain that changed any
+/// of them, ordered by block number.
+///
+/// The changes are computed from the execution outcome of the chain, by walking the reverts of the
+/// blocks backwards from the state at the tip of the chain.
+fn state_diffs<N: NodePrimitives>(
+    chain: &Chain<N>,
+    addresses: &HashSet<Address>,
+    removed: bool,
+) -> Vec<StateDiff> {
+    let outcome = chain.execution_outcome();
+    let bundle = &outcome.bundle;
+
+    // the accounts and storage of the watched addresses at the end of the current block
+    let mut state = addresses
+        .iter()
+        .filter_map(|address| {
+            let account = bundle.account(address)?;
+            let storage = account
+                .storage
+                .iter()
+                .map(|(slot, value)| (*slot, value.present_value))
+                .collect::<HashMap<_, _>>();
+            Some((*address, (account.info.clone(), storage)))
+        })
+        .collect::<HashMap<_, _>>();
+    if state.is_empty() {
+        return Vec::new()
+    }
+
+    let mut diffs = Vec::new();
+    for block in chain.blocks_iter().collect::<Vec<_>>().into_iter().rev() {
+        let index = block.header().number().saturating_sub(outcome.first_block()) as usize;
+        let Some(reverts) = bundle.reverts.get(index) else { continue };
+
+        let mut accounts = BTreeMap::new();
+        for (address, revert) in reverts {
+            let Some((info, storage)) = state.get_mut(address) else { continue };
+
+            let previous = match &revert.account {
+                AccountInfoRevert::DoNothing => info.clone(),
+                AccountInfoRevert::DeleteIt => None,
+                AccountInfoRevert::RevertTo(previous) => Some(previous.clone()),
+            };
+            let (balance, nonce) =
+                info.as_ref().map(|info| (info.balance, info.nonce)).unwrap_or_default();
+            let (previous_balance, previous_nonce) =
+                previous.as_ref().map(|info| (info.balance, info.nonce)).unwrap_or_default();
+            *info = previous;
+
+            let mut diff = AccountDiff {
+                balance: (balance != previous_balance).then_some(balance),
+                nonce: (nonce != previous_nonce).then_some(nonce),
+                storage: BTreeMap::new(),
+            };
+            for (slot, previous) in &revert.storage {
+                let previous = previous.to_previous_value();
+                let value = storage.insert(*slot, previous).unwrap_or_default();
+                if value != previous {
+                    diff.storage.insert((*slot).into(), value.into());
+                }
+            }
+
+            if !diff.is_empty() {
+                accounts.insert(*address, diff);
+            }
+        }
+
+        if !accounts.is_empty() {
+            diffs.push(StateDiff {
+                block_number: block.header().number(),
+                block_hash: block.hash(),
+                removed,
+                accounts,
+            });
+        }
+    }
+
+    diffs.reverse();
+    diffs
 }
 
 #[async_trait]
@@ -95,8 +604,9 @@ impl<Provider> RethApiServer for RethApi<Provider>
 where
     Provider: BlockReaderIdExt
         + ChangeSetReader
+        + StorageChangeSetReader
         + StateProviderFactory
-        + CanonStateSubscriptions
+        + CanonStateSubscriptions<Primitives: NodePrimitives<SignedTx: Encodable2718>>
         + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
@@ -107,6 +617,73 @@ where
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
 
+    /// Handler for `reth_getBlockStateChanges`
+    async fn reth_get_block_state_changes(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<BlockStateChanges> {
+        Ok(Self::block_state_changes(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getStorageChanges`
+    async fn reth_get_storage_changes(
+        &self,
+        address: Address,
+        slot: B256,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<StorageChange>> {
+        Ok(Self::storage_changes(self, address, slot, from_block, to_block).await?)
+    }
+
+    /// Handler for `reth_getBalanceHistory`
+    async fn reth_get_balance_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        step: U64,
+    ) -> RpcResult<Vec<AccountHistoryPoint>> {
+        Ok(Self::account_history(
+            self,
+            address,
+            from_block,
+            to_block,
+            step.to(),
+            AccountHistoryValue::Balance,
+        )
+        .await?)
+    }
+
+    /// Handler for `reth_getNonceHistory`
+    async fn reth_get_nonce_history(
+        &self,
+        address: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+        step: U64,
+    ) -> RpcResult<Vec<AccountHistoryPoint>> {
+        Ok(Self::account_history(
+            self,
+            address,
+            from_block,
+            to_block,
+            step.to(),
+            AccountHistoryValue::Nonce,
+        )
+        .await?)
+    }
+
+    /// Handler for `reth_getSponsoredTransactions`
+    async fn reth_get_sponsored_transactions(
+        &self,
+        fee_payer: Address,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<Vec<SponsoredTransaction>> {
+        Ok(Self::sponsored_transactions(self, fee_payer, from_block, to_block).await?)
+    }
+
     /// Handler for `reth_subscribeChainNotifications`
     async fn reth_subscribe_chain_notifications(
         &self,
@@ -120,13 +697,72 @@ where
 
         Ok(())
     }
+
+    /// Handler for `reth_subscribe`
+    async fn reth_subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        kind: RethSubscriptionKind,
+        params: Option<RethSubscriptionParams>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        match kind {
+            RethSubscriptionKind::StateDiffs => {
+                let addresses = match params {
+                    Some(RethSubscriptionParams::StateDiffs(params)) => params.addresses,
+                    _ => Vec::new(),
+                };
+                if addresses.is_empty() || addresses.len() > MAX_STATE_DIFF_ADDRESSES {
+                    pending
+                        .reject(invalid_params_rpc_err(format!(
+                            "stateDiffs requires between 1 and {MAX_STATE_DIFF_ADDRESSES} addresses"
+                        )))
+                        .await;
+                    return Ok(())
+                }
+
+                let sink = pending.accept().await?;
+                let stream = self.state_diff_stream(addresses.into_iter().collect());
+                self.inner.task_spawner.spawn(Box::pin(async move {
+                    let _ = pipe_from_stream(sink, Box::pin(stream)).await;
+                }));
+            }
+            RethSubscriptionKind::Logs => {
+                // if no params are provided, used default filter params
+                let filter = match params {
+                    Some(RethSubscriptionParams::Logs(filter)) => *filter,
+                    Some(RethSubscriptionParams::StateDiffs(_)) => {
+                        pending.reject(invalid_params_rpc_err("Invalid params for logs")).await;
+                        return Ok(())
+                    }
+                    None => Filter::default(),
+                };
+                let Some(stream) = self.canonical_log_stream(filter) else {
+                    pending
+                        .reject(internal_rpc_err("canonical epochs are not tracked by the node"))
+                        .await;
+                    return Ok(())
+                };
+
+                let sink = pending.accept().await?;
+                self.inner.task_spawner.spawn(Box::pin(async move {
+                    let _ = pipe_from_stream(sink, Box::pin(stream)).await;
+                }));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// Pipes all stream items to the subscription sink.
-async fn pipe_from_stream<N: NodePrimitives>(
+async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
-    mut stream: CanonStateNotificationStream<N>,
-) -> Result<(), ErrorObject<'static>> {
+    mut stream: St,
+) -> Result<(), ErrorObject<'static>>
+where
+    St: Stream<Item = T> + Unpin,
+    T: Serialize,
+{
     loop {
         tokio::select! {
             _ = sink.closed() => {
@@ -170,3 +806,279 @@ struct RethApiInner<Provider> {
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
 }
+
+/// `reth` API implementation for inspecting the state of the pruner.
+#[derive(Debug, Clone)]
+pub struct RethPrune<Provider> {
+    /// The provider to read the prune checkpoints from.
+    provider: Provider,
+    /// The block ranges of segments pinned by `ExEx`es.
+    prune_pins: watch::Receiver<PrunePins>,
+}
+
+// === impl RethPrune ===
+
+impl<Provider> RethPrune<Provider> {
+    /// Create a new instance of the [`RethPrune`]
+    pub const fn new(provider: Provider, prune_pins: watch::Receiver<PrunePins>) -> Self {
+        Self { provider, prune_pins }
+    }
+}
+
+#[async_trait]
+impl<Provider> RethPruneApiServer for RethPrune<Provider>
+where
+    Provider: PruneCheckpointReader + 'static,
+{
+    /// Handler for `reth_getPruneCheckpoints`
+    async fn reth_get_prune_checkpoints(&self) -> RpcResult<PruneCheckpoints> {
+        let checkpoints =
+            self.provider.get_prune_checkpoints().map_err(EthApiError::from)?.into_iter().collect();
+        Ok(PruneCheckpoints { checkpoints, pins: self.prune_pins.borrow().clone() })
+    }
+}
+
+/// `reth` API implementation for inspecting the precompile usage of executed blocks.
+#[derive(Debug, Clone)]
+pub struct RethPrecompiles<Provider> {
+    /// The provider to resolve the canonical blocks of a range.
+    provider: Provider,
+    /// The precompile usage of the recently executed blocks.
+    precompile_stats: PrecompileStatsStore,
+}
+
+// === impl RethPrecompiles ===
+
+impl<Provider> RethPrecompiles<Provider> {
+    /// Create a new instance of the [`RethPrecompiles`]
+    pub const fn new(provider: Provider, precompile_stats: PrecompileStatsStore) -> Self {
+        Self { provider, precompile_stats }
+    }
+}
+
+impl<Provider> RethPrecompiles<Provider>
+where
+    Provider: BlockIdReader,
+{
+    /// Returns the precompile usage in the given range of canonical blocks.
+    pub fn precompile_stats(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<PrecompileStatsReport> {
+        let Some(from) = self.provider.convert_block_number(from_block)? else {
+            return Err(EthApiError::HeaderNotFound(from_block.into()))
+        };
+        let Some(to) = self.provider.convert_block_number(to_block)? else {
+            return Err(EthApiError::HeaderNotFound(to_block.into()))
+        };
+        if from > to {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+        let max_range = self.precompile_stats.capacity() as u64;
+        if to - from >= max_range {
+            return Err(EthApiError::InvalidParams(format!(
+                "block range exceeds the {max_range} blocks precompile usage is kept for"
+            )))
+        }
+
+        let block_hashes = self.provider.canonical_hashes_range(from, to + 1)?;
+        let (stats, covered_blocks) = self.precompile_stats.aggregate(&block_hashes);
+
+        let mut precompiles = stats
+            .iter()
+            .map(|(address, usage)| PrecompileStat {
+                address: *address,
+                calls: usage.calls,
+                gas_used: usage.gas_used,
+            })
+            .collect::<Vec<_>>();
+        precompiles.sort_by(|a, b| b.gas_used.cmp(&a.gas_used));
+
+        Ok(PrecompileStatsReport {
+            from_block: from,
+            to_block: to,
+            covered_blocks: covered_blocks as u64,
+            precompiles,
+        })
+    }
+}
+
+#[async_trait]
+impl<Provider> RethPrecompileApiServer for RethPrecompiles<Provider>
+where
+    Provider: BlockIdReader + 'static,
+{
+    /// Handler for `reth_precompileStats`
+    async fn reth_precompile_stats(
+        &self,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> RpcResult<PrecompileStatsReport> {
+        Ok(self.precompile_stats(from_block, to_block)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxEip7702};
+    use alloy_eips::eip7702::{Authorization, SignedAuthorization};
+    use alloy_primitives::{Bytes, Log};
+    use reth_chain_state::test_utils::TestCanonStateSubscriptions;
+    use reth_ethereum_primitives::{Block, BlockBody, Receipt, Transaction};
+    use reth_execution_types::ExecutionOutcome;
+    use reth_primitives_traits::crypto::secp256k1::sign_message;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_tasks::TokioTaskExecutor;
+    use reth_testing_utils::generators::{
+        self, generate_keys, random_signed_tx, sign_tx_with_key_pair,
+    };
+    use revm::database::BundleState;
+    use secp256k1::Keypair;
+
+    fn address(key: &Keypair) -> Address {
+        Address::from_raw_public_key(&key.public_key().serialize_uncompressed()[1..])
+    }
+
+    fn authorization(key: &Keypair, chain_id: u64, nonce: u64) -> SignedAuthorization {
+        let auth = Authorization { chain_id: U256::from(chain_id), address: Address::ZERO, nonce };
+        let signature =
+            sign_message(B256::from_slice(&key.secret_bytes()), auth.signature_hash()).unwrap();
+        auth.into_signed(signature)
+    }
+
+    #[test]
+    fn sponsored_transactions_of_applied_authorizations() {
+        let mut rng = generators::rng();
+        let [fee_payer, fresh, stale, contract, other_chain, any_chain, delegated] =
+            generate_keys(&mut rng, 7).try_into().unwrap();
+
+        let state = MockEthProvider::default();
+        state.add_account(address(&stale), ExtendedAccount::new(5, U256::ZERO));
+        state.add_account(
+            address(&contract),
+            ExtendedAccount::new(1, U256::ZERO).with_bytecode(Bytes::from_static(&[0x60, 0x00])),
+        );
+        state.add_account(address(&any_chain), ExtendedAccount::new(3, U256::ZERO));
+        let mut delegation = vec![0xef, 0x01, 0x00];
+        delegation.extend_from_slice(Address::ZERO.as_slice());
+        state.add_account(
+            address(&delegated),
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(delegation.into()),
+        );
+
+        let tx = |key: &Keypair, nonce, authorization_list| {
+            sign_tx_with_key_pair(
+                *key,
+                Transaction::Eip7702(TxEip7702 {
+                    chain_id: 1,
+                    nonce,
+                    authorization_list,
+                    ..Default::default()
+                }),
+            )
+        };
+        let transactions = vec![
+            tx(
+                &fee_payer,
+                0,
+                vec![
+                    authorization(&fresh, 1, 0),
+                    // the nonce doesn't match the nonce of the authority
+                    authorization(&stale, 1, 0),
+                    // the authority is a contract
+                    authorization(&contract, 1, 1),
+                    // the chain id doesn't match
+                    authorization(&other_chain, 2, 0),
+                    authorization(&any_chain, 0, 3),
+                    authorization(&delegated, 1, 0),
+                    // the fee payer delegates itself, after its nonce was increased by the tx
+                    authorization(&fee_payer, 1, 1),
+                    // the nonce of the authority was increased by its first authorization
+                    authorization(&fresh, 1, 0),
+                ],
+            ),
+            tx(&fee_payer, 2, vec![authorization(&fresh, 1, 1)]),
+            // not sent by the fee payer
+            tx(&fresh, 2, vec![authorization(&stale, 1, 5)]),
+        ];
+        let senders = vec![address(&fee_payer), address(&fee_payer), address(&fresh)];
+        let hashes = transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>();
+        let block = RecoveredBlock::new_unhashed(
+            Block {
+                header: Header { number: 1, ..Default::default() },
+                body: BlockBody { transactions, ..Default::default() },
+            },
+            senders,
+        );
+
+        let sponsored = sponsored_transactions(&block, address(&fee_payer), &state).unwrap();
+        assert_eq!(
+            sponsored,
+            vec![
+                SponsoredTransaction {
+                    block_number: 1,
+                    transaction_hash: hashes[0],
+                    transaction_index: 0,
+                    sponsored: vec![address(&fresh), address(&any_chain), address(&delegated)],
+                },
+                SponsoredTransaction {
+                    block_number: 1,
+                    transaction_hash: hashes[1],
+                    transaction_index: 1,
+                    sponsored: vec![address(&fresh)],
+                },
+            ]
+        );
+    }
+
+    /// Returns a chain of a single block with a single log, emitted by the given address.
+    fn chain_with_log(number: u64, emitter: Address) -> Arc<Chain> {
+        let transactions = vec![random_signed_tx(&mut generators::rng())];
+        let block = RecoveredBlock::new_unhashed(
+            Block {
+                header: Header { number, ..Default::default() },
+                body: BlockBody { transactions, ..Default::default() },
+            },
+            vec![Address::ZERO],
+        );
+        let receipt = Receipt {
+            success: true,
+            logs: vec![Log::new_unchecked(emitter, Vec::new(), Bytes::new())],
+            ..Default::default()
+        };
+        let outcome =
+            ExecutionOutcome::new(BundleState::default(), vec![vec![receipt]], number, Vec::new());
+        Arc::new(Chain::new([block], outcome, None))
+    }
+
+    #[tokio::test]
+    async fn log_stream_tags_logs_with_epoch_of_notification() {
+        let provider = TestCanonStateSubscriptions::default();
+        let api = RethApi::new(provider.clone(), Box::new(TokioTaskExecutor::default()));
+
+        let [a, b, c, d] = [1, 2, 3, 4].map(Address::with_last_byte);
+        // a reorg before the subscription is reflected in the epochs of later notifications
+        provider.add_next_reorg(chain_with_log(1, Address::ZERO), chain_with_log(1, a));
+        let mut stream = Box::pin(api.canonical_log_stream(Filter::default()).unwrap());
+
+        provider.add_next_commit(chain_with_log(2, b));
+        provider.add_next_reorg(chain_with_log(2, b), chain_with_log(2, c));
+        provider.add_next_commit(chain_with_log(3, d));
+
+        let mut received = Vec::new();
+        for _ in 0..4 {
+            let log = stream.next().await.unwrap();
+            received.push((log.canonical_epoch, log.log.address(), log.log.removed));
+        }
+        assert_eq!(received, vec![(1, b, false), (2, b, true), (2, c, false), (2, d, false)]);
+        assert_eq!(provider.canonical_epoch(), Some(2));
+    }
+
+    #[test]
+    fn no_log_stream_without_canonical_epoch() {
+        let api = RethApi::new(MockEthProvider::default(), Box::new(TokioTaskExecutor::default()));
+        assert!(api.canonical_log_stream(Filter::default()).is_none());
+    }
+}
@@ -1,14 +1,14 @@
 use alloy_consensus::BlockHeader as _;
 use alloy_eips::BlockId;
 use alloy_evm::block::calc::{base_block_reward_pre_merge, block_reward, ommer_reward};
-use alloy_primitives::{map::HashSet, Bytes, B256, U256};
+use alloy_primitives::{map::HashSet, Address, Bytes, B256, U256};
 use alloy_rpc_types_eth::{
     state::{EvmOverrides, StateOverride},
     transaction::TransactionRequest,
     BlockOverrides, Index,
 };
 use alloy_rpc_types_trace::{
-    filter::TraceFilter,
+    filter::{TraceFilter, TraceFilterMode},
     opcode::{BlockOpcodeGas, TransactionOpcodeGas},
     parity::*,
     tracerequest::TraceCallRequest,
@@ -25,7 +25,7 @@ use reth_rpc_eth_api::{
     FromEthApiError, RpcNodeCore,
 };
 use reth_rpc_eth_types::{error::EthApiError, utils::recover_raw_transaction, EthConfig};
-use reth_storage_api::{BlockNumReader, BlockReader};
+use reth_storage_api::{BlockNumReader, BlockReader, TraceIndexProvider, TransactionVariant};
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{PoolPooledTx, PoolTransaction, TransactionPool};
 use revm::DatabaseCommit;
@@ -41,6 +41,8 @@ use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 /// This type provides the functionality for handling `trace` related requests.
 pub struct TraceApi<Eth> {
     inner: Arc<TraceApiInner<Eth>>,
+    /// Optional index used to narrow down the blocks that need to be traced by `trace_filter`.
+    trace_index: Option<Arc<dyn TraceIndexProvider>>,
 }
 
 // === impl TraceApi ===
@@ -53,7 +55,14 @@ impl<Eth> TraceApi<Eth> {
         eth_config: EthConfig,
     ) -> Self {
         let inner = Arc::new(TraceApiInner { eth_api, blocking_task_guard, eth_config });
-        Self { inner }
+        Self { inner, trace_index: None }
+    }
+
+    /// Configures the [`TraceIndexProvider`] that `trace_filter` consults to only trace the blocks
+    /// that involve the filtered addresses.
+    pub fn with_trace_index(mut self, trace_index: Arc<dyn TraceIndexProvider>) -> Self {
+        self.trace_index = Some(trace_index);
+        self
     }
 
     /// Acquires a permit to execute a tracing call.
@@ -358,6 +367,7 @@ where
     ) -> Result<Vec<LocalizedTransactionTrace>, Eth::Error> {
         // We'll reuse the matcher across multiple blocks that are traced in parallel
         let matcher = Arc::new(filter.matcher());
+        let required_addresses = required_trace_addresses(&filter);
         let TraceFilter { from_block, to_block, after, count, .. } = filter;
         let start = from_block.unwrap_or(0);
 
@@ -375,8 +385,19 @@ where
             .into())
         }
 
-        // ensure that the range is not too large, since we need to fetch all blocks in the range
-        let distance = end.saturating_sub(start);
+        // if the range is covered by the trace index, only the blocks that involve the filtered
+        // addresses need to be traced
+        let indexed_blocks = match (&self.trace_index, required_addresses) {
+            (Some(trace_index), Some(addresses)) => trace_index
+                .blocks_with_addresses(&addresses, start..=end)
+                .map_err(Eth::Error::from_eth_err)?,
+            _ => None,
+        };
+
+        // ensure that the number of blocks is not too large, since we need to fetch all of them
+        let distance = indexed_blocks
+            .as_ref()
+            .map_or_else(|| end.saturating_sub(start), |blocks| blocks.len() as u64);
         if distance > self.inner.eth_config.max_trace_filter_blocks {
             return Err(EthApiError::InvalidParams(
                 "Block range too large; currently limited to 100 blocks".to_string(),
@@ -384,14 +405,28 @@ where
             .into())
         }
 
-        // fetch all blocks in that range
-        let blocks = self
-            .provider()
-            .recovered_block_range(start..=end)
-            .map_err(Eth::Error::from_eth_err)?
-            .into_iter()
-            .map(Arc::new)
-            .collect::<Vec<_>>();
+        // fetch all blocks that need to be traced
+        let blocks = match indexed_blocks {
+            Some(block_numbers) => {
+                let mut blocks = Vec::with_capacity(block_numbers.len());
+                for block_number in block_numbers {
+                    let block = self
+                        .provider()
+                        .recovered_block(block_number.into(), TransactionVariant::WithHash)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+                    blocks.push(block);
+                }
+                blocks
+            }
+            None => self
+                .provider()
+                .recovered_block_range(start..=end)
+                .map_err(Eth::Error::from_eth_err)?,
+        }
+        .into_iter()
+        .map(Arc::new)
+        .collect::<Vec<_>>();
 
         // trace all blocks
         let mut block_traces = Vec::with_capacity(blocks.len());
@@ -698,7 +733,7 @@ impl<Eth> std::fmt::Debug for TraceApi<Eth> {
 }
 impl<Eth> Clone for TraceApi<Eth> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), trace_index: self.trace_index.clone() }
     }
 }
 
@@ -711,6 +746,17 @@ struct TraceApiInner<Eth> {
     eth_config: EthConfig,
 }
 
+/// Returns the addresses of which at least one is involved in every trace that matches the filter,
+/// or `None` if the filter can match traces of any address.
+fn required_trace_addresses(filter: &TraceFilter) -> Option<Vec<Address>> {
+    let (from, to) = (&filter.from_address, &filter.to_address);
+    let required = match filter.mode {
+        TraceFilterMode::Union => !from.is_empty() && !to.is_empty(),
+        TraceFilterMode::Intersection => !from.is_empty() || !to.is_empty(),
+    };
+    required.then(|| from.iter().chain(to).copied().collect())
+}
+
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
 /// beneficiary.
 fn reward_trace<H: BlockHeader>(header: &H, reward: RewardAction) -> LocalizedTransactionTrace {
@@ -1,5 +1,5 @@
 use core::fmt;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, ops::Bound, sync::Arc};
 
 use alloy_consensus::Transaction;
 use alloy_primitives::Address;
@@ -10,10 +10,10 @@ use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_primitives_traits::NodePrimitives;
 use reth_rpc_api::TxPoolApiServer;
-use reth_rpc_convert::{RpcConvert, RpcTypes};
+use reth_rpc_convert::RpcConvert;
 use reth_rpc_eth_api::RpcTransaction;
 use reth_transaction_pool::{
-    AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
+    AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool, ValidPoolTransaction,
 };
 use tracing::trace;
 
@@ -39,40 +39,75 @@ where
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus: Transaction>> + 'static,
     Eth: RpcConvert<Primitives: NodePrimitives<SignedTx = PoolConsensusTx<Pool>>>,
 {
-    fn content(&self) -> Result<TxpoolContent<RpcTransaction<Eth::Network>>, Eth::Error> {
-        #[inline]
-        fn insert<Tx, RpcTxB>(
-            tx: &Tx,
-            content: &mut BTreeMap<
-                Address,
-                BTreeMap<String, <RpcTxB::Network as RpcTypes>::TransactionResponse>,
-            >,
-            resp_builder: &RpcTxB,
-        ) -> Result<(), RpcTxB::Error>
-        where
-            Tx: PoolTransaction,
-            RpcTxB: RpcConvert<Primitives: NodePrimitives<SignedTx = Tx::Consensus>>,
-        {
-            content.entry(tx.sender()).or_default().insert(
-                tx.nonce().to_string(),
-                resp_builder.fill_pending(tx.clone_into_consensus())?,
-            );
-
-            Ok(())
-        }
-
+    /// Returns the pending and queued transactions of the senders after `cursor`, in ascending
+    /// order of the sender address.
+    ///
+    /// If a `limit` is given, senders are added until the next one would exceed the limit of
+    /// transactions. The transactions of a sender are never split across pages and a page always
+    /// contains at least one sender.
+    fn content(
+        &self,
+        cursor: Option<Address>,
+        limit: Option<usize>,
+    ) -> Result<TxpoolContent<RpcTransaction<Eth::Network>>, Eth::Error> {
         let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
 
-        let mut content = TxpoolContent::default();
-        for pending in pending {
-            insert::<_, Eth>(&pending.transaction, &mut content.pending, &self.tx_resp_builder)?;
+        let mut senders = BTreeMap::<Address, (Vec<_>, Vec<_>)>::new();
+        for tx in &pending {
+            senders.entry(tx.sender()).or_default().0.push(tx);
+        }
+        for tx in &queued {
+            senders.entry(tx.sender()).or_default().1.push(tx);
         }
-        for queued in queued {
-            insert::<_, Eth>(&queued.transaction, &mut content.queued, &self.tx_resp_builder)?;
+
+        let start = cursor.map_or(Bound::Unbounded, Bound::Excluded);
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut content = TxpoolContent::default();
+        let mut included = 0usize;
+        for (sender, (pending, queued)) in senders.range((start, Bound::Unbounded)) {
+            let count = pending.len() + queued.len();
+            if included > 0 && included.saturating_add(count) > limit {
+                break
+            }
+            included += count;
+
+            if !pending.is_empty() {
+                content.pending.insert(*sender, self.by_nonce(pending.iter().copied())?);
+            }
+            if !queued.is_empty() {
+                content.queued.insert(*sender, self.by_nonce(queued.iter().copied())?);
+            }
         }
 
         Ok(content)
     }
+
+    /// Returns the pending and queued transactions of the given sender.
+    fn content_from(
+        &self,
+        from: Address,
+    ) -> Result<TxpoolContentFrom<RpcTransaction<Eth::Network>>, Eth::Error> {
+        let pending = self.pool.get_pending_transactions_by_sender(from);
+        let queued = self.pool.get_queued_transactions_by_sender(from);
+        Ok(TxpoolContentFrom {
+            pending: self.by_nonce(pending.iter())?,
+            queued: self.by_nonce(queued.iter())?,
+        })
+    }
+
+    /// Converts the transactions into their RPC representation, keyed by nonce.
+    fn by_nonce<'a>(
+        &self,
+        txs: impl IntoIterator<Item = &'a Arc<ValidPoolTransaction<Pool::Transaction>>>,
+    ) -> Result<BTreeMap<String, RpcTransaction<Eth::Network>>, Eth::Error> {
+        txs.into_iter()
+            .map(|tx| {
+                let rpc_tx =
+                    self.tx_resp_builder.fill_pending(tx.transaction.clone_into_consensus())?;
+                Ok((tx.nonce().to_string(), rpc_tx))
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -135,7 +170,7 @@ where
         from: Address,
     ) -> RpcResult<TxpoolContentFrom<RpcTransaction<Eth::Network>>> {
         trace!(target: "rpc::eth", ?from, "Serving txpool_contentFrom");
-        Ok(self.content().map_err(Into::into)?.remove_from(&from))
+        Ok(self.content_from(from).map_err(Into::into)?)
     }
 
     /// Returns the details of all transactions currently pending for inclusion in the next
@@ -143,9 +178,13 @@ where
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     /// Handler for `txpool_content`
-    async fn txpool_content(&self) -> RpcResult<TxpoolContent<RpcTransaction<Eth::Network>>> {
-        trace!(target: "rpc::eth", "Serving txpool_content");
-        Ok(self.content().map_err(Into::into)?)
+    async fn txpool_content(
+        &self,
+        cursor: Option<Address>,
+        limit: Option<usize>,
+    ) -> RpcResult<TxpoolContent<RpcTransaction<Eth::Network>>> {
+        trace!(target: "rpc::eth", ?cursor, ?limit, "Serving txpool_content");
+        Ok(self.content(cursor, limit).map_err(Into::into)?)
     }
 }
 
@@ -1,7 +1,10 @@
 use alloy_consensus::{
     BlobTransactionValidationError, BlockHeader, EnvKzgSettings, Transaction, TxReceipt,
 };
-use alloy_eips::{eip4844::kzg_to_versioned_hash, eip7685::RequestsOrHash};
+use alloy_eips::{
+    eip4844::kzg_to_versioned_hash,
+    eip7685::{Requests, RequestsOrHash},
+};
 use alloy_rpc_types_beacon::relay::{
     BidTrace, BuilderBlockValidationRequest, BuilderBlockValidationRequestV2,
     BuilderBlockValidationRequestV3, BuilderBlockValidationRequestV4,
@@ -126,6 +129,25 @@ where
         block: RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
         message: BidTrace,
         registered_gas_limit: u64,
+    ) -> Result<(), ValidationApiError> {
+        self.validate_message_against_block_with_requests(
+            block,
+            message,
+            registered_gas_limit,
+            None,
+        )
+        .await
+    }
+
+    /// Validates the given block and a [`BidTrace`] against it like
+    /// [`Self::validate_message_against_block`], comparing the requests produced by the block
+    /// against the submitted execution requests, if any.
+    async fn validate_message_against_block_with_requests(
+        &self,
+        block: RecoveredBlock<<E::Primitives as NodePrimitives>::Block>,
+        message: BidTrace,
+        registered_gas_limit: u64,
+        expected_requests: Option<Requests>,
     ) -> Result<(), ValidationApiError> {
         self.validate_message_against_header(block.sealed_header(), &message)?;
 
@@ -201,7 +223,15 @@ where
         // update the cached reads
         self.update_cached_reads(parent_header_hash, request_cache).await;
 
-        self.consensus.validate_block_post_execution(&block, &output)?;
+        if let Some(expected_requests) = &expected_requests {
+            self.consensus.validate_block_post_execution_with_requests(
+                &block,
+                &output,
+                expected_requests,
+            )?;
+        } else {
+            self.consensus.validate_block_post_execution(&block, &output)?;
+        }
 
         self.ensure_payment(&block, &output, &message)?;
 
@@ -411,6 +441,7 @@ where
         &self,
         request: BuilderBlockValidationRequestV4,
     ) -> Result<(), ValidationApiError> {
+        let execution_requests = request.request.execution_requests.to_requests();
         let block = self.payload_validator.ensure_well_formed_payload(ExecutionData {
             payload: ExecutionPayload::V3(request.request.execution_payload),
             sidecar: ExecutionPayloadSidecar::v4(
@@ -419,17 +450,16 @@ where
                     versioned_hashes: self.validate_blobs_bundle(request.request.blobs_bundle)?,
                 },
                 PraguePayloadFields {
-                    requests: RequestsOrHash::Requests(
-                        request.request.execution_requests.to_requests(),
-                    ),
+                    requests: RequestsOrHash::Requests(execution_requests.clone()),
                 },
             ),
         })?;
 
-        self.validate_message_against_block(
+        self.validate_message_against_block_with_requests(
             block,
             request.request.message,
             request.registered_gas_limit,
+            Some(execution_requests),
         )
         .await
     }
@@ -439,6 +469,7 @@ where
         &self,
         request: BuilderBlockValidationRequestV5,
     ) -> Result<(), ValidationApiError> {
+        let execution_requests = request.request.execution_requests.to_requests();
         let block = self.payload_validator.ensure_well_formed_payload(ExecutionData {
             payload: ExecutionPayload::V3(request.request.execution_payload),
             sidecar: ExecutionPayloadSidecar::v4(
@@ -448,17 +479,16 @@ where
                         .validate_blobs_bundle_v2(request.request.blobs_bundle)?,
                 },
                 PraguePayloadFields {
-                    requests: RequestsOrHash::Requests(
-                        request.request.execution_requests.to_requests(),
-                    ),
+                    requests: RequestsOrHash::Requests(execution_requests.clone()),
                 },
             ),
         })?;
 
-        self.validate_message_against_block(
+        self.validate_message_against_block_with_requests(
             block,
             request.request.message,
             request.registered_gas_limit,
+            Some(execution_requests),
         )
         .await
     }
@@ -1,10 +1,13 @@
-use crate::stages::MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD;
+use crate::stages::{
+    FastUnwindThresholds, GenesisState, MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD,
+};
 use alloy_consensus::{BlockHeader, Header};
 use alloy_primitives::BlockNumber;
 use num_traits::Zero;
 use reth_config::config::ExecutionConfig;
 use reth_consensus::{ConsensusError, FullConsensus};
 use reth_db::{static_file::HeaderMask, tables};
+use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
 use reth_evm::{execute::Executor, metrics::ExecutorMetrics, ConfigureEvm};
 use reth_execution_types::Chain;
 use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
@@ -61,6 +64,11 @@ use super::missing_static_data_error;
 /// - [`tables::AccountsHistory`] to remove change set and apply old values to
 /// - [`tables::PlainAccountState`] [`tables::StoragesHistory`] to remove change set and apply old
 ///   values to [`tables::PlainStorageState`]
+///
+/// If a [`GenesisState`] is configured, deep unwinds may instead resync from genesis: restore the
+/// genesis state into [`tables::PlainAccountState`] and [`tables::PlainStorageState`] and replay
+/// all blocks up to the unwind target, if that is estimated to be cheaper according to the
+/// [`FastUnwindThresholds`].
 // false positive, we cannot derive it if !DB: Debug.
 #[derive(Debug)]
 pub struct ExecutionStage<E>
@@ -90,6 +98,10 @@ where
     exex_manager_handle: ExExManagerHandle<E::Primitives>,
     /// Executor metrics.
     metrics: ExecutorMetrics,
+    /// The genesis state used to unwind deep ranges by resyncing from genesis.
+    genesis_state: Option<Arc<GenesisState>>,
+    /// Thresholds for resyncing from genesis instead of reverting changesets.
+    fast_unwind_thresholds: FastUnwindThresholds,
 }
 
 impl<E> ExecutionStage<E>
@@ -113,9 +125,26 @@ where
             post_unwind_commit_input: None,
             exex_manager_handle,
             metrics: ExecutorMetrics::default(),
+            genesis_state: None,
+            fast_unwind_thresholds: FastUnwindThresholds::default(),
         }
     }
 
+    /// Sets the genesis state used to unwind deep ranges.
+    ///
+    /// Unwinds will restore the genesis state and replay all blocks up to the unwind target
+    /// instead of reverting changesets if that is estimated to be cheaper.
+    pub fn with_genesis_state(mut self, genesis_state: Arc<GenesisState>) -> Self {
+        self.genesis_state = Some(genesis_state);
+        self
+    }
+
+    /// Sets the thresholds for resyncing from genesis.
+    pub const fn with_fast_unwind_thresholds(mut self, thresholds: FastUnwindThresholds) -> Self {
+        self.fast_unwind_thresholds = thresholds;
+        self
+    }
+
     /// Create an execution stage with the provided executor.
     ///
     /// The commit threshold will be set to [`MERKLE_STAGE_DEFAULT_INCREMENTAL_THRESHOLD`].
@@ -247,6 +276,168 @@ where
 
         Ok(())
     }
+
+    /// Returns the genesis state to resync from, if resyncing from genesis is estimated to be
+    /// cheaper than reverting the changesets above the unwind target, together with the gas used
+    /// by the unwound blocks.
+    fn genesis_resync<Provider>(
+        &self,
+        provider: &Provider,
+        input: &UnwindInput,
+    ) -> Result<Option<(Arc<GenesisState>, u64)>, StageError>
+    where
+        Provider: DBProvider + StaticFileProviderFactory,
+    {
+        let Some(genesis_state) = &self.genesis_state else { return Ok(None) };
+
+        // ExExes need the reverted state, which is only available when reverting changesets.
+        if self.exex_manager_handle.has_exexs() {
+            return Ok(None)
+        }
+
+        let tip = input.checkpoint.block_number;
+        if tip.saturating_sub(input.unwind_to) < self.fast_unwind_thresholds.min_blocks {
+            return Ok(None)
+        }
+
+        // The genesis state must have been written by this node. This rules out databases whose
+        // state was imported at a later block.
+        if provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?.seek_exact(0)?.is_none() {
+            return Ok(None)
+        }
+
+        let static_file_provider = provider.static_file_provider();
+        let replay_gas = if input.unwind_to > 0 {
+            calculate_gas_used_from_headers(&static_file_provider, 1..=input.unwind_to)?
+        } else {
+            0
+        };
+        let revert_gas =
+            calculate_gas_used_from_headers(&static_file_provider, input.unwind_to + 1..=tip)?;
+
+        let prefers_resync = self.fast_unwind_thresholds.prefers_resync(
+            genesis_state.entries(),
+            replay_gas,
+            revert_gas,
+        );
+        debug!(
+            target: "sync::stages::execution",
+            unwind_to = input.unwind_to,
+            replay_gas,
+            revert_gas,
+            prefers_resync,
+            "Estimated unwind cost"
+        );
+
+        Ok(prefers_resync.then(|| (genesis_state.clone(), revert_gas)))
+    }
+
+    /// Replays the given block range on top of the current plain state, without writing
+    /// changesets or receipts.
+    fn replay_blocks<Provider>(
+        &self,
+        provider: &Provider,
+        range: RangeInclusive<BlockNumber>,
+    ) -> Result<(), StageError>
+    where
+        Provider: DBProvider
+            + BlockReader<Block = <E::Primitives as NodePrimitives>::Block>
+            + StateWriter
+            + StateCommitmentProvider,
+    {
+        let mut next_block = *range.start();
+        while next_block <= *range.end() {
+            let start_block = next_block;
+            let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
+            let mut executor = self.evm_config.batch_executor(db);
+
+            let mut cumulative_gas = 0;
+            let batch_start = Instant::now();
+
+            for block_number in start_block..=*range.end() {
+                let block = provider
+                    .recovered_block(block_number.into(), TransactionVariant::NoHash)?
+                    .ok_or_else(|| ProviderError::HeaderNotFound(block_number.into()))?;
+
+                cumulative_gas += block.header().gas_used();
+
+                let result = executor.execute_one(&block).map_err(|error| StageError::Block {
+                    block: Box::new(block.block_with_parent()),
+                    error: BlockErrorKind::Execution(error),
+                })?;
+
+                // Validating the replayed blocks detects a genesis state that doesn't match the
+                // chain.
+                if let Err(err) = self.consensus.validate_block_post_execution(&block, &result) {
+                    return Err(StageError::Block {
+                        block: Box::new(block.block_with_parent()),
+                        error: BlockErrorKind::Validation(err),
+                    })
+                }
+
+                next_block = block_number + 1;
+
+                if self.thresholds.is_end_of_batch(
+                    block_number - start_block,
+                    executor.size_hint() as u64,
+                    cumulative_gas,
+                    batch_start.elapsed(),
+                ) {
+                    break
+                }
+            }
+
+            debug!(target: "sync::stages::execution", start = start_block, end = next_block - 1, "Replayed block range");
+
+            let changes =
+                executor.into_state().take_bundle().to_plain_state(OriginalValuesKnown::Yes);
+            provider.write_state_changes(changes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Unwinds the stage by restoring the given genesis state and replaying all blocks up to the
+    /// unwind target, instead of reverting the changesets above it.
+    fn unwind_from_genesis<Provider>(
+        &self,
+        provider: &Provider,
+        input: UnwindInput,
+        genesis_state: &GenesisState,
+        unwound_gas: u64,
+    ) -> Result<UnwindOutput, StageError>
+    where
+        Provider: DBProvider
+            + BlockReader<Block = <E::Primitives as NodePrimitives>::Block>
+            + HeaderProvider
+            + StaticFileProviderFactory
+            + StateWriter
+            + StateCommitmentProvider,
+    {
+        let unwind_to = input.unwind_to;
+
+        info!(target: "sync::stages::execution", unwind_to, "Unwinding by resyncing from genesis");
+
+        self.ensure_consistency(provider, input.checkpoint.block_number, Some(unwind_to))?;
+
+        // Drop changesets and receipts above the unwind target without applying them, then
+        // rebuild the plain state of the unwind target from genesis.
+        provider.discard_state_above(unwind_to, StorageLocation::Both)?;
+        provider.replace_plain_state(genesis_state.plain_state()?)?;
+        if unwind_to > 0 {
+            self.replay_blocks(provider, 1..=unwind_to)?;
+        }
+
+        let checkpoint =
+            if let Some(mut stage_checkpoint) = input.checkpoint.execution_stage_checkpoint() {
+                stage_checkpoint.progress.processed -= unwound_gas;
+                StageCheckpoint::new(unwind_to).with_execution_stage_checkpoint(stage_checkpoint)
+            } else {
+                StageCheckpoint::new(unwind_to)
+            };
+
+        Ok(UnwindOutput { checkpoint })
+    }
 }
 
 impl<E, Provider> Stage<Provider> for ExecutionStage<E>
@@ -499,6 +690,10 @@ where
             })
         }
 
+        if let Some((genesis_state, unwound_gas)) = self.genesis_resync(provider, &input)? {
+            return self.unwind_from_genesis(provider, input, &genesis_state, unwound_gas)
+        }
+
         self.ensure_consistency(provider, input.checkpoint.block_number, Some(unwind_to))?;
 
         // Unwind account and storage changesets, as well as receipts.
@@ -657,6 +852,7 @@ pub fn calculate_gas_used_from_headers<N: NodePrimitives>(
 mod tests {
     use super::*;
     use crate::{stages::MERKLE_STAGE_DEFAULT_REBUILD_THRESHOLD, test_utils::TestStageDB};
+    use alloy_genesis::GenesisAccount;
     use alloy_primitives::{address, hex_literal::hex, keccak256, Address, B256, U256};
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
@@ -670,8 +866,9 @@ mod tests {
     use reth_evm_ethereum::EthEvmConfig;
     use reth_primitives_traits::{Account, Bytecode, SealedBlock, StorageEntry};
     use reth_provider::{
-        test_utils::create_test_provider_factory, AccountReader, DatabaseProviderFactory,
-        ReceiptProvider, StaticFileProviderFactory,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        AccountReader, DatabaseProviderFactory, ProviderFactory, ReceiptProvider,
+        StaticFileProviderFactory,
     };
     use reth_prune::PruneModes;
     use reth_prune_types::{PruneMode, ReceiptsLogPruneConfig};
@@ -1088,6 +1285,118 @@ mod tests {
         }
     }
 
+    /// Inserts the genesis block and block 1 of [`sanity_execute_unwind`].
+    fn insert_unwind_test_blocks(factory: &ProviderFactory<MockNodeTypesWithDB>) {
+        let provider = factory.provider_rw().unwrap();
+        let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
+        let genesis = SealedBlock::<Block>::decode(&mut genesis_rlp).unwrap();
+        let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
+        let block = SealedBlock::<Block>::decode(&mut block_rlp).unwrap();
+        provider.insert_historical_block(genesis.try_recover().unwrap()).unwrap();
+        provider.insert_historical_block(block.try_recover().unwrap()).unwrap();
+        provider
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::Headers)
+            .unwrap()
+            .commit()
+            .unwrap();
+        {
+            let static_file_provider = provider.static_file_provider();
+            let mut receipts_writer =
+                static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+            receipts_writer.increment_block(0).unwrap();
+            receipts_writer.commit().unwrap();
+        }
+        provider.commit().unwrap();
+    }
+
+    #[tokio::test]
+    async fn genesis_resync_unwind_matches_changeset_unwind() {
+        let code = hex!("5a465a905090036002900360015500");
+        let balance = U256::from(0x3635c9adc5dea00000u128);
+        let code_hash = keccak256(code);
+        let acc1 = address!("0x1000000000000000000000000000000000000000");
+        let acc2 = address!("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let alloc = BTreeMap::from([
+            (acc1, GenesisAccount::default().with_code(Some(code.to_vec().into()))),
+            (acc2, GenesisAccount::default().with_balance(balance)),
+        ]);
+        let input = ExecInput { target: Some(1), checkpoint: None };
+
+        let mut unwound_states = Vec::new();
+        for genesis_state in [None, Some(Arc::new(GenesisState::new(alloc)))] {
+            let factory = create_test_provider_factory();
+            insert_unwind_test_blocks(&factory);
+
+            // genesis state, written with its changesets like `init_genesis` does
+            let provider = factory.provider_rw().unwrap();
+            let db_tx = provider.tx_ref();
+            db_tx
+                .put::<tables::PlainAccountState>(
+                    acc1,
+                    Account { nonce: 0, balance: U256::ZERO, bytecode_hash: Some(code_hash) },
+                )
+                .unwrap();
+            db_tx
+                .put::<tables::PlainAccountState>(
+                    acc2,
+                    Account { nonce: 0, balance, bytecode_hash: None },
+                )
+                .unwrap();
+            db_tx
+                .put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into()))
+                .unwrap();
+            for address in [acc1, acc2] {
+                db_tx
+                    .put::<tables::AccountChangeSets>(0, AccountBeforeTx { address, info: None })
+                    .unwrap();
+            }
+            provider.commit().unwrap();
+
+            let provider = factory.database_provider_rw().unwrap();
+            let output = stage().execute(&provider, input).unwrap();
+            provider.commit().unwrap();
+
+            let provider = factory.database_provider_rw().unwrap();
+            let unwind_input =
+                UnwindInput { checkpoint: output.checkpoint, unwind_to: 0, bad_block: None };
+            let mut stage = stage();
+            if let Some(genesis_state) = genesis_state {
+                stage = stage.with_genesis_state(genesis_state).with_fast_unwind_thresholds(
+                    FastUnwindThresholds {
+                        min_blocks: 0,
+                        genesis_entry_cost: 0,
+                        ..Default::default()
+                    },
+                );
+                assert_matches!(stage.genesis_resync(&provider, &unwind_input), Ok(Some(_)));
+            }
+            let result = stage.unwind(&provider, unwind_input).unwrap();
+            assert_eq!(result.checkpoint.block_number, 0);
+            assert!(matches!(provider.receipt(0), Ok(None)));
+
+            let accounts = provider
+                .tx_ref()
+                .cursor_read::<tables::PlainAccountState>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            let storage = provider
+                .tx_ref()
+                .cursor_read::<tables::PlainStorageState>()
+                .unwrap()
+                .walk(None)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            unwound_states.push((accounts, storage));
+        }
+
+        assert_eq!(unwound_states[0], unwound_states[1]);
+    }
+
     #[tokio::test]
     async fn test_selfdestruct() {
         let test_db = TestStageDB::default();
@@ -0,0 +1,173 @@
+use alloy_genesis::GenesisAccount;
+use alloy_primitives::{Address, U256};
+use reth_revm::{
+    db::states::{PlainStorageChangeset, StateChangeset},
+    state::{AccountInfo, Bytecode},
+};
+use reth_storage_errors::{db::DatabaseError, provider::ProviderResult};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// The default minimum number of blocks to unwind before the [`ExecutionStage`] considers
+/// resyncing from genesis.
+///
+/// Shallower unwinds, e.g. reorgs, are always cheap enough to revert.
+///
+/// [`ExecutionStage`]: super::ExecutionStage
+pub const DEFAULT_FAST_UNWIND_MIN_BLOCKS: u64 = 10_000;
+
+/// The default cost of replaying a block relative to reverting it, per unit of gas.
+///
+/// Reverting a block only writes back the previous values recorded in its changesets, while
+/// replaying it executes its transactions, which reads the touched state and runs the EVM before
+/// writing the same amount of state changes.
+pub const DEFAULT_REPLAY_COST_FACTOR: u64 = 10;
+
+/// The default cost of restoring a single account or storage slot of the genesis state, in units
+/// of gas.
+///
+/// Restoring an entry is a single plain state write, priced like writing a new storage slot
+/// during execution, i.e. the `SSTORE` gas of setting a zero slot to a non-zero value.
+pub const DEFAULT_GENESIS_ENTRY_COST: u64 = 20_000;
+
+/// The genesis state the [`ExecutionStage`](super::ExecutionStage) resyncs from on deep unwinds.
+///
+/// Instead of reverting every changeset above the unwind target, the stage restores the genesis
+/// state and replays all blocks from genesis up to the unwind target. This is only estimated to be
+/// cheaper than reverting for unwinds to a block close to genesis, e.g. when resyncing a young
+/// devnet or testnet from scratch.
+#[derive(Debug, Clone, Default)]
+pub struct GenesisState {
+    /// The genesis allocation.
+    alloc: Arc<BTreeMap<Address, GenesisAccount>>,
+    /// The number of accounts and storage slots in the genesis allocation.
+    entries: u64,
+}
+
+impl GenesisState {
+    /// Creates a new genesis state of the given genesis allocation.
+    pub fn new(alloc: BTreeMap<Address, GenesisAccount>) -> Self {
+        let entries = alloc
+            .values()
+            .map(|account| 1 + account.storage.as_ref().map_or(0, |storage| storage.len() as u64))
+            .sum();
+        Self { alloc: Arc::new(alloc), entries }
+    }
+
+    /// Returns the number of accounts and storage slots in the genesis allocation.
+    pub const fn entries(&self) -> u64 {
+        self.entries
+    }
+
+    /// Returns the complete plain state of the genesis allocation.
+    pub fn plain_state(&self) -> ProviderResult<StateChangeset> {
+        let mut state = StateChangeset::default();
+        for (address, account) in self.alloc.iter() {
+            let mut info = AccountInfo {
+                balance: account.balance,
+                nonce: account.nonce.unwrap_or_default(),
+                ..Default::default()
+            };
+            if let Some(code) = &account.code {
+                let bytecode = Bytecode::new_raw_checked(code.clone())
+                    .map_err(|err| DatabaseError::Other(err.to_string()))?;
+                info.code_hash = bytecode.hash_slow();
+                state.contracts.push((info.code_hash, bytecode.clone()));
+                info.code = Some(bytecode);
+            }
+            state.accounts.push((*address, Some(info)));
+
+            if let Some(storage) = &account.storage {
+                state.storage.push(PlainStorageChangeset {
+                    address: *address,
+                    wipe_storage: false,
+                    storage: storage
+                        .iter()
+                        .map(|(key, value)| {
+                            (U256::from_be_bytes(key.0), U256::from_be_bytes(value.0))
+                        })
+                        .collect(),
+                });
+            }
+        }
+
+        Ok(state)
+    }
+}
+
+/// Thresholds for unwinding the [`ExecutionStage`](super::ExecutionStage) by resyncing from
+/// [`GenesisState`].
+///
+/// Costs are estimated in units of gas: reverting a range costs the gas used by its blocks, while
+/// resyncing costs restoring the genesis state plus replaying the blocks up to the unwind target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FastUnwindThresholds {
+    /// The minimum number of blocks to unwind before resyncing from genesis is considered.
+    pub min_blocks: u64,
+    /// How many times more expensive replaying a block is than reverting its changes, per unit
+    /// of gas. See [`DEFAULT_REPLAY_COST_FACTOR`].
+    pub replay_cost_factor: u64,
+    /// The cost of restoring a single entry of the genesis state, in units of gas. See
+    /// [`DEFAULT_GENESIS_ENTRY_COST`].
+    pub genesis_entry_cost: u64,
+}
+
+impl Default for FastUnwindThresholds {
+    fn default() -> Self {
+        Self {
+            min_blocks: DEFAULT_FAST_UNWIND_MIN_BLOCKS,
+            replay_cost_factor: DEFAULT_REPLAY_COST_FACTOR,
+            genesis_entry_cost: DEFAULT_GENESIS_ENTRY_COST,
+        }
+    }
+}
+
+impl FastUnwindThresholds {
+    /// Returns `true` if restoring the given number of genesis entries and replaying `replay_gas`
+    /// is estimated to be cheaper than reverting `revert_gas`.
+    pub const fn prefers_resync(
+        &self,
+        genesis_entries: u64,
+        replay_gas: u64,
+        revert_gas: u64,
+    ) -> bool {
+        let restore = genesis_entries.saturating_mul(self.genesis_entry_cost);
+        let replay = replay_gas.saturating_mul(self.replay_cost_factor);
+        restore.saturating_add(replay) < revert_gas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Bytes, B256};
+
+    #[test]
+    fn genesis_plain_state() {
+        let contract = Address::with_last_byte(2);
+        let alloc = BTreeMap::from([
+            (Address::with_last_byte(1), GenesisAccount::default().with_balance(U256::from(1))),
+            (
+                contract,
+                GenesisAccount::default()
+                    .with_code(Some(Bytes::from_static(&[0x60, 0x00])))
+                    .with_storage(Some(BTreeMap::from([(B256::ZERO, B256::with_last_byte(1))]))),
+            ),
+        ]);
+        let genesis = GenesisState::new(alloc);
+        assert_eq!(genesis.entries(), 3);
+
+        let state = genesis.plain_state().unwrap();
+        assert_eq!(state.accounts.len(), 2);
+        assert_eq!(state.contracts.len(), 1);
+        assert_eq!(state.storage[0].address, contract);
+        assert_eq!(state.storage[0].storage, vec![(U256::ZERO, U256::from(1))]);
+    }
+
+    #[test]
+    fn prefers_cheaper_direction() {
+        let thresholds = FastUnwindThresholds::default();
+
+        assert!(thresholds.prefers_resync(10, 1_000_000, 100_000_000));
+        assert!(!thresholds.prefers_resync(10, 100_000_000, 100_000_000));
+    }
+}
@@ -4,6 +4,8 @@ mod bodies;
 mod execution;
 /// The finish stage
 mod finish;
+/// Genesis resyncs used for deep execution stage unwinds.
+mod genesis_resync;
 /// Account hashing stage.
 mod hashing_account;
 /// Storage hashing stage.
@@ -28,6 +30,7 @@ pub use bodies::*;
 pub use era::*;
 pub use execution::*;
 pub use finish::*;
+pub use genesis_resync::*;
 pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
@@ -109,7 +109,7 @@ where
     /// For each [Some] target in [`StaticFileTargets`], initializes a corresponding [Segment] and
     /// runs it with the provided block range using [`reth_provider::providers::StaticFileProvider`]
     /// and a read-only database transaction from [`DatabaseProviderFactory`]. All segments are run
-    /// in parallel.
+    /// in parallel, and every segment commits its own static files as soon as it's finished.
     ///
     /// NOTE: it doesn't delete the data from database, and the actual deleting (aka pruning) logic
     /// lives in the `prune` crate.
@@ -149,6 +149,7 @@ where
             // transactions
             let provider = self.provider.database_provider_ro()?.disable_long_read_transaction_safety();
             segment.copy_to_static_files(provider,  block_range.clone())?;
+            self.provider.static_file_provider().commit_segment(segment.segment())?;
 
             let elapsed = start.elapsed(); // TODO(alexey): track in metrics
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, ?elapsed, "Finished StaticFileProducer segment");
@@ -156,7 +157,6 @@ where
             Ok(())
         })?;
 
-        self.provider.static_file_provider().commit()?;
         for (segment, block_range) in segments {
             self.provider
                 .static_file_provider()
@@ -99,6 +99,10 @@ pub struct DatabaseArguments {
     ///
     /// This flag affects only at environment opening but can't be changed after.
     exclusive: Option<bool>,
+    /// The host of a process whose storage lock is taken over.
+    ///
+    /// See [`StorageLock::try_acquire_with_takeover`].
+    lock_takeover_host: Option<String>,
 }
 
 impl Default for DatabaseArguments {
@@ -121,6 +125,7 @@ impl DatabaseArguments {
             log_level: None,
             max_read_transaction_duration: None,
             exclusive: None,
+            lock_takeover_host: None,
         }
     }
 
@@ -169,6 +174,12 @@ impl DatabaseArguments {
         self
     }
 
+    /// Set the host of a process whose storage lock is taken over.
+    pub fn with_lock_takeover_host(mut self, lock_takeover_host: Option<String>) -> Self {
+        self.lock_takeover_host = lock_takeover_host;
+        self
+    }
+
     /// Returns the client version if any.
     pub const fn client_version(&self) -> &ClientVersion {
         &self.client_version
@@ -296,7 +307,7 @@ impl DatabaseEnv {
         args: DatabaseArguments,
     ) -> Result<Self, DatabaseError> {
         let _lock_file = if kind.is_rw() {
-            StorageLock::try_acquire(path)
+            StorageLock::try_acquire_with_takeover(path, args.lock_takeover_host.as_deref())
                 .map_err(|err| DatabaseError::Other(err.to_string()))?
                 .into()
         } else {
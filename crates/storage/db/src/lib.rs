@@ -19,6 +19,7 @@ mod implementation;
 pub mod lockfile;
 #[cfg(feature = "mdbx")]
 mod metrics;
+pub mod migration;
 pub mod static_file;
 #[cfg(feature = "mdbx")]
 mod utils;
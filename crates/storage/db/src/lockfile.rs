@@ -16,9 +16,11 @@ const LOCKFILE_NAME: &str = "lock";
 /// A file lock for a storage directory to ensure exclusive read-write access across different
 /// processes.
 ///
-/// This lock stores the PID of the process holding it and is released (deleted) on a graceful
-/// shutdown. On resuming from a crash, the stored PID helps verify that no other process holds the
-/// lock.
+/// This lock stores the PID, start time and host of the process holding it and is released
+/// (deleted) on a graceful shutdown. On resuming from a crash, the stored owner helps verify that
+/// no other process holds the lock: the lock of a process on this host that is no longer running
+/// is taken over automatically, while the lock of a process on another host, which can't be
+/// checked, is only taken over by force if that host is named explicitly.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StorageLock(Arc<StorageLockInner>);
 
@@ -29,31 +31,80 @@ impl StorageLock {
     /// Note: In-process exclusivity is not on scope. If called from the same process (or another
     /// with the same PID), it will succeed.
     pub fn try_acquire(path: &Path) -> Result<Self, StorageLockError> {
+        Self::try_acquire_with_takeover(path, None)
+    }
+
+    /// Tries to acquire a write lock on the target directory like [`StorageLock::try_acquire`],
+    /// taking over the lock of a process on another host if that host is `takeover_host`.
+    ///
+    /// Whether a process on another host is still running can't be checked, e.g. if the directory
+    /// is on a network share, so its lock is only taken over by force, and only if the host of the
+    /// process matches the given one. The lock of a running process on this host is never taken
+    /// over.
+    pub fn try_acquire_with_takeover(
+        path: &Path,
+        takeover_host: Option<&str>,
+    ) -> Result<Self, StorageLockError> {
         #[cfg(feature = "disable-lock")]
         {
+            let _ = takeover_host;
             let file_path = path.join(LOCKFILE_NAME);
             // Too expensive for ef-tests to write/read lock to/from disk.
             Ok(Self(Arc::new(StorageLockInner { file_path })))
         }
 
         #[cfg(not(feature = "disable-lock"))]
-        Self::try_acquire_file_lock(path)
+        Self::try_acquire_file_lock(path, takeover_host)
     }
 
     /// Acquire a file write lock.
     #[cfg(any(test, not(feature = "disable-lock")))]
-    fn try_acquire_file_lock(path: &Path) -> Result<Self, StorageLockError> {
+    fn try_acquire_file_lock(
+        path: &Path,
+        takeover_host: Option<&str>,
+    ) -> Result<Self, StorageLockError> {
         let file_path = path.join(LOCKFILE_NAME);
-        if let Some(process_lock) = ProcessUID::parse(&file_path)? {
-            if process_lock.pid != (process::id() as usize) && process_lock.is_active() {
-                reth_tracing::tracing::error!(
-                    target: "reth::db::lockfile",
-                    path = ?file_path,
-                    pid = process_lock.pid,
-                    start_time = process_lock.start_time,
-                    "Storage lock already taken."
-                );
-                return Err(StorageLockError::Taken(process_lock.pid))
+        if let Some(owner) = ProcessUID::parse(&file_path)? {
+            if !owner.is_own() {
+                if let Some(host) = owner.foreign_host() {
+                    if takeover_host != Some(host) {
+                        reth_tracing::tracing::error!(
+                            target: "reth::db::lockfile",
+                            path = ?file_path,
+                            pid = owner.pid,
+                            start_time = owner.start_time,
+                            host,
+                            "Storage lock taken by a process on another host."
+                        );
+                        return Err(StorageLockError::TakenByHost {
+                            pid: owner.pid,
+                            host: host.to_string(),
+                        })
+                    }
+                    reth_tracing::tracing::warn!(
+                        target: "reth::db::lockfile",
+                        path = ?file_path,
+                        pid = owner.pid,
+                        host,
+                        "Forcefully taking over storage lock of a process on another host."
+                    );
+                } else if owner.is_active() {
+                    reth_tracing::tracing::error!(
+                        target: "reth::db::lockfile",
+                        path = ?file_path,
+                        pid = owner.pid,
+                        start_time = owner.start_time,
+                        "Storage lock already taken."
+                    );
+                    return Err(StorageLockError::Taken(owner.pid))
+                } else {
+                    reth_tracing::tracing::info!(
+                        target: "reth::db::lockfile",
+                        path = ?file_path,
+                        pid = owner.pid,
+                        "Taking over stale storage lock of a process that is no longer running."
+                    );
+                }
             }
         }
 
@@ -96,12 +147,18 @@ impl StorageLockInner {
     }
 }
 
-#[derive(Clone, Debug)]
+/// The owner of a storage lock.
+#[derive(Clone, Debug, PartialEq, Eq)]
 struct ProcessUID {
     /// OS process identifier
     pid: usize,
     /// Process start time
     start_time: u64,
+    /// Name of the host the process runs on, if known.
+    ///
+    /// Lock files written by older versions don't include it, these are assumed to be written on
+    /// this host.
+    host: Option<String>,
 }
 
 impl ProcessUID {
@@ -114,7 +171,11 @@ impl ProcessUID {
             true,
             ProcessRefreshKind::nothing(),
         );
-        system.process(pid2).map(|process| Self { pid, start_time: process.start_time() })
+        system.process(pid2).map(|process| Self {
+            pid,
+            start_time: process.start_time(),
+            host: System::host_name(),
+        })
     }
 
     /// Creates [`Self`] from own process.
@@ -132,14 +193,29 @@ impl ProcessUID {
                     lines.next().map(str::trim).map(str::parse),
                     lines.next().map(str::trim).map(str::parse),
                 ) {
-                    return Ok(Some(Self { pid, start_time }));
+                    let host = lines
+                        .next()
+                        .map(str::trim)
+                        .filter(|host| !host.is_empty())
+                        .map(str::to_string);
+                    return Ok(Some(Self { pid, start_time, host }));
                 }
             }
         }
         Ok(None)
     }
 
-    /// Whether a process with this `pid` and `start_time` exists.
+    /// Whether this is the identifier of the current process.
+    fn is_own(&self) -> bool {
+        self.pid == process::id() as usize && self.foreign_host().is_none()
+    }
+
+    /// Returns the host of the process if it's not this host.
+    fn foreign_host(&self) -> Option<&str> {
+        self.host.as_deref().filter(|host| Some(*host) != Self::own().host.as_deref())
+    }
+
+    /// Whether a process with this `pid` and `start_time` exists on this host.
     fn is_active(&self) -> bool {
         System::new_with_specifics(
             RefreshKind::nothing().with_processes(ProcessRefreshKind::nothing()),
@@ -148,10 +224,14 @@ impl ProcessUID {
         .is_some_and(|p| p.start_time() == self.start_time)
     }
 
-    /// Writes `pid` and `start_time` to a file.
+    /// Writes `pid`, `start_time` and `host` to a file.
     fn write(&self, path: &Path) -> Result<(), StorageLockError> {
-        reth_fs_util::write(path, format!("{}\n{}", self.pid, self.start_time))
-            .map_err(StorageLockError::other)
+        let mut contents = format!("{}\n{}", self.pid, self.start_time);
+        if let Some(host) = &self.host {
+            contents.push('\n');
+            contents.push_str(host);
+        }
+        reth_fs_util::write(path, contents).map_err(StorageLockError::other)
     }
 }
 
@@ -173,10 +253,10 @@ mod tests {
 
         let temp_dir = tempfile::tempdir().unwrap();
 
-        let lock = StorageLock::try_acquire_file_lock(temp_dir.path()).unwrap();
+        let lock = StorageLock::try_acquire_file_lock(temp_dir.path(), None).unwrap();
 
         // Same process can re-acquire the lock
-        assert_eq!(Ok(lock.clone()), StorageLock::try_acquire_file_lock(temp_dir.path()));
+        assert_eq!(Ok(lock.clone()), StorageLock::try_acquire_file_lock(temp_dir.path(), None));
 
         // A lock of a non existent PID can be acquired.
         let lock_file = temp_dir.path().join(LOCKFILE_NAME);
@@ -185,8 +265,8 @@ mod tests {
         while system.process(fake_pid.into()).is_some() {
             fake_pid += 1;
         }
-        ProcessUID { pid: fake_pid, start_time: u64::MAX }.write(&lock_file).unwrap();
-        assert_eq!(Ok(lock.clone()), StorageLock::try_acquire_file_lock(temp_dir.path()));
+        ProcessUID { pid: fake_pid, start_time: u64::MAX, host: None }.write(&lock_file).unwrap();
+        assert_eq!(Ok(lock.clone()), StorageLock::try_acquire_file_lock(temp_dir.path(), None));
 
         let mut pid_1 = ProcessUID::new(1).unwrap();
 
@@ -194,13 +274,13 @@ mod tests {
         pid_1.write(&lock_file).unwrap();
         assert_eq!(
             Err(StorageLockError::Taken(1)),
-            StorageLock::try_acquire_file_lock(temp_dir.path())
+            StorageLock::try_acquire_file_lock(temp_dir.path(), None)
         );
 
         // A lock of a different but existing PID can be acquired ONLY IF the start_time differs.
         pid_1.start_time += 1;
         pid_1.write(&lock_file).unwrap();
-        assert_eq!(Ok(lock), StorageLock::try_acquire_file_lock(temp_dir.path()));
+        assert_eq!(Ok(lock), StorageLock::try_acquire_file_lock(temp_dir.path(), None));
     }
 
     #[test]
@@ -210,10 +290,61 @@ mod tests {
         let temp_dir = tempfile::tempdir().unwrap();
         let lock_file = temp_dir.path().join(LOCKFILE_NAME);
 
-        let lock = StorageLock::try_acquire_file_lock(temp_dir.path()).unwrap();
+        let lock = StorageLock::try_acquire_file_lock(temp_dir.path(), None).unwrap();
 
         assert!(lock_file.exists());
         drop(lock);
         assert!(!lock_file.exists());
     }
+
+    #[test]
+    fn test_lock_of_other_host() {
+        let _guard = serial_lock();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let lock_file = temp_dir.path().join(LOCKFILE_NAME);
+
+        // The lock of a process on another host can't be checked, so it's not taken over unless
+        // its host is given.
+        let other_host = ProcessUID { host: Some("other-host".to_string()), ..ProcessUID::own() };
+        other_host.write(&lock_file).unwrap();
+        let taken = Err(StorageLockError::TakenByHost {
+            pid: other_host.pid,
+            host: "other-host".to_string(),
+        });
+        assert_eq!(taken, StorageLock::try_acquire_file_lock(temp_dir.path(), None));
+        assert_eq!(taken, StorageLock::try_acquire_file_lock(temp_dir.path(), Some("third-host")));
+        assert_eq!(ProcessUID::parse(&lock_file).unwrap(), Some(other_host));
+    }
+
+    #[test]
+    fn test_force_takeover_lock_of_other_host() {
+        let _guard = serial_lock();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let lock_file = temp_dir.path().join(LOCKFILE_NAME);
+
+        let other_host =
+            ProcessUID { pid: 1337, start_time: 42, host: Some("other-host".to_string()) };
+        other_host.write(&lock_file).unwrap();
+
+        // The lock is replaced by the lock of this process, which is released on drop like any
+        // other lock.
+        let lock = StorageLock::try_acquire_file_lock(temp_dir.path(), Some("other-host")).unwrap();
+        assert_eq!(ProcessUID::parse(&lock_file).unwrap(), Some(ProcessUID::own()));
+        drop(lock);
+        assert!(!lock_file.exists());
+    }
+
+    #[test]
+    fn test_parse_lock_without_host() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let lock_file = temp_dir.path().join(LOCKFILE_NAME);
+
+        reth_fs_util::write(&lock_file, "1337\n42").unwrap();
+        assert_eq!(
+            ProcessUID::parse(&lock_file).unwrap(),
+            Some(ProcessUID { pid: 1337, start_time: 42, host: None })
+        );
+    }
 }
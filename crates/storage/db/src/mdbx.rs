@@ -2,6 +2,7 @@
 
 use crate::{is_database_empty, TableSet, Tables};
 use eyre::Context;
+use reth_tracing::tracing::info;
 use std::path::Path;
 
 pub use crate::implementation::mdbx::*;
@@ -9,10 +10,17 @@ pub use reth_libmdbx::*;
 
 /// Creates a new database at the specified path if it doesn't exist. Does NOT create tables. Check
 /// [`init_db`].
+///
+/// Databases of a previous version are upgraded with the [migrations](crate::migration::migrations)
+/// of the database.
 pub fn create_db<P: AsRef<Path>>(path: P, args: DatabaseArguments) -> eyre::Result<DatabaseEnv> {
-    use crate::version::{check_db_version_file, create_db_version_file, DatabaseVersionError};
+    use crate::{
+        migration::migrations,
+        version::{check_db_version_file, create_db_version_file, DatabaseVersionError},
+    };
 
     let rpath = path.as_ref();
+    let mut migrate = false;
     if is_database_empty(rpath) {
         reth_fs_util::create_dir_all(rpath)
             .wrap_err_with(|| format!("Could not create database directory {}", rpath.display()))?;
@@ -21,11 +29,27 @@ pub fn create_db<P: AsRef<Path>>(path: P, args: DatabaseArguments) -> eyre::Resu
         match check_db_version_file(rpath) {
             Ok(_) => (),
             Err(DatabaseVersionError::MissingFile) => create_db_version_file(rpath)?,
+            Err(DatabaseVersionError::VersionMismatch { version }) => {
+                migrations::<DatabaseEnv>().check(version)?;
+                migrate = true;
+            }
             Err(err) => return Err(err.into()),
         }
     }
 
-    Ok(DatabaseEnv::open(rpath, DatabaseEnvKind::RW, args)?)
+    let db = DatabaseEnv::open(rpath, DatabaseEnvKind::RW, args)?;
+    if migrate {
+        migrations().run(&db, rpath, |progress| {
+            info!(
+                target: "storage::db",
+                from_version = progress.from_version,
+                migrated = progress.migrated,
+                entries = progress.entries,
+                "Migrating database"
+            )
+        })?;
+    }
+    Ok(db)
 }
 
 /// Opens up an existing database or creates a new one at the specified path. Creates tables defined
@@ -0,0 +1,438 @@
+//! Versioned migrations of the database.
+//!
+//! The version of the database is stored in the [`DB_VERSION_FILE_NAME`] file next to it. A change
+//! of the format of a table bumps [`DB_VERSION`] and registers a [`Migration`] from the previous
+//! version in [`migrations`], so existing databases are upgraded when they're opened, instead of
+//! having to be resynced.
+//!
+//! Migrations run in batches, and a checkpoint is recorded in the
+//! [`MIGRATION_CHECKPOINT_FILE_NAME`] file after every batch, so an interrupted migration resumes
+//! from its last checkpoint.
+//!
+//! [`DB_VERSION_FILE_NAME`]: crate::version::DB_VERSION_FILE_NAME
+
+use crate::version::{get_db_version, write_db_version_file, DatabaseVersionError, DB_VERSION};
+use alloy_primitives::hex;
+use reth_db_api::database::Database;
+use reth_storage_errors::db::DatabaseError;
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The name of the file that contains the checkpoint of an interrupted migration, in the same
+/// directory as the database.
+pub const MIGRATION_CHECKPOINT_FILE_NAME: &str = "database.migration";
+
+/// Returns the migrations of the database to [`DB_VERSION`].
+pub const fn migrations<DB: Database>() -> Migrator<DB> {
+    // There are no migrations yet, databases of previous versions have to be resynced.
+    Migrator::new(DB_VERSION)
+}
+
+/// A migration of the database from [`Migration::source_version`] to the next version.
+pub trait Migration<DB>: Send + Sync {
+    /// The version of the database that is migrated to the next version.
+    fn source_version(&self) -> u64;
+
+    /// A short description of the migration.
+    fn description(&self) -> &'static str;
+
+    /// Returns the number of entries to migrate, to report the progress of the migration.
+    fn entries(&self, db: &DB) -> Result<u64, DatabaseError>;
+
+    /// Migrates the next batch of entries after the checkpoint, or the first batch if there's no
+    /// checkpoint.
+    ///
+    /// The batch must be committed before returning. The checkpoint is recorded after the batch is
+    /// committed, so migrating a batch again after an interruption must be safe.
+    fn migrate_batch(
+        &self,
+        db: &DB,
+        checkpoint: Option<&[u8]>,
+    ) -> Result<MigrationStep, DatabaseError>;
+}
+
+/// The result of migrating a batch of a [`Migration`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationStep {
+    /// The batch was migrated, and the migration continues after the checkpoint.
+    Continue {
+        /// The checkpoint to continue the migration from.
+        checkpoint: Vec<u8>,
+        /// The number of entries migrated in the batch.
+        migrated: u64,
+    },
+    /// The last batch was migrated.
+    Done {
+        /// The number of entries migrated in the batch.
+        migrated: u64,
+    },
+}
+
+/// The progress of a running [`Migration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationProgress {
+    /// The version of the database that is migrated.
+    pub from_version: u64,
+    /// The number of entries migrated since the migration was started or resumed.
+    pub migrated: u64,
+    /// The number of entries to migrate.
+    pub entries: u64,
+}
+
+/// A [`Migration`] that is pending for a database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingMigration {
+    /// The version of the database that is migrated.
+    pub from_version: u64,
+    /// The description of the migration.
+    pub description: &'static str,
+    /// The number of entries to migrate.
+    pub entries: u64,
+    /// Whether the migration resumes from a checkpoint of an interrupted run.
+    pub resumed: bool,
+}
+
+/// The pending migrations of a database, as reported by [`Migrator::dry_run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// The version of the database.
+    pub version: u64,
+    /// The version the database is migrated to.
+    pub target_version: u64,
+    /// The pending migrations, in the order they run.
+    pub migrations: Vec<PendingMigration>,
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.migrations.is_empty() {
+            return write!(f, "Database version v{} is up to date", self.version)
+        }
+
+        write!(
+            f,
+            "Database version v{} will be migrated to v{}:",
+            self.version, self.target_version
+        )?;
+        for migration in &self.migrations {
+            write!(
+                f,
+                "\n  v{} -> v{}: {} ({} entries{})",
+                migration.from_version,
+                migration.from_version + 1,
+                migration.description,
+                migration.entries,
+                if migration.resumed { ", resumed from checkpoint" } else { "" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Error when migrating a database with a [`Migrator`].
+#[derive(thiserror::Error, Debug)]
+pub enum MigrationError {
+    /// The version of the database couldn't be determined.
+    #[error(transparent)]
+    Version(#[from] DatabaseVersionError),
+    /// The database is newer than the supported version.
+    #[error(
+        "database version v{version} is newer than the supported database version \
+         v{target_version}"
+    )]
+    Downgrade {
+        /// The version of the database.
+        version: u64,
+        /// The supported version.
+        target_version: u64,
+    },
+    /// There's no migration from a version of the database.
+    #[error(
+        "breaking database change detected: there is no migration from database version \
+         v{version}, the database must be resynced"
+    )]
+    MissingMigration {
+        /// The version without migration.
+        version: u64,
+    },
+    /// The checkpoint file of an interrupted migration is malformed.
+    #[error("the migration checkpoint file is malformed")]
+    MalformedCheckpoint,
+    /// A migration failed.
+    #[error(transparent)]
+    Database(#[from] DatabaseError),
+    /// IO error occurred while accessing the database version or checkpoint file.
+    #[error("IO error occurred while accessing {path}: {err}")]
+    Io {
+        /// The encountered IO error.
+        err: io::Error,
+        /// The path of the file.
+        path: PathBuf,
+    },
+}
+
+/// Runs the ordered [`Migration`]s of a database to a target version.
+pub struct Migrator<DB> {
+    /// The version the database is migrated to.
+    target_version: u64,
+    /// The registered migrations.
+    migrations: Vec<Box<dyn Migration<DB>>>,
+}
+
+impl<DB: Database> Migrator<DB> {
+    /// Creates a new migrator to the given version, without migrations.
+    pub const fn new(target_version: u64) -> Self {
+        Self { target_version, migrations: Vec::new() }
+    }
+
+    /// Registers a migration.
+    pub fn with_migration(mut self, migration: impl Migration<DB> + 'static) -> Self {
+        self.migrations.push(Box::new(migration));
+        self
+    }
+
+    /// Returns the version the database is migrated to.
+    pub const fn target_version(&self) -> u64 {
+        self.target_version
+    }
+
+    /// Checks that a database of the given version can be migrated to the target version.
+    pub fn check(&self, version: u64) -> Result<(), MigrationError> {
+        self.pending(version).map(drop)
+    }
+
+    /// Returns the migrations of a database of the given version, in the order they run.
+    fn pending(&self, version: u64) -> Result<Vec<&dyn Migration<DB>>, MigrationError> {
+        if version > self.target_version {
+            return Err(MigrationError::Downgrade { version, target_version: self.target_version })
+        }
+
+        (version..self.target_version)
+            .map(|version| {
+                self.migrations
+                    .iter()
+                    .find(|migration| migration.source_version() == version)
+                    .map(|migration| migration.as_ref())
+                    .ok_or(MigrationError::MissingMigration { version })
+            })
+            .collect()
+    }
+
+    /// Reports the pending migrations of the database at the given path, without running them.
+    pub fn dry_run(&self, db: &DB, db_path: &Path) -> Result<MigrationReport, MigrationError> {
+        let version = get_db_version(db_path)?;
+        let checkpoint = read_checkpoint(db_path)?;
+        let migrations = self
+            .pending(version)?
+            .into_iter()
+            .map(|migration| {
+                Ok(PendingMigration {
+                    from_version: migration.source_version(),
+                    description: migration.description(),
+                    entries: migration.entries(db)?,
+                    resumed: checkpoint
+                        .as_ref()
+                        .is_some_and(|(version, _)| *version == migration.source_version()),
+                })
+            })
+            .collect::<Result<_, MigrationError>>()?;
+
+        Ok(MigrationReport { version, target_version: self.target_version, migrations })
+    }
+
+    /// Runs the pending migrations of the database at the given path, and returns the version of
+    /// the migrated database.
+    ///
+    /// The version file of the database is updated after every migration, and a migration resumes
+    /// from the checkpoint of an interrupted run.
+    pub fn run(
+        &self,
+        db: &DB,
+        db_path: &Path,
+        mut on_progress: impl FnMut(MigrationProgress),
+    ) -> Result<u64, MigrationError> {
+        let version = get_db_version(db_path)?;
+        for migration in self.pending(version)? {
+            let from_version = migration.source_version();
+            let entries = migration.entries(db)?;
+            let mut checkpoint = read_checkpoint(db_path)?
+                .filter(|(version, _)| *version == from_version)
+                .map(|(_, checkpoint)| checkpoint);
+
+            let mut migrated = 0;
+            loop {
+                match migration.migrate_batch(db, checkpoint.as_deref())? {
+                    MigrationStep::Continue { checkpoint: next, migrated: batch } => {
+                        migrated += batch;
+                        write_checkpoint(db_path, from_version, &next)?;
+                        checkpoint = Some(next);
+                        on_progress(MigrationProgress { from_version, migrated, entries });
+                    }
+                    MigrationStep::Done { migrated: batch } => {
+                        migrated += batch;
+                        on_progress(MigrationProgress { from_version, migrated, entries });
+                        break
+                    }
+                }
+            }
+
+            write_db_version_file(db_path, from_version + 1)
+                .map_err(|err| MigrationError::Io { err, path: db_path.to_path_buf() })?;
+            remove_checkpoint(db_path)?;
+        }
+
+        Ok(self.target_version)
+    }
+}
+
+impl<DB> fmt::Debug for Migrator<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migrator")
+            .field("target_version", &self.target_version)
+            .field("migrations", &self.migrations.len())
+            .finish()
+    }
+}
+
+/// Returns the path of the migration checkpoint file of the database.
+pub fn migration_checkpoint_file_path<P: AsRef<Path>>(db_path: P) -> PathBuf {
+    db_path.as_ref().join(MIGRATION_CHECKPOINT_FILE_NAME)
+}
+
+/// Reads the version of the interrupted migration and its checkpoint, if any.
+///
+/// The file contains the version on the first line and the hex encoded checkpoint on the second.
+fn read_checkpoint(db_path: &Path) -> Result<Option<(u64, Vec<u8>)>, MigrationError> {
+    let path = migration_checkpoint_file_path(db_path);
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(MigrationError::Io { err, path }),
+    };
+
+    let (version, checkpoint) = raw.split_once('\n').ok_or(MigrationError::MalformedCheckpoint)?;
+    let version = version.parse().map_err(|_| MigrationError::MalformedCheckpoint)?;
+    let checkpoint =
+        hex::decode(checkpoint.trim()).map_err(|_| MigrationError::MalformedCheckpoint)?;
+    Ok(Some((version, checkpoint)))
+}
+
+/// Records the checkpoint of the running migration.
+fn write_checkpoint(db_path: &Path, version: u64, checkpoint: &[u8]) -> Result<(), MigrationError> {
+    let path = migration_checkpoint_file_path(db_path);
+    fs::write(&path, format!("{version}\n{}", hex::encode(checkpoint)))
+        .map_err(|err| MigrationError::Io { err, path })
+}
+
+/// Removes the checkpoint of a completed migration.
+fn remove_checkpoint(db_path: &Path) -> Result<(), MigrationError> {
+    let path = migration_checkpoint_file_path(db_path);
+    match fs::remove_file(&path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(MigrationError::Io { err, path }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_rw_db, DatabaseEnv};
+    use assert_matches::assert_matches;
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    };
+
+    /// Migrates three entries in batches of one entry, and is interrupted once at an entry.
+    struct TestMigration {
+        from_version: u64,
+        interrupt_at: Option<u8>,
+        interrupted: AtomicBool,
+        migrated: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl TestMigration {
+        fn new(from_version: u64, interrupt_at: Option<u8>, migrated: Arc<Mutex<Vec<u8>>>) -> Self {
+            Self { from_version, interrupt_at, interrupted: AtomicBool::new(false), migrated }
+        }
+    }
+
+    impl<DB: Database> Migration<DB> for TestMigration {
+        fn source_version(&self) -> u64 {
+            self.from_version
+        }
+
+        fn description(&self) -> &'static str {
+            "test migration"
+        }
+
+        fn entries(&self, _db: &DB) -> Result<u64, DatabaseError> {
+            Ok(3)
+        }
+
+        fn migrate_batch(
+            &self,
+            _db: &DB,
+            checkpoint: Option<&[u8]>,
+        ) -> Result<MigrationStep, DatabaseError> {
+            let entry = checkpoint.map_or(0, |checkpoint| checkpoint[0] + 1);
+            if self.interrupt_at == Some(entry) && !self.interrupted.swap(true, Ordering::Relaxed) {
+                return Err(DatabaseError::Other("interrupted".to_string()))
+            }
+
+            self.migrated.lock().unwrap().push(entry);
+            if entry == 2 {
+                Ok(MigrationStep::Done { migrated: 1 })
+            } else {
+                Ok(MigrationStep::Continue { checkpoint: vec![entry], migrated: 1 })
+            }
+        }
+    }
+
+    #[test]
+    fn runs_and_resumes_migrations() {
+        let db = create_test_rw_db();
+        let path = db.path();
+        write_db_version_file(path, 1).unwrap();
+
+        let migrated = Arc::new(Mutex::new(Vec::new()));
+        let migrator = Migrator::new(3)
+            .with_migration(TestMigration::new(2, None, migrated.clone()))
+            .with_migration(TestMigration::new(1, Some(2), migrated.clone()));
+
+        let report = migrator.dry_run(&db, path).unwrap();
+        assert_eq!(
+            report.migrations.iter().map(|migration| migration.from_version).collect::<Vec<_>>(),
+            [1, 2]
+        );
+
+        // the first run is interrupted at the last batch of the first migration
+        assert_matches!(migrator.run(&db, path, |_| {}), Err(MigrationError::Database(_)));
+        assert_eq!(get_db_version(path).unwrap(), 1);
+        assert!(migrator.dry_run(&db, path).unwrap().migrations[0].resumed);
+
+        let mut progress = Vec::new();
+        assert_eq!(migrator.run(&db, path, |p| progress.push(p)).unwrap(), 3);
+        assert_eq!(get_db_version(path).unwrap(), 3);
+        assert!(!migration_checkpoint_file_path(path).exists());
+        assert_eq!(*migrated.lock().unwrap(), [0, 1, 2, 0, 1, 2]);
+        assert_eq!(progress[0], MigrationProgress { from_version: 1, migrated: 1, entries: 3 });
+        assert!(migrator.dry_run(&db, path).unwrap().migrations.is_empty());
+    }
+
+    #[test]
+    fn rejects_unsupported_versions() {
+        let migrator = Migrator::<DatabaseEnv>::new(3).with_migration(TestMigration::new(
+            2,
+            None,
+            Default::default(),
+        ));
+
+        assert_matches!(migrator.check(1), Err(MigrationError::MissingMigration { version: 1 }));
+        assert_matches!(migrator.check(4), Err(MigrationError::Downgrade { version: 4, .. }));
+        assert!(migrator.check(2).is_ok());
+        assert!(migrator.check(3).is_ok());
+    }
+}
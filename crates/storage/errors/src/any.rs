@@ -1,4 +1,4 @@
-use alloc::sync::Arc;
+use alloc::{string::ToString, sync::Arc};
 use core::{error::Error, fmt};
 
 /// A thread-safe cloneable wrapper for any error type.
@@ -28,6 +28,15 @@ impl fmt::Debug for AnyError {
     }
 }
 
+/// Two [`AnyError`]s are equal if their errors display the same message.
+impl PartialEq for AnyError {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner) || self.to_string() == other.to_string()
+    }
+}
+
+impl Eq for AnyError {}
+
 impl fmt::Display for AnyError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&self.inner, f)
@@ -6,6 +6,16 @@ pub enum StorageLockError {
     /// Write lock taken
     #[error("storage directory is currently in use as read-write by another process: PID {_0}")]
     Taken(usize),
+    /// Write lock taken by a process on another host, which can't be checked for liveness.
+    #[error(
+        "storage directory is currently in use as read-write by a process on another host: PID {pid} on {host}"
+    )]
+    TakenByHost {
+        /// The PID of the process holding the lock.
+        pid: usize,
+        /// The host the process holding the lock runs on.
+        host: String,
+    },
     /// Indicates other unspecified errors.
     #[error("{_0}")]
     Other(String),
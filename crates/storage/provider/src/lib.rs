@@ -45,7 +45,8 @@ pub mod writer;
 
 pub use reth_chain_state::{
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotificationStream,
-    CanonStateNotifications, CanonStateSubscriptions,
+    CanonStateNotifications, CanonStateSubscriptions, CanonicalEpochNotification,
+    CanonicalEpochNotificationStream,
 };
 
 // reexport traits to avoid breaking changes
@@ -17,8 +17,8 @@ use alloy_eips::{
 use alloy_primitives::{Address, BlockHash, BlockNumber, Sealable, TxHash, TxNumber, B256, U256};
 use alloy_rpc_types_engine::ForkchoiceState;
 use reth_chain_state::{
-    BlockState, CanonicalInMemoryState, ForkChoiceNotifications, ForkChoiceSubscriptions,
-    MemoryOverlayStateProvider,
+    BlockState, CanonicalEpochNotificationStream, CanonicalInMemoryState, ForkChoiceNotifications,
+    ForkChoiceSubscriptions, MemoryOverlayStateProvider,
 };
 use reth_chainspec::{ChainInfo, EthereumHardforks};
 use reth_db_api::{
@@ -680,6 +680,16 @@ impl<N: ProviderNodeTypes> CanonStateSubscriptions for BlockchainProvider<N> {
     fn subscribe_to_canonical_state(&self) -> CanonStateNotifications<Self::Primitives> {
         self.canonical_in_memory_state.subscribe_canon_state()
     }
+
+    fn subscribe_to_canonical_epochs(
+        &self,
+    ) -> Option<CanonicalEpochNotificationStream<Self::Primitives>> {
+        Some(self.canonical_in_memory_state.subscribe_canonical_epochs())
+    }
+
+    fn canonical_epoch(&self) -> Option<u64> {
+        Some(self.canonical_in_memory_state.canonical_epoch())
+    }
 }
 
 impl<N: ProviderNodeTypes> ForkChoiceSubscriptions for BlockchainProvider<N> {
@@ -703,6 +713,15 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for BlockchainProvider<N> {
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
         self.consistent_provider()?.storage_changeset(block_number)
     }
+
+    fn storage_slot_changes(
+        &self,
+        address: Address,
+        slot: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>> {
+        self.consistent_provider()?.storage_slot_changes(address, slot, range)
+    }
 }
 
 impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
@@ -712,6 +731,14 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
         self.consistent_provider()?.account_block_changeset(block_number)
     }
+
+    fn account_changes(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        self.consistent_provider()?.account_changes(address, range)
+    }
 }
 
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
@@ -753,8 +780,9 @@ mod tests {
         BlockWriter, CanonChainTracker, ProviderFactory, StaticFileProviderFactory,
         StaticFileWriter,
     };
-    use alloy_eips::{BlockHashOrNumber, BlockNumHash, BlockNumberOrTag};
+    use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag};
     use alloy_primitives::{BlockNumber, TxNumber, B256};
+    use assert_matches::assert_matches;
     use itertools::Itertools;
     use rand::Rng;
     use reth_chain_state::{
@@ -1934,6 +1962,67 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_state_by_block_id_require_canonical() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let (provider, database_blocks, _, _) = provider_with_random_blocks(
+            &mut rng,
+            TEST_BLOCKS_COUNT,
+            0,
+            BlockRangeParams::default(),
+        )?;
+
+        // the pending block is known, but not part of the canonical chain
+        let tip = database_blocks.last().unwrap();
+        let pending_block = random_block(
+            &mut rng,
+            tip.number + 1,
+            BlockParams { parent: Some(tip.hash()), tx_count: Some(0), ..Default::default() },
+        );
+        provider.canonical_in_memory_state.set_pending_block(ExecutedBlockWithTrieUpdates {
+            block: ExecutedBlock {
+                recovered_block: Arc::new(RecoveredBlock::new_sealed(
+                    pending_block.clone(),
+                    Default::default(),
+                )),
+                execution_output: Default::default(),
+                hashed_state: Default::default(),
+            },
+            trie: ExecutedTrieUpdates::empty(),
+        });
+
+        assert_eq!(
+            tip.hash(),
+            provider
+                .state_by_block_id(BlockId::hash_canonical(tip.hash()))?
+                .block_hash(tip.number)?
+                .unwrap()
+        );
+        assert_eq!(
+            pending_block.hash(),
+            provider
+                .state_by_block_id(BlockId::hash(pending_block.hash()))?
+                .block_hash(pending_block.number)?
+                .unwrap()
+        );
+        assert!(matches!(
+            provider.state_by_block_id(BlockId::hash_canonical(pending_block.hash())),
+            Err(ProviderError::StateForHashNotFound(hash)) if hash == pending_block.hash()
+        ));
+        assert!(provider.state_by_block_id(BlockId::hash(B256::random())).is_err());
+
+        assert_eq!(
+            provider.block_hash_for_id(BlockId::hash_canonical(tip.hash()))?,
+            Some(tip.hash())
+        );
+        assert_eq!(
+            provider.block_number_for_id(BlockId::hash_canonical(pending_block.hash()))?,
+            None
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_block_id_reader() -> eyre::Result<()> {
         // Create a new provider
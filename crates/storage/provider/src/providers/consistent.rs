@@ -1305,6 +1305,9 @@ impl<N: ProviderNodeTypes> BlockReaderIdExt for ConsistentProvider<N> {
     ) -> ProviderResult<Option<SealedHeader<HeaderTy<N>>>> {
         Ok(match id {
             BlockId::Number(num) => self.sealed_header_by_number_or_tag(num)?,
+            BlockId::Hash(hash) if hash.require_canonical == Some(true) => self
+                .canonical_block_number(hash.block_hash)?
+                .map_or_else(|| Ok(None), |num| self.sealed_header(num))?,
             BlockId::Hash(hash) => self.header(&hash.block_hash)?.map(SealedHeader::seal_slow),
         })
     }
@@ -1312,6 +1315,9 @@ impl<N: ProviderNodeTypes> BlockReaderIdExt for ConsistentProvider<N> {
     fn header_by_id(&self, id: BlockId) -> ProviderResult<Option<HeaderTy<N>>> {
         Ok(match id {
             BlockId::Number(num) => self.header_by_number_or_tag(num)?,
+            BlockId::Hash(hash) if hash.require_canonical == Some(true) => self
+                .canonical_block_number(hash.block_hash)?
+                .map_or_else(|| Ok(None), |num| self.header_by_number(num))?,
             BlockId::Hash(hash) => self.header(&hash.block_hash)?,
         })
     }
@@ -1368,6 +1374,54 @@ impl<N: ProviderNodeTypes> StorageChangeSetReader for ConsistentProvider<N> {
             self.storage_provider.storage_changeset(block_number)
         }
     }
+
+    fn storage_slot_changes(
+        &self,
+        address: Address,
+        slot: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>> {
+        // The in-memory chain always covers the highest blocks, everything below its lowest block
+        // is read from the database using the history index.
+        let lowest_in_memory = self
+            .head_block
+            .as_ref()
+            .and_then(|head| head.chain().last().map(|block| block.number()))
+            .unwrap_or(BlockNumber::MAX);
+
+        let mut changes = if *range.start() < lowest_in_memory {
+            // No prune checkpoint means history should exist.
+            let storage_history_exists = self
+                .storage_provider
+                .get_prune_checkpoint(PruneSegment::StorageHistory)?
+                .and_then(|checkpoint| {
+                    checkpoint.block_number.map(|checkpoint| *range.start() > checkpoint)
+                })
+                .unwrap_or(true);
+
+            if !storage_history_exists {
+                return Err(ProviderError::StateAtBlockPruned(*range.start()))
+            }
+
+            let db_range = *range.start()..=(*range.end()).min(lowest_in_memory.saturating_sub(1));
+            self.storage_provider.storage_slot_changes(address, slot, db_range)?
+        } else {
+            Vec::new()
+        };
+
+        for block_number in lowest_in_memory.max(*range.start())..=*range.end() {
+            changes.extend(
+                self.storage_changeset(block_number)?
+                    .into_iter()
+                    .find(|(block_address, entry)| {
+                        block_address.address() == address && entry.key == slot
+                    })
+                    .map(|(_, entry)| (block_number, entry.value)),
+            );
+        }
+
+        Ok(changes)
+    }
 }
 
 impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
@@ -1414,6 +1468,51 @@ impl<N: ProviderNodeTypes> ChangeSetReader for ConsistentProvider<N> {
             self.storage_provider.account_block_changeset(block_number)
         }
     }
+
+    fn account_changes(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        // The in-memory chain always covers the highest blocks, everything below its lowest block
+        // is read from the database using the history index.
+        let lowest_in_memory = self
+            .head_block
+            .as_ref()
+            .and_then(|head| head.chain().last().map(|block| block.number()))
+            .unwrap_or(BlockNumber::MAX);
+
+        let mut changes = if *range.start() < lowest_in_memory {
+            // No prune checkpoint means history should exist.
+            let account_history_exists = self
+                .storage_provider
+                .get_prune_checkpoint(PruneSegment::AccountHistory)?
+                .and_then(|checkpoint| {
+                    checkpoint.block_number.map(|checkpoint| *range.start() > checkpoint)
+                })
+                .unwrap_or(true);
+
+            if !account_history_exists {
+                return Err(ProviderError::StateAtBlockPruned(*range.start()))
+            }
+
+            let db_range = *range.start()..=(*range.end()).min(lowest_in_memory.saturating_sub(1));
+            self.storage_provider.account_changes(address, db_range)?
+        } else {
+            Vec::new()
+        };
+
+        for block_number in lowest_in_memory.max(*range.start())..=*range.end() {
+            changes.extend(
+                self.account_block_changeset(block_number)?
+                    .into_iter()
+                    .find(|account_before| account_before.address == address)
+                    .map(|account_before| (block_number, account_before.info)),
+            );
+        }
+
+        Ok(changes)
+    }
 }
 
 impl<N: ProviderNodeTypes> AccountReader for ConsistentProvider<N> {
@@ -931,6 +931,49 @@ impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N>
             .map(|result| -> ProviderResult<_> { Ok(result?) })
             .collect()
     }
+
+    fn storage_slot_changes(
+        &self,
+        address: Address,
+        slot: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, U256)>> {
+        let mut history_cursor = self.tx.cursor_read::<tables::StoragesHistory>()?;
+        let mut changeset_cursor = self.tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+
+        let mut changes = Vec::new();
+        // Shards are keyed by their highest block number, so the first shard that can contain the
+        // start of the range is the first one with a key greater or equal to it.
+        let start = StorageShardedKey::new(address, slot, *range.start());
+        for entry in history_cursor.walk(Some(start))? {
+            let (key, list) = entry?;
+            if key.address != address || key.sharded_key.key != slot {
+                break
+            }
+
+            for block_number in list.iter().skip_while(|block| block < range.start()) {
+                if block_number > *range.end() {
+                    return Ok(changes)
+                }
+
+                let entry = changeset_cursor
+                    .seek_by_key_subkey((block_number, address).into(), slot)?
+                    .filter(|entry| entry.key == slot)
+                    .ok_or_else(|| ProviderError::StorageChangesetNotFound {
+                        block_number,
+                        address,
+                        storage_key: Box::new(slot),
+                    })?;
+                changes.push((block_number, entry.value));
+            }
+
+            if key.sharded_key.highest_block_number >= *range.end() {
+                break
+            }
+        }
+
+        Ok(changes)
+    }
 }
 
 impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
@@ -948,6 +991,44 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
             })
             .collect()
     }
+
+    fn account_changes(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        let mut history_cursor = self.tx.cursor_read::<tables::AccountsHistory>()?;
+        let mut changeset_cursor = self.tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+
+        let mut changes = Vec::new();
+        // Shards are keyed by their highest block number, so the first shard that can contain the
+        // start of the range is the first one with a key greater or equal to it.
+        let start = ShardedKey::new(address, *range.start());
+        for entry in history_cursor.walk(Some(start))? {
+            let (key, list) = entry?;
+            if key.key != address {
+                break
+            }
+
+            for block_number in list.iter().skip_while(|block| block < range.start()) {
+                if block_number > *range.end() {
+                    return Ok(changes)
+                }
+
+                let account_before = changeset_cursor
+                    .seek_by_key_subkey(block_number, address)?
+                    .filter(|account_before| account_before.address == address)
+                    .ok_or(ProviderError::AccountChangesetNotFound { block_number, address })?;
+                changes.push((block_number, account_before.info));
+            }
+
+            if key.highest_block_number >= *range.end() {
+                break
+            }
+        }
+
+        Ok(changes)
+    }
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> HeaderSyncGapProvider
@@ -2162,6 +2243,39 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         Ok(())
     }
 
+    fn discard_state_above(
+        &self,
+        block: BlockNumber,
+        remove_receipts_from: StorageLocation,
+    ) -> ProviderResult<()> {
+        let range = block + 1..=self.last_block_number()?;
+
+        if range.is_empty() {
+            return Ok(());
+        }
+
+        // We are not removing block meta as it is used to get block changesets.
+        let block_bodies = self.block_body_indices_range(range.clone())?;
+
+        // get transaction receipts
+        let from_transaction_num =
+            block_bodies.first().expect("already checked if there are blocks").first_tx_num();
+
+        self.remove::<tables::StorageChangeSets>(BlockNumberAddress::range(range.clone()))?;
+        self.remove::<tables::AccountChangeSets>(range)?;
+
+        self.remove_receipts_from(from_transaction_num, block, remove_receipts_from)?;
+
+        Ok(())
+    }
+
+    fn replace_plain_state(&self, state: StateChangeset) -> ProviderResult<()> {
+        self.tx.clear::<tables::PlainAccountState>()?;
+        self.tx.clear::<tables::PlainStorageState>()?;
+
+        self.write_state_changes(state)
+    }
+
     /// Take the last N blocks of state, recreating the [`ExecutionOutcome`].
     ///
     /// The latest state will be unwound and returned back with all the blocks
@@ -3211,6 +3325,99 @@ mod tests {
     };
     use reth_testing_utils::generators::{self, random_block, BlockParams};
 
+    #[test]
+    fn test_storage_slot_changes() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let slot = B256::with_last_byte(2);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::StoragesHistory>(
+                StorageShardedKey::new(address, slot, 5),
+                BlockNumberList::new_pre_sorted([2, 5]),
+            )
+            .unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::StoragesHistory>(
+                StorageShardedKey::last(address, slot),
+                BlockNumberList::new_pre_sorted([9]),
+            )
+            .unwrap();
+        for block_number in [2u64, 5, 9] {
+            provider_rw
+                .tx_ref()
+                .put::<tables::StorageChangeSets>(
+                    (block_number, address).into(),
+                    StorageEntry { key: slot, value: U256::from(block_number) },
+                )
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.storage_slot_changes(address, slot, 0..=10).unwrap(),
+            vec![(2, U256::from(2)), (5, U256::from(5)), (9, U256::from(9))]
+        );
+        assert_eq!(
+            provider.storage_slot_changes(address, slot, 3..=9).unwrap(),
+            vec![(5, U256::from(5)), (9, U256::from(9))]
+        );
+        assert_eq!(provider.storage_slot_changes(address, slot, 6..=8).unwrap(), vec![]);
+        assert_eq!(
+            provider.storage_slot_changes(address, B256::with_last_byte(3), 0..=10).unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_account_changes() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let account = |nonce| Some(Account { nonce, ..Default::default() });
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::AccountsHistory>(
+                ShardedKey::new(address, 5),
+                BlockNumberList::new_pre_sorted([2, 5]),
+            )
+            .unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::AccountsHistory>(
+                ShardedKey::last(address),
+                BlockNumberList::new_pre_sorted([9]),
+            )
+            .unwrap();
+        for block_number in [2u64, 5, 9] {
+            provider_rw
+                .tx_ref()
+                .put::<tables::AccountChangeSets>(
+                    block_number,
+                    AccountBeforeTx { address, info: account(block_number) },
+                )
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(
+            provider.account_changes(address, 0..=10).unwrap(),
+            vec![(2, account(2)), (5, account(5)), (9, account(9))]
+        );
+        assert_eq!(
+            provider.account_changes(address, 3..=9).unwrap(),
+            vec![(5, account(5)), (9, account(9))]
+        );
+        assert_eq!(provider.account_changes(address, 6..=8).unwrap(), vec![]);
+        assert_eq!(provider.account_changes(Address::with_last_byte(2), 0..=10).unwrap(), vec![]);
+    }
+
     #[test]
     fn test_receipts_by_block_range_empty_range() {
         let factory = create_test_provider_factory();
@@ -0,0 +1,158 @@
+use crate::{
+    providers::{BlockchainProvider, ProviderNodeTypes},
+    BlockHashReader, BlockNumReader, BlockReader, ChainStateBlockReader, HeaderProvider,
+};
+use alloy_consensus::BlockHeader as _;
+use reth_chain_state::CanonStateNotification;
+use reth_execution_types::Chain;
+use reth_node_types::{HeaderTy, NodeTypesWithDB};
+use reth_primitives_traits::SealedHeader;
+use reth_storage_errors::provider::ProviderResult;
+use std::sync::Arc;
+use tracing::{debug, trace};
+
+/// The maximum number of newly committed blocks that are announced through canonical state
+/// notifications at once.
+///
+/// If the replica falls further behind, e.g. while the primary is syncing, only the head is
+/// advanced.
+const MAX_NOTIFIED_BLOCKS: u64 = 64;
+
+/// Follows the chain of a primary node that writes to the same datadir from another process.
+///
+/// The storage of the [`BlockchainProvider`] is expected to be opened read-only, with the static
+/// files being watched for changes. The primary only advances its `Finish` stage checkpoint once
+/// all data of a block has been committed to both the database and the static files, so the
+/// checkpoint is used to detect new blocks that are safe to serve.
+#[derive(Debug)]
+pub struct ReplicaFollower<N: NodeTypesWithDB> {
+    provider: BlockchainProvider<N>,
+}
+
+impl<N: ProviderNodeTypes> ReplicaFollower<N> {
+    /// Creates a new [`ReplicaFollower`] that advances the heads of the given provider.
+    pub const fn new(provider: BlockchainProvider<N>) -> Self {
+        Self { provider }
+    }
+
+    /// Checks whether the primary committed new blocks and advances the canonical, safe and
+    /// finalized heads of the provider accordingly.
+    ///
+    /// Returns the new canonical head, if it changed.
+    pub fn poll(&self) -> ProviderResult<Option<SealedHeader<HeaderTy<N>>>> {
+        let provider = self.provider.database.provider()?;
+        let best_number = provider.best_block_number()?;
+        let Some(head) = provider.sealed_header(best_number)? else {
+            // the static files of the block may not have been picked up yet
+            trace!(target: "providers::replica", best_number, "Committed head not available yet");
+            return Ok(None)
+        };
+
+        let state = &self.provider.canonical_in_memory_state;
+        let current = state.get_canonical_head();
+        if head.hash() == current.hash() {
+            return Ok(None)
+        }
+
+        // Only a linear extension of the chain is announced, a reorg of persisted blocks is only
+        // reflected by the new head.
+        let extends = provider.block_hash(current.number())? == Some(current.hash());
+        let new_blocks = head.number().saturating_sub(current.number());
+        let notification = if extends && (1..=MAX_NOTIFIED_BLOCKS).contains(&new_blocks) {
+            let range = current.number() + 1..=head.number();
+            let blocks = provider.recovered_block_range(range.clone())?;
+            match self.provider.get_state(range)? {
+                Some(execution_outcome) if !blocks.is_empty() => {
+                    Some(CanonStateNotification::Commit {
+                        new: Arc::new(Chain::new(blocks, execution_outcome, None)),
+                    })
+                }
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        let finalized = provider
+            .last_finalized_block_number()?
+            .map(|number| provider.sealed_header(number))
+            .transpose()?
+            .flatten();
+        let safe = provider
+            .last_safe_block_number()?
+            .map(|number| provider.sealed_header(number))
+            .transpose()?
+            .flatten();
+        drop(provider);
+
+        debug!(
+            target: "providers::replica",
+            head = ?head.num_hash(),
+            previous = ?current.num_hash(),
+            "Advancing replica head"
+        );
+        state.set_canonical_head(head.clone());
+        if let Some(finalized) = finalized {
+            state.set_finalized(finalized);
+        }
+        if let Some(safe) = safe {
+            state.set_safe(safe);
+        }
+        if let Some(notification) = notification {
+            state.notify_canon_state(notification);
+        }
+
+        Ok(Some(head))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_utils::create_test_provider_factory, StageCheckpointWriter};
+    use alloy_primitives::B256;
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn follows_finish_checkpoint() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+        let factory = create_test_provider_factory();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=5,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..1, ..Default::default() },
+        );
+        let (committed, pending) = blocks.split_at(3);
+
+        let provider_rw = factory.provider_rw()?;
+        for block in committed {
+            provider_rw.insert_historical_block(block.clone().try_recover()?)?;
+        }
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(2))?;
+        provider_rw.commit()?;
+
+        let provider = BlockchainProvider::new(factory.clone())?;
+        let follower = ReplicaFollower::new(provider.clone());
+        assert_eq!(follower.poll()?, None);
+
+        // blocks that are written but not yet marked as finished are not served
+        let provider_rw = factory.provider_rw()?;
+        for block in pending {
+            provider_rw.insert_historical_block(block.clone().try_recover()?)?;
+        }
+        provider_rw.commit()?;
+        assert_eq!(follower.poll()?, None);
+
+        let provider_rw = factory.provider_rw()?;
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(5))?;
+        provider_rw.commit()?;
+
+        let head = follower.poll()?.expect("head advanced");
+        assert_eq!(head.hash(), pending.last().unwrap().hash());
+        assert_eq!(provider.canonical_in_memory_state.get_canonical_head().hash(), head.hash());
+        assert_eq!(follower.poll()?, None);
+
+        Ok(())
+    }
+}
@@ -100,8 +100,13 @@ impl<N> Clone for StaticFileProvider<N> {
 
 impl<N: NodePrimitives> StaticFileProvider<N> {
     /// Creates a new [`StaticFileProvider`] with the given [`StaticFileAccess`].
-    fn new(path: impl AsRef<Path>, access: StaticFileAccess) -> ProviderResult<Self> {
-        let provider = Self(Arc::new(StaticFileProviderInner::new(path, access)?));
+    fn new(
+        path: impl AsRef<Path>,
+        access: StaticFileAccess,
+        lock_takeover_host: Option<&str>,
+    ) -> ProviderResult<Self> {
+        let provider =
+            Self(Arc::new(StaticFileProviderInner::new(path, access, lock_takeover_host)?));
         provider.initialize_index()?;
         Ok(provider)
     }
@@ -116,7 +121,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     ///
     /// See also [`StaticFileProvider::watch_directory`].
     pub fn read_only(path: impl AsRef<Path>, watch_directory: bool) -> ProviderResult<Self> {
-        let provider = Self::new(path, StaticFileAccess::RO)?;
+        let provider = Self::new(path, StaticFileAccess::RO, None)?;
 
         if watch_directory {
             provider.watch_directory();
@@ -127,7 +132,18 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
     /// Creates a new [`StaticFileProvider`] with read-write access.
     pub fn read_write(path: impl AsRef<Path>) -> ProviderResult<Self> {
-        Self::new(path, StaticFileAccess::RW)
+        Self::new(path, StaticFileAccess::RW, None)
+    }
+
+    /// Creates a new [`StaticFileProvider`] with read-write access, taking over the storage lock of
+    /// a process on another host if that host is `lock_takeover_host`.
+    ///
+    /// See [`StorageLock::try_acquire_with_takeover`].
+    pub fn read_write_with_lock_takeover(
+        path: impl AsRef<Path>,
+        lock_takeover_host: Option<&str>,
+    ) -> ProviderResult<Self> {
+        Self::new(path, StaticFileAccess::RW, lock_takeover_host)
     }
 
     /// Watches the directory for changes and updates the in-memory index when modifications
@@ -272,9 +288,15 @@ pub struct StaticFileProviderInner<N> {
 
 impl<N: NodePrimitives> StaticFileProviderInner<N> {
     /// Creates a new [`StaticFileProviderInner`].
-    fn new(path: impl AsRef<Path>, access: StaticFileAccess) -> ProviderResult<Self> {
+    fn new(
+        path: impl AsRef<Path>,
+        access: StaticFileAccess,
+        lock_takeover_host: Option<&str>,
+    ) -> ProviderResult<Self> {
         let _lock_file = if access.is_read_write() {
-            StorageLock::try_acquire(path.as_ref()).map_err(ProviderError::other)?.into()
+            StorageLock::try_acquire_with_takeover(path.as_ref(), lock_takeover_host)
+                .map_err(ProviderError::other)?
+                .into()
         } else {
             None
         };
@@ -1335,6 +1357,11 @@ pub trait StaticFileWriter {
         segment: StaticFileSegment,
     ) -> ProviderResult<StaticFileProviderRWRefMut<'_, Self::Primitives>>;
 
+    /// Commits all changes of the [`StaticFileProviderRW`] of the given [`StaticFileSegment`].
+    ///
+    /// Writers of different segments are independent, so they can be committed concurrently.
+    fn commit_segment(&self, segment: StaticFileSegment) -> ProviderResult<()>;
+
     /// Commits all changes of all [`StaticFileProviderRW`] of all [`StaticFileSegment`].
     fn commit(&self) -> ProviderResult<()>;
 }
@@ -1364,6 +1391,10 @@ impl<N: NodePrimitives> StaticFileWriter for StaticFileProvider<N> {
         self.get_writer(self.get_highest_static_file_block(segment).unwrap_or_default(), segment)
     }
 
+    fn commit_segment(&self, segment: StaticFileSegment) -> ProviderResult<()> {
+        self.writers.commit_segment(segment)
+    }
+
     fn commit(&self) -> ProviderResult<()> {
         self.writers.commit()
     }
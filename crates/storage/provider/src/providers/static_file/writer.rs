@@ -5,6 +5,7 @@ use crate::providers::static_file::metrics::StaticFileProviderOperation;
 use alloy_consensus::BlockHeader;
 use alloy_primitives::{BlockHash, BlockNumber, TxNumber, U256};
 use parking_lot::{lock_api::RwLockWriteGuard, RawRwLock, RwLock};
+use rayon::prelude::*;
 use reth_codecs::Compact;
 use reth_db_api::models::{
     CompactU256, StoredBlockBodyIndices, StoredBlockOmmers, StoredBlockWithdrawals,
@@ -46,17 +47,21 @@ impl<N> Default for StaticFileWriters<N> {
 }
 
 impl<N: NodePrimitives> StaticFileWriters<N> {
+    const fn writer(&self, segment: StaticFileSegment) -> &RwLock<Option<StaticFileProviderRW<N>>> {
+        match segment {
+            StaticFileSegment::Headers => &self.headers,
+            StaticFileSegment::Transactions => &self.transactions,
+            StaticFileSegment::Receipts => &self.receipts,
+            StaticFileSegment::BlockMeta => &self.block_meta,
+        }
+    }
+
     pub(crate) fn get_or_create(
         &self,
         segment: StaticFileSegment,
         create_fn: impl FnOnce() -> ProviderResult<StaticFileProviderRW<N>>,
     ) -> ProviderResult<StaticFileProviderRWRefMut<'_, N>> {
-        let mut write_guard = match segment {
-            StaticFileSegment::Headers => self.headers.write(),
-            StaticFileSegment::Transactions => self.transactions.write(),
-            StaticFileSegment::Receipts => self.receipts.write(),
-            StaticFileSegment::BlockMeta => self.block_meta.write(),
-        };
+        let mut write_guard = self.writer(segment).write();
 
         if write_guard.is_none() {
             *write_guard = Some(create_fn()?);
@@ -65,15 +70,23 @@ impl<N: NodePrimitives> StaticFileWriters<N> {
         Ok(StaticFileProviderRWRefMut(write_guard))
     }
 
-    pub(crate) fn commit(&self) -> ProviderResult<()> {
-        for writer_lock in [&self.headers, &self.transactions, &self.receipts] {
-            let mut writer = writer_lock.write();
-            if let Some(writer) = writer.as_mut() {
-                writer.commit()?;
-            }
+    /// Commits the writer of the given segment, if it was created.
+    pub(crate) fn commit_segment(&self, segment: StaticFileSegment) -> ProviderResult<()> {
+        if let Some(writer) = self.writer(segment).write().as_mut() {
+            writer.commit()?;
         }
         Ok(())
     }
+
+    /// Commits the writers of all segments.
+    ///
+    /// Every segment has its own writer and files, so they're committed in parallel and a slow
+    /// sync of one segment doesn't hold up the others.
+    pub(crate) fn commit(&self) -> ProviderResult<()> {
+        [StaticFileSegment::Headers, StaticFileSegment::Transactions, StaticFileSegment::Receipts]
+            .into_par_iter()
+            .try_for_each(|segment| self.commit_segment(segment))
+    }
 }
 
 /// Mutable reference to a [`StaticFileProviderRW`] behind a [`RwLockWriteGuard`].
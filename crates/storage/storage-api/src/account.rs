@@ -54,4 +54,25 @@ pub trait ChangeSetReader {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>>;
+
+    /// Returns every block in the given range that changed the account, together with the account
+    /// state from before that block, in ascending block order.
+    ///
+    /// The default implementation walks the account changesets of every block in the range.
+    fn account_changes(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, Option<Account>)>> {
+        let mut changes = Vec::new();
+        for block_number in range {
+            changes.extend(
+                self.account_block_changeset(block_number)?
+                    .into_iter()
+                    .find(|account_before| account_before.address == address)
+                    .map(|account_before| (block_number, account_before.info)),
+            );
+        }
+        Ok(changes)
+    }
 }
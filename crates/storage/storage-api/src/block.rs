@@ -328,6 +328,9 @@ pub trait BlockReaderIdExt: BlockReader + ReceiptProviderIdExt {
         transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<RecoveredBlock<Self::Block>>> {
         match id {
+            BlockId::Hash(hash) if hash.require_canonical == Some(true) => self
+                .canonical_block_number(hash.block_hash)?
+                .map_or_else(|| Ok(None), |num| self.recovered_block(num.into(), transaction_kind)),
             BlockId::Hash(hash) => self.recovered_block(hash.block_hash.into(), transaction_kind),
             BlockId::Number(num) => self
                 .convert_block_number(num)?
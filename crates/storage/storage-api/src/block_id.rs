@@ -81,6 +81,9 @@ pub trait BlockIdReader: BlockNumReader + Send + Sync {
     /// Get the hash of the block by matching the given id.
     fn block_hash_for_id(&self, block_id: BlockId) -> ProviderResult<Option<B256>> {
         match block_id {
+            BlockId::Hash(hash) if hash.require_canonical == Some(true) => {
+                Ok(self.canonical_block_number(hash.block_hash)?.map(|_| hash.block_hash))
+            }
             BlockId::Hash(hash) => Ok(Some(hash.into())),
             BlockId::Number(num) => match num {
                 BlockNumberOrTag::Latest => Ok(Some(self.chain_info()?.best_hash)),
@@ -98,11 +101,20 @@ pub trait BlockIdReader: BlockNumReader + Send + Sync {
     /// Get the number of the block by matching the given id.
     fn block_number_for_id(&self, block_id: BlockId) -> ProviderResult<Option<BlockNumber>> {
         match block_id {
+            BlockId::Hash(hash) if hash.require_canonical == Some(true) => {
+                self.canonical_block_number(hash.block_hash)
+            }
             BlockId::Hash(hash) => self.block_number(hash.into()),
             BlockId::Number(num) => self.convert_block_number(num),
         }
     }
 
+    /// Returns the number of the block with the given hash, if it's part of the canonical chain.
+    fn canonical_block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
+        let Some(number) = self.block_number(hash)? else { return Ok(None) };
+        Ok((self.block_hash(number)? == Some(hash)).then_some(number))
+    }
+
     /// Get the current pending block number and hash.
     fn pending_block_num_hash(&self) -> ProviderResult<Option<alloy_eips::BlockNumHash>>;
 
@@ -54,6 +54,9 @@ pub use transactions::*;
 mod trie;
 pub use trie::*;
 
+mod trace_index;
+pub use trace_index::*;
+
 mod chain_info;
 pub use chain_info::*;
 
@@ -9,7 +9,7 @@ use alloy_primitives::{Address, BlockHash, BlockNumber, StorageKey, StorageValue
 use auto_impl::auto_impl;
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives_traits::Bytecode;
-use reth_storage_errors::provider::ProviderResult;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie_common::HashedPostState;
 use revm_database::BundleState;
 
@@ -158,12 +158,19 @@ pub trait StateProviderFactory: BlockIdReader + Send + Sync {
 
     /// Returns a [`StateProvider`] indexed by the given [`BlockId`].
     ///
-    /// Note: if a number or hash is provided this will __only__ look at historical(canonical)
-    /// state.
+    /// Note: if a number is provided this will __only__ look at historical(canonical) state. A hash
+    /// also resolves known blocks that aren't canonical, such as the pending block, unless
+    /// `requireCanonical` is set as per EIP-1898.
     fn state_by_block_id(&self, block_id: BlockId) -> ProviderResult<StateProviderBox> {
         match block_id {
             BlockId::Number(block_number) => self.state_by_block_number_or_tag(block_number),
-            BlockId::Hash(block_hash) => self.history_by_block_hash(block_hash.into()),
+            BlockId::Hash(block_hash) if block_hash.require_canonical == Some(true) => {
+                if self.canonical_block_number(block_hash.block_hash)?.is_none() {
+                    return Err(ProviderError::StateForHashNotFound(block_hash.block_hash))
+                }
+                self.history_by_block_hash(block_hash.block_hash)
+            }
+            BlockId::Hash(block_hash) => self.state_by_block_hash(block_hash.block_hash),
         }
     }
 
@@ -1,6 +1,6 @@
 use alloy_primitives::BlockNumber;
 use reth_execution_types::ExecutionOutcome;
-use reth_storage_errors::provider::ProviderResult;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use reth_trie_common::HashedPostStateSorted;
 use revm_database::{
     states::{PlainStateReverts, StateChangeset},
@@ -46,6 +46,30 @@ pub trait StateWriter {
         remove_receipts_from: StorageLocation,
     ) -> ProviderResult<()>;
 
+    /// Remove the changesets and receipts of the block range above the given block without
+    /// reverting them in the plain state.
+    ///
+    /// This leaves the plain state inconsistent with the remaining changesets, so it must be
+    /// followed by [`Self::replace_plain_state`] and a replay of the blocks up to `block`.
+    ///
+    /// Returns [`ProviderError::UnsupportedProvider`] by default.
+    fn discard_state_above(
+        &self,
+        _block: BlockNumber,
+        _remove_receipts_from: StorageLocation,
+    ) -> ProviderResult<()> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    /// Replace the entire plain state with the given state.
+    ///
+    /// Bytecodes are only added, never removed.
+    ///
+    /// Returns [`ProviderError::UnsupportedProvider`] by default.
+    fn replace_plain_state(&self, _state: StateChangeset) -> ProviderResult<()> {
+        Err(ProviderError::UnsupportedProvider)
+    }
+
     /// Take the block range of state, recreating the [`ExecutionOutcome`]. The state of the passed
     /// block is not removed.
     fn take_state_above(
@@ -41,6 +41,30 @@ pub trait StorageChangeSetReader: Send + Sync {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(reth_db_api::models::BlockNumberAddress, StorageEntry)>>;
+
+    /// Returns every block in the given range that changed the storage slot of the account,
+    /// together with the value of the slot before that block, in ascending block order.
+    ///
+    /// The default implementation walks the storage changesets of every block in the range.
+    fn storage_slot_changes(
+        &self,
+        address: Address,
+        slot: B256,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, alloy_primitives::U256)>> {
+        let mut changes = Vec::new();
+        for block_number in range {
+            changes.extend(
+                self.storage_changeset(block_number)?
+                    .into_iter()
+                    .find(|(block_address, entry)| {
+                        block_address.address() == address && entry.key == slot
+                    })
+                    .map(|(_, entry)| (block_number, entry.value)),
+            );
+        }
+        Ok(changes)
+    }
 }
 
 /// An enum that represents the storage location for a piece of data.
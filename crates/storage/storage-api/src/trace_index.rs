@@ -0,0 +1,19 @@
+use alloc::vec::Vec;
+use alloy_primitives::{Address, BlockNumber};
+use core::ops::RangeInclusive;
+use reth_storage_errors::provider::ProviderResult;
+
+/// A type that maintains an index of the blocks in which an address appears in a call trace.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait TraceIndexProvider: Send + Sync + core::fmt::Debug {
+    /// Returns the numbers of all blocks in the given range in which any of the given addresses
+    /// appears as the sender or recipient of a trace, in ascending order.
+    ///
+    /// Returns `None` if the range is not fully covered by the index, in which case the caller
+    /// should fall back to tracing every block in the range.
+    fn blocks_with_addresses(
+        &self,
+        addresses: &[Address],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Option<Vec<BlockNumber>>>;
+}
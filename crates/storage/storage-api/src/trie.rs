@@ -89,6 +89,21 @@ pub trait StateProofProvider: Send + Sync {
     fn witness(&self, input: TrieInput, target: HashedPostState) -> ProviderResult<Vec<Bytes>>;
 }
 
+/// A type that can generate state transition witnesses on top of persisted historical state.
+#[auto_impl::auto_impl(&, Box, Arc)]
+pub trait HistoricalWitnessProvider: Send + Sync + core::fmt::Debug {
+    /// Get trie witness for applying the provided state on top of the state at the end of the
+    /// given block.
+    ///
+    /// Returns `None` if the block is not part of the persisted canonical chain, in which case the
+    /// caller should fall back to [`StateProofProvider::witness`].
+    fn historical_witness(
+        &self,
+        block_hash: B256,
+        target: HashedPostState,
+    ) -> ProviderResult<Option<Vec<Bytes>>>;
+}
+
 /// Trie Writer
 #[auto_impl::auto_impl(&, Arc, Box)]
 pub trait TrieWriter: Send + Sync {
@@ -1,5 +1,9 @@
 //! Reth task management.
 //!
+//! Tasks are spawned via the [`TaskExecutor`] onto the main tokio runtime, or onto the
+//! [`DedicatedRuntime`](runtime::DedicatedRuntime) of their component, see
+//! [`TaskExecutor::for_component`].
+//!
 //! # Feature Flags
 //!
 //! - `rayon`: Enable rayon thread pool for blocking tasks.
@@ -14,11 +18,12 @@
 
 use crate::{
     metrics::{IncCounterOnDrop, TaskExecutorMetrics},
+    runtime::{DedicatedRuntimes, RuntimeComponent, RuntimeMonitor, RUNTIME_METRICS_INTERVAL},
     shutdown::{signal, GracefulShutdown, GracefulShutdownGuard, Shutdown, Signal},
 };
 use dyn_clone::DynClone;
 use futures_util::{
-    future::{select, BoxFuture},
+    future::{poll_fn, select, BoxFuture},
     Future, FutureExt, TryFutureExt,
 };
 use std::{
@@ -40,6 +45,7 @@ use tracing::{debug, error};
 use tracing_futures::Instrument;
 
 pub mod metrics;
+pub mod runtime;
 pub mod shutdown;
 
 #[cfg(feature = "rayon")]
@@ -224,6 +230,7 @@ impl TaskManager {
             task_events_tx: self.task_events_tx.clone(),
             metrics: Default::default(),
             graceful_tasks: Arc::clone(&self.graceful_tasks),
+            runtimes: Default::default(),
         }
     }
 
@@ -330,6 +337,8 @@ pub struct TaskExecutor {
     metrics: TaskExecutorMetrics,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// The dedicated runtimes of components, see [`TaskExecutor::for_component`].
+    runtimes: Arc<DedicatedRuntimes>,
 }
 
 // === impl TaskExecutor ===
@@ -362,6 +371,69 @@ impl TaskExecutor {
         &self.on_shutdown
     }
 
+    /// Returns an executor that is aware of the given dedicated runtimes.
+    ///
+    /// The runtimes are kept alive for as long as this executor or any executor derived from it
+    /// exists.
+    pub fn with_dedicated_runtimes(mut self, runtimes: DedicatedRuntimes) -> Self {
+        self.runtimes = Arc::new(runtimes);
+        self
+    }
+
+    /// Returns the dedicated runtimes of components.
+    pub fn dedicated_runtimes(&self) -> &DedicatedRuntimes {
+        &self.runtimes
+    }
+
+    /// Returns an executor that spawns tasks onto the dedicated runtime of the component.
+    ///
+    /// If the component has no dedicated runtime, the returned executor spawns onto the same
+    /// runtime as this executor. Either way, tasks are still tied to the [`TaskManager`] of this
+    /// executor: they are shut down with it and a panicked critical task is reported to it.
+    pub fn for_component(&self, component: RuntimeComponent) -> Self {
+        let mut executor = self.clone();
+        if let Some(runtime) = self.runtimes.get(component) {
+            executor.handle = runtime.handle().clone();
+        }
+        executor
+    }
+
+    /// Drives the future with the runtime of this executor entered.
+    ///
+    /// The future itself is polled by the caller, but tasks it spawns via [`tokio::spawn`] and I/O
+    /// resources it creates, e.g. the listeners of a server, are bound to the runtime of this
+    /// executor.
+    pub async fn in_runtime<F: Future>(&self, fut: F) -> F::Output {
+        let mut fut = pin!(fut);
+        poll_fn(|cx| {
+            let _guard = self.handle.enter();
+            fut.as_mut().poll(cx)
+        })
+        .await
+    }
+
+    /// Spawns a task that periodically records the utilization of the runtime of this executor and
+    /// of all dedicated runtimes.
+    pub fn spawn_runtime_metrics_task(&self) -> JoinHandle<()> {
+        let mut monitors = std::iter::once(RuntimeMonitor::new("main", self.handle.clone()))
+            .chain(self.runtimes.iter().map(|runtime| {
+                RuntimeMonitor::new(runtime.component().as_str(), runtime.handle().clone())
+            }))
+            .collect::<Vec<_>>();
+
+        self.spawn(async move {
+            let mut interval = tokio::time::interval(RUNTIME_METRICS_INTERVAL);
+            let mut last = interval.tick().await;
+            loop {
+                let now = interval.tick().await;
+                for monitor in &mut monitors {
+                    monitor.record(now - last);
+                }
+                last = now;
+            }
+        })
+    }
+
     /// Spawns a future on the tokio runtime depending on the [`TaskKind`]
     fn spawn_on_rt<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
@@ -862,6 +934,29 @@ mod tests {
         assert!(!val.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn test_spawn_on_dedicated_runtime() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let manager = TaskManager::new(runtime.handle().clone());
+        let runtimes = DedicatedRuntimes::default().with_runtime(RuntimeComponent::Rpc, 1).unwrap();
+        let executor = manager.executor().with_dedicated_runtimes(runtimes);
+
+        let thread_name = |executor: TaskExecutor| {
+            let task = executor
+                .handle()
+                .spawn(async { std::thread::current().name().map(ToString::to_string) });
+            runtime.block_on(task).unwrap()
+        };
+        assert_eq!(
+            thread_name(executor.for_component(RuntimeComponent::Rpc)).as_deref(),
+            Some("reth-rpc")
+        );
+        assert_ne!(
+            thread_name(executor.for_component(RuntimeComponent::Engine)).as_deref(),
+            Some("reth-rpc")
+        );
+    }
+
     #[test]
     fn can_access_global() {
         let runtime = tokio::runtime::Runtime::new().unwrap();
@@ -0,0 +1,216 @@
+//! Dedicated tokio runtimes for components of the node.
+//!
+//! By default all tasks of the node share a single tokio runtime, so a component that is under
+//! heavy load, e.g. the RPC server, can delay the tasks of all other components. A component can
+//! instead be isolated on a [`DedicatedRuntime`] with its own worker threads, see
+//! [`TaskExecutor::with_dedicated_runtimes`](crate::TaskExecutor::with_dedicated_runtimes).
+
+use reth_metrics::{metrics::Gauge, Metrics};
+use std::{fmt, io, time::Duration};
+use tokio::runtime::{Builder, Handle, Runtime};
+
+/// The interval at which the utilization of the runtimes is recorded.
+pub const RUNTIME_METRICS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A component of the node that can run on a dedicated runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuntimeComponent {
+    /// The network stack, including peer sessions and the transaction gossip.
+    Network,
+    /// The consensus engine and the engine API server.
+    Engine,
+    /// The RPC servers and the blocking tasks of RPC handlers.
+    Rpc,
+}
+
+impl RuntimeComponent {
+    /// Returns the name of the component.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Network => "network",
+            Self::Engine => "engine",
+            Self::Rpc => "rpc",
+        }
+    }
+}
+
+impl fmt::Display for RuntimeComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A multi-threaded tokio runtime dedicated to a single [`RuntimeComponent`].
+///
+/// The runtime is shut down in the background when this type is dropped, without waiting for its
+/// tasks, so it can be dropped from within an asynchronous context.
+pub struct DedicatedRuntime {
+    component: RuntimeComponent,
+    runtime: Option<Runtime>,
+}
+
+impl DedicatedRuntime {
+    /// Creates a new runtime for the component with the given number of worker threads.
+    pub fn new(component: RuntimeComponent, worker_threads: usize) -> io::Result<Self> {
+        let runtime = Builder::new_multi_thread()
+            .worker_threads(worker_threads.max(1))
+            .thread_name(format!("reth-{component}"))
+            .enable_all()
+            .build()?;
+        Ok(Self { component, runtime: Some(runtime) })
+    }
+
+    /// Returns the component the runtime is dedicated to.
+    pub const fn component(&self) -> RuntimeComponent {
+        self.component
+    }
+
+    /// Returns the [Handle] to the runtime.
+    pub fn handle(&self) -> &Handle {
+        self.runtime.as_ref().expect("runtime is only taken on drop").handle()
+    }
+}
+
+impl fmt::Debug for DedicatedRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DedicatedRuntime")
+            .field("component", &self.component)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for DedicatedRuntime {
+    fn drop(&mut self) {
+        if let Some(runtime) = self.runtime.take() {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// The dedicated runtimes of the node, by component.
+///
+/// Components without a dedicated runtime run on the main runtime.
+#[derive(Debug, Default)]
+pub struct DedicatedRuntimes {
+    runtimes: Vec<DedicatedRuntime>,
+}
+
+impl DedicatedRuntimes {
+    /// Creates a dedicated runtime with the given number of worker threads for the component.
+    ///
+    /// Replaces an existing runtime of the component.
+    pub fn with_runtime(
+        mut self,
+        component: RuntimeComponent,
+        worker_threads: usize,
+    ) -> io::Result<Self> {
+        let runtime = DedicatedRuntime::new(component, worker_threads)?;
+        self.runtimes.retain(|runtime| runtime.component() != component);
+        self.runtimes.push(runtime);
+        Ok(self)
+    }
+
+    /// Returns the dedicated runtime of the component, if any.
+    pub fn get(&self, component: RuntimeComponent) -> Option<&DedicatedRuntime> {
+        self.runtimes.iter().find(|runtime| runtime.component() == component)
+    }
+
+    /// Returns an iterator over all dedicated runtimes.
+    pub fn iter(&self) -> impl Iterator<Item = &DedicatedRuntime> {
+        self.runtimes.iter()
+    }
+
+    /// Returns `true` if no component has a dedicated runtime.
+    pub fn is_empty(&self) -> bool {
+        self.runtimes.is_empty()
+    }
+}
+
+/// Utilization metrics of a tokio runtime.
+#[derive(Metrics)]
+#[metrics(scope = "executor.runtime")]
+pub(crate) struct RuntimeMetrics {
+    /// The number of worker threads of the runtime.
+    workers: Gauge,
+    /// The number of tasks that are currently alive.
+    alive_tasks: Gauge,
+    /// The number of tasks that are waiting in the global queue of the runtime.
+    global_queue_depth: Gauge,
+    /// The share of time the worker threads were busy in the last interval, between 0 and 1.
+    busy_ratio: Gauge,
+}
+
+/// Records the utilization of a tokio runtime.
+pub(crate) struct RuntimeMonitor {
+    handle: Handle,
+    metrics: RuntimeMetrics,
+    /// The total busy duration of all workers at the last sample.
+    busy: Duration,
+}
+
+impl RuntimeMonitor {
+    /// Creates a new monitor of the runtime with the given name.
+    pub(crate) fn new(name: &'static str, handle: Handle) -> Self {
+        let metrics = RuntimeMetrics::new_with_labels(&[("runtime", name)]);
+        let mut monitor = Self { handle, metrics, busy: Duration::ZERO };
+        monitor.busy = monitor.total_busy_duration();
+        monitor
+    }
+
+    /// Records the current utilization of the runtime, `elapsed` after the last sample.
+    pub(crate) fn record(&mut self, elapsed: Duration) {
+        let runtime_metrics = self.handle.metrics();
+        let workers = runtime_metrics.num_workers();
+        self.metrics.workers.set(workers as f64);
+        self.metrics.alive_tasks.set(runtime_metrics.num_alive_tasks() as f64);
+        self.metrics.global_queue_depth.set(runtime_metrics.global_queue_depth() as f64);
+
+        let busy = self.total_busy_duration();
+        let available = elapsed.as_secs_f64() * workers as f64;
+        if available > 0.0 {
+            let ratio = busy.saturating_sub(self.busy).as_secs_f64() / available;
+            self.metrics.busy_ratio.set(ratio.min(1.0));
+        }
+        self.busy = busy;
+    }
+
+    /// Returns the total time all workers of the runtime were busy.
+    #[cfg(target_has_atomic = "64")]
+    fn total_busy_duration(&self) -> Duration {
+        let runtime_metrics = self.handle.metrics();
+        (0..runtime_metrics.num_workers())
+            .map(|worker| runtime_metrics.worker_total_busy_duration(worker))
+            .sum()
+    }
+
+    /// Returns the total time all workers of the runtime were busy.
+    ///
+    /// Busy durations aren't tracked on targets without 64 bit atomics.
+    #[cfg(not(target_has_atomic = "64"))]
+    const fn total_busy_duration(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedicated_runtime_per_component() {
+        let runtimes = DedicatedRuntimes::default()
+            .with_runtime(RuntimeComponent::Rpc, 1)
+            .unwrap()
+            .with_runtime(RuntimeComponent::Rpc, 2)
+            .unwrap();
+
+        assert_eq!(runtimes.iter().count(), 1);
+        assert!(runtimes.get(RuntimeComponent::Engine).is_none());
+        let rpc = runtimes.get(RuntimeComponent::Rpc).unwrap();
+        assert_eq!(rpc.handle().metrics().num_workers(), 2);
+
+        // the runtime can be dropped from within another runtime
+        let main = tokio::runtime::Runtime::new().unwrap();
+        main.block_on(async move { drop(runtimes) });
+    }
+}
@@ -196,6 +196,14 @@ impl BlobStore for DiskFileBlobStore {
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError> {
+        // only return the blobs if we found all requested versioned hashes
+        Ok(self.get_by_versioned_hashes_v3(versioned_hashes)?.into_iter().collect())
+    }
+
+    fn get_by_versioned_hashes_v3(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError> {
         // we must return the blobs in order but we don't necessarily find them in the requested
         // order
         let mut result = vec![None; versioned_hashes.len()];
@@ -213,7 +221,7 @@ impl BlobStore for DiskFileBlobStore {
             // return early if all blobs are found.
             if result.iter().all(|blob| blob.is_some()) {
                 // got all blobs, can return early
-                return Ok(Some(result.into_iter().map(Option::unwrap).collect()))
+                return Ok(result)
             }
         }
 
@@ -249,12 +257,7 @@ impl BlobStore for DiskFileBlobStore {
             }
         }
 
-        // only return the blobs if we found all requested versioned hashes
-        if result.iter().all(|blob| blob.is_some()) {
-            Ok(Some(result.into_iter().map(Option::unwrap).collect()))
-        } else {
-            Ok(None)
-        }
+        Ok(result)
     }
 
     fn data_size_hint(&self) -> Option<usize> {
@@ -639,7 +642,12 @@ pub enum OpenDiskFileBlobStore {
 #[cfg(test)]
 mod tests {
     use alloy_consensus::BlobTransactionSidecar;
-    use alloy_eips::eip7594::BlobTransactionSidecarVariant;
+    use alloy_eips::{
+        eip4844::{kzg_to_versioned_hash, Blob, Bytes48},
+        eip7594::{
+            BlobTransactionSidecarEip7594, BlobTransactionSidecarVariant, CELLS_PER_EXT_BLOB,
+        },
+    };
 
     use super::*;
     use std::sync::atomic::Ordering;
@@ -834,4 +842,29 @@ mod tests {
         assert_eq!(stat.delete_succeed, 3);
         assert_eq!(stat.delete_failed, 0);
     }
+
+    #[test]
+    fn disk_get_by_versioned_hashes_partial() {
+        let (store, _dir) = tmp_store();
+
+        let commitment = Bytes48::repeat_byte(1);
+        let sidecar = BlobTransactionSidecarEip7594::new(
+            vec![Blob::ZERO],
+            vec![commitment],
+            vec![Bytes48::ZERO; CELLS_PER_EXT_BLOB],
+        );
+        store.insert(TxHash::random(), BlobTransactionSidecarVariant::Eip7594(sidecar)).unwrap();
+
+        let known = kzg_to_versioned_hash(commitment.as_slice());
+        let missing = B256::random();
+
+        let blobs = store.get_by_versioned_hashes_v3(&[known, missing]).unwrap();
+        assert_eq!(blobs.len(), 2);
+        assert_eq!(blobs[0].as_ref().unwrap().proofs.len(), CELLS_PER_EXT_BLOB);
+        assert!(blobs[1].is_none());
+
+        // v2 only returns the blobs if all of them are found
+        assert!(store.get_by_versioned_hashes_v2(&[known, missing]).unwrap().is_none());
+        assert_eq!(store.get_by_versioned_hashes_v2(&[known]).unwrap().unwrap().len(), 1);
+    }
 }
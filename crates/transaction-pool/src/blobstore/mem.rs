@@ -129,6 +129,14 @@ impl BlobStore for InMemoryBlobStore {
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError> {
+        // only return the blobs if we found all requested versioned hashes
+        Ok(self.get_by_versioned_hashes_v3(versioned_hashes)?.into_iter().collect())
+    }
+
+    fn get_by_versioned_hashes_v3(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError> {
         let mut result = vec![None; versioned_hashes.len()];
         for (_tx_hash, blob_sidecar) in self.inner.store.read().iter() {
             if let Some(blob_sidecar) = blob_sidecar.as_eip7594() {
@@ -143,11 +151,7 @@ impl BlobStore for InMemoryBlobStore {
                 break;
             }
         }
-        if result.iter().all(|blob| blob.is_some()) {
-            Ok(Some(result.into_iter().map(Option::unwrap).collect()))
-        } else {
-            Ok(None)
-        }
+        Ok(result)
     }
 
     fn data_size_hint(&self) -> Option<usize> {
@@ -98,6 +98,17 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
         versioned_hashes: &[B256],
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError>;
 
+    /// Return the [`BlobAndProofV2`]s for a list of blob versioned hashes, with an entry for each
+    /// requested versioned hash.
+    ///
+    /// Unlike [`BlobStore::get_by_versioned_hashes_v2`] this returns partial results: blobs that
+    /// are not present in the blob store are returned as `None`, so `[A, None, C]` is returned
+    /// instead of `None`. This allows the consensus client to only fetch the missing blobs.
+    fn get_by_versioned_hashes_v3(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError>;
+
     /// Data size of all transactions in the blob store.
     fn data_size_hint(&self) -> Option<usize>;
 
@@ -65,6 +65,11 @@ pub struct PoolConfig {
     pub max_new_pending_txs_notifications: usize,
     /// Maximum lifetime for transactions in the pool
     pub max_queued_lifetime: Duration,
+    /// Maximum time a transaction may stay parked before it's evicted with a
+    /// [`TransactionEvent::Expired`](crate::TransactionEvent::Expired) event.
+    ///
+    /// Disabled if `None`.
+    pub max_parked_lifetime: Option<Duration>,
 }
 
 impl PoolConfig {
@@ -96,6 +101,7 @@ impl Default for PoolConfig {
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_parked_lifetime: None,
         }
     }
 }
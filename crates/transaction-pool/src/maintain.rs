@@ -57,6 +57,11 @@ pub struct MaintainPoolConfig {
     /// Default: 3 hours
     pub max_tx_lifetime: Duration,
 
+    /// Maximum amount of time a transaction may stay parked before it's evicted, notifying
+    /// listeners with the reason why it wasn't included.
+    /// Default: disabled
+    pub max_parked_lifetime: Option<Duration>,
+
     /// Apply no exemptions to the locally received transactions.
     ///
     /// This includes:
@@ -71,6 +76,7 @@ impl Default for MaintainPoolConfig {
             max_update_depth: 64,
             max_reload_accounts: 100,
             max_tx_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
+            max_parked_lifetime: None,
             no_local_exemptions: false,
         }
     }
@@ -148,8 +154,10 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
             pending_basefee: chain_spec
                 .next_block_base_fee(latest.header(), latest.timestamp())
                 .unwrap_or_default(),
-            pending_blob_fee: latest
-                .maybe_next_block_blob_fee(chain_spec.blob_params_at_timestamp(latest.timestamp())),
+            pending_blob_fee: chain_spec.next_block_blob_fee(
+                latest.header(),
+                chain_spec.blob_params_at_timestamp(latest.timestamp()),
+            ),
         };
         pool.set_block_info(info);
     }
@@ -173,6 +181,10 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     // eviction interval for stale non local txs
     let mut stale_eviction_interval = time::interval(config.max_tx_lifetime);
 
+    // eviction interval for expired parked txs, only polled if enabled
+    let mut parked_eviction_interval =
+        time::interval(config.max_parked_lifetime.unwrap_or(config.max_tx_lifetime));
+
     // toggle for the first notification
     let mut first_event = true;
 
@@ -281,6 +293,12 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 debug!(target: "txpool", count=%stale_txs.len(), "removing stale transactions");
                 pool.remove_transactions(stale_txs);
             }
+            _ = parked_eviction_interval.tick(), if config.max_parked_lifetime.is_some() => {
+                if let Some(max_lifetime) = config.max_parked_lifetime {
+                    let expired = pool.remove_expired_transactions(max_lifetime);
+                    debug!(target: "txpool", count=%expired.len(), "removed expired transactions");
+                }
+            }
         }
         // handle the result of the account reload
         match reloaded {
@@ -328,7 +346,8 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 let pending_block_base_fee = chain_spec
                     .next_block_base_fee(new_tip.header(), new_tip.timestamp())
                     .unwrap_or_default();
-                let pending_block_blob_fee = new_tip.header().maybe_next_block_blob_fee(
+                let pending_block_blob_fee = chain_spec.next_block_blob_fee(
+                    new_tip.header(),
                     chain_spec.blob_params_at_timestamp(new_tip.timestamp()),
                 );
 
@@ -431,7 +450,8 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 let pending_block_base_fee = chain_spec
                     .next_block_base_fee(tip.header(), tip.timestamp())
                     .unwrap_or_default();
-                let pending_block_blob_fee = tip.header().maybe_next_block_blob_fee(
+                let pending_block_blob_fee = chain_spec.next_block_blob_fee(
+                    tip.header(),
                     chain_spec.blob_params_at_timestamp(tip.timestamp()),
                 );
 
@@ -716,16 +736,117 @@ pub async fn backup_local_transactions_task<P>(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
+        blobstore::InMemoryBlobStore, test_utils::testing_pool,
+        validate::EthTransactionValidatorBuilder, CoinbaseTipOrdering, EthPooledTransaction, Pool,
+        TransactionOrigin,
+    };
+    use alloy_consensus::Header;
+    use alloy_eips::{eip2718::Decodable2718, eip7840::BlobParams};
+    use alloy_genesis::Genesis;
+    use alloy_primitives::{hex, B256, U256};
+    use reth_chainspec::{
+        BaseFeeParams, Chain, ChainSpec, ChainSpecBuilder, DepositContract, EthChainSpec,
     };
-    use alloy_eips::eip2718::Decodable2718;
-    use alloy_primitives::{hex, U256};
-    use reth_ethereum_primitives::{PooledTransactionVariant, TransactionSigned};
+    use reth_ethereum_primitives::{EthPrimitives, PooledTransactionVariant, TransactionSigned};
     use reth_fs_util as fs;
+    use reth_network_peers::NodeRecord;
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
 
+    /// The blob base fee of [`FlatBlobFeeChainSpec`].
+    const FLAT_BLOB_FEE: u128 = 1_000;
+
+    /// A chain with a flat blob base fee, independent of the excess blob gas.
+    #[derive(Debug)]
+    struct FlatBlobFeeChainSpec(ChainSpec);
+
+    impl EthChainSpec for FlatBlobFeeChainSpec {
+        type Header = Header;
+
+        fn chain(&self) -> Chain {
+            self.0.chain()
+        }
+
+        fn base_fee_params_at_block(&self, block_number: u64) -> BaseFeeParams {
+            self.0.base_fee_params_at_block(block_number)
+        }
+
+        fn base_fee_params_at_timestamp(&self, timestamp: u64) -> BaseFeeParams {
+            self.0.base_fee_params_at_timestamp(timestamp)
+        }
+
+        fn blob_params_at_timestamp(&self, timestamp: u64) -> Option<BlobParams> {
+            self.0.blob_params_at_timestamp(timestamp)
+        }
+
+        fn deposit_contract(&self) -> Option<&DepositContract> {
+            self.0.deposit_contract()
+        }
+
+        fn genesis_hash(&self) -> B256 {
+            self.0.genesis_hash()
+        }
+
+        fn prune_delete_limit(&self) -> usize {
+            self.0.prune_delete_limit()
+        }
+
+        fn display_hardforks(&self) -> Box<dyn std::fmt::Display> {
+            Box::new(self.0.display_hardforks())
+        }
+
+        fn genesis_header(&self) -> &Self::Header {
+            self.0.genesis_header()
+        }
+
+        fn genesis(&self) -> &Genesis {
+            self.0.genesis()
+        }
+
+        fn bootnodes(&self) -> Option<Vec<NodeRecord>> {
+            self.0.bootnodes()
+        }
+
+        fn final_paris_total_difficulty(&self) -> Option<U256> {
+            self.0.final_paris_total_difficulty()
+        }
+
+        fn blob_base_fee(&self, _blob_params: BlobParams, _excess_blob_gas: u64) -> u128 {
+            FLAT_BLOB_FEE
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pending_blob_fee_of_chain_spec() {
+        let chain_spec =
+            FlatBlobFeeChainSpec(ChainSpecBuilder::mainnet().cancun_activated().build());
+        let provider = MockEthProvider::default().with_chain_spec(chain_spec);
+        let latest = Header {
+            number: 1,
+            timestamp: 12,
+            gas_limit: 30_000_000,
+            base_fee_per_gas: Some(7),
+            blob_gas_used: Some(0),
+            excess_blob_gas: Some(10_000_000),
+            ..Default::default()
+        };
+        provider.add_header(latest.hash_slow(), latest);
+
+        let pool = testing_pool();
+        let manager = TaskManager::new(tokio::runtime::Handle::current());
+        // the pool is initialized from the latest header before the (empty) event stream ends
+        maintain_transaction_pool(
+            provider,
+            pool.clone(),
+            futures_util::stream::empty::<CanonStateNotification<EthPrimitives>>(),
+            manager.executor(),
+            Default::default(),
+        )
+        .await;
+
+        assert_eq!(pool.block_info().pending_blob_fee, Some(FLAT_BLOB_FEE));
+    }
+
     #[test]
     fn changed_acc_entry() {
         let changed_acc = ChangedAccountEntry(ChangedAccount::empty(Address::random()));
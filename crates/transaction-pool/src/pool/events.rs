@@ -32,6 +32,13 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
     Discarded(TxHash),
     /// Transaction became invalid indefinitely.
     Invalid(TxHash),
+    /// Transaction was evicted after being parked for longer than the configured lifetime.
+    Expired {
+        /// The hash of the evicted transaction.
+        tx_hash: TxHash,
+        /// Why the transaction wasn't included while it was parked.
+        reason: NotIncludedReason,
+    },
     /// Transaction was propagated to peers.
     Propagated(Arc<Vec<PropagateKind>>),
 }
@@ -49,6 +56,9 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
             }
             Self::Discarded(hash) => Self::Discarded(*hash),
             Self::Invalid(hash) => Self::Invalid(*hash),
+            Self::Expired { tx_hash, reason } => {
+                Self::Expired { tx_hash: *tx_hash, reason: *reason }
+            }
             Self::Propagated(propagated) => Self::Propagated(Arc::clone(propagated)),
         }
     }
@@ -72,6 +82,9 @@ pub enum TransactionEvent {
     Discarded,
     /// Transaction became invalid indefinitely.
     Invalid,
+    /// Transaction was evicted after being parked for longer than the configured lifetime, with
+    /// the reason why it wasn't included.
+    Expired(NotIncludedReason),
     /// Transaction was propagated to peers.
     Propagated(Arc<Vec<PropagateKind>>),
 }
@@ -80,7 +93,61 @@ impl TransactionEvent {
     /// Returns `true` if the event is final and no more events are expected for this transaction
     /// hash.
     pub const fn is_final(&self) -> bool {
-        matches!(self, Self::Replaced(_) | Self::Mined(_) | Self::Discarded)
+        matches!(self, Self::Replaced(_) | Self::Mined(_) | Self::Discarded | Self::Expired(_))
+    }
+}
+
+/// Why a parked transaction wasn't included in a block, see [`TransactionEvent::Expired`].
+///
+/// This is a hint for resubmitting the transaction, derived from the first requirement of the
+/// pending block the transaction didn't meet.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum NotIncludedReason {
+    /// The nonce of the transaction is ahead of the next nonce of the sender and the transactions
+    /// in between are missing.
+    NonceGap {
+        /// The on-chain nonce of the sender.
+        state_nonce: u64,
+        /// The nonce of the transaction.
+        nonce: u64,
+    },
+    /// A previous transaction of the sender is parked.
+    ParkedAncestor,
+    /// The balance of the sender doesn't cover the cost of the transaction and all previous
+    /// transactions of the sender.
+    InsufficientBalance,
+    /// The gas limit of the transaction exceeds the block gas limit.
+    ExceedsBlockGasLimit,
+    /// The max fee per gas of the transaction is below the base fee of the pending block.
+    Underpriced {
+        /// The max fee per gas of the transaction.
+        max_fee_per_gas: u128,
+        /// The base fee of the pending block.
+        base_fee: u128,
+    },
+    /// The max fee per blob gas of the transaction is below the blob fee of the pending block.
+    BlobFeeTooLow {
+        /// The max fee per blob gas of the transaction.
+        max_fee_per_blob_gas: u128,
+        /// The blob fee of the pending block.
+        blob_fee: u128,
+    },
+}
+
+impl NotIncludedReason {
+    /// Returns by how much the fee cap of the transaction is below the fee of the pending block,
+    /// if the transaction was underpriced.
+    pub const fn fee_shortfall(&self) -> Option<u128> {
+        match self {
+            Self::Underpriced { max_fee_per_gas, base_fee } => {
+                Some(base_fee.saturating_sub(*max_fee_per_gas))
+            }
+            Self::BlobFeeTooLow { max_fee_per_blob_gas, blob_fee } => {
+                Some(blob_fee.saturating_sub(*max_fee_per_blob_gas))
+            }
+            _ => None,
+        }
     }
 }
 
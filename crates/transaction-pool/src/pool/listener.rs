@@ -1,7 +1,9 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent},
+    pool::events::{
+        FullTransactionEvent, NewTransactionEvent, NotIncludedReason, TransactionEvent,
+    },
     traits::{NewBlobSidecar, PropagateKind},
     PoolTransaction, ValidPoolTransaction,
 };
@@ -172,6 +174,15 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
     }
 
+    /// Notify listeners about a parked transaction that expired.
+    pub(crate) fn expired(&mut self, tx: &TxHash, reason: NotIncludedReason) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Expired(reason),
+            FullTransactionEvent::Expired { tx_hash: *tx, reason },
+        );
+    }
+
     /// Notify listeners about a transaction that was invalid.
     pub(crate) fn invalid(&mut self, tx: &TxHash) {
         self.broadcast_event(tx, TransactionEvent::Invalid, FullTransactionEvent::Invalid(*tx));
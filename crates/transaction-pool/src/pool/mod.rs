@@ -97,13 +97,18 @@ use reth_execution_types::ChangedAccount;
 use alloy_eips::{eip7594::BlobTransactionSidecarVariant, Typed2718};
 use reth_primitives_traits::Recovered;
 use rustc_hash::FxHashMap;
-use std::{collections::HashSet, fmt, sync::Arc, time::Instant};
+use std::{
+    collections::HashSet,
+    fmt,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
 mod events;
 pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
-pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
+pub use events::{FullTransactionEvent, NewTransactionEvent, NotIncludedReason, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents, TransactionListenerKind};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
@@ -771,6 +776,28 @@ where
         removed
     }
 
+    /// Removes and returns all parked transactions that have been in the pool for longer than
+    /// `max_lifetime`.
+    ///
+    /// Listeners are notified with a [`TransactionEvent::Expired`] event that includes the reason
+    /// why the transaction wasn't included.
+    pub fn remove_expired_transactions(
+        &self,
+        max_lifetime: Duration,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let expired = self.pool.write().remove_expired_transactions(max_lifetime);
+
+        let mut listener = self.event_listener.write();
+
+        expired
+            .into_iter()
+            .map(|(tx, reason)| {
+                listener.expired(tx.hash(), reason);
+                tx
+            })
+            .collect()
+    }
+
     /// Removes and returns all matching transactions and their dependent transactions from the
     /// pool.
     pub fn remove_transactions_and_descendants(
@@ -11,6 +11,7 @@ use crate::{
     pool::{
         best::BestTransactions,
         blob::BlobTransactions,
+        events::NotIncludedReason,
         parked::{BasefeeOrd, ParkedPool, QueuedOrd},
         pending::PendingPool,
         state::{SubPool, TxState},
@@ -39,6 +40,7 @@ use std::{
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
+    time::Duration,
 };
 use tracing::trace;
 
@@ -888,6 +890,77 @@ impl<T: TransactionOrdering> TxPool<T> {
         txs
     }
 
+    /// Removes and returns all parked transactions that have been in the pool for longer than
+    /// `max_lifetime`, together with the reason why they weren't included.
+    ///
+    /// Local transactions are exempt unless local exemptions are disabled.
+    pub(crate) fn remove_expired_transactions(
+        &mut self,
+        max_lifetime: Duration,
+    ) -> Vec<(Arc<ValidPoolTransaction<T::Transaction>>, NotIncludedReason)> {
+        let expired = self
+            .all_transactions
+            .txs
+            .values()
+            .filter(|tx| {
+                !tx.subpool.is_pending() &&
+                    tx.transaction.timestamp.elapsed() > max_lifetime &&
+                    !self
+                        .config
+                        .local_transactions_config
+                        .is_local(tx.transaction.origin, tx.transaction.sender_ref())
+            })
+            .map(|tx| (*tx.transaction.hash(), self.not_included_reason(tx)))
+            .collect::<Vec<_>>();
+
+        let mut removed = Vec::with_capacity(expired.len());
+        for (hash, reason) in expired {
+            if let Some(tx) = self.remove_transaction_by_hash(&hash) {
+                removed.push((tx, reason));
+            }
+        }
+        self.update_size_metrics();
+        removed
+    }
+
+    /// Returns why the given parked transaction isn't included in the pending block.
+    ///
+    /// This is derived from the first unmet requirement of the transaction's [`TxState`].
+    fn not_included_reason(
+        &self,
+        tx: &PoolInternalTransaction<T::Transaction>,
+    ) -> NotIncludedReason {
+        let state = tx.state;
+        if state.has_nonce_gap() {
+            let state_nonce = self
+                .sender_info
+                .get(&tx.transaction.sender_id())
+                .map(|info| info.state_nonce)
+                .unwrap_or_default();
+            return NotIncludedReason::NonceGap { state_nonce, nonce: tx.transaction.nonce() }
+        }
+        if !state.contains(TxState::NO_PARKED_ANCESTORS) {
+            return NotIncludedReason::ParkedAncestor
+        }
+        if !state.contains(TxState::ENOUGH_BALANCE) {
+            return NotIncludedReason::InsufficientBalance
+        }
+        if !state.contains(TxState::NOT_TOO_MUCH_GAS) {
+            return NotIncludedReason::ExceedsBlockGasLimit
+        }
+        let pending_fees = &self.all_transactions.pending_fees;
+        if !state.contains(TxState::ENOUGH_FEE_CAP_BLOCK) {
+            return NotIncludedReason::Underpriced {
+                max_fee_per_gas: tx.transaction.max_fee_per_gas(),
+                base_fee: pending_fees.base_fee as u128,
+            }
+        }
+        NotIncludedReason::BlobFeeTooLow {
+            max_fee_per_blob_gas: tx.transaction.max_fee_per_blob_gas().unwrap_or_default(),
+            blob_fee: pending_fees.blob_fee,
+        }
+    }
+
     /// Removes and returns all matching transactions and their descendants from the pool.
     pub(crate) fn remove_transactions_and_descendants(
         &mut self,
@@ -86,6 +86,7 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 use tokio::sync::mpsc::Receiver;
 
@@ -391,6 +392,18 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Removes all parked transactions that have been in the pool for longer than `max_lifetime`.
+    ///
+    /// Listeners are notified with a
+    /// [`TransactionEvent::Expired`](crate::TransactionEvent::Expired) event that includes the
+    /// reason why the transaction wasn't included.
+    ///
+    /// Consumer: Utility
+    fn remove_expired_transactions(
+        &self,
+        max_lifetime: Duration,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Retains only those hashes that are unknown to the pool.
     /// In other words, removes all transactions from the given set that are currently present in
     /// the pool. Returns hashes already known to the pool.
@@ -558,6 +571,14 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         &self,
         versioned_hashes: &[B256],
     ) -> Result<Option<Vec<BlobAndProofV2>>, BlobStoreError>;
+
+    /// Return the [`BlobAndProofV2`]s for a list of blob versioned hashes.
+    /// Unlike [`TransactionPool::get_blobs_for_versioned_hashes_v2`], the blobs that are present
+    /// are returned even if some of the requested versioned hashes are missing.
+    fn get_blobs_for_versioned_hashes_v3(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV2>>, BlobStoreError>;
 }
 
 /// Extension for [`TransactionPool`] trait that allows to set the current block info.
@@ -2,10 +2,10 @@ use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, PoolTransaction, TransactionEvent, TransactionListenerKind,
-    TransactionOrigin, TransactionPool,
+    FullTransactionEvent, NotIncludedReason, PoolTransaction, TransactionEvent,
+    TransactionListenerKind, TransactionOrigin, TransactionPool,
 };
-use std::{future::poll_fn, task::Poll};
+use std::{future::poll_fn, task::Poll, time::Duration};
 use tokio_stream::StreamExt;
 
 #[tokio::test(flavor = "multi_thread")]
@@ -85,6 +85,36 @@ async fn txpool_listener_queued_event() {
     assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Queued(hash)) if hash == *transaction.get_hash());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_listener_expired_event() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let transaction = mock_tx_factory.create_eip1559().transaction.inc_nonce();
+    let nonce = *transaction.get_nonce();
+
+    let mut all_tx_events = txpool.all_transactions_event_listener();
+
+    let result = txpool
+        .add_transaction_and_subscribe(TransactionOrigin::External, transaction.clone())
+        .await;
+    assert_matches!(result, Ok(_));
+
+    let mut events = result.unwrap();
+    assert_matches!(events.next().await, Some(TransactionEvent::Queued));
+    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Queued(hash)) if hash == *transaction.get_hash());
+
+    let expired = txpool.remove_expired_transactions(Duration::ZERO);
+    assert_eq!(expired.len(), 1);
+    assert!(!txpool.contains(transaction.get_hash()));
+
+    // The listeners should receive the expired event with the nonce gap as reason.
+    assert_matches!(
+        events.next().await,
+        Some(TransactionEvent::Expired(NotIncludedReason::NonceGap { nonce: n, .. })) if n == nonce
+    );
+    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Expired { tx_hash, reason: NotIncludedReason::NonceGap { .. } }) if tx_hash == *transaction.get_hash());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_listener_invalid_event() {
     let txpool =
@@ -22,6 +22,9 @@ pub mod proof;
 
 pub mod proof_task;
 
+/// Implementation of parallel witness computation.
+pub mod witness;
+
 /// Parallel state root metrics.
 #[cfg(feature = "metrics")]
 pub mod metrics;
@@ -0,0 +1,302 @@
+use crate::{
+    proof::ParallelProof,
+    proof_task::{ProofTaskCtx, ProofTaskManager, ProofTaskManagerHandle},
+    root::ParallelStateRootError,
+};
+use alloy_primitives::{map::B256Map, Bytes, B256};
+use reth_execution_errors::{StateProofError, TrieWitnessError};
+use reth_provider::{
+    providers::ConsistentDbView, BlockHashReader, BlockNumReader, BlockReader, DBProvider,
+    DatabaseProviderFactory, FactoryTx, HistoricalWitnessProvider, ProviderError,
+    StateCommitmentProvider,
+};
+use reth_storage_errors::{db::DatabaseError, provider::ProviderResult};
+use reth_trie::{
+    hashed_cursor::HashedPostStateCursorFactory, prefix_set::TriePrefixSetsMut,
+    trie_cursor::InMemoryTrieCursorFactory, updates::TrieUpdatesSorted, witness::TrieWitness,
+    HashedPostState, HashedPostStateSorted, TrieInput,
+};
+use reth_trie_db::{
+    DatabaseHashedCursorFactory, DatabaseHashedPostState, DatabaseTrieCursorFactory,
+    StateCommitment,
+};
+use std::{fmt::Debug, sync::Arc};
+use tokio::runtime::Handle;
+use tracing::debug;
+
+/// Parallel state transition witness calculator.
+///
+/// The initial multiproof for the witness targets is collected with [`ParallelProof`], and any
+/// additional blinded nodes required to apply the state transition are fetched through the proof
+/// task manager, so that all database reads are spread across multiple transactions.
+#[derive(Debug)]
+pub struct ParallelWitness<Factory: DatabaseProviderFactory> {
+    /// Consistent view of the database.
+    view: ConsistentDbView<Factory>,
+    /// The sorted collection of cached in-memory intermediate trie nodes that
+    /// can be reused for computation.
+    pub nodes_sorted: Arc<TrieUpdatesSorted>,
+    /// The sorted in-memory overlay hashed state.
+    pub state_sorted: Arc<HashedPostStateSorted>,
+    /// The collection of prefix sets for the computation.
+    pub prefix_sets: Arc<TriePrefixSetsMut>,
+    /// Flag indicating whether the root node should always be included (even if the target state
+    /// is empty).
+    always_include_root_node: bool,
+    /// Handle to the proof task manager.
+    proof_task_handle: ProofTaskManagerHandle<FactoryTx<Factory>>,
+}
+
+impl<Factory: DatabaseProviderFactory> ParallelWitness<Factory> {
+    /// Create new parallel witness generator.
+    pub const fn new(
+        view: ConsistentDbView<Factory>,
+        nodes_sorted: Arc<TrieUpdatesSorted>,
+        state_sorted: Arc<HashedPostStateSorted>,
+        prefix_sets: Arc<TriePrefixSetsMut>,
+        proof_task_handle: ProofTaskManagerHandle<FactoryTx<Factory>>,
+    ) -> Self {
+        Self {
+            view,
+            nodes_sorted,
+            state_sorted,
+            prefix_sets,
+            always_include_root_node: false,
+            proof_task_handle,
+        }
+    }
+
+    /// Set `always_include_root_node` to true. Root node will be included even on empty state.
+    pub const fn always_include_root_node(mut self) -> Self {
+        self.always_include_root_node = true;
+        self
+    }
+}
+
+impl<Factory> ParallelWitness<Factory>
+where
+    Factory:
+        DatabaseProviderFactory<Provider: BlockReader> + StateCommitmentProvider + Clone + 'static,
+{
+    /// Compute the state transition witness for the trie. Gather all required nodes
+    /// to apply `state` on top of the current trie state.
+    pub fn compute(self, state: HashedPostState) -> Result<B256Map<Bytes>, ParallelStateRootError> {
+        let provider_ro = self.view.provider_ro()?;
+        let trie_cursor_factory = InMemoryTrieCursorFactory::new(
+            DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+            &self.nodes_sorted,
+        );
+        let hashed_cursor_factory = HashedPostStateCursorFactory::new(
+            DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
+            &self.state_sorted,
+        );
+
+        let mut witness = TrieWitness::new(trie_cursor_factory, hashed_cursor_factory)
+            .with_prefix_sets_mut((*self.prefix_sets).clone());
+        if self.always_include_root_node {
+            witness = witness.always_include_root_node();
+        }
+
+        let proof = ParallelProof::new(
+            self.view.clone(),
+            self.nodes_sorted.clone(),
+            self.state_sorted.clone(),
+            self.prefix_sets.clone(),
+            self.proof_task_handle.clone(),
+        );
+        let result = witness
+            .compute_with_multiproof(
+                state,
+                |targets| {
+                    proof.decoded_multiproof(targets).map_err(|err| {
+                        TrieWitnessError::Proof(StateProofError::Database(DatabaseError::Other(
+                            err.to_string(),
+                        )))
+                    })
+                },
+                self.proof_task_handle,
+            )
+            .map_err(ProviderError::from)?;
+
+        debug!(target: "trie::parallel_witness", nodes = result.len(), "Calculated witness");
+
+        Ok(result)
+    }
+}
+
+/// A [`HistoricalWitnessProvider`] that computes witnesses with [`ParallelWitness`].
+///
+/// The historical state is reconstructed by reverting the persisted state back to the requested
+/// block, after which all trie nodes are collected in parallel on top of it.
+#[derive(Debug, Clone)]
+pub struct ParallelWitnessProvider<Factory> {
+    /// The database provider factory.
+    factory: Factory,
+    /// Handle to the runtime used for spawning proof tasks.
+    executor: Handle,
+    /// The maximum number of database transactions used for proof tasks.
+    max_concurrency: usize,
+}
+
+impl<Factory> ParallelWitnessProvider<Factory> {
+    /// Create new parallel historical witness provider.
+    pub const fn new(factory: Factory, executor: Handle, max_concurrency: usize) -> Self {
+        Self { factory, executor, max_concurrency }
+    }
+}
+
+impl<Factory> HistoricalWitnessProvider for ParallelWitnessProvider<Factory>
+where
+    Factory: DatabaseProviderFactory<Provider: BlockReader>
+        + StateCommitmentProvider
+        + Clone
+        + Debug
+        + 'static,
+{
+    fn historical_witness(
+        &self,
+        block_hash: B256,
+        target: HashedPostState,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        let view = ConsistentDbView::new_with_latest_tip(self.factory.clone())?;
+        let provider_ro = view.provider_ro()?;
+
+        // Only canonical blocks that have already been persisted can be reverted to.
+        let Some(block_number) = provider_ro.block_number(block_hash)? else { return Ok(None) };
+        if block_number > provider_ro.last_block_number()? ||
+            provider_ro.block_hash(block_number)? != Some(block_hash)
+        {
+            return Ok(None)
+        }
+
+        let revert_state = HashedPostState::from_reverts::<
+            <Factory::StateCommitment as StateCommitment>::KeyHasher,
+        >(provider_ro.tx_ref(), block_number + 1)?;
+        drop(provider_ro);
+
+        let input = TrieInput::from_state(revert_state);
+        let nodes_sorted = Arc::new(input.nodes.into_sorted());
+        let state_sorted = Arc::new(input.state.into_sorted());
+        let prefix_sets = Arc::new(input.prefix_sets);
+
+        let task_ctx =
+            ProofTaskCtx::new(nodes_sorted.clone(), state_sorted.clone(), prefix_sets.clone());
+        let proof_task = ProofTaskManager::new(
+            self.executor.clone(),
+            view.clone(),
+            task_ctx,
+            self.max_concurrency,
+        );
+        let proof_task_handle = proof_task.handle();
+        // The proof task manager terminates once the last handle is dropped.
+        self.executor.spawn_blocking(move || proof_task.run());
+
+        debug!(
+            target: "trie::parallel_witness",
+            block_number,
+            ?block_hash,
+            "Computing historical witness"
+        );
+
+        let witness =
+            ParallelWitness::new(view, nodes_sorted, state_sorted, prefix_sets, proof_task_handle)
+                .compute(target)?;
+
+        Ok(Some(witness.into_values().collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, map::HashMap, Address, U256};
+    use rand::Rng;
+    use reth_primitives_traits::{Account, StorageEntry};
+    use reth_provider::{test_utils::create_test_provider_factory, HashingWriter};
+    use reth_trie::HashedStorage;
+    use tokio::runtime::Runtime;
+
+    #[test]
+    fn random_parallel_witness() {
+        let factory = create_test_provider_factory();
+        let consistent_view = ConsistentDbView::new(factory.clone(), None);
+
+        let mut rng = rand::rng();
+        let state = (0..100)
+            .map(|_| {
+                let address = Address::random();
+                let account =
+                    Account { balance: U256::from(rng.random::<u64>()), ..Default::default() };
+                let storage = (0..10)
+                    .map(|_| StorageEntry {
+                        key: B256::from(U256::from(rng.random::<u64>())),
+                        value: U256::from(rng.random::<u64>()),
+                    })
+                    .collect::<Vec<_>>();
+                (address, (account, storage))
+            })
+            .collect::<HashMap<_, _>>();
+
+        {
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw
+                .insert_account_for_hashing(
+                    state.iter().map(|(address, (account, _))| (*address, Some(*account))),
+                )
+                .unwrap();
+            provider_rw
+                .insert_storage_for_hashing(
+                    state.iter().map(|(address, (_, storage))| (*address, storage.clone())),
+                )
+                .unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        // Update the balance and first slot of a subset of accounts.
+        let mut target = HashedPostState::default();
+        for (address, (account, storage)) in state.iter().take(10) {
+            let hashed_address = keccak256(*address);
+            let account = Account { balance: account.balance + U256::from(1), ..*account };
+            target.accounts.insert(hashed_address, Some(account));
+            target.storages.insert(
+                hashed_address,
+                HashedStorage::from_iter(
+                    false,
+                    [(keccak256(storage[0].key), storage[0].value + U256::from(1))],
+                ),
+            );
+        }
+
+        let provider = factory.provider().unwrap();
+        let sequential_result = TrieWitness::new(
+            DatabaseTrieCursorFactory::new(provider.tx_ref()),
+            DatabaseHashedCursorFactory::new(provider.tx_ref()),
+        )
+        .compute(target.clone())
+        .unwrap();
+
+        let rt = Runtime::new().unwrap();
+
+        let task_ctx =
+            ProofTaskCtx::new(Default::default(), Default::default(), Default::default());
+        let proof_task =
+            ProofTaskManager::new(rt.handle().clone(), consistent_view.clone(), task_ctx, 4);
+        let proof_task_handle = proof_task.handle();
+        let join_handle = rt.spawn_blocking(move || proof_task.run());
+
+        let parallel_result = ParallelWitness::new(
+            consistent_view,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            proof_task_handle,
+        )
+        .compute(target)
+        .unwrap();
+
+        assert_eq!(parallel_result, sequential_result);
+
+        // The proof task manager terminates once the last handle is dropped.
+        rt.block_on(join_handle).unwrap().unwrap();
+    }
+}
@@ -1,23 +1,27 @@
 use crate::{
-    blinded::{BlindedProvider, BlindedProviderFactory},
+    blinded::{BlindedProvider, BlindedProviderFactory, DefaultBlindedProvider},
     traits::SparseTrieInterface,
-    SerialSparseTrie, SparseTrie, TrieMasks,
+    LeafLookupError, SerialSparseTrie, SparseTrie, TrieMasks,
 };
 use alloc::{collections::VecDeque, vec::Vec};
 use alloy_primitives::{
-    map::{B256Map, HashMap, HashSet},
-    Bytes, B256,
+    keccak256,
+    map::{B256Map, B256Set, HashMap, HashSet},
+    Address, Bytes, B256, KECCAK256_EMPTY, U256,
 };
-use alloy_rlp::{Decodable, Encodable};
+use alloy_rlp::{encode_fixed_size, Decodable, Encodable};
 use alloy_trie::proof::DecodedProofNodes;
 use core::iter::Peekable;
-use reth_execution_errors::{SparseStateTrieErrorKind, SparseStateTrieResult, SparseTrieErrorKind};
+use reth_execution_errors::{
+    SparseStateTrieErrorKind, SparseStateTrieResult, SparseTrieErrorKind, SparseTrieResult,
+};
 use reth_primitives_traits::Account;
 use reth_trie_common::{
     proof::ProofNodes,
     updates::{StorageTrieUpdates, TrieUpdates},
-    DecodedMultiProof, DecodedStorageMultiProof, MultiProof, Nibbles, RlpNode, StorageMultiProof,
-    TrieAccount, TrieMask, TrieNode, EMPTY_ROOT_HASH, TRIE_ACCOUNT_RLP_MAX_SIZE,
+    AccountProof, BranchNodeCompact, BranchNodeRef, DecodedMultiProof, DecodedStorageMultiProof,
+    HashedPostState, MultiProof, Nibbles, RlpNode, StorageMultiProof, StorageProof, TrieAccount,
+    TrieMask, TrieNode, EMPTY_ROOT_HASH, TRIE_ACCOUNT_RLP_MAX_SIZE,
 };
 use tracing::trace;
 
@@ -853,6 +857,158 @@ where
     }
 }
 
+impl SparseStateTrie {
+    /// Applies the state changes of a block to the revealed tries.
+    ///
+    /// Changed leaves that are revealed, or that would be inserted into revealed nodes, are updated
+    /// directly. The blinded nodes that contain any other changed leaves are replaced with the
+    /// hashes of the branch nodes updated by the block, so they don't need to be revealed.
+    ///
+    /// Returns an error if a changed blinded node can't be derived from the updated branch nodes,
+    /// e.g. because it's a leaf, or if the storage of a revealed account changed but its storage
+    /// trie is blinded. The tries must then be revealed again from a new proof.
+    pub fn apply_block_changes(
+        &mut self,
+        state: &HashedPostState,
+        updates: &TrieUpdates,
+    ) -> SparseStateTrieResult<()> {
+        let Self { state: account_trie, storages, account_rlp_buf, .. } = self;
+        let no_updated_nodes = HashMap::default();
+
+        // Storage tries are updated first, because their roots are part of the account leaves.
+        for (address, storage) in &state.storages {
+            let Some(trie) = storages.get_mut(address).and_then(SparseTrie::as_revealed_mut) else {
+                continue
+            };
+            if storage.wiped {
+                trie.wipe();
+            }
+
+            let updated_nodes = updates
+                .storage_tries
+                .get(address)
+                .map_or(&no_updated_nodes, |updates| &updates.storage_nodes);
+            let revealed =
+                update_blinded_nodes(trie, storage.storage.keys().copied(), updated_nodes)
+                    .map_err(|error| {
+                        SparseStateTrieErrorKind::SparseStorageTrie(*address, error.into_kind())
+                    })?;
+
+            for slot in revealed {
+                let path = Nibbles::unpack(slot);
+                let value = storage.storage[&slot];
+                if value.is_zero() {
+                    trie.remove_leaf(&path, DefaultBlindedProvider)?;
+                } else {
+                    trie.update_leaf(
+                        path,
+                        encode_fixed_size(&value).to_vec(),
+                        DefaultBlindedProvider,
+                    )?;
+                }
+            }
+        }
+
+        let account_trie = account_trie.as_revealed_mut().ok_or(SparseTrieErrorKind::Blind)?;
+        let addresses =
+            state.accounts.keys().chain(state.storages.keys()).copied().collect::<B256Set>();
+        let revealed = update_blinded_nodes(account_trie, addresses, &updates.account_nodes)?;
+
+        for address in revealed {
+            let path = Nibbles::unpack(address);
+            let current = account_trie
+                .get_leaf_value(&path)
+                .map(|value| TrieAccount::decode(&mut &value[..]))
+                .transpose()?;
+
+            let storage_root = if let Some(storage_trie) =
+                storages.get_mut(&address).and_then(SparseTrie::as_revealed_mut)
+            {
+                storage_trie.root()
+            } else if state.storages.contains_key(&address) {
+                return Err(SparseStateTrieErrorKind::SparseStorageTrie(
+                    address,
+                    SparseTrieErrorKind::Blind,
+                )
+                .into())
+            } else {
+                current.map_or(EMPTY_ROOT_HASH, |account| account.storage_root)
+            };
+
+            let account = match state.accounts.get(&address) {
+                Some(&account) => account.unwrap_or_default(),
+                None => current.map(trie_account_info).unwrap_or_default(),
+            };
+
+            if account.is_empty() && storage_root == EMPTY_ROOT_HASH {
+                if current.is_some() {
+                    account_trie.remove_leaf(&path, DefaultBlindedProvider)?;
+                }
+            } else {
+                account_rlp_buf.clear();
+                account.into_trie_account(storage_root).encode(account_rlp_buf);
+                account_trie.update_leaf(path, account_rlp_buf.clone(), DefaultBlindedProvider)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the proof of the account and the given storage slots from the revealed nodes, like
+    /// [`MultiProof::account_proof`].
+    ///
+    /// Returns an error if the account or any of the slots are blinded.
+    pub fn account_proof(
+        &mut self,
+        address: Address,
+        slots: &[B256],
+    ) -> SparseStateTrieResult<AccountProof> {
+        let hashed_address = keccak256(address);
+        let nibbles = Nibbles::unpack(hashed_address);
+
+        let account_trie = self.state.as_revealed_mut().ok_or(SparseTrieErrorKind::Blind)?;
+        account_trie.root();
+        let proof = account_trie.proof_nodes(&nibbles)?.into_iter().map(|(_, node)| node).collect();
+        let account = account_trie
+            .get_leaf_value(&nibbles)
+            .map(|value| TrieAccount::decode(&mut &value[..]))
+            .transpose()?;
+
+        let storage_root = account.map_or(EMPTY_ROOT_HASH, |account| account.storage_root);
+        let mut storage_trie =
+            self.storages.get_mut(&hashed_address).and_then(SparseTrie::as_revealed_mut);
+        if let Some(trie) = &mut storage_trie {
+            trie.root();
+        }
+
+        let mut storage_proofs = Vec::with_capacity(slots.len());
+        for slot in slots {
+            let mut proof = StorageProof::new(*slot);
+            if storage_root != EMPTY_ROOT_HASH {
+                let trie =
+                    storage_trie.as_deref().ok_or(SparseStateTrieErrorKind::SparseStorageTrie(
+                        hashed_address,
+                        SparseTrieErrorKind::Blind,
+                    ))?;
+                proof.proof =
+                    trie.proof_nodes(&proof.nibbles)?.into_iter().map(|(_, node)| node).collect();
+                if let Some(value) = trie.get_leaf_value(&proof.nibbles) {
+                    proof.value = U256::decode(&mut &value[..])?;
+                }
+            }
+            storage_proofs.push(proof);
+        }
+
+        Ok(AccountProof {
+            address,
+            info: account.map(trie_account_info),
+            proof,
+            storage_root,
+            storage_proofs,
+        })
+    }
+}
+
 /// Result of [`filter_revealed_nodes`].
 #[derive(Debug, PartialEq, Eq)]
 struct FilteredProofNodes {
@@ -902,6 +1058,67 @@ fn filter_revealed_nodes(
     Ok(result)
 }
 
+/// Replaces the blinded nodes that contain any of the changed leaves with the hashes of the branch
+/// nodes updated by a block.
+///
+/// Returns the changed leaves that are revealed, or would be inserted into revealed nodes.
+fn update_blinded_nodes(
+    trie: &mut SerialSparseTrie,
+    changed: impl IntoIterator<Item = B256>,
+    updated_nodes: &HashMap<Nibbles, BranchNodeCompact>,
+) -> SparseTrieResult<Vec<B256>> {
+    let mut revealed = Vec::new();
+    let mut updated = HashSet::<Nibbles>::default();
+    for key in changed {
+        match trie.find_leaf(&Nibbles::unpack(key), None) {
+            Err(LeafLookupError::BlindedNode { path, hash }) => {
+                if updated.contains(&path) {
+                    continue
+                }
+                let new_hash = updated_node_hash(&path, updated_nodes)
+                    .ok_or(SparseTrieErrorKind::BlindedNode { path, hash })?;
+                trie.update_blinded_node(path, new_hash)?;
+                updated.insert(path);
+            }
+            _ => revealed.push(key),
+        }
+    }
+    Ok(revealed)
+}
+
+/// Returns the hash of the node at the given path as derived from the branch nodes updated by a
+/// block.
+///
+/// Updated branch nodes carry the hashes of their children that are branch nodes, and a branch node
+/// whose children are all branch nodes can be encoded from these hashes. The hashes of other nodes
+/// are unknown.
+fn updated_node_hash(
+    path: &Nibbles,
+    updated_nodes: &HashMap<Nibbles, BranchNodeCompact>,
+) -> Option<B256> {
+    if let Some(nibble) = path.last() {
+        let parent = path.slice(..path.len() - 1);
+        if let Some(node) =
+            updated_nodes.get(&parent).filter(|node| node.hash_mask.is_bit_set(nibble))
+        {
+            return Some(node.hash_for_nibble(nibble))
+        }
+    }
+
+    let node = updated_nodes.get(path).filter(|node| node.hash_mask == node.state_mask)?;
+    let children = node.hashes.iter().map(RlpNode::word_rlp).collect::<Vec<_>>();
+    BranchNodeRef::new(&children, node.state_mask).rlp(&mut Vec::new()).as_hash()
+}
+
+/// Converts a trie account into the account info, without the storage root.
+fn trie_account_info(account: TrieAccount) -> Account {
+    Account {
+        balance: account.balance,
+        nonce: account.nonce,
+        bytecode_hash: (account.code_hash != KECCAK256_EMPTY).then_some(account.code_hash),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1286,6 +1503,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn account_proof_and_block_changes() {
+        let mut accounts = (1..=3u8)
+            .map(|i| {
+                let address = Address::with_last_byte(i);
+                (keccak256(address), address, Account { nonce: i as u64, ..Default::default() })
+            })
+            .collect::<Vec<_>>();
+        accounts.sort_unstable_by_key(|(hashed_address, ..)| *hashed_address);
+        let (target_hashed, target, _) = accounts[0];
+
+        let multiproof = |accounts: &[(B256, Address, Account)]| {
+            let mut hash_builder = HashBuilder::default()
+                .with_proof_retainer(ProofRetainer::from_iter([Nibbles::unpack(target_hashed)]));
+            for (hashed_address, _, account) in accounts {
+                hash_builder.add_leaf(
+                    Nibbles::unpack(hashed_address),
+                    &alloy_rlp::encode(account.into_trie_account(EMPTY_ROOT_HASH)),
+                );
+            }
+            let root = hash_builder.root();
+            let proof = MultiProof {
+                account_subtree: hash_builder.take_proof_nodes(),
+                ..Default::default()
+            };
+            (root, proof)
+        };
+
+        let (root, proof) = multiproof(&accounts);
+        let mut sparse = SparseStateTrie::<SerialSparseTrie>::default();
+        sparse.reveal_multiproof(proof.clone()).unwrap();
+        assert_eq!(sparse.root(&DefaultBlindedProviderFactory).unwrap(), root);
+        assert_eq!(
+            sparse.account_proof(target, &[]).unwrap(),
+            proof.account_proof(target, &[]).unwrap()
+        );
+
+        // A change of the revealed account is applied to its leaf.
+        accounts[0].2.balance = U256::from(1);
+        let mut state = HashedPostState::default();
+        state.accounts.insert(target_hashed, Some(accounts[0].2));
+        sparse.apply_block_changes(&state, &TrieUpdates::default()).unwrap();
+
+        let (updated_root, proof) = multiproof(&accounts);
+        assert_ne!(updated_root, root);
+        assert_eq!(sparse.root(&DefaultBlindedProviderFactory).unwrap(), updated_root);
+        assert_eq!(
+            sparse.account_proof(target, &[]).unwrap(),
+            proof.account_proof(target, &[]).unwrap()
+        );
+
+        // The hash of a changed blinded leaf can't be derived from the updated branch nodes.
+        let mut state = HashedPostState::default();
+        state.accounts.insert(accounts[1].0, None);
+        assert_matches!(
+            sparse.apply_block_changes(&state, &TrieUpdates::default()).map_err(|e| e.into_kind()),
+            Err(SparseStateTrieErrorKind::Sparse(SparseTrieErrorKind::BlindedNode { .. }))
+        );
+    }
+
     #[test]
     fn test_filter_revealed_nodes() {
         let revealed_nodes = HashSet::from_iter([Nibbles::from_nibbles([0x0])]);
@@ -13,9 +13,9 @@ use alloc::{
 use alloy_primitives::{
     hex, keccak256,
     map::{Entry, HashMap, HashSet},
-    B256,
+    Bytes, B256,
 };
-use alloy_rlp::Decodable;
+use alloy_rlp::{Decodable, Encodable, EMPTY_STRING_CODE};
 use reth_execution_errors::{SparseTrieErrorKind, SparseTrieResult};
 use reth_trie_common::{
     prefix_set::{PrefixSet, PrefixSetMut},
@@ -1085,6 +1085,112 @@ impl SerialSparseTrie {
         &self.nodes
     }
 
+    /// Replaces the hash of the blinded node at the given path, e.g. because the subtrie it stands
+    /// for was changed.
+    ///
+    /// The hashes of the nodes above it are recalculated with the next root calculation.
+    ///
+    /// Returns an error if the node at the path is not blinded.
+    pub fn update_blinded_node(&mut self, path: Nibbles, hash: B256) -> SparseTrieResult<()> {
+        match self.nodes.get_mut(&path) {
+            Some(SparseNode::Hash(blinded)) => {
+                *blinded = hash;
+                self.prefix_set.insert(path);
+                Ok(())
+            }
+            node => Err(SparseTrieErrorKind::Reveal { path, node: Box::new(node.cloned()) }.into()),
+        }
+    }
+
+    /// Returns the RLP encoded nodes on the path from the root to the leaf at the given full path,
+    /// which prove the inclusion or the exclusion of the leaf.
+    ///
+    /// The node hashes must be up to date, see [`SparseTrieInterface::root`]. An empty trie has
+    /// no proof nodes.
+    ///
+    /// Returns an error if the path leads to a blinded node.
+    pub fn proof_nodes(&self, full_path: &Nibbles) -> SparseTrieResult<Vec<(Nibbles, Bytes)>> {
+        let mut proof = Vec::new();
+        let mut current = Nibbles::default();
+        loop {
+            let next = match self.nodes.get(&current).expect("node on the proof path exists") {
+                SparseNode::Empty => break,
+                &SparseNode::Hash(hash) => {
+                    return Err(SparseTrieErrorKind::BlindedNode { path: current, hash }.into())
+                }
+                SparseNode::Leaf { .. } => None,
+                SparseNode::Extension { key, .. } => {
+                    let mut child_path = current;
+                    child_path.extend(key);
+                    full_path.starts_with(&child_path).then_some(child_path)
+                }
+                SparseNode::Branch { state_mask, .. } => (current.len() < full_path.len())
+                    .then(|| full_path.get_unchecked(current.len()))
+                    .filter(|nibble| state_mask.is_bit_set(*nibble))
+                    .map(|nibble| {
+                        let mut child_path = current;
+                        child_path.push_unchecked(nibble);
+                        child_path
+                    }),
+            };
+
+            proof.push((current, Bytes::from(self.encode_node(&current)?)));
+
+            match next {
+                Some(child_path) => current = child_path,
+                None => break,
+            }
+        }
+        Ok(proof)
+    }
+
+    /// Returns the RLP encoding of the revealed node at the given path.
+    fn encode_node(&self, path: &Nibbles) -> SparseTrieResult<Vec<u8>> {
+        let mut rlp_buf = Vec::new();
+        match self.nodes.get(path).expect("node exists") {
+            SparseNode::Empty => rlp_buf.push(EMPTY_STRING_CODE),
+            &SparseNode::Hash(hash) => {
+                return Err(SparseTrieErrorKind::BlindedNode { path: *path, hash }.into())
+            }
+            SparseNode::Leaf { key, .. } => {
+                let mut full_path = *path;
+                full_path.extend(key);
+                let value = self.values.get(&full_path).expect("leaf value exists");
+                LeafNodeRef { key, value }.encode(&mut rlp_buf);
+            }
+            SparseNode::Extension { key, .. } => {
+                let mut child_path = *path;
+                child_path.extend(key);
+                let child = self.child_rlp_node(&child_path)?;
+                ExtensionNodeRef::new(key, &child).encode(&mut rlp_buf);
+            }
+            SparseNode::Branch { state_mask, .. } => {
+                let children = CHILD_INDEX_RANGE
+                    .filter(|nibble| state_mask.is_bit_set(*nibble))
+                    .map(|nibble| {
+                        let mut child_path = *path;
+                        child_path.push_unchecked(nibble);
+                        self.child_rlp_node(&child_path)
+                    })
+                    .collect::<SparseTrieResult<Vec<_>>>()?;
+                BranchNodeRef::new(&children, *state_mask).encode(&mut rlp_buf);
+            }
+        }
+        Ok(rlp_buf)
+    }
+
+    /// Returns the reference to the node at the given path as stored in its parent node, which is
+    /// either the hash of the node or the node itself if its encoding is shorter than 32 bytes.
+    fn child_rlp_node(&self, path: &Nibbles) -> SparseTrieResult<RlpNode> {
+        match self.nodes.get(path).expect("node exists") {
+            SparseNode::Hash(hash) |
+            SparseNode::Leaf { hash: Some(hash), .. } |
+            SparseNode::Extension { hash: Some(hash), .. } |
+            SparseNode::Branch { hash: Some(hash), .. } => Ok(RlpNode::word_rlp(hash)),
+            _ => Ok(RlpNode::from_rlp(&self.encode_node(path)?)),
+        }
+    }
+
     /// Reveals either a node or its hash placeholder based on the provided child data.
     ///
     /// When traversing the trie, we often encounter references to child nodes that
@@ -19,7 +19,7 @@ use reth_execution_errors::{
     SparseStateTrieErrorKind, SparseTrieError, SparseTrieErrorKind, StateProofError,
     TrieWitnessError,
 };
-use reth_trie_common::{MultiProofTargets, Nibbles};
+use reth_trie_common::{DecodedMultiProof, MultiProofTargets, Nibbles};
 use reth_trie_sparse::{
     blinded::{BlindedProvider, BlindedProviderFactory, RevealedNode},
     SerialSparseTrie, SparseStateTrie,
@@ -104,7 +104,40 @@ where
     /// # Arguments
     ///
     /// `state` - state transition containing both modified and touched accounts and storage slots.
-    pub fn compute(mut self, state: HashedPostState) -> Result<B256Map<Bytes>, TrieWitnessError> {
+    pub fn compute(self, state: HashedPostState) -> Result<B256Map<Bytes>, TrieWitnessError> {
+        let proof =
+            Proof::new(self.trie_cursor_factory.clone(), self.hashed_cursor_factory.clone())
+                .with_prefix_sets_mut(self.prefix_sets.clone());
+        let blinded_provider_factory = ProofBlindedProviderFactory::new(
+            self.trie_cursor_factory.clone(),
+            self.hashed_cursor_factory.clone(),
+            Arc::new(self.prefix_sets.clone()),
+        );
+        self.compute_with_multiproof(
+            state,
+            |targets| Ok(proof.multiproof(targets)?.try_into()?),
+            blinded_provider_factory,
+        )
+    }
+
+    /// Compute the state transition witness for the trie with a custom proof source.
+    ///
+    /// The initial multiproof for the witness targets is obtained from `multiproof_fn`, and any
+    /// additional nodes that are required to apply `state` are fetched from
+    /// `blinded_provider_factory`. This allows callers to collect the witness with an alternative
+    /// proof implementation, e.g. the parallel one.
+    pub fn compute_with_multiproof<MF, F>(
+        mut self,
+        state: HashedPostState,
+        multiproof_fn: MF,
+        blinded_provider_factory: F,
+    ) -> Result<B256Map<Bytes>, TrieWitnessError>
+    where
+        MF: FnOnce(MultiProofTargets) -> Result<DecodedMultiProof, TrieWitnessError>,
+        F: BlindedProviderFactory,
+        F::AccountNodeProvider: BlindedProvider,
+        F::StorageNodeProvider: BlindedProvider,
+    {
         let is_state_empty = state.is_empty();
         if is_state_empty && !self.always_include_root_node {
             return Ok(Default::default())
@@ -115,10 +148,7 @@ where
         } else {
             self.get_proof_targets(&state)?
         };
-        let multiproof =
-            Proof::new(self.trie_cursor_factory.clone(), self.hashed_cursor_factory.clone())
-                .with_prefix_sets_mut(self.prefix_sets.clone())
-                .multiproof(proof_targets.clone())?;
+        let multiproof = multiproof_fn(proof_targets.clone())?;
 
         // No need to reconstruct the rest of the trie, we just need to include
         // the root node and return.
@@ -126,6 +156,7 @@ where
             let (root_hash, root_node) = if let Some(root_node) =
                 multiproof.account_subtree.into_inner().remove(&Nibbles::default())
             {
+                let root_node = Bytes::from(alloy_rlp::encode(&root_node));
                 (keccak256(&root_node), root_node)
             } else {
                 (EMPTY_ROOT_HASH, Bytes::from([EMPTY_STRING_CODE]))
@@ -135,27 +166,23 @@ where
 
         // Record all nodes from multiproof in the witness
         for account_node in multiproof.account_subtree.values() {
-            if let Entry::Vacant(entry) = self.witness.entry(keccak256(account_node.as_ref())) {
-                entry.insert(account_node.clone());
+            let account_node = Bytes::from(alloy_rlp::encode(account_node));
+            if let Entry::Vacant(entry) = self.witness.entry(keccak256(&account_node)) {
+                entry.insert(account_node);
             }
         }
         for storage_node in multiproof.storages.values().flat_map(|s| s.subtree.values()) {
-            if let Entry::Vacant(entry) = self.witness.entry(keccak256(storage_node.as_ref())) {
-                entry.insert(storage_node.clone());
+            let storage_node = Bytes::from(alloy_rlp::encode(storage_node));
+            if let Entry::Vacant(entry) = self.witness.entry(keccak256(&storage_node)) {
+                entry.insert(storage_node);
             }
         }
 
         let (tx, rx) = mpsc::channel();
-        let blinded_provider_factory = WitnessBlindedProviderFactory::new(
-            ProofBlindedProviderFactory::new(
-                self.trie_cursor_factory,
-                self.hashed_cursor_factory,
-                Arc::new(self.prefix_sets),
-            ),
-            tx,
-        );
+        let blinded_provider_factory =
+            WitnessBlindedProviderFactory::new(blinded_provider_factory, tx);
         let mut sparse_trie = SparseStateTrie::<SerialSparseTrie>::new();
-        sparse_trie.reveal_multiproof(multiproof)?;
+        sparse_trie.reveal_decoded_multiproof(multiproof)?;
 
         // Attempt to update state trie to gather additional information for the witness.
         for (hashed_address, hashed_slots) in
@@ -280,6 +280,10 @@ export const sidebar: SidebarItem[] = [
             {
                 text: "rpc",
                 link: "/jsonrpc/rpc"
+            },
+            {
+                text: "mev",
+                link: "/jsonrpc/mev"
             }
         ]
     },
@@ -317,6 +321,21 @@ export const sidebar: SidebarItem[] = [
                         text: "reth export-era",
                         link: "/cli/reth/export-era"
                     },
+                    {
+                        text: "reth export",
+                        link: "/cli/reth/export",
+                        collapsed: true,
+                        items: [
+                            {
+                                text: "reth export state",
+                                link: "/cli/reth/export/state"
+                            }
+                        ]
+                    },
+                    {
+                        text: "reth verify-manifest",
+                        link: "/cli/reth/verify-manifest"
+                    },
                     {
                         text: "reth dump-genesis",
                         link: "/cli/reth/dump-genesis"
@@ -380,6 +399,10 @@ export const sidebar: SidebarItem[] = [
                                 text: "reth db version",
                                 link: "/cli/reth/db/version"
                             },
+                            {
+                                text: "reth db migrate",
+                                link: "/cli/reth/db/migrate"
+                            },
                             {
                                 text: "reth db path",
                                 link: "/cli/reth/db/path"
@@ -471,7 +494,18 @@ export const sidebar: SidebarItem[] = [
                     },
                     {
                         text: "reth config",
-                        link: "/cli/reth/config"
+                        link: "/cli/reth/config",
+                        collapsed: true,
+                        items: [
+                            {
+                                text: "reth config validate",
+                                link: "/cli/reth/config/validate"
+                            },
+                            {
+                                text: "reth config show",
+                                link: "/cli/reth/config/show"
+                            }
+                        ]
                     },
                     {
                         text: "reth debug",
@@ -504,12 +538,20 @@ export const sidebar: SidebarItem[] = [
                             {
                                 text: "reth recover storage-tries",
                                 link: "/cli/reth/recover/storage-tries"
+                            },
+                            {
+                                text: "reth recover restore",
+                                link: "/cli/reth/recover/restore"
                             }
                         ]
                     },
                     {
                         text: "reth prune",
                         link: "/cli/reth/prune"
+                    },
+                    {
+                        text: "reth verify-chain",
+                        link: "/cli/reth/verify-chain"
                     }
                 ]
             }
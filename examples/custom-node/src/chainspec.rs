@@ -88,6 +88,10 @@ impl EthChainSpec for CustomChainSpec {
         self.inner.prune_delete_limit()
     }
 
+    fn max_extra_data_size(&self) -> usize {
+        self.inner.max_extra_data_size()
+    }
+
     fn genesis(&self) -> &Genesis {
         self.inner.genesis()
     }